@@ -0,0 +1,101 @@
+//! Embeds build metadata for `build_info` (see src/build_info.rs)
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=JOBSUCHE_GIT_COMMIT");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    let git_commit = env::var("JOBSUCHE_GIT_COMMIT")
+        .ok()
+        .filter(|commit| !commit.trim().is_empty())
+        .or_else(|| git_commit(&manifest_dir))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=JOBSUCHE_BUILD_GIT_COMMIT={}", git_commit);
+
+    println!(
+        "cargo:rustc-env=JOBSUCHE_BUILD_TIMESTAMP={}",
+        build_timestamp()
+    );
+
+    let lock_file = manifest_dir.join("..").join("Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock_file.display());
+    let jobsuche_version = fs::read_to_string(&lock_file)
+        .ok()
+        .and_then(|lock| locked_version(&lock, "jobsuche"))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!(
+        "cargo:rustc-env=JOBSUCHE_BUILD_JOBSUCHE_VERSION={}",
+        jobsuche_version
+    );
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(name, _)| {
+            name.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+    println!(
+        "cargo:rustc-env=JOBSUCHE_BUILD_FEATURES={}",
+        features.join(",")
+    );
+}
+
+/// Short commit hash of the checkout, with `-dirty` if it has local changes
+fn git_commit(manifest_dir: &Path) -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(manifest_dir)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    // Rebuild when HEAD moves to another commit
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        println!("cargo:rerun-if-changed={}/HEAD", git_dir);
+        println!("cargo:rerun-if-changed={}/index", git_dir);
+    }
+
+    let commit = git(&["rev-parse", "--short=12", "HEAD"]).filter(|commit| !commit.is_empty())?;
+    let dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.is_empty());
+    Some(if dirty {
+        format!("{}-dirty", commit)
+    } else {
+        commit
+    })
+}
+
+/// Build time, or SOURCE_DATE_EPOCH for reproducible builds
+fn build_timestamp() -> String {
+    let time = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse().ok())
+        .and_then(|epoch| OffsetDateTime::from_unix_timestamp(epoch).ok())
+        .unwrap_or_else(OffsetDateTime::now_utc);
+    time.format(&Rfc3339).unwrap_or_default()
+}
+
+/// Version of a package in a Cargo.lock file
+fn locked_version(lock: &str, package: &str) -> Option<String> {
+    let name = format!("name = \"{}\"", package);
+    let mut lines = lock.lines().map(str::trim);
+    lines.find(|line| *line == name)?;
+    lines
+        .next()?
+        .strip_prefix("version = \"")?
+        .strip_suffix('"')
+        .map(str::to_string)
+}
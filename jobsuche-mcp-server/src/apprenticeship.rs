@@ -0,0 +1,291 @@
+//! Apprenticeship search
+//!
+//! Apprenticeships (Ausbildungsplätze) are a separate offer type in the API
+//! (`angebotsart`). School leavers care about other things than job seekers:
+//! when the apprenticeship starts, which school-leaving certificate is asked
+//! for and how long the training takes. The start comes with the search
+//! results; certificate and duration are read from the descriptions of the
+//! top results.
+
+use crate::JobSummary;
+use jobsuche::{Angebotsart, SearchOptions};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Default number of apprenticeships whose details are fetched
+pub const DEFAULT_APPRENTICESHIP_DETAILS: u64 = 3;
+
+/// Maximum number of apprenticeships whose details are fetched
+pub const MAX_APPRENTICESHIP_DETAILS: u64 = 10;
+
+/// Parameters for search_apprenticeships
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SearchApprenticeshipsParams {
+    /// Occupation or keyword (e.g. "Mechatroniker", "Kaufmann Büromanagement")
+    pub job_title: Option<String>,
+
+    /// City or postal code
+    pub location: Option<String>,
+
+    /// Search radius in km around the location
+    pub radius_km: Option<u64>,
+
+    /// Only apprenticeships starting in this year (e.g. 2026); postings without
+    /// a start date are kept
+    pub start_year: Option<i32>,
+
+    /// Results per page (default: JOBSUCHE_DEFAULT_PAGE_SIZE)
+    pub page_size: Option<u64>,
+
+    /// Page number, starting at 1
+    pub page: Option<u64>,
+
+    /// Fetch details of the top N results to read school requirements and
+    /// duration (default: 3, max: 10; 0 skips it)
+    pub max_details: Option<u64>,
+}
+
+/// School-leaving certificate, lowest first
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SchoolCertificate {
+    /// Hauptschulabschluss (Berufsreife, erster Schulabschluss)
+    Hauptschulabschluss,
+    /// Mittlerer Schulabschluss (Realschulabschluss, Mittlere Reife)
+    MittlererSchulabschluss,
+    /// Fachhochschulreife (Fachabitur)
+    Fachhochschulreife,
+    /// Allgemeine Hochschulreife (Abitur)
+    Abitur,
+}
+
+/// Phrases naming each certificate, lowercase
+const CERTIFICATE_PHRASES: &[(SchoolCertificate, &[&str])] = &[
+    (
+        SchoolCertificate::Hauptschulabschluss,
+        &[
+            "hauptschulabschluss",
+            "hauptschule",
+            "berufsreife",
+            "erster schulabschluss",
+            "ersten schulabschluss",
+            "erster allgemeinbildender schulabschluss",
+            "ersten allgemeinbildenden schulabschluss",
+        ],
+    ),
+    (
+        SchoolCertificate::MittlererSchulabschluss,
+        &[
+            "mittlerer schulabschluss",
+            "mittleren schulabschluss",
+            "mittlere reife",
+            "realschulabschluss",
+            "fachoberschulreife",
+            "sekundarabschluss i",
+        ],
+    ),
+    (
+        SchoolCertificate::Fachhochschulreife,
+        &[
+            "fachhochschulreife",
+            "fachabitur",
+            "fachgebundene hochschulreife",
+        ],
+    ),
+    (
+        SchoolCertificate::Abitur,
+        &[
+            "abitur",
+            "allgemeine hochschulreife",
+            "allgemeinen hochschulreife",
+        ],
+    ),
+];
+
+/// Spelled-out durations before "jährig", longest first
+const DURATION_WORDS: &[(&str, f64)] = &[
+    ("dreieinhalb", 3.5),
+    ("zweieinhalb", 2.5),
+    ("zwei", 2.0),
+    ("drei", 3.0),
+    ("vier", 4.0),
+];
+
+/// An apprenticeship posting
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Apprenticeship {
+    /// Posting, as returned by search_jobs
+    #[serde(flatten)]
+    pub job: JobSummary,
+
+    /// Start of the apprenticeship (ISO 8601, YYYY-MM-DD)
+    pub start_date: Option<String>,
+
+    /// Year the apprenticeship starts
+    pub start_year: Option<i32>,
+
+    /// School-leaving certificates the description mentions, lowest first
+    /// (only for the top results whose details were fetched)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub school_requirements: Option<Vec<SchoolCertificate>>,
+
+    /// Duration of the apprenticeship in years, if the description states it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_years: Option<f64>,
+}
+
+/// Result from search_apprenticeships
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchApprenticeshipsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Total number of apprenticeships found
+    pub total_results: Option<u64>,
+
+    /// Current page number
+    pub current_page: Option<u64>,
+
+    /// Page size used
+    pub page_size: Option<u64>,
+
+    /// Number of apprenticeships returned
+    pub jobs_count: usize,
+
+    /// Apprenticeships of this page
+    pub jobs: Vec<Apprenticeship>,
+
+    /// Top results whose details could not be fetched
+    pub missing_details: Vec<String>,
+}
+
+/// Search options for apprenticeships
+pub fn apprenticeship_search_options(
+    params: &SearchApprenticeshipsParams,
+    page_size: u64,
+) -> SearchOptions {
+    let mut options = SearchOptions::builder();
+    options.angebotsart(Angebotsart::Ausbildung).size(page_size);
+    if let Some(ref job_title) = params.job_title {
+        options.was(job_title);
+    }
+    if let Some(ref location) = params.location {
+        options.wo(location);
+    }
+    if let Some(radius_km) = params.radius_km {
+        options.umkreis(radius_km);
+    }
+    if let Some(page) = params.page {
+        options.page(page);
+    }
+    options.build()
+}
+
+/// Year of an ISO 8601 date
+pub fn year_of(date: &str) -> Option<i32> {
+    date.get(..4)?.parse().ok()
+}
+
+/// School-leaving certificates mentioned in a description, lowest first
+pub fn school_requirements(description: &str) -> Vec<SchoolCertificate> {
+    let text = description.to_lowercase();
+    // "Fachabitur" and "fachgebundene Hochschulreife" must not count as Abitur
+    let without_fach = [
+        "fachabitur",
+        "fachhochschulreife",
+        "fachgebundene hochschulreife",
+    ]
+    .iter()
+    .fold(text.clone(), |text, phrase| text.replace(phrase, ""));
+    CERTIFICATE_PHRASES
+        .iter()
+        .filter(|(certificate, phrases)| {
+            let text = if *certificate == SchoolCertificate::Abitur {
+                &without_fach
+            } else {
+                &text
+            };
+            phrases.iter().any(|phrase| text.contains(phrase))
+        })
+        .map(|(certificate, _)| *certificate)
+        .collect()
+}
+
+/// Duration in years from phrases like "dreijährige" or "3,5-jährige Ausbildung"
+pub fn duration_years(description: &str) -> Option<f64> {
+    let text = description.to_lowercase();
+    text.match_indices("jährig").find_map(|(idx, _)| {
+        let before = text[..idx].trim_end_matches(['-', ' ']);
+        if let Some((_, years)) = DURATION_WORDS
+            .iter()
+            .find(|(word, _)| before.ends_with(word))
+        {
+            return Some(*years);
+        }
+        let number: String = before
+            .chars()
+            .rev()
+            .take_while(|c| c.is_ascii_digit() || *c == ',' || *c == '.')
+            .collect::<String>()
+            .chars()
+            .rev()
+            .collect();
+        number
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok()
+            .filter(|years| (1.0..=4.0).contains(years))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_year_of() {
+        assert_eq!(year_of("2026-08-01"), Some(2026));
+        assert_eq!(year_of("ab"), None);
+    }
+
+    #[test]
+    fn test_school_requirements() {
+        assert_eq!(
+            school_requirements(
+                "Sie haben mindestens einen guten Realschulabschluss oder das Abitur."
+            ),
+            vec![
+                SchoolCertificate::MittlererSchulabschluss,
+                SchoolCertificate::Abitur
+            ]
+        );
+        assert_eq!(
+            school_requirements("Voraussetzung: Fachabitur"),
+            vec![SchoolCertificate::Fachhochschulreife]
+        );
+        assert_eq!(
+            school_requirements("Erster Schulabschluss oder Mittlere Reife"),
+            vec![
+                SchoolCertificate::Hauptschulabschluss,
+                SchoolCertificate::MittlererSchulabschluss
+            ]
+        );
+        assert!(school_requirements("Freude am Umgang mit Menschen").is_empty());
+    }
+
+    #[test]
+    fn test_duration_years() {
+        assert_eq!(
+            duration_years("Die dreijährige Ausbildung beginnt"),
+            Some(3.0)
+        );
+        assert_eq!(duration_years("eine 3,5-jährige Ausbildung"), Some(3.5));
+        assert_eq!(duration_years("Dreieinhalbjährige Ausbildung"), Some(3.5));
+        assert_eq!(duration_years("Ausbildung mit 25-jähriger Tradition"), None);
+        assert_eq!(duration_years("Ausbildung zum Koch"), None);
+    }
+}
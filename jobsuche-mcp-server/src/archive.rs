@@ -0,0 +1,86 @@
+//! Job archive
+//!
+//! Postings disappear from the API once they are filled, taking the
+//! description a user prepared an application against with them. When
+//! enabled (by default only with the SQLite backend), every successfully
+//! fetched job is stored as a snapshot without its raw API data, and
+//! get_archived_job serves the last known version if the live API no longer
+//! returns the posting.
+
+use crate::detail_level::DetailLevel;
+use crate::GetJobDetailsResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Stored snapshot of a job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedJob {
+    /// When the snapshot was taken (RFC 3339)
+    pub archived_at: String,
+
+    /// Job details at that time (without user notes and raw API data)
+    pub details: GetJobDetailsResult,
+}
+
+/// Where the returned job details come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JobSource {
+    /// Fetched from the API just now
+    Live,
+    /// Last archived snapshot
+    Archive,
+}
+
+/// Parameters for get_archived_job
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetArchivedJobParams {
+    /// Job reference number
+    pub reference_number: String,
+
+    /// How much of the job is returned: "summary", "standard" or "full"
+    /// (default; see get_job_details)
+    pub detail_level: Option<DetailLevel>,
+
+    /// Include the raw API data as `raw_data` (see get_job_details); only
+    /// for live details, snapshots do not keep it
+    pub include_raw: Option<bool>,
+}
+
+/// Result from get_archived_job
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetArchivedJobResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Whether the details are live or from the archive
+    pub source: JobSource,
+
+    /// When the returned snapshot was taken (RFC 3339), for archived details
+    pub archived_at: Option<String>,
+
+    /// Why the live API could not be used, for archived details
+    pub live_error: Option<String>,
+
+    /// Job details
+    pub details: GetJobDetailsResult,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_serialization() {
+        assert_eq!(
+            serde_json::to_value(JobSource::Archive).unwrap(),
+            serde_json::json!("archive")
+        );
+        assert_eq!(
+            serde_json::to_value(JobSource::Live).unwrap(),
+            serde_json::json!("live")
+        );
+    }
+}
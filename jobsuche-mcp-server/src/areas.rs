@@ -0,0 +1,231 @@
+//! Searches across several areas
+//!
+//! Dual-career households look for jobs around more than one place, e.g. the
+//! home postal code and the partner's workplace. The API takes a single
+//! location per query, so the search runs once per area and the result sets
+//! are combined: the union lists jobs in any area, the intersection only jobs
+//! within reach of every area. Intersections are computed over the postings
+//! collected per area (`max_jobs_per_area`), so very broad searches can miss
+//! matches beyond that limit.
+
+use crate::{JobSummary, SearchJobsParams};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of areas per search
+pub const MAX_SEARCH_AREAS: usize = 5;
+
+/// Default number of postings collected per area
+pub const DEFAULT_AREA_JOBS: u64 = 100;
+
+/// Maximum number of postings collected per area
+pub const MAX_AREA_JOBS: u64 = 500;
+
+/// One location with its own radius
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SearchArea {
+    /// Postal code or town
+    #[schemars(length(min = 1), example = &"42103")]
+    pub location: String,
+
+    /// Search radius in km around the location (0-200)
+    #[schemars(range(max = 200), example = 25)]
+    pub radius_km: Option<u64>,
+}
+
+/// How the result sets of the areas are combined
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AreaCombination {
+    /// Jobs in any of the areas
+    #[default]
+    Union,
+    /// Only jobs in all of the areas
+    Intersection,
+}
+
+/// Parameters for search_jobs_in_areas
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SearchJobsInAreasParams {
+    /// Search to run in every area (same parameters as search_jobs; location,
+    /// radius_km, nationwide, page_size and page are ignored)
+    pub search: SearchJobsParams,
+
+    /// Areas to search (1-5), each a postal code or town with its own radius
+    pub areas: Vec<SearchArea>,
+
+    /// "union" (default) for jobs in any area, "intersection" for jobs within
+    /// reach of all areas
+    #[serde(default)]
+    pub combine: AreaCombination,
+
+    /// Maximum number of postings collected per area (default: 100, max: 500)
+    pub max_jobs_per_area: Option<u64>,
+}
+
+/// Distance of a job from one of the areas it was found in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AreaDistance {
+    /// Location of the area
+    pub location: String,
+
+    /// Distance in km from the area's location, as reported by the API
+    pub distance_km: Option<f64>,
+}
+
+/// A job with the areas it was found in
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AreaJob {
+    /// Posting, as returned by search_jobs
+    #[serde(flatten)]
+    pub job: JobSummary,
+
+    /// Areas the job was found in, with the distance from each
+    pub areas: Vec<AreaDistance>,
+}
+
+/// Outcome of the search in one area
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AreaSummary {
+    /// Location of the area
+    pub location: String,
+
+    /// Search radius in km
+    pub radius_km: Option<u64>,
+
+    /// Total number of results in the area
+    pub total_results: Option<u64>,
+
+    /// Number of postings collected in the area
+    pub collected_jobs: usize,
+}
+
+/// Result from search_jobs_in_areas
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchJobsInAreasResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// How the areas were combined
+    pub combine: AreaCombination,
+
+    /// Search outcome per area
+    pub areas: Vec<AreaSummary>,
+
+    /// Number of jobs in this response
+    pub jobs_count: usize,
+
+    /// Combined jobs, nearest first; `distance_km` is the distance from the
+    /// closest area for a union and from the farthest area for an intersection
+    pub jobs: Vec<AreaJob>,
+}
+
+/// Combine the jobs found per area
+///
+/// `found` holds the location of each area with its jobs, in area order.
+pub fn combine_areas(
+    found: Vec<(String, Vec<JobSummary>)>,
+    combine: AreaCombination,
+) -> Vec<AreaJob> {
+    let area_count = found.len();
+    let mut jobs: Vec<AreaJob> = Vec::new();
+    for (location, area_jobs) in found {
+        for job in area_jobs {
+            let distance = AreaDistance {
+                location: location.clone(),
+                distance_km: job.distance_km,
+            };
+            match jobs
+                .iter_mut()
+                .find(|existing| existing.job.reference_number == job.reference_number)
+            {
+                Some(existing) => existing.areas.push(distance),
+                None => jobs.push(AreaJob {
+                    job,
+                    areas: vec![distance],
+                }),
+            }
+        }
+    }
+
+    if combine == AreaCombination::Intersection {
+        jobs.retain(|job| job.areas.len() == area_count);
+    }
+    for job in &mut jobs {
+        job.job.distance_km = ranking_distance(&job.areas, combine);
+    }
+    // Stable, so jobs at equal distance keep the API's order
+    jobs.sort_by(|a, b| match (a.job.distance_km, b.job.distance_km) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    jobs
+}
+
+/// Closest area distance for a union, farthest for an intersection
+fn ranking_distance(areas: &[AreaDistance], combine: AreaCombination) -> Option<f64> {
+    let distances = areas.iter().filter_map(|area| area.distance_km);
+    match combine {
+        AreaCombination::Union => distances.reduce(f64::min),
+        AreaCombination::Intersection => distances.reduce(f64::max),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(reference_number: &str, distance_km: Option<f64>) -> JobSummary {
+        serde_json::from_value(serde_json::json!({
+            "reference_number": reference_number,
+            "title": "Job",
+            "employer": "BARMER",
+            "location": "Wuppertal",
+            "distance_km": distance_km,
+            "published_date": null,
+            "external_url": null
+        }))
+        .unwrap()
+    }
+
+    fn found() -> Vec<(String, Vec<JobSummary>)> {
+        vec![
+            (
+                "42103".to_string(),
+                vec![job("A", Some(5.0)), job("B", Some(20.0)), job("C", None)],
+            ),
+            (
+                "40210".to_string(),
+                vec![job("B", Some(10.0)), job("D", Some(2.0))],
+            ),
+        ]
+    }
+
+    fn references(jobs: &[AreaJob]) -> Vec<&str> {
+        jobs.iter()
+            .map(|job| job.job.reference_number.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_union() {
+        let jobs = combine_areas(found(), AreaCombination::Union);
+        assert_eq!(references(&jobs), ["D", "A", "B", "C"]);
+        let shared = &jobs[2];
+        assert_eq!(shared.job.distance_km, Some(10.0));
+        assert_eq!(shared.areas.len(), 2);
+        assert_eq!(shared.areas[1].location, "40210");
+    }
+
+    #[test]
+    fn test_intersection() {
+        let jobs = combine_areas(found(), AreaCombination::Intersection);
+        assert_eq!(references(&jobs), ["B"]);
+        assert_eq!(jobs[0].job.distance_km, Some(20.0));
+    }
+}
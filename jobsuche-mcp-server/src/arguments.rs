@@ -0,0 +1,216 @@
+//! Argument pre-validation
+//!
+//! Tool parameters reject unknown fields, and the serde error for a typo
+//! ("unknown field `locaton`, expected one of ...") gives no hint which field
+//! was meant. Transport integrations can check the arguments of a tool call
+//! with [`check_arguments`] before dispatching it: every unknown field is
+//! reported with the closest known name and the list of valid fields, including
+//! fields of nested objects such as the `search` of summarize_market, so a
+//! model can correct its call.
+
+use crate::tools::input_schema_for;
+use anyhow::Result;
+use serde_json::Value;
+
+/// A field of the arguments that the tool does not know
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownField {
+    /// Path of the field (e.g. "locaton", "search.locaton", "searches[1].nme")
+    pub path: String,
+
+    /// Known field with the most similar name, if one is close enough
+    pub suggestion: Option<String>,
+
+    /// Known fields of the object holding the unknown field
+    pub valid_fields: Vec<String>,
+}
+
+impl std::fmt::Display for UnknownField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown field '{}'", self.path)?;
+        if let Some(ref suggestion) = self.suggestion {
+            write!(f, ", did you mean '{}'?", suggestion)?;
+        }
+        write!(f, " Valid fields: {}", self.valid_fields.join(", "))
+    }
+}
+
+/// Number of single-character edits turning `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Known name closest to `name`, if it differs in at most a third of the
+/// characters (at least two)
+fn closest<'a>(name: &str, known: &'a [String]) -> Option<&'a str> {
+    let name = name.to_lowercase();
+    let max_distance = (name.chars().count() / 3).max(2);
+    known
+        .iter()
+        .map(|candidate| (edit_distance(&name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+/// Follow `$ref`s and unwrap optional values (`anyOf` with `null`)
+fn resolve<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return match reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+        {
+            Some(target) => resolve(root, target),
+            None => schema,
+        };
+    }
+    let variants = schema
+        .get("anyOf")
+        .or_else(|| schema.get("oneOf"))
+        .and_then(Value::as_array);
+    if let Some(variants) = variants {
+        let mut non_null = variants
+            .iter()
+            .filter(|variant| variant.get("type").and_then(Value::as_str) != Some("null"));
+        if let (Some(variant), None) = (non_null.next(), non_null.next()) {
+            return resolve(root, variant);
+        }
+    }
+    schema
+}
+
+/// Collect the unknown fields of `value` at `path`
+fn collect(root: &Value, schema: &Value, value: &Value, path: &str, found: &mut Vec<UnknownField>) {
+    let schema = resolve(root, schema);
+    match value {
+        Value::Object(fields) => {
+            let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+                return;
+            };
+            let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+            let known: Vec<String> = properties.keys().cloned().collect();
+            for (name, field) in fields {
+                let field_path = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", path, name)
+                };
+                match properties.get(name) {
+                    Some(field_schema) => collect(root, field_schema, field, &field_path, found),
+                    None if closed => found.push(UnknownField {
+                        path: field_path,
+                        suggestion: closest(name, &known).map(str::to_string),
+                        valid_fields: known.clone(),
+                    }),
+                    None => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (idx, item) in items.iter().enumerate() {
+                    collect(
+                        root,
+                        item_schema,
+                        item,
+                        &format!("{}[{}]", path, idx),
+                        found,
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Unknown fields of `arguments` according to a parameter schema
+pub fn unknown_fields(schema: &Value, arguments: &Value) -> Vec<UnknownField> {
+    let mut found = Vec::new();
+    collect(schema, schema, arguments, "", &mut found);
+    found
+}
+
+/// Check the arguments of a tool call for unknown fields
+///
+/// Fails with one line per unknown field, e.g. "unknown field 'locaton', did
+/// you mean 'location'? Valid fields: job_title, location, ...". Other
+/// problems (missing or mistyped values) are left to deserialization.
+pub fn check_arguments(tool: &str, arguments: &Value) -> Result<()> {
+    let Some(schema) = input_schema_for(tool) else {
+        anyhow::bail!("Unknown tool '{}'", tool);
+    };
+    let unknown = unknown_fields(&schema.to_value(), arguments);
+    if !unknown.is_empty() {
+        let lines: Vec<String> = unknown.iter().map(UnknownField::to_string).collect();
+        anyhow::bail!("Invalid arguments for {}:\n{}", tool, lines.join("\n"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("locaton", "location"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("radius", "radius"), 0);
+    }
+
+    #[test]
+    fn test_check_arguments_suggests_field() {
+        let error = check_arguments("search_jobs", &json!({"locaton": "Berlin"}))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("unknown field 'locaton', did you mean 'location'?"),
+            "{}",
+            error
+        );
+        assert!(error.contains("Valid fields: "), "{}", error);
+        assert!(check_arguments("search_jobs", &json!({"location": "Berlin"})).is_ok());
+        assert!(check_arguments("no_such_tool", &json!({})).is_err());
+    }
+
+    #[test]
+    fn test_nested_unknown_fields() {
+        let schema = input_schema_for("summarize_market").unwrap().to_value();
+        let unknown = unknown_fields(
+            &schema,
+            &json!({"search": {"job_titel": "Koch", "fields": {"include": ["title"]}}}),
+        );
+        let mut paths: Vec<&str> = unknown.iter().map(|field| field.path.as_str()).collect();
+        paths.sort_unstable();
+        assert_eq!(paths, vec!["search.fields.include", "search.job_titel"]);
+        let typo = unknown
+            .iter()
+            .find(|field| field.path == "search.job_titel")
+            .unwrap();
+        assert_eq!(typo.suggestion.as_deref(), Some("job_title"));
+
+        let schema = input_schema_for("batch_search_jobs").unwrap().to_value();
+        let unknown = unknown_fields(&schema, &json!({"searches": [{"name": "A"}, {"nme": "B"}]}));
+        assert_eq!(unknown[0].path, "searches[1].nme");
+        assert_eq!(unknown[0].suggestion.as_deref(), Some("name"));
+    }
+
+    #[test]
+    fn test_no_suggestion_for_unrelated_name() {
+        let schema = input_schema_for("search_jobs").unwrap().to_value();
+        let unknown = unknown_fields(&schema, &json!({"salary_minimum": 50000}));
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].suggestion, None);
+    }
+}
@@ -0,0 +1,529 @@
+//! Tool invocation audit log
+//!
+//! With `JOBSUCHE_AUDIT_LOG=true`, every tool call received by the transport
+//! is appended to a bounded log in the local store: when it ran, the tool,
+//! its parameters, how long it took and whether it failed, so users can
+//! review with get_audit_log what their agent did on their behalf. Only
+//! parameters in [`RECORDED_PARAMS`] keep their values; everything else,
+//! such as CV text, notes and profile data, is stored as `[redacted]`, and
+//! long values are shortened.
+
+use crate::storage::{now_rfc3339, Storage, AUDIT_LOG};
+use anyhow::Result;
+use pulseengine_mcp_protocol::{CallToolRequestParam, CallToolResult, Content, Error};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Maximum number of entries kept; older entries are dropped
+pub const MAX_AUDIT_ENTRIES: usize = 1000;
+
+/// Default number of entries returned by get_audit_log
+pub const DEFAULT_AUDIT_ENTRIES: usize = 50;
+
+/// Parameters whose values are stored; all others are redacted
+///
+/// Search criteria, identifiers and options. Free text and profile data (CV
+/// text, notes, skills, salary expectations, languages, labels) are left out,
+/// as is any parameter added later until it is listed here.
+pub const RECORDED_PARAMS: &[&str] = &[
+    "allow",
+    "areas",
+    "block",
+    "branch",
+    "clear",
+    "collapse_duplicates",
+    "columns",
+    "combine",
+    "commute_mode",
+    "contract_stability",
+    "contract_type",
+    "country",
+    "days",
+    "degree_type",
+    "detail_level",
+    "disallow",
+    "distance",
+    "dual_only",
+    "employer",
+    "employers",
+    "employment_type",
+    "english_only",
+    "english_postings_only",
+    "exclude_fields",
+    "exclude_locations",
+    "exclude_temp_agency",
+    "fields",
+    "file_name",
+    "format",
+    "freshness",
+    "fulltime",
+    "fundable_only",
+    "group_by",
+    "hide_seen",
+    "include_abroad",
+    "include_details",
+    "include_fields",
+    "include_passed",
+    "include_raw",
+    "index",
+    "interval",
+    "job_title",
+    "keyword",
+    "lang",
+    "level",
+    "limit",
+    "location",
+    "max_commute_minutes",
+    "max_details",
+    "max_details_per_search",
+    "max_jobs",
+    "max_jobs_per_area",
+    "max_jobs_per_employer",
+    "max_results",
+    "name",
+    "nationwide",
+    "occupation",
+    "occupation_code",
+    "offset",
+    "outcome",
+    "page",
+    "page_size",
+    "permanent_only",
+    "preview",
+    "profile_match",
+    "published_since_days",
+    "query",
+    "radius_km",
+    "reference_number",
+    "reference_numbers",
+    "region",
+    "relocation_only",
+    "remote_only",
+    "request_id",
+    "resolve_external_url",
+    "retention_days",
+    "search",
+    "searches",
+    "since",
+    "sort_by",
+    "sort_by_distance",
+    "subject",
+    "target",
+    "title",
+    "tool",
+    "top_jobs",
+    "topic",
+    "unblock",
+    "visa_sponsorship_only",
+    "within_days",
+];
+
+/// Stored in place of the value of a parameter not in [`RECORDED_PARAMS`]
+const REDACTED: &str = "[redacted]";
+
+/// Number of characters kept of longer parameter values
+const MAX_VALUE_CHARS: usize = 200;
+
+/// Result of a tool call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// The tool returned a result
+    Ok,
+    /// The tool returned an error
+    Error,
+}
+
+/// One recorded tool call
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AuditEntry {
+    /// Stable index of the entry (increases with every call)
+    pub index: u64,
+
+    /// When the call started (RFC 3339)
+    pub timestamp: String,
+
+    /// Tool name
+    pub tool: String,
+
+    /// Request ID of the call, as returned in its result and logged
+    #[serde(default)]
+    pub request_id: String,
+
+    /// Parameters as JSON, redacted and with long values shortened
+    pub params: String,
+
+    /// Duration of the call in milliseconds
+    pub duration_ms: u64,
+
+    /// Whether the call succeeded
+    pub outcome: AuditOutcome,
+
+    /// Error message of a failed call
+    pub error: Option<String>,
+}
+
+/// Prepare the arguments of a tool call for the audit log
+///
+/// Keeps the values of [`RECORDED_PARAMS`] (also inside nested searches),
+/// redacts all other values, shortens long strings and drops unset
+/// parameters.
+pub fn redact_params(arguments: &Value) -> Value {
+    match arguments {
+        Value::Object(params) => params
+            .iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(name, value)| {
+                let value = if RECORDED_PARAMS.contains(&name.as_str()) {
+                    redact_params(value)
+                } else {
+                    Value::String(REDACTED.to_string())
+                };
+                (name.clone(), value)
+            })
+            .collect(),
+        Value::Array(values) => values.iter().map(redact_params).collect(),
+        Value::String(value) if value.chars().count() > MAX_VALUE_CHARS => {
+            let shortened: String = value.chars().take(MAX_VALUE_CHARS).collect();
+            Value::String(shortened + "…")
+        }
+        value => value.clone(),
+    }
+}
+
+/// A tool call in progress
+#[derive(Debug)]
+pub struct ToolCall {
+    tool: String,
+    timestamp: String,
+    params: String,
+    started: Instant,
+}
+
+impl ToolCall {
+    /// Note the start of a call
+    pub fn start(request: &CallToolRequestParam) -> Self {
+        let params = request
+            .arguments
+            .as_ref()
+            .map_or_else(|| Value::Object(Default::default()), redact_params);
+        Self {
+            tool: request.name.clone(),
+            timestamp: now_rfc3339(),
+            params: params.to_string(),
+            started: Instant::now(),
+        }
+    }
+
+    /// Audit entry for the finished call
+    ///
+    /// The index is assigned when the entry is written.
+    pub fn finish(self, result: &Result<CallToolResult, Error>) -> AuditEntry {
+        let (request_id, error) = match result {
+            Ok(result) => {
+                let request_id = result
+                    .structured_content
+                    .as_ref()
+                    .and_then(|content| content.get("request_id"))
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                let error = result.is_error.unwrap_or(false).then(|| {
+                    result
+                        .content
+                        .iter()
+                        .filter_map(|content| match content {
+                            Content::Text { text, .. } => Some(text.as_str()),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                });
+                (request_id, error)
+            }
+            Err(e) => (String::new(), Some(e.message.clone())),
+        };
+        AuditEntry {
+            index: 0,
+            timestamp: self.timestamp,
+            tool: self.tool,
+            request_id,
+            params: self.params,
+            duration_ms: self.started.elapsed().as_millis() as u64,
+            outcome: match error {
+                Some(_) => AuditOutcome::Error,
+                None => AuditOutcome::Ok,
+            },
+            error,
+        }
+    }
+}
+
+/// Storage key of an entry; zero-padded so that keys sort like indices
+fn entry_key(index: u64) -> String {
+    format!("{:020}", index)
+}
+
+/// Recorded entries, oldest first
+pub fn entries(storage: &Storage) -> Vec<AuditEntry> {
+    storage.list(AUDIT_LOG)
+}
+
+/// Store an entry, dropping the one that left the window of `max_entries`
+fn append(storage: &Storage, entry: &AuditEntry, max_entries: usize) -> Result<()> {
+    storage.put(AUDIT_LOG, &entry_key(entry.index), entry)?;
+    if let Some(expired) = entry.index.checked_sub(max_entries as u64) {
+        storage.remove(AUDIT_LOG, &entry_key(expired))?;
+    }
+    Ok(())
+}
+
+/// Writes audit entries to the store
+///
+/// Entries are appended one document each by a background task, so a tool
+/// call never waits for the store. Cloning shares the writer.
+#[derive(Debug, Clone)]
+pub struct AuditRecorder {
+    entries: mpsc::UnboundedSender<AuditEntry>,
+}
+
+impl AuditRecorder {
+    /// Start the writer task; must be called within a Tokio runtime
+    pub fn start(storage: Storage) -> Self {
+        Self::with_max_entries(storage, MAX_AUDIT_ENTRIES)
+    }
+
+    fn with_max_entries(storage: Storage, max_entries: usize) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<AuditEntry>();
+        let mut next_index = entries(&storage).last().map_or(0, |entry| entry.index + 1);
+        tokio::spawn(async move {
+            while let Some(mut entry) = receiver.recv().await {
+                entry.index = next_index;
+                next_index += 1;
+                // The audit log is best effort and must not fail the call
+                if let Err(e) = append(&storage, &entry, max_entries) {
+                    warn!("Failed to record tool call in the audit log: {:#}", e);
+                }
+            }
+        });
+        Self { entries: sender }
+    }
+
+    /// Queue an entry for writing
+    pub fn record(&self, entry: AuditEntry) {
+        let _ = self.entries.send(entry);
+    }
+}
+
+/// Parameters for get_audit_log
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAuditLogParams {
+    /// Only calls of this tool
+    pub tool: Option<String>,
+
+    /// Only the call with this request ID (the `request_id` of its result)
+    pub request_id: Option<String>,
+
+    /// Only calls at or after this time (RFC 3339 or YYYY-MM-DD)
+    #[schemars(example = &"2025-11-03")]
+    pub since: Option<String>,
+
+    /// Only calls with this outcome ("ok" or "error")
+    pub outcome: Option<AuditOutcome>,
+
+    /// Maximum number of entries (1-1000, default: 50)
+    #[schemars(range(min = 1, max = 1000))]
+    pub limit: Option<usize>,
+}
+
+/// Result from get_audit_log
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetAuditLogResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Whether tool calls are currently recorded (JOBSUCHE_AUDIT_LOG)
+    pub enabled: bool,
+
+    /// Number of stored entries matching the filters
+    pub matching_entries: usize,
+
+    /// Matching entries, newest first
+    pub entries: Vec<AuditEntry>,
+}
+
+impl GetAuditLogParams {
+    /// Matching entries of a log (oldest first), newest first
+    pub fn select(&self, entries: &[AuditEntry]) -> (usize, Vec<AuditEntry>) {
+        let since = self.since.as_deref().map(str::trim);
+        let matching: Vec<&AuditEntry> = entries
+            .iter()
+            .rev()
+            .filter(|entry| {
+                self.tool
+                    .as_ref()
+                    .is_none_or(|tool| entry.tool == tool.trim())
+            })
+            .filter(|entry| {
+                self.request_id
+                    .as_ref()
+                    .is_none_or(|id| entry.request_id == id.trim())
+            })
+            .filter(|entry| self.outcome.is_none_or(|outcome| entry.outcome == outcome))
+            // RFC 3339 timestamps in UTC compare like the times they denote
+            .filter(|entry| since.is_none_or(|since| entry.timestamp.as_str() >= since))
+            .collect();
+        let limit = self
+            .limit
+            .unwrap_or(DEFAULT_AUDIT_ENTRIES)
+            .clamp(1, MAX_AUDIT_ENTRIES);
+        let count = matching.len();
+        (count, matching.into_iter().take(limit).cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(tool: &str, timestamp: &str, outcome: AuditOutcome) -> AuditEntry {
+        AuditEntry {
+            index: 0,
+            timestamp: timestamp.to_string(),
+            tool: tool.to_string(),
+            request_id: String::new(),
+            params: String::new(),
+            duration_ms: 5,
+            outcome,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_redact_params() {
+        let params = json!({"cv_text": "Max Mustermann, Rust ...", "reference_numbers": ["1"]});
+        assert_eq!(
+            redact_params(&params),
+            json!({"cv_text": "[redacted]", "reference_numbers": ["1"]})
+        );
+
+        let params = json!({
+            "skills": ["Rust"],
+            "min_annual_salary": 65000.0,
+            "remote_only": null,
+            "searches": [{"job_title": "Koch", "unknown": "x"}]
+        });
+        assert_eq!(
+            redact_params(&params),
+            json!({
+                "skills": "[redacted]",
+                "min_annual_salary": "[redacted]",
+                "searches": [{"job_title": "Koch", "unknown": "[redacted]"}]
+            })
+        );
+
+        let params = json!({"job_title": "a".repeat(300)});
+        assert_eq!(
+            redact_params(&params)["job_title"],
+            format!("{}…", "a".repeat(MAX_VALUE_CHARS))
+        );
+    }
+
+    #[test]
+    fn test_finish() {
+        let request = CallToolRequestParam {
+            name: "add_job_note".to_string(),
+            arguments: Some(
+                json!({"reference_number": "10001-1234567890-S", "text": "Call Frau Müller"}),
+            ),
+        };
+        let mut result = CallToolResult::success(vec![]);
+        result.structured_content = Some(json!({"request_id": "req-1"}));
+        let entry = ToolCall::start(&request).finish(&Ok(result));
+        assert_eq!(entry.tool, "add_job_note");
+        assert_eq!(entry.request_id, "req-1");
+        assert_eq!(entry.outcome, AuditOutcome::Ok);
+        assert!(entry.params.contains("10001-1234567890-S"));
+        assert!(!entry.params.contains("Müller"));
+
+        let error = Error::internal_error("reference_number is empty");
+        let entry = ToolCall::start(&request).finish(&Err(error));
+        assert_eq!(entry.outcome, AuditOutcome::Error);
+        assert_eq!(entry.error.as_deref(), Some("reference_number is empty"));
+    }
+
+    /// Indices of the stored entries once the writer stored `last`
+    async fn written(storage: &Storage, last: u64) -> Vec<u64> {
+        for _ in 0..100 {
+            if entries(storage)
+                .last()
+                .is_some_and(|entry| entry.index == last)
+            {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        entries(storage).iter().map(|entry| entry.index).collect()
+    }
+
+    #[tokio::test]
+    async fn test_recorder_appends_bounded_entries() {
+        let storage = Storage::in_memory();
+        let recorder = AuditRecorder::with_max_entries(storage.clone(), 3);
+        for _ in 0..5 {
+            recorder.record(entry(
+                "search_jobs",
+                "2025-11-03T10:00:00Z",
+                AuditOutcome::Ok,
+            ));
+        }
+        assert_eq!(written(&storage, 4).await, vec![2, 3, 4]);
+
+        // A restarted server continues the numbering
+        let recorder = AuditRecorder::with_max_entries(storage.clone(), 3);
+        recorder.record(entry(
+            "search_jobs",
+            "2025-11-03T11:00:00Z",
+            AuditOutcome::Ok,
+        ));
+        assert_eq!(written(&storage, 5).await, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_select() {
+        let mut entries = vec![
+            entry("search_jobs", "2025-11-02T10:00:00Z", AuditOutcome::Ok),
+            entry(
+                "get_job_details",
+                "2025-11-03T09:00:00Z",
+                AuditOutcome::Error,
+            ),
+            entry("search_jobs", "2025-11-03T11:00:00Z", AuditOutcome::Ok),
+        ];
+        for (index, entry) in entries.iter_mut().enumerate() {
+            entry.index = index as u64;
+        }
+
+        let params = |value: serde_json::Value| -> GetAuditLogParams {
+            serde_json::from_value(value).unwrap()
+        };
+        let (count, selected) = params(json!({})).select(&entries);
+        assert_eq!(count, 3);
+        assert_eq!(selected[0].index, 2);
+
+        let (count, _) = params(json!({"tool": "search_jobs"})).select(&entries);
+        assert_eq!(count, 2);
+        let (count, _) = params(json!({"outcome": "error"})).select(&entries);
+        assert_eq!(count, 1);
+        let (count, selected) = params(json!({"since": "2025-11-03", "limit": 1})).select(&entries);
+        assert_eq!(count, 2);
+        assert_eq!(selected.len(), 1);
+    }
+}
@@ -0,0 +1,438 @@
+//! Candidate search in the Bewerberbörse
+//!
+//! The Bewerberbörse of the Bundesagentur für Arbeit is the counterpart of the
+//! Jobbörse: job seekers publish anonymized profiles that employers can search.
+//! These tools are meant for recruiters and are off unless
+//! `JOBSUCHE_RECRUITER_TOOLS` is set, so deployments for job seekers do not
+//! offer them. The public API only returns anonymized profiles; contact
+//! details are shown to employers logged in to the Bewerberbörse, so results
+//! only say whether a candidate published any.
+
+use crate::dates::parse_date;
+use crate::weiterbildung::{date, first_text, place, text};
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+use time::Date;
+
+/// Default base URL of the Bewerberbörse API
+pub const DEFAULT_BEWERBERBOERSE_URL: &str =
+    "https://rest.arbeitsagentur.de/jobboerse/bewerbersuche-service";
+
+/// Public API key of the Bewerberbörse web application
+pub const DEFAULT_BEWERBERBOERSE_API_KEY: &str = "jobboerse-bewerbersuche-ui";
+
+/// Candidate page on arbeitsagentur.de
+const PROFILE_URL: &str = "https://www.arbeitsagentur.de/bewerberboerse/bewerberdetail";
+
+/// Error returned by the recruiter tools while they are disabled
+pub const RECRUITER_TOOLS_DISABLED: &str =
+    "Recruiter tools are disabled; set JOBSUCHE_RECRUITER_TOOLS=true to enable them";
+
+/// How candidates can be contacted
+const CONTACT_NOTE: &str = "Profiles are anonymized. Contact details are only shown to \
+    employers logged in to the Bewerberbörse at arbeitsagentur.de.";
+
+/// Default number of candidates per page
+pub const DEFAULT_CANDIDATES: u64 = 25;
+
+/// Maximum number of candidates per page
+pub const MAX_CANDIDATES: u64 = 100;
+
+/// Parameters for search_candidates
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SearchCandidatesParams {
+    /// Occupation or keywords (e.g. "Elektroniker", "Buchhalter")
+    pub occupation: String,
+
+    /// City or postal code
+    pub location: Option<String>,
+
+    /// Search radius in km around the location
+    pub radius_km: Option<u64>,
+
+    /// Only candidates available on or before this date (YYYY-MM-DD);
+    /// candidates without an availability date are kept
+    pub available_by: Option<String>,
+
+    /// Candidates per page (default: 25, max: 100)
+    pub page_size: Option<u64>,
+
+    /// Page number, starting at 1
+    pub page: Option<u64>,
+}
+
+/// An anonymized candidate profile from a search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Candidate {
+    /// Reference number of the profile
+    pub reference_number: String,
+
+    /// Occupations the candidate is looking for
+    pub occupations: Vec<String>,
+
+    /// Most recent position
+    pub last_position: Option<String>,
+
+    /// Place of residence ("Bremen (28195)")
+    pub location: Option<String>,
+
+    /// Available from (ISO 8601, YYYY-MM-DD)
+    pub available_from: Option<String>,
+
+    /// Last update of the profile (ISO 8601, YYYY-MM-DD)
+    pub updated_at: Option<String>,
+
+    /// Whether the candidate published an email address, phone number or
+    /// postal address
+    pub has_contact_details: bool,
+
+    /// Profile page on arbeitsagentur.de
+    pub web_url: String,
+}
+
+/// Result from search_candidates
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchCandidatesResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// How to contact candidates
+    pub note: String,
+
+    /// Total number of candidates found
+    pub total_results: Option<u64>,
+
+    /// Current page number
+    pub current_page: u64,
+
+    /// Number of candidates returned
+    pub candidates_count: usize,
+
+    /// Candidates of this page (after the availability filter)
+    pub candidates: Vec<Candidate>,
+}
+
+/// Parameters for get_candidate_profile
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetCandidateProfileParams {
+    /// Reference number from search_candidates
+    pub reference_number: String,
+}
+
+/// Full anonymized profile of a candidate
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetCandidateProfileResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// How to contact the candidate
+    pub note: String,
+
+    /// Reference number of the profile
+    pub reference_number: String,
+
+    /// Occupations the candidate is looking for
+    pub occupations: Vec<String>,
+
+    /// Place of residence
+    pub location: Option<String>,
+
+    /// Available from (ISO 8601, YYYY-MM-DD)
+    pub available_from: Option<String>,
+
+    /// Working time models the candidate accepts (e.g. "Vollzeit")
+    pub working_times: Vec<String>,
+
+    /// Previous positions
+    pub experience: Vec<String>,
+
+    /// School and vocational education
+    pub education: Vec<String>,
+
+    /// Skills
+    pub skills: Vec<String>,
+
+    /// Languages
+    pub languages: Vec<String>,
+
+    /// Profile page on arbeitsagentur.de
+    pub web_url: String,
+
+    /// Profile as delivered by the API
+    pub raw_data: Value,
+}
+
+/// Strings of a list at a JSON pointer; entries are strings or objects named
+/// by one of `names`
+fn texts(entry: &Value, pointer: &str, names: &[&str]) -> Vec<String> {
+    entry
+        .pointer(pointer)
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| match item {
+                    Value::String(name) => Some(name.trim().to_string()),
+                    _ => first_text(item, names),
+                })
+                .filter(|name| !name.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Profile page of a reference number
+fn profile_url(reference_number: &str) -> String {
+    format!("{}/{}", PROFILE_URL, reference_number)
+}
+
+/// Convert one candidate of a search response
+fn parse_candidate(entry: &Value) -> Option<Candidate> {
+    let reference_number = text(entry, "/refnr")?;
+    let has_contact_details = ["hatEmail", "hatTelefon", "hatAdresse"]
+        .iter()
+        .any(|flag| entry.get(flag).and_then(Value::as_bool) == Some(true));
+
+    Some(Candidate {
+        occupations: texts(entry, "/berufe", &["/bezeichnung"]),
+        last_position: first_text(
+            entry,
+            &["/letzteTaetigkeit/bezeichnung", "/letzteTaetigkeit"],
+        ),
+        location: place(entry, "/lokation"),
+        available_from: date(entry, "/verfuegbarkeitVon"),
+        updated_at: date(entry, "/aktualisierungsdatum"),
+        has_contact_details,
+        web_url: profile_url(&reference_number),
+        reference_number,
+    })
+}
+
+/// Extract the candidates and the total count from a search response
+fn parse_candidates(response: &Value) -> (Vec<Candidate>, Option<u64>) {
+    let candidates = response
+        .get("bewerber")
+        .and_then(Value::as_array)
+        .map(|entries| entries.iter().filter_map(parse_candidate).collect())
+        .unwrap_or_default();
+    let total = response.get("maxErgebnisse").and_then(Value::as_u64);
+    (candidates, total)
+}
+
+/// Convert a profile response
+fn parse_profile(reference_number: &str, profile: Value) -> GetCandidateProfileResult {
+    GetCandidateProfileResult {
+        request_id: String::new(),
+        note: CONTACT_NOTE.to_string(),
+        reference_number: reference_number.to_string(),
+        occupations: texts(&profile, "/berufe", &["/bezeichnung"]),
+        location: place(&profile, "/lokation"),
+        available_from: date(&profile, "/verfuegbarkeitVon"),
+        working_times: texts(&profile, "/arbeitszeitModelle", &["/bezeichnung"]),
+        experience: texts(
+            &profile,
+            "/erfahrungen",
+            &["/berufsbezeichnung", "/bezeichnung"],
+        ),
+        education: texts(&profile, "/ausbildungen", &["/art", "/bezeichnung"]),
+        skills: texts(&profile, "/kenntnisse", &["/bezeichnung"]),
+        languages: texts(&profile, "/sprachkenntnisse", &["/sprache", "/bezeichnung"]),
+        web_url: profile_url(reference_number),
+        raw_data: profile,
+    }
+}
+
+/// Whether a candidate is available on or before `available_by`
+pub fn available_by(candidate: &Candidate, available_by: Option<Date>) -> bool {
+    match (available_by, candidate.available_from.as_deref()) {
+        (Some(by), Some(from)) => parse_date(from).is_none_or(|from| from <= by),
+        _ => true,
+    }
+}
+
+/// Client for the Bewerberbörse API
+#[derive(Debug, Clone)]
+pub struct BewerberboerseClient {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+}
+
+impl BewerberboerseClient {
+    /// Create a client for the given base URL and API key
+    pub fn new(base_url: &str, api_key: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .context("Failed to create Bewerberbörse client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+        })
+    }
+
+    /// GET a path of the API as JSON
+    async fn get(&self, path: &str, query: &[(&str, String)]) -> Result<Value> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self
+            .client
+            .get(&url)
+            .header("X-API-Key", &self.api_key)
+            .query(query)
+            .send()
+            .await
+            .context("Bewerberbörse request failed")?;
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            anyhow::bail!(
+                "Bewerberbörse rejected the API key (HTTP {}); check JOBSUCHE_BEWERBERBOERSE_API_KEY",
+                status
+            );
+        }
+        if !status.is_success() {
+            anyhow::bail!("Bewerberbörse responded with HTTP {}", status);
+        }
+        response
+            .json()
+            .await
+            .context("Invalid Bewerberbörse response")
+    }
+
+    /// Search candidate profiles
+    pub async fn search(&self, params: &SearchCandidatesParams) -> Result<SearchCandidatesResult> {
+        if params.occupation.trim().is_empty() {
+            anyhow::bail!("occupation must not be empty");
+        }
+        let by = match params.available_by.as_deref() {
+            Some(by) => Some(parse_date(by).with_context(|| {
+                format!(
+                    "Invalid available_by '{}': expected a date such as 2026-03-01",
+                    by
+                )
+            })?),
+            None => None,
+        };
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params
+            .page_size
+            .unwrap_or(DEFAULT_CANDIDATES)
+            .clamp(1, MAX_CANDIDATES);
+
+        let mut query = vec![
+            ("was", params.occupation.trim().to_string()),
+            ("page", page.to_string()),
+            ("size", page_size.to_string()),
+        ];
+        if let Some(ref location) = params.location {
+            query.push(("wo", location.trim().to_string()));
+        }
+        if let Some(radius_km) = params.radius_km {
+            query.push(("umkreis", radius_km.to_string()));
+        }
+
+        let body = self.get("/pc/v1/bewerber", &query).await?;
+        let (candidates, total_results) = parse_candidates(&body);
+        let candidates: Vec<Candidate> = candidates
+            .into_iter()
+            .filter(|candidate| available_by(candidate, by))
+            .collect();
+        Ok(SearchCandidatesResult {
+            request_id: String::new(),
+            note: CONTACT_NOTE.to_string(),
+            total_results,
+            current_page: page,
+            candidates_count: candidates.len(),
+            candidates,
+        })
+    }
+
+    /// Fetch the full profile of a candidate
+    pub async fn profile(&self, reference_number: &str) -> Result<GetCandidateProfileResult> {
+        let reference_number = reference_number.trim();
+        if reference_number.is_empty() {
+            anyhow::bail!("reference_number must not be empty");
+        }
+        // Like job details, profiles are addressed by the Base64 reference number
+        let path = format!("/pc/v1/bewerberdetails/{}", BASE64.encode(reference_number));
+        let body = self.get(&path, &[]).await?;
+        Ok(parse_profile(reference_number, body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_candidates() {
+        let response = json!({
+            "maxErgebnisse": 48,
+            "bewerber": [
+                {
+                    "refnr": "10000-1234567-B",
+                    "berufe": ["Elektroniker/in - Energie- und Gebäudetechnik"],
+                    "letzteTaetigkeit": {"bezeichnung": "Elektroinstallateur", "jahr": 2025},
+                    "lokation": {"ort": "Bremen", "plz": "28195"},
+                    "verfuegbarkeitVon": "2026-04-01",
+                    "aktualisierungsdatum": "2026-02-11T09:12:00",
+                    "hatEmail": false,
+                    "hatTelefon": true
+                },
+                {"berufe": ["ohne Referenznummer"]}
+            ]
+        });
+
+        let (candidates, total) = parse_candidates(&response);
+        assert_eq!(total, Some(48));
+        assert_eq!(candidates.len(), 1);
+        let candidate = &candidates[0];
+        assert_eq!(
+            candidate.last_position.as_deref(),
+            Some("Elektroinstallateur")
+        );
+        assert_eq!(candidate.location.as_deref(), Some("Bremen (28195)"));
+        assert_eq!(candidate.updated_at.as_deref(), Some("2026-02-11"));
+        assert!(candidate.has_contact_details);
+        assert_eq!(
+            candidate.web_url,
+            "https://www.arbeitsagentur.de/bewerberboerse/bewerberdetail/10000-1234567-B"
+        );
+
+        assert!(available_by(candidate, parse_date("2026-04-01")));
+        assert!(!available_by(candidate, parse_date("2026-03-31")));
+        assert!(available_by(candidate, None));
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        let profile = parse_profile(
+            "10000-1234567-B",
+            json!({
+                "berufe": [{"bezeichnung": "Buchhalter/in"}],
+                "arbeitszeitModelle": ["Vollzeit", "Teilzeit"],
+                "erfahrungen": [{"berufsbezeichnung": "Finanzbuchhalter", "von": "2019"}],
+                "ausbildungen": [{"art": "Bachelor Betriebswirtschaft"}],
+                "kenntnisse": [{"bezeichnung": "DATEV"}, {"bezeichnung": ""}],
+                "sprachkenntnisse": [{"sprache": "Englisch", "niveau": "B2"}]
+            }),
+        );
+        assert_eq!(profile.occupations, vec!["Buchhalter/in"]);
+        assert_eq!(profile.working_times, vec!["Vollzeit", "Teilzeit"]);
+        assert_eq!(profile.experience, vec!["Finanzbuchhalter"]);
+        assert_eq!(profile.skills, vec!["DATEV"]);
+        assert_eq!(profile.languages, vec!["Englisch"]);
+        assert_eq!(profile.available_from, None);
+    }
+}
@@ -0,0 +1,87 @@
+//! Build metadata
+//!
+//! The build script embeds the git commit, the build time, the version of
+//! the jobsuche API client and the enabled cargo features, so a bug report
+//! can name the exact build. get_server_status includes them as `build`, and
+//! the `jobsuche://about` resource returns them on their own.
+//!
+//! Builds outside a git checkout report the commit as "unknown" unless
+//! `JOBSUCHE_GIT_COMMIT` is set at build time; `SOURCE_DATE_EPOCH` fixes the
+//! build time for reproducible builds.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Short git commit hash, with `-dirty` for uncommitted changes
+pub const GIT_COMMIT: &str = env!("JOBSUCHE_BUILD_GIT_COMMIT");
+
+/// Build time (RFC 3339)
+pub const BUILD_TIMESTAMP: &str = env!("JOBSUCHE_BUILD_TIMESTAMP");
+
+/// Version of the jobsuche crate from Cargo.lock
+pub const JOBSUCHE_VERSION: &str = env!("JOBSUCHE_BUILD_JOBSUCHE_VERSION");
+
+/// Enabled cargo features, comma-separated
+pub const FEATURES: &str = env!("JOBSUCHE_BUILD_FEATURES");
+
+/// Metadata identifying a build
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BuildInfo {
+    /// Package name
+    pub name: String,
+
+    /// Server version
+    pub version: String,
+
+    /// Short git commit hash (`-dirty` if built with uncommitted changes)
+    pub git_commit: String,
+
+    /// Build time (RFC 3339)
+    pub build_timestamp: String,
+
+    /// Version of the jobsuche API client crate
+    pub jobsuche_version: String,
+
+    /// Enabled cargo features (e.g. "parquet", "keychain")
+    pub features: Vec<String>,
+}
+
+impl BuildInfo {
+    /// Metadata of the running build
+    pub fn current() -> Self {
+        Self {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: GIT_COMMIT.to_string(),
+            build_timestamp: BUILD_TIMESTAMP.to_string(),
+            jobsuche_version: JOBSUCHE_VERSION.to_string(),
+            features: FEATURES
+                .split(',')
+                .filter(|feature| !feature.is_empty() && *feature != "default")
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current() {
+        let info = BuildInfo::current();
+        assert_eq!(info.name, "jobsuche-mcp-server");
+        assert!(!info.git_commit.is_empty());
+        assert!(!info.jobsuche_version.is_empty());
+        assert!(time::OffsetDateTime::parse(
+            &info.build_timestamp,
+            &time::format_description::well_known::Rfc3339
+        )
+        .is_ok());
+        assert_eq!(
+            info.features.contains(&"parquet".to_string()),
+            cfg!(feature = "parquet")
+        );
+    }
+}
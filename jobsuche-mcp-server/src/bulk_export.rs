@@ -0,0 +1,328 @@
+//! Bulk export for data analysis
+//!
+//! Collects large numbers of postings (optionally with full details) across
+//! result pages and streams them to a file, one record at a time. NDJSON is
+//! always available; Parquet output requires the `parquet` feature.
+
+use crate::SearchJobsParams;
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Default number of jobs per bulk export
+pub const DEFAULT_BULK_JOBS: usize = 500;
+
+/// Maximum number of jobs per bulk export
+pub const MAX_BULK_JOBS: usize = 10_000;
+
+/// File format of a bulk export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkExportFormat {
+    #[default]
+    Ndjson,
+    Parquet,
+}
+
+impl BulkExportFormat {
+    /// File extension without dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ndjson => "ndjson",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+/// Streaming writer for bulk export records
+pub enum BulkWriter {
+    Ndjson(BufWriter<File>),
+    #[cfg(feature = "parquet")]
+    Parquet(Box<parquet_sink::ParquetSink>),
+}
+
+impl BulkWriter {
+    /// Create the output file
+    pub fn create(path: &Path, format: BulkExportFormat) -> Result<Self> {
+        match format {
+            BulkExportFormat::Ndjson => {
+                let file = File::create(path)
+                    .with_context(|| format!("Failed to create {}", path.display()))?;
+                Ok(Self::Ndjson(BufWriter::new(file)))
+            }
+            #[cfg(feature = "parquet")]
+            BulkExportFormat::Parquet => Ok(Self::Parquet(Box::new(
+                parquet_sink::ParquetSink::create(path)?,
+            ))),
+            #[cfg(not(feature = "parquet"))]
+            BulkExportFormat::Parquet => {
+                anyhow::bail!("Parquet export requires building with the `parquet` feature")
+            }
+        }
+    }
+
+    /// Append one record
+    pub fn write(&mut self, record: &serde_json::Value) -> Result<()> {
+        match self {
+            Self::Ndjson(writer) => {
+                serde_json::to_writer(&mut *writer, record)?;
+                writer.write_all(b"\n")?;
+            }
+            #[cfg(feature = "parquet")]
+            Self::Parquet(sink) => sink.write(record)?,
+        }
+        Ok(())
+    }
+
+    /// Make the records written so far durable where the format allows it
+    pub fn flush(&mut self) -> Result<()> {
+        match self {
+            Self::Ndjson(writer) => writer.flush()?,
+            #[cfg(feature = "parquet")]
+            Self::Parquet(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Write remaining data and close the file
+    pub fn finish(self) -> Result<()> {
+        match self {
+            Self::Ndjson(mut writer) => writer.flush()?,
+            #[cfg(feature = "parquet")]
+            Self::Parquet(sink) => sink.finish()?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_sink {
+    use anyhow::{Context, Result};
+    use arrow_array::{ArrayRef, RecordBatch, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+    use parquet::arrow::ArrowWriter;
+    use parquet::basic::Compression;
+    use parquet::file::properties::WriterProperties;
+    use std::fs::File;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    /// Record fields stored as separate columns; the full record goes into `json`
+    pub const COLUMNS: &[&str] = &[
+        "reference_number",
+        "title",
+        "employer",
+        "location",
+        "published_date",
+        "first_published",
+        "employment_type",
+        "salary",
+        "contract_duration",
+        "entry_period",
+        "external_url",
+        "web_url",
+        "description",
+    ];
+
+    /// Rows per row group
+    const BATCH_SIZE: usize = 500;
+
+    /// Parquet file with one nullable string column per field
+    pub struct ParquetSink {
+        writer: ArrowWriter<File>,
+        schema: Arc<Schema>,
+        rows: Vec<serde_json::Value>,
+    }
+
+    impl ParquetSink {
+        pub fn create(path: &Path) -> Result<Self> {
+            let mut fields: Vec<Field> = COLUMNS
+                .iter()
+                .map(|name| Field::new(*name, DataType::Utf8, true))
+                .collect();
+            fields.push(Field::new("json", DataType::Utf8, false));
+            let schema = Arc::new(Schema::new(fields));
+
+            let file = File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            let properties = WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .build();
+            let writer = ArrowWriter::try_new(file, schema.clone(), Some(properties))?;
+
+            Ok(Self {
+                writer,
+                schema,
+                rows: Vec::new(),
+            })
+        }
+
+        pub fn write(&mut self, record: &serde_json::Value) -> Result<()> {
+            self.rows.push(record.clone());
+            if self.rows.len() >= BATCH_SIZE {
+                self.write_batch()?;
+            }
+            Ok(())
+        }
+
+        pub fn finish(mut self) -> Result<()> {
+            self.write_batch()?;
+            self.writer.close()?;
+            Ok(())
+        }
+
+        fn write_batch(&mut self) -> Result<()> {
+            if self.rows.is_empty() {
+                return Ok(());
+            }
+
+            let mut columns: Vec<ArrayRef> = COLUMNS
+                .iter()
+                .map(|name| {
+                    let values: Vec<Option<String>> =
+                        self.rows.iter().map(|row| text_field(row, name)).collect();
+                    Arc::new(StringArray::from(values)) as ArrayRef
+                })
+                .collect();
+            let json: Vec<String> = self.rows.iter().map(|row| row.to_string()).collect();
+            columns.push(Arc::new(StringArray::from(json)));
+
+            let batch = RecordBatch::try_new(self.schema.clone(), columns)?;
+            self.writer.write(&batch)?;
+            self.rows.clear();
+            Ok(())
+        }
+    }
+
+    /// Field as text; strings unquoted, other values as JSON, null/missing as None
+    fn text_field(record: &serde_json::Value, name: &str) -> Option<String> {
+        match record.get(name)? {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(text) => Some(text.clone()),
+            other => Some(other.to_string()),
+        }
+    }
+}
+
+/// Parameters for bulk_export_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BulkExportParams {
+    /// Search to export (same parameters as search_jobs; page_size and hide_seen are ignored)
+    pub search: SearchJobsParams,
+
+    /// Maximum number of jobs to export (default: 500, max: 10000)
+    pub max_jobs: Option<usize>,
+
+    /// Fetch full details for every job (default: true); slower, about 10 jobs per second
+    pub include_details: Option<bool>,
+
+    /// File format: "ndjson" (default) or "parquet" (if built with the parquet feature)
+    pub format: Option<BulkExportFormat>,
+
+    /// File name inside the export directory (default: jobs-<timestamp>.ndjson or .parquet)
+    pub file_name: Option<String>,
+}
+
+/// Result from bulk_export_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BulkExportResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Path of the written file
+    pub path: String,
+
+    /// File format
+    pub format: BulkExportFormat,
+
+    /// Number of records written
+    pub jobs_written: usize,
+
+    /// Number of result pages fetched
+    pub pages_fetched: u64,
+
+    /// Total number of results of the search
+    pub total_results: Option<u64>,
+
+    /// Jobs skipped because their details could not be fetched
+    pub failed_details: Vec<String>,
+
+    /// True if the client cancelled the request; the file then holds the
+    /// records written before cancellation
+    pub cancelled: bool,
+
+    /// Duration of the export
+    pub duration_ms: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("jobsuche-bulk-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_ndjson_one_record_per_line() {
+        let path = temp_path("records.ndjson");
+        let mut writer = BulkWriter::create(&path, BulkExportFormat::Ndjson).unwrap();
+        writer
+            .write(&serde_json::json!({"reference_number": "A", "title": "Line\nbreak"}))
+            .unwrap();
+        writer
+            .write(&serde_json::json!({"reference_number": "B"}))
+            .unwrap();
+        writer.finish().unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let records: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["title"], "Line\nbreak");
+        assert_eq!(records[1]["reference_number"], "B");
+    }
+
+    #[cfg(not(feature = "parquet"))]
+    #[test]
+    fn test_parquet_requires_feature() {
+        let path = temp_path("disabled.parquet");
+        let result = BulkWriter::create(&path, BulkExportFormat::Parquet);
+        assert!(result.is_err());
+        assert!(!path.exists());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_roundtrip() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let path = temp_path("records.parquet");
+        let mut writer = BulkWriter::create(&path, BulkExportFormat::Parquet).unwrap();
+        for i in 0..3 {
+            writer
+                .write(&serde_json::json!({"reference_number": format!("REF-{}", i), "fulltime": true}))
+                .unwrap();
+        }
+        writer.finish().unwrap();
+
+        let reader = SerializedFileReader::new(File::open(&path).unwrap()).unwrap();
+        let metadata = reader.metadata().file_metadata();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(metadata.num_rows(), 3);
+        assert_eq!(
+            metadata.schema_descr().num_columns(),
+            parquet_sink::COLUMNS.len() + 1
+        );
+    }
+}
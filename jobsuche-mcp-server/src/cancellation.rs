@@ -0,0 +1,105 @@
+//! Cancellation of in-flight tool calls
+//!
+//! Clients cancel a request with an MCP `notifications/cancelled` message that
+//! names its request ID. The [`CancellationRegistry`] maps active request IDs to
+//! cancellation tokens; long-running tools check the token of their
+//! [`RequestContext`](crate::context::RequestContext) between upstream calls.
+
+use crate::context::RequestId;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+/// Cancellation tokens of all requests currently being processed
+#[derive(Debug, Default)]
+pub struct CancellationRegistry {
+    active: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl CancellationRegistry {
+    /// Register a new request and return its cancellation token
+    pub fn register(&self, request_id: &RequestId) -> CancellationToken {
+        let token = CancellationToken::new();
+        self.active
+            .lock()
+            .unwrap()
+            .insert(request_id.to_string(), token.clone());
+        token
+    }
+
+    /// Cancel a request; returns `false` if it is unknown or already finished
+    pub fn cancel(&self, request_id: &RequestId) -> bool {
+        match self.active.lock().unwrap().get(&request_id.to_string()) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a finished request
+    pub fn finish(&self, request_id: &RequestId) {
+        self.active.lock().unwrap().remove(&request_id.to_string());
+    }
+
+    /// Number of requests currently registered
+    pub fn active_count(&self) -> usize {
+        self.active.lock().unwrap().len()
+    }
+}
+
+/// Run `future` unless `token` is cancelled first
+///
+/// Returns `None` if the future was aborted because of cancellation.
+pub async fn run_cancellable<F: Future>(token: &CancellationToken, future: F) -> Option<F::Output> {
+    tokio::select! {
+        biased;
+        _ = token.cancelled() => None,
+        output = future => Some(output),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_register_and_cancel() {
+        let registry = CancellationRegistry::default();
+        let id = serde_json::json!(42);
+
+        let token = registry.register(&id);
+        assert_eq!(registry.active_count(), 1);
+        assert!(registry.cancel(&id));
+        assert!(token.is_cancelled());
+
+        registry.finish(&id);
+        assert_eq!(registry.active_count(), 0);
+        assert!(!registry.cancel(&id));
+    }
+
+    #[test]
+    fn test_string_and_number_ids_are_distinct() {
+        let registry = CancellationRegistry::default();
+        let token = registry.register(&serde_json::json!("1"));
+        assert!(!registry.cancel(&serde_json::json!(1)));
+        assert!(!token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_run_cancellable_completes() {
+        let token = CancellationToken::new();
+        assert_eq!(run_cancellable(&token, async { 5 }).await, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_run_cancellable_aborts() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let result = run_cancellable(&token, tokio::time::sleep(Duration::from_secs(60))).await;
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,553 @@
+//! Application checklists
+//!
+//! Turns the details of a posting into concrete to-dos for an application:
+//! the documents the description asks for, the languages and certificates to
+//! prove, what to state in the application and how to send it. Every item
+//! has an ID that stays the same for the same posting, so hosts can track
+//! which items are done. Items come from keywords and the extracted details;
+//! the description stays authoritative.
+
+use crate::description::{clean_description, DescriptionFormat};
+use crate::skills::SkillCategory;
+use crate::GetJobDetailsResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Maximum length of the evidence quoted from the description, in characters
+const MAX_EVIDENCE_CHARS: usize = 160;
+
+/// Documents: ID, task and lowercase terms, matched at the start of a word so
+/// "zeugnis" covers "Zeugnisse" but not "Führungszeugnis"
+const DOCUMENTS: &[(&str, &str, &[&str])] = &[
+    (
+        "cv",
+        "Prepare an up-to-date CV (Lebenslauf)",
+        &["lebenslauf", "cv", "curriculum vitae", "resume", "résumé"],
+    ),
+    (
+        "cover_letter",
+        "Write a cover letter (Anschreiben) for this position",
+        &[
+            "anschreiben",
+            "motivationsschreiben",
+            "bewerbungsschreiben",
+            "cover letter",
+            "letter of motivation",
+        ],
+    ),
+    (
+        "references",
+        "Collect employer references and school or degree certificates (Zeugnisse)",
+        &[
+            "zeugnis",
+            "arbeitszeugnis",
+            "abschlusszeugnis",
+            "referenzen",
+            "references",
+        ],
+    ),
+    (
+        "qualifications",
+        "Gather proof of your qualifications (certificates, diplomas)",
+        &[
+            "zertifikat",
+            "qualifikationsnachweis",
+            "diplom",
+            "urkunde",
+            "certificates",
+        ],
+    ),
+    (
+        "police_clearance",
+        "Request a certificate of good conduct (Führungszeugnis); it can take two weeks",
+        &[
+            "führungszeugnis",
+            "certificate of good conduct",
+            "police clearance",
+        ],
+    ),
+    (
+        "health_certificate",
+        "Get the health instruction under § 43 IfSG (Gesundheitszeugnis)",
+        &["gesundheitszeugnis", "infektionsschutz", "ifsg"],
+    ),
+    (
+        "measles",
+        "Have proof of measles immunity ready",
+        &["masern", "measles"],
+    ),
+    (
+        "work_permit",
+        "Have proof of your work permit or residence title ready",
+        &[
+            "arbeitserlaubnis",
+            "aufenthaltstitel",
+            "aufenthaltserlaubnis",
+            "work permit",
+        ],
+    ),
+    (
+        "photo",
+        "Add an application photo",
+        &["bewerbungsfoto", "lichtbild"],
+    ),
+    (
+        "work_samples",
+        "Prepare work samples",
+        &["arbeitsprobe", "work sample"],
+    ),
+    (
+        "single_pdf",
+        "Combine all documents into one PDF file",
+        &[
+            "einer pdf",
+            "eine pdf",
+            "einem pdf",
+            "single pdf",
+            "one pdf",
+        ],
+    ),
+];
+
+/// Information to state in the application: ID, task and terms
+const DETAILS: &[(&str, &str, &[&str])] = &[
+    (
+        "salary_expectation",
+        "State your salary expectation",
+        &["gehaltsvorstellung", "gehaltswunsch", "salary expectation"],
+    ),
+    (
+        "start_date",
+        "State your earliest possible start date",
+        &[
+            "eintrittstermin",
+            "frühestmöglich",
+            "earliest start",
+            "earliest possible start",
+        ],
+    ),
+    (
+        "reference",
+        "Quote the reference number given in the posting",
+        &[
+            "kennziffer",
+            "referenznummer",
+            "stellennummer",
+            "reference number",
+        ],
+    ),
+];
+
+/// Phrases asking for applications by post
+const POSTAL_TERMS: &[&str] = &[
+    "postalisch",
+    "per post",
+    "auf dem postweg",
+    "schriftliche bewerbung",
+    "by post",
+];
+
+/// Phrases asking for applications through an online form
+const ONLINE_TERMS: &[&str] = &[
+    "online-bewerbung",
+    "onlinebewerbung",
+    "online bewerben",
+    "bewerbungsportal",
+    "bewerbungsformular",
+    "karriereportal",
+    "apply online",
+];
+
+/// Parameters for build_application_checklist
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BuildApplicationChecklistParams {
+    /// Job reference number (refnr, e.g. "10001-1234567890-S") or hashId
+    #[schemars(length(min = 1), example = &"10001-1234567890-S")]
+    pub reference_number: String,
+}
+
+/// Kind of a checklist item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecklistCategory {
+    /// How and to whom to send the application
+    ApplicationChannel,
+    /// Documents to include
+    Document,
+    /// Information to state in the application
+    ApplicationDetail,
+    /// Spoken languages to prove
+    Language,
+    /// Certificates and driver's licenses to prove
+    Certification,
+}
+
+/// One to-do for an application
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ChecklistItem {
+    /// ID of the item, the same for the same posting ("document:cv",
+    /// "language:german", "certification:fuhrerschein_ce")
+    pub id: String,
+
+    /// Kind of item
+    pub category: ChecklistCategory,
+
+    /// What to do
+    pub task: String,
+
+    /// False if the posting only calls it a plus
+    pub required: bool,
+
+    /// Sentence or value from the posting the item is based on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<String>,
+}
+
+impl ChecklistItem {
+    fn new(category: ChecklistCategory, key: &str, task: String) -> Self {
+        let prefix = match category {
+            ChecklistCategory::ApplicationChannel => "channel",
+            ChecklistCategory::Document => "document",
+            ChecklistCategory::ApplicationDetail => "detail",
+            ChecklistCategory::Language => "language",
+            ChecklistCategory::Certification => "certification",
+        };
+        Self {
+            id: format!("{}:{}", prefix, key),
+            category,
+            task,
+            required: true,
+            evidence: None,
+        }
+    }
+
+    fn with_evidence(mut self, evidence: String) -> Self {
+        self.evidence = Some(evidence);
+        self
+    }
+}
+
+/// Result from build_application_checklist
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BuildApplicationChecklistResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Job title
+    pub title: Option<String>,
+
+    /// Employer name
+    pub employer: Option<String>,
+
+    /// Job page on arbeitsagentur.de
+    pub web_url: String,
+
+    /// Number of items
+    pub items_count: usize,
+
+    /// To-dos: application channel first, then documents, details to state,
+    /// languages and certifications
+    pub items: Vec<ChecklistItem>,
+}
+
+/// Checklist for a posting
+pub fn build_checklist(details: &GetJobDetailsResult) -> Vec<ChecklistItem> {
+    let description = details
+        .description
+        .as_deref()
+        .map(|text| clean_description(text, DescriptionFormat::Text))
+        .unwrap_or_default();
+    let sentences: Vec<&str> = sentences(&description).collect();
+
+    let mut items = channel_items(details, &sentences);
+    for (category, entries) in [
+        (ChecklistCategory::Document, DOCUMENTS),
+        (ChecklistCategory::ApplicationDetail, DETAILS),
+    ] {
+        for (key, task, terms) in entries {
+            if let Some(sentence) = find_sentence(&sentences, terms) {
+                items.push(
+                    ChecklistItem::new(category, key, task.to_string())
+                        .with_evidence(evidence(sentence)),
+                );
+            }
+        }
+    }
+
+    for requirement in &details.language_requirements {
+        let level = match (&requirement.level, &requirement.proficiency) {
+            (Some(level), _) => format!(" at level {}", level),
+            (None, Some(proficiency)) => format!(" ({})", proficiency),
+            (None, None) => String::new(),
+        };
+        let task = format!(
+            "Show your {} skills{} in the CV, with a certificate if you have one",
+            requirement.language, level
+        );
+        let mut item = ChecklistItem::new(
+            ChecklistCategory::Language,
+            &slug(&requirement.language),
+            task,
+        );
+        item.required = requirement.required;
+        items.push(item);
+    }
+
+    for skill in &details.extracted_skills {
+        if matches!(
+            skill.category,
+            SkillCategory::Certification | SkillCategory::DriversLicense
+        ) {
+            items.push(ChecklistItem::new(
+                ChecklistCategory::Certification,
+                &slug(&skill.name),
+                format!("Have proof of {} ready", skill.name),
+            ));
+        }
+    }
+    items
+}
+
+/// How to send the application
+fn channel_items(details: &GetJobDetailsResult, sentences: &[&str]) -> Vec<ChecklistItem> {
+    use ChecklistCategory::ApplicationChannel;
+
+    let mut items = Vec::new();
+    if let Some(ref cipher) = details.cipher_number {
+        let task = format!(
+            "Apply by post to the address in the posting, quoting cipher number {} on the \
+             envelope and in the letter; the employer stays anonymous until it replies",
+            cipher
+        );
+        items.push(
+            ChecklistItem::new(ApplicationChannel, "cipher", task).with_evidence(cipher.clone()),
+        );
+    } else if let Some(sentence) = find_sentence(sentences, POSTAL_TERMS) {
+        items.push(
+            ChecklistItem::new(
+                ApplicationChannel,
+                "post",
+                "Send the application by post to the address in the posting".to_string(),
+            )
+            .with_evidence(evidence(sentence)),
+        );
+    }
+
+    let url = details
+        .resolved_external_url
+        .as_ref()
+        .or(details.external_url.as_ref());
+    if let Some(url) = url {
+        let portal = details.application_domain.as_deref().unwrap_or(url);
+        items.push(
+            ChecklistItem::new(
+                ApplicationChannel,
+                "online",
+                format!("Apply online at {}", portal),
+            )
+            .with_evidence(url.clone()),
+        );
+    } else if let Some(sentence) = find_sentence(sentences, ONLINE_TERMS) {
+        items.push(
+            ChecklistItem::new(
+                ApplicationChannel,
+                "online",
+                "Apply through the employer's online form named in the posting".to_string(),
+            )
+            .with_evidence(evidence(sentence)),
+        );
+    }
+
+    let contact = details.contact_info.as_ref();
+    if let Some(email) = contact.and_then(|contact| contact.emails.first()) {
+        items.push(
+            ChecklistItem::new(
+                ApplicationChannel,
+                "email",
+                format!("Send the application by e-mail to {}", email),
+            )
+            .with_evidence(email.clone()),
+        );
+    }
+    if let Some(name) = contact.and_then(|contact| contact.names.first()) {
+        items.push(
+            ChecklistItem::new(
+                ApplicationChannel,
+                "contact",
+                format!("Address the application to {}", name),
+            )
+            .with_evidence(name.clone()),
+        );
+    }
+
+    if items.is_empty() && !details.web_url.is_empty() {
+        items.push(
+            ChecklistItem::new(
+                ApplicationChannel,
+                "jobsuche",
+                "Apply as described on the job page".to_string(),
+            )
+            .with_evidence(details.web_url.clone()),
+        );
+    }
+    items
+}
+
+/// Sentences and list items of a text
+fn sentences(text: &str) -> impl Iterator<Item = &str> {
+    text.split(['.', '!', '?', ';', '\n', '•'])
+        .map(str::trim)
+        .filter(|sentence| !sentence.is_empty())
+}
+
+/// First sentence mentioning any of the terms at the start of a word
+fn find_sentence<'a>(sentences: &[&'a str], terms: &[&str]) -> Option<&'a str> {
+    sentences.iter().copied().find(|sentence| {
+        let lower = sentence.to_lowercase();
+        terms.iter().any(|term| mentions(&lower, term))
+    })
+}
+
+/// Whether `term` occurs in `lower` at the start of a word
+fn mentions(lower: &str, term: &str) -> bool {
+    lower.match_indices(term).any(|(idx, _)| {
+        lower[..idx]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric())
+    })
+}
+
+/// Sentence shortened to [`MAX_EVIDENCE_CHARS`]
+fn evidence(sentence: &str) -> String {
+    if sentence.chars().count() <= MAX_EVIDENCE_CHARS {
+        return sentence.to_string();
+    }
+    let mut short: String = sentence.chars().take(MAX_EVIDENCE_CHARS - 1).collect();
+    short.push('…');
+    short
+}
+
+/// Lowercase ASCII key of a name ("Führerschein CE" -> "fuhrerschein_ce")
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.to_lowercase().chars() {
+        let c = match c {
+            'ä' => 'a',
+            'ö' => 'o',
+            'ü' => 'u',
+            'ß' => 's',
+            c => c,
+        };
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    slug.trim_end_matches('_').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn details(extra: serde_json::Value) -> GetJobDetailsResult {
+        let mut value = json!({
+            "reference_number": "10001-1234567890-S",
+            "web_url": "https://www.arbeitsagentur.de/jobsuche/jobdetail/10001-1234567890-S",
+            "raw_data": {}
+        });
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn ids(items: &[ChecklistItem]) -> Vec<&str> {
+        items.iter().map(|item| item.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_documents_and_details() {
+        let items = build_checklist(&details(json!({
+            "description": "Bitte senden Sie Ihren Lebenslauf und Ihre Zeugnisse \
+                            mit Ihrer Gehaltsvorstellung. Ein erweitertes Führungszeugnis \
+                            ist vorzulegen."
+        })));
+        assert_eq!(
+            ids(&items),
+            [
+                "channel:jobsuche",
+                "document:cv",
+                "document:references",
+                "document:police_clearance",
+                "detail:salary_expectation",
+            ]
+        );
+        assert_eq!(
+            items[4].evidence.as_deref(),
+            Some(
+                "Bitte senden Sie Ihren Lebenslauf und Ihre Zeugnisse mit Ihrer Gehaltsvorstellung"
+            )
+        );
+    }
+
+    #[test]
+    fn test_cipher_application() {
+        let items = build_checklist(&details(json!({
+            "cipher_number": "A 12345",
+            "description": "Schriftliche Bewerbung unter Chiffre.",
+            "contact_info": {
+                "best_effort": true,
+                "names": ["Frau Anna Müller"],
+                "emails": [],
+                "phones": []
+            }
+        })));
+        assert_eq!(ids(&items), ["channel:cipher", "channel:contact"]);
+        assert!(items[0].task.contains("A 12345"));
+    }
+
+    #[test]
+    fn test_languages_and_certifications() {
+        let items = build_checklist(&details(json!({
+            "external_url": "https://jobs.example.com/apply/1",
+            "application_domain": "jobs.example.com",
+            "language_requirements": [
+                {"language": "German", "level": "C1", "required": true},
+                {"language": "English", "level": null, "proficiency": "good", "required": false}
+            ],
+            "extracted_skills": [
+                {"name": "Rust", "category": "programming_language"},
+                {"name": "Führerschein CE", "category": "drivers_license"}
+            ]
+        })));
+        assert_eq!(
+            ids(&items),
+            [
+                "channel:online",
+                "language:german",
+                "language:english",
+                "certification:fuhrerschein_ce",
+            ]
+        );
+        assert_eq!(items[0].task, "Apply online at jobs.example.com");
+        assert!(items[1].task.contains("at level C1"));
+        assert!(!items[2].required);
+    }
+
+    #[test]
+    fn test_terms_match_at_word_start() {
+        assert!(mentions("ihre zeugnisse", "zeugnis"));
+        assert!(!mentions("ihr führungszeugnis", "zeugnis"));
+        assert!(!mentions("scv", "cv"));
+    }
+}
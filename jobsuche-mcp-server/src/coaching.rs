@@ -0,0 +1,263 @@
+//! Coaching offers for the Aktivierungs- und Vermittlungsgutschein
+//!
+//! Job seekers can receive an Aktivierungs- und Vermittlungsgutschein (AVGS)
+//! from their Agentur für Arbeit or Jobcenter, which pays for coaching by an
+//! approved provider: application training, career orientation, start-up
+//! coaching and the like. The Coachingangebote search of the Bundesagentur für
+//! Arbeit lists these offers. Its responses use the same structure as the
+//! Weiterbildungssuche and are read the same way.
+
+use crate::weiterbildung::{date, first_text, place};
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Default base URL of the Coachingangebote API
+pub const DEFAULT_COACHING_URL: &str = "https://rest.arbeitsagentur.de/infosysbub/avgs/pc/v1";
+
+/// Public API key of the Coachingangebote web application
+const API_KEY: &str = "infosysbub-avgs";
+
+/// Default search radius in km around the location
+pub const DEFAULT_COACHING_RADIUS_KM: u64 = 25;
+
+/// Default number of offers per page
+pub const DEFAULT_COACHING_OFFERS: u64 = 20;
+
+/// Maximum number of offers per page
+pub const MAX_COACHING_OFFERS: u64 = 100;
+
+/// Parameters for search_coaching_offers
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SearchCoachingOffersParams {
+    /// Topic (e.g. "Bewerbungscoaching", "Existenzgründung", "Berufliche
+    /// Orientierung")
+    pub topic: Option<String>,
+
+    /// City or postal code; without it offers from all of Germany are found
+    pub location: Option<String>,
+
+    /// Search radius in km around the location (default: 25)
+    pub radius_km: Option<u64>,
+
+    /// Offers per page (default: 20, max: 100)
+    pub page_size: Option<u64>,
+
+    /// Page number, starting at 1
+    pub page: Option<u64>,
+}
+
+/// A coaching offer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CoachingOffer {
+    /// Offer ID of the Coachingangebote search
+    pub offer_id: Option<String>,
+
+    /// Title of the offer
+    pub title: Option<String>,
+
+    /// Coaching provider
+    pub provider: Option<String>,
+
+    /// Place of the coaching ("Berlin (10115)")
+    pub location: Option<String>,
+
+    /// Format such as "Einzelcoaching", "Gruppencoaching" or "Online"
+    pub format: Option<String>,
+
+    /// Duration as stated by the provider (e.g. "8 Wochen", "40 Unterrichtseinheiten")
+    pub duration: Option<String>,
+
+    /// Next start (ISO 8601, YYYY-MM-DD); many offers start at any time
+    pub start_date: Option<String>,
+}
+
+/// Result from search_coaching_offers
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchCoachingOffersResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Total number of offers found
+    pub total_results: Option<u64>,
+
+    /// Current page number
+    pub current_page: u64,
+
+    /// Number of offers returned
+    pub offers_count: usize,
+
+    /// Offers of this page
+    pub offers: Vec<CoachingOffer>,
+}
+
+/// Convert one offer of a Coachingangebote response
+fn parse_offer(entry: &Value) -> CoachingOffer {
+    CoachingOffer {
+        offer_id: entry
+            .pointer("/angebot/id")
+            .or_else(|| entry.pointer("/id"))
+            .filter(|id| !id.is_null())
+            .map(|id| id.to_string().trim_matches('"').to_string()),
+        title: first_text(entry, &["/angebot/titel", "/titel"]),
+        provider: first_text(entry, &["/angebot/bildungsanbieter/name", "/anbieter/name"]),
+        location: place(entry, "/adresse/adresse").or_else(|| place(entry, "/adresse")),
+        format: first_text(
+            entry,
+            &[
+                "/unterrichtsform/bezeichnung",
+                "/durchfuehrungsform/bezeichnung",
+            ],
+        ),
+        duration: first_text(entry, &["/dauer/bezeichnung", "/dauer"]),
+        start_date: date(entry, "/beginn"),
+    }
+}
+
+/// Extract the offers and the total count from a response
+///
+/// The offers are the first list below `_embedded`, whatever its name.
+fn parse_offers(response: &Value) -> (Vec<CoachingOffer>, Option<u64>) {
+    let offers = response
+        .get("_embedded")
+        .and_then(Value::as_object)
+        .and_then(|embedded| embedded.values().find_map(Value::as_array))
+        .map(|entries| entries.iter().map(parse_offer).collect())
+        .unwrap_or_default();
+    let total = response
+        .pointer("/page/totalElements")
+        .and_then(Value::as_u64);
+    (offers, total)
+}
+
+/// Client for the Coachingangebote API
+#[derive(Debug, Clone)]
+pub struct CoachingClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl CoachingClient {
+    /// Create a client for the given base URL
+    pub fn new(base_url: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .context("Failed to create Coachingangebote client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Search coaching offers
+    pub async fn search(
+        &self,
+        params: &SearchCoachingOffersParams,
+    ) -> Result<SearchCoachingOffersResult> {
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params
+            .page_size
+            .unwrap_or(DEFAULT_COACHING_OFFERS)
+            .clamp(1, MAX_COACHING_OFFERS);
+
+        let mut query = vec![
+            // The API counts pages from 0
+            ("page", (page - 1).to_string()),
+            ("size", page_size.to_string()),
+        ];
+        if let Some(topic) = params
+            .topic
+            .as_deref()
+            .map(str::trim)
+            .filter(|topic| !topic.is_empty())
+        {
+            query.push(("sw", topic.to_string()));
+        }
+        if let Some(ref location) = params.location {
+            query.push(("orte", location.trim().to_string()));
+            let radius_km = params.radius_km.unwrap_or(DEFAULT_COACHING_RADIUS_KM);
+            query.push(("uk", radius_km.to_string()));
+        } else {
+            query.push(("uk", "Bundesweit".to_string()));
+        }
+
+        let url = format!("{}/angebote", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .header("X-API-Key", API_KEY)
+            .query(&query)
+            .send()
+            .await
+            .context("Coachingangebote request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("Coachingangebote responded with HTTP {}", status);
+        }
+        let body: Value = response
+            .json()
+            .await
+            .context("Invalid Coachingangebote response")?;
+
+        let (offers, total_results) = parse_offers(&body);
+        Ok(SearchCoachingOffersResult {
+            request_id: String::new(),
+            total_results,
+            current_page: page,
+            offers_count: offers.len(),
+            offers,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_offers() {
+        let response = json!({
+            "_embedded": {"angebote": [
+                {
+                    "angebot": {
+                        "id": 98765,
+                        "titel": "Bewerbungscoaching für Quereinsteiger",
+                        "bildungsanbieter": {"name": "Coaching Nord GmbH"}
+                    },
+                    "adresse": {"adresse": {"ort": "Kiel", "plz": "24103"}},
+                    "unterrichtsform": {"bezeichnung": "Einzelcoaching"},
+                    "dauer": {"bezeichnung": "6 Wochen"},
+                    "beginn": "2026-02-01"
+                },
+                {"id": "A-1", "titel": "Existenzgründung", "adresse": {"ort": "Kiel"}}
+            ]},
+            "page": {"totalElements": 12}
+        });
+
+        let (offers, total) = parse_offers(&response);
+        assert_eq!(total, Some(12));
+        assert_eq!(
+            offers[0],
+            CoachingOffer {
+                offer_id: Some("98765".to_string()),
+                title: Some("Bewerbungscoaching für Quereinsteiger".to_string()),
+                provider: Some("Coaching Nord GmbH".to_string()),
+                location: Some("Kiel (24103)".to_string()),
+                format: Some("Einzelcoaching".to_string()),
+                duration: Some("6 Wochen".to_string()),
+                start_date: Some("2026-02-01".to_string()),
+            }
+        );
+        assert_eq!(offers[1].offer_id.as_deref(), Some("A-1"));
+        assert_eq!(offers[1].title.as_deref(), Some("Existenzgründung"));
+        assert_eq!(offers[1].location.as_deref(), Some("Kiel"));
+        assert_eq!(parse_offers(&json!({"_embedded": {}})), (Vec::new(), None));
+    }
+}
@@ -0,0 +1,447 @@
+//! Commute times from a routing service
+//!
+//! Distance from the search location says little about how long it takes to
+//! get to work. With a routing service and a home location configured,
+//! search_jobs_with_details asks the service for the travel time from home to
+//! each job's work location and attaches it as `commute_minutes`;
+//! `max_commute_minutes` drops jobs that take longer.
+//!
+//! Three services are supported:
+//! - OSRM (driving only, coordinates only)
+//! - Valhalla (driving and public transport, coordinates only)
+//! - Google Distance Matrix (driving and public transport, coordinates or
+//!   addresses, needs an API key)
+//!
+//! Job locations are taken from the coordinates of the first work location,
+//! or for Google from its address; with geocoding enabled, addresses are
+//! resolved to coordinates for OSRM and Valhalla. Routing is an optional extra:
+//! jobs whose commute cannot be computed keep `commute_minutes` empty.
+
+use crate::geocoding::{coordinates_in, Coordinates};
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Default base URL of the public OSRM demo server
+pub const DEFAULT_OSRM_URL: &str = "https://router.project-osrm.org";
+
+/// Default base URL of the public Valhalla server of openstreetmap.de
+pub const DEFAULT_VALHALLA_URL: &str = "https://valhalla1.openstreetmap.de";
+
+/// Default base URL of the Google Maps APIs
+pub const DEFAULT_GOOGLE_URL: &str = "https://maps.googleapis.com";
+
+/// Routing service computing commute times
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoutingProvider {
+    /// OSRM route service
+    Osrm,
+    /// Valhalla route service
+    Valhalla,
+    /// Google Distance Matrix API
+    Google,
+}
+
+impl RoutingProvider {
+    /// Base URL of the public instance of the service
+    pub fn default_url(self) -> &'static str {
+        match self {
+            Self::Osrm => DEFAULT_OSRM_URL,
+            Self::Valhalla => DEFAULT_VALHALLA_URL,
+            Self::Google => DEFAULT_GOOGLE_URL,
+        }
+    }
+}
+
+impl FromStr for RoutingProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "osrm" => Ok(Self::Osrm),
+            "valhalla" => Ok(Self::Valhalla),
+            "google" => Ok(Self::Google),
+            other => anyhow::bail!(
+                "Unknown routing provider '{}' (expected osrm, valhalla or google)",
+                other
+            ),
+        }
+    }
+}
+
+/// How the commute is travelled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CommuteMode {
+    /// By car
+    #[default]
+    Driving,
+    /// By public transport
+    Transit,
+}
+
+impl fmt::Display for CommuteMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Driving => "driving",
+            Self::Transit => "transit",
+        })
+    }
+}
+
+/// Start or end of a commute
+#[derive(Debug, Clone, PartialEq)]
+pub enum Place {
+    /// Latitude and longitude
+    Coordinates(Coordinates),
+    /// Postal address (only Google routes to these; see [`crate::geocoding`])
+    Address(String),
+}
+
+impl Place {
+    /// Parse "lat,lon" as coordinates and anything else as an address
+    pub fn parse(value: &str) -> Self {
+        let coordinates = value.split_once(',').and_then(|(lat, lon)| {
+            let lat: f64 = lat.trim().parse().ok()?;
+            let lon: f64 = lon.trim().parse().ok()?;
+            ((-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon))
+                .then_some(Self::Coordinates(Coordinates { lat, lon }))
+        });
+        coordinates.unwrap_or_else(|| Self::Address(value.trim().to_string()))
+    }
+
+    /// Work location of a job from its raw API data: the coordinates of the
+    /// first work location, or its address if it has none
+    pub fn of_job(raw_data: &Value) -> Option<Self> {
+        let location = raw_data.pointer("/arbeitsorte/0")?;
+        if let Some(coordinates) = coordinates_in(location) {
+            return Some(Self::Coordinates(coordinates));
+        }
+
+        let address = location.get("adresse")?;
+        let part = |name: &str| {
+            address
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|part| !part.is_empty())
+        };
+        let town = [part("plz"), part("ort")]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let parts: Vec<&str> = [part("strasse"), Some(town.as_str())]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.is_empty())
+            .collect();
+        if parts.is_empty() {
+            return None;
+        }
+        Some(Self::Address(format!("{}, Deutschland", parts.join(", "))))
+    }
+
+    /// Latitude and longitude, if the place is given as coordinates
+    pub fn coordinates(&self) -> Result<Coordinates> {
+        match self {
+            Self::Coordinates(coordinates) => Ok(*coordinates),
+            Self::Address(address) => anyhow::bail!(
+                "'{}' has no coordinates; OSRM and Valhalla need latitude and longitude",
+                address
+            ),
+        }
+    }
+
+    /// "lat,lon" or the address, as the Distance Matrix API expects
+    fn google_query(&self) -> String {
+        match self {
+            Self::Coordinates(Coordinates { lat, lon }) => format!("{},{}", lat, lon),
+            Self::Address(address) => address.clone(),
+        }
+    }
+}
+
+/// Duration in seconds of the first route of an OSRM response
+fn osrm_duration(body: &Value) -> Result<f64> {
+    let code = body.get("code").and_then(Value::as_str).unwrap_or_default();
+    if code != "Ok" {
+        anyhow::bail!("OSRM found no route ({})", code);
+    }
+    body.pointer("/routes/0/duration")
+        .and_then(Value::as_f64)
+        .context("OSRM response has no route duration")
+}
+
+/// Duration in seconds of the trip of a Valhalla response
+fn valhalla_duration(body: &Value) -> Result<f64> {
+    if let Some(message) = body.get("error").and_then(Value::as_str) {
+        anyhow::bail!("Valhalla found no route ({})", message);
+    }
+    body.pointer("/trip/summary/time")
+        .and_then(Value::as_f64)
+        .context("Valhalla response has no trip time")
+}
+
+/// Duration in seconds of the single element of a Distance Matrix response
+fn google_duration(body: &Value) -> Result<f64> {
+    let status = body
+        .get("status")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if status != "OK" {
+        let message = body
+            .get("error_message")
+            .and_then(Value::as_str)
+            .unwrap_or(status);
+        anyhow::bail!("Distance Matrix request failed: {}", message);
+    }
+    let element = body
+        .pointer("/rows/0/elements/0")
+        .context("Distance Matrix response has no element")?;
+    let status = element
+        .get("status")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if status != "OK" {
+        anyhow::bail!("Distance Matrix found no route ({})", status);
+    }
+    element
+        .pointer("/duration/value")
+        .and_then(Value::as_f64)
+        .context("Distance Matrix response has no duration")
+}
+
+/// Whole minutes of a duration in seconds
+fn to_minutes(seconds: f64) -> u32 {
+    (seconds / 60.0).round().max(0.0) as u32
+}
+
+/// Client of the configured routing service
+#[derive(Debug)]
+pub struct RoutingClient {
+    client: reqwest::Client,
+    provider: RoutingProvider,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl RoutingClient {
+    /// Create a client for a provider, using its public instance unless a
+    /// base URL is given
+    pub fn new(
+        provider: RoutingProvider,
+        base_url: Option<&str>,
+        api_key: Option<String>,
+    ) -> Result<Self> {
+        if provider == RoutingProvider::Google && api_key.is_none() {
+            anyhow::bail!("The google routing provider requires JOBSUCHE_ROUTING_API_KEY");
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .context("Failed to create routing client")?;
+
+        Ok(Self {
+            client,
+            provider,
+            base_url: base_url
+                .unwrap_or(provider.default_url())
+                .trim_end_matches('/')
+                .to_string(),
+            api_key,
+        })
+    }
+
+    /// Routing service in use
+    pub fn provider(&self) -> RoutingProvider {
+        self.provider
+    }
+
+    /// Whether the provider only routes between coordinates
+    pub fn needs_coordinates(&self) -> bool {
+        self.provider != RoutingProvider::Google
+    }
+
+    /// Travel time in minutes between two places
+    pub async fn commute_minutes(
+        &self,
+        from: &Place,
+        to: &Place,
+        mode: CommuteMode,
+    ) -> Result<u32> {
+        let seconds = match self.provider {
+            RoutingProvider::Osrm => self.osrm(from, to, mode).await?,
+            RoutingProvider::Valhalla => self.valhalla(from, to, mode).await?,
+            RoutingProvider::Google => self.google(from, to, mode).await?,
+        };
+        Ok(to_minutes(seconds))
+    }
+
+    async fn osrm(&self, from: &Place, to: &Place, mode: CommuteMode) -> Result<f64> {
+        if mode == CommuteMode::Transit {
+            anyhow::bail!("OSRM does not route public transport; use valhalla or google");
+        }
+        let (from, to) = (from.coordinates()?, to.coordinates()?);
+        let url = format!(
+            "{}/route/v1/driving/{},{};{},{}?overview=false",
+            self.base_url, from.lon, from.lat, to.lon, to.lat
+        );
+        let body = self.send(self.client.get(&url)).await?;
+        osrm_duration(&body)
+    }
+
+    async fn valhalla(&self, from: &Place, to: &Place, mode: CommuteMode) -> Result<f64> {
+        let (from, to) = (from.coordinates()?, to.coordinates()?);
+        let costing = match mode {
+            CommuteMode::Driving => "auto",
+            CommuteMode::Transit => "multimodal",
+        };
+        let request = json!({
+            "locations": [from, to],
+            "costing": costing,
+        });
+        let url = format!("{}/route", self.base_url);
+        let body = self.send(self.client.post(&url).json(&request)).await?;
+        valhalla_duration(&body)
+    }
+
+    async fn google(&self, from: &Place, to: &Place, mode: CommuteMode) -> Result<f64> {
+        let url = format!("{}/maps/api/distancematrix/json", self.base_url);
+        let mut query = vec![
+            ("origins", from.google_query()),
+            ("destinations", to.google_query()),
+            ("mode", mode.to_string()),
+            ("key", self.api_key.clone().unwrap_or_default()),
+        ];
+        if mode == CommuteMode::Transit {
+            query.push(("departure_time", "now".to_string()));
+        }
+        let body = self.send(self.client.get(&url).query(&query)).await?;
+        google_duration(&body)
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        let response = request
+            .send()
+            .await
+            .context("Failed to reach the routing service")?;
+        // Valhalla explains unroutable locations in the body of a 400
+        if !response.status().is_success() && self.provider != RoutingProvider::Valhalla {
+            anyhow::bail!(
+                "The routing service responded with HTTP {}",
+                response.status()
+            );
+        }
+        response
+            .json()
+            .await
+            .context("Failed to parse the routing service response")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_provider() {
+        assert_eq!(
+            " OSRM ".parse::<RoutingProvider>().unwrap(),
+            RoutingProvider::Osrm
+        );
+        assert_eq!(
+            "google".parse::<RoutingProvider>().unwrap(),
+            RoutingProvider::Google
+        );
+        assert!("here".parse::<RoutingProvider>().is_err());
+    }
+
+    #[test]
+    fn test_parse_place() {
+        assert_eq!(
+            Place::parse("51.2562, 7.1508"),
+            Place::Coordinates(Coordinates {
+                lat: 51.2562,
+                lon: 7.1508
+            })
+        );
+        assert_eq!(
+            Place::parse("Friedrich-Engels-Allee 1, 42103 Wuppertal"),
+            Place::Address("Friedrich-Engels-Allee 1, 42103 Wuppertal".to_string())
+        );
+        assert!(matches!(Place::parse("123,456"), Place::Address(_)));
+    }
+
+    #[test]
+    fn test_place_of_job() {
+        let raw = json!({"arbeitsorte": [{
+            "adresse": {"plz": "42103", "ort": "Wuppertal", "strasse": "Neumarkt 10"},
+            "koordinaten": {"lat": 51.2562, "lon": 7.1508}
+        }]});
+        assert_eq!(
+            Place::of_job(&raw),
+            Some(Place::Coordinates(Coordinates {
+                lat: 51.2562,
+                lon: 7.1508
+            }))
+        );
+
+        let raw = json!({"arbeitsorte": [{
+            "adresse": {"plz": "42103", "ort": "Wuppertal", "strasse": "Neumarkt 10"}
+        }]});
+        assert_eq!(
+            Place::of_job(&raw),
+            Some(Place::Address(
+                "Neumarkt 10, 42103 Wuppertal, Deutschland".to_string()
+            ))
+        );
+
+        assert_eq!(Place::of_job(&json!({"arbeitsorte": []})), None);
+    }
+
+    #[test]
+    fn test_durations() {
+        let osrm = json!({"code": "Ok", "routes": [{"duration": 1712.4, "distance": 30512.0}]});
+        assert_eq!(to_minutes(osrm_duration(&osrm).unwrap()), 29);
+        assert!(osrm_duration(&json!({"code": "NoRoute"})).is_err());
+
+        let valhalla = json!({"trip": {"summary": {"time": 2400.0, "length": 25.1}}});
+        assert_eq!(to_minutes(valhalla_duration(&valhalla).unwrap()), 40);
+        let error = json!({"error_code": 442, "error": "No path could be found for input"});
+        assert!(valhalla_duration(&error)
+            .unwrap_err()
+            .to_string()
+            .contains("No path"));
+
+        let google = json!({"status": "OK", "rows": [{"elements": [
+            {"status": "OK", "duration": {"value": 3150, "text": "53 mins"}}
+        ]}]});
+        assert_eq!(to_minutes(google_duration(&google).unwrap()), 53);
+        let no_route =
+            json!({"status": "OK", "rows": [{"elements": [{"status": "ZERO_RESULTS"}]}]});
+        assert!(google_duration(&no_route).is_err());
+        let denied = json!({"status": "REQUEST_DENIED", "error_message": "Invalid key"});
+        assert!(google_duration(&denied)
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid key"));
+    }
+
+    #[test]
+    fn test_new_client() {
+        let client = RoutingClient::new(RoutingProvider::Osrm, None, None).unwrap();
+        assert!(client.needs_coordinates());
+        assert!(RoutingClient::new(RoutingProvider::Google, None, None).is_err());
+        let client =
+            RoutingClient::new(RoutingProvider::Google, None, Some("key".to_string())).unwrap();
+        assert_eq!(client.base_url, DEFAULT_GOOGLE_URL);
+        assert!(!client.needs_coordinates());
+    }
+}
@@ -0,0 +1,558 @@
+//! Side-by-side comparison of jobs
+//!
+//! Normalizes the details of a few postings into one structure per job and a
+//! table with one row per criterion, so hosts can render a comparison without
+//! the model assembling one from free text. Salaries are converted to yearly
+//! amounts to be comparable across hourly, monthly and yearly figures.
+
+use crate::description::{clean_description, DescriptionFormat};
+use crate::salary::SalaryPeriod;
+use crate::GetJobDetailsResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Minimum number of jobs in a comparison
+pub const MIN_COMPARE_JOBS: usize = 2;
+
+/// Maximum number of jobs in a comparison
+pub const MAX_COMPARE_JOBS: usize = 5;
+
+/// Working hours per year (40 h on 52 weeks) for hourly salaries
+const HOURS_PER_YEAR: f64 = 2080.0;
+
+/// Working days per year for daily salaries
+const DAYS_PER_YEAR: f64 = 220.0;
+
+/// Phrases saying that working from home is possible
+const REMOTE_PHRASES: &[&str] = &[
+    "homeoffice",
+    "home-office",
+    "home office",
+    "remote",
+    "mobiles arbeiten",
+    "mobile arbeit",
+    "mobilem arbeiten",
+    "telearbeit",
+    "work from home",
+];
+
+/// Phrases for positions without an office
+const FULLY_REMOTE_PHRASES: &[&str] = &[
+    "100% remote",
+    "100 % remote",
+    "fully remote",
+    "full remote",
+    "vollständig remote",
+    "komplett remote",
+    "ausschließlich remote",
+    "remote-first",
+    "remote first",
+    "100% homeoffice",
+    "100 % homeoffice",
+];
+
+/// Phrases ruling out working from home
+const NO_REMOTE_PHRASES: &[&str] = &[
+    "kein homeoffice",
+    "kein home-office",
+    "kein home office",
+    "keine homeoffice",
+    "kein remote",
+    "keine remote",
+    "no remote",
+    "not remote",
+];
+
+/// Parameters for compare_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CompareJobsParams {
+    /// Reference numbers of the jobs to compare (2 to 5)
+    pub reference_numbers: Vec<String>,
+}
+
+/// Remote work as stated in the description
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RemoteFlags {
+    /// Home office or remote work is offered
+    pub remote_possible: bool,
+
+    /// The position is fully remote
+    pub fully_remote: bool,
+}
+
+impl RemoteFlags {
+    /// Table value: "fully remote", "possible" or "not mentioned"
+    pub fn label(self) -> &'static str {
+        if self.fully_remote {
+            "fully remote"
+        } else if self.remote_possible {
+            "possible"
+        } else {
+            "not mentioned"
+        }
+    }
+}
+
+/// Normalized values of one compared job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ComparedJob {
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Job title
+    pub title: Option<String>,
+
+    /// Employer name
+    pub employer: Option<String>,
+
+    /// Location
+    pub location: Option<String>,
+
+    /// Salary text as stated
+    pub salary: Option<String>,
+
+    /// Lower bound of the salary per year
+    pub annual_salary_min: Option<f64>,
+
+    /// Upper bound of the salary per year
+    pub annual_salary_max: Option<f64>,
+
+    /// Currency of the salary
+    pub salary_currency: Option<String>,
+
+    /// Collective agreement and pay grade (e.g. "TVöD E13")
+    pub tariff: Option<String>,
+
+    /// Employment type
+    pub employment_type: Option<String>,
+
+    /// Full-time employment
+    pub fulltime: Option<bool>,
+
+    /// Contract type
+    pub contract_type: Option<String>,
+
+    /// Contract duration
+    pub contract_duration: Option<String>,
+
+    /// Start date (ISO 8601)
+    pub start_date: Option<String>,
+
+    /// Temporary employment agency (Zeitarbeit)
+    pub is_temp_agency: Option<bool>,
+
+    /// Remote work
+    pub remote: RemoteFlags,
+
+    /// Skills asked for (see get_job_details)
+    pub skills: Vec<String>,
+
+    /// Skills only this job asks for among the compared ones
+    pub unique_skills: Vec<String>,
+
+    /// Spoken languages asked for (e.g. "German C1")
+    pub languages: Vec<String>,
+
+    /// First publication date (ISO 8601)
+    pub first_published: Option<String>,
+
+    /// Job page on arbeitsagentur.de
+    pub web_url: String,
+}
+
+/// One criterion across all compared jobs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ComparisonRow {
+    /// Criterion key (e.g. "salary", "remote")
+    pub criterion: String,
+
+    /// Human-readable name of the criterion
+    pub label: String,
+
+    /// Value per job, in the order of `jobs`; null if unknown
+    pub values: Vec<Option<String>>,
+
+    /// True if the known values are not all the same
+    pub differs: bool,
+}
+
+/// Result from compare_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompareJobsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Compared jobs, in the requested order
+    pub jobs: Vec<ComparedJob>,
+
+    /// Comparison table, one row per criterion
+    pub rows: Vec<ComparisonRow>,
+
+    /// Skills all compared jobs ask for
+    pub common_skills: Vec<String>,
+}
+
+/// Trim the requested reference numbers and drop empty ones and duplicates
+pub fn unique_reference_numbers(reference_numbers: &[String]) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::new();
+    for reference_number in reference_numbers {
+        let reference_number = reference_number.trim();
+        if !reference_number.is_empty() && !unique.iter().any(|r| r == reference_number) {
+            unique.push(reference_number.to_string());
+        }
+    }
+    unique
+}
+
+/// Deduplicate the requested reference numbers and check their count
+pub fn compare_reference_numbers(reference_numbers: &[String]) -> anyhow::Result<Vec<String>> {
+    let unique = unique_reference_numbers(reference_numbers);
+    anyhow::ensure!(
+        (MIN_COMPARE_JOBS..=MAX_COMPARE_JOBS).contains(&unique.len()),
+        "Compare {} to {} different jobs, got {}",
+        MIN_COMPARE_JOBS,
+        MAX_COMPARE_JOBS,
+        unique.len()
+    );
+    Ok(unique)
+}
+
+/// Detect remote work offers in a description
+pub fn remote_flags(text: &str) -> RemoteFlags {
+    let text = text.to_lowercase();
+    let contains_any = |phrases: &[&str]| phrases.iter().any(|phrase| text.contains(phrase));
+    if contains_any(NO_REMOTE_PHRASES) {
+        return RemoteFlags::default();
+    }
+    let fully_remote = contains_any(FULLY_REMOTE_PHRASES);
+    RemoteFlags {
+        remote_possible: fully_remote || contains_any(REMOTE_PHRASES),
+        fully_remote,
+    }
+}
+
+/// Convert an amount to a yearly amount
+pub fn annual_amount(amount: f64, period: SalaryPeriod) -> f64 {
+    match period {
+        SalaryPeriod::Hour => amount * HOURS_PER_YEAR,
+        SalaryPeriod::Day => amount * DAYS_PER_YEAR,
+        SalaryPeriod::Week => amount * 52.0,
+        SalaryPeriod::Month => amount * 12.0,
+        SalaryPeriod::Year => amount,
+    }
+}
+
+impl ComparedJob {
+    /// Normalize the details of a job
+    pub fn from_details(details: &GetJobDetailsResult) -> Self {
+        let description = details
+            .description
+            .as_deref()
+            .map(|text| clean_description(text, DescriptionFormat::Text))
+            .unwrap_or_default();
+        let parsed = details.salary_parsed.as_ref();
+        // Amounts without a period are not comparable
+        let annual = |amount: Option<f64>| {
+            let period = parsed?.period?;
+            amount.map(|amount| annual_amount(amount, period))
+        };
+        let tariff = parsed.and_then(|salary| match (&salary.tariff, &salary.pay_grade) {
+            (Some(tariff), Some(grade)) => Some(format!("{} {}", tariff, grade)),
+            (Some(tariff), None) => Some(tariff.clone()),
+            (None, grade) => grade.clone(),
+        });
+        let languages = details
+            .language_requirements
+            .iter()
+            .map(|requirement| {
+                let level = requirement
+                    .level
+                    .as_deref()
+                    .or(requirement.proficiency.as_deref());
+                let mut language = match level {
+                    Some(level) => format!("{} {}", requirement.language, level),
+                    None => requirement.language.clone(),
+                };
+                if !requirement.required {
+                    language.push_str(" (optional)");
+                }
+                language
+            })
+            .collect();
+
+        Self {
+            reference_number: details.reference_number.clone(),
+            title: details.title.clone(),
+            employer: details.employer.clone(),
+            location: details.location.clone(),
+            salary: details.salary.clone(),
+            annual_salary_min: annual(parsed.and_then(|salary| salary.min)),
+            annual_salary_max: annual(parsed.and_then(|salary| salary.max)),
+            salary_currency: parsed.and_then(|salary| salary.currency.clone()),
+            tariff,
+            employment_type: details.employment_type.clone(),
+            fulltime: details.fulltime,
+            contract_type: details.contract_type.clone(),
+            contract_duration: details.contract_duration.clone(),
+            start_date: details.start_date.clone(),
+            is_temp_agency: details.is_temp_agency,
+            remote: remote_flags(&format!(
+                "{}\n{}",
+                details.title.as_deref().unwrap_or_default(),
+                description
+            )),
+            skills: details
+                .extracted_skills
+                .iter()
+                .map(|skill| skill.name.clone())
+                .collect(),
+            unique_skills: Vec::new(),
+            languages,
+            first_published: details.first_published.clone(),
+            web_url: details.web_url.clone(),
+        }
+    }
+
+    /// Permanent or fixed-term as stated in the contract type or duration
+    pub fn is_permanent(&self) -> Option<bool> {
+        [&self.contract_type, &self.contract_duration]
+            .into_iter()
+            .flatten()
+            .find_map(|text| {
+                let text = text.to_lowercase();
+                if text.contains("unbefristet")
+                    || text.contains("nicht befristet")
+                    || text.contains("permanent")
+                {
+                    Some(true)
+                } else if text.contains("befristet") || text.contains("fixed-term") {
+                    Some(false)
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Yearly salary range as text, e.g. "45000-55000 EUR"
+    fn annual_salary(&self) -> Option<String> {
+        let range = match (self.annual_salary_min, self.annual_salary_max) {
+            (Some(min), Some(max)) if min != max => format!("{:.0}-{:.0}", min, max),
+            (Some(amount), _) | (None, Some(amount)) => format!("{:.0}", amount),
+            (None, None) => return None,
+        };
+        Some(match self.salary_currency {
+            Some(ref currency) => format!("{} {}", range, currency),
+            None => range,
+        })
+    }
+}
+
+fn yes_no(value: Option<bool>) -> Option<String> {
+    value.map(|value| if value { "yes" } else { "no" }.to_string())
+}
+
+fn list(values: &[String]) -> Option<String> {
+    (!values.is_empty()).then(|| values.join(", "))
+}
+
+/// Skills every job asks for; fills in the `unique_skills` of each job
+pub fn compare_skills(jobs: &mut [ComparedJob]) -> Vec<String> {
+    let common: Vec<String> = match jobs.first() {
+        Some(first) => first
+            .skills
+            .iter()
+            .filter(|skill| jobs.iter().all(|job| job.skills.contains(skill)))
+            .cloned()
+            .collect(),
+        None => Vec::new(),
+    };
+    for index in 0..jobs.len() {
+        let unique = jobs[index]
+            .skills
+            .iter()
+            .filter(|skill| {
+                jobs.iter()
+                    .enumerate()
+                    .all(|(other, job)| other == index || !job.skills.contains(skill))
+            })
+            .cloned()
+            .collect();
+        jobs[index].unique_skills = unique;
+    }
+    common
+}
+
+/// Value of a criterion for one job
+type CriterionValue = fn(&ComparedJob) -> Option<String>;
+
+/// The comparison table
+pub fn comparison_rows(jobs: &[ComparedJob]) -> Vec<ComparisonRow> {
+    let criteria: &[(&str, &str, CriterionValue)] = &[
+        ("title", "Title", |job| job.title.clone()),
+        ("employer", "Employer", |job| job.employer.clone()),
+        ("location", "Location", |job| job.location.clone()),
+        ("salary", "Salary", |job| job.salary.clone()),
+        (
+            "annual_salary",
+            "Salary per year",
+            ComparedJob::annual_salary,
+        ),
+        ("tariff", "Collective agreement", |job| job.tariff.clone()),
+        ("employment_type", "Employment type", |job| {
+            job.employment_type.clone()
+        }),
+        ("contract_type", "Contract", |job| job.contract_type.clone()),
+        ("contract_duration", "Contract duration", |job| {
+            job.contract_duration.clone()
+        }),
+        ("start_date", "Start", |job| job.start_date.clone()),
+        ("remote", "Remote work", |job| {
+            Some(job.remote.label().to_string())
+        }),
+        ("temp_agency", "Temporary agency", |job| {
+            yes_no(job.is_temp_agency)
+        }),
+        ("skills", "Skills", |job| list(&job.skills)),
+        ("unique_skills", "Skills only here", |job| {
+            list(&job.unique_skills)
+        }),
+        ("languages", "Languages", |job| list(&job.languages)),
+        ("first_published", "Published", |job| {
+            job.first_published.clone()
+        }),
+    ];
+
+    criteria
+        .iter()
+        .map(|(criterion, label, value)| {
+            let values: Vec<Option<String>> = jobs.iter().map(value).collect();
+            let mut known = values.iter().flatten();
+            let differs = match known.next() {
+                Some(first) => known.any(|value| value != first),
+                None => false,
+            };
+            ComparisonRow {
+                criterion: criterion.to_string(),
+                label: label.to_string(),
+                values,
+                differs,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(reference_number: &str, extra: serde_json::Value) -> GetJobDetailsResult {
+        let mut value = serde_json::json!({
+            "reference_number": reference_number,
+            "raw_data": {}
+        });
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_compare_reference_numbers() {
+        let refs = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            compare_reference_numbers(&refs(&["A", " B ", "A"])).unwrap(),
+            vec!["A", "B"]
+        );
+        assert!(compare_reference_numbers(&refs(&["A", "A"])).is_err());
+        assert!(compare_reference_numbers(&refs(&["A", "B", "C", "D", "E", "F"])).is_err());
+    }
+
+    #[test]
+    fn test_remote_flags() {
+        assert_eq!(remote_flags("Arbeit vor Ort"), RemoteFlags::default());
+        assert!(remote_flags("2 Tage Homeoffice pro Woche").remote_possible);
+        assert!(!remote_flags("2 Tage Homeoffice").fully_remote);
+        assert_eq!(
+            remote_flags("Die Stelle ist 100% remote"),
+            RemoteFlags {
+                remote_possible: true,
+                fully_remote: true
+            }
+        );
+        assert!(!remote_flags("Kein Homeoffice möglich").remote_possible);
+    }
+
+    #[test]
+    fn test_annual_salary() {
+        let job = ComparedJob::from_details(&details(
+            "A",
+            serde_json::json!({
+                "salary": "3.500 - 4.000 EUR im Monat",
+                "salary_parsed": {"min": 3500.0, "max": 4000.0, "currency": "EUR", "period": "month"}
+            }),
+        ));
+        assert_eq!(job.annual_salary_min, Some(42000.0));
+        assert_eq!(job.annual_salary_max, Some(48000.0));
+        assert_eq!(job.annual_salary(), Some("42000-48000 EUR".to_string()));
+
+        let job = ComparedJob::from_details(&details(
+            "B",
+            serde_json::json!({"salary_parsed": {"min": 3500.0, "max": 3500.0}}),
+        ));
+        assert_eq!(job.annual_salary_min, None);
+        assert_eq!(job.annual_salary(), None);
+    }
+
+    #[test]
+    fn test_skills_and_rows() {
+        let mut jobs = vec![
+            ComparedJob::from_details(&details(
+                "A",
+                serde_json::json!({
+                    "title": "Rust Developer",
+                    "contract_type": "Unbefristet",
+                    "description": "Homeoffice möglich",
+                    "extracted_skills": [
+                        {"name": "Rust", "category": "programming_language"},
+                        {"name": "Docker", "category": "tool"}
+                    ],
+                    "language_requirements": [
+                        {"language": "German", "level": "C1", "required": true}
+                    ]
+                }),
+            )),
+            ComparedJob::from_details(&details(
+                "B",
+                serde_json::json!({
+                    "title": "Backend Developer",
+                    "contract_type": "Unbefristet",
+                    "extracted_skills": [{"name": "Rust", "category": "programming_language"}]
+                }),
+            )),
+        ];
+
+        assert_eq!(compare_skills(&mut jobs), vec!["Rust"]);
+        assert_eq!(jobs[0].unique_skills, vec!["Docker"]);
+        assert!(jobs[1].unique_skills.is_empty());
+
+        let rows = comparison_rows(&jobs);
+        let row = |criterion: &str| rows.iter().find(|row| row.criterion == criterion).unwrap();
+        assert!(row("title").differs);
+        assert!(!row("contract_type").differs);
+        assert!(!row("salary").differs);
+        assert_eq!(
+            row("remote").values,
+            vec![
+                Some("possible".to_string()),
+                Some("not mentioned".to_string())
+            ]
+        );
+        assert_eq!(
+            row("languages").values,
+            vec![Some("German C1".to_string()), None]
+        );
+    }
+}
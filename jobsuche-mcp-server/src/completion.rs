@@ -0,0 +1,281 @@
+//! Argument completion
+//!
+//! Implements the values for MCP `completion/complete` requests so interactive
+//! hosts can offer autocomplete for enum-like tool arguments and German city
+//! names instead of letting users guess the accepted strings.
+
+use crate::freshness::FRESHNESS_VALUES;
+use crate::sort::SORT_BY_VALUES;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of values per completion response (MCP limit)
+pub const MAX_COMPLETION_VALUES: usize = 100;
+
+/// Accepted values for `employment_type`
+pub const EMPLOYMENT_TYPES: &[&str] = &["fulltime", "parttime", "mini_job", "home_office", "shift"];
+
+/// Accepted values for `contract_type`
+pub const CONTRACT_TYPES: &[&str] = &["permanent", "temporary"];
+
+/// Commonly used values for `published_since_days`
+const PUBLISHED_SINCE_DAYS: &[&str] = &["1", "3", "7", "14", "30", "60", "100"];
+
+/// Commonly used values for `radius_km`
+const RADIUS_KM: &[&str] = &["5", "10", "25", "50", "100", "200"];
+
+/// Larger German cities offered for `location`
+pub const GERMAN_CITIES: &[&str] = &[
+    "Aachen",
+    "Augsburg",
+    "Bergisch Gladbach",
+    "Berlin",
+    "Bielefeld",
+    "Bochum",
+    "Bonn",
+    "Bottrop",
+    "Braunschweig",
+    "Bremen",
+    "Bremerhaven",
+    "Chemnitz",
+    "Cottbus",
+    "Darmstadt",
+    "Deutschland",
+    "Dortmund",
+    "Dresden",
+    "Duisburg",
+    "Düsseldorf",
+    "Erfurt",
+    "Erlangen",
+    "Essen",
+    "Frankfurt am Main",
+    "Freiburg im Breisgau",
+    "Fürth",
+    "Gelsenkirchen",
+    "Göttingen",
+    "Hagen",
+    "Halle (Saale)",
+    "Hamburg",
+    "Hamm",
+    "Hannover",
+    "Heidelberg",
+    "Heilbronn",
+    "Herne",
+    "Hildesheim",
+    "Ingolstadt",
+    "Jena",
+    "Karlsruhe",
+    "Kassel",
+    "Kiel",
+    "Koblenz",
+    "Köln",
+    "Krefeld",
+    "Leipzig",
+    "Leverkusen",
+    "Lübeck",
+    "Ludwigshafen am Rhein",
+    "Magdeburg",
+    "Mainz",
+    "Mannheim",
+    "Moers",
+    "Mönchengladbach",
+    "Mülheim an der Ruhr",
+    "München",
+    "Münster",
+    "Neuss",
+    "Nürnberg",
+    "Oberhausen",
+    "Offenbach am Main",
+    "Oldenburg",
+    "Osnabrück",
+    "Paderborn",
+    "Pforzheim",
+    "Potsdam",
+    "Recklinghausen",
+    "Regensburg",
+    "Remscheid",
+    "Reutlingen",
+    "Rostock",
+    "Saarbrücken",
+    "Salzgitter",
+    "Schwerin",
+    "Siegen",
+    "Solingen",
+    "Stuttgart",
+    "Trier",
+    "Ulm",
+    "Wiesbaden",
+    "Wolfsburg",
+    "Würzburg",
+    "Wuppertal",
+];
+
+/// Parameters of an MCP `completion/complete` request
+///
+/// Only the argument is used; the same names complete to the same values in
+/// every tool, prompt and resource.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompleteRequest {
+    /// Argument being completed
+    pub argument: CompletionArgument,
+}
+
+/// Argument of a completion request
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompletionArgument {
+    /// Argument name
+    pub name: String,
+
+    /// Value typed so far
+    pub value: String,
+}
+
+/// Result of a completion request (the `completion` object of the MCP response)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Completion {
+    /// Suggested values, at most [`MAX_COMPLETION_VALUES`]
+    pub values: Vec<String>,
+
+    /// Total number of matching values
+    pub total: usize,
+
+    /// More values matched than are returned
+    pub has_more: bool,
+}
+
+impl Completion {
+    fn from_matches(matches: Vec<String>) -> Self {
+        let total = matches.len();
+        let values: Vec<String> = matches.into_iter().take(MAX_COMPLETION_VALUES).collect();
+        Self {
+            has_more: total > values.len(),
+            values,
+            total,
+        }
+    }
+}
+
+/// Complete the value of a tool argument
+///
+/// Unknown arguments yield an empty completion.
+pub fn complete_argument(argument_name: &str, value: &str) -> Completion {
+    let candidates: &[&str] = match argument_name {
+        "employment_type" => EMPLOYMENT_TYPES,
+        "contract_type" => CONTRACT_TYPES,
+        "published_since_days" => PUBLISHED_SINCE_DAYS,
+        "freshness" => FRESHNESS_VALUES,
+        "sort_by" => SORT_BY_VALUES,
+        "radius_km" => RADIUS_KM,
+        "location" => GERMAN_CITIES,
+        _ => &[],
+    };
+
+    let matches = candidates
+        .iter()
+        .filter(|candidate| matches_prefix(candidate, value))
+        .map(|candidate| candidate.to_string())
+        .collect();
+
+    Completion::from_matches(matches)
+}
+
+/// Case- and umlaut-insensitive prefix match
+///
+/// "munch", "muench" and "Münch" all match "München".
+fn matches_prefix(candidate: &str, input: &str) -> bool {
+    let input = input.trim().to_lowercase();
+    let candidate = candidate.to_lowercase();
+
+    fold_umlauts(&candidate, false).starts_with(&fold_umlauts(&input, false))
+        || fold_umlauts(&candidate, true).starts_with(&input)
+}
+
+/// Replace umlauts and ß by their ASCII base letter or transliteration
+pub(crate) fn fold_umlauts(text: &str, transliterate: bool) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match (c, transliterate) {
+            ('ä', false) => folded.push('a'),
+            ('ö', false) => folded.push('o'),
+            ('ü', false) => folded.push('u'),
+            ('ä', true) => folded.push_str("ae"),
+            ('ö', true) => folded.push_str("oe"),
+            ('ü', true) => folded.push_str("ue"),
+            ('ß', _) => folded.push_str("ss"),
+            (c, _) => folded.push(c),
+        }
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complete_employment_type() {
+        let completion = complete_argument("employment_type", "p");
+        assert_eq!(completion.values, vec!["parttime"]);
+        assert_eq!(completion.total, 1);
+        assert!(!completion.has_more);
+    }
+
+    #[test]
+    fn test_complete_empty_prefix_lists_all() {
+        let completion = complete_argument("contract_type", "");
+        assert_eq!(completion.values, vec!["permanent", "temporary"]);
+    }
+
+    #[test]
+    fn test_complete_freshness() {
+        let completion = complete_argument("freshness", "");
+        assert_eq!(completion.values, vec!["today", "3days", "week", "month"]);
+    }
+
+    #[test]
+    fn test_complete_sort_by() {
+        let completion = complete_argument("sort_by", "");
+        assert_eq!(completion.values, vec!["relevance", "newest", "distance"]);
+        assert_eq!(complete_argument("sort_by", "d").values, vec!["distance"]);
+    }
+
+    #[test]
+    fn test_complete_city_umlaut_variants() {
+        for input in ["münch", "munch", "Muench", "MÜNCHEN"] {
+            let completion = complete_argument("location", input);
+            assert_eq!(completion.values, vec!["München"], "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_complete_city_multiple_matches() {
+        let completion = complete_argument("location", "Bo");
+        assert_eq!(completion.values, vec!["Bochum", "Bonn", "Bottrop"]);
+    }
+
+    #[test]
+    fn test_complete_unknown_argument() {
+        let completion = complete_argument("page", "1");
+        assert!(completion.values.is_empty());
+        assert_eq!(completion.total, 0);
+    }
+
+    #[test]
+    fn test_completion_serialization() {
+        let json = serde_json::to_value(complete_argument("radius_km", "1")).unwrap();
+        assert_eq!(json["values"], serde_json::json!(["10", "100"]));
+        assert_eq!(json["hasMore"], false);
+    }
+
+    #[test]
+    fn test_employment_types_are_parseable() {
+        use crate::JobsucheMcpServer;
+        for value in EMPLOYMENT_TYPES {
+            assert!(
+                JobsucheMcpServer::parse_employment_type(value).is_some(),
+                "{}",
+                value
+            );
+        }
+    }
+}
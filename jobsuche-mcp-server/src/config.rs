@@ -1,8 +1,53 @@
 //! Configuration module for Jobsuche MCP Server
 
+use crate::bewerberboerse::DEFAULT_BEWERBERBOERSE_URL;
+use crate::coaching::DEFAULT_COACHING_URL;
+use crate::commute::{Place, RoutingProvider};
+use crate::description::DescriptionFormat;
+use crate::entgeltatlas::DEFAULT_ENTGELTATLAS_URL;
+use crate::geocoding::DEFAULT_GEOCODING_URL;
+use crate::locale::Locale;
+use crate::notifications::WebhookFormat;
+use crate::scheduler::Schedule;
+use crate::studiensuche::DEFAULT_STUDIENSUCHE_URL;
+use crate::validation::MAX_RADIUS_KM;
+use crate::weiterbildung::DEFAULT_WEITERBILDUNG_URL;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Backend for persistent data (see [`crate::storage`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// Keep everything in memory
+    Memory,
+    /// JSON file in the data directory
+    Json,
+    /// SQLite database (`db_path`, or `jobsuche.db` in the data directory)
+    Sqlite,
+}
+
+impl FromStr for StorageBackend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "memory" => Ok(Self::Memory),
+            "json" => Ok(Self::Json),
+            "sqlite" => Ok(Self::Sqlite),
+            other => anyhow::bail!(
+                "Unknown storage backend '{}' (expected memory, json or sqlite)",
+                other
+            ),
+        }
+    }
+}
+
+/// File name of the SQLite database inside the data directory
+pub const DB_FILE_NAME: &str = "jobsuche.db";
 
 /// Configuration for the Jobsuche MCP Server
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +65,169 @@ pub struct JobsucheConfig {
     /// Maximum page size allowed
     #[serde(default = "default_max_page_size")]
     pub max_page_size: u64,
+
+    /// Ask the user for a job title or location (via MCP elicitation) when
+    /// search_jobs is called without either
+    #[serde(default = "default_elicit_underspecified_searches")]
+    pub elicit_underspecified_searches: bool,
+
+    /// Location searched in when a search names none
+    #[serde(default)]
+    pub default_location: Option<String>,
+
+    /// Radius in kilometers used when a search with a location names none
+    #[serde(default)]
+    pub default_radius_km: Option<u64>,
+
+    /// Directory for persistent data such as favorites (`None` keeps data in memory only)
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+
+    /// SQLite database for persistent data
+    #[serde(default)]
+    pub db_path: Option<PathBuf>,
+
+    /// Key for encrypting persistent data at rest: 32 bytes as hex or base64,
+    /// or "keychain" to use a key kept in the OS keychain
+    #[serde(default, skip_serializing)]
+    pub store_key: Option<String>,
+
+    /// Backend for persistent data (`None` picks SQLite if `db_path` is set,
+    /// else the JSON store if `data_dir` is set, else memory)
+    #[serde(default)]
+    pub storage_backend: Option<StorageBackend>,
+
+    /// Days after which job snapshots, seen jobs, history entries and indexed
+    /// jobs are pruned (0 keeps them forever)
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u64,
+
+    /// Hours between background maintenance runs (0 disables them)
+    #[serde(default = "default_maintenance_interval_hours")]
+    pub maintenance_interval_hours: u64,
+
+    /// Format of job descriptions returned by the detail tools
+    #[serde(default)]
+    pub description_format: DescriptionFormat,
+
+    /// Language of enumerated values (employment type, job type, ...) in job details
+    #[serde(default)]
+    pub locale: Locale,
+
+    /// Base URL of the Entgeltatlas API used for salary estimates
+    #[serde(default = "default_entgeltatlas_url")]
+    pub entgeltatlas_url: String,
+
+    /// Base URL of the Weiterbildungssuche API used for course searches
+    #[serde(default = "default_weiterbildung_url")]
+    pub weiterbildung_url: String,
+
+    /// Base URL of the Coachingangebote API used for AVGS coaching searches
+    #[serde(default = "default_coaching_url")]
+    pub coaching_url: String,
+
+    /// Base URL of the Studiensuche API used for degree program searches
+    #[serde(default = "default_studiensuche_url")]
+    pub studiensuche_url: String,
+
+    /// Offer the recruiter tools (candidate search in the Bewerberbörse)
+    #[serde(default)]
+    pub recruiter_tools: bool,
+
+    /// Base URL of the Bewerberbörse API used by the recruiter tools
+    #[serde(default = "default_bewerberboerse_url")]
+    pub bewerberboerse_url: String,
+
+    /// API key for the Bewerberbörse (`None` uses the public key)
+    #[serde(default, skip_serializing)]
+    pub bewerberboerse_api_key: Option<String>,
+
+    /// Cron expression (UTC) for checking all watchlists in the background
+    #[serde(default)]
+    pub watchlist_schedule: Option<String>,
+
+    /// Cron expression (UTC) for running all saved searches in the background
+    #[serde(default)]
+    pub saved_search_schedule: Option<String>,
+
+    /// Webhook notified about new watchlist jobs (Slack, Discord or generic JSON)
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+
+    /// Payload format of the webhook (`None` detects it from the URL)
+    #[serde(default)]
+    pub webhook_format: Option<WebhookFormat>,
+
+    /// Secret for signing webhook requests with HMAC-SHA256
+    #[serde(default, skip_serializing)]
+    pub webhook_secret: Option<String>,
+
+    /// Keep a snapshot of every fetched job so it can be served after the
+    /// posting has been taken down (`None`: only with the SQLite backend, see
+    /// [`Self::archives_job_details`])
+    #[serde(default)]
+    pub archive_job_details: Option<bool>,
+
+    /// Return the raw API data of job details unless a call asks otherwise
+    #[serde(default)]
+    pub include_raw_data: bool,
+
+    /// Replace transliterated umlauts in known city names of search locations
+    /// ("Muenchen" → "München")
+    #[serde(default = "default_transliterate_umlauts")]
+    pub transliterate_umlauts: bool,
+
+    /// Employer patterns dropped from all search results (see
+    /// [`crate::employer_filter`])
+    #[serde(default)]
+    pub employer_blocklist: Vec<String>,
+
+    /// If not empty, only employers matching these patterns are returned
+    #[serde(default)]
+    pub employer_allowlist: Vec<String>,
+
+    /// Soft daily budget of Jobsuche API calls after which batch tools refuse
+    /// (see [`crate::quota`]; `None` means no budget)
+    #[serde(default)]
+    pub daily_api_budget: Option<u64>,
+
+    /// Offer the admin tools (set_log_level) that change the running server
+    #[serde(default)]
+    pub admin_tools: bool,
+
+    /// Record every tool call in the audit log (see [`crate::audit`])
+    #[serde(default)]
+    pub audit_log: bool,
+
+    /// Routing service computing commute times (see [`crate::commute`];
+    /// `None` disables commute times)
+    #[serde(default)]
+    pub routing_provider: Option<RoutingProvider>,
+
+    /// Base URL of the routing service (`None` uses its public instance)
+    #[serde(default)]
+    pub routing_url: Option<String>,
+
+    /// API key of the routing service (required by Google)
+    #[serde(default, skip_serializing)]
+    pub routing_api_key: Option<String>,
+
+    /// Start of commutes: "lat,lon", or an address with the Google provider
+    /// or geocoding
+    #[serde(default)]
+    pub home_location: Option<String>,
+
+    /// Look up coordinates of places without them (see [`crate::geocoding`])
+    #[serde(default)]
+    pub geocoding: bool,
+
+    /// Base URL of the Nominatim-compatible geocoding service
+    #[serde(default = "default_geocoding_url")]
+    pub geocoding_url: String,
+
+    /// Directory for files written by export_results
+    #[serde(default = "default_export_dir")]
+    pub export_dir: PathBuf,
 }
 
 fn default_page_size() -> u64 {
@@ -30,6 +238,91 @@ fn default_max_page_size() -> u64 {
     100
 }
 
+fn default_elicit_underspecified_searches() -> bool {
+    true
+}
+
+fn default_transliterate_umlauts() -> bool {
+    true
+}
+
+fn default_entgeltatlas_url() -> String {
+    DEFAULT_ENTGELTATLAS_URL.to_string()
+}
+
+fn default_weiterbildung_url() -> String {
+    DEFAULT_WEITERBILDUNG_URL.to_string()
+}
+
+fn default_coaching_url() -> String {
+    DEFAULT_COACHING_URL.to_string()
+}
+
+fn default_studiensuche_url() -> String {
+    DEFAULT_STUDIENSUCHE_URL.to_string()
+}
+
+fn default_bewerberboerse_url() -> String {
+    DEFAULT_BEWERBERBOERSE_URL.to_string()
+}
+
+fn default_geocoding_url() -> String {
+    DEFAULT_GEOCODING_URL.to_string()
+}
+
+fn default_retention_days() -> u64 {
+    180
+}
+
+fn default_maintenance_interval_hours() -> u64 {
+    24
+}
+
+/// Value of JOBSUCHE_DATA_DIR selecting the platform data directory
+const PLATFORM_DATA_DIR: &str = "default";
+
+/// Platform data directory: `$XDG_DATA_HOME`, `~/.local/share` or `%APPDATA%`
+fn platform_data_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+
+    Some(base.join("jobsuche-mcp-server"))
+}
+
+/// `exports` inside the data directory, or the system temp directory without one
+fn export_dir_for(data_dir: Option<&Path>) -> PathBuf {
+    match data_dir {
+        Some(dir) => dir.join("exports"),
+        None => env::temp_dir().join("jobsuche-exports"),
+    }
+}
+
+fn default_export_dir() -> PathBuf {
+    export_dir_for(None)
+}
+
+/// Parse a comma-separated list environment variable, skipping empty items
+fn parse_list_env(name: &str) -> Vec<String> {
+    env::var(name)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Parse a boolean environment variable ("true"/"false", "1"/"0", "yes"/"no")
+fn parse_bool_env(name: &str) -> Option<bool> {
+    match env::var(name).ok()?.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Some(true),
+        "false" | "0" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
 impl Default for JobsucheConfig {
     fn default() -> Self {
         Self {
@@ -37,6 +330,44 @@ impl Default for JobsucheConfig {
             api_key: None,
             default_page_size: default_page_size(),
             max_page_size: default_max_page_size(),
+            elicit_underspecified_searches: default_elicit_underspecified_searches(),
+            default_location: None,
+            default_radius_km: None,
+            data_dir: None,
+            db_path: None,
+            storage_backend: None,
+            store_key: None,
+            retention_days: default_retention_days(),
+            maintenance_interval_hours: default_maintenance_interval_hours(),
+            description_format: DescriptionFormat::default(),
+            locale: Locale::default(),
+            entgeltatlas_url: default_entgeltatlas_url(),
+            weiterbildung_url: default_weiterbildung_url(),
+            coaching_url: default_coaching_url(),
+            studiensuche_url: default_studiensuche_url(),
+            recruiter_tools: false,
+            bewerberboerse_url: default_bewerberboerse_url(),
+            bewerberboerse_api_key: None,
+            watchlist_schedule: None,
+            saved_search_schedule: None,
+            webhook_url: None,
+            webhook_format: None,
+            webhook_secret: None,
+            archive_job_details: None,
+            include_raw_data: false,
+            transliterate_umlauts: default_transliterate_umlauts(),
+            employer_blocklist: Vec::new(),
+            employer_allowlist: Vec::new(),
+            daily_api_budget: None,
+            admin_tools: false,
+            audit_log: false,
+            routing_provider: None,
+            routing_url: None,
+            routing_api_key: None,
+            home_location: None,
+            geocoding: false,
+            geocoding_url: default_geocoding_url(),
+            export_dir: default_export_dir(),
         }
     }
 }
@@ -49,6 +380,79 @@ impl JobsucheConfig {
     /// - `JOBSUCHE_API_KEY`: API key (optional, uses default if not specified)
     /// - `JOBSUCHE_DEFAULT_PAGE_SIZE`: Default page size (optional, defaults to 25)
     /// - `JOBSUCHE_MAX_PAGE_SIZE`: Maximum page size (optional, defaults to 100)
+    /// - `JOBSUCHE_ELICIT_UNDERSPECIFIED`: Ask for a job title or location when both are
+    ///   missing (optional, defaults to true)
+    /// - `JOBSUCHE_DEFAULT_LOCATION`: Location for searches without one, e.g. `Wuppertal`
+    ///   (optional)
+    /// - `JOBSUCHE_DEFAULT_RADIUS_KM`: Radius for searches with a location but no radius
+    ///   (optional, 0-200)
+    /// - `JOBSUCHE_DATA_DIR`: Directory for persistent data (optional, defaults to the
+    ///   platform data directory; an empty value keeps all data in memory)
+    /// - `JOBSUCHE_DB_PATH`: SQLite database for persistent data (optional, replaces the
+    ///   JSON store in the data directory)
+    /// - `JOBSUCHE_STORAGE`: Storage backend, `memory`, `json` or `sqlite` (optional,
+    ///   derived from `JOBSUCHE_DB_PATH` and `JOBSUCHE_DATA_DIR` by default)
+    /// - `JOBSUCHE_STORE_KEY`: Encrypt persistent data with this key, 32 bytes as hex or
+    ///   base64, or `keychain` for a key in the OS keychain (optional)
+    /// - `JOBSUCHE_RETENTION_DAYS`: Prune job snapshots, seen jobs, history entries and
+    ///   indexed jobs older than this (optional, defaults to 180; 0 keeps everything)
+    /// - `JOBSUCHE_MAINTENANCE_INTERVAL_HOURS`: Hours between background maintenance runs
+    ///   (optional, defaults to 24; 0 disables them)
+    /// - `JOBSUCHE_DESCRIPTION_FORMAT`: Job descriptions as `markdown`, `text` or `raw` HTML
+    ///   (optional, defaults to markdown)
+    /// - `JOBSUCHE_LOCALE`: `de` to return enumerated values as delivered or `en` to
+    ///   translate them (optional, defaults to de)
+    /// - `JOBSUCHE_ENTGELTATLAS_URL`: Entgeltatlas API base URL for salary estimates
+    ///   (optional, defaults to the official API)
+    /// - `JOBSUCHE_WEITERBILDUNG_URL`: Weiterbildungssuche API base URL for course
+    ///   searches (optional, defaults to the official API)
+    /// - `JOBSUCHE_COACHING_URL`: Coachingangebote API base URL for AVGS coaching
+    ///   searches (optional, defaults to the official API)
+    /// - `JOBSUCHE_STUDIENSUCHE_URL`: Studiensuche API base URL for degree program
+    ///   searches (optional, defaults to the official API)
+    /// - `JOBSUCHE_RECRUITER_TOOLS`: Enable the candidate search tools for recruiters
+    ///   (optional, defaults to false)
+    /// - `JOBSUCHE_BEWERBERBOERSE_URL`: Bewerberbörse API base URL for the recruiter
+    ///   tools (optional, defaults to the official API)
+    /// - `JOBSUCHE_BEWERBERBOERSE_API_KEY`: Bewerberbörse API key (optional, defaults to
+    ///   the public key of the web application)
+    /// - `JOBSUCHE_WATCHLIST_SCHEDULE`: Cron expression (UTC) for checking watchlists in
+    ///   the background, e.g. `0 7 * * *` (optional)
+    /// - `JOBSUCHE_SAVED_SEARCH_SCHEDULE`: Cron expression (UTC) for running saved searches
+    ///   in the background (optional)
+    /// - `JOBSUCHE_WEBHOOK_URL`: Webhook notified about new watchlist jobs (optional)
+    /// - `JOBSUCHE_WEBHOOK_FORMAT`: Webhook payload format, `generic`, `slack` or `discord`
+    ///   (optional, detected from the URL by default)
+    /// - `JOBSUCHE_WEBHOOK_SECRET`: Sign webhook requests with HMAC-SHA256 (optional)
+    /// - `JOBSUCHE_ARCHIVE_JOBS`: Keep snapshots of fetched jobs (optional, defaults to true
+    ///   with the SQLite backend)
+    /// - `JOBSUCHE_INCLUDE_RAW`: Return the raw API data of job details by default
+    ///   (optional, defaults to false)
+    /// - `JOBSUCHE_TRANSLITERATE_UMLAUTS`: Search "Muenchen" as "München" (optional,
+    ///   defaults to true)
+    /// - `JOBSUCHE_EMPLOYER_BLOCKLIST`: Comma-separated employer patterns dropped from all
+    ///   search results, e.g. `Randstad,Adecco,zeitarbeit` (optional)
+    /// - `JOBSUCHE_EMPLOYER_ALLOWLIST`: Comma-separated employer patterns; only matching
+    ///   employers are returned (optional)
+    /// - `JOBSUCHE_DAILY_API_BUDGET`: Jobsuche API calls per day (UTC) after which batch
+    ///   tools refuse to start (optional, unset or 0 means no budget)
+    /// - `JOBSUCHE_ADMIN_TOOLS`: Enable the admin tools such as set_log_level (optional,
+    ///   defaults to false)
+    /// - `JOBSUCHE_AUDIT_LOG`: Record every tool call in the audit log (optional,
+    ///   defaults to false)
+    /// - `JOBSUCHE_ROUTING_PROVIDER`: Routing service for commute times, `osrm`,
+    ///   `valhalla` or `google` (optional, unset disables commute times)
+    /// - `JOBSUCHE_ROUTING_URL`: Base URL of the routing service (optional, defaults to
+    ///   the public instance of the provider)
+    /// - `JOBSUCHE_ROUTING_API_KEY`: API key of the routing service (required by google)
+    /// - `JOBSUCHE_HOME_LOCATION`: Start of commutes as `lat,lon`, or an address with
+    ///   google or geocoding (required with a routing provider)
+    /// - `JOBSUCHE_GEOCODING`: Look up coordinates of places without them (optional,
+    ///   defaults to false)
+    /// - `JOBSUCHE_GEOCODING_URL`: Nominatim-compatible geocoding service (optional,
+    ///   defaults to the public Nominatim instance)
+    /// - `JOBSUCHE_EXPORT_DIR`: Directory for CSV exports (optional, defaults to `exports`
+    ///   in the data directory)
     pub fn load() -> Result<Self> {
         let api_url = env::var("JOBSUCHE_API_URL").unwrap_or_else(|_| {
             "https://rest.arbeitsagentur.de/jobboerse/jobsuche-service".to_string()
@@ -66,6 +470,158 @@ impl JobsucheConfig {
             .and_then(|v| v.parse().ok())
             .unwrap_or(default_max_page_size());
 
+        let elicit_underspecified_searches = parse_bool_env("JOBSUCHE_ELICIT_UNDERSPECIFIED")
+            .unwrap_or(default_elicit_underspecified_searches());
+
+        let default_location = env::var("JOBSUCHE_DEFAULT_LOCATION")
+            .ok()
+            .filter(|location| !location.trim().is_empty());
+
+        let default_radius_km = env::var("JOBSUCHE_DEFAULT_RADIUS_KM")
+            .ok()
+            .and_then(|v| v.parse().ok());
+
+        // Nothing is written to disk unless the user opts in
+        let data_dir = match env::var("JOBSUCHE_DATA_DIR") {
+            Ok(dir) if dir.trim().is_empty() => None,
+            Ok(dir) if dir.trim() == PLATFORM_DATA_DIR => platform_data_dir(),
+            Ok(dir) => Some(PathBuf::from(dir)),
+            Err(_) => None,
+        };
+
+        let db_path = env::var("JOBSUCHE_DB_PATH")
+            .ok()
+            .filter(|path| !path.trim().is_empty())
+            .map(PathBuf::from);
+
+        let storage_backend = match env::var("JOBSUCHE_STORAGE") {
+            Ok(backend) if !backend.trim().is_empty() => Some(backend.parse()?),
+            _ => None,
+        };
+
+        let store_key = env::var("JOBSUCHE_STORE_KEY")
+            .ok()
+            .filter(|key| !key.trim().is_empty());
+
+        let retention_days = env::var("JOBSUCHE_RETENTION_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_retention_days());
+
+        let maintenance_interval_hours = env::var("JOBSUCHE_MAINTENANCE_INTERVAL_HOURS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default_maintenance_interval_hours());
+
+        let description_format = match env::var("JOBSUCHE_DESCRIPTION_FORMAT") {
+            Ok(format) if !format.trim().is_empty() => format.parse()?,
+            _ => DescriptionFormat::default(),
+        };
+
+        let locale = match env::var("JOBSUCHE_LOCALE") {
+            Ok(locale) if !locale.trim().is_empty() => locale.parse()?,
+            _ => Locale::default(),
+        };
+
+        let entgeltatlas_url = env::var("JOBSUCHE_ENTGELTATLAS_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(default_entgeltatlas_url);
+
+        let weiterbildung_url = env::var("JOBSUCHE_WEITERBILDUNG_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(default_weiterbildung_url);
+
+        let coaching_url = env::var("JOBSUCHE_COACHING_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(default_coaching_url);
+
+        let studiensuche_url = env::var("JOBSUCHE_STUDIENSUCHE_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(default_studiensuche_url);
+
+        let recruiter_tools = parse_bool_env("JOBSUCHE_RECRUITER_TOOLS").unwrap_or(false);
+
+        let bewerberboerse_url = env::var("JOBSUCHE_BEWERBERBOERSE_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(default_bewerberboerse_url);
+
+        let bewerberboerse_api_key = env::var("JOBSUCHE_BEWERBERBOERSE_API_KEY")
+            .ok()
+            .filter(|key| !key.trim().is_empty());
+
+        let watchlist_schedule = env::var("JOBSUCHE_WATCHLIST_SCHEDULE")
+            .ok()
+            .filter(|schedule| !schedule.trim().is_empty());
+
+        let saved_search_schedule = env::var("JOBSUCHE_SAVED_SEARCH_SCHEDULE")
+            .ok()
+            .filter(|schedule| !schedule.trim().is_empty());
+
+        let webhook_url = env::var("JOBSUCHE_WEBHOOK_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty());
+
+        let webhook_format = match env::var("JOBSUCHE_WEBHOOK_FORMAT") {
+            Ok(format) if !format.trim().is_empty() => Some(format.parse()?),
+            _ => None,
+        };
+
+        let webhook_secret = env::var("JOBSUCHE_WEBHOOK_SECRET")
+            .ok()
+            .filter(|secret| !secret.is_empty());
+
+        let archive_job_details = parse_bool_env("JOBSUCHE_ARCHIVE_JOBS");
+
+        let include_raw_data = parse_bool_env("JOBSUCHE_INCLUDE_RAW").unwrap_or(false);
+
+        let transliterate_umlauts = parse_bool_env("JOBSUCHE_TRANSLITERATE_UMLAUTS")
+            .unwrap_or(default_transliterate_umlauts());
+
+        let employer_blocklist = parse_list_env("JOBSUCHE_EMPLOYER_BLOCKLIST");
+        let employer_allowlist = parse_list_env("JOBSUCHE_EMPLOYER_ALLOWLIST");
+
+        let daily_api_budget = env::var("JOBSUCHE_DAILY_API_BUDGET")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|budget| *budget > 0);
+
+        let admin_tools = parse_bool_env("JOBSUCHE_ADMIN_TOOLS").unwrap_or(false);
+        let audit_log = parse_bool_env("JOBSUCHE_AUDIT_LOG").unwrap_or(false);
+
+        let routing_provider = match env::var("JOBSUCHE_ROUTING_PROVIDER") {
+            Ok(provider) if !provider.trim().is_empty() => Some(provider.parse()?),
+            _ => None,
+        };
+
+        let routing_url = env::var("JOBSUCHE_ROUTING_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty());
+
+        let routing_api_key = env::var("JOBSUCHE_ROUTING_API_KEY")
+            .ok()
+            .filter(|key| !key.trim().is_empty());
+
+        let home_location = env::var("JOBSUCHE_HOME_LOCATION")
+            .ok()
+            .filter(|location| !location.trim().is_empty());
+
+        let geocoding = parse_bool_env("JOBSUCHE_GEOCODING").unwrap_or(false);
+
+        let geocoding_url = env::var("JOBSUCHE_GEOCODING_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or_else(default_geocoding_url);
+
+        let export_dir = match env::var("JOBSUCHE_EXPORT_DIR") {
+            Ok(dir) if !dir.trim().is_empty() => PathBuf::from(dir),
+            _ => export_dir_for(data_dir.as_deref()),
+        };
+
         // Validate configuration
         if default_page_size == 0 {
             anyhow::bail!("Default page size must be greater than 0");
@@ -88,14 +644,82 @@ impl JobsucheConfig {
             anyhow::bail!("Max page size cannot exceed 100 (API limitation)");
         }
 
+        if default_radius_km.is_some_and(|radius| radius > MAX_RADIUS_KM) {
+            anyhow::bail!("Default radius cannot exceed {} km", MAX_RADIUS_KM);
+        }
+
         Ok(Self {
             api_url,
             api_key,
             default_page_size,
             max_page_size,
+            elicit_underspecified_searches,
+            default_location,
+            default_radius_km,
+            data_dir,
+            db_path,
+            storage_backend,
+            store_key,
+            retention_days,
+            maintenance_interval_hours,
+            description_format,
+            locale,
+            entgeltatlas_url,
+            weiterbildung_url,
+            coaching_url,
+            studiensuche_url,
+            recruiter_tools,
+            bewerberboerse_url,
+            bewerberboerse_api_key,
+            watchlist_schedule,
+            saved_search_schedule,
+            webhook_url,
+            webhook_format,
+            webhook_secret,
+            archive_job_details,
+            include_raw_data,
+            transliterate_umlauts,
+            employer_blocklist,
+            employer_allowlist,
+            daily_api_budget,
+            admin_tools,
+            audit_log,
+            routing_provider,
+            routing_url,
+            routing_api_key,
+            home_location,
+            geocoding,
+            geocoding_url,
+            export_dir,
         })
     }
 
+    /// Storage backend in effect
+    pub fn effective_storage_backend(&self) -> StorageBackend {
+        match self.storage_backend {
+            Some(backend) => backend,
+            None if self.db_path.is_some() => StorageBackend::Sqlite,
+            None if self.data_dir.is_some() => StorageBackend::Json,
+            None => StorageBackend::Memory,
+        }
+    }
+
+    /// Whether fetched job details are archived
+    ///
+    /// On by default only with SQLite, which writes a snapshot as one row; the
+    /// JSON backend rewrites its whole file on every write.
+    pub fn archives_job_details(&self) -> bool {
+        self.archive_job_details
+            .unwrap_or(self.effective_storage_backend() == StorageBackend::Sqlite)
+    }
+
+    /// SQLite database: `db_path`, or `jobsuche.db` in the data directory
+    pub fn sqlite_path(&self) -> Option<PathBuf> {
+        self.db_path
+            .clone()
+            .or_else(|| self.data_dir.as_ref().map(|dir| dir.join(DB_FILE_NAME)))
+    }
+
     /// Validate that the configuration is correct
     pub fn validate(&self) -> Result<()> {
         if self.api_url.is_empty() {
@@ -106,6 +730,47 @@ impl JobsucheConfig {
             anyhow::bail!("API URL must start with http:// or https://");
         }
 
+        match self.effective_storage_backend() {
+            StorageBackend::Json if self.data_dir.is_none() => {
+                anyhow::bail!("The json storage backend requires a data directory");
+            }
+            StorageBackend::Sqlite if self.sqlite_path().is_none() => {
+                anyhow::bail!(
+                    "The sqlite storage backend requires a database path or data directory"
+                );
+            }
+            _ => {}
+        }
+
+        for schedule in [&self.watchlist_schedule, &self.saved_search_schedule]
+            .into_iter()
+            .flatten()
+        {
+            schedule.parse::<Schedule>()?;
+        }
+
+        if let Some(ref url) = self.webhook_url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                anyhow::bail!("Webhook URL must start with http:// or https://");
+            }
+        }
+
+        if let Some(provider) = self.routing_provider {
+            let Some(ref home) = self.home_location else {
+                anyhow::bail!("A routing provider requires JOBSUCHE_HOME_LOCATION");
+            };
+            if provider != RoutingProvider::Google
+                && !self.geocoding
+                && Place::parse(home).coordinates().is_err()
+            {
+                anyhow::bail!(
+                    "JOBSUCHE_HOME_LOCATION must be \"lat,lon\" for {:?} unless \
+                     JOBSUCHE_GEOCODING is enabled",
+                    provider
+                );
+            }
+        }
+
         Ok(())
     }
 }
@@ -126,6 +791,8 @@ mod tests {
         assert_eq!(config.api_key, None);
         assert_eq!(config.default_page_size, 25);
         assert_eq!(config.max_page_size, 100);
+        assert!(config.elicit_underspecified_searches);
+        assert!(!config.archives_job_details());
     }
 
     #[test]
@@ -254,6 +921,419 @@ mod tests {
         env::remove_var("JOBSUCHE_MAX_PAGE_SIZE");
     }
 
+    #[test]
+    #[serial]
+    fn test_load_with_elicitation_disabled() {
+        env::set_var("JOBSUCHE_ELICIT_UNDERSPECIFIED", "false");
+        let config = JobsucheConfig::load().unwrap();
+        assert!(!config.elicit_underspecified_searches);
+        env::remove_var("JOBSUCHE_ELICIT_UNDERSPECIFIED");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_default_location() {
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.default_location, None);
+        assert_eq!(config.default_radius_km, None);
+
+        env::set_var("JOBSUCHE_DEFAULT_LOCATION", "Wuppertal");
+        env::set_var("JOBSUCHE_DEFAULT_RADIUS_KM", "25");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.default_location.as_deref(), Some("Wuppertal"));
+        assert_eq!(config.default_radius_km, Some(25));
+
+        env::set_var("JOBSUCHE_DEFAULT_RADIUS_KM", "500");
+        assert!(JobsucheConfig::load().is_err());
+        env::remove_var("JOBSUCHE_DEFAULT_LOCATION");
+        env::remove_var("JOBSUCHE_DEFAULT_RADIUS_KM");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_employer_lists() {
+        let config = JobsucheConfig::load().unwrap();
+        assert!(config.employer_blocklist.is_empty());
+
+        env::set_var("JOBSUCHE_EMPLOYER_BLOCKLIST", " Randstad, ,zeitarbeit ");
+        env::set_var("JOBSUCHE_EMPLOYER_ALLOWLIST", "BARMER");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.employer_blocklist, vec!["Randstad", "zeitarbeit"]);
+        assert_eq!(config.employer_allowlist, vec!["BARMER"]);
+        env::remove_var("JOBSUCHE_EMPLOYER_BLOCKLIST");
+        env::remove_var("JOBSUCHE_EMPLOYER_ALLOWLIST");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_daily_api_budget() {
+        assert_eq!(JobsucheConfig::load().unwrap().daily_api_budget, None);
+
+        env::set_var("JOBSUCHE_DAILY_API_BUDGET", "5000");
+        assert_eq!(JobsucheConfig::load().unwrap().daily_api_budget, Some(5000));
+
+        env::set_var("JOBSUCHE_DAILY_API_BUDGET", "0");
+        assert_eq!(JobsucheConfig::load().unwrap().daily_api_budget, None);
+        env::remove_var("JOBSUCHE_DAILY_API_BUDGET");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_admin_tools() {
+        assert!(!JobsucheConfig::load().unwrap().admin_tools);
+
+        env::set_var("JOBSUCHE_ADMIN_TOOLS", "true");
+        assert!(JobsucheConfig::load().unwrap().admin_tools);
+        env::remove_var("JOBSUCHE_ADMIN_TOOLS");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_audit_log() {
+        assert!(!JobsucheConfig::load().unwrap().audit_log);
+
+        env::set_var("JOBSUCHE_AUDIT_LOG", "1");
+        assert!(JobsucheConfig::load().unwrap().audit_log);
+        env::remove_var("JOBSUCHE_AUDIT_LOG");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_routing() {
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.routing_provider, None);
+        assert!(config.validate().is_ok());
+
+        env::set_var("JOBSUCHE_ROUTING_PROVIDER", "Valhalla");
+        env::set_var("JOBSUCHE_ROUTING_URL", "http://localhost:8002");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.routing_provider, Some(RoutingProvider::Valhalla));
+        assert_eq!(config.routing_url.as_deref(), Some("http://localhost:8002"));
+        assert!(config.validate().is_err());
+
+        env::set_var("JOBSUCHE_HOME_LOCATION", "51.2562,7.1508");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.home_location.as_deref(), Some("51.2562,7.1508"));
+        assert!(config.validate().is_ok());
+
+        // Addresses need geocoding unless Google routes them
+        env::set_var("JOBSUCHE_HOME_LOCATION", "42103 Wuppertal");
+        assert!(JobsucheConfig::load().unwrap().validate().is_err());
+        env::set_var("JOBSUCHE_GEOCODING", "true");
+        assert!(JobsucheConfig::load().unwrap().validate().is_ok());
+        env::remove_var("JOBSUCHE_GEOCODING");
+
+        env::set_var("JOBSUCHE_ROUTING_PROVIDER", "here");
+        assert!(JobsucheConfig::load().is_err());
+        env::remove_var("JOBSUCHE_ROUTING_PROVIDER");
+        env::remove_var("JOBSUCHE_ROUTING_URL");
+        env::remove_var("JOBSUCHE_HOME_LOCATION");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_geocoding() {
+        let config = JobsucheConfig::load().unwrap();
+        assert!(!config.geocoding);
+        assert_eq!(config.geocoding_url, DEFAULT_GEOCODING_URL);
+
+        env::set_var("JOBSUCHE_GEOCODING", "true");
+        env::set_var("JOBSUCHE_GEOCODING_URL", "http://localhost:8080");
+        let config = JobsucheConfig::load().unwrap();
+        assert!(config.geocoding);
+        assert_eq!(config.geocoding_url, "http://localhost:8080");
+        env::remove_var("JOBSUCHE_GEOCODING");
+        env::remove_var("JOBSUCHE_GEOCODING_URL");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_archive_disabled() {
+        let config = JobsucheConfig {
+            db_path: Some(PathBuf::from("jobsuche.db")),
+            ..Default::default()
+        };
+        assert!(config.archives_job_details());
+
+        env::set_var("JOBSUCHE_ARCHIVE_JOBS", "0");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.archive_job_details, Some(false));
+        env::remove_var("JOBSUCHE_ARCHIVE_JOBS");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_include_raw() {
+        let config = JobsucheConfig::load().unwrap();
+        assert!(!config.include_raw_data);
+
+        env::set_var("JOBSUCHE_INCLUDE_RAW", "true");
+        let config = JobsucheConfig::load().unwrap();
+        assert!(config.include_raw_data);
+        env::remove_var("JOBSUCHE_INCLUDE_RAW");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_transliterate_umlauts() {
+        let config = JobsucheConfig::load().unwrap();
+        assert!(config.transliterate_umlauts);
+
+        env::set_var("JOBSUCHE_TRANSLITERATE_UMLAUTS", "false");
+        let config = JobsucheConfig::load().unwrap();
+        assert!(!config.transliterate_umlauts);
+        env::remove_var("JOBSUCHE_TRANSLITERATE_UMLAUTS");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_recruiter_tools() {
+        let config = JobsucheConfig::load().unwrap();
+        assert!(!config.recruiter_tools);
+
+        env::set_var("JOBSUCHE_RECRUITER_TOOLS", "true");
+        env::set_var("JOBSUCHE_BEWERBERBOERSE_API_KEY", "recruiter-key");
+        let config = JobsucheConfig::load().unwrap();
+        assert!(config.recruiter_tools);
+        assert_eq!(
+            config.bewerberboerse_api_key.as_deref(),
+            Some("recruiter-key")
+        );
+        env::remove_var("JOBSUCHE_RECRUITER_TOOLS");
+        env::remove_var("JOBSUCHE_BEWERBERBOERSE_API_KEY");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_with_custom_data_dir() {
+        env::set_var("JOBSUCHE_DATA_DIR", "/tmp/jobsuche-data");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.data_dir, Some(PathBuf::from("/tmp/jobsuche-data")));
+
+        env::set_var("JOBSUCHE_DATA_DIR", "");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.data_dir, None);
+        env::remove_var("JOBSUCHE_DATA_DIR");
+
+        // Persistence is opt-in
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.data_dir, None);
+        assert_eq!(config.effective_storage_backend(), StorageBackend::Memory);
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_db_path() {
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.db_path, None);
+
+        env::set_var("JOBSUCHE_DB_PATH", "/tmp/jobsuche.db");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.db_path, Some(PathBuf::from("/tmp/jobsuche.db")));
+        env::remove_var("JOBSUCHE_DB_PATH");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_storage_backend() {
+        env::set_var("JOBSUCHE_STORAGE", "SQLite");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.storage_backend, Some(StorageBackend::Sqlite));
+
+        env::set_var("JOBSUCHE_STORAGE", "postgres");
+        assert!(JobsucheConfig::load().is_err());
+        env::remove_var("JOBSUCHE_STORAGE");
+    }
+
+    #[test]
+    fn test_effective_storage_backend() {
+        let mut config = JobsucheConfig {
+            data_dir: Some(PathBuf::from("/data")),
+            ..Default::default()
+        };
+        assert_eq!(config.effective_storage_backend(), StorageBackend::Json);
+        assert_eq!(
+            config.sqlite_path(),
+            Some(PathBuf::from("/data/jobsuche.db"))
+        );
+
+        config.db_path = Some(PathBuf::from("/db/jobs.db"));
+        assert_eq!(config.effective_storage_backend(), StorageBackend::Sqlite);
+        assert_eq!(config.sqlite_path(), Some(PathBuf::from("/db/jobs.db")));
+
+        config.storage_backend = Some(StorageBackend::Memory);
+        assert_eq!(config.effective_storage_backend(), StorageBackend::Memory);
+    }
+
+    #[test]
+    fn test_validate_storage_backend_requires_location() {
+        let config = JobsucheConfig {
+            data_dir: None,
+            storage_backend: Some(StorageBackend::Json),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = JobsucheConfig {
+            data_dir: None,
+            storage_backend: Some(StorageBackend::Sqlite),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_retention() {
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.retention_days, 180);
+        assert_eq!(config.maintenance_interval_hours, 24);
+
+        env::set_var("JOBSUCHE_RETENTION_DAYS", "0");
+        env::set_var("JOBSUCHE_MAINTENANCE_INTERVAL_HOURS", "6");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.retention_days, 0);
+        assert_eq!(config.maintenance_interval_hours, 6);
+        env::remove_var("JOBSUCHE_RETENTION_DAYS");
+        env::remove_var("JOBSUCHE_MAINTENANCE_INTERVAL_HOURS");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_webhook() {
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.webhook_url, None);
+
+        env::set_var(
+            "JOBSUCHE_WEBHOOK_URL",
+            "https://hooks.slack.com/services/T0/B0/X",
+        );
+        env::set_var("JOBSUCHE_WEBHOOK_FORMAT", "generic");
+        env::set_var("JOBSUCHE_WEBHOOK_SECRET", "s3cret");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(
+            config.webhook_url.as_deref(),
+            Some("https://hooks.slack.com/services/T0/B0/X")
+        );
+        assert_eq!(config.webhook_format, Some(WebhookFormat::Generic));
+        assert_eq!(config.webhook_secret.as_deref(), Some("s3cret"));
+        assert!(config.validate().is_ok());
+
+        env::set_var("JOBSUCHE_WEBHOOK_FORMAT", "teams");
+        assert!(JobsucheConfig::load().is_err());
+        env::remove_var("JOBSUCHE_WEBHOOK_URL");
+        env::remove_var("JOBSUCHE_WEBHOOK_FORMAT");
+        env::remove_var("JOBSUCHE_WEBHOOK_SECRET");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_description_format() {
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.description_format, DescriptionFormat::Markdown);
+
+        env::set_var("JOBSUCHE_DESCRIPTION_FORMAT", "text");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.description_format, DescriptionFormat::Text);
+
+        env::set_var("JOBSUCHE_DESCRIPTION_FORMAT", "pdf");
+        assert!(JobsucheConfig::load().is_err());
+        env::remove_var("JOBSUCHE_DESCRIPTION_FORMAT");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_locale() {
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.locale, Locale::De);
+
+        env::set_var("JOBSUCHE_LOCALE", "en_US");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.locale, Locale::En);
+
+        env::set_var("JOBSUCHE_LOCALE", "fr");
+        assert!(JobsucheConfig::load().is_err());
+        env::remove_var("JOBSUCHE_LOCALE");
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_schedules() {
+        env::set_var("JOBSUCHE_WATCHLIST_SCHEDULE", "0 7 * * *");
+        env::set_var("JOBSUCHE_SAVED_SEARCH_SCHEDULE", " ");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.watchlist_schedule.as_deref(), Some("0 7 * * *"));
+        assert_eq!(config.saved_search_schedule, None);
+        assert!(config.validate().is_ok());
+
+        env::set_var("JOBSUCHE_SAVED_SEARCH_SCHEDULE", "daily");
+        let config = JobsucheConfig::load().unwrap();
+        assert!(config.validate().is_err());
+        env::remove_var("JOBSUCHE_WATCHLIST_SCHEDULE");
+        env::remove_var("JOBSUCHE_SAVED_SEARCH_SCHEDULE");
+    }
+
+    #[test]
+    fn test_validate_webhook_url() {
+        let config = JobsucheConfig {
+            webhook_url: Some("ftp://example.com/hook".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_export_dir() {
+        env::set_var("JOBSUCHE_DATA_DIR", "/tmp/jobsuche-data");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(
+            config.export_dir,
+            PathBuf::from("/tmp/jobsuche-data/exports")
+        );
+
+        env::set_var("JOBSUCHE_EXPORT_DIR", "/tmp/jobsuche-exports");
+        let config = JobsucheConfig::load().unwrap();
+        assert_eq!(config.export_dir, PathBuf::from("/tmp/jobsuche-exports"));
+
+        env::remove_var("JOBSUCHE_EXPORT_DIR");
+        env::remove_var("JOBSUCHE_DATA_DIR");
+    }
+
+    #[test]
+    #[serial]
+    fn test_platform_data_dir_uses_xdg() {
+        let previous = env::var_os("XDG_DATA_HOME");
+        env::set_var("XDG_DATA_HOME", "/tmp/xdg");
+        assert_eq!(
+            platform_data_dir(),
+            Some(PathBuf::from("/tmp/xdg/jobsuche-mcp-server"))
+        );
+        env::set_var("JOBSUCHE_DATA_DIR", "default");
+        assert_eq!(
+            JobsucheConfig::load().unwrap().data_dir,
+            Some(PathBuf::from("/tmp/xdg/jobsuche-mcp-server"))
+        );
+        env::remove_var("JOBSUCHE_DATA_DIR");
+        match previous {
+            Some(value) => env::set_var("XDG_DATA_HOME", value),
+            None => env::remove_var("XDG_DATA_HOME"),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_bool_env() {
+        env::set_var("JOBSUCHE_TEST_BOOL", "YES");
+        assert_eq!(parse_bool_env("JOBSUCHE_TEST_BOOL"), Some(true));
+        env::set_var("JOBSUCHE_TEST_BOOL", "0");
+        assert_eq!(parse_bool_env("JOBSUCHE_TEST_BOOL"), Some(false));
+        env::set_var("JOBSUCHE_TEST_BOOL", "maybe");
+        assert_eq!(parse_bool_env("JOBSUCHE_TEST_BOOL"), None);
+        env::remove_var("JOBSUCHE_TEST_BOOL");
+        assert_eq!(parse_bool_env("JOBSUCHE_TEST_BOOL"), None);
+    }
+
     #[test]
     fn test_validate_valid_config() {
         let config = JobsucheConfig::default();
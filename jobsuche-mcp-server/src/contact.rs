@@ -0,0 +1,163 @@
+//! Contact extraction from descriptions
+//!
+//! The API has no contact field, but many employers put a contact person,
+//! e-mail address or phone number into the description. These are picked up
+//! with simple patterns. The result is best-effort: it may miss contacts or
+//! contain unrelated numbers, which `best_effort` makes explicit to clients.
+
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Contact details found in a description
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ContactInfo {
+    /// Always true: extracted heuristically from the description, not provided
+    /// by the API; verify before use
+    pub best_effort: bool,
+
+    /// Contact persons named after "Ansprechpartner", "Kontakt" and similar
+    pub names: Vec<String>,
+
+    /// E-mail addresses
+    pub emails: Vec<String>,
+
+    /// Phone numbers
+    pub phones: Vec<String>,
+}
+
+/// Name after a contact cue, e.g. "Ihre Ansprechpartnerin: Frau Dr. Anna Müller"
+fn name_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i:ansprechpartner(?:in)?|kontaktperson|ansprechperson|contact person|kontakt)(?:[ \t]+(?i:ist|is))?[ \t]*:?\s*((?:(?:Frau|Herr|Mr\.?|Ms\.?|Mrs\.?)[ \t]+)?(?:(?:Dr|Prof)\.[ \t]+)*\p{Lu}[\p{L}'-]+(?:[ \t]+\p{Lu}[\p{L}'-]+){0,2})",
+        )
+        .expect("valid name pattern")
+    })
+}
+
+fn email_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}")
+            .expect("valid e-mail pattern")
+    })
+}
+
+/// Numbers after a phone cue, or in international notation anywhere
+fn phone_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i:\b(?:tel(?:efon)?|phone|mobil|handy|fon|rufnummer)\.?(?:[ \t]*nr\.?)?[ \t]*:?[ \t]*)(\+?\d[\d \t/()–-]{5,}\d)|(\+\d{2}[\d \t/()–-]{6,}\d)",
+        )
+        .expect("valid phone pattern")
+    })
+}
+
+/// Words that start a capitalized phrase after a cue but are no names
+const NOT_NAMES: &[&str] = &[
+    "Bei", "Bitte", "Wir", "Sie", "Ihr", "Ihre", "Für", "Fragen", "Die", "Der", "Das", "Unser",
+    "Unsere", "Please", "For", "Our", "The", "Per", "Via",
+];
+
+/// Contact persons, e-mail addresses and phone numbers in a text, or `None`
+/// if there are none
+pub fn extract_contact_info(text: &str) -> Option<ContactInfo> {
+    let mut names: Vec<String> = Vec::new();
+    for captures in name_pattern().captures_iter(text) {
+        let name = captures[1].trim().to_string();
+        let first_word = name.split_whitespace().next().unwrap_or_default();
+        // A single capitalized word is more likely a department than a person
+        let is_name = name.split_whitespace().count() > 1 && !NOT_NAMES.contains(&first_word);
+        if is_name && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    let mut emails: Vec<String> = Vec::new();
+    for found in email_pattern().find_iter(text) {
+        let email = found.as_str().trim_end_matches('.').to_lowercase();
+        if !emails.contains(&email) {
+            emails.push(email);
+        }
+    }
+
+    let mut phones: Vec<String> = Vec::new();
+    for captures in phone_pattern().captures_iter(text) {
+        let Some(number) = captures.get(1).or_else(|| captures.get(2)) else {
+            continue;
+        };
+        let number = number
+            .as_str()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let digits = number.chars().filter(char::is_ascii_digit).count();
+        if (7..=15).contains(&digits) && !phones.contains(&number) {
+            phones.push(number);
+        }
+    }
+
+    if names.is_empty() && emails.is_empty() && phones.is_empty() {
+        return None;
+    }
+    Some(ContactInfo {
+        best_effort: true,
+        names,
+        emails,
+        phones,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_contact_info() {
+        let text = "Ihre Ansprechpartnerin: Frau Dr. Anna Müller\n\
+                    Telefon: 0202 / 123 45-67\n\
+                    E-Mail: Bewerbung@Example-GmbH.de.\n\
+                    Bewerbungsschluss ist der 01.11.2025.";
+        let contact = extract_contact_info(text).unwrap();
+        assert!(contact.best_effort);
+        assert_eq!(contact.names, vec!["Frau Dr. Anna Müller"]);
+        assert_eq!(contact.emails, vec!["bewerbung@example-gmbh.de"]);
+        assert_eq!(contact.phones, vec!["0202 / 123 45-67"]);
+    }
+
+    #[test]
+    fn test_international_phone_without_cue() {
+        let contact = extract_contact_info("Rufen Sie uns an unter +49 30 1234567!").unwrap();
+        assert_eq!(contact.phones, vec!["+49 30 1234567"]);
+        assert!(contact.names.is_empty());
+    }
+
+    #[test]
+    fn test_name_on_next_line() {
+        let contact = extract_contact_info("Ansprechpartner:\nHerr Max Mustermann").unwrap();
+        assert_eq!(contact.names, vec!["Herr Max Mustermann"]);
+    }
+
+    #[test]
+    fn test_cue_without_name() {
+        // "Kontakt: Bitte ..." is no name
+        assert_eq!(
+            extract_contact_info(
+                "Kontakt: Bitte bewerben Sie sich online. Kontakt: Personalabteilung"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_no_contact_info() {
+        assert_eq!(
+            extract_contact_info("Vollzeit ab 01.11.2025, 38,5 Stunden, PLZ 42103"),
+            None
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Per-request context for tool invocations
+//!
+//! The MCP request metadata (`_meta`) is not passed into tool methods, so the
+//! transport layer stores it in a task-local [`RequestContext`] for the duration
+//! of a tool call. Tools read it with [`RequestContext::current`].
+//!
+//! Independent of the transport, every tool call gets a request ID of its own
+//! ([`record_request_id`]). It is returned as `request_id` in the result and
+//! recorded on the tool's tracing span, so all log lines of the call (upstream
+//! calls, retries, failed batch items) carry it and a result a user reports
+//! can be found in the logs.
+
+use crate::progress::ProgressToken;
+use std::future::Future;
+use tokio_util::sync::CancellationToken;
+
+tokio::task_local! {
+    static REQUEST_CONTEXT: RequestContext;
+}
+
+/// JSON-RPC request ID (string or integer)
+pub type RequestId = serde_json::Value;
+
+/// Metadata of the MCP request currently being handled
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    /// ID of the JSON-RPC request
+    pub request_id: Option<RequestId>,
+
+    /// Progress token supplied by the client in `_meta.progressToken`
+    pub progress_token: Option<ProgressToken>,
+
+    /// Cancelled when the client sends `notifications/cancelled` for this request
+    pub cancellation: CancellationToken,
+}
+
+impl RequestContext {
+    /// Create a context carrying the given progress token
+    pub fn with_progress_token(progress_token: ProgressToken) -> Self {
+        Self {
+            progress_token: Some(progress_token),
+            ..Default::default()
+        }
+    }
+
+    /// Context of the current tool call (empty outside of [`RequestContext::scope`])
+    pub fn current() -> Self {
+        REQUEST_CONTEXT
+            .try_with(|ctx| ctx.clone())
+            .unwrap_or_default()
+    }
+
+    /// Run `future` with this context installed
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        REQUEST_CONTEXT.scope(self, future).await
+    }
+}
+
+/// Generate a request ID: 16 random hex digits
+///
+/// Random rather than counted, so the IDs of several server processes
+/// writing to the same log do not collide.
+pub fn new_request_id() -> String {
+    hex::encode(rand::random::<[u8; 8]>())
+}
+
+/// Generate the request ID of a tool call and record it on the current span
+///
+/// Call first thing in a tool whose `#[instrument]` declares an empty
+/// `request_id` field; returns the ID for the tool's result.
+pub fn record_request_id() -> String {
+    let request_id = new_request_id();
+    tracing::Span::current().record("request_id", request_id.as_str());
+    request_id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_outside_scope_is_empty() {
+        let ctx = RequestContext::current();
+        assert!(ctx.request_id.is_none());
+        assert!(ctx.progress_token.is_none());
+        assert!(!ctx.cancellation.is_cancelled());
+    }
+
+    #[test]
+    fn test_new_request_id() {
+        let id = new_request_id();
+        assert_eq!(id.len(), 16);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(id, new_request_id());
+    }
+
+    #[tokio::test]
+    async fn test_scope_exposes_progress_token() {
+        let ctx = RequestContext::with_progress_token(serde_json::json!("token-1"));
+        let token = ctx
+            .scope(async { RequestContext::current().progress_token })
+            .await;
+        assert_eq!(token, Some(serde_json::json!("token-1")));
+    }
+
+    #[tokio::test]
+    async fn test_scope_shares_cancellation() {
+        let ctx = RequestContext::default();
+        let cancellation = ctx.cancellation.clone();
+        cancellation.cancel();
+
+        let cancelled = ctx
+            .scope(async { RequestContext::current().cancellation.is_cancelled() })
+            .await;
+        assert!(cancelled);
+    }
+}
@@ -0,0 +1,183 @@
+//! Postings abroad
+//!
+//! The Bundesagentur also lists jobs outside Germany, mostly in the
+//! neighbouring countries, which matters for cross-border commuters. The API
+//! names the country of a work location in German (`land`); searches can
+//! target a country (by German or English name or ISO code) or leave postings
+//! abroad out. Job summaries list foreign locations with their country,
+//! "Basel (4051), Schweiz", while German ones stay "Wuppertal (42103)".
+
+use serde_json::Value;
+
+/// Country of the Bundesagentur, as the API names it
+pub const HOME_COUNTRY: &str = "Deutschland";
+
+/// German country names with the other names and ISO codes they are known by
+const COUNTRIES: &[(&str, &[&str])] = &[
+    ("Deutschland", &["de", "deu", "germany"]),
+    ("Schweiz", &["ch", "che", "switzerland", "suisse"]),
+    ("Österreich", &["at", "aut", "austria", "oesterreich"]),
+    ("Niederlande", &["nl", "nld", "netherlands", "nederland"]),
+    ("Belgien", &["be", "bel", "belgium", "belgique"]),
+    ("Luxemburg", &["lu", "lux", "luxembourg"]),
+    ("Frankreich", &["fr", "fra", "france"]),
+    ("Dänemark", &["dk", "dnk", "denmark", "daenemark"]),
+    ("Polen", &["pl", "pol", "poland", "polska"]),
+    ("Tschechien", &["cz", "cze", "czechia"]),
+    ("Liechtenstein", &["li", "lie"]),
+    ("Italien", &["it", "ita", "italy", "italia"]),
+    ("Spanien", &["es", "esp", "spain", "espana"]),
+];
+
+/// German name of a known country given by name or ISO code
+fn known_country(country: &str) -> Option<&'static str> {
+    let country = country.trim().to_lowercase();
+    COUNTRIES
+        .iter()
+        .find(|(name, aliases)| {
+            name.to_lowercase() == country || aliases.contains(&country.as_str())
+        })
+        .map(|(name, _)| *name)
+}
+
+/// German name of a country given by name or ISO code, or the input if it is
+/// not known
+pub fn country_name(country: &str) -> String {
+    known_country(country).map_or_else(|| country.trim().to_string(), str::to_string)
+}
+
+/// Whether a country given by name or ISO code is Germany
+pub fn is_home_country(country: &str) -> bool {
+    country_name(country) == HOME_COUNTRY
+}
+
+/// Country of a work location of the Jobsuche API (`land`), if it is not
+/// Germany
+pub fn country_in(location: &Value) -> Option<String> {
+    let country = location.get("land")?.as_str()?.trim();
+    (!country.is_empty() && !is_home_country(country)).then(|| country_name(country))
+}
+
+/// Whether a job in `country` (`None` for Germany) lies in the wanted country
+pub fn in_country(country: Option<&str>, wanted: &str) -> bool {
+    let wanted = country_name(wanted);
+    match country {
+        Some(country) => country_name(country).to_lowercase() == wanted.to_lowercase(),
+        None => wanted == HOME_COUNTRY,
+    }
+}
+
+/// Parts of a location as listed in job summaries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Listing<'a> {
+    /// Town
+    pub town: &'a str,
+
+    /// Postal code
+    pub postal_code: Option<&'a str>,
+
+    /// Country, if not Germany
+    pub country: Option<&'a str>,
+}
+
+impl<'a> Listing<'a> {
+    /// Split "Wuppertal (42103)", "Basel (4051), Schweiz" or "Wien, Österreich"
+    pub fn parse(location: &'a str) -> Self {
+        let location = location.trim();
+        let (place, country) = match location.rsplit_once(", ") {
+            Some((place, country)) if place.ends_with(')') || known_country(country).is_some() => {
+                (place, Some(country.trim()))
+            }
+            _ => (location, None),
+        };
+        let (town, postal_code) = match place
+            .strip_suffix(')')
+            .and_then(|rest| rest.rsplit_once('('))
+        {
+            Some((town, code)) => (town.trim(), Some(code.trim())),
+            None => (place, None),
+        };
+        Self {
+            town,
+            postal_code: postal_code.filter(|code| !code.is_empty()),
+            country: country.filter(|country| !country.is_empty()),
+        }
+    }
+}
+
+/// Location as listed in job summaries
+pub fn format_listing(town: &str, postal_code: Option<&str>, country: Option<&str>) -> String {
+    let mut listing = town.to_string();
+    if let Some(code) = postal_code {
+        listing.push_str(&format!(" ({})", code));
+    }
+    if let Some(country) = country {
+        listing.push_str(&format!(", {}", country));
+    }
+    listing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_country_name() {
+        assert_eq!(country_name("CH"), "Schweiz");
+        assert_eq!(country_name(" austria "), "Österreich");
+        assert_eq!(country_name("niederlande"), "Niederlande");
+        assert_eq!(country_name("Norwegen"), "Norwegen");
+        assert!(is_home_country("de"));
+        assert!(!is_home_country("Schweiz"));
+    }
+
+    #[test]
+    fn test_country_in() {
+        assert_eq!(
+            country_in(&json!({"ort": "Basel", "land": "Schweiz"})),
+            Some("Schweiz".to_string())
+        );
+        assert_eq!(
+            country_in(&json!({"ort": "Wuppertal", "land": "Deutschland"})),
+            None
+        );
+        assert_eq!(country_in(&json!({"ort": "Wuppertal"})), None);
+    }
+
+    #[test]
+    fn test_in_country() {
+        assert!(in_country(Some("Schweiz"), "CH"));
+        assert!(!in_country(Some("Schweiz"), "AT"));
+        assert!(in_country(None, "Germany"));
+        assert!(!in_country(None, "NL"));
+    }
+
+    #[test]
+    fn test_listing() {
+        let listing = format_listing("Basel", Some("4051"), Some("Schweiz"));
+        assert_eq!(listing, "Basel (4051), Schweiz");
+        assert_eq!(
+            Listing::parse(&listing),
+            Listing {
+                town: "Basel",
+                postal_code: Some("4051"),
+                country: Some("Schweiz"),
+            }
+        );
+        assert_eq!(
+            Listing::parse("Frankfurt (Oder) (15230)"),
+            Listing {
+                town: "Frankfurt (Oder)",
+                postal_code: Some("15230"),
+                country: None,
+            }
+        );
+
+        let listing = format_listing("Wien", None, Some("Österreich"));
+        assert_eq!(listing, "Wien, Österreich");
+        assert_eq!(Listing::parse(&listing).town, "Wien");
+        assert_eq!(Listing::parse("Halle, Saale").country, None);
+        assert_eq!(Listing::parse("Wuppertal").postal_code, None);
+    }
+}
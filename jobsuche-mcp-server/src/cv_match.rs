@@ -0,0 +1,401 @@
+//! Matching a CV against job postings
+//!
+//! Skills, languages and keywords are extracted from the CV and from each job
+//! with the same deterministic rules, and the overlap is condensed into a score
+//! from 0 to 100. This is a cheap pre-filter: it tells which postings are worth
+//! reading in full, not whether the candidate fits.
+
+use crate::skills::{extract_language_requirements, extract_skills};
+use crate::GetJobDetailsResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Maximum number of jobs matched at once
+pub const MAX_CV_JOBS: usize = 10;
+
+/// Number of keywords taken from a job description
+const JOB_KEYWORDS: usize = 15;
+
+/// Minimum length of a keyword
+const MIN_KEYWORD_LENGTH: usize = 5;
+
+/// Weight of the skill overlap in the score
+const SKILLS_WEIGHT: f64 = 60.0;
+
+/// Weight of the keyword overlap in the score
+const KEYWORDS_WEIGHT: f64 = 30.0;
+
+/// Weight of the required languages in the score
+const LANGUAGES_WEIGHT: f64 = 10.0;
+
+/// Frequent words of job ads that say nothing about the job
+const STOPWORDS: &[&str] = &[
+    "sowie",
+    "unsere",
+    "unser",
+    "unseren",
+    "unserem",
+    "unserer",
+    "ihren",
+    "ihrem",
+    "ihrer",
+    "ihnen",
+    "werden",
+    "wurde",
+    "haben",
+    "bieten",
+    "bietet",
+    "suchen",
+    "aufgaben",
+    "profil",
+    "bereich",
+    "einer",
+    "eines",
+    "einen",
+    "einem",
+    "dieser",
+    "diese",
+    "dieses",
+    "durch",
+    "unter",
+    "zwischen",
+    "sowohl",
+    "gerne",
+    "freuen",
+    "bewerbung",
+    "bewerben",
+    "möglich",
+    "erste",
+    "unterstützung",
+    "unternehmen",
+    "mitarbeiter",
+    "mitarbeiterin",
+    "mitarbeitende",
+    "stelle",
+    "tätigkeit",
+    "mindestens",
+    "wünschenswert",
+    "idealerweise",
+    "kenntnisse",
+    "erfahrung",
+    "erfahrungen",
+    "position",
+    "unbefristet",
+    "befristet",
+    "vollzeit",
+    "teilzeit",
+    "arbeitszeit",
+    "arbeitsplatz",
+    "zusammenarbeit",
+    "selbstständig",
+    "selbständig",
+    "sicher",
+    "sicheren",
+    "abgeschlossene",
+    "abgeschlossenes",
+    "attraktive",
+    "attraktives",
+    "vielseitige",
+    "spannende",
+    "interessante",
+    "abwechslungsreiche",
+    "kollegen",
+    "kolleginnen",
+    "there",
+    "their",
+    "which",
+    "would",
+    "about",
+    "experience",
+    "skills",
+    "company",
+    "offer",
+    "looking",
+    "working",
+];
+
+/// Parameters for match_cv
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MatchCvParams {
+    /// CV as plain text
+    pub cv_text: String,
+
+    /// Reference numbers of the jobs to match against (1 to 10)
+    pub reference_numbers: Vec<String>,
+}
+
+/// Match of the CV against one job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CvJobMatch {
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Job title
+    pub title: Option<String>,
+
+    /// Employer name
+    pub employer: Option<String>,
+
+    /// Match score from 0 to 100: skills (60), keywords (30) and required
+    /// languages (10); parts the job does not state are left out and the rest
+    /// scaled up
+    pub score: u8,
+
+    /// Skills the job asks for that the CV mentions
+    pub matching_skills: Vec<String>,
+
+    /// Skills the job asks for that the CV does not mention
+    pub missing_skills: Vec<String>,
+
+    /// Frequent words of the job description found in the CV
+    pub matching_keywords: Vec<String>,
+
+    /// Frequent words of the job description not found in the CV
+    pub missing_keywords: Vec<String>,
+
+    /// Required languages the CV does not mention
+    pub missing_languages: Vec<String>,
+}
+
+/// Result from match_cv
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MatchCvResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Skills found in the CV
+    pub cv_skills: Vec<String>,
+
+    /// Languages found in the CV
+    pub cv_languages: Vec<String>,
+
+    /// Number of matched jobs
+    pub jobs_count: usize,
+
+    /// Matches, best first
+    pub jobs: Vec<CvJobMatch>,
+
+    /// Jobs left out because their details could not be fetched
+    pub missing_details: Vec<String>,
+}
+
+/// Lowercase words of a text
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// The most frequent significant words of a text, most frequent first
+pub fn keywords(text: &str, limit: usize) -> Vec<String> {
+    let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+    let significant = words(text).filter(|word| {
+        word.chars().count() >= MIN_KEYWORD_LENGTH
+            && word.chars().all(char::is_alphabetic)
+            && !STOPWORDS.contains(&word.as_str())
+    });
+    for (position, word) in significant.enumerate() {
+        counts.entry(word).or_insert((0, position)).0 += 1;
+    }
+    let mut ranked: Vec<(String, (usize, usize))> = counts.into_iter().collect();
+    // Ties in order of first appearance
+    ranked.sort_by(|(_, (a_count, a_pos)), (_, (b_count, b_pos))| {
+        b_count.cmp(a_count).then(a_pos.cmp(b_pos))
+    });
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(word, _)| word)
+        .collect()
+}
+
+/// What is extracted from the CV once for all jobs
+#[derive(Debug, Clone)]
+pub struct Cv {
+    text: String,
+    skills: Vec<String>,
+    languages: Vec<String>,
+}
+
+impl Cv {
+    /// Extract skills and languages from a CV
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_lowercase(),
+            skills: extract_skills(text)
+                .into_iter()
+                .map(|skill| skill.name)
+                .collect(),
+            languages: extract_language_requirements(text)
+                .into_iter()
+                .map(|language| language.language)
+                .collect(),
+        }
+    }
+
+    /// Skills found in the CV
+    pub fn skills(&self) -> &[String] {
+        &self.skills
+    }
+
+    /// Languages found in the CV
+    pub fn languages(&self) -> &[String] {
+        &self.languages
+    }
+
+    /// Match the CV against a job; `description` is the plain-text description
+    pub fn match_job(&self, details: &GetJobDetailsResult, description: &str) -> CvJobMatch {
+        let (matching_skills, missing_skills): (Vec<String>, Vec<String>) = details
+            .extracted_skills
+            .iter()
+            .map(|skill| skill.name.clone())
+            .partition(|skill| self.skills.contains(skill));
+
+        // Compound words make substring matching more useful than word matching
+        let job_text = format!(
+            "{}\n{}",
+            details.title.as_deref().unwrap_or_default(),
+            description
+        );
+        let (matching_keywords, missing_keywords): (Vec<String>, Vec<String>) =
+            keywords(&job_text, JOB_KEYWORDS)
+                .into_iter()
+                .partition(|keyword| self.text.contains(keyword.as_str()));
+
+        let required_languages: Vec<&str> = details
+            .language_requirements
+            .iter()
+            .filter(|requirement| requirement.required)
+            .map(|requirement| requirement.language.as_str())
+            .collect();
+        let missing_languages: Vec<String> = required_languages
+            .iter()
+            .filter(|language| !self.languages.iter().any(|known| known == *language))
+            .map(|language| language.to_string())
+            .collect();
+
+        let mut points = 0.0;
+        let mut total = 0.0;
+        let mut add = |weight: f64, matched: usize, asked: usize| {
+            if asked > 0 {
+                points += weight * matched as f64 / asked as f64;
+                total += weight;
+            }
+        };
+        add(
+            SKILLS_WEIGHT,
+            matching_skills.len(),
+            matching_skills.len() + missing_skills.len(),
+        );
+        add(
+            KEYWORDS_WEIGHT,
+            matching_keywords.len(),
+            matching_keywords.len() + missing_keywords.len(),
+        );
+        add(
+            LANGUAGES_WEIGHT,
+            required_languages.len() - missing_languages.len(),
+            required_languages.len(),
+        );
+        let score = if total > 0.0 {
+            (100.0 * points / total).round() as u8
+        } else {
+            0
+        };
+
+        CvJobMatch {
+            reference_number: details.reference_number.clone(),
+            title: details.title.clone(),
+            employer: details.employer.clone(),
+            score,
+            matching_skills,
+            missing_skills,
+            matching_keywords,
+            missing_keywords,
+            missing_languages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CV: &str = "Softwareentwickler mit 5 Jahren Erfahrung in Rust und Docker. \
+                      Schwerpunkt Backend und Datenbanken (PostgreSQL). \
+                      Sprachen: Deutsch Muttersprache, Englisch C1.";
+
+    fn details(description: &str, extra: serde_json::Value) -> GetJobDetailsResult {
+        let mut value = serde_json::json!({
+            "reference_number": "REF-1",
+            "title": "Backend Entwickler",
+            "description": description,
+            "raw_data": {}
+        });
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_keywords() {
+        assert_eq!(
+            keywords(
+                "Backend Entwicklung: Backend Services mit Kubernetes. Unsere Backend Services.",
+                3
+            ),
+            vec!["backend", "services", "entwicklung"]
+        );
+    }
+
+    #[test]
+    fn test_cv_extraction() {
+        let cv = Cv::new(CV);
+        assert!(cv.skills().contains(&"Rust".to_string()));
+        assert!(cv.skills().contains(&"Docker".to_string()));
+        assert_eq!(cv.languages(), ["German", "English"]);
+    }
+
+    #[test]
+    fn test_match_job() {
+        let description = "Wir suchen Verstärkung für unser Backend Team. \
+                           Du entwickelst Backend Services mit Rust und Kubernetes.";
+        let job = details(
+            description,
+            serde_json::json!({
+                "extracted_skills": [
+                    {"name": "Rust", "category": "programming_language"},
+                    {"name": "Kubernetes", "category": "tool"}
+                ],
+                "language_requirements": [
+                    {"language": "German", "level": "C1", "required": true},
+                    {"language": "French", "required": true}
+                ]
+            }),
+        );
+
+        let matched = Cv::new(CV).match_job(&job, description);
+        assert_eq!(matched.matching_skills, vec!["Rust"]);
+        assert_eq!(matched.missing_skills, vec!["Kubernetes"]);
+        assert_eq!(matched.missing_languages, vec!["French"]);
+        assert!(matched.matching_keywords.contains(&"backend".to_string()));
+        assert!(matched.missing_keywords.contains(&"services".to_string()));
+        assert!(
+            matched.score > 0 && matched.score < 100,
+            "{}",
+            matched.score
+        );
+    }
+
+    #[test]
+    fn test_match_job_without_requirements() {
+        let matched = Cv::new(CV).match_job(&details("", serde_json::json!({"title": null})), "");
+        assert_eq!(matched.score, 0);
+        assert!(matched.missing_keywords.is_empty());
+    }
+}
@@ -0,0 +1,259 @@
+//! Date normalization
+//!
+//! The API delivers dates as plain dates, timestamps or occasionally in German
+//! notation (`01.11.2025`). Responses use ISO 8601 throughout: dates as
+//! `YYYY-MM-DD` and date ranges as intervals (`2025-11-01/2025-11-30`, open ends
+//! as `..`). Values that cannot be parsed are passed on in a `*_raw` field
+//! instead of being dropped.
+
+use time::{Date, Duration, Month, OffsetDateTime, Time};
+
+/// Parse a date in ISO (`2025-11-01`, `2025-11-01T08:00:00Z`), German
+/// (`01.11.2025`) or compact (`20251101`) notation
+pub fn parse_date(raw: &str) -> Option<Date> {
+    let raw = raw.trim();
+    // Drop the time of timestamps
+    let date = raw.split(['T', ' ']).next().unwrap_or_default();
+
+    let (year, month, day) = if let Some((day, rest)) = date.split_once('.') {
+        let (month, year) = rest.split_once('.')?;
+        (year, month, day)
+    } else if date.len() == 8 && date.chars().all(|c| c.is_ascii_digit()) {
+        (&date[..4], &date[4..6], &date[6..])
+    } else {
+        let mut parts = date.splitn(3, '-');
+        (parts.next()?, parts.next()?, parts.next()?)
+    };
+
+    if year.len() != 4 {
+        return None;
+    }
+    let month = Month::try_from(month.parse::<u8>().ok()?).ok()?;
+    Date::from_calendar_date(year.parse().ok()?, month, day.parse().ok()?).ok()
+}
+
+/// ISO 8601 date, or the raw value if it cannot be parsed
+///
+/// Returns `(normalized, raw)`; exactly one is set for a non-empty value.
+pub fn normalize_date(raw: Option<&str>) -> (Option<String>, Option<String>) {
+    match raw.map(str::trim).filter(|raw| !raw.is_empty()) {
+        None => (None, None),
+        Some(raw) => match parse_date(raw) {
+            Some(date) => (Some(date.to_string()), None),
+            None => (None, Some(raw.to_string())),
+        },
+    }
+}
+
+/// ISO 8601 interval of a date range, or the raw bounds if they cannot be parsed
+///
+/// Open ends are written as `..` (`2025-11-01/..`). Returns `(normalized, raw)`
+/// like [`normalize_date`]; the raw form is `<from> - <until>`.
+pub fn normalize_period(
+    from: Option<&str>,
+    until: Option<&str>,
+) -> (Option<String>, Option<String>) {
+    let from = from.map(str::trim).filter(|from| !from.is_empty());
+    let until = until.map(str::trim).filter(|until| !until.is_empty());
+    if from.is_none() && until.is_none() {
+        return (None, None);
+    }
+
+    let bound = |value: Option<&str>| match value {
+        None => Some("..".to_string()),
+        Some(value) => parse_date(value).map(|date| date.to_string()),
+    };
+    match (bound(from), bound(until)) {
+        (Some(from), Some(until)) => (Some(format!("{}/{}", from, until)), None),
+        _ => (
+            None,
+            Some(format!(
+                "{} - {}",
+                from.unwrap_or_default(),
+                until.unwrap_or_default()
+            )),
+        ),
+    }
+}
+
+/// Start of an ISO 8601 interval produced by [`normalize_period`]
+pub fn period_start(period: &str) -> Option<&str> {
+    period.split('/').next().filter(|start| *start != "..")
+}
+
+/// Whole days from a date to today (0 for today, negative for future dates)
+pub fn days_since(date: &str, today: Date) -> Option<i64> {
+    parse_date(date).map(|date| (today - date).whole_days())
+}
+
+/// Age in words for a number of days ("today", "3 days ago", "2 weeks ago")
+pub fn relative_age(days: i64) -> String {
+    let plural =
+        |count: i64, unit: &str| format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" });
+    match days {
+        i64::MIN..=-1 => format!("in {}", plural(-days, "day")),
+        0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        2..=13 => format!("{} ago", plural(days, "day")),
+        14..=59 => format!("{} ago", plural(days / 7, "week")),
+        60..=729 => format!("{} ago", plural(days / 30, "month")),
+        _ => format!("{} ago", plural(days / 365, "year")),
+    }
+}
+
+/// Today's date in Germany
+///
+/// Publication dates of the API are German local dates, so "today" has to be
+/// the date in Berlin; the UTC date lags behind it until 01:00 or 02:00.
+pub fn today() -> Date {
+    berlin_date(OffsetDateTime::now_utc())
+}
+
+/// Date in Europe/Berlin at an instant
+///
+/// Germany follows the EU daylight saving rules: CEST (UTC+2) from 01:00 UTC on
+/// the last Sunday of March until 01:00 UTC on the last Sunday of October, CET
+/// (UTC+1) otherwise.
+pub fn berlin_date(instant: OffsetDateTime) -> Date {
+    let utc = instant.to_offset(time::UtcOffset::UTC);
+    let switch_time = Time::from_hms(1, 0, 0).expect("valid time");
+    let summer_start = last_sunday(utc.year(), Month::March)
+        .with_time(switch_time)
+        .assume_utc();
+    let summer_end = last_sunday(utc.year(), Month::October)
+        .with_time(switch_time)
+        .assume_utc();
+
+    let offset_hours = if utc >= summer_start && utc < summer_end {
+        2
+    } else {
+        1
+    };
+    (utc + Duration::hours(offset_hours)).date()
+}
+
+/// Last Sunday of a month
+fn last_sunday(year: i32, month: Month) -> Date {
+    let last_day = Date::from_calendar_date(year, month, time::util::days_in_month(month, year))
+        .expect("valid date");
+    last_day - Duration::days(last_day.weekday().number_days_from_sunday() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    #[test]
+    fn test_parse_date_formats() {
+        let expected = Some(date!(2025 - 11 - 01));
+        assert_eq!(parse_date("2025-11-01"), expected);
+        assert_eq!(parse_date("2025-11-01T08:15:00.123"), expected);
+        assert_eq!(parse_date("2025-11-01 08:15:00"), expected);
+        assert_eq!(parse_date("01.11.2025"), expected);
+        assert_eq!(parse_date("1.11.2025"), expected);
+        assert_eq!(parse_date("20251101"), expected);
+        assert_eq!(parse_date("2025-02-30"), None);
+        assert_eq!(parse_date("01.11.25"), None);
+        assert_eq!(parse_date("sofort"), None);
+    }
+
+    #[test]
+    fn test_normalize_date() {
+        assert_eq!(
+            normalize_date(Some("15.10.2025")),
+            (Some("2025-10-15".to_string()), None)
+        );
+        assert_eq!(
+            normalize_date(Some("ab sofort")),
+            (None, Some("ab sofort".to_string()))
+        );
+        assert_eq!(normalize_date(Some(" ")), (None, None));
+        assert_eq!(normalize_date(None), (None, None));
+    }
+
+    #[test]
+    fn test_normalize_period() {
+        assert_eq!(
+            normalize_period(Some("2025-10-01"), Some("30.11.2025")),
+            (Some("2025-10-01/2025-11-30".to_string()), None)
+        );
+        assert_eq!(
+            normalize_period(Some("2025-11-01"), None),
+            (Some("2025-11-01/..".to_string()), None)
+        );
+        assert_eq!(
+            normalize_period(None, Some("2025-11-30")),
+            (Some("../2025-11-30".to_string()), None)
+        );
+        assert_eq!(
+            normalize_period(Some("nach Absprache"), None),
+            (None, Some("nach Absprache - ".to_string()))
+        );
+        assert_eq!(normalize_period(None, None), (None, None));
+    }
+
+    #[test]
+    fn test_period_start_and_days_since() {
+        assert_eq!(period_start("2025-11-01/.."), Some("2025-11-01"));
+        assert_eq!(period_start("../2025-11-30"), None);
+
+        let today = date!(2025 - 10 - 20);
+        assert_eq!(days_since("2025-10-15", today), Some(5));
+        assert_eq!(days_since("2025-10-20T23:59:00", today), Some(0));
+        assert_eq!(days_since("2025-10-21", today), Some(-1));
+        assert_eq!(days_since("unbekannt", today), None);
+    }
+
+    #[test]
+    fn test_relative_age() {
+        assert_eq!(relative_age(0), "today");
+        assert_eq!(relative_age(1), "yesterday");
+        assert_eq!(relative_age(3), "3 days ago");
+        assert_eq!(relative_age(20), "2 weeks ago");
+        assert_eq!(relative_age(95), "3 months ago");
+        assert_eq!(relative_age(800), "2 years ago");
+        assert_eq!(relative_age(-1), "in 1 day");
+    }
+
+    #[test]
+    fn test_berlin_date() {
+        // CET: 23:30 UTC is already the next day in Berlin
+        assert_eq!(
+            berlin_date(datetime!(2025-11-03 23:30 UTC)),
+            date!(2025 - 11 - 04)
+        );
+        assert_eq!(
+            berlin_date(datetime!(2025-11-03 22:59 UTC)),
+            date!(2025 - 11 - 03)
+        );
+        // CEST: 22:30 UTC is 00:30 in Berlin
+        assert_eq!(
+            berlin_date(datetime!(2025-07-01 22:30 UTC)),
+            date!(2025 - 07 - 02)
+        );
+        assert_eq!(
+            berlin_date(datetime!(2025-07-01 21:59 UTC)),
+            date!(2025 - 07 - 01)
+        );
+    }
+
+    #[test]
+    fn test_berlin_date_around_dst_switch() {
+        // Summer time 2025 ran from March 30 to October 26
+        assert_eq!(last_sunday(2025, Month::March), date!(2025 - 03 - 30));
+        assert_eq!(last_sunday(2025, Month::October), date!(2025 - 10 - 26));
+        assert_eq!(
+            berlin_date(datetime!(2025-03-29 22:30 UTC)),
+            date!(2025 - 03 - 29)
+        );
+        assert_eq!(
+            berlin_date(datetime!(2025-10-25 22:30 UTC)),
+            date!(2025 - 10 - 26)
+        );
+        assert_eq!(
+            berlin_date(datetime!(2025-10-26 22:30 UTC)),
+            date!(2025 - 10 - 26)
+        );
+    }
+}
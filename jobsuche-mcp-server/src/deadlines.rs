@@ -0,0 +1,404 @@
+//! Application deadlines and start dates
+//!
+//! The API has no deadline field, but some employers name one in the
+//! description ("Bewerbungsschluss: 15.11.2026", "Bitte bewerben Sie sich bis
+//! zum 30. November"). Such phrases are parsed into ISO 8601 dates; dates
+//! without a year are taken as the next occurrence. Favorites keep their
+//! deadline and start date (from `eintrittszeitraum`), so upcoming
+//! application windows can be listed by urgency without calling the API.
+
+use crate::dates::parse_date;
+use crate::favorites::Favorite;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use time::{Date, Duration, Month};
+
+/// Default look-ahead of list_upcoming_deadlines in days
+pub const DEFAULT_DEADLINE_DAYS: u64 = 30;
+
+/// Maximum look-ahead of list_upcoming_deadlines in days
+pub const MAX_DEADLINE_DAYS: u64 = 365;
+
+/// Days a passed application deadline stays listed with `include_passed`
+pub const PASSED_DEADLINE_DAYS: i64 = 14;
+
+/// Characters after a deadline cue searched for the date
+const DATE_WINDOW_CHARS: usize = 80;
+
+/// Days a date without year may lie in the past before it is taken as next year's
+const YEARLESS_GRACE_DAYS: i64 = 30;
+
+/// Month names and abbreviations (German and English) with their number,
+/// full names first so they win over their abbreviations
+const MONTHS: &[(&str, u8)] = &[
+    ("januar", 1),
+    ("january", 1),
+    ("februar", 2),
+    ("february", 2),
+    ("märz", 3),
+    ("maerz", 3),
+    ("march", 3),
+    ("april", 4),
+    ("mai", 5),
+    ("may", 5),
+    ("juni", 6),
+    ("june", 6),
+    ("juli", 7),
+    ("july", 7),
+    ("august", 8),
+    ("september", 9),
+    ("oktober", 10),
+    ("october", 10),
+    ("november", 11),
+    ("dezember", 12),
+    ("december", 12),
+    ("jan", 1),
+    ("feb", 2),
+    ("mär", 3),
+    ("mar", 3),
+    ("apr", 4),
+    ("jun", 6),
+    ("jul", 7),
+    ("aug", 8),
+    ("sept", 9),
+    ("sep", 9),
+    ("okt", 10),
+    ("oct", 10),
+    ("nov", 11),
+    ("dez", 12),
+    ("dec", 12),
+];
+
+/// Phrases introducing an application deadline
+fn cue_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(?:bewerbungsschluss|bewerbungsfrist|bewerbungsende|einsendeschluss|ausschreibungsende|bewerb\w*(?:[ \t]+\S+){0,4}?[ \t]+(?:bis|spätestens)|application deadline|deadline|closing date|apply(?:[ \t]+\S+){0,3}?[ \t]+(?:by|before|until))\b",
+        )
+        .expect("valid deadline cue pattern")
+    })
+}
+
+/// Dates as "2026-11-15", "15.11.2026", "15.11.", "15. November 2026" or
+/// "November 15, 2026"
+fn date_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        let months = MONTHS
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join("|");
+        Regex::new(&format!(
+            r"(?i)\b(?:(?P<iso_y>\d{{4}})-(?P<iso_m>\d{{2}})-(?P<iso_d>\d{{2}})|(?P<num_d>\d{{1,2}})\.[ \t]?(?P<num_m>\d{{1,2}})\.(?:[ \t]?(?P<num_y>\d{{4}}|\d{{2}})\b)?|(?P<de_d>\d{{1,2}})\.?[ \t]+(?P<de_m>{months})\b\.?(?:[ \t]+(?P<de_y>\d{{4}}))?|(?P<en_m>{months})\b\.?[ \t]+(?P<en_d>\d{{1,2}})(?:st|nd|rd|th)?\b,?(?:[ \t]+(?P<en_y>\d{{4}}))?)",
+        ))
+        .expect("valid date pattern")
+    })
+}
+
+/// Application deadline named in a description (ISO 8601)
+///
+/// Only dates shortly after a deadline cue count, so contract end dates
+/// ("befristet bis 31.12.2027") are not mistaken for deadlines.
+pub fn extract_deadline(text: &str, today: Date) -> Option<String> {
+    cue_pattern().find_iter(text).find_map(|cue| {
+        let window: String = text[cue.end()..]
+            .chars()
+            .take_while(|c| *c != '\n')
+            .take(DATE_WINDOW_CHARS)
+            .collect();
+        let date = parse_date_match(&window, today)?;
+        Some(date.to_string())
+    })
+}
+
+/// First date in a text; dates without year are the next occurrence
+fn parse_date_match(text: &str, today: Date) -> Option<Date> {
+    let captures = date_pattern().captures(text)?;
+    let group = |name: &str| captures.name(name).map(|m| m.as_str());
+    let month_of = |name: &str| {
+        let name = group(name)?.to_lowercase();
+        MONTHS
+            .iter()
+            .find(|(month, _)| *month == name)
+            .map(|(_, number)| *number)
+    };
+
+    let (day, month, year) = if let Some(year) = group("iso_y") {
+        (group("iso_d")?, group("iso_m")?.parse().ok()?, Some(year))
+    } else if let Some(day) = group("num_d") {
+        (day, group("num_m")?.parse().ok()?, group("num_y"))
+    } else if let Some(day) = group("de_d") {
+        (day, month_of("de_m")?, group("de_y"))
+    } else {
+        (group("en_d")?, month_of("en_m")?, group("en_y"))
+    };
+    let day: u8 = day.parse().ok()?;
+    let month = Month::try_from(month).ok()?;
+
+    match year {
+        Some(year) => {
+            let year: i32 = year.parse().ok()?;
+            let year = if year < 100 { 2000 + year } else { year };
+            Date::from_calendar_date(year, month, day).ok()
+        }
+        None => {
+            let date = Date::from_calendar_date(today.year(), month, day).ok()?;
+            if date < today - Duration::days(YEARLESS_GRACE_DAYS) {
+                Date::from_calendar_date(today.year() + 1, month, day).ok()
+            } else {
+                Some(date)
+            }
+        }
+    }
+}
+
+/// Kind of a tracked date
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadlineKind {
+    /// Last day to apply, from the description
+    Application,
+    /// Start of employment, from the entry period
+    Start,
+}
+
+/// How soon a date is due
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Urgency {
+    /// Already passed
+    Passed,
+    /// Due today
+    Today,
+    /// Due within 7 days
+    ThisWeek,
+    /// Due within 30 days
+    ThisMonth,
+    /// Due later
+    Later,
+}
+
+impl Urgency {
+    /// Urgency of a date `days_left` days ahead
+    pub fn of(days_left: i64) -> Self {
+        match days_left {
+            i64::MIN..=-1 => Self::Passed,
+            0 => Self::Today,
+            1..=7 => Self::ThisWeek,
+            8..=30 => Self::ThisMonth,
+            _ => Self::Later,
+        }
+    }
+}
+
+/// Parameters for list_upcoming_deadlines
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListUpcomingDeadlinesParams {
+    /// Days ahead to look (default: 30, max: 365)
+    #[schemars(range(max = 365))]
+    pub within_days: Option<u64>,
+
+    /// Only favorites carrying this label
+    pub label: Option<String>,
+
+    /// Also list application deadlines that passed in the last 14 days
+    /// (default: false)
+    pub include_passed: Option<bool>,
+}
+
+/// A deadline or start date of a favorite
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UpcomingDeadline {
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Job title
+    pub title: Option<String>,
+
+    /// Employer name
+    pub employer: Option<String>,
+
+    /// Kind of date
+    pub kind: DeadlineKind,
+
+    /// Date (YYYY-MM-DD)
+    pub date: String,
+
+    /// Days from today to the date (negative if passed)
+    pub days_left: i64,
+
+    /// How soon the date is due
+    pub urgency: Urgency,
+}
+
+/// Result from list_upcoming_deadlines
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListUpcomingDeadlinesResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Today's date (YYYY-MM-DD) the days are counted from
+    pub today: String,
+
+    /// Number of dates returned
+    pub deadlines_count: usize,
+
+    /// Dates, most urgent first; application deadlines before start dates on
+    /// the same day
+    pub deadlines: Vec<UpcomingDeadline>,
+
+    /// Favorites without deadline or start date (saved before dates were
+    /// tracked, or the posting names none); save them again to refresh
+    pub favorites_without_dates: usize,
+}
+
+/// Deadlines and start dates of favorites due within `within_days`, most
+/// urgent first
+pub fn upcoming_deadlines(
+    favorites: &[Favorite],
+    today: Date,
+    within_days: i64,
+    include_passed: bool,
+) -> Vec<UpcomingDeadline> {
+    let mut deadlines: Vec<UpcomingDeadline> = favorites
+        .iter()
+        .flat_map(|favorite| {
+            [
+                (DeadlineKind::Application, &favorite.application_deadline),
+                (DeadlineKind::Start, &favorite.start_date),
+            ]
+            .into_iter()
+            .filter_map(move |(kind, date)| {
+                let date = parse_date(date.as_deref()?)?;
+                let days_left = (date - today).whole_days();
+                let earliest = match kind {
+                    DeadlineKind::Application if include_passed => -PASSED_DEADLINE_DAYS,
+                    _ => 0,
+                };
+                (earliest..=within_days)
+                    .contains(&days_left)
+                    .then(|| UpcomingDeadline {
+                        reference_number: favorite.reference_number.clone(),
+                        title: favorite.title.clone(),
+                        employer: favorite.employer.clone(),
+                        kind,
+                        date: date.to_string(),
+                        days_left,
+                        urgency: Urgency::of(days_left),
+                    })
+            })
+        })
+        .collect();
+    deadlines.sort_by_key(|deadline| (deadline.days_left, deadline.kind));
+    deadlines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    const TODAY: Date = date!(2026 - 10 - 16);
+
+    fn deadline(text: &str) -> Option<String> {
+        extract_deadline(text, TODAY)
+    }
+
+    #[test]
+    fn test_extract_deadline_formats() {
+        let expected = Some("2026-11-15".to_string());
+        assert_eq!(deadline("Bewerbungsschluss: 15.11.2026"), expected);
+        assert_eq!(deadline("Bewerbungsfrist endet am 15.11.26"), expected);
+        assert_eq!(
+            deadline("Bitte bewerben Sie sich bis zum 15. November 2026."),
+            expected
+        );
+        assert_eq!(
+            deadline("Ihre Bewerbung senden Sie bitte bis spätestens 15.11."),
+            expected
+        );
+        assert_eq!(deadline("Application deadline: 2026-11-15"), expected);
+        assert_eq!(deadline("Please apply by November 15th, 2026"), expected);
+    }
+
+    #[test]
+    fn test_extract_deadline_needs_cue() {
+        assert_eq!(deadline("Die Stelle ist befristet bis 31.12.2027."), None);
+        assert_eq!(deadline("Start am 01.01.2027, Bewerbungen gerne."), None);
+        assert_eq!(deadline("Bewerbungsschluss\n15.11.2026"), None);
+    }
+
+    #[test]
+    fn test_yearless_date_is_next_occurrence() {
+        assert_eq!(
+            deadline("Bewerbungsschluss: 10.10."),
+            Some("2026-10-10".to_string())
+        );
+        assert_eq!(
+            deadline("Bewerbungsschluss: 15. Januar"),
+            Some("2027-01-15".to_string())
+        );
+    }
+
+    fn favorite(reference_number: &str, deadline: Option<&str>, start: Option<&str>) -> Favorite {
+        serde_json::from_value(serde_json::json!({
+            "reference_number": reference_number,
+            "title": "Job",
+            "employer": "BARMER",
+            "location": null,
+            "salary": null,
+            "employment_type": null,
+            "labels": [],
+            "saved_at": "2026-10-01T08:00:00Z",
+            "updated_at": "2026-10-01T08:00:00Z",
+            "application_deadline": deadline,
+            "start_date": start
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_upcoming_deadlines_by_urgency() {
+        let favorites = [
+            favorite("A", Some("2026-11-10"), Some("2026-10-20")),
+            favorite("B", Some("2026-10-20"), Some("2027-03-01")),
+            favorite("C", Some("2026-10-10"), None),
+            favorite("D", None, None),
+        ];
+        let listed = |deadlines: Vec<UpcomingDeadline>| {
+            deadlines
+                .into_iter()
+                .map(|deadline| (deadline.reference_number, deadline.kind, deadline.urgency))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            listed(upcoming_deadlines(&favorites, TODAY, 30, false)),
+            [
+                (
+                    "B".to_string(),
+                    DeadlineKind::Application,
+                    Urgency::ThisWeek
+                ),
+                ("A".to_string(), DeadlineKind::Start, Urgency::ThisWeek),
+                (
+                    "A".to_string(),
+                    DeadlineKind::Application,
+                    Urgency::ThisMonth
+                ),
+            ]
+        );
+        let with_passed = upcoming_deadlines(&favorites, TODAY, 7, true);
+        assert_eq!(with_passed[0].reference_number, "C");
+        assert_eq!(with_passed[0].days_left, -6);
+        assert_eq!(with_passed.len(), 3);
+    }
+}
@@ -0,0 +1,321 @@
+//! Cleanup of job descriptions
+//!
+//! `stellenbeschreibung` is free text entered by employers and often contains
+//! HTML markup and entities. Passing it through verbatim wastes tokens and
+//! confuses models, so descriptions are converted to Markdown (default) or
+//! plain text before they are returned. `raw_data` always keeps the original.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// How job descriptions are returned
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DescriptionFormat {
+    /// Markdown with emphasis, lists and links
+    #[default]
+    Markdown,
+    /// Plain text with line breaks and list bullets
+    Text,
+    /// Unchanged as delivered by the API
+    Raw,
+}
+
+impl FromStr for DescriptionFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "text" | "plain" => Ok(Self::Text),
+            "raw" | "html" => Ok(Self::Raw),
+            other => anyhow::bail!(
+                "Unknown description format '{}' (expected markdown, text or raw)",
+                other
+            ),
+        }
+    }
+}
+
+/// Inline element whose content is wrapped once it is closed
+struct Inline {
+    tag: String,
+    start: usize,
+    href: Option<String>,
+}
+
+/// Convert a description to the given format
+pub fn clean_description(description: &str, format: DescriptionFormat) -> String {
+    if format == DescriptionFormat::Raw {
+        return description.to_string();
+    }
+    let markdown = format == DescriptionFormat::Markdown;
+
+    let mut out = String::new();
+    let mut inline: Vec<Inline> = Vec::new();
+    // One entry per open list: the next number of an ordered list, None for bullets
+    let mut lists: Vec<Option<usize>> = Vec::new();
+    let mut skip_until: Option<&str> = None;
+    let has_tags = description.contains('>')
+        && description
+            .match_indices('<')
+            .any(|(idx, _)| starts_tag(&description[idx..]));
+
+    let mut rest = description;
+    while !rest.is_empty() {
+        let (text, tag) = match rest.find('<') {
+            Some(0) if starts_tag(rest) => match rest.find('>') {
+                Some(end) => ("", Some(&rest[1..end])),
+                None => (rest, None),
+            },
+            // A lone "<" as in "Gehalt < 50.000 EUR"
+            Some(0) => ("<", None),
+            Some(idx) => (&rest[..idx], None),
+            None => (rest, None),
+        };
+
+        if let Some(tag) = tag {
+            rest = &rest[tag.len() + 2..];
+            let (closing, name, attributes) = parse_tag(tag);
+
+            if let Some(until) = skip_until {
+                if closing && name == until {
+                    skip_until = None;
+                }
+                continue;
+            }
+
+            match (name.as_str(), closing) {
+                ("script", false) => skip_until = Some("script"),
+                ("style", false) => skip_until = Some("style"),
+                ("br", _) => out.push('\n'),
+                ("p" | "div" | "section" | "article" | "table" | "tr", _) => out.push_str("\n\n"),
+                ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                    out.push_str("\n\n");
+                    if markdown {
+                        out.push_str("### ");
+                    }
+                }
+                ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => out.push_str("\n\n"),
+                ("hr", _) => out.push_str(if markdown { "\n\n---\n\n" } else { "\n\n" }),
+                ("td" | "th", false) => out.push(' '),
+                ("ul", false) => lists.push(None),
+                ("ol", false) => lists.push(Some(1)),
+                ("ul" | "ol", true) => {
+                    lists.pop();
+                    if lists.is_empty() {
+                        out.push('\n');
+                    }
+                }
+                ("li", false) => {
+                    out.push('\n');
+                    out.push_str(&"  ".repeat(lists.len().saturating_sub(1)));
+                    match lists.last_mut() {
+                        Some(Some(number)) => {
+                            out.push_str(&format!("{}. ", number));
+                            *number += 1;
+                        }
+                        _ => out.push_str("- "),
+                    }
+                }
+                ("b" | "strong" | "i" | "em" | "a", false) if markdown => inline.push(Inline {
+                    tag: name.clone(),
+                    start: out.len(),
+                    href: attribute(attributes, "href"),
+                }),
+                ("b" | "strong" | "i" | "em" | "a", true) if markdown => {
+                    if let Some(idx) = inline.iter().rposition(|open| open.tag == name) {
+                        let open = inline.remove(idx);
+                        wrap_inline(&mut out, &open);
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        rest = &rest[text.len()..];
+        if skip_until.is_some() {
+            continue;
+        }
+        // Whitespace between tags is only source formatting
+        if has_tags && text.trim().is_empty() {
+            if !text.contains('\n') {
+                out.push(' ');
+            }
+            continue;
+        }
+        out.push_str(&html_escape::decode_html_entities(text));
+    }
+
+    normalize_whitespace(&out)
+}
+
+/// Whether `text` starts with an opening or closing tag (or a comment)
+fn starts_tag(text: &str) -> bool {
+    text.strip_prefix('<')
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!')
+}
+
+/// Split a tag into (closing, lowercase name, attributes)
+fn parse_tag(tag: &str) -> (bool, String, &str) {
+    let tag = tag.trim().trim_end_matches('/').trim_end();
+    let (closing, tag) = match tag.strip_prefix('/') {
+        Some(tag) => (true, tag.trim_start()),
+        None => (false, tag),
+    };
+    let name_end = tag.find(|c: char| c.is_whitespace()).unwrap_or(tag.len());
+    (
+        closing,
+        tag[..name_end].to_lowercase(),
+        tag[name_end..].trim(),
+    )
+}
+
+/// Value of an attribute such as `href="..."`
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let lower = attributes.to_lowercase();
+    let start = lower.find(&format!("{}=", name))? + name.len() + 1;
+    let value = &attributes[start..];
+    let value = match value.chars().next()? {
+        quote @ ('"' | '\'') => value[1..].split(quote).next()?,
+        _ => value.split_whitespace().next()?,
+    };
+    let value = html_escape::decode_html_entities(value.trim()).to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+/// Wrap the text written since an inline element opened in Markdown syntax
+fn wrap_inline(out: &mut String, open: &Inline) {
+    let content = out.split_off(open.start);
+    let trimmed = content.trim();
+    if trimmed.is_empty() {
+        out.push_str(&content);
+        return;
+    }
+
+    let leading = &content[..content.len() - content.trim_start().len()];
+    let trailing = &content[content.trim_end().len()..];
+    let wrapped = match (open.tag.as_str(), &open.href) {
+        ("a", Some(href)) if href.as_str() != trimmed => format!("[{}]({})", trimmed, href),
+        ("a", _) => trimmed.to_string(),
+        ("i" | "em", _) => format!("_{}_", trimmed),
+        _ => format!("**{}**", trimmed),
+    };
+    out.push_str(leading);
+    out.push_str(&wrapped);
+    out.push_str(trailing);
+}
+
+/// Collapse runs of spaces, trim lines and keep at most one blank line
+fn normalize_whitespace(text: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.replace("\r\n", "\n").replace('\r', "\n").split('\n') {
+        let line = line.replace('\u{a0}', " ");
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.is_empty() {
+            if lines.last().is_some_and(|last| !last.is_empty()) {
+                lines.push(String::new());
+            }
+            continue;
+        }
+        // Keep the indentation of nested list items
+        let is_item = words[0] == "-"
+            || words[0]
+                .strip_suffix('.')
+                .is_some_and(|number| number.parse::<u32>().is_ok());
+        let indent = if is_item { indent } else { 0 };
+        lines.push(format!("{}{}", " ".repeat(indent), words.join(" ")));
+    }
+
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn markdown(html: &str) -> String {
+        clean_description(html, DescriptionFormat::Markdown)
+    }
+
+    #[test]
+    fn test_plain_text_keeps_line_breaks() {
+        let text = "Wir suchen Sie!\n\nIhre Aufgaben:\n- Entwicklung\n- Betrieb  ";
+        assert_eq!(
+            markdown(text),
+            "Wir suchen Sie!\n\nIhre Aufgaben:\n- Entwicklung\n- Betrieb"
+        );
+    }
+
+    #[test]
+    fn test_entities_are_decoded() {
+        assert_eq!(
+            markdown("M&uuml;ller &amp; S&ouml;hne&nbsp;GmbH &#8211; Berlin"),
+            "Müller & Söhne GmbH – Berlin"
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        let html = "<p><strong>Ihre Aufgaben: </strong></p>\n<ul>\n<li>Rust &amp; Go</li>\n\
+                    <li>Code <em>Reviews</em></li>\n</ul>\n<p>Mehr unter \
+                    <a href=\"https://example.com/jobs\">unserer Seite</a>.<br/>Danke!</p>";
+        assert_eq!(
+            markdown(html),
+            "**Ihre Aufgaben:**\n\n- Rust & Go\n- Code _Reviews_\n\n\
+             Mehr unter [unserer Seite](https://example.com/jobs).\nDanke!"
+        );
+    }
+
+    #[test]
+    fn test_nested_and_ordered_lists() {
+        let html = "<ol><li>Erstens<ul><li>Detail</li></ul></li><li>Zweitens</li></ol>";
+        assert_eq!(markdown(html), "1. Erstens\n  - Detail\n2. Zweitens");
+    }
+
+    #[test]
+    fn test_text_format() {
+        let html = "<h2>Profil</h2><p>Sie haben <b>Erfahrung</b> mit \
+                    <a href='https://example.com'>Rust</a>.</p><script>alert(1)</script>";
+        assert_eq!(
+            clean_description(html, DescriptionFormat::Text),
+            "Profil\n\nSie haben Erfahrung mit Rust."
+        );
+        assert_eq!(
+            markdown(html),
+            "### Profil\n\nSie haben **Erfahrung** mit [Rust](https://example.com)."
+        );
+    }
+
+    #[test]
+    fn test_raw_format_is_unchanged() {
+        let html = "<p>A &amp; B</p>";
+        assert_eq!(clean_description(html, DescriptionFormat::Raw), html);
+    }
+
+    #[test]
+    fn test_stray_angle_brackets() {
+        assert_eq!(markdown("Gehalt < 50.000 EUR"), "Gehalt < 50.000 EUR");
+        assert_eq!(markdown("a > b"), "a > b");
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            "Markdown".parse::<DescriptionFormat>().unwrap(),
+            DescriptionFormat::Markdown
+        );
+        assert_eq!(
+            "plain".parse::<DescriptionFormat>().unwrap(),
+            DescriptionFormat::Text
+        );
+        assert!("pdf".parse::<DescriptionFormat>().is_err());
+    }
+}
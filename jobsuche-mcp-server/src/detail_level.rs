@@ -0,0 +1,232 @@
+//! Detail levels of job details
+//!
+//! A full posting with its raw API data easily takes a few thousand tokens,
+//! which adds up when several jobs are returned at once. Lower detail levels
+//! drop the raw data and, for "summary", condense the description to its
+//! first few hundred characters after removing boilerplate sections such as
+//! benefits, company introductions and application instructions. The
+//! structured fields (salary, location, contract, skills) stay as key facts.
+
+use crate::description::{clean_description, DescriptionFormat};
+use crate::GetJobDetailsResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Target length of a summarized description in characters
+pub const SUMMARY_LENGTH: usize = 500;
+
+/// Longest line treated as a section heading
+const MAX_HEADING_LENGTH: usize = 60;
+
+/// Headings of sections that say nothing about the job itself
+const BOILERPLATE_HEADINGS: &[&str] = &[
+    "wir bieten",
+    "bieten wir",
+    "unser angebot",
+    "das erwartet sie",
+    "das erwartet dich",
+    "benefits",
+    "vorteile",
+    "über uns",
+    "wer wir sind",
+    "das sind wir",
+    "bewerbung",
+    "bewerben",
+    "kontakt",
+    "ansprechpartner",
+    "interesse",
+    "interessiert",
+    "datenschutz",
+    "hinweis",
+    "we offer",
+    "about us",
+    "how to apply",
+    "contact",
+];
+
+/// How much of each job is returned
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DetailLevel {
+    /// Description condensed to about 500 characters, no raw data
+    Summary,
+    /// Complete description, no raw data
+    Standard,
+    /// Complete description, plus the raw API data if requested
+    #[default]
+    Full,
+}
+
+/// Whether a line of a plain-text description is a section heading
+fn is_heading(line: &str) -> bool {
+    !line.is_empty()
+        && line.chars().count() <= MAX_HEADING_LENGTH
+        && !line.starts_with(['-', '•', '*'])
+        && !line.ends_with(['.', ',', ';', '!'])
+}
+
+/// Whether a heading starts a boilerplate section
+fn is_boilerplate(heading: &str) -> bool {
+    let heading = heading.to_lowercase();
+    BOILERPLATE_HEADINGS
+        .iter()
+        .any(|marker| heading.contains(marker))
+}
+
+/// Cut a text to at most `max_chars`, at the end of a sentence if one ends in
+/// the second half, otherwise at a word boundary with an ellipsis
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut = text
+        .char_indices()
+        .nth(max_chars)
+        .map_or(text.len(), |(idx, _)| idx);
+    let head = &text[..cut];
+
+    let sentence_end = head
+        .rmatch_indices(['.', '!', '?'])
+        .map(|(idx, _)| idx + 1)
+        .find(|&end| end > head.len() / 2 && text[end..].starts_with(' '));
+    if let Some(end) = sentence_end {
+        return head[..end].to_string();
+    }
+    let word_end = head.rfind(' ').unwrap_or(head.len());
+    format!(
+        "{}…",
+        head[..word_end].trim_end_matches([',', ';', ':', ' '])
+    )
+}
+
+/// Condense a plain-text description to about `max_chars` characters
+///
+/// Sections under boilerplate headings are dropped and the remaining lines are
+/// joined into running text before it is cut.
+pub fn summarize_description(text: &str, max_chars: usize) -> String {
+    let mut kept = Vec::new();
+    let mut skipping = false;
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        if is_heading(line) {
+            skipping = is_boilerplate(line);
+        }
+        if !skipping {
+            kept.push(line.trim_start_matches(['-', '•', '*', ' ']));
+        }
+    }
+
+    let condensed = kept.join(" ");
+    let condensed = condensed.split_whitespace().collect::<Vec<_>>().join(" ");
+    truncate(&condensed, max_chars)
+}
+
+/// Reduce job details to the given detail level
+///
+/// The raw API data is kept only at level "full" and if `include_raw` is set.
+pub fn apply_detail_level(
+    details: &mut GetJobDetailsResult,
+    level: DetailLevel,
+    include_raw: bool,
+) {
+    if level == DetailLevel::Full {
+        if !include_raw {
+            details.raw_data = serde_json::Value::Null;
+        }
+        return;
+    }
+
+    if level == DetailLevel::Summary {
+        // Summarize the original so markup of the configured format is not cut
+        let plain = details
+            .raw_data
+            .get("stellenbeschreibung")
+            .and_then(|text| text.as_str())
+            .map(|text| clean_description(text, DescriptionFormat::Text))
+            .or_else(|| details.description.clone());
+        details.description = plain
+            .map(|text| summarize_description(&text, SUMMARY_LENGTH))
+            .filter(|summary| !summary.is_empty());
+    }
+    details.raw_data = serde_json::Value::Null;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESCRIPTION: &str = "Über uns\n\
+        Wir sind ein familiengeführtes Unternehmen mit 200 Mitarbeitenden.\n\n\
+        Ihre Aufgaben\n\
+        - Wartung und Reparatur von Landmaschinen\n\
+        - Beratung unserer Kunden\n\n\
+        Ihr Profil\n\
+        - Ausbildung als Land- und Baumaschinenmechatroniker\n\n\
+        Wir bieten\n\
+        - 30 Tage Urlaub\n\n\
+        Haben wir Ihr Interesse geweckt?\n\
+        Dann senden Sie uns Ihre Bewerbung.";
+
+    #[test]
+    fn test_summary_drops_boilerplate() {
+        assert_eq!(
+            summarize_description(DESCRIPTION, SUMMARY_LENGTH),
+            "Ihre Aufgaben Wartung und Reparatur von Landmaschinen Beratung unserer Kunden \
+             Ihr Profil Ausbildung als Land- und Baumaschinenmechatroniker"
+        );
+    }
+
+    #[test]
+    fn test_truncate_at_sentence() {
+        let text = "Erster Satz mit Inhalt. Zweiter Satz mit mehr Inhalt. Dritter Satz.";
+        assert_eq!(
+            truncate(text, 60),
+            "Erster Satz mit Inhalt. Zweiter Satz mit mehr Inhalt."
+        );
+    }
+
+    #[test]
+    fn test_truncate_at_word() {
+        assert_eq!(truncate("Montage, Wartung und Service", 12), "Montage…");
+        assert_eq!(truncate("kurz", 12), "kurz");
+    }
+
+    #[test]
+    fn test_apply_detail_level() {
+        let details: GetJobDetailsResult = serde_json::from_value(serde_json::json!({
+            "reference_number": "REF-1",
+            "description": "**Ihre Aufgaben**",
+            "raw_data": {"stellenbeschreibung": DESCRIPTION}
+        }))
+        .unwrap();
+
+        let mut full = details.clone();
+        apply_detail_level(&mut full, DetailLevel::Full, true);
+        assert!(full.raw_data.is_object());
+
+        let mut without_raw = details.clone();
+        apply_detail_level(&mut without_raw, DetailLevel::Full, false);
+        assert!(without_raw.raw_data.is_null());
+        assert_eq!(
+            without_raw.description.as_deref(),
+            Some("**Ihre Aufgaben**")
+        );
+
+        let mut standard = details.clone();
+        apply_detail_level(&mut standard, DetailLevel::Standard, true);
+        assert!(standard.raw_data.is_null());
+        assert_eq!(standard.description.as_deref(), Some("**Ihre Aufgaben**"));
+        assert!(!serde_json::to_value(&standard)
+            .unwrap()
+            .as_object()
+            .unwrap()
+            .contains_key("raw_data"));
+
+        let mut summary = details;
+        apply_detail_level(&mut summary, DetailLevel::Summary, false);
+        assert!(summary
+            .description
+            .as_deref()
+            .unwrap()
+            .starts_with("Ihre Aufgaben Wartung"));
+    }
+}
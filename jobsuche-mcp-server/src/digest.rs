@@ -0,0 +1,267 @@
+//! Job-market digests
+//!
+//! A digest runs every saved search and watchlist and lists, per search, the
+//! postings that are new since the previous digest, with the newest few as
+//! highlights, e.g. for a career coach's weekly update to a client. Each
+//! search keeps its own digest baseline, independent of watchlist checks and
+//! scheduled runs, so checking a watchlist in between does not empty the
+//! next digest. A search without a baseline yet, such as on the first digest,
+//! counts the postings of the last [`FIRST_DIGEST_DAYS`] days as new.
+
+use crate::watchlists::Watchlist;
+use crate::{JobSummary, SearchJobsParams};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Storage key of the digest state in the [`DIGESTS`](crate::storage::DIGESTS) collection
+pub const DIGEST_STATE_KEY: &str = "state";
+
+/// Default number of highlighted jobs per search
+pub const DEFAULT_DIGEST_TOP_JOBS: u64 = 5;
+
+/// Maximum number of highlighted jobs per search
+pub const MAX_DIGEST_TOP_JOBS: u64 = 20;
+
+/// Publication window counted as new for searches without a baseline
+pub const FIRST_DIGEST_DAYS: i64 = 7;
+
+/// Kind of search a digest section comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestSource {
+    SavedSearch,
+    Watchlist,
+}
+
+impl DigestSource {
+    /// Key of the digest baseline of a search
+    pub fn baseline_key(self, name_key: &str) -> String {
+        match self {
+            Self::SavedSearch => format!("saved_search:{}", name_key),
+            Self::Watchlist => format!("watchlist:{}", name_key),
+        }
+    }
+}
+
+/// Time of the last digest
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DigestState {
+    /// When the last digest was generated (RFC 3339)
+    pub generated_at: Option<String>,
+}
+
+/// Parameters for generate_digest
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GenerateDigestParams {
+    /// Number of new jobs highlighted per search, newest first (default: 5,
+    /// max: 20)
+    #[schemars(range(max = 20))]
+    pub top_jobs: Option<u64>,
+
+    /// Build the digest without marking its jobs as reported, so the next
+    /// digest lists them again (default: false)
+    pub preview: Option<bool>,
+}
+
+/// Key facts of a new job
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DigestJob {
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Job title
+    pub title: String,
+
+    /// Employer name
+    pub employer: String,
+
+    /// Location as listed
+    pub location: String,
+
+    /// Publication date (YYYY-MM-DD)
+    pub published_date: Option<String>,
+
+    /// Distance in km from the search location
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+
+    /// Job page on arbeitsagentur.de
+    pub web_url: String,
+}
+
+impl From<&JobSummary> for DigestJob {
+    fn from(job: &JobSummary) -> Self {
+        Self {
+            reference_number: job.reference_number.clone(),
+            title: job.title.clone(),
+            employer: job.employer.clone(),
+            location: job.location.clone(),
+            published_date: job.published_date.clone(),
+            distance_km: job.distance_km,
+            web_url: job.web_url.clone(),
+        }
+    }
+}
+
+/// New postings of one search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DigestSection {
+    /// Name of the saved search or watchlist
+    pub name: String,
+
+    /// Kind of search
+    pub source: DigestSource,
+
+    /// Total number of results of the search
+    pub total_results: Option<u64>,
+
+    /// Number of postings new since the previous digest
+    pub new_jobs_count: usize,
+
+    /// Newest of the new postings
+    pub top_jobs: Vec<DigestJob>,
+
+    /// Error message if the search failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result from generate_digest
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GenerateDigestResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// When this digest was generated (RFC 3339)
+    pub generated_at: String,
+
+    /// When the previous digest was generated (RFC 3339; none for the first)
+    pub previous_digest_at: Option<String>,
+
+    /// Number of searches run
+    pub searches_count: usize,
+
+    /// Number of new postings over all searches (a posting found by several
+    /// searches counts once per search)
+    pub total_new_jobs: usize,
+
+    /// One section per saved search and watchlist, in that order
+    pub sections: Vec<DigestSection>,
+
+    /// True if the digest was cancelled before all searches ran
+    #[serde(default)]
+    pub cancelled: bool,
+}
+
+/// Jobs of a search that are new since its digest baseline
+///
+/// Without a baseline, jobs published within [`FIRST_DIGEST_DAYS`] count as
+/// new. Returns the updated baseline with the new jobs.
+pub fn new_since_baseline(
+    baseline: Option<Watchlist>,
+    name: &str,
+    search: &SearchJobsParams,
+    jobs: Vec<JobSummary>,
+    now: &str,
+) -> (Watchlist, Vec<JobSummary>) {
+    match baseline {
+        Some(mut baseline) => {
+            let new_jobs = baseline
+                .absorb(jobs, now)
+                .into_iter()
+                .map(|new_job| new_job.job)
+                .collect();
+            (baseline, new_jobs)
+        }
+        None => {
+            let new_jobs = jobs
+                .iter()
+                .filter(|job| {
+                    job.published_days_ago
+                        .is_some_and(|days| days <= FIRST_DIGEST_DAYS)
+                })
+                .cloned()
+                .collect();
+            (Watchlist::new(name, search.clone(), &jobs, now), new_jobs)
+        }
+    }
+}
+
+/// The newest `limit` jobs; jobs without a publication date come last
+pub fn top_jobs(jobs: &[JobSummary], limit: usize) -> Vec<DigestJob> {
+    let mut newest: Vec<&JobSummary> = jobs.iter().collect();
+    newest.sort_by_key(|job| job.published_days_ago.unwrap_or(i64::MAX));
+    newest
+        .into_iter()
+        .take(limit)
+        .map(DigestJob::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn job(reference_number: &str, published_days_ago: Option<i64>) -> JobSummary {
+        serde_json::from_value(json!({
+            "reference_number": reference_number,
+            "title": "Job",
+            "employer": "BARMER",
+            "location": "Wuppertal",
+            "published_date": null,
+            "published_days_ago": published_days_ago,
+            "external_url": null
+        }))
+        .unwrap()
+    }
+
+    fn search() -> SearchJobsParams {
+        serde_json::from_value(json!({"job_title": "Rust"})).unwrap()
+    }
+
+    fn references(jobs: &[JobSummary]) -> Vec<&str> {
+        jobs.iter()
+            .map(|job| job.reference_number.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_first_digest_counts_recent_jobs() {
+        let jobs = vec![job("A", Some(2)), job("B", Some(30)), job("C", None)];
+        let (baseline, new_jobs) =
+            new_since_baseline(None, "Rust", &search(), jobs, "2026-10-01T08:00:00Z");
+        assert_eq!(references(&new_jobs), ["A"]);
+        assert_eq!(baseline.known_jobs.len(), 3);
+
+        let jobs = vec![job("A", Some(9)), job("D", Some(1))];
+        let (_, new_jobs) = new_since_baseline(
+            Some(baseline),
+            "Rust",
+            &search(),
+            jobs,
+            "2026-10-08T08:00:00Z",
+        );
+        assert_eq!(references(&new_jobs), ["D"]);
+    }
+
+    #[test]
+    fn test_top_jobs_newest_first() {
+        let jobs = vec![job("A", Some(5)), job("B", None), job("C", Some(1))];
+        let top: Vec<String> = top_jobs(&jobs, 2)
+            .into_iter()
+            .map(|job| job.reference_number)
+            .collect();
+        assert_eq!(top, ["C", "A"]);
+    }
+
+    #[test]
+    fn test_baseline_keys_do_not_collide() {
+        assert_ne!(
+            DigestSource::SavedSearch.baseline_key("rust"),
+            DigestSource::Watchlist.baseline_key("rust")
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! Duplicate posting detection
+//!
+//! The same role is often posted by the employer and by several temp
+//! agencies, each with its own reference number. Postings are treated as
+//! duplicates when they are in the same place and their titles share most
+//! significant words; if both descriptions are known, the descriptions must
+//! also overlap (compared as word shingles). With `collapse_duplicates` the
+//! search tools keep the first posting of a cluster and list the others in
+//! its `duplicates`.
+
+use crate::similar::{city_of, title_keywords};
+use crate::JobSummary;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Share of common title words (Jaccard) from which titles count as the same role
+const TITLE_SIMILARITY: f64 = 0.8;
+
+/// Share of common description shingles (Jaccard) from which descriptions count as the same text
+const DESCRIPTION_SIMILARITY: f64 = 0.5;
+
+/// Number of words per description shingle
+const SHINGLE_WORDS: usize = 3;
+
+/// A posting folded into another one as its duplicate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DuplicatePosting {
+    /// Reference number of the duplicate
+    pub reference_number: String,
+
+    /// Employer (or agency) that published the duplicate
+    pub employer: Option<String>,
+
+    /// Title of the duplicate
+    pub title: Option<String>,
+}
+
+/// What postings are compared by
+#[derive(Debug, Clone)]
+pub struct Posting<'a> {
+    /// Job title
+    pub title: &'a str,
+
+    /// Location like "Berlin (10115)"
+    pub location: &'a str,
+
+    /// Job description, if fetched
+    pub description: Option<&'a str>,
+}
+
+/// Comparable form of a posting
+struct Fingerprint {
+    keywords: HashSet<String>,
+    city: String,
+    shingles: Option<HashSet<String>>,
+}
+
+impl Fingerprint {
+    fn new(posting: &Posting) -> Self {
+        Self {
+            keywords: title_keywords(posting.title).into_iter().collect(),
+            city: city_of(posting.location).to_lowercase(),
+            shingles: posting.description.map(shingles),
+        }
+    }
+
+    fn is_duplicate_of(&self, other: &Self) -> bool {
+        if self.keywords.is_empty() || self.city.is_empty() || self.city != other.city {
+            return false;
+        }
+        if jaccard(&self.keywords, &other.keywords) < TITLE_SIMILARITY {
+            return false;
+        }
+        match (&self.shingles, &other.shingles) {
+            (Some(own), Some(other)) => jaccard(own, other) >= DESCRIPTION_SIMILARITY,
+            _ => true,
+        }
+    }
+}
+
+/// Overlap of two sets, from 0.0 (disjoint) to 1.0 (equal)
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Overlapping runs of [`SHINGLE_WORDS`] lowercase words of a text
+fn shingles(text: &str) -> HashSet<String> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.len() < SHINGLE_WORDS {
+        return HashSet::from([words.join(" ")]);
+    }
+    words
+        .windows(SHINGLE_WORDS)
+        .map(|window| window.join(" "))
+        .collect()
+}
+
+/// Group postings into clusters of duplicates
+///
+/// Returns the indices of each cluster in input order; the first index of a
+/// cluster is its representative, and postings are only compared against
+/// representatives. Postings without duplicates form clusters of one.
+pub fn duplicate_clusters(postings: &[Posting]) -> Vec<Vec<usize>> {
+    let fingerprints: Vec<Fingerprint> = postings.iter().map(Fingerprint::new).collect();
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    for (index, fingerprint) in fingerprints.iter().enumerate() {
+        let cluster = clusters
+            .iter_mut()
+            .find(|cluster| fingerprint.is_duplicate_of(&fingerprints[cluster[0]]));
+        match cluster {
+            Some(cluster) => cluster.push(index),
+            None => clusters.push(vec![index]),
+        }
+    }
+    clusters
+}
+
+/// Keep the first job of each duplicate cluster, listing the others in its `duplicates`
+///
+/// Returns the remaining jobs and the number of jobs folded away.
+pub fn collapse_duplicates(jobs: Vec<JobSummary>) -> (Vec<JobSummary>, usize) {
+    let postings: Vec<Posting> = jobs
+        .iter()
+        .map(|job| Posting {
+            title: &job.title,
+            location: &job.location,
+            description: None,
+        })
+        .collect();
+    let clusters = duplicate_clusters(&postings);
+
+    let mut jobs: Vec<Option<JobSummary>> = jobs.into_iter().map(Some).collect();
+    let mut collapsed = 0;
+    let mut representatives = Vec::with_capacity(clusters.len());
+    for cluster in clusters {
+        let Some(mut representative) = jobs[cluster[0]].take() else {
+            continue;
+        };
+        for &index in &cluster[1..] {
+            if let Some(duplicate) = jobs[index].take() {
+                representative.duplicates.push(DuplicatePosting {
+                    reference_number: duplicate.reference_number,
+                    employer: Some(duplicate.employer),
+                    title: Some(duplicate.title),
+                });
+                collapsed += 1;
+            }
+        }
+        representatives.push(representative);
+    }
+    (representatives, collapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(reference_number: &str, title: &str, employer: &str, location: &str) -> JobSummary {
+        serde_json::from_value(serde_json::json!({
+            "reference_number": reference_number,
+            "title": title,
+            "employer": employer,
+            "location": location,
+            "published_date": null,
+            "external_url": null
+        }))
+        .unwrap()
+    }
+
+    fn posting<'a>(title: &'a str, location: &'a str, description: Option<&'a str>) -> Posting<'a> {
+        Posting {
+            title,
+            location,
+            description,
+        }
+    }
+
+    #[test]
+    fn test_clusters_by_title_and_place() {
+        let clusters = duplicate_clusters(&[
+            posting("Rust Entwickler (m/w/d)", "Berlin (10115)", None),
+            posting("Rust-Entwickler m/w/d", "Berlin (10117)", None),
+            posting("Rust Entwickler (m/w/d)", "Hamburg (20095)", None),
+            posting("Java Entwickler (m/w/d)", "Berlin (10115)", None),
+        ]);
+        assert_eq!(clusters, vec![vec![0, 1], vec![2], vec![3]]);
+    }
+
+    #[test]
+    fn test_descriptions_must_overlap_when_known() {
+        let text = "Wir suchen zur Verstärkung unseres Teams in Berlin eine erfahrene \
+                    Pflegefachkraft für die Intensivstation in Vollzeit";
+        let other = "Als Pflegefachkraft betreuen Sie in unserer Seniorenresidenz \
+                     Bewohnerinnen und Bewohner im Tagdienst";
+        let clusters = duplicate_clusters(&[
+            posting("Pflegefachkraft (m/w/d)", "Berlin", Some(text)),
+            posting("Pflegefachkraft (m/w/d)", "Berlin", Some(text)),
+            posting("Pflegefachkraft (m/w/d)", "Berlin", Some(other)),
+        ]);
+        assert_eq!(clusters, vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn test_collapse_keeps_first_with_duplicates() {
+        let (jobs, collapsed) = collapse_duplicates(vec![
+            job("A", "Lagerhelfer (m/w/d)", "Logistik GmbH", "Köln"),
+            job("B", "Helfer Lager", "Logistik GmbH", "Köln"),
+            job("C", "Lagerhelfer (m/w/d)", "Zeitarbeit AG", "Köln"),
+        ]);
+        assert_eq!(collapsed, 1);
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].reference_number, "A");
+        assert_eq!(
+            jobs[0].duplicates,
+            vec![DuplicatePosting {
+                reference_number: "C".to_string(),
+                employer: Some("Zeitarbeit AG".to_string()),
+                title: Some("Lagerhelfer (m/w/d)".to_string()),
+            }]
+        );
+        assert!(jobs[1].duplicates.is_empty());
+    }
+}
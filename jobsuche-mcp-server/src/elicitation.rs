@@ -0,0 +1,132 @@
+//! Elicitation for underspecified searches
+//!
+//! A search without any keyword or location matches every posting in Germany.
+//! When the client supports MCP elicitation, the server asks the user for at
+//! least one constraint before running such a search.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Parameters of an MCP `elicitation/create` request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElicitationRequest {
+    /// Message shown to the user
+    pub message: String,
+
+    /// Flat JSON Schema describing the requested fields
+    pub requested_schema: serde_json::Value,
+}
+
+/// How the user responded to an elicitation request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ElicitationAction {
+    /// The user submitted the form
+    Accept,
+    /// The user explicitly declined to provide the data
+    Decline,
+    /// The user dismissed the request
+    Cancel,
+}
+
+/// Result of an MCP `elicitation/create` request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElicitationResponse {
+    /// User action
+    pub action: ElicitationAction,
+
+    /// Submitted values (only for [`ElicitationAction::Accept`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl ElicitationResponse {
+    /// Non-empty string value of a submitted field
+    pub fn string_field(&self, name: &str) -> Option<String> {
+        self.content
+            .as_ref()?
+            .get(name)?
+            .as_str()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    }
+}
+
+/// Sends elicitation requests to the client
+///
+/// Implemented by the transport integration when the connected client
+/// announced the `elicitation` capability.
+#[async_trait]
+pub trait Elicitor: Send + Sync {
+    /// Whether the connected client accepts elicitation requests
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    /// Ask the user and wait for the response
+    async fn elicit(&self, request: ElicitationRequest) -> anyhow::Result<ElicitationResponse>;
+}
+
+/// Elicitation request asking for a job title and/or location
+pub fn search_constraints_request() -> ElicitationRequest {
+    ElicitationRequest {
+        message: "This search has no job title and no location and would match every job in \
+                  Germany. Please provide at least one of them."
+            .to_string(),
+        requested_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "job_title": {
+                    "type": "string",
+                    "title": "Job title",
+                    "description": "Job title or keywords, e.g. \"Software Engineer\""
+                },
+                "location": {
+                    "type": "string",
+                    "title": "Location",
+                    "description": "City or region, e.g. \"Berlin\""
+                }
+            }
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_serialization() {
+        let json = serde_json::to_value(search_constraints_request()).unwrap();
+        assert!(json["message"].as_str().unwrap().contains("job title"));
+        assert_eq!(json["requestedSchema"]["type"], "object");
+        assert!(json["requestedSchema"]["properties"]["location"].is_object());
+    }
+
+    #[test]
+    fn test_response_deserialization() {
+        let response: ElicitationResponse = serde_json::from_value(serde_json::json!({
+            "action": "accept",
+            "content": {"job_title": "  Pflegefachkraft ", "location": ""}
+        }))
+        .unwrap();
+
+        assert_eq!(response.action, ElicitationAction::Accept);
+        assert_eq!(
+            response.string_field("job_title"),
+            Some("Pflegefachkraft".to_string())
+        );
+        assert_eq!(response.string_field("location"), None);
+        assert_eq!(response.string_field("missing"), None);
+    }
+
+    #[test]
+    fn test_decline_without_content() {
+        let response: ElicitationResponse =
+            serde_json::from_value(serde_json::json!({"action": "decline"})).unwrap();
+        assert_eq!(response.action, ElicitationAction::Decline);
+        assert!(response.content.is_none());
+    }
+}
@@ -0,0 +1,217 @@
+//! Employer profiles
+//!
+//! Lists the current postings of one employer together with aggregate figures
+//! (postings per location and job type, share of fixed-term contracts), a
+//! common question when researching an employer.
+
+use crate::JobSummary;
+use jobsuche::{Angebotsart, Befristung, SearchOptions};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Default number of postings returned by get_employer_jobs
+pub const DEFAULT_EMPLOYER_JOBS: u64 = 100;
+
+/// Maximum number of postings returned by get_employer_jobs
+pub const MAX_EMPLOYER_JOBS: u64 = 500;
+
+/// Job types counted with one query each, with their names in the result
+pub const JOB_TYPES: &[(Angebotsart, &str)] = &[
+    (Angebotsart::Arbeit, "job"),
+    (Angebotsart::Ausbildung, "apprenticeship"),
+    (Angebotsart::PraktikumTrainee, "internship_trainee"),
+    (Angebotsart::Selbstaendigkeit, "self_employment"),
+];
+
+/// Contract types counted with one query each
+pub const CONTRACT_TYPES: &[Befristung] = &[Befristung::Befristet, Befristung::Unbefristet];
+
+/// Parameters for get_employer_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetEmployerJobsParams {
+    /// Employer name as shown in search results (e.g. "BARMER")
+    pub employer: String,
+
+    /// Only postings in or near this location
+    pub location: Option<String>,
+
+    /// Maximum number of postings to return (default: 100, max: 500)
+    pub max_jobs: Option<u64>,
+}
+
+/// Number of postings in one location
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct LocationCount {
+    /// City
+    pub location: String,
+
+    /// Number of postings
+    pub count: usize,
+}
+
+/// Result from get_employer_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetEmployerJobsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Employer that was requested
+    pub employer: String,
+
+    /// Total number of current postings reported by the API
+    pub total_jobs: Option<u64>,
+
+    /// Number of postings returned
+    pub jobs_count: usize,
+
+    /// Postings, as returned by search_jobs
+    pub jobs: Vec<JobSummary>,
+
+    /// Returned postings per city, most postings first
+    pub jobs_by_location: Vec<LocationCount>,
+
+    /// Current postings per job type ("job", "apprenticeship",
+    /// "internship_trainee", "self_employment"), counted by the API
+    pub jobs_by_type: BTreeMap<String, u64>,
+
+    /// Current postings with a fixed-term contract
+    pub fixed_term_jobs: u64,
+
+    /// Current postings with a permanent contract
+    pub permanent_jobs: u64,
+
+    /// Share of fixed-term contracts among postings stating the contract type
+    /// (0.0 to 1.0)
+    pub fixed_term_share: Option<f64>,
+}
+
+/// Search options for the postings of an employer
+///
+/// With `size` 1 only the total count in the response is of interest, which
+/// lets the API count postings per job type or contract type.
+pub fn employer_search_options(
+    employer: &str,
+    location: Option<&str>,
+    size: u64,
+    page: u64,
+    job_type: Option<Angebotsart>,
+    contract_type: Option<Befristung>,
+) -> SearchOptions {
+    let mut options = SearchOptions::builder();
+    options.arbeitgeber(employer).size(size).page(page);
+    if let Some(location) = location {
+        options.wo(location);
+    }
+    if let Some(job_type) = job_type {
+        options.angebotsart(job_type);
+    }
+    if let Some(contract_type) = contract_type {
+        options.befristung(vec![contract_type]);
+    }
+    options.build()
+}
+
+/// City of a job summary location like "Berlin (10115)"
+fn city(location: &str) -> &str {
+    location
+        .split_once(" (")
+        .map_or(location, |(city, _)| city)
+        .trim()
+}
+
+/// Count postings per city, most postings first (ties alphabetically)
+pub fn count_by_location(jobs: &[JobSummary]) -> Vec<LocationCount> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for job in jobs {
+        let city = city(&job.location);
+        let city = if city.is_empty() { "unknown" } else { city };
+        *counts.entry(city).or_default() += 1;
+    }
+
+    let mut counts: Vec<LocationCount> = counts
+        .into_iter()
+        .map(|(location, count)| LocationCount {
+            location: location.to_string(),
+            count,
+        })
+        .collect();
+    counts.sort_by_key(|count| std::cmp::Reverse(count.count));
+    counts
+}
+
+/// Whether a job was posted by the employer (case-insensitive substring)
+///
+/// The API matches employers loosely, so results are checked again.
+pub fn matches_employer(job: &JobSummary, employer: &str) -> bool {
+    job.employer
+        .to_lowercase()
+        .contains(&employer.trim().to_lowercase())
+}
+
+/// Share of fixed-term contracts, `None` if no posting states the contract type
+pub fn fixed_term_share(fixed_term: u64, permanent: u64) -> Option<f64> {
+    let known = fixed_term + permanent;
+    (known > 0).then(|| fixed_term as f64 / known as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(employer: &str, location: &str) -> JobSummary {
+        serde_json::from_value(serde_json::json!({
+            "reference_number": "REF",
+            "title": "Job",
+            "employer": employer,
+            "location": location,
+            "published_date": null,
+            "external_url": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_count_by_location() {
+        let jobs = vec![
+            job("BARMER", "Wuppertal (42103)"),
+            job("BARMER", "Berlin (10115)"),
+            job("BARMER", "Wuppertal (42105)"),
+            job("BARMER", ""),
+        ];
+        assert_eq!(
+            count_by_location(&jobs),
+            vec![
+                LocationCount {
+                    location: "Wuppertal".to_string(),
+                    count: 2
+                },
+                LocationCount {
+                    location: "Berlin".to_string(),
+                    count: 1
+                },
+                LocationCount {
+                    location: "unknown".to_string(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matches_employer() {
+        assert!(matches_employer(
+            &job("BARMER Ersatzkasse", "Wuppertal"),
+            " barmer "
+        ));
+        assert!(!matches_employer(&job("AOK", "Wuppertal"), "BARMER"));
+    }
+
+    #[test]
+    fn test_fixed_term_share() {
+        assert_eq!(fixed_term_share(1, 3), Some(0.25));
+        assert_eq!(fixed_term_share(0, 0), None);
+    }
+}
@@ -0,0 +1,302 @@
+//! Employer blocklist and allowlist
+//!
+//! Many users never want to see postings of certain employers, typically
+//! staffing firms. Blocked employers are dropped from all search results;
+//! with an allowlist, only the named employers remain. Patterns match the
+//! employer name case-insensitively anywhere in the name, and `*` stands for
+//! any text in between ("personal*service"). The lists come from the
+//! configuration and from update_employer_filter, which stores its part
+//! once per server; both apply together.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Storage key of the filter in the [`EMPLOYER_FILTER`](crate::storage::EMPLOYER_FILTER) collection
+pub const EMPLOYER_FILTER_KEY: &str = "default";
+
+/// Maximum number of patterns per list
+pub const MAX_FILTER_PATTERNS: usize = 200;
+
+/// Employer patterns to drop and to keep
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EmployerFilter {
+    /// Employers dropped from search results
+    #[serde(default)]
+    pub blocklist: Vec<String>,
+
+    /// If not empty, only these employers are kept
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+}
+
+/// Whether an employer name matches a pattern
+///
+/// The pattern matches case-insensitively anywhere in the name; the parts
+/// between `*` must appear in order.
+pub fn pattern_matches(pattern: &str, employer: &str) -> bool {
+    let employer = employer.to_lowercase();
+    let pattern = pattern.trim().to_lowercase();
+    if pattern.chars().all(|c| c == '*') {
+        return false;
+    }
+
+    let mut rest = employer.as_str();
+    for part in pattern.split('*').filter(|part| !part.is_empty()) {
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Add patterns to a list, skipping empty ones and case-insensitive duplicates
+fn add_patterns(list: &mut Vec<String>, patterns: &[String]) {
+    for pattern in patterns.iter().map(|pattern| pattern.trim()) {
+        let known = list
+            .iter()
+            .any(|existing| existing.to_lowercase() == pattern.to_lowercase());
+        if !pattern.is_empty() && !known {
+            list.push(pattern.to_string());
+        }
+    }
+}
+
+/// Remove patterns from a list (case-insensitive); returns the number removed
+fn remove_patterns(list: &mut Vec<String>, patterns: &[String]) -> usize {
+    let before = list.len();
+    list.retain(|existing| {
+        !patterns
+            .iter()
+            .any(|pattern| pattern.trim().to_lowercase() == existing.to_lowercase())
+    });
+    before - list.len()
+}
+
+impl EmployerFilter {
+    /// Filter from the comma-separated lists of the configuration
+    pub fn from_config(blocklist: &[String], allowlist: &[String]) -> Self {
+        let mut filter = Self::default();
+        add_patterns(&mut filter.blocklist, blocklist);
+        add_patterns(&mut filter.allowlist, allowlist);
+        filter
+    }
+
+    /// Both filters combined
+    pub fn merged(&self, other: &Self) -> Self {
+        let mut merged = self.clone();
+        add_patterns(&mut merged.blocklist, &other.blocklist);
+        add_patterns(&mut merged.allowlist, &other.allowlist);
+        merged
+    }
+
+    /// True if neither list holds a pattern
+    pub fn is_empty(&self) -> bool {
+        self.blocklist.is_empty() && self.allowlist.is_empty()
+    }
+
+    /// Whether postings of an employer are kept
+    pub fn allows(&self, employer: &str) -> bool {
+        if self
+            .blocklist
+            .iter()
+            .any(|pattern| pattern_matches(pattern, employer))
+        {
+            return false;
+        }
+        self.allowlist.is_empty()
+            || self
+                .allowlist
+                .iter()
+                .any(|pattern| pattern_matches(pattern, employer))
+    }
+
+    /// Check that neither list exceeds [`MAX_FILTER_PATTERNS`]
+    pub fn check_size(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.blocklist.len() <= MAX_FILTER_PATTERNS
+                && self.allowlist.len() <= MAX_FILTER_PATTERNS,
+            "The blocklist and the allowlist may hold at most {} patterns each",
+            MAX_FILTER_PATTERNS
+        );
+        Ok(())
+    }
+
+    /// Apply changes; returns the number of patterns added and removed
+    pub fn apply(&mut self, params: &UpdateEmployerFilterParams) -> (usize, usize) {
+        let before = self.blocklist.len() + self.allowlist.len();
+        let mut removed = 0;
+        if params.clear.unwrap_or(false) {
+            removed += before;
+            self.blocklist.clear();
+            self.allowlist.clear();
+        }
+        removed += remove_patterns(
+            &mut self.blocklist,
+            params.unblock.as_deref().unwrap_or(&[]),
+        );
+        removed += remove_patterns(
+            &mut self.allowlist,
+            params.disallow.as_deref().unwrap_or(&[]),
+        );
+
+        let kept = self.blocklist.len() + self.allowlist.len();
+        add_patterns(&mut self.blocklist, params.block.as_deref().unwrap_or(&[]));
+        add_patterns(&mut self.allowlist, params.allow.as_deref().unwrap_or(&[]));
+        let added = self.blocklist.len() + self.allowlist.len() - kept;
+        (added, removed)
+    }
+}
+
+/// Parameters for update_employer_filter
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateEmployerFilterParams {
+    /// Employer patterns to add to the blocklist (e.g. ["Randstad", "personal*service"])
+    pub block: Option<Vec<String>>,
+
+    /// Patterns to remove from the blocklist
+    pub unblock: Option<Vec<String>>,
+
+    /// Employer patterns to add to the allowlist; once it holds a pattern, only
+    /// matching employers are returned
+    pub allow: Option<Vec<String>>,
+
+    /// Patterns to remove from the allowlist
+    pub disallow: Option<Vec<String>>,
+
+    /// Remove all stored patterns first (default: false; configured patterns
+    /// are kept)
+    pub clear: Option<bool>,
+}
+
+impl UpdateEmployerFilterParams {
+    /// Check that the call changes something
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let lists = [&self.block, &self.unblock, &self.allow, &self.disallow];
+        let has_patterns = lists
+            .iter()
+            .any(|list| list.as_ref().is_some_and(|list| !list.is_empty()));
+        anyhow::ensure!(
+            has_patterns || self.clear.unwrap_or(false),
+            "Nothing to update: pass block, unblock, allow, disallow or clear"
+        );
+        Ok(())
+    }
+}
+
+/// Result from update_employer_filter
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateEmployerFilterResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Number of patterns added
+    pub added: usize,
+
+    /// Number of patterns removed
+    pub removed: usize,
+
+    /// Patterns stored by update_employer_filter
+    pub stored: EmployerFilter,
+
+    /// Patterns in effect (configured and stored)
+    pub effective: EmployerFilter,
+}
+
+/// Parameters for get_employer_filter
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetEmployerFilterParams {}
+
+/// Result from get_employer_filter
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetEmployerFilterResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Patterns from JOBSUCHE_EMPLOYER_BLOCKLIST and JOBSUCHE_EMPLOYER_ALLOWLIST
+    pub configured: EmployerFilter,
+
+    /// Patterns stored by update_employer_filter
+    pub stored: EmployerFilter,
+
+    /// Patterns in effect (configured and stored)
+    pub effective: EmployerFilter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches(
+            "randstad",
+            "Randstad Deutschland GmbH & Co. KG"
+        ));
+        assert!(pattern_matches(
+            "personal*service",
+            "ABC Personal- und Service GmbH"
+        ));
+        assert!(!pattern_matches(
+            "service*personal",
+            "ABC Personalservice GmbH"
+        ));
+        assert!(!pattern_matches("adecco", "BARMER"));
+        assert!(!pattern_matches(" * ", "BARMER"));
+    }
+
+    #[test]
+    fn test_blocklist_wins_over_allowlist() {
+        let filter = EmployerFilter::from_config(&patterns(&["zeitarbeit"]), &[]);
+        assert!(filter.allows("BARMER"));
+        assert!(!filter.allows("Muster Zeitarbeit GmbH"));
+
+        let filter = filter.merged(&EmployerFilter {
+            blocklist: Vec::new(),
+            allowlist: patterns(&["barmer", "zeitarbeit"]),
+        });
+        assert!(filter.allows("BARMER"));
+        assert!(!filter.allows("Siemens AG"));
+        assert!(!filter.allows("Muster Zeitarbeit GmbH"));
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut filter = EmployerFilter::default();
+        let params: UpdateEmployerFilterParams = serde_json::from_value(serde_json::json!({
+            "block": ["Randstad", "randstad", " ", "Adecco"]
+        }))
+        .unwrap();
+        assert_eq!(filter.apply(&params), (2, 0));
+        assert_eq!(filter.blocklist, patterns(&["Randstad", "Adecco"]));
+
+        let params: UpdateEmployerFilterParams = serde_json::from_value(serde_json::json!({
+            "unblock": ["RANDSTAD"],
+            "allow": ["BARMER"]
+        }))
+        .unwrap();
+        assert_eq!(filter.apply(&params), (1, 1));
+        assert_eq!(filter.blocklist, patterns(&["Adecco"]));
+        assert_eq!(filter.allowlist, patterns(&["BARMER"]));
+
+        let params: UpdateEmployerFilterParams =
+            serde_json::from_value(serde_json::json!({"clear": true})).unwrap();
+        assert_eq!(filter.apply(&params), (0, 2));
+        assert!(filter.is_empty());
+    }
+
+    #[test]
+    fn test_validate() {
+        let params: UpdateEmployerFilterParams =
+            serde_json::from_value(serde_json::json!({"block": []})).unwrap();
+        assert!(params.validate().is_err());
+    }
+}
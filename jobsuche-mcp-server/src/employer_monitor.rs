@@ -0,0 +1,291 @@
+//! Employer monitoring
+//!
+//! Recruiters keep an eye on the hiring of competitors: how many postings an
+//! employer has online, which ones are new and whether the number moves
+//! noticeably. `monitor_employers` answers this for several employers in one
+//! call. The postings seen per employer (and location) are persisted, so each
+//! call reports what appeared since the previous one; the first call records
+//! the baseline.
+
+use crate::JobSummary;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Maximum number of employers per monitor_employers call
+pub const MAX_MONITORED_EMPLOYERS: usize = 10;
+
+/// Default number of postings fetched per employer
+pub const DEFAULT_MONITORED_JOBS: u64 = 100;
+
+/// Maximum number of postings fetched per employer
+pub const MAX_MONITORED_JOBS: u64 = 500;
+
+/// Maximum number of known reference numbers kept per employer
+pub const MAX_KNOWN_EMPLOYER_JOBS: usize = 2000;
+
+/// Relative change of the posting count reported as notable
+const NOTABLE_CHANGE_SHARE: f64 = 0.2;
+
+/// Smallest absolute change of the posting count reported as notable
+const NOTABLE_CHANGE_JOBS: u64 = 5;
+
+/// Persisted state of one monitored employer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmployerMonitor {
+    /// Employer name as given by the user
+    pub employer: String,
+
+    /// Location the postings are restricted to
+    pub location: Option<String>,
+
+    /// When the employer was last checked (RFC 3339)
+    pub last_checked_at: String,
+
+    /// Number of current postings at the last check
+    pub total_jobs: Option<u64>,
+
+    /// Reference number -> time of first appearance (RFC 3339)
+    pub known_jobs: BTreeMap<String, String>,
+}
+
+impl EmployerMonitor {
+    /// Start monitoring with the given postings as baseline
+    pub fn new(
+        employer: &str,
+        location: Option<&str>,
+        jobs: &[JobSummary],
+        total_jobs: Option<u64>,
+        now: &str,
+    ) -> Self {
+        let mut monitor = Self {
+            employer: employer.trim().to_string(),
+            location: location.map(|location| location.trim().to_string()),
+            last_checked_at: now.to_string(),
+            total_jobs,
+            known_jobs: BTreeMap::new(),
+        };
+        monitor.absorb(jobs.to_vec(), total_jobs, now);
+        monitor
+    }
+
+    /// Record the postings of a check and return the ones not known before
+    pub fn absorb(
+        &mut self,
+        jobs: Vec<JobSummary>,
+        total_jobs: Option<u64>,
+        now: &str,
+    ) -> Vec<JobSummary> {
+        let mut new_jobs = Vec::new();
+        for job in jobs {
+            if self.known_jobs.contains_key(&job.reference_number) {
+                continue;
+            }
+            self.known_jobs
+                .insert(job.reference_number.clone(), now.to_string());
+            new_jobs.push(job);
+        }
+        self.last_checked_at = now.to_string();
+        self.total_jobs = total_jobs;
+        self.prune();
+        new_jobs
+    }
+
+    /// Drop the oldest known jobs beyond [`MAX_KNOWN_EMPLOYER_JOBS`]
+    fn prune(&mut self) {
+        if self.known_jobs.len() <= MAX_KNOWN_EMPLOYER_JOBS {
+            return;
+        }
+
+        let mut by_age: Vec<(String, String)> =
+            std::mem::take(&mut self.known_jobs).into_iter().collect();
+        // RFC 3339 timestamps in UTC sort chronologically
+        by_age.sort_by(|a, b| b.1.cmp(&a.1));
+        by_age.truncate(MAX_KNOWN_EMPLOYER_JOBS);
+        self.known_jobs = by_age.into_iter().collect();
+    }
+}
+
+/// Storage key of a monitored employer: lowercase name, plus the location if any
+pub fn monitor_key(employer: &str, location: Option<&str>) -> String {
+    let employer = employer.trim().to_lowercase();
+    match location.map(str::trim).filter(|l| !l.is_empty()) {
+        Some(location) => format!("{}@{}", employer, location.to_lowercase()),
+        None => employer,
+    }
+}
+
+/// Notable changes between two checks, in plain words
+pub fn notable_changes(
+    previous_total: Option<u64>,
+    total: Option<u64>,
+    new_jobs: usize,
+) -> Vec<String> {
+    let mut changes = Vec::new();
+    if new_jobs > 0 {
+        changes.push(format!("{} new postings", new_jobs));
+    }
+
+    let (Some(previous), Some(current)) = (previous_total, total) else {
+        return changes;
+    };
+    if previous > 0 && current == 0 {
+        changes.push(format!("no postings online anymore (was {})", previous));
+        return changes;
+    }
+    if previous == 0 && current > 0 {
+        changes.push(format!("started posting ({} postings)", current));
+        return changes;
+    }
+
+    let difference = current.abs_diff(previous);
+    let share = difference as f64 / previous.max(1) as f64;
+    if difference >= NOTABLE_CHANGE_JOBS && share >= NOTABLE_CHANGE_SHARE {
+        let direction = if current > previous { "up" } else { "down" };
+        changes.push(format!(
+            "postings {} {:.0}% ({} → {})",
+            direction,
+            share * 100.0,
+            previous,
+            current
+        ));
+    }
+    changes
+}
+
+/// Parameters for monitor_employers
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MonitorEmployersParams {
+    /// Employer names as shown in search results (1-10, e.g. ["BARMER", "TK"])
+    pub employers: Vec<String>,
+
+    /// Only postings in or near this location
+    pub location: Option<String>,
+
+    /// Maximum number of postings fetched per employer to detect new ones
+    /// (default: 100, max: 500)
+    pub max_jobs_per_employer: Option<u64>,
+}
+
+/// Monitoring result of one employer
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EmployerMonitorEntry {
+    /// Employer that was requested
+    pub employer: String,
+
+    /// Number of current postings reported by the API
+    pub total_jobs: Option<u64>,
+
+    /// Number of postings at the previous check (`None` on the first check)
+    pub previous_total_jobs: Option<u64>,
+
+    /// When the employer was previously checked (RFC 3339)
+    pub previous_checked_at: Option<String>,
+
+    /// True if this check recorded the baseline; nothing is reported as new then
+    pub first_check: bool,
+
+    /// Number of new postings
+    pub new_jobs_count: usize,
+
+    /// Postings that did not appear in earlier checks
+    pub new_jobs: Vec<JobSummary>,
+
+    /// Notable changes since the previous check (e.g. "postings up 40% (30 → 42)")
+    pub changes: Vec<String>,
+
+    /// Error message if the search failed
+    pub error: Option<String>,
+}
+
+/// Result from monitor_employers
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MonitorEmployersResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// When the check ran (RFC 3339)
+    pub checked_at: String,
+
+    /// Number of employers checked
+    pub employers_checked: usize,
+
+    /// Total number of new postings across all employers
+    pub total_new_jobs: usize,
+
+    /// Results per employer, in the requested order
+    pub employers: Vec<EmployerMonitorEntry>,
+
+    /// True if the client cancelled the request; `employers` then holds the
+    /// employers checked before cancellation
+    pub cancelled: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(reference_number: &str) -> JobSummary {
+        serde_json::from_value(serde_json::json!({
+            "reference_number": reference_number,
+            "title": "Job",
+            "employer": "BARMER",
+            "location": "Wuppertal",
+            "published_date": null,
+            "external_url": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_baseline_is_not_reported_as_new() {
+        let mut monitor = EmployerMonitor::new(
+            " BARMER ",
+            Some("Wuppertal"),
+            &[job("A"), job("B")],
+            Some(2),
+            "2025-10-20T08:00:00Z",
+        );
+        assert_eq!(monitor.employer, "BARMER");
+        assert_eq!(monitor.known_jobs.len(), 2);
+
+        let new_jobs = monitor.absorb(vec![job("B"), job("C")], Some(3), "2025-10-21T08:00:00Z");
+        assert_eq!(new_jobs.len(), 1);
+        assert_eq!(new_jobs[0].reference_number, "C");
+        assert_eq!(monitor.total_jobs, Some(3));
+        assert_eq!(monitor.last_checked_at, "2025-10-21T08:00:00Z");
+    }
+
+    #[test]
+    fn test_monitor_key() {
+        assert_eq!(monitor_key(" BARMER ", None), "barmer");
+        assert_eq!(monitor_key("BARMER", Some(" ")), "barmer");
+        assert_eq!(monitor_key("BARMER", Some("Köln")), "barmer@köln");
+    }
+
+    #[test]
+    fn test_notable_changes() {
+        assert!(notable_changes(Some(10), Some(11), 0).is_empty());
+        assert_eq!(
+            notable_changes(Some(10), Some(15), 5),
+            vec!["5 new postings", "postings up 50% (10 → 15)"]
+        );
+        assert_eq!(
+            notable_changes(Some(100), Some(70), 0),
+            vec!["postings down 30% (100 → 70)"]
+        );
+        // Large relative change, but too few postings to matter
+        assert!(notable_changes(Some(2), Some(4), 0).is_empty());
+        assert_eq!(
+            notable_changes(Some(8), Some(0), 0),
+            vec!["no postings online anymore (was 8)"]
+        );
+        assert_eq!(
+            notable_changes(Some(0), Some(3), 3),
+            vec!["3 new postings", "started posting (3 postings)"]
+        );
+        assert!(notable_changes(None, Some(3), 0).is_empty());
+    }
+}
@@ -0,0 +1,424 @@
+//! Salary estimates from the Entgeltatlas
+//!
+//! Most postings state no salary. The Entgeltatlas of the Bundesagentur für
+//! Arbeit publishes the median monthly gross pay of full-time employees per
+//! occupation (KldB 2010 code) and region. get_job_details can attach these
+//! statistics as a clearly labelled estimate; they describe the occupation in
+//! the posting's federal state, not what the employer offers.
+//! get_salary_statistics returns the same figures broken down by gender and
+//! age group for negotiation preparation.
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Default base URL of the Entgeltatlas API
+pub const DEFAULT_ENTGELTATLAS_URL: &str =
+    "https://rest.arbeitsagentur.de/infosysbub/entgeltatlas/pc/v1";
+
+/// Public API key of the Entgeltatlas web application
+const API_KEY: &str = "infosysbub-ega";
+
+/// Region code for Germany as a whole
+const GERMANY: (u8, &str) = (1, "Deutschland");
+
+/// Region codes of the federal states (`r` parameter)
+const FEDERAL_STATES: &[(u8, &str)] = &[
+    (4, "Baden-Württemberg"),
+    (5, "Bayern"),
+    (6, "Berlin"),
+    (7, "Brandenburg"),
+    (8, "Bremen"),
+    (9, "Hamburg"),
+    (10, "Hessen"),
+    (11, "Mecklenburg-Vorpommern"),
+    (12, "Niedersachsen"),
+    (13, "Nordrhein-Westfalen"),
+    (14, "Rheinland-Pfalz"),
+    (15, "Saarland"),
+    (16, "Sachsen"),
+    (17, "Sachsen-Anhalt"),
+    (18, "Schleswig-Holstein"),
+    (19, "Thüringen"),
+];
+
+/// Gender groups (`g` parameter) with their names in the result
+const GENDERS: &[(u8, &str)] = &[(2, "men"), (3, "women")];
+
+/// Age groups (`a` parameter) with their names in the result
+const AGE_GROUPS: &[(u8, &str)] = &[(2, "under_25"), (3, "25_to_54"), (4, "55_and_older")];
+
+/// Label attached to every estimate
+const ESTIMATE_NOTE: &str = "Estimate, not offered by the employer: median monthly gross pay \
+     of full-time employees in this occupation and region (Entgeltatlas der Bundesagentur für Arbeit)";
+
+/// Median pay statistics for an occupation and region
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SalaryEstimate {
+    /// Always true; the figures are statistics, not the posting's salary
+    pub is_estimate: bool,
+
+    /// What the figures mean and where they come from
+    pub note: String,
+
+    /// KldB 2010 occupation code
+    pub occupation_code: String,
+
+    /// Region of the statistics (federal state, or "Deutschland")
+    pub region: String,
+
+    /// Median monthly gross pay in EUR
+    pub median_monthly_gross: f64,
+
+    /// Lower quartile of the monthly gross pay in EUR
+    pub lower_quartile: Option<f64>,
+
+    /// Upper quartile of the monthly gross pay in EUR
+    pub upper_quartile: Option<f64>,
+
+    /// Number of employees the statistics are based on
+    pub employees: Option<u64>,
+}
+
+/// Parameters for get_salary_statistics
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetSalaryStatisticsParams {
+    /// KldB 2010 occupation code (five digits, e.g. "43104" for software
+    /// development)
+    pub occupation_code: String,
+
+    /// Federal state (e.g. "Bayern") or "Deutschland" (default)
+    pub region: Option<String>,
+}
+
+/// Pay statistics of one group of employees
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SalaryGroup {
+    /// Group ("all", "men", "women", "under_25", "25_to_54", "55_and_older")
+    pub group: String,
+
+    /// Median monthly gross pay in EUR
+    pub median_monthly_gross: f64,
+
+    /// Lower quartile of the monthly gross pay in EUR
+    pub lower_quartile: Option<f64>,
+
+    /// Upper quartile of the monthly gross pay in EUR
+    pub upper_quartile: Option<f64>,
+
+    /// Number of employees the statistics are based on
+    pub employees: Option<u64>,
+}
+
+/// Result from get_salary_statistics
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetSalaryStatisticsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// What the figures mean and where they come from
+    pub note: String,
+
+    /// KldB 2010 occupation code
+    pub occupation_code: String,
+
+    /// Region of the statistics; "Deutschland" if the federal state has no data
+    pub region: String,
+
+    /// All full-time employees of the occupation
+    pub overall: SalaryGroup,
+
+    /// By gender; groups without published figures are left out
+    pub by_gender: Vec<SalaryGroup>,
+
+    /// By age group; groups without published figures are left out
+    pub by_age: Vec<SalaryGroup>,
+}
+
+/// Region code of a federal state, matched case-insensitively
+pub(crate) fn federal_state(name: &str) -> Option<(u8, &'static str)> {
+    let name = name.trim().to_lowercase();
+    FEDERAL_STATES
+        .iter()
+        .find(|(_, state)| state.to_lowercase() == name)
+        .copied()
+}
+
+/// Check that `code` is a KldB 2010 occupation code (five digits)
+pub fn validate_occupation_code(code: &str) -> Result<&str> {
+    let code = code.trim();
+    if code.len() != 5 || !code.chars().all(|c| c.is_ascii_digit()) {
+        anyhow::bail!(
+            "Invalid occupation code '{}': expected a five-digit KldB 2010 code such as 43104",
+            code
+        );
+    }
+    Ok(code)
+}
+
+/// Region code of a region name: a federal state or Germany
+fn resolve_region(name: Option<&str>) -> Result<(u8, &'static str)> {
+    match name.map(|name| name.trim().to_lowercase()).as_deref() {
+        None | Some("") | Some("deutschland") | Some("germany") => Ok(GERMANY),
+        Some(name) => federal_state(name).with_context(|| {
+            format!(
+                "Unknown region '{}': expected a federal state such as Bayern, or Deutschland",
+                name
+            )
+        }),
+    }
+}
+
+/// Extract the figures of one group from an Entgeltatlas response
+fn parse_group(response: &Value, group: &str) -> Option<SalaryGroup> {
+    let entry = match response {
+        Value::Array(entries) => entries.first()?,
+        entry => entry,
+    };
+    let number = |field: &str| {
+        entry
+            .get(field)
+            .and_then(Value::as_f64)
+            .filter(|n| *n > 0.0)
+    };
+
+    Some(SalaryGroup {
+        group: group.to_string(),
+        median_monthly_gross: number("entgelt")?,
+        lower_quartile: number("entgeltQ25"),
+        upper_quartile: number("entgeltQ75"),
+        employees: entry.get("besetzung").and_then(Value::as_u64),
+    })
+}
+
+/// Extract the statistics from an Entgeltatlas response
+fn parse_statistics(
+    response: &Value,
+    occupation_code: &str,
+    region: &str,
+) -> Option<SalaryEstimate> {
+    let figures = parse_group(response, "all")?;
+    Some(SalaryEstimate {
+        is_estimate: true,
+        note: ESTIMATE_NOTE.to_string(),
+        occupation_code: occupation_code.to_string(),
+        region: region.to_string(),
+        median_monthly_gross: figures.median_monthly_gross,
+        lower_quartile: figures.lower_quartile,
+        upper_quartile: figures.upper_quartile,
+        employees: figures.employees,
+    })
+}
+
+/// Client for the Entgeltatlas API
+#[derive(Debug, Clone)]
+pub struct EntgeltatlasClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl EntgeltatlasClient {
+    /// Create a client for the given base URL
+    pub fn new(base_url: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .context("Failed to create Entgeltatlas client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Median pay for an occupation in a federal state, or in Germany if the
+    /// state is unknown or has no data; `None` if there are no statistics
+    pub async fn estimate(
+        &self,
+        occupation_code: &str,
+        federal_state_name: Option<&str>,
+    ) -> Result<Option<SalaryEstimate>> {
+        let occupation_code = validate_occupation_code(occupation_code)?;
+
+        if let Some((code, state)) = federal_state_name.and_then(federal_state) {
+            if let Some(estimate) = self.fetch(occupation_code, code, state).await? {
+                return Ok(Some(estimate));
+            }
+        }
+        self.fetch(occupation_code, GERMANY.0, GERMANY.1).await
+    }
+
+    /// Median pay for an occupation in a region (federal state or Germany),
+    /// overall and by gender and age group
+    ///
+    /// Falls back to Germany if the federal state has no data.
+    pub async fn statistics(
+        &self,
+        occupation_code: &str,
+        region_name: Option<&str>,
+    ) -> Result<Option<GetSalaryStatisticsResult>> {
+        let occupation_code = validate_occupation_code(occupation_code)?;
+        let (mut region_code, mut region) = resolve_region(region_name)?;
+
+        let mut overall = self
+            .query(occupation_code, region_code, 1, 1)
+            .await?
+            .and_then(|body| parse_group(&body, "all"));
+        if overall.is_none() && region_code != GERMANY.0 {
+            (region_code, region) = GERMANY;
+            overall = self
+                .query(occupation_code, region_code, 1, 1)
+                .await?
+                .and_then(|body| parse_group(&body, "all"));
+        }
+        let Some(overall) = overall else {
+            return Ok(None);
+        };
+
+        let mut by_gender = Vec::new();
+        for &(gender, group) in GENDERS {
+            let body = self.query(occupation_code, region_code, gender, 1).await?;
+            by_gender.extend(body.and_then(|body| parse_group(&body, group)));
+        }
+        let mut by_age = Vec::new();
+        for &(age, group) in AGE_GROUPS {
+            let body = self.query(occupation_code, region_code, 1, age).await?;
+            by_age.extend(body.and_then(|body| parse_group(&body, group)));
+        }
+
+        Ok(Some(GetSalaryStatisticsResult {
+            request_id: String::new(),
+            note: ESTIMATE_NOTE.to_string(),
+            occupation_code: occupation_code.to_string(),
+            region: region.to_string(),
+            overall,
+            by_gender,
+            by_age,
+        }))
+    }
+
+    async fn fetch(
+        &self,
+        occupation_code: &str,
+        region_code: u8,
+        region: &str,
+    ) -> Result<Option<SalaryEstimate>> {
+        let body = self.query(occupation_code, region_code, 1, 1).await?;
+        Ok(body.and_then(|body| parse_statistics(&body, occupation_code, region)))
+    }
+
+    /// Raw statistics of one gender (`g`) and age group (`a`), all performance
+    /// levels and industries; `None` if the API has none
+    async fn query(
+        &self,
+        occupation_code: &str,
+        region_code: u8,
+        gender: u8,
+        age: u8,
+    ) -> Result<Option<Value>> {
+        let url = format!("{}/entgelte/{}", self.base_url, occupation_code);
+        let response = self
+            .client
+            .get(&url)
+            .header("X-API-Key", API_KEY)
+            .query(&[
+                ("l", "1"),
+                ("r", &region_code.to_string()),
+                ("g", &gender.to_string()),
+                ("a", &age.to_string()),
+                ("b", "1"),
+            ])
+            .send()
+            .await
+            .context("Entgeltatlas request failed")?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("Entgeltatlas responded with HTTP {}", status);
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .context("Invalid Entgeltatlas response")?;
+        Ok(Some(body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_federal_state() {
+        assert_eq!(federal_state("Berlin"), Some((6, "Berlin")));
+        assert_eq!(
+            federal_state(" nordrhein-westfalen "),
+            Some((13, "Nordrhein-Westfalen"))
+        );
+        assert_eq!(federal_state("THÜRINGEN"), Some((19, "Thüringen")));
+        assert_eq!(federal_state("Wien"), None);
+    }
+
+    #[test]
+    fn test_resolve_region() {
+        assert_eq!(resolve_region(None).unwrap(), GERMANY);
+        assert_eq!(resolve_region(Some("Deutschland")).unwrap(), GERMANY);
+        assert_eq!(resolve_region(Some("bayern")).unwrap(), (5, "Bayern"));
+        assert!(resolve_region(Some("Tirol")).is_err());
+    }
+
+    #[test]
+    fn test_validate_occupation_code() {
+        assert_eq!(validate_occupation_code(" 43104 ").unwrap(), "43104");
+        assert!(validate_occupation_code("4310").is_err());
+        assert!(validate_occupation_code("4310x").is_err());
+    }
+
+    #[test]
+    fn test_parse_statistics() {
+        let response = json!([{
+            "kldb": "43104",
+            "entgelt": 5123.0,
+            "entgeltQ25": 4201.0,
+            "entgeltQ75": 6234.0,
+            "besetzung": 51234
+        }]);
+        let estimate = parse_statistics(&response, "43104", "Berlin").unwrap();
+        assert!(estimate.is_estimate);
+        assert_eq!(estimate.region, "Berlin");
+        assert_eq!(estimate.median_monthly_gross, 5123.0);
+        assert_eq!(estimate.lower_quartile, Some(4201.0));
+        assert_eq!(estimate.employees, Some(51234));
+
+        // Suppressed values (too few employees) come without a median
+        assert_eq!(
+            parse_statistics(&json!([{"entgelt": null}]), "43104", "Bremen"),
+            None
+        );
+        assert_eq!(parse_statistics(&json!([]), "43104", "Bremen"), None);
+    }
+
+    #[test]
+    fn test_parse_group() {
+        let response = json!({"entgelt": 3650.0, "entgeltQ25": null, "besetzung": 812});
+        assert_eq!(
+            parse_group(&response, "women"),
+            Some(SalaryGroup {
+                group: "women".to_string(),
+                median_monthly_gross: 3650.0,
+                lower_quartile: None,
+                upper_quartile: None,
+                employees: Some(812),
+            })
+        );
+    }
+}
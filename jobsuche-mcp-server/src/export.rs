@@ -0,0 +1,488 @@
+//! Export of job lists
+//!
+//! Writes job lists to CSV files for use in spreadsheets, or to Markdown
+//! reports (see [`crate::report`]). Files are created in the configured export
+//! directory; the file name is restricted to a plain name so a tool call can
+//! never write outside of it.
+
+use crate::report::ReportGrouping;
+use crate::{GetJobDetailsResult, JobSummary, SearchJobsParams};
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// Maximum number of jobs whose details are fetched for one export
+pub const MAX_EXPORT_DETAILS: usize = 50;
+
+/// Public job page on arbeitsagentur.de
+const JOB_PAGE_URL: &str = "https://www.arbeitsagentur.de/jobsuche/jobdetail/";
+
+/// Output format of export_results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Markdown,
+}
+
+impl ExportFormat {
+    /// File extension without dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Markdown => "md",
+        }
+    }
+}
+
+/// A column of the exported file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportColumn {
+    ReferenceNumber,
+    Title,
+    Employer,
+    Location,
+    Salary,
+    EmploymentType,
+    PublishedDate,
+    Url,
+}
+
+impl ExportColumn {
+    /// Column header in the CSV file
+    pub fn header(self) -> &'static str {
+        match self {
+            Self::ReferenceNumber => "reference_number",
+            Self::Title => "title",
+            Self::Employer => "employer",
+            Self::Location => "location",
+            Self::Salary => "salary",
+            Self::EmploymentType => "employment_type",
+            Self::PublishedDate => "published_date",
+            Self::Url => "url",
+        }
+    }
+
+    /// Human-readable name used in reports
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ReferenceNumber => "Reference",
+            Self::Title => "Title",
+            Self::Employer => "Employer",
+            Self::Location => "Location",
+            Self::Salary => "Salary",
+            Self::EmploymentType => "Employment type",
+            Self::PublishedDate => "Published",
+            Self::Url => "Link",
+        }
+    }
+
+    /// Whether the column is only available from the job details
+    pub fn needs_details(self) -> bool {
+        matches!(self, Self::Salary | Self::EmploymentType)
+    }
+}
+
+/// Columns exported when none are requested
+pub const DEFAULT_COLUMNS: &[ExportColumn] = &[
+    ExportColumn::Title,
+    ExportColumn::Employer,
+    ExportColumn::Location,
+    ExportColumn::Salary,
+    ExportColumn::Url,
+];
+
+/// One exported job
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExportRow {
+    reference_number: String,
+    title: Option<String>,
+    employer: Option<String>,
+    location: Option<String>,
+    salary: Option<String>,
+    employment_type: Option<String>,
+    published_date: Option<String>,
+    url: String,
+}
+
+impl ExportRow {
+    /// Row from a search result
+    pub fn from_summary(job: &JobSummary) -> Self {
+        Self {
+            reference_number: job.reference_number.clone(),
+            title: Some(job.title.clone()),
+            employer: Some(job.employer.clone()),
+            location: Some(job.location.clone()),
+            salary: None,
+            employment_type: None,
+            published_date: job.published_date.clone(),
+            url: job_url(&job.reference_number, job.external_url.as_deref()),
+        }
+    }
+
+    /// Row that only knows the reference number, to be filled by [`Self::with_details`]
+    pub fn from_reference(reference_number: &str) -> Self {
+        Self {
+            reference_number: reference_number.to_string(),
+            url: job_url(reference_number, None),
+            ..Self::default()
+        }
+    }
+
+    /// Reference number of the job
+    pub fn reference_number(&self) -> &str {
+        &self.reference_number
+    }
+
+    /// Fill in the values known from the job details
+    pub fn with_details(self, details: &GetJobDetailsResult) -> Self {
+        Self {
+            reference_number: details.reference_number.clone(),
+            title: details.title.clone().or(self.title),
+            employer: details.employer.clone().or(self.employer),
+            location: details.location.clone().or(self.location),
+            salary: details.salary.clone(),
+            employment_type: details.employment_type.clone(),
+            published_date: self.published_date.or(details.first_published.clone()),
+            url: match details.external_url {
+                Some(ref url) => job_url(&details.reference_number, Some(url)),
+                None => self.url,
+            },
+        }
+    }
+
+    /// Value of a column, empty if unknown
+    pub fn value(&self, column: ExportColumn) -> &str {
+        let value = match column {
+            ExportColumn::ReferenceNumber => return &self.reference_number,
+            ExportColumn::Url => return &self.url,
+            ExportColumn::Title => &self.title,
+            ExportColumn::Employer => &self.employer,
+            ExportColumn::Location => &self.location,
+            ExportColumn::Salary => &self.salary,
+            ExportColumn::EmploymentType => &self.employment_type,
+            ExportColumn::PublishedDate => &self.published_date,
+        };
+        value.as_deref().unwrap_or("")
+    }
+}
+
+/// Public arbeitsagentur.de page of a job
+pub fn web_url(reference_number: &str) -> String {
+    let mut url = JOB_PAGE_URL.to_string();
+    for byte in reference_number.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            url.push(byte as char);
+        } else {
+            url.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    url
+}
+
+/// Link to a job: the external URL if there is one, else the arbeitsagentur.de page
+pub fn job_url(reference_number: &str, external_url: Option<&str>) -> String {
+    match external_url {
+        Some(url) if !url.is_empty() => url.to_string(),
+        _ => web_url(reference_number),
+    }
+}
+
+/// Validate a requested file name, or derive one from the timestamp
+///
+/// Only letters, digits, `-`, `_` and `.` are accepted; `extension` is
+/// appended if missing.
+pub fn export_file_name(
+    requested: Option<&str>,
+    timestamp: &str,
+    extension: &str,
+) -> Result<String> {
+    let name = match requested {
+        Some(name) => name.trim().to_string(),
+        None => {
+            let stamp: String = timestamp.chars().filter(char::is_ascii_digit).collect();
+            format!("jobs-{}", stamp)
+        }
+    };
+
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        anyhow::bail!(
+            "Invalid file name '{}': use letters, digits, '-', '_' and '.' only",
+            name
+        );
+    }
+
+    let extension = format!(".{}", extension);
+    if name.to_lowercase().ends_with(&extension) {
+        Ok(name)
+    } else {
+        Ok(format!("{}{}", name, extension))
+    }
+}
+
+/// Write rows as CSV with a header line
+///
+/// The file starts with a UTF-8 byte order mark so spreadsheet applications
+/// display umlauts correctly.
+pub fn write_csv(path: &Path, columns: &[ExportColumn], rows: &[ExportRow]) -> Result<()> {
+    let mut file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    file.write_all(b"\xEF\xBB\xBF")?;
+
+    let mut writer = csv::Writer::from_writer(file);
+    writer.write_record(columns.iter().map(|column| column.header()))?;
+    for row in rows {
+        writer.write_record(
+            columns
+                .iter()
+                .map(|column| spreadsheet_safe(row.value(*column))),
+        )?;
+    }
+    writer
+        .flush()
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Neutralize a cell that a spreadsheet would evaluate as a formula
+///
+/// Job titles and employer names come from third parties. Excel and
+/// LibreOffice run cells starting with `=`, `+`, `-` or `@` (and tab or
+/// carriage return) as formulas, so such values get a leading `'`, which
+/// makes them plain text.
+fn spreadsheet_safe(value: &str) -> String {
+    if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Parameters for export_results
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExportResultsParams {
+    /// Search to export (same parameters as search_jobs)
+    pub search: Option<SearchJobsParams>,
+
+    /// Reference numbers to export instead of a search
+    pub reference_numbers: Option<Vec<String>>,
+
+    /// Columns to export (default: title, employer, location, salary, url)
+    /// Salary and employment_type require fetching the job details
+    pub columns: Option<Vec<ExportColumn>>,
+
+    /// Output format: "csv" (default) or "markdown"
+    pub format: Option<ExportFormat>,
+
+    /// Group a Markdown report by "employer" or "location" (default: no grouping)
+    pub group_by: Option<ReportGrouping>,
+
+    /// Heading of a Markdown report (default: derived from the search)
+    pub title: Option<String>,
+
+    /// File name inside the export directory (default: jobs-<timestamp>.csv or .md)
+    pub file_name: Option<String>,
+}
+
+/// Result from export_results
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportResultsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Path of the written file
+    pub path: String,
+
+    /// Output format
+    pub format: ExportFormat,
+
+    /// Number of exported jobs
+    pub rows: usize,
+
+    /// Exported columns
+    pub columns: Vec<ExportColumn>,
+
+    /// Jobs exported without details because fetching failed or the limit of
+    /// 50 detail fetches was reached
+    pub missing_details: Vec<String>,
+
+    /// The Markdown report, to hand directly to the user (Markdown format only)
+    pub report: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary() -> JobSummary {
+        JobSummary {
+            reference_number: "10001-1234567890-S".to_string(),
+            title: "Entwickler, \"Backend\"".to_string(),
+            employer: "Müller GmbH".to_string(),
+            location: "Köln".to_string(),
+            distance_km: None,
+            coordinates: None,
+            country: None,
+            published_date: Some("2025-10-15".to_string()),
+            published_date_raw: None,
+            published_days_ago: None,
+            published_ago: None,
+            external_url: None,
+            web_url: String::new(),
+            duplicates: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_job_url() {
+        assert_eq!(
+            job_url("REF-1", None),
+            "https://www.arbeitsagentur.de/jobsuche/jobdetail/REF-1"
+        );
+        assert_eq!(
+            job_url("REF-1", Some("https://example.com/job")),
+            "https://example.com/job"
+        );
+    }
+
+    #[test]
+    fn test_web_url_encodes_reference_number() {
+        assert_eq!(
+            web_url("10001-1234567890-S"),
+            "https://www.arbeitsagentur.de/jobsuche/jobdetail/10001-1234567890-S"
+        );
+        assert_eq!(
+            web_url("A/B ü"),
+            "https://www.arbeitsagentur.de/jobsuche/jobdetail/A%2FB%20%C3%BC"
+        );
+    }
+
+    #[test]
+    fn test_export_file_name() {
+        assert_eq!(
+            export_file_name(None, "2025-10-20T10:00:00Z", "csv").unwrap(),
+            "jobs-20251020100000.csv"
+        );
+        assert_eq!(
+            export_file_name(Some("berlin_jobs"), "", "csv").unwrap(),
+            "berlin_jobs.csv"
+        );
+        assert_eq!(
+            export_file_name(Some("report.CSV"), "", "csv").unwrap(),
+            "report.CSV"
+        );
+        assert_eq!(
+            export_file_name(Some("weekly"), "", "md").unwrap(),
+            "weekly.md"
+        );
+        for invalid in ["../etc/passwd", "a/b", ".hidden", "", "name with space"] {
+            assert!(
+                export_file_name(Some(invalid), "", "csv").is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_csv_quotes_and_bom() {
+        let path = std::env::temp_dir().join(format!("jobsuche-export-{}.csv", std::process::id()));
+        let rows = vec![ExportRow::from_summary(&summary())];
+
+        write_csv(&path, DEFAULT_COLUMNS, &rows).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let content = content.strip_prefix('\u{feff}').unwrap();
+        let mut lines = content.lines();
+        assert_eq!(lines.next(), Some("title,employer,location,salary,url"));
+        assert_eq!(
+            lines.next(),
+            Some(
+                "\"Entwickler, \"\"Backend\"\"\",Müller GmbH,Köln,,\
+                 https://www.arbeitsagentur.de/jobsuche/jobdetail/10001-1234567890-S"
+            )
+        );
+    }
+
+    #[test]
+    fn test_write_csv_neutralizes_formulas() {
+        let path = std::env::temp_dir().join(format!(
+            "jobsuche-export-formula-{}.csv",
+            std::process::id()
+        ));
+        let mut job = summary();
+        job.title = "=HYPERLINK(\"http://evil.example\",\"Apply\")".to_string();
+        job.employer = "@SUM(A1:A9)".to_string();
+        job.location = "-2+3".to_string();
+        let rows = vec![ExportRow::from_summary(&job)];
+
+        write_csv(&path, DEFAULT_COLUMNS, &rows).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut reader =
+            csv::Reader::from_reader(content.strip_prefix('\u{feff}').unwrap().as_bytes());
+        let record = reader.records().next().unwrap().unwrap();
+        assert_eq!(&record[0], "'=HYPERLINK(\"http://evil.example\",\"Apply\")");
+        assert_eq!(&record[1], "'@SUM(A1:A9)");
+        assert_eq!(&record[2], "'-2+3");
+        assert!(record[4].starts_with("https://"));
+    }
+
+    #[test]
+    fn test_spreadsheet_safe() {
+        assert_eq!(spreadsheet_safe("+49 30 1234"), "'+49 30 1234");
+        assert_eq!(spreadsheet_safe("\tcmd"), "'\tcmd");
+        assert_eq!(spreadsheet_safe("Müller GmbH"), "Müller GmbH");
+        assert_eq!(spreadsheet_safe("3.000 - 4.000 EUR"), "3.000 - 4.000 EUR");
+        assert_eq!(spreadsheet_safe(""), "");
+    }
+
+    #[test]
+    fn test_row_with_details() {
+        let details: GetJobDetailsResult = serde_json::from_value(serde_json::json!({
+            "reference_number": "REF-1",
+            "title": "Pflegefachkraft",
+            "salary": "3.500 EUR",
+            "raw_data": {}
+        }))
+        .unwrap();
+
+        let row = ExportRow::from_reference("REF-1").with_details(&details);
+        assert_eq!(row.value(ExportColumn::Title), "Pflegefachkraft");
+        assert_eq!(row.value(ExportColumn::Salary), "3.500 EUR");
+        assert_eq!(row.value(ExportColumn::Employer), "");
+        assert_eq!(
+            row.value(ExportColumn::Url),
+            "https://www.arbeitsagentur.de/jobsuche/jobdetail/REF-1"
+        );
+    }
+
+    #[test]
+    fn test_columns_deserialize() {
+        let params: ExportResultsParams = serde_json::from_value(serde_json::json!({
+            "reference_numbers": ["REF-1"],
+            "columns": ["reference_number", "employment_type"]
+        }))
+        .unwrap();
+        let columns = params.columns.unwrap();
+        assert_eq!(
+            columns,
+            vec![ExportColumn::ReferenceNumber, ExportColumn::EmploymentType]
+        );
+        assert!(columns[1].needs_details());
+    }
+}
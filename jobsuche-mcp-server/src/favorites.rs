@@ -0,0 +1,245 @@
+//! Favorites (bookmarked jobs)
+//!
+//! A favorite stores the reference number together with a snapshot of the key
+//! job details and user-supplied labels, so a shortlist survives restarts and
+//! remains readable even after the posting has been taken down. The start
+//! date and application deadline are kept for list_upcoming_deadlines.
+
+use crate::GetJobDetailsResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A bookmarked job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Favorite {
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Job title at the time of saving
+    pub title: Option<String>,
+
+    /// Employer name at the time of saving
+    pub employer: Option<String>,
+
+    /// Location at the time of saving
+    pub location: Option<String>,
+
+    /// Salary information at the time of saving
+    pub salary: Option<String>,
+
+    /// Employment type at the time of saving
+    pub employment_type: Option<String>,
+
+    /// Start date (YYYY-MM-DD) at the time of saving
+    #[serde(default)]
+    pub start_date: Option<String>,
+
+    /// Application deadline (YYYY-MM-DD) named in the description at the time
+    /// of saving
+    #[serde(default)]
+    pub application_deadline: Option<String>,
+
+    /// User-supplied labels (e.g. "apply", "backup")
+    pub labels: Vec<String>,
+
+    /// When the job was first saved (RFC 3339)
+    pub saved_at: String,
+
+    /// When the snapshot was last refreshed (RFC 3339)
+    pub updated_at: String,
+}
+
+impl Favorite {
+    /// Create a favorite from job details
+    pub fn from_details(details: &GetJobDetailsResult, labels: Vec<String>, now: &str) -> Self {
+        Self {
+            reference_number: details.reference_number.clone(),
+            title: details.title.clone(),
+            employer: details.employer.clone(),
+            location: details.location.clone(),
+            salary: details.salary.clone(),
+            employment_type: details.employment_type.clone(),
+            start_date: details.start_date.clone(),
+            application_deadline: details.application_deadline.clone(),
+            labels: normalize_labels(labels),
+            saved_at: now.to_string(),
+            updated_at: now.to_string(),
+        }
+    }
+
+    /// Refresh the snapshot of an existing favorite and add new labels
+    pub fn refresh(
+        mut self,
+        details: &GetJobDetailsResult,
+        labels: Vec<String>,
+        now: &str,
+    ) -> Self {
+        let mut all_labels = std::mem::take(&mut self.labels);
+        all_labels.extend(labels);
+
+        Self {
+            labels: normalize_labels(all_labels),
+            saved_at: self.saved_at,
+            ..Self::from_details(details, Vec::new(), now)
+        }
+    }
+
+    /// Whether the favorite carries the given label (case-insensitive)
+    pub fn has_label(&self, label: &str) -> bool {
+        self.labels
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(label.trim()))
+    }
+}
+
+/// Trim labels, drop empty ones and remove duplicates (case-insensitive)
+fn normalize_labels(labels: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for label in labels {
+        let label = label.trim();
+        if !label.is_empty() && !normalized.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+            normalized.push(label.to_string());
+        }
+    }
+    normalized
+}
+
+/// Parameters for save_favorite
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SaveFavoriteParams {
+    /// Job reference number (refnr from search results)
+    pub reference_number: String,
+
+    /// Labels to attach (e.g. ["apply", "remote"]); added to existing labels
+    pub labels: Option<Vec<String>>,
+}
+
+/// Result from save_favorite
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SaveFavoriteResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// The stored favorite
+    pub favorite: Favorite,
+
+    /// True if the job was already a favorite and has been refreshed
+    pub updated: bool,
+}
+
+/// Parameters for list_favorites
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListFavoritesParams {
+    /// Only return favorites carrying this label
+    pub label: Option<String>,
+}
+
+/// Result from list_favorites
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListFavoritesResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Number of favorites returned
+    pub favorites_count: usize,
+
+    /// Favorites, most recently saved first
+    pub favorites: Vec<Favorite>,
+}
+
+/// Parameters for remove_favorite
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RemoveFavoriteParams {
+    /// Job reference number of the favorite to remove
+    pub reference_number: String,
+}
+
+/// Result from remove_favorite
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RemoveFavoriteResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Reference number that was requested
+    pub reference_number: String,
+
+    /// False if the job was not a favorite
+    pub removed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(title: &str) -> GetJobDetailsResult {
+        // Omitted optional fields deserialize as None
+        serde_json::from_value(serde_json::json!({
+            "reference_number": "10001-1234567890-S",
+            "title": title,
+            "employer": "Example GmbH",
+            "location": "Berlin (10115)",
+            "employment_type": "Vollzeit",
+            "salary": "50.000 EUR",
+            "raw_data": {}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_details() {
+        let favorite = Favorite::from_details(
+            &details("Backend Developer"),
+            vec![" apply ".to_string(), "".to_string(), "Apply".to_string()],
+            "2025-10-20T10:00:00Z",
+        );
+
+        assert_eq!(favorite.reference_number, "10001-1234567890-S");
+        assert_eq!(favorite.title, Some("Backend Developer".to_string()));
+        assert_eq!(favorite.salary, Some("50.000 EUR".to_string()));
+        assert_eq!(favorite.labels, vec!["apply"]);
+        assert_eq!(favorite.saved_at, favorite.updated_at);
+    }
+
+    #[test]
+    fn test_refresh_keeps_saved_at_and_merges_labels() {
+        let favorite = Favorite::from_details(
+            &details("Old Title"),
+            vec!["apply".to_string()],
+            "2025-10-20T10:00:00Z",
+        );
+        let refreshed = favorite.refresh(
+            &details("New Title"),
+            vec!["remote".to_string(), "APPLY".to_string()],
+            "2025-10-21T10:00:00Z",
+        );
+
+        assert_eq!(refreshed.title, Some("New Title".to_string()));
+        assert_eq!(refreshed.labels, vec!["apply", "remote"]);
+        assert_eq!(refreshed.saved_at, "2025-10-20T10:00:00Z");
+        assert_eq!(refreshed.updated_at, "2025-10-21T10:00:00Z");
+    }
+
+    #[test]
+    fn test_has_label() {
+        let favorite =
+            Favorite::from_details(&details("Job"), vec!["Remote".to_string()], "2025-10-20");
+        assert!(favorite.has_label("remote"));
+        assert!(favorite.has_label(" REMOTE "));
+        assert!(!favorite.has_label("apply"));
+    }
+
+    #[test]
+    fn test_save_favorite_params_reject_unknown_fields() {
+        let result: Result<SaveFavoriteParams, _> = serde_json::from_value(serde_json::json!({
+            "reference_number": "REF",
+            "label": "typo"
+        }));
+        assert!(result.is_err());
+    }
+}
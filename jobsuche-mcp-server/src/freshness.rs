@@ -0,0 +1,89 @@
+//! Publication freshness
+//!
+//! Models asked for "jobs posted today" or "this week" tend to miscompute
+//! `published_since_days` (or pass 0 and get nothing new). `freshness` names
+//! the common windows instead. The API counts whole days, so a search for
+//! today's postings asks for the last day and keeps only jobs published on
+//! the current date.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Accepted values for `freshness`
+pub const FRESHNESS_VALUES: &[&str] = &["today", "3days", "week", "month"];
+
+/// How recently a job must have been published
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Freshness {
+    /// Published today
+    #[serde(rename = "today")]
+    Today,
+    /// Published within the last 3 days
+    #[serde(rename = "3days")]
+    ThreeDays,
+    /// Published within the last 7 days
+    #[serde(rename = "week")]
+    Week,
+    /// Published within the last 30 days
+    #[serde(rename = "month")]
+    Month,
+}
+
+impl Freshness {
+    /// Value of `published_since_days` sent to the API
+    pub fn published_since_days(self) -> u64 {
+        match self {
+            Self::Today => 1,
+            Self::ThreeDays => 3,
+            Self::Week => 7,
+            Self::Month => 30,
+        }
+    }
+
+    /// Whether a job published the given number of days ago is kept
+    ///
+    /// Only "today" filters beyond the API; jobs without a publication date
+    /// are dropped then.
+    pub fn keeps(self, published_days_ago: Option<i64>) -> bool {
+        match self {
+            Self::Today => published_days_ago == Some(0),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let freshness: Vec<Freshness> =
+            serde_json::from_value(serde_json::json!(FRESHNESS_VALUES)).unwrap();
+        assert_eq!(
+            freshness,
+            vec![
+                Freshness::Today,
+                Freshness::ThreeDays,
+                Freshness::Week,
+                Freshness::Month
+            ]
+        );
+        assert!(serde_json::from_value::<Freshness>(serde_json::json!("year")).is_err());
+    }
+
+    #[test]
+    fn test_published_since_days() {
+        assert_eq!(Freshness::Today.published_since_days(), 1);
+        assert_eq!(Freshness::Week.published_since_days(), 7);
+    }
+
+    #[test]
+    fn test_keeps() {
+        assert!(Freshness::Today.keeps(Some(0)));
+        assert!(!Freshness::Today.keeps(Some(1)));
+        assert!(!Freshness::Today.keeps(None));
+        assert!(Freshness::Week.keeps(Some(5)));
+        assert!(Freshness::Month.keeps(None));
+    }
+}
@@ -0,0 +1,538 @@
+//! Geocoding of job locations
+//!
+//! Commute times, distance sorting and map exports need coordinates, but many
+//! postings only name a street, postal code and town. [`Geocoder`] resolves
+//! such places with a Nominatim-compatible service and keeps every answer in
+//! the store, so a place is looked up once. Places do not move: cached
+//! coordinates, and places the service could not find, never expire.
+//!
+//! Cache keys are keyed hashes of the place, since the store encrypts values
+//! but not keys, and places include the configured home address.
+//!
+//! The public Nominatim instance allows one request per second and asks for an
+//! identifying User-Agent, so lookups are spaced a second apart. Point
+//! `JOBSUCHE_GEOCODING_URL` at your own instance for heavy use.
+
+use crate::country::{is_home_country, Listing};
+use crate::storage::{now_rfc3339, Storage, GEOCODE_CACHE};
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Default base URL of the public Nominatim instance
+pub const DEFAULT_GEOCODING_URL: &str = "https://nominatim.openstreetmap.org";
+
+/// Minimum time between two lookups (Nominatim usage policy)
+const MIN_LOOKUP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Mean radius of the earth in km
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Latitude and longitude in degrees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Coordinates {
+    /// Latitude
+    pub lat: f64,
+
+    /// Longitude
+    pub lon: f64,
+}
+
+impl Coordinates {
+    /// Great-circle distance in km
+    pub fn distance_km(&self, other: &Self) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.lon - self.lon).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+/// Coordinates of a work location of the Jobsuche API (`koordinaten`), if
+/// it has them
+pub fn coordinates_in(location: &Value) -> Option<Coordinates> {
+    let coordinates = location.get("koordinaten")?;
+    Some(Coordinates {
+        lat: coordinates.get("lat")?.as_f64()?,
+        lon: coordinates.get("lon")?.as_f64()?,
+    })
+}
+
+/// A place to resolve, as structured as it is known
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlaceQuery {
+    /// Street and house number
+    pub street: Option<String>,
+
+    /// Postal code
+    pub postal_code: Option<String>,
+
+    /// Town
+    pub town: Option<String>,
+
+    /// Country (`None` searches Germany)
+    pub country: Option<String>,
+}
+
+impl PlaceQuery {
+    /// Parse "Street 1, 42103 Wuppertal", "42103 Wuppertal", "42103" or "Wuppertal"
+    pub fn parse(text: &str) -> Self {
+        let (street, place) = match text.rsplit_once(',') {
+            Some((street, place)) => (non_empty(street), place.trim()),
+            None => (None, text.trim()),
+        };
+        let (postal_code, town) = match place.split_once(char::is_whitespace) {
+            Some((code, town)) if is_postal_code(code) => (Some(code.to_string()), non_empty(town)),
+            _ if is_postal_code(place) => (Some(place.to_string()), None),
+            _ => (None, non_empty(place)),
+        };
+        Self {
+            street,
+            postal_code,
+            town,
+            country: None,
+        }
+    }
+
+    /// First work location of a job from its raw API data
+    pub fn of_job(raw_data: &Value) -> Option<Self> {
+        let address = raw_data.pointer("/arbeitsorte/0/adresse")?;
+        let part = |name: &str| {
+            address
+                .get(name)
+                .and_then(Value::as_str)
+                .and_then(non_empty)
+        };
+        let query = Self {
+            street: part("strasse"),
+            postal_code: part("plz"),
+            town: part("ort"),
+            country: part("land").filter(|country| !is_home_country(country)),
+        };
+        (!query.is_empty()).then_some(query)
+    }
+
+    /// Location of a search result as listed, "Wuppertal (42103)" or
+    /// "Basel (4051), Schweiz"
+    pub fn of_listing(location: &str) -> Option<Self> {
+        let listing = Listing::parse(location);
+        let query = Self {
+            street: None,
+            postal_code: listing.postal_code.and_then(non_empty),
+            town: non_empty(listing.town),
+            country: listing.country.and_then(non_empty),
+        };
+        (!query.is_empty()).then_some(query)
+    }
+
+    /// Whether neither postal code nor town is known
+    pub fn is_empty(&self) -> bool {
+        self.postal_code.is_none() && self.town.is_none()
+    }
+
+    /// Key of the cached answer: HMAC-SHA256 of the lowercase parts,
+    /// separated by "|", with `secret` (empty without a store key)
+    pub fn cache_key(&self, secret: &[u8]) -> String {
+        let place = [&self.street, &self.postal_code, &self.town, &self.country]
+            .iter()
+            .map(|part| part.as_deref().unwrap_or_default().to_lowercase())
+            .collect::<Vec<_>>()
+            .join("|");
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+        mac.update(place.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    /// Postal code and town, for log messages
+    pub fn locality(&self) -> String {
+        [&self.postal_code, &self.town]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Query parameters of a structured Nominatim search
+    fn parameters(&self) -> Vec<(&'static str, String)> {
+        let mut parameters = vec![("format", "jsonv2".to_string()), ("limit", "1".to_string())];
+        let parts = [
+            ("street", &self.street),
+            ("postalcode", &self.postal_code),
+            ("city", &self.town),
+            ("country", &self.country),
+        ];
+        for (name, value) in parts {
+            if let Some(value) = value {
+                parameters.push((name, value.clone()));
+            }
+        }
+        if self.country.is_none() {
+            parameters.push(("countrycodes", "de".to_string()));
+        }
+        parameters
+    }
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn is_postal_code(text: &str) -> bool {
+    text.len() == 5 && text.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Cached answer of the geocoding service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedPlace {
+    /// Coordinates (`None` if the service did not find the place)
+    pub coordinates: Option<Coordinates>,
+
+    /// Name of the place as the service resolved it
+    #[serde(default)]
+    pub display_name: Option<String>,
+
+    /// When the place was looked up (RFC 3339)
+    pub resolved_at: String,
+}
+
+/// A resolved place
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct GeocodedPlace {
+    /// Coordinates (`None` if the place was not found)
+    pub coordinates: Option<Coordinates>,
+
+    /// Name of the place as the service resolved it
+    pub display_name: Option<String>,
+
+    /// Whether the answer came from the local cache
+    pub cached: bool,
+}
+
+/// Parameters for geocode_location
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GeocodeLocationParams {
+    /// Place as "Street 1, 42103 Wuppertal", "42103 Wuppertal", a postal code
+    /// or a town
+    pub query: String,
+}
+
+/// Result from geocode_location
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GeocodeLocationResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Query as resolved
+    pub query: String,
+
+    /// Whether the place was found
+    pub found: bool,
+
+    /// The resolved place
+    #[serde(flatten)]
+    pub place: GeocodedPlace,
+}
+
+/// Coordinates and name of the first place of a Nominatim response
+fn parse_response(body: &Value) -> Result<(Option<Coordinates>, Option<String>)> {
+    let places = body
+        .as_array()
+        .context("Geocoding response is not a list of places")?;
+    let Some(place) = places.first() else {
+        return Ok((None, None));
+    };
+    // Nominatim returns coordinates as strings
+    let coordinate = |name: &str| {
+        let value = place.get(name)?;
+        value
+            .as_f64()
+            .or_else(|| value.as_str().and_then(|text| text.parse().ok()))
+    };
+    let coordinates = match (coordinate("lat"), coordinate("lon")) {
+        (Some(lat), Some(lon)) => Some(Coordinates { lat, lon }),
+        _ => None,
+    };
+    let display_name = place
+        .get("display_name")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    Ok((coordinates, display_name))
+}
+
+/// Drop cache entries stored under the place itself by earlier versions
+pub fn remove_plain_cache_keys(storage: &Storage) -> Result<()> {
+    for key in storage.keys(GEOCODE_CACHE) {
+        if key.contains('|') {
+            storage.remove(GEOCODE_CACHE, &key)?;
+        }
+    }
+    Ok(())
+}
+
+/// Resolves places to coordinates, cached in the store
+#[derive(Debug)]
+pub struct Geocoder {
+    client: reqwest::Client,
+    base_url: String,
+    cache_secret: Vec<u8>,
+    last_lookup: Mutex<Option<Instant>>,
+}
+
+impl Geocoder {
+    /// Create a geocoder for the given base URL
+    ///
+    /// `cache_secret` keys the hashes of cached places; see
+    /// [`PlaceQuery::cache_key`].
+    pub fn new(base_url: &str, cache_secret: Option<[u8; 32]>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .user_agent(concat!("jobsuche-mcp-server/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Failed to create geocoding client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            cache_secret: cache_secret.map(Vec::from).unwrap_or_default(),
+            last_lookup: Mutex::new(None),
+        })
+    }
+
+    /// Coordinates of a place, from the cache or the geocoding service
+    pub async fn geocode(&self, storage: &Storage, query: &PlaceQuery) -> Result<GeocodedPlace> {
+        if query.is_empty() {
+            anyhow::bail!("A place needs a postal code or town to be geocoded");
+        }
+        let key = query.cache_key(&self.cache_secret);
+        if let Some(cached) = storage.get::<CachedPlace>(GEOCODE_CACHE, &key)? {
+            return Ok(GeocodedPlace {
+                coordinates: cached.coordinates,
+                display_name: cached.display_name,
+                cached: true,
+            });
+        }
+
+        let (coordinates, display_name) = self.lookup(query).await?;
+        let cached = CachedPlace {
+            coordinates,
+            display_name: display_name.clone(),
+            resolved_at: now_rfc3339(),
+        };
+        storage.put(GEOCODE_CACHE, &key, &cached)?;
+        Ok(GeocodedPlace {
+            coordinates,
+            display_name,
+            cached: false,
+        })
+    }
+
+    async fn lookup(&self, query: &PlaceQuery) -> Result<(Option<Coordinates>, Option<String>)> {
+        // Lookups are serialized to keep to the rate limit of the service
+        let mut last_lookup = self.last_lookup.lock().await;
+        if let Some(wait) =
+            last_lookup.and_then(|last| MIN_LOOKUP_INTERVAL.checked_sub(last.elapsed()))
+        {
+            tokio::time::sleep(wait).await;
+        }
+        *last_lookup = Some(Instant::now());
+
+        let url = format!("{}/search", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .query(&query.parameters())
+            .send()
+            .await
+            .context("Failed to reach the geocoding service")?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "The geocoding service responded with HTTP {}",
+                response.status()
+            );
+        }
+        let body: Value = response
+            .json()
+            .await
+            .context("Failed to parse the geocoding response")?;
+        parse_response(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_distance_km() {
+        let wuppertal = Coordinates {
+            lat: 51.2562,
+            lon: 7.1508,
+        };
+        let cologne = Coordinates {
+            lat: 50.9375,
+            lon: 6.9603,
+        };
+        let distance = wuppertal.distance_km(&cologne);
+        assert!((distance - 38.0).abs() < 1.0, "{}", distance);
+        assert_eq!(wuppertal.distance_km(&wuppertal), 0.0);
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(
+            PlaceQuery::parse("Neumarkt 10, 42103 Wuppertal"),
+            PlaceQuery {
+                street: Some("Neumarkt 10".to_string()),
+                postal_code: Some("42103".to_string()),
+                town: Some("Wuppertal".to_string()),
+                country: None,
+            }
+        );
+        assert_eq!(
+            PlaceQuery::parse(" 42103 ").postal_code.as_deref(),
+            Some("42103")
+        );
+        let query = PlaceQuery::parse("Bad Homburg");
+        assert_eq!(query.town.as_deref(), Some("Bad Homburg"));
+        assert_eq!(query.postal_code, None);
+        assert!(PlaceQuery::parse(" ").is_empty());
+    }
+
+    #[test]
+    fn test_query_of_listing() {
+        let query = PlaceQuery::of_listing("Wuppertal (42103)").unwrap();
+        assert_eq!(query.town.as_deref(), Some("Wuppertal"));
+        assert_eq!(query.postal_code.as_deref(), Some("42103"));
+        let query = PlaceQuery::of_listing("Frankfurt am Main").unwrap();
+        assert_eq!(query.town.as_deref(), Some("Frankfurt am Main"));
+        assert_eq!(query.postal_code, None);
+        let query = PlaceQuery::of_listing("Basel (4051), Schweiz").unwrap();
+        assert_eq!(query.town.as_deref(), Some("Basel"));
+        assert_eq!(query.country.as_deref(), Some("Schweiz"));
+        assert_eq!(PlaceQuery::of_listing(""), None);
+    }
+
+    #[test]
+    fn test_coordinates_in() {
+        let location = json!({"ort": "Wuppertal", "koordinaten": {"lat": 51.25, "lon": 7.15}});
+        assert_eq!(
+            coordinates_in(&location),
+            Some(Coordinates {
+                lat: 51.25,
+                lon: 7.15
+            })
+        );
+        assert_eq!(coordinates_in(&json!({"ort": "Wuppertal"})), None);
+    }
+
+    #[test]
+    fn test_query_of_job() {
+        let raw = json!({"arbeitsorte": [{"adresse": {
+            "plz": "42103", "ort": "Wuppertal", "land": "Deutschland", "strasse": ""
+        }}]});
+        let query = PlaceQuery::of_job(&raw).unwrap();
+        assert_eq!(query.locality(), "42103 Wuppertal");
+        assert!(query
+            .parameters()
+            .contains(&("countrycodes", "de".to_string())));
+
+        let raw = json!({"arbeitsorte": [{"adresse": {"ort": "Wien", "land": "Österreich"}}]});
+        let query = PlaceQuery::of_job(&raw).unwrap();
+        assert_eq!(query.country.as_deref(), Some("Österreich"));
+        assert!(!query
+            .parameters()
+            .iter()
+            .any(|(name, _)| *name == "countrycodes"));
+
+        assert_eq!(PlaceQuery::of_job(&json!({"arbeitsorte": []})), None);
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let body = json!([{
+            "lat": "51.2562128",
+            "lon": "7.1507636",
+            "display_name": "Wuppertal, Nordrhein-Westfalen, Deutschland"
+        }]);
+        let (coordinates, name) = parse_response(&body).unwrap();
+        assert_eq!(
+            coordinates,
+            Some(Coordinates {
+                lat: 51.2562128,
+                lon: 7.1507636
+            })
+        );
+        assert_eq!(
+            name.as_deref(),
+            Some("Wuppertal, Nordrhein-Westfalen, Deutschland")
+        );
+        assert_eq!(parse_response(&json!([])).unwrap(), (None, None));
+        assert!(parse_response(&json!({"error": "Bad request"})).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_geocode_uses_cache() {
+        let storage = Storage::in_memory();
+        let query = PlaceQuery::parse("42103 Wuppertal");
+        let cached = CachedPlace {
+            coordinates: Some(Coordinates {
+                lat: 51.2562,
+                lon: 7.1508,
+            }),
+            display_name: Some("Wuppertal".to_string()),
+            resolved_at: now_rfc3339(),
+        };
+        let secret = [7u8; 32];
+        storage
+            .put(GEOCODE_CACHE, &query.cache_key(&secret), &cached)
+            .unwrap();
+
+        // The unreachable service is never asked
+        let geocoder = Geocoder::new("http://127.0.0.1:9", Some(secret)).unwrap();
+        let place = geocoder.geocode(&storage, &query).await.unwrap();
+        assert!(place.cached);
+        assert_eq!(place.coordinates, cached.coordinates);
+        assert!(geocoder
+            .geocode(&storage, &PlaceQuery::default())
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_cache_key_hides_the_place() {
+        let query = PlaceQuery::parse("Neumarkt 10, 42103 Wuppertal");
+        let key = query.cache_key(&[]);
+        assert_eq!(key.len(), 64);
+        assert!(!key.contains("neumarkt"));
+        assert_eq!(
+            key,
+            PlaceQuery::parse("neumarkt 10, 42103 WUPPERTAL").cache_key(&[])
+        );
+        assert_ne!(key, query.cache_key(&[1u8; 32]));
+
+        let storage = Storage::in_memory();
+        let cached = CachedPlace {
+            coordinates: None,
+            display_name: None,
+            resolved_at: now_rfc3339(),
+        };
+        storage
+            .put(GEOCODE_CACHE, "neumarkt 10|42103|wuppertal|", &cached)
+            .unwrap();
+        storage.put(GEOCODE_CACHE, &key, &cached).unwrap();
+        remove_plain_cache_keys(&storage).unwrap();
+        assert_eq!(storage.keys(GEOCODE_CACHE), [key].into());
+    }
+}
@@ -0,0 +1,213 @@
+//! GeoJSON export of search results
+//!
+//! Turns a page of job summaries into a GeoJSON FeatureCollection (RFC 7946)
+//! with one point per job, so hosts and GIS tools can put the job market on
+//! a map. Listings without coordinates keep their feature with a `null`
+//! geometry, as the spec allows, so nothing is silently dropped.
+
+use crate::geocoding::Coordinates;
+use crate::{JobSummary, SearchJobsParams};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Parameters for search_jobs_geojson
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SearchJobsGeoJsonParams {
+    /// Search to export (same parameters as search_jobs)
+    pub search: SearchJobsParams,
+}
+
+/// GeoJSON type tag of a feature collection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum FeatureCollectionType {
+    #[default]
+    FeatureCollection,
+}
+
+/// GeoJSON type tag of a feature
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum FeatureType {
+    #[default]
+    Feature,
+}
+
+/// GeoJSON type tag of a point
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum PointType {
+    #[default]
+    Point,
+}
+
+/// GeoJSON point geometry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Point {
+    /// Always "Point"
+    #[serde(rename = "type")]
+    pub kind: PointType,
+
+    /// Longitude and latitude, in that order
+    pub coordinates: [f64; 2],
+}
+
+impl From<Coordinates> for Point {
+    fn from(coordinates: Coordinates) -> Self {
+        Self {
+            kind: PointType::Point,
+            coordinates: [coordinates.lon, coordinates.lat],
+        }
+    }
+}
+
+/// Properties of a job feature
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JobProperties {
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Job title
+    pub title: String,
+
+    /// Employer name
+    pub employer: String,
+
+    /// Location as listed
+    pub location: String,
+
+    /// Link to the posting on the Arbeitsagentur website
+    pub url: String,
+
+    /// External application URL, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_url: Option<String>,
+
+    /// Publication date (YYYY-MM-DD)
+    pub published_date: Option<String>,
+
+    /// Distance in km from the search location (only when searching with a location)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+}
+
+/// One job as a GeoJSON feature
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JobFeature {
+    /// Always "Feature"
+    #[serde(rename = "type")]
+    pub kind: FeatureType,
+
+    /// Job reference number
+    pub id: String,
+
+    /// Work location (`null` if it could not be located)
+    pub geometry: Option<Point>,
+
+    /// Job details for popups and tables
+    pub properties: JobProperties,
+}
+
+impl From<&JobSummary> for JobFeature {
+    fn from(job: &JobSummary) -> Self {
+        Self {
+            kind: FeatureType::Feature,
+            id: job.reference_number.clone(),
+            geometry: job.coordinates.map(Point::from),
+            properties: JobProperties {
+                reference_number: job.reference_number.clone(),
+                title: job.title.clone(),
+                employer: job.employer.clone(),
+                location: job.location.clone(),
+                url: job.web_url.clone(),
+                external_url: job.external_url.clone(),
+                published_date: job.published_date.clone(),
+                distance_km: job.distance_km,
+            },
+        }
+    }
+}
+
+/// Result from search_jobs_geojson, a GeoJSON FeatureCollection
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchJobsGeoJsonResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Always "FeatureCollection"
+    #[serde(rename = "type")]
+    pub kind: FeatureCollectionType,
+
+    /// One feature per job
+    pub features: Vec<JobFeature>,
+
+    /// Total number of results of the search
+    pub total_results: Option<u64>,
+
+    /// Number of features without geometry
+    pub unlocated_jobs: usize,
+}
+
+impl SearchJobsGeoJsonResult {
+    /// Feature collection of the given jobs
+    pub fn new(jobs: &[JobSummary], total_results: Option<u64>) -> Self {
+        let features: Vec<JobFeature> = jobs.iter().map(JobFeature::from).collect();
+        Self {
+            request_id: String::new(),
+            kind: FeatureCollectionType::FeatureCollection,
+            unlocated_jobs: features
+                .iter()
+                .filter(|feature| feature.geometry.is_none())
+                .count(),
+            features,
+            total_results,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn job(reference_number: &str, coordinates: Option<Coordinates>) -> JobSummary {
+        serde_json::from_value(json!({
+            "reference_number": reference_number,
+            "title": "Rust Entwickler",
+            "employer": "BARMER",
+            "location": "Wuppertal (42103)",
+            "coordinates": coordinates,
+            "published_date": "2026-10-01",
+            "external_url": null,
+            "web_url": format!("https://www.arbeitsagentur.de/jobsuche/jobdetail/{}", reference_number)
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_feature_collection() {
+        let wuppertal = Coordinates {
+            lat: 51.25,
+            lon: 7.15,
+        };
+        let result = SearchJobsGeoJsonResult::new(
+            &[job("REF-1", Some(wuppertal)), job("REF-2", None)],
+            Some(2),
+        );
+        assert_eq!(result.unlocated_jobs, 1);
+
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["type"], "FeatureCollection");
+        assert_eq!(value["features"][0]["type"], "Feature");
+        assert_eq!(value["features"][0]["id"], "REF-1");
+        assert_eq!(
+            value["features"][0]["geometry"],
+            json!({"type": "Point", "coordinates": [7.15, 51.25]})
+        );
+        assert_eq!(value["features"][0]["properties"]["employer"], "BARMER");
+        assert_eq!(
+            value["features"][0]["properties"]["url"],
+            "https://www.arbeitsagentur.de/jobsuche/jobdetail/REF-1"
+        );
+        assert!(value["features"][1]["geometry"].is_null());
+    }
+}
@@ -0,0 +1,264 @@
+//! Employer hiring history
+//!
+//! The API only knows the postings online right now. Whenever the server
+//! learns the posting count of an employer (get_employer_jobs,
+//! monitor_employers), the count is recorded for the day, so the history
+//! grows with every lookup. employer_hiring_history returns it as a daily or
+//! weekly time series, which shows hiring freezes and surges that a single
+//! count cannot.
+
+use crate::dates::parse_date;
+use crate::trends::{direction, TrendDirection};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use time::{Date, Duration};
+
+/// Default number of days returned by employer_hiring_history
+pub const DEFAULT_HISTORY_DAYS: u64 = 90;
+
+/// Number of days counts are kept (and the maximum returned)
+pub const MAX_HISTORY_DAYS: u64 = 730;
+
+/// Recorded posting counts of one employer (and location)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmployerHistory {
+    /// Employer name as last given
+    pub employer: String,
+
+    /// Location the counts are restricted to
+    pub location: Option<String>,
+
+    /// Day (YYYY-MM-DD) -> last posting count observed that day
+    pub counts: BTreeMap<String, u64>,
+}
+
+impl EmployerHistory {
+    /// Record the posting count of a day, dropping days beyond [`MAX_HISTORY_DAYS`]
+    pub fn record(&mut self, employer: &str, location: Option<&str>, day: Date, total_jobs: u64) {
+        self.employer = employer.trim().to_string();
+        self.location = location
+            .map(str::trim)
+            .filter(|location| !location.is_empty())
+            .map(str::to_string);
+        self.counts.insert(day.to_string(), total_jobs);
+
+        let oldest = (day - Duration::days(MAX_HISTORY_DAYS as i64)).to_string();
+        // ISO dates sort chronologically
+        self.counts.retain(|recorded, _| *recorded >= oldest);
+    }
+}
+
+/// Granularity of the time series
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryInterval {
+    /// One point per day with a recorded count
+    #[default]
+    Daily,
+    /// One point per calendar week (starting Monday), the last count of the week
+    Weekly,
+}
+
+/// Posting count of one period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct HiringHistoryPoint {
+    /// First day of the period (YYYY-MM-DD)
+    pub period_start: String,
+
+    /// Last posting count observed in the period
+    pub total_jobs: u64,
+
+    /// Difference to the previous point
+    pub change: Option<i64>,
+}
+
+/// First day of the period a day belongs to
+fn period_start(day: Date, interval: HistoryInterval) -> Date {
+    match interval {
+        HistoryInterval::Daily => day,
+        HistoryInterval::Weekly => {
+            day - Duration::days(day.weekday().number_days_from_monday() as i64)
+        }
+    }
+}
+
+/// Time series of the counts recorded since `since`, oldest first
+pub fn hiring_series(
+    counts: &BTreeMap<String, u64>,
+    interval: HistoryInterval,
+    since: Date,
+) -> Vec<HiringHistoryPoint> {
+    // Counts are ordered by day, so the last count of a period wins
+    let mut periods: BTreeMap<Date, u64> = BTreeMap::new();
+    for (day, total_jobs) in counts {
+        let Some(day) = parse_date(day).filter(|day| *day >= since) else {
+            continue;
+        };
+        periods.insert(period_start(day, interval), *total_jobs);
+    }
+
+    let mut previous: Option<u64> = None;
+    periods
+        .into_iter()
+        .map(|(start, total_jobs)| {
+            let change = previous.map(|previous| total_jobs as i64 - previous as i64);
+            previous = Some(total_jobs);
+            HiringHistoryPoint {
+                period_start: start.to_string(),
+                total_jobs,
+                change,
+            }
+        })
+        .collect()
+}
+
+/// Direction of the posting count from the first to the last point
+///
+/// `None` with fewer than two points.
+pub fn hiring_trend(series: &[HiringHistoryPoint]) -> Option<TrendDirection> {
+    let (first, last) = match series {
+        [first, .., last] => (first.total_jobs, last.total_jobs),
+        _ => return None,
+    };
+    if first == 0 {
+        return Some(if last > 0 {
+            TrendDirection::Rising
+        } else {
+            TrendDirection::Stable
+        });
+    }
+    Some(direction(last as f64 / first as f64))
+}
+
+/// Parameters for employer_hiring_history
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EmployerHiringHistoryParams {
+    /// Employer name as used with get_employer_jobs or monitor_employers
+    pub employer: String,
+
+    /// Location as used with get_employer_jobs or monitor_employers
+    pub location: Option<String>,
+
+    /// "daily" (default) or "weekly"
+    pub interval: Option<HistoryInterval>,
+
+    /// Number of days to look back (default: 90, max: 730)
+    pub days: Option<u64>,
+}
+
+/// Result from employer_hiring_history
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EmployerHiringHistoryResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Employer that was requested
+    pub employer: String,
+
+    /// Location that was requested
+    pub location: Option<String>,
+
+    /// Granularity of the series
+    pub interval: HistoryInterval,
+
+    /// Number of days looked back
+    pub days: u64,
+
+    /// Number of days with a recorded count in the period
+    pub observations: usize,
+
+    /// Posting counts, oldest first
+    pub series: Vec<HiringHistoryPoint>,
+
+    /// Direction of the posting count over the period ("rising" hints at a
+    /// hiring surge, "falling" at a freeze); `None` with fewer than two points
+    pub trend: Option<TrendDirection>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    fn counts(entries: &[(&str, u64)]) -> BTreeMap<String, u64> {
+        entries
+            .iter()
+            .map(|(day, count)| (day.to_string(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn test_record_prunes_old_days() {
+        let mut history = EmployerHistory::default();
+        history.record(" BARMER ", Some(" "), date!(2023 - 01 - 01), 10);
+        history.record("BARMER", None, date!(2025 - 01 - 01), 12);
+        history.record("BARMER", None, date!(2025 - 01 - 01), 14);
+
+        assert_eq!(history.employer, "BARMER");
+        assert_eq!(history.location, None);
+        assert_eq!(history.counts, counts(&[("2025-01-01", 14)]));
+    }
+
+    #[test]
+    fn test_daily_series() {
+        let counts = counts(&[("2025-10-01", 10), ("2025-10-02", 12), ("2025-10-05", 9)]);
+        let series = hiring_series(&counts, HistoryInterval::Daily, date!(2025 - 10 - 02));
+        assert_eq!(
+            series,
+            vec![
+                HiringHistoryPoint {
+                    period_start: "2025-10-02".to_string(),
+                    total_jobs: 12,
+                    change: None,
+                },
+                HiringHistoryPoint {
+                    period_start: "2025-10-05".to_string(),
+                    total_jobs: 9,
+                    change: Some(-3),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weekly_series_keeps_last_count_of_week() {
+        // 2025-10-06 is a Monday
+        let counts = counts(&[("2025-10-06", 10), ("2025-10-09", 12), ("2025-10-13", 20)]);
+        let series = hiring_series(&counts, HistoryInterval::Weekly, date!(2025 - 01 - 01));
+        let points: Vec<(&str, u64)> = series
+            .iter()
+            .map(|point| (point.period_start.as_str(), point.total_jobs))
+            .collect();
+        assert_eq!(points, vec![("2025-10-06", 12), ("2025-10-13", 20)]);
+        assert_eq!(series[1].change, Some(8));
+    }
+
+    #[test]
+    fn test_hiring_trend() {
+        let point = |total_jobs| HiringHistoryPoint {
+            period_start: String::new(),
+            total_jobs,
+            change: None,
+        };
+        assert_eq!(hiring_trend(&[point(10)]), None);
+        assert_eq!(
+            hiring_trend(&[point(10), point(20)]),
+            Some(TrendDirection::Rising)
+        );
+        assert_eq!(
+            hiring_trend(&[point(10), point(5), point(4)]),
+            Some(TrendDirection::Falling)
+        );
+        assert_eq!(
+            hiring_trend(&[point(10), point(11)]),
+            Some(TrendDirection::Stable)
+        );
+        assert_eq!(
+            hiring_trend(&[point(0), point(0)]),
+            Some(TrendDirection::Stable)
+        );
+    }
+}
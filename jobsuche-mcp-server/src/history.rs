@@ -0,0 +1,197 @@
+//! Search history
+//!
+//! Every executed search is appended to a bounded log in the local store, so
+//! users and agents can look up what has already been explored and re-run an
+//! earlier search by its index.
+
+use crate::{SearchJobsParams, SearchJobsResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of entries kept; older entries are dropped
+pub const MAX_HISTORY_ENTRIES: usize = 200;
+
+/// Storage key of the history document in the [`SEARCH_HISTORY`](crate::storage::SEARCH_HISTORY) collection
+pub const HISTORY_KEY: &str = "log";
+
+/// One executed search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HistoryEntry {
+    /// Stable index of the entry (increases with every search)
+    pub index: u64,
+
+    /// When the search was executed (RFC 3339)
+    pub executed_at: String,
+
+    /// Search parameters
+    pub search: SearchJobsParams,
+
+    /// Total number of results reported by the API
+    pub total_results: Option<u64>,
+
+    /// Number of jobs returned
+    pub jobs_count: usize,
+}
+
+/// Persisted history log, oldest entry first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchHistory {
+    /// Index assigned to the next entry
+    pub next_index: u64,
+
+    /// Recorded entries
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl SearchHistory {
+    /// Append a search, dropping the oldest entries beyond `max_entries`
+    pub fn record(
+        &mut self,
+        search: SearchJobsParams,
+        result: &SearchJobsResult,
+        now: &str,
+        max_entries: usize,
+    ) -> u64 {
+        let index = self.next_index;
+        self.next_index += 1;
+        self.entries.push(HistoryEntry {
+            index,
+            executed_at: now.to_string(),
+            search,
+            total_results: result.total_results,
+            jobs_count: result.jobs_count,
+        });
+
+        if self.entries.len() > max_entries {
+            let excess = self.entries.len() - max_entries;
+            self.entries.drain(..excess);
+        }
+        index
+    }
+
+    /// Look up an entry by index
+    pub fn find(&self, index: u64) -> Option<&HistoryEntry> {
+        self.entries.iter().find(|entry| entry.index == index)
+    }
+
+    /// The most recent entries, newest first
+    pub fn latest(&self, limit: usize) -> Vec<HistoryEntry> {
+        self.entries.iter().rev().take(limit).cloned().collect()
+    }
+}
+
+/// Parameters for get_search_history
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetSearchHistoryParams {
+    /// Maximum number of entries to return (default: 20)
+    pub limit: Option<usize>,
+}
+
+/// Result from get_search_history
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetSearchHistoryResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Number of entries returned
+    pub entries_count: usize,
+
+    /// Number of entries stored in total
+    pub total_entries: usize,
+
+    /// History entries, newest first
+    pub entries: Vec<HistoryEntry>,
+}
+
+/// Parameters for rerun_search_history
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RerunSearchHistoryParams {
+    /// Index of the history entry (from get_search_history)
+    pub index: u64,
+
+    /// Page number, overrides the recorded value
+    pub page: Option<u64>,
+}
+
+/// Result from rerun_search_history
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RerunSearchHistoryResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Index of the entry that was re-run
+    pub index: u64,
+
+    /// Search parameters that were used
+    pub search: SearchJobsParams,
+
+    /// Search result
+    pub result: SearchJobsResult,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(title: &str) -> SearchJobsParams {
+        serde_json::from_value(serde_json::json!({ "job_title": title })).unwrap()
+    }
+
+    fn result(total: u64) -> SearchJobsResult {
+        SearchJobsResult {
+            request_id: String::new(),
+            total_results: Some(total),
+            current_page: Some(1),
+            page_size: Some(25),
+            jobs_count: 0,
+            jobs: vec![],
+            hidden_seen_count: 0,
+            duplicates_collapsed: 0,
+            employer_filtered_count: 0,
+            location_excluded_count: 0,
+            search_duration_ms: 10,
+            effective_query: Default::default(),
+            relaxations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_record_assigns_increasing_indexes() {
+        let mut history = SearchHistory::default();
+        assert_eq!(history.record(search("a"), &result(1), "t1", 10), 0);
+        assert_eq!(history.record(search("b"), &result(2), "t2", 10), 1);
+
+        let entry = history.find(1).unwrap();
+        assert_eq!(entry.search.job_title.as_deref(), Some("b"));
+        assert_eq!(entry.total_results, Some(2));
+        assert_eq!(entry.executed_at, "t2");
+    }
+
+    #[test]
+    fn test_record_drops_oldest_entries() {
+        let mut history = SearchHistory::default();
+        for i in 0..5 {
+            history.record(search(&i.to_string()), &result(i), "t", 3);
+        }
+
+        assert_eq!(history.entries.len(), 3);
+        assert!(history.find(1).is_none());
+        assert!(history.find(4).is_some());
+        assert_eq!(history.next_index, 5);
+    }
+
+    #[test]
+    fn test_latest_is_newest_first() {
+        let mut history = SearchHistory::default();
+        for i in 0..4 {
+            history.record(search(&i.to_string()), &result(i), "t", 10);
+        }
+
+        let indexes: Vec<u64> = history.latest(2).iter().map(|entry| entry.index).collect();
+        assert_eq!(indexes, vec![3, 2]);
+    }
+}
@@ -0,0 +1,193 @@
+//! Import of reference numbers
+//!
+//! Users often collect postings on the arbeitsagentur.de website. The import
+//! accepts such a list as free text (one per line, comma/semicolon separated,
+//! or job page URLs) and fetches and stores the details of every job.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of reference numbers per import
+pub const MAX_IMPORT: usize = 100;
+
+/// Where imported jobs are stored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportTarget {
+    /// Job archive only (see get_archived_job)
+    #[default]
+    Archive,
+    /// Favorites (and the archive)
+    Favorites,
+}
+
+/// Reference numbers found in a text, plus tokens that are not reference numbers
+#[derive(Debug, Default, PartialEq)]
+pub struct ParsedReferences {
+    pub reference_numbers: Vec<String>,
+    pub invalid: Vec<String>,
+}
+
+/// Extract reference numbers from free text
+///
+/// Tokens are separated by whitespace, commas, semicolons or quotes. URLs are
+/// reduced to their last path segment, so job page links work as well.
+/// Duplicates are removed, the order is kept.
+pub fn parse_reference_numbers(text: &str) -> ParsedReferences {
+    let mut parsed = ParsedReferences::default();
+
+    let tokens = text
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '"' | '\''))
+        .filter(|token| !token.is_empty());
+
+    for token in tokens {
+        let candidate = if token.contains("://") {
+            token
+                .split(['?', '#'])
+                .next()
+                .unwrap_or(token)
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or(token)
+        } else {
+            token
+        };
+
+        if !is_reference_number(candidate) {
+            // Column headers of CSV lists are not worth reporting
+            if !candidate.eq_ignore_ascii_case("refnr")
+                && !candidate.eq_ignore_ascii_case("reference_number")
+            {
+                parsed.invalid.push(token.to_string());
+            }
+            continue;
+        }
+
+        if !parsed.reference_numbers.iter().any(|r| r == candidate) {
+            parsed.reference_numbers.push(candidate.to_string());
+        }
+    }
+    parsed
+}
+
+/// Whether a token looks like a BA reference number (e.g. "10001-1234567890-S")
+pub fn is_reference_number(token: &str) -> bool {
+    let mut parts = token.split('-');
+    let first = parts.next().unwrap_or_default();
+
+    !first.is_empty()
+        && first.chars().all(|c| c.is_ascii_digit())
+        && token.matches('-').count() >= 2
+        && token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+}
+
+/// Parameters for import_refnrs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ImportRefnrsParams {
+    /// Reference numbers, one per line or comma/semicolon separated; job page URLs
+    /// from arbeitsagentur.de are accepted too (max: 100)
+    pub text: String,
+
+    /// Where to store the jobs: "archive" (default) or "favorites"
+    pub target: Option<ImportTarget>,
+
+    /// Labels for imported favorites (only with target "favorites")
+    pub labels: Option<Vec<String>>,
+}
+
+/// A successfully imported job
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ImportedJob {
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Job title
+    pub title: Option<String>,
+
+    /// Employer name
+    pub employer: Option<String>,
+}
+
+/// A job that could not be imported
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FailedImport {
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Error message
+    pub error: String,
+}
+
+/// Result from import_refnrs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ImportRefnrsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Where the jobs were stored
+    pub target: ImportTarget,
+
+    /// Number of imported jobs
+    pub imported_count: usize,
+
+    /// Imported jobs
+    pub imported: Vec<ImportedJob>,
+
+    /// Jobs whose details could not be fetched
+    pub failed: Vec<FailedImport>,
+
+    /// Tokens that are not reference numbers
+    pub invalid: Vec<String>,
+
+    /// Reference numbers beyond the limit of 100, not imported
+    pub skipped: Vec<String>,
+
+    /// True if the client cancelled the request; `imported` then holds the
+    /// jobs imported before cancellation
+    pub cancelled: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines_csv_and_urls() {
+        let text = "refnr\n10001-1234567890-S\n\
+                    12265-426335_JB3787416-S, 10001-1234567890-S;\n\
+                    https://www.arbeitsagentur.de/jobsuche/jobdetail/14208-20250923100536-S?pos=3\n\
+                    Notiz 42";
+        let parsed = parse_reference_numbers(text);
+
+        assert_eq!(
+            parsed.reference_numbers,
+            vec![
+                "10001-1234567890-S",
+                "12265-426335_JB3787416-S",
+                "14208-20250923100536-S"
+            ]
+        );
+        assert_eq!(parsed.invalid, vec!["Notiz", "42"]);
+    }
+
+    #[test]
+    fn test_is_reference_number() {
+        assert!(is_reference_number("10001-1234567890-S"));
+        assert!(!is_reference_number("ABC-123-S"));
+        assert!(!is_reference_number("10001-1234567890"));
+        assert!(!is_reference_number("10001-12/34-S"));
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert_eq!(
+            parse_reference_numbers("  \n "),
+            ParsedReferences::default()
+        );
+    }
+}
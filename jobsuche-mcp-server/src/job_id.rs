@@ -0,0 +1,84 @@
+//! Job identifiers
+//!
+//! The API identifies jobs by their reference number (refnr, e.g.
+//! "10001-1234567890-S"). Links on arbeitsagentur.de often carry the hashId
+//! instead, which is the Base64 encoding of the refnr. Both are accepted where
+//! a job is looked up, and a hashId is converted to the refnr the API expects.
+
+use crate::import::is_reference_number;
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Form in which a job was identified
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentifierType {
+    /// Reference number as returned by search_jobs
+    Refnr,
+    /// Base64 hashId from a link on arbeitsagentur.de
+    HashId,
+}
+
+/// Decode a hashId to the reference number it encodes
+fn decode_hash_id(id: &str) -> Option<String> {
+    // Padding is often percent-encoded in links
+    let id = id.replace("%3D", "=").replace("%3d", "=");
+    [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD]
+        .iter()
+        .filter_map(|engine| engine.decode(&id).ok())
+        .filter_map(|bytes| String::from_utf8(bytes).ok())
+        .map(|decoded| decoded.trim().to_string())
+        .find(|decoded| is_reference_number(decoded))
+}
+
+/// Reference number for a refnr or hashId
+///
+/// Identifiers that are neither a reference number nor a hashId of one are
+/// passed on unchanged, so the API decides whether the job exists.
+pub fn resolve_job_id(id: &str) -> (String, IdentifierType) {
+    let id = id.trim();
+    if is_reference_number(id) {
+        return (id.to_string(), IdentifierType::Refnr);
+    }
+    match decode_hash_id(id) {
+        Some(reference_number) => (reference_number, IdentifierType::HashId),
+        None => (id.to_string(), IdentifierType::Refnr),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_job_id() {
+        let refnr = "10001-1234567890-S";
+        let hash_id = STANDARD.encode(refnr);
+        assert_eq!(
+            resolve_job_id(refnr),
+            (refnr.to_string(), IdentifierType::Refnr)
+        );
+        assert_eq!(
+            resolve_job_id(&hash_id),
+            (refnr.to_string(), IdentifierType::HashId)
+        );
+        assert_eq!(
+            resolve_job_id(&hash_id.replace('=', "%3D")),
+            (refnr.to_string(), IdentifierType::HashId)
+        );
+        assert_eq!(
+            resolve_job_id(&URL_SAFE_NO_PAD.encode("14208-20250923100536-S")),
+            ("14208-20250923100536-S".to_string(), IdentifierType::HashId)
+        );
+    }
+
+    #[test]
+    fn test_resolve_unknown_id_unchanged() {
+        assert_eq!(
+            resolve_job_id(" TEST-REF-123 "),
+            ("TEST-REF-123".to_string(), IdentifierType::Refnr)
+        );
+    }
+}
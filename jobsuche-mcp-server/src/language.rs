@@ -0,0 +1,199 @@
+//! Language detection for job descriptions
+//!
+//! Most postings are German, but international employers often publish in
+//! English. Detecting the language lets clients show only postings the user
+//! can read. Titles are too short for statistical detection, so they are
+//! judged by the words they use.
+
+use whatlang::{Detector, Lang};
+
+/// Languages postings on the job board are written in
+const CANDIDATES: &[Lang] = &[
+    Lang::Deu,
+    Lang::Eng,
+    Lang::Fra,
+    Lang::Spa,
+    Lang::Ita,
+    Lang::Nld,
+    Lang::Pol,
+    Lang::Por,
+    Lang::Ces,
+    Lang::Tur,
+    Lang::Rus,
+    Lang::Ukr,
+];
+
+/// Texts shorter than this are too short to classify
+const MIN_TEXT_CHARS: usize = 40;
+
+/// Phrases in a title that address English speakers, lowercase
+const ENGLISH_MARKERS: &[&str] = &["english", "englischsprachig", "no german", "without german"];
+
+/// Words that only occur in German titles, lowercase
+const GERMAN_TITLE_WORDS: &[&str] = &[
+    "und",
+    "mit",
+    "für",
+    "der",
+    "die",
+    "das",
+    "im",
+    "zum",
+    "zur",
+    "bei",
+    "oder",
+    "als",
+    "mitarbeiter",
+    "mitarbeiterin",
+    "fachkraft",
+    "kaufmann",
+    "kauffrau",
+    "sachbearbeiter",
+    "sachbearbeiterin",
+    "helfer",
+    "helferin",
+    "leiter",
+    "leiterin",
+    "ausbildung",
+    "meister",
+    "pflegefachkraft",
+    "werkstudent",
+    "werkstudentin",
+    "praktikum",
+    "aushilfe",
+];
+
+/// Words typical of English titles, lowercase
+const ENGLISH_TITLE_WORDS: &[&str] = &[
+    "and",
+    "with",
+    "for",
+    "of",
+    "the",
+    "engineer",
+    "developer",
+    "manager",
+    "consultant",
+    "specialist",
+    "analyst",
+    "scientist",
+    "designer",
+    "architect",
+    "lead",
+    "head",
+    "intern",
+    "assistant",
+    "officer",
+    "representative",
+    "associate",
+];
+
+/// ISO 639-1 code of the language a text is written in ("de", "en", ...), or
+/// `None` if the text is too short or mixed to tell
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    if text.chars().filter(|c| c.is_alphabetic()).count() < MIN_TEXT_CHARS {
+        return None;
+    }
+
+    let info = Detector::with_allowlist(CANDIDATES.to_vec()).detect(text)?;
+    if !info.is_reliable() {
+        return None;
+    }
+    Some(match info.lang() {
+        Lang::Deu => "de",
+        Lang::Eng => "en",
+        Lang::Fra => "fr",
+        Lang::Spa => "es",
+        Lang::Ita => "it",
+        Lang::Nld => "nl",
+        Lang::Pol => "pl",
+        Lang::Por => "pt",
+        Lang::Ces => "cs",
+        Lang::Tur => "tr",
+        Lang::Rus => "ru",
+        Lang::Ukr => "uk",
+        _ => return None,
+    })
+}
+
+/// Whether a job title suggests a posting in English
+///
+/// Titles naming English ("English-speaking", "no German required") count,
+/// as do titles detected as English. Otherwise a title counts if it has an
+/// English word and no German one, so "Data Engineer" does while "Data
+/// Engineer für Energiehandel" does not. Postings often have an English
+/// title above a German description, so this is only a first filter.
+pub fn is_english_title(title: &str) -> bool {
+    let lower = title.to_lowercase();
+    if ENGLISH_MARKERS.iter().any(|marker| lower.contains(marker)) {
+        return true;
+    }
+    if let Some(language) = detect_language(title) {
+        return language == "en";
+    }
+
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let german = words.iter().any(|word| {
+        GERMAN_TITLE_WORDS.contains(word) || word.ends_with("innen") || word.contains('ß')
+    });
+    !german && words.iter().any(|word| ENGLISH_TITLE_WORDS.contains(word))
+}
+
+/// Whether a posting is realistically applicable without German
+///
+/// A description detected as another language rules the posting out. A
+/// description too short to tell falls back to the title.
+pub fn is_english_posting(title: &str, description_language: Option<&str>) -> bool {
+    match description_language {
+        Some(language) => language == "en",
+        None => is_english_title(title),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_german() {
+        let text = "Wir suchen zum nächstmöglichen Zeitpunkt eine engagierte Fachkraft für \
+                    unser Team in Berlin. Sie verfügen über eine abgeschlossene Ausbildung \
+                    und arbeiten gerne selbstständig.";
+        assert_eq!(detect_language(text), Some("de"));
+    }
+
+    #[test]
+    fn test_detect_english() {
+        let text = "We are looking for an experienced software engineer to join our growing \
+                    team in Berlin. You will design and build services used by millions of \
+                    customers and work closely with product managers.";
+        assert_eq!(detect_language(text), Some("en"));
+    }
+
+    #[test]
+    fn test_short_text_is_undetermined() {
+        assert_eq!(detect_language("Senior Developer (m/w/d)"), None);
+        assert_eq!(detect_language(""), None);
+    }
+
+    #[test]
+    fn test_is_english_title() {
+        assert!(is_english_title("Senior Data Engineer (m/f/d)"));
+        assert!(is_english_title("Kundenservice Englischsprachig (m/w/d)"));
+        assert!(is_english_title("Sales Manager - English speaking"));
+        assert!(!is_english_title("Data Engineer für Energiehandel (m/w/d)"));
+        assert!(!is_english_title("Verkäufer/in Lebensmittel"));
+        assert!(!is_english_title("Elektroniker (m/w/d)"));
+    }
+
+    #[test]
+    fn test_is_english_posting() {
+        assert!(is_english_posting("Backend Developer", Some("en")));
+        assert!(!is_english_posting("Backend Developer", Some("de")));
+        assert!(is_english_posting("Backend Developer", None));
+        assert!(!is_english_posting("Elektroniker (m/w/d)", None));
+    }
+}
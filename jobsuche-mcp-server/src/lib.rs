@@ -12,26 +12,315 @@
 //! - **Comprehensive Details**: Get full job information including descriptions and requirements
 //! - **Pagination Support**: Handle large result sets efficiently
 
-use jobsuche::{Arbeitszeit, Credentials, JobDetails, JobSearchResponse, JobsucheAsync, SearchOptions};
+use anyhow::Context;
+use jobsuche::{
+    Arbeitszeit, Credentials, JobDetails, JobSearchResponse, JobsucheAsync, SearchOptions,
+};
 use pulseengine_mcp_macros::{mcp_server, mcp_tools};
+use pulseengine_mcp_protocol::{CallToolRequestParam, CallToolResult};
+use pulseengine_mcp_server::McpBackend;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Instant;
-use tracing::{info, instrument};
-
+use time::format_description::well_known::Rfc3339;
+use tracing::{info, instrument, warn, Instrument};
+
+pub mod apprenticeship;
+pub mod archive;
+pub mod areas;
+pub mod arguments;
+pub mod audit;
+pub mod bewerberboerse;
+pub mod build_info;
+pub mod bulk_export;
+pub mod cancellation;
+pub mod checklist;
+pub mod coaching;
+pub mod commute;
+pub mod comparison;
+pub mod completion;
 pub mod config;
-use config::JobsucheConfig;
+pub mod contact;
+pub mod context;
+pub mod country;
+pub mod cv_match;
+pub mod dates;
+pub mod deadlines;
+pub mod description;
+pub mod detail_level;
+pub mod digest;
+pub mod duplicates;
+pub mod elicitation;
+pub mod employer;
+pub mod employer_filter;
+pub mod employer_monitor;
+pub mod entgeltatlas;
+pub mod export;
+pub mod favorites;
+pub mod freshness;
+pub mod geocoding;
+pub mod geojson;
+pub mod hiring_history;
+pub mod history;
+pub mod import;
+pub mod job_id;
+pub mod language;
+pub mod local_search;
+pub mod locale;
+pub mod location_filter;
+pub mod logging;
+pub mod maintenance;
+pub mod market;
+pub mod notes;
+pub mod notifications;
+pub mod occupation_fields;
+pub mod profile;
+pub mod progress;
+pub mod projection;
+pub mod query;
+pub mod quota;
+pub mod ranking;
+pub mod redirects;
+pub mod regional;
+pub mod relaxation;
+pub mod relocation;
+pub mod report;
+pub mod resources;
+pub mod salary;
+pub mod saved_searches;
+pub mod scheduler;
+pub mod seen;
+pub mod similar;
+pub mod skills;
+pub mod sort;
+pub mod stdio;
+pub mod storage;
+pub mod storage_stats;
+pub mod studiensuche;
+pub mod suggestions;
+pub mod tools;
+pub mod trends;
+pub mod validation;
+pub mod watchlists;
+pub mod weiterbildung;
+use apprenticeship::{
+    apprenticeship_search_options, duration_years, school_requirements, year_of, Apprenticeship,
+    SearchApprenticeshipsParams, SearchApprenticeshipsResult, DEFAULT_APPRENTICESHIP_DETAILS,
+    MAX_APPRENTICESHIP_DETAILS,
+};
+use archive::{ArchivedJob, GetArchivedJobParams, GetArchivedJobResult, JobSource};
+use areas::{
+    combine_areas, AreaSummary, SearchJobsInAreasParams, SearchJobsInAreasResult,
+    DEFAULT_AREA_JOBS, MAX_AREA_JOBS, MAX_SEARCH_AREAS,
+};
+use audit::{AuditRecorder, GetAuditLogParams, GetAuditLogResult, ToolCall};
+use bewerberboerse::{
+    BewerberboerseClient, GetCandidateProfileParams, GetCandidateProfileResult,
+    SearchCandidatesParams, SearchCandidatesResult, DEFAULT_BEWERBERBOERSE_API_KEY,
+    RECRUITER_TOOLS_DISABLED,
+};
+use build_info::BuildInfo;
+use bulk_export::{
+    BulkExportParams, BulkExportResult, BulkWriter, DEFAULT_BULK_JOBS, MAX_BULK_JOBS,
+};
+use cancellation::{run_cancellable, CancellationRegistry};
+use checklist::{
+    build_checklist, BuildApplicationChecklistParams, BuildApplicationChecklistResult,
+};
+use coaching::{CoachingClient, SearchCoachingOffersParams, SearchCoachingOffersResult};
+use commute::{CommuteMode, Place, RoutingClient};
+use comparison::{
+    compare_reference_numbers, compare_skills, comparison_rows, unique_reference_numbers,
+    CompareJobsParams, CompareJobsResult, ComparedJob,
+};
+use config::{JobsucheConfig, StorageBackend};
+use contact::{extract_contact_info, ContactInfo};
+use context::{record_request_id, RequestContext, RequestId};
+use country::{country_in, format_listing, in_country};
+use cv_match::{Cv, MatchCvParams, MatchCvResult, MAX_CV_JOBS};
+use dates::{
+    days_since, normalize_date, normalize_period, parse_date, period_start, relative_age, today,
+};
+use deadlines::{
+    extract_deadline, upcoming_deadlines, ListUpcomingDeadlinesParams, ListUpcomingDeadlinesResult,
+    DEFAULT_DEADLINE_DAYS, MAX_DEADLINE_DAYS,
+};
+use description::{clean_description, DescriptionFormat};
+use detail_level::{apply_detail_level, DetailLevel};
+use digest::{
+    new_since_baseline, top_jobs, DigestSection, DigestSource, DigestState, GenerateDigestParams,
+    GenerateDigestResult, DEFAULT_DIGEST_TOP_JOBS, DIGEST_STATE_KEY, MAX_DIGEST_TOP_JOBS,
+};
+use duplicates::{collapse_duplicates, duplicate_clusters, DuplicatePosting, Posting};
+use elicitation::{search_constraints_request, ElicitationAction, Elicitor};
+use employer::{
+    count_by_location, employer_search_options, fixed_term_share, matches_employer,
+    GetEmployerJobsParams, GetEmployerJobsResult, CONTRACT_TYPES, DEFAULT_EMPLOYER_JOBS, JOB_TYPES,
+    MAX_EMPLOYER_JOBS,
+};
+use employer_filter::{
+    EmployerFilter, GetEmployerFilterParams, GetEmployerFilterResult, UpdateEmployerFilterParams,
+    UpdateEmployerFilterResult, EMPLOYER_FILTER_KEY,
+};
+use employer_monitor::{
+    monitor_key, notable_changes, EmployerMonitor, EmployerMonitorEntry, MonitorEmployersParams,
+    MonitorEmployersResult, DEFAULT_MONITORED_JOBS, MAX_MONITORED_EMPLOYERS, MAX_MONITORED_JOBS,
+};
+use entgeltatlas::{
+    validate_occupation_code, EntgeltatlasClient, GetSalaryStatisticsParams,
+    GetSalaryStatisticsResult, SalaryEstimate,
+};
+use export::{
+    export_file_name, web_url, write_csv, ExportColumn, ExportFormat, ExportResultsParams,
+    ExportResultsResult, ExportRow, DEFAULT_COLUMNS, MAX_EXPORT_DETAILS,
+};
+use favorites::{
+    Favorite, ListFavoritesParams, ListFavoritesResult, RemoveFavoriteParams, RemoveFavoriteResult,
+    SaveFavoriteParams, SaveFavoriteResult,
+};
+use freshness::Freshness;
+use geocoding::{
+    coordinates_in, remove_plain_cache_keys, Coordinates, GeocodeLocationParams,
+    GeocodeLocationResult, Geocoder, PlaceQuery,
+};
+use geojson::{SearchJobsGeoJsonParams, SearchJobsGeoJsonResult};
+use hiring_history::{
+    hiring_series, hiring_trend, EmployerHiringHistoryParams, EmployerHiringHistoryResult,
+    EmployerHistory, DEFAULT_HISTORY_DAYS, MAX_HISTORY_DAYS,
+};
+use history::{
+    GetSearchHistoryParams, GetSearchHistoryResult, RerunSearchHistoryParams,
+    RerunSearchHistoryResult, SearchHistory, HISTORY_KEY, MAX_HISTORY_ENTRIES,
+};
+use import::{
+    parse_reference_numbers, FailedImport, ImportRefnrsParams, ImportRefnrsResult, ImportTarget,
+    ImportedJob, MAX_IMPORT,
+};
+use job_id::{resolve_job_id, IdentifierType};
+use language::{detect_language, is_english_posting, is_english_title};
+use local_search::{
+    JobIndex, SearchLocalJobsParams, SearchLocalJobsResult, DEFAULT_LOCAL_RESULTS, INDEX_FILE_NAME,
+    MAX_LOCAL_RESULTS,
+};
+use locale::{localize_details, Locale};
+use location_filter::is_excluded_location;
+use logging::{
+    refnr_list, SetLogLevelParams, SetLogLevelResult, ADMIN_TOOLS_DISABLED, UPSTREAM_TARGET,
+};
+use maintenance::{run_maintenance, spawn_maintenance, CompactStorageParams, CompactStorageResult};
+use market::{
+    count_by_employer, median, share, SummarizeMarketParams, SummarizeMarketResult,
+    DEFAULT_MARKET_JOBS, DEFAULT_SALARY_SAMPLE, EMPLOYMENT_TYPES, MAX_MARKET_JOBS,
+    MAX_SALARY_SAMPLE, TOP_ENTRIES,
+};
+use notes::{
+    note_text, AddJobNoteParams, AddJobNoteResult, GetJobNotesParams, GetJobNotesResult, JobNote,
+};
+use notifications::{WatchlistAlert, WebhookNotifier};
+use occupation_fields::{
+    list_occupation_fields, ListOccupationFieldsParams, ListOccupationFieldsResult,
+};
+use profile::{
+    DeleteUserProfileParams, DeleteUserProfileResult, GetUserProfileParams, GetUserProfileResult,
+    ProfileMatch, SetUserProfileParams, SetUserProfileResult, UserProfile, PROFILE_KEY,
+};
+use progress::ProgressToken;
+use progress::{ProgressReporter, ProgressSink, TracingProgressSink};
+use projection::{field_names, project, project_jobs};
+use query::{effective_query, EffectiveQuery};
+use quota::{GetQuotaUsageParams, QuotaTracker, QuotaUsage, UpstreamCall};
+use ranking::{
+    rank_jobs, RankCandidate, RankJobsParams, RankJobsResult, DEFAULT_RANK_JOBS, MAX_RANK_JOBS,
+};
+use redirects::{url_domain, UrlResolver};
+use regional::{
+    count_by_occupation, region_search, RegionalOverviewParams, RegionalOverviewResult,
+    DEFAULT_REGION_JOBS, MAX_REGION_JOBS, TOP_REGION_ENTRIES,
+};
+use relaxation::{relaxed_searches, SearchRelaxation};
+use relocation::{mentions_relocation, mentions_visa_sponsorship};
+use report::{render_markdown, report_title};
+use resources::{
+    parse_job_uri, ChunkRange, DescriptionChunk, ReadJobDescriptionParams, ABOUT_URI, RESOURCES,
+    RESOURCE_TEMPLATES,
+};
+use salary::{parse_salary, ParsedSalary};
+use saved_searches::{
+    CreateSavedSearchParams, CreateSavedSearchResult, DeleteSavedSearchParams,
+    DeleteSavedSearchResult, ListSavedSearchesParams, ListSavedSearchesResult,
+    RunSavedSearchParams, RunSavedSearchResult, SavedSearch,
+};
+use scheduler::{
+    parse_since, spawn_schedule, GetScheduledUpdatesParams, GetScheduledUpdatesResult, Schedule,
+    ScheduleInfo, ScheduledResult, ScheduledRun, ScheduledRunLog, ScheduledSource,
+    MAX_SCHEDULED_RUNS, RUNS_KEY,
+};
+use seen::{append_unique, SeenJob};
+use similar::{
+    city_of, title_keywords, FindSimilarJobsParams, FindSimilarJobsResult, SimilarityBase,
+    DEFAULT_SIMILAR_JOBS, DEFAULT_SIMILAR_RADIUS_KM, MAX_SIMILAR_JOBS,
+};
+use skills::{extract_language_requirements, extract_skills, ExtractedSkill, LanguageRequirement};
+use sort::SortBy;
+use std::future::Future;
+use storage::{
+    ensure_unencrypted, name_key, now_rfc3339, EncryptedStore, JobStore, JsonFileStore,
+    MemoryStore, SqliteStore, Storage, StoreKey, DIGESTS, DIGEST_BASELINES, EMPLOYER_FILTER,
+    EMPLOYER_HISTORY, EMPLOYER_MONITORS, FAVORITES, GEOCODE_CACHE, JOB_ARCHIVE, JOB_NOTES,
+    SAVED_SEARCHES, SCHEDULED_RUNS, SCHEDULE_BASELINES, SEARCH_HISTORY, SEEN_JOBS, USER_PROFILE,
+    WATCHLISTS,
+};
+use storage_stats::{storage_stats, GetStorageStatsParams, GetStorageStatsResult};
+use studiensuche::{SearchDegreeProgramsParams, SearchDegreeProgramsResult, StudiensucheClient};
+use suggestions::{
+    SuggestJobTitlesParams, SuggestJobTitlesResult, SuggestLocationsParams, SuggestLocationsResult,
+    SuggestionClient,
+};
+use tools::{enabled_tools, feature_status, FeatureStatus, TOOLS};
+use trends::{
+    buckets, snapshots, trend, JobTrendsParams, JobTrendsResult, TrendWindow, TREND_WINDOWS,
+};
+use validation::{
+    validate_batch_search, validate_job_details, validate_search_jobs,
+    validate_search_with_details, Validator, MAX_DETAILS, MAX_RADIUS_KM,
+};
+use watchlists::{
+    CheckWatchlistsParams, CheckWatchlistsResult, CreateWatchlistParams, CreateWatchlistResult,
+    DeleteWatchlistParams, DeleteWatchlistResult, ListWatchlistsParams, ListWatchlistsResult,
+    Watchlist, WatchlistCheckResult, WatchlistInfo, WatchlistJob,
+};
+use weiterbildung::{SearchTrainingsParams, SearchTrainingsResult, WeiterbildungClient};
 
 /// Server status information
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct JobsucheServerStatus {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
     pub server_name: String,
     pub version: String,
     pub uptime_seconds: u64,
     pub api_url: String,
     pub api_connection_status: String,
+    /// Number of registered tools, including disabled ones
     pub tools_count: usize,
+    /// Tools that work with the current configuration
+    pub enabled_tools: Vec<String>,
+    /// Optional features and the tools that depend on them
+    pub features: Vec<FeatureStatus>,
+    /// Number of fixed resources (`jobsuche://about`)
+    pub resources_count: usize,
+    /// Number of resource URI templates (`jobsuche://jobs/{reference_number}`)
+    pub resource_templates_count: usize,
+    /// Number of prompts
+    pub prompts_count: usize,
+    /// Calls to the Jobsuche API per hour and day (see get_quota_usage)
+    pub api_usage: QuotaUsage,
+    /// Git commit, build time, jobsuche version and cargo features of this build
+    pub build: BuildInfo,
 }
 
 /// Parameters for searching jobs
@@ -39,46 +328,117 @@ pub struct JobsucheServerStatus {
 #[serde(deny_unknown_fields)]
 pub struct SearchJobsParams {
     /// Job title or keywords (e.g., "Software Engineer", "Data Scientist")
+    #[schemars(example = &"Softwareentwickler")]
     pub job_title: Option<String>,
 
     /// Location name (e.g., "Berlin", "München", "Deutschland")
+    /// Default: the configured default location, if any ("Deutschland" searches
+    /// nationwide)
+    #[schemars(example = &"Berlin")]
     pub location: Option<String>,
 
-    /// Search radius in kilometers from the location (default: 25)
+    /// Search radius in kilometers from the location (0-200, default from
+    /// config, else 25)
+    #[schemars(range(max = 200), example = 25)]
     pub radius_km: Option<u64>,
 
+    /// Search all of Germany, ignoring location, radius_km and the configured
+    /// default location (default: false)
+    /// A search without any location is nationwide as well; effective_query
+    /// in the result tells which applied
+    pub nationwide: Option<bool>,
+
     /// Employment type filter
-    /// Options: "fulltime" (Vollzeit), "parttime" (Teilzeit), "mini_job", "home_office"
+    /// Options: "fulltime" (Vollzeit), "parttime" (Teilzeit), "mini_job", "home_office",
+    /// "shift" (Schichtarbeit)
+    #[schemars(extend("items" = {"type": "string", "enum": completion::EMPLOYMENT_TYPES}))]
     pub employment_type: Option<Vec<String>>,
 
     /// Contract type filter
     /// Options: "permanent" (unbefristet), "temporary" (befristet)
+    #[schemars(extend("items" = {"type": "string", "enum": completion::CONTRACT_TYPES}))]
     pub contract_type: Option<Vec<String>>,
 
     /// Days since publication (0-100, default: 30)
     /// Example: 7 for jobs posted in the last week
+    #[schemars(range(max = 100), example = 7)]
     pub published_since_days: Option<u64>,
 
+    /// Publication window instead of published_since_days: "today", "3days",
+    /// "week" or "month"
+    /// "today" only returns jobs published on the current date
+    #[schemars(example = &"week")]
+    pub freshness: Option<Freshness>,
+
     /// Number of results per page (1-100, default from config)
+    #[schemars(range(min = 1, max = 100))]
     pub page_size: Option<u64>,
 
     /// Page number for pagination (starting from 1)
+    #[schemars(range(min = 1))]
     pub page: Option<u64>,
 
     /// Employer name to search for
     /// Note: This is combined with job_title in the search query
     /// Example: "BARMER", "Siemens", "Deutsche Bahn"
+    #[schemars(example = &"Deutsche Bahn")]
     pub employer: Option<String>,
 
     /// Branch/industry to search in
     /// Note: This is combined with job_title in the search query
     /// Example: "IT", "Gesundheitswesen", "Automotive"
     pub branch: Option<String>,
+
+    /// Skip jobs already returned by earlier searches (default: false)
+    /// The page is refilled from the following result pages
+    pub hide_seen: Option<bool>,
+
+    /// Only return jobs whose title suggests a posting in English, for
+    /// applicants without German (default: false). Judged by the title only,
+    /// so the page may hold fewer jobs; search_jobs_with_details also checks
+    /// the description language
+    pub english_postings_only: Option<bool>,
+
+    /// Collapse postings of the same role (e.g. by the employer and several
+    /// temp agencies) into the first one, listing the others in its
+    /// `duplicates` (default: false). Without descriptions, postings with
+    /// nearly the same title in the same place count as duplicates
+    pub collapse_duplicates: Option<bool>,
+
+    /// Drop jobs in these places (up to 50), e.g. towns you won't commute to
+    /// near the search location. Postal codes match by prefix ("42" drops
+    /// 42xxx), towns case-insensitively including their districts
+    /// ("Köln" drops "Köln-Ehrenfeld"). Applied after the search, so the page
+    /// may hold fewer jobs
+    pub exclude_locations: Option<Vec<String>>,
+
+    /// Only return jobs in this country, by German or English name or ISO code
+    /// ("Schweiz", "Austria", "NL"). Without a location the search runs in
+    /// the whole country; with one, e.g. a border town and a radius, jobs on
+    /// the German side are dropped
+    #[schemars(example = &"CH")]
+    pub country: Option<String>,
+
+    /// Include postings abroad (default: true); false keeps only jobs in
+    /// Germany
+    pub include_abroad: Option<bool>,
+
+    /// Order of the jobs on the page: "relevance" (the API's order, default),
+    /// "newest" or "distance" from the search location
+    pub sort_by: Option<SortBy>,
+
+    /// Optional field filtering of the returned jobs (search_jobs only; fields
+    /// as in the job summaries)
+    pub fields: Option<FieldFilter>,
 }
 
 /// Result from job search
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SearchJobsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
     /// Total number of results found
     pub total_results: Option<u64>,
 
@@ -94,12 +454,38 @@ pub struct SearchJobsResult {
     /// Job listings
     pub jobs: Vec<JobSummary>,
 
+    /// Number of already seen jobs skipped because of hide_seen
+    pub hidden_seen_count: usize,
+
+    /// Number of jobs folded into `duplicates` of other jobs because of
+    /// collapse_duplicates
+    #[serde(default)]
+    pub duplicates_collapsed: usize,
+
+    /// Number of jobs dropped by the employer blocklist or allowlist
+    #[serde(default)]
+    pub employer_filtered_count: usize,
+
+    /// Number of jobs dropped because of exclude_locations, country or
+    /// include_abroad
+    #[serde(default)]
+    pub location_excluded_count: usize,
+
     /// Search performance info
     pub search_duration_ms: u64,
+
+    /// Keywords and location as sent to the API, after normalization
+    #[serde(default)]
+    pub effective_query: EffectiveQuery,
+
+    /// Relaxed variants of the search with their result counts (only if the
+    /// search found nothing)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relaxations: Vec<SearchRelaxation>,
 }
 
 /// Summary information for a job listing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct JobSummary {
     /// Reference number (use this to get job details)
     pub reference_number: String,
@@ -113,26 +499,97 @@ pub struct JobSummary {
     /// Location information
     pub location: String,
 
-    /// Publication date (YYYY-MM-DD format)
+    /// Distance in km from the search location (only when searching with a location)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+
+    /// Coordinates of the work location, if the API delivers them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<Coordinates>,
+
+    /// Country of the work location, if it is abroad (German name, e.g.
+    /// "Schweiz"; also appended to `location`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+
+    /// Publication date (ISO 8601, YYYY-MM-DD)
     pub published_date: Option<String>,
 
+    /// Publication date as delivered if it could not be normalized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_date_raw: Option<String>,
+
+    /// Days since publication (computed server-side from today's date)
+    #[serde(default)]
+    pub published_days_ago: Option<i64>,
+
+    /// Publication age in words ("today", "3 days ago", "2 weeks ago")
+    #[serde(default)]
+    pub published_ago: Option<String>,
+
     /// External URL if available
     pub external_url: Option<String>,
+
+    /// Job page on arbeitsagentur.de
+    #[serde(default)]
+    pub web_url: String,
+
+    /// Postings of the same role folded into this one (only with
+    /// collapse_duplicates)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<DuplicatePosting>,
 }
 
 /// Parameters for getting job details
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct GetJobDetailsParams {
-    /// Job reference number (refnr from search results)
+    /// Job reference number (refnr from search results) or the Base64 hashId
+    /// from an arbeitsagentur.de link
+    #[schemars(length(min = 1), example = &"10000-1234567890-S")]
     pub reference_number: String,
+
+    /// KldB 2010 occupation code (five digits, e.g. "43104"); if the posting
+    /// states no salary, regional median pay for this occupation is attached
+    /// from the Entgeltatlas as `salary_estimate`
+    pub occupation_code: Option<String>,
+
+    /// Language of enumerated values such as employment type ("de" or "en";
+    /// default: JOBSUCHE_LOCALE)
+    pub lang: Option<Locale>,
+
+    /// Follow the redirects of the external application URL to report the
+    /// final application portal (default: false; the job must have been
+    /// returned by a search before)
+    pub resolve_external_url: Option<bool>,
+
+    /// Compare the job against the stored user profile and attach
+    /// `profile_match` (default: false; see set_user_profile)
+    pub profile_match: Option<bool>,
+
+    /// How much of the job is returned: "summary" (description condensed to
+    /// about 500 characters, no raw data), "standard" (no raw data) or "full"
+    /// (default)
+    pub detail_level: Option<DetailLevel>,
+
+    /// Include the raw API data as `raw_data`, which roughly doubles the
+    /// response (only with detail level "full"; default: JOBSUCHE_INCLUDE_RAW,
+    /// false unless configured)
+    pub include_raw: Option<bool>,
+
+    /// Optional field filtering to reduce response size
+    pub fields: Option<FieldFilter>,
 }
 
 /// Optional field filtering for responses
+///
+/// Fields are named as in get_job_details; dots reach into nested objects
+/// and arrays (e.g. "raw_data.arbeitgeberAdresse.ort", "extracted_skills.name").
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct FieldFilter {
-    /// Fields to include (if specified, only these fields are returned)
+    /// Fields to include (if specified, only these fields are returned;
+    /// reference_number is always kept)
     pub include_fields: Option<Vec<String>>,
 
     /// Fields to exclude (these fields will be omitted from the response)
@@ -144,27 +601,103 @@ pub struct FieldFilter {
 #[serde(deny_unknown_fields)]
 pub struct SearchJobsWithDetailsParams {
     /// Search parameters (same as search_jobs)
+    #[schemars(example = &"Softwareentwickler")]
     pub job_title: Option<String>,
+    #[schemars(example = &"Berlin")]
     pub location: Option<String>,
+    #[schemars(range(max = 200), example = 25)]
     pub radius_km: Option<u64>,
+    pub nationwide: Option<bool>,
+    #[schemars(extend("items" = {"type": "string", "enum": completion::EMPLOYMENT_TYPES}))]
     pub employment_type: Option<Vec<String>>,
+    #[schemars(extend("items" = {"type": "string", "enum": completion::CONTRACT_TYPES}))]
     pub contract_type: Option<Vec<String>>,
+    #[schemars(range(max = 100), example = 7)]
     pub published_since_days: Option<u64>,
+    #[schemars(example = &"week")]
+    pub freshness: Option<Freshness>,
+    #[schemars(range(min = 1, max = 100))]
     pub page_size: Option<u64>,
+    #[schemars(range(min = 1))]
     pub page: Option<u64>,
+    #[schemars(example = &"Deutsche Bahn")]
     pub employer: Option<String>,
     pub branch: Option<String>,
+    pub hide_seen: Option<bool>,
+    pub english_postings_only: Option<bool>,
+    pub exclude_locations: Option<Vec<String>>,
+    #[schemars(example = &"CH")]
+    pub country: Option<String>,
+    pub include_abroad: Option<bool>,
+
+    /// Collapse postings of the same role into the first one, listing the
+    /// others in its `duplicates` (default: false). Titles, places and
+    /// descriptions are compared, so fewer than `max_details` jobs may be
+    /// returned
+    pub collapse_duplicates: Option<bool>,
 
     /// Automatically fetch details for top N results (default: 3, max: 10)
+    #[schemars(range(max = 10))]
     pub max_details: Option<u64>,
 
+    /// Only return jobs whose description is written in English (checked after
+    /// fetching details, so fewer than `max_details` jobs may be returned)
+    pub english_only: Option<bool>,
+
+    /// Only return jobs whose description offers visa sponsorship (checked
+    /// after fetching details, like `english_only`)
+    pub visa_sponsorship_only: Option<bool>,
+
+    /// Only return jobs whose description offers relocation support (checked
+    /// after fetching details, like `english_only`)
+    pub relocation_only: Option<bool>,
+
+    /// Only return jobs reachable from the home location within this many
+    /// minutes (checked after fetching details, like `english_only`; needs
+    /// JOBSUCHE_ROUTING_PROVIDER). Jobs whose commute cannot be computed are
+    /// kept
+    #[schemars(example = 45)]
+    pub max_commute_minutes: Option<u32>,
+
+    /// Attach `commute_minutes` from the home location to each job, travelling
+    /// by "driving" or "transit" (default: driving whenever
+    /// `max_commute_minutes` is set)
+    pub commute_mode: Option<CommuteMode>,
+
+    /// Sort the jobs by straight-line distance from the home location
+    /// (JOBSUCHE_HOME_LOCATION) and attach it as `home_distance_km`; jobs whose
+    /// location is unknown come last (default: false)
+    pub sort_by_distance: Option<bool>,
+
+    /// Language of enumerated values such as employment type ("de" or "en";
+    /// default: JOBSUCHE_LOCALE)
+    pub lang: Option<Locale>,
+
+    /// Compare each job against the stored user profile and attach
+    /// `profile_match` (default: false; see set_user_profile)
+    pub profile_match: Option<bool>,
+
+    /// How much of each job is returned: "summary" (description condensed to
+    /// about 500 characters, no raw data), "standard" (no raw data) or "full"
+    /// (default)
+    pub detail_level: Option<DetailLevel>,
+
+    /// Include the raw API data as `raw_data`, which roughly doubles the
+    /// response (only with detail level "full"; default: JOBSUCHE_INCLUDE_RAW,
+    /// false unless configured)
+    pub include_raw: Option<bool>,
+
     /// Optional field filtering to reduce response size
     pub fields: Option<FieldFilter>,
 }
 
 /// Result from search_jobs_with_details
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SearchJobsWithDetailsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
     /// Total number of results found
     pub total_results: Option<u64>,
 
@@ -177,14 +710,30 @@ pub struct SearchJobsWithDetailsResult {
     /// Number of jobs returned
     pub jobs_count: usize,
 
-    /// Job listings with full details
-    pub jobs: Vec<GetJobDetailsResult>,
+    /// Job listings with full details, reduced to the requested fields
+    pub jobs: Vec<serde_json::Value>,
 
     /// Search performance info
     pub search_duration_ms: u64,
 
     /// Details fetch performance info
     pub details_duration_ms: u64,
+
+    /// True if the client cancelled the request; `jobs` then holds the details
+    /// fetched before cancellation
+    pub cancelled: bool,
+
+    /// Number of jobs folded into `duplicates` of other jobs because of
+    /// collapse_duplicates
+    pub duplicates_collapsed: usize,
+
+    /// Keywords and location as sent to the API, after normalization
+    pub effective_query: EffectiveQuery,
+
+    /// Relaxed variants of the search with their result counts (only if the
+    /// search found nothing)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relaxations: Vec<SearchRelaxation>,
 }
 
 /// Single search configuration for batch operations
@@ -195,12 +744,22 @@ pub struct BatchSearchItem {
     pub name: String,
 
     /// Search parameters
+    #[schemars(example = &"Softwareentwickler")]
     pub job_title: Option<String>,
+    #[schemars(example = &"Berlin")]
     pub location: Option<String>,
+    #[schemars(range(max = 200), example = 25)]
     pub radius_km: Option<u64>,
+    pub nationwide: Option<bool>,
+    #[schemars(extend("items" = {"type": "string", "enum": completion::EMPLOYMENT_TYPES}))]
     pub employment_type: Option<Vec<String>>,
+    #[schemars(extend("items" = {"type": "string", "enum": completion::CONTRACT_TYPES}))]
     pub contract_type: Option<Vec<String>>,
+    #[schemars(range(max = 100), example = 7)]
     pub published_since_days: Option<u64>,
+    #[schemars(example = &"week")]
+    pub freshness: Option<Freshness>,
+    #[schemars(example = &"Deutsche Bahn")]
     pub employer: Option<String>,
     pub branch: Option<String>,
 }
@@ -210,17 +769,29 @@ pub struct BatchSearchItem {
 #[serde(deny_unknown_fields)]
 pub struct BatchSearchJobsParams {
     /// List of searches to perform (max: 5)
+    #[schemars(length(min = 1, max = 5))]
     pub searches: Vec<BatchSearchItem>,
 
     /// Automatically fetch details for top N results per search (default: 2, max: 5)
+    #[schemars(range(max = 5))]
     pub max_details_per_search: Option<u64>,
 
+    /// How much of each job is returned: "summary" (description condensed to
+    /// about 500 characters, no raw data), "standard" (no raw data) or "full"
+    /// (default)
+    pub detail_level: Option<DetailLevel>,
+
+    /// Include the raw API data as `raw_data`, which roughly doubles the
+    /// response (only with detail level "full"; default: JOBSUCHE_INCLUDE_RAW,
+    /// false unless configured)
+    pub include_raw: Option<bool>,
+
     /// Optional field filtering to reduce response size
     pub fields: Option<FieldFilter>,
 }
 
 /// Result from a single batch search
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BatchSearchItemResult {
     /// Name of this search
     pub search_name: String,
@@ -231,16 +802,21 @@ pub struct BatchSearchItemResult {
     /// Number of jobs returned with details
     pub jobs_count: usize,
 
-    /// Job listings with full details (if max_details_per_search > 0)
-    pub jobs: Vec<GetJobDetailsResult>,
+    /// Job listings with full details (if max_details_per_search > 0),
+    /// reduced to the requested fields
+    pub jobs: Vec<serde_json::Value>,
 
     /// Error message if search failed
     pub error: Option<String>,
 }
 
 /// Result from batch_search_jobs
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BatchSearchJobsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
     /// Number of searches performed
     pub searches_count: usize,
 
@@ -249,11 +825,19 @@ pub struct BatchSearchJobsResult {
 
     /// Total execution time
     pub total_duration_ms: u64,
+
+    /// True if the client cancelled the request; `results` then holds the
+    /// searches completed before cancellation
+    pub cancelled: bool,
 }
 
 /// Detailed job information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GetJobDetailsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
     /// Reference number
     pub reference_number: String,
 
@@ -263,6 +847,10 @@ pub struct GetJobDetailsResult {
     /// Job description
     pub description: Option<String>,
 
+    /// ISO 639-1 code of the language the description is written in ("de",
+    /// "en", ...); null if it cannot be told
+    pub description_language: Option<String>,
+
     /// Employer name
     pub employer: Option<String>,
 
@@ -275,18 +863,38 @@ pub struct GetJobDetailsResult {
     /// Contract type
     pub contract_type: Option<String>,
 
-    /// Start date
+    /// Start date (ISO 8601, start of `entry_period`)
     pub start_date: Option<String>,
 
-    /// Application deadline (not available in API)
+    /// Application deadline (ISO 8601) named in the description, e.g. after
+    /// "Bewerbungsschluss" (best-effort; the API has no deadline field)
     pub application_deadline: Option<String>,
 
-    /// Contact information (not available in API)
-    pub contact_info: Option<String>,
+    /// Contact persons, e-mail addresses and phone numbers found in the
+    /// description (best-effort; the API has no contact field)
+    pub contact_info: Option<ContactInfo>,
 
-    /// External application URL
+    /// External application URL (known if the job appeared in an earlier search)
     pub external_url: Option<String>,
 
+    /// Final URL of `external_url` after following redirects (only with
+    /// `resolve_external_url`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_external_url: Option<String>,
+
+    /// Whether the job was requested by refnr or hashId (only from
+    /// get_job_details)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identifier_type: Option<IdentifierType>,
+
+    /// Domain of the application portal, e.g. "jobs.example.com"
+    #[serde(default)]
+    pub application_domain: Option<String>,
+
+    /// Job page on arbeitsagentur.de
+    #[serde(default)]
+    pub web_url: String,
+
     /// Employer profile/presentation URL
     pub employer_profile_url: Option<String>,
 
@@ -296,6 +904,31 @@ pub struct GetJobDetailsResult {
     /// Salary/compensation information
     pub salary: Option<String>,
 
+    /// Salary range, currency, period and tariff parsed from `salary`
+    pub salary_parsed: Option<ParsedSalary>,
+
+    /// Regional median pay for the occupation if the posting states no salary
+    /// (only with `occupation_code`; an estimate, not the employer's offer)
+    pub salary_estimate: Option<SalaryEstimate>,
+
+    /// Programming languages, frameworks, tools, certifications and driver's
+    /// licenses mentioned in the title or description
+    #[serde(default)]
+    pub extracted_skills: Vec<ExtractedSkill>,
+
+    /// Spoken languages asked for, with CEFR level or proficiency if stated
+    #[serde(default)]
+    pub language_requirements: Vec<LanguageRequirement>,
+
+    /// Whether the description offers visa sponsorship or help with the Blue
+    /// Card (heuristic)
+    #[serde(default)]
+    pub mentions_visa_sponsorship: bool,
+
+    /// Whether the description offers relocation support (heuristic)
+    #[serde(default)]
+    pub mentions_relocation: bool,
+
     /// Contract duration
     pub contract_duration: Option<String>,
 
@@ -320,21 +953,41 @@ pub struct GetJobDetailsResult {
     /// Publication date (not available in API v0.3.0)
     pub published_date: Option<String>,
 
-    /// First publication date
+    /// First publication date (ISO 8601)
     pub first_published: Option<String>,
 
+    /// First publication date as delivered if it could not be normalized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_published_raw: Option<String>,
+
+    /// Days since the current publication started (or since first publication)
+    #[serde(default)]
+    pub published_days_ago: Option<i64>,
+
+    /// Publication age in words ("today", "3 days ago", "2 weeks ago")
+    #[serde(default)]
+    pub published_ago: Option<String>,
+
     /// Only for severely disabled persons
     pub only_for_disabled: Option<bool>,
 
     /// Full-time employment
     pub fulltime: Option<bool>,
 
-    /// Entry period (date range)
+    /// Entry period (ISO 8601 interval, e.g. "2025-11-01/.." for "from 2025-11-01")
     pub entry_period: Option<String>,
 
-    /// Publication period (date range)
+    /// Entry period as delivered if it could not be normalized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_period_raw: Option<String>,
+
+    /// Publication period (ISO 8601 interval)
     pub publication_period: Option<String>,
 
+    /// Publication period as delivered if it could not be normalized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publication_period_raw: Option<String>,
+
     /// Minor employment (Geringfügige Beschäftigung/Minijob)
     pub is_minor_employment: Option<bool>,
 
@@ -350,8 +1003,25 @@ pub struct GetJobDetailsResult {
     /// Cipher number (for anonymous job postings)
     pub cipher_number: Option<String>,
 
-    /// Raw JSON for additional fields
+    /// Raw JSON for additional fields (only with include_raw and detail level
+    /// "full")
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
     pub raw_data: serde_json::Value,
+
+    /// User notes on this job (see add_job_note)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<JobNote>,
+
+    /// Fit to the stored user profile (only with `profile_match`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile_match: Option<ProfileMatch>,
+}
+
+/// Jobs dropped by the filters applied after the API search
+#[derive(Debug, Default)]
+struct FilteredCounts {
+    employer: usize,
+    location: usize,
 }
 
 /// Jobsuche MCP Server
@@ -373,6 +1043,58 @@ pub struct JobsucheMcpServer {
 
     /// Configuration
     config: Arc<JobsucheConfig>,
+
+    /// Destination for progress notifications of long-running tools
+    progress_sink: Arc<dyn ProgressSink>,
+
+    /// Cancellation tokens of in-flight requests
+    cancellations: Arc<CancellationRegistry>,
+
+    /// Counts of the calls to the Jobsuche API
+    quota: Arc<QuotaTracker>,
+
+    /// Elicitation support of the connected client, if any
+    elicitor: Option<Arc<dyn Elicitor>>,
+
+    /// Writer of the audit log (JOBSUCHE_AUDIT_LOG)
+    audit: Option<AuditRecorder>,
+
+    /// Persistent local data (favorites, watchlists, history, ...)
+    storage: Storage,
+
+    /// Full-text index of fetched jobs
+    job_index: Arc<JobIndex>,
+
+    /// Entgeltatlas client for salary estimates
+    entgeltatlas: Arc<EntgeltatlasClient>,
+
+    /// Weiterbildungssuche client for course searches
+    weiterbildung: Arc<WeiterbildungClient>,
+
+    /// Coachingangebote client for AVGS coaching searches
+    coaching: Arc<CoachingClient>,
+
+    /// Studiensuche client for degree program searches
+    studiensuche: Arc<StudiensucheClient>,
+
+    /// Bewerberbörse client for the recruiter tools (`None` while they are
+    /// disabled)
+    bewerberboerse: Option<Arc<BewerberboerseClient>>,
+
+    /// Jobbörse autocomplete client for job title and place suggestions
+    suggestions: Arc<SuggestionClient>,
+
+    /// Follows redirects of external application URLs
+    url_resolver: Arc<UrlResolver>,
+
+    /// Webhook notified about new watchlist jobs, if configured
+    notifier: Option<Arc<WebhookNotifier>>,
+
+    /// Routing service for commute times, if configured
+    routing: Option<Arc<RoutingClient>>,
+
+    /// Geocoding service for places without coordinates, if enabled
+    geocoder: Option<Arc<Geocoder>>,
 }
 
 impl Default for JobsucheMcpServer {
@@ -385,10 +1107,32 @@ impl JobsucheMcpServer {
     /// Create a new Jobsuche MCP Server
     #[instrument]
     pub async fn new() -> anyhow::Result<Self> {
-        info!("Initializing Jobsuche MCP Server");
+        Self::with_config(JobsucheConfig::load()?).await
+    }
+
+    /// Create a server from an explicit configuration instead of the environment
+    pub async fn with_config(config: JobsucheConfig) -> anyhow::Result<Self> {
+        config.validate()?;
+        let store = Self::open_store(&config)?;
+        Self::build(config, store).await
+    }
 
-        let config = Arc::new(JobsucheConfig::load()?);
+    /// Create a server that keeps its persistent data in `store`
+    ///
+    /// Lets embedders of the library keep favorites, saved searches, watchlists,
+    /// the search history and the job archive in their own [`JobStore`]. The
+    /// store is encrypted like the built-in ones if JOBSUCHE_STORE_KEY is set.
+    pub async fn new_with_store(store: Arc<dyn JobStore>) -> anyhow::Result<Self> {
+        let config = JobsucheConfig::load()?;
         config.validate()?;
+        Self::build(config, store).await
+    }
+
+    /// Set up the server around its store, then start the background tasks
+    async fn build(config: JobsucheConfig, store: Arc<dyn JobStore>) -> anyhow::Result<Self> {
+        info!("Initializing Jobsuche MCP Server");
+
+        let config = Arc::new(config);
 
         info!("Configuration loaded: API URL = {}", config.api_url);
 
@@ -402,20 +1146,362 @@ impl JobsucheMcpServer {
 
         let client = JobsucheAsync::new(&config.api_url, credentials).await?;
 
+        let store_key = config
+            .store_key
+            .as_deref()
+            .map(Self::store_key)
+            .transpose()?;
+        let storage = Self::storage_for(store, store_key.as_ref())?;
+        remove_plain_cache_keys(&storage)?;
+
+        // The index holds descriptions in plain text, so it stays in memory
+        // when the store is encrypted
+        let job_index = Arc::new(match config.data_dir {
+            Some(ref data_dir) if config.store_key.is_none() => {
+                JobIndex::open(&data_dir.join(INDEX_FILE_NAME))?
+            }
+            _ => JobIndex::in_memory()?,
+        });
+
+        let notifier = match config.webhook_url {
+            Some(ref url) => {
+                let notifier = WebhookNotifier::new(
+                    url,
+                    config.webhook_format,
+                    config.webhook_secret.clone(),
+                )?;
+                info!(
+                    "Sending watchlist notifications to webhook ({:?} format)",
+                    notifier.format()
+                );
+                Some(Arc::new(notifier))
+            }
+            None => None,
+        };
+
         info!("Jobsuche MCP Server initialized successfully");
 
-        Ok(Self {
+        let entgeltatlas = Arc::new(EntgeltatlasClient::new(&config.entgeltatlas_url)?);
+        let weiterbildung = Arc::new(WeiterbildungClient::new(&config.weiterbildung_url)?);
+        let coaching = Arc::new(CoachingClient::new(&config.coaching_url)?);
+        let studiensuche = Arc::new(StudiensucheClient::new(&config.studiensuche_url)?);
+        let bewerberboerse = if config.recruiter_tools {
+            let api_key = config
+                .bewerberboerse_api_key
+                .as_deref()
+                .unwrap_or(DEFAULT_BEWERBERBOERSE_API_KEY);
+            info!("Recruiter tools enabled");
+            Some(Arc::new(BewerberboerseClient::new(
+                &config.bewerberboerse_url,
+                api_key,
+            )?))
+        } else {
+            None
+        };
+        let suggestions = Arc::new(SuggestionClient::new(
+            &config.api_url,
+            config.api_key.as_deref(),
+        )?);
+        let url_resolver = Arc::new(UrlResolver::new()?);
+        let routing = match config.routing_provider {
+            Some(provider) => {
+                info!("Computing commute times with {:?}", provider);
+                Some(Arc::new(RoutingClient::new(
+                    provider,
+                    config.routing_url.as_deref(),
+                    config.routing_api_key.clone(),
+                )?))
+            }
+            None => None,
+        };
+        let geocoder = if config.geocoding {
+            info!("Geocoding places with {}", config.geocoding_url);
+            let cache_secret = store_key.as_ref().map(|key| key.derive(GEOCODE_CACHE));
+            Some(Arc::new(Geocoder::new(
+                &config.geocoding_url,
+                cache_secret,
+            )?))
+        } else {
+            None
+        };
+        if let Some(budget) = config.daily_api_budget {
+            info!(
+                "Batch tools stop after {} Jobsuche API calls per day",
+                budget
+            );
+        }
+        let quota = Arc::new(QuotaTracker::new(config.daily_api_budget));
+        let audit = config.audit_log.then(|| {
+            info!("Recording tool calls in the audit log");
+            AuditRecorder::start(storage.clone())
+        });
+
+        let server = Self {
             start_time: Instant::now(),
             client: Arc::new(client),
             config,
+            progress_sink: Arc::new(TracingProgressSink),
+            cancellations: Arc::new(CancellationRegistry::default()),
+            quota,
+            elicitor: None,
+            audit,
+            storage,
+            job_index,
+            entgeltatlas,
+            weiterbildung,
+            coaching,
+            studiensuche,
+            bewerberboerse,
+            suggestions,
+            url_resolver,
+            notifier,
+            routing,
+            geocoder,
+        };
+        server.start_background_tasks()?;
+
+        Ok(server)
+    }
+
+    /// Start the maintenance task and the scheduled runs of watchlists and
+    /// saved searches
+    ///
+    /// Called once the storage is final, since every task keeps its own handle
+    /// to it.
+    fn start_background_tasks(&self) -> anyhow::Result<()> {
+        if self.config.maintenance_interval_hours > 0 {
+            spawn_maintenance(
+                self.storage.clone(),
+                self.job_index.clone(),
+                self.config.retention_days,
+                std::time::Duration::from_secs(self.config.maintenance_interval_hours * 3600),
+            );
+        }
+
+        if let Some(ref expression) = self.config.watchlist_schedule {
+            let server = self.clone();
+            spawn_schedule(expression.parse()?, "watchlist checks", move || {
+                let server = server.clone();
+                async move { server.run_scheduled(ScheduledSource::Watchlist).await }
+            });
+        }
+
+        if let Some(ref expression) = self.config.saved_search_schedule {
+            let server = self.clone();
+            spawn_schedule(expression.parse()?, "saved searches", move || {
+                let server = server.clone();
+                async move { server.run_scheduled(ScheduledSource::SavedSearch).await }
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether watchlists or saved searches run on a schedule
+    pub fn has_schedules(&self) -> bool {
+        self.config.watchlist_schedule.is_some() || self.config.saved_search_schedule.is_some()
+    }
+
+    /// Open the configured storage backend
+    fn open_store(config: &JobsucheConfig) -> anyhow::Result<Arc<dyn JobStore>> {
+        Ok(match config.effective_storage_backend() {
+            StorageBackend::Sqlite => {
+                let path = config
+                    .sqlite_path()
+                    .context("No database path configured")?;
+                Arc::new(SqliteStore::open(&path)?)
+            }
+            StorageBackend::Json => {
+                let data_dir = config
+                    .data_dir
+                    .as_deref()
+                    .context("No data directory configured")?;
+                Arc::new(JsonFileStore::open(data_dir)?)
+            }
+            StorageBackend::Memory => {
+                info!("Using in-memory storage, favorites and other data are not persisted");
+                Arc::new(MemoryStore::new())
+            }
         })
     }
 
+    /// Typed access to `store`, encrypted if a store key is set
+    fn storage_for(store: Arc<dyn JobStore>, key: Option<&StoreKey>) -> anyhow::Result<Storage> {
+        let Some(key) = key else {
+            ensure_unencrypted(store.as_ref())?;
+            return Ok(Storage::new(store));
+        };
+        info!("Encrypting persistent data at rest");
+        Ok(Storage::new(Arc::new(EncryptedStore::open(store, key)?)))
+    }
+
+    /// Parse the configured store key, or read it from the OS keychain
+    fn store_key(key: &str) -> anyhow::Result<StoreKey> {
+        if key.trim().eq_ignore_ascii_case("keychain") {
+            #[cfg(feature = "keychain")]
+            return storage::keychain_key();
+            #[cfg(not(feature = "keychain"))]
+            anyhow::bail!("JOBSUCHE_STORE_KEY=keychain requires building with --features keychain");
+        }
+        StoreKey::parse(key).context("Invalid JOBSUCHE_STORE_KEY")
+    }
+
+    /// Replace the webhook notified about new watchlist jobs
+    pub fn with_notifier(mut self, notifier: WebhookNotifier) -> Self {
+        self.notifier = Some(Arc::new(notifier));
+        self
+    }
+
+    /// Replace the sink receiving progress notifications
+    ///
+    /// Used by transport integrations that forward `notifications/progress`
+    /// messages to the client.
+    pub fn with_progress_sink(mut self, sink: Arc<dyn ProgressSink>) -> Self {
+        self.progress_sink = sink;
+        self
+    }
+
+    /// Enable elicitation for clients that support it
+    pub fn with_elicitor(mut self, elicitor: Arc<dyn Elicitor>) -> Self {
+        self.elicitor = Some(elicitor);
+        self
+    }
+
+    /// Run a tool call with its request context installed
+    ///
+    /// Registers the request so that [`cancel_request`](Self::cancel_request) can
+    /// abort it, and makes the progress token available to the tool.
+    pub async fn with_request<F: Future>(
+        &self,
+        request_id: RequestId,
+        progress_token: Option<ProgressToken>,
+        future: F,
+    ) -> F::Output {
+        let ctx = RequestContext {
+            request_id: Some(request_id.clone()),
+            progress_token,
+            cancellation: self.cancellations.register(&request_id),
+        };
+
+        let output = ctx.scope(future).await;
+        self.cancellations.finish(&request_id);
+        output
+    }
+
+    /// Run a tool call received by a transport
+    ///
+    /// Runs it with its request context (see [`with_request`](Self::with_request))
+    /// and records it in the audit log when `JOBSUCHE_AUDIT_LOG` is set.
+    pub async fn call_tool_request(
+        &self,
+        request_id: RequestId,
+        progress_token: Option<ProgressToken>,
+        request: CallToolRequestParam,
+    ) -> Result<CallToolResult, pulseengine_mcp_protocol::Error> {
+        let call = self.audit.as_ref().map(|_| ToolCall::start(&request));
+        let result = self
+            .with_request(
+                request_id,
+                progress_token,
+                McpBackend::call_tool(self, request),
+            )
+            .await
+            .map_err(Into::into);
+        if let (Some(audit), Some(call)) = (&self.audit, call) {
+            audit.record(call.finish(&result));
+        }
+        result
+    }
+
+    /// Handle an MCP `notifications/cancelled` message
+    ///
+    /// Returns `false` if no request with this ID is in flight.
+    pub fn cancel_request(&self, request_id: &RequestId) -> bool {
+        let cancelled = self.cancellations.cancel(request_id);
+        if cancelled {
+            info!("Request {} cancelled by client", request_id);
+        }
+        cancelled
+    }
+
     /// Get server uptime in seconds
     fn get_uptime_seconds(&self) -> u64 {
         self.start_time.elapsed().as_secs()
     }
 
+    /// Search the Jobsuche API, counting the call in the quota usage
+    async fn search_api(&self, options: SearchOptions) -> anyhow::Result<JobSearchResponse> {
+        self.quota.record(UpstreamCall::Search);
+        let start = Instant::now();
+        let result = self.client.search().list(options).await;
+        let duration_ms = start.elapsed().as_millis() as u64;
+        match result {
+            Ok(response) => {
+                let refnrs = refnr_list(
+                    response
+                        .stellenangebote
+                        .iter()
+                        .map(|job| job.refnr.as_str()),
+                );
+                info!(
+                    target: UPSTREAM_TARGET,
+                    endpoint = "search",
+                    status = "ok",
+                    duration_ms,
+                    refnrs = %refnrs,
+                    "Jobsuche API search returned {} jobs",
+                    response.stellenangebote.len()
+                );
+                Ok(response)
+            }
+            Err(e) => {
+                warn!(
+                    target: UPSTREAM_TARGET,
+                    endpoint = "search",
+                    status = "error",
+                    duration_ms,
+                    "Jobsuche API search failed: {}",
+                    e
+                );
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Fetch job details from the Jobsuche API, counting the call in the quota usage
+    async fn job_details_api(&self, reference_number: &str) -> anyhow::Result<JobDetails> {
+        self.quota.record(UpstreamCall::JobDetails);
+        let start = Instant::now();
+        let result = self.client.job_details(reference_number).await;
+        let duration_ms = start.elapsed().as_millis() as u64;
+        match result {
+            Ok(details) => {
+                info!(
+                    target: UPSTREAM_TARGET,
+                    endpoint = "job_details",
+                    status = "ok",
+                    duration_ms,
+                    refnrs = reference_number,
+                    "Jobsuche API returned details of {}",
+                    reference_number
+                );
+                Ok(details)
+            }
+            Err(e) => {
+                warn!(
+                    target: UPSTREAM_TARGET,
+                    endpoint = "job_details",
+                    status = "error",
+                    duration_ms,
+                    refnrs = reference_number,
+                    "Jobsuche API details of {} failed: {}",
+                    reference_number,
+                    e
+                );
+                Err(e.into())
+            }
+        }
+    }
+
     /// Convert employment type string to Arbeitszeit enum
     fn parse_employment_type(emp_type: &str) -> Option<Arbeitszeit> {
         match emp_type.to_lowercase().as_str() {
@@ -427,55 +1513,179 @@ impl JobsucheMcpServer {
             _ => None,
         }
     }
-}
 
-/// MCP tools implementation
-#[mcp_tools]
-impl JobsucheMcpServer {
-    /// Search for jobs in Germany using the Federal Employment Agency database
-    ///
-    /// This tool allows searching for jobs with various filters including location,
-    /// job title, employment type, and more. Results include job summaries with
-    /// reference numbers that can be used to get detailed information.
+    /// Execute a job search against the API
     ///
-    /// # Examples
-    /// - Search for software jobs in Berlin: `{"job_title": "Software Engineer", "location": "Berlin"}`
-    /// - Recent jobs in München: `{"location": "München", "published_since_days": 7}`
-    /// - Full-time jobs nationwide: `{"employment_type": ["fulltime"]}`
-    #[instrument(skip(self))]
-    pub async fn search_jobs(&self, params: SearchJobsParams) -> anyhow::Result<SearchJobsResult> {
+    /// Shared by search_jobs and the bulk tools. Does not mark jobs as seen or
+    /// record the search; see execute_user_search.
+    async fn execute_search(&self, params: SearchJobsParams) -> anyhow::Result<SearchJobsResult> {
         info!("Searching jobs with params: {:?}", params);
         let start = Instant::now();
 
-        let mut search_opts = SearchOptions::builder();
+        // Pagination
+        let page_size = params
+            .page_size
+            .unwrap_or(self.config.default_page_size)
+            .min(self.config.max_page_size);
+
+        let options = self.search_options(&params, page_size, params.page);
+        let response: JobSearchResponse = self.search_api(options).await?;
+        let mut jobs = Self::summarize_jobs(&response);
+
+        // Filter before backfilling so hide_seen refills the page to its size
+        let employer_filter = self.employer_filter();
+        let mut filtered = FilteredCounts::default();
+        Self::post_filter(&params, &employer_filter, &mut jobs, &mut filtered);
+
+        let hidden_seen_count = if params.hide_seen.unwrap_or(false) {
+            self.backfill_unseen(
+                &params,
+                page_size,
+                response.stellenangebote.len(),
+                &mut jobs,
+                |more| Self::post_filter(&params, &employer_filter, more, &mut filtered),
+            )
+            .await?
+        } else {
+            0
+        };
+        let duplicates_collapsed = if params.collapse_duplicates.unwrap_or(false) {
+            let (representatives, collapsed) = collapse_duplicates(jobs);
+            jobs = representatives;
+            collapsed
+        } else {
+            0
+        };
+        params.sort_by.unwrap_or_default().sort(&mut jobs);
+
+        let duration = start.elapsed();
+        info!(
+            "Search completed: {} jobs found in {:?}",
+            jobs.len(),
+            duration
+        );
+
+        let result = SearchJobsResult {
+            request_id: String::new(),
+            total_results: response.max_ergebnisse,
+            current_page: response.page,
+            page_size: response.size,
+            jobs_count: jobs.len(),
+            jobs,
+            hidden_seen_count,
+            duplicates_collapsed,
+            employer_filtered_count: filtered.employer,
+            location_excluded_count: filtered.location,
+            search_duration_ms: duration.as_millis() as u64,
+            effective_query: effective_query(&params, &self.config),
+            relaxations: Vec::new(),
+        };
+
+        Ok(result)
+    }
 
-        // Build search query combining job_title, employer, and branch
-        let mut search_terms = Vec::new();
+    /// Execute a job search the user sees and remember it
+    ///
+    /// Marks the returned jobs as seen and adds the search to the history.
+    /// Tools that only process results internally call execute_search.
+    async fn execute_user_search(
+        &self,
+        params: SearchJobsParams,
+    ) -> anyhow::Result<SearchJobsResult> {
+        let result = self.execute_search(params.clone()).await?;
+        self.record_seen(&result.jobs);
+        self.record_history(params, &result);
+        Ok(result)
+    }
 
-        if let Some(ref title) = params.job_title {
-            search_terms.push(title.clone());
+    /// Apply the filters the API does not support to a page of jobs
+    fn post_filter(
+        params: &SearchJobsParams,
+        employer_filter: &EmployerFilter,
+        jobs: &mut Vec<JobSummary>,
+        filtered: &mut FilteredCounts,
+    ) {
+        if params.english_postings_only.unwrap_or(false) {
+            jobs.retain(|job| is_english_title(&job.title));
+        }
+        if let Some(freshness) = params.freshness {
+            jobs.retain(|job| freshness.keeps(job.published_days_ago));
+        }
+        let unfiltered_count = jobs.len();
+        jobs.retain(|job| employer_filter.allows(&job.employer));
+        filtered.employer += unfiltered_count - jobs.len();
+        let excluded_locations = params.exclude_locations.as_deref().unwrap_or_default();
+        let unexcluded_count = jobs.len();
+        jobs.retain(|job| !is_excluded_location(&job.location, excluded_locations));
+        if let Some(ref country) = params.country {
+            jobs.retain(|job| in_country(job.country.as_deref(), country));
         }
+        if !params.include_abroad.unwrap_or(true) {
+            jobs.retain(|job| job.country.is_none());
+        }
+        filtered.location += unexcluded_count - jobs.len();
+    }
 
-        if let Some(ref employer) = params.employer {
-            search_terms.push(employer.clone());
+    /// Count the results of relaxed variants of a search that found nothing
+    ///
+    /// Returns nothing if the search has results. Probing is best effort: a
+    /// failed count leaves the variant out.
+    async fn relax_empty_search(
+        &self,
+        params: &SearchJobsParams,
+        result: &SearchJobsResult,
+    ) -> Vec<SearchRelaxation> {
+        if !result.jobs.is_empty() || result.total_results.unwrap_or(0) > 0 {
+            return Vec::new();
         }
 
-        if let Some(ref branch) = params.branch {
-            search_terms.push(branch.clone());
+        let mut relaxations = Vec::new();
+        for (relaxation, search) in relaxed_searches(params) {
+            let options = self.search_options(&search, 1, None);
+            match self.count_jobs(options).await {
+                Ok(total_results) => relaxations.push(SearchRelaxation {
+                    relaxation,
+                    total_results,
+                    search,
+                }),
+                Err(e) => warn!("Failed to probe '{}': {:#}", relaxation, e),
+            }
         }
+        info!(
+            "Search found nothing; {} of {} relaxations would find jobs",
+            relaxations.iter().filter(|r| r.total_results > 0).count(),
+            relaxations.len()
+        );
+        relaxations
+    }
 
-        if !search_terms.is_empty() {
-            let combined_query = search_terms.join(" ");
-            search_opts.was(&combined_query);
+    /// Build the API search options for one page
+    fn search_options(
+        &self,
+        params: &SearchJobsParams,
+        page_size: u64,
+        page: Option<u64>,
+    ) -> SearchOptions {
+        let mut search_opts = SearchOptions::builder();
+
+        // Search query combining job_title, employer, and branch, normalized
+        let query = effective_query(params, &self.config);
+        info!(
+            "Effective query: keywords = {:?}, location = {:?}, radius = {:?}, nationwide = {}",
+            query.keywords, query.location, query.radius_km, query.nationwide
+        );
+
+        if let Some(ref keywords) = query.keywords {
+            search_opts.was(keywords);
         }
 
         // Location
-        if let Some(ref location) = params.location {
+        if let Some(ref location) = query.location {
             search_opts.wo(location);
         }
 
         // Radius
-        if let Some(radius) = params.radius_km {
+        if let Some(radius) = query.radius_km {
             search_opts.umkreis(radius);
         }
 
@@ -491,83 +1701,132 @@ impl JobsucheMcpServer {
             }
         }
 
-        // Published since
-        if let Some(days) = params.published_since_days {
+        // Published since, from freshness if given
+        let published_since_days = params
+            .freshness
+            .map(Freshness::published_since_days)
+            .or(params.published_since_days);
+        if let Some(days) = published_since_days {
             search_opts.veroeffentlichtseit(days);
         }
 
-        // Pagination
-        let page_size = params
-            .page_size
-            .unwrap_or(self.config.default_page_size)
-            .min(self.config.max_page_size);
-
         search_opts.size(page_size);
 
-        if let Some(page) = params.page {
+        if let Some(page) = page {
             search_opts.page(page);
         }
 
-        let options = search_opts.build();
-        let response: JobSearchResponse = self.client.search().list(options).await?;
+        search_opts.build()
+    }
 
-        let jobs: Vec<JobSummary> = response
+    /// Convert API listings into job summaries
+    fn summarize_jobs(response: &JobSearchResponse) -> Vec<JobSummary> {
+        response
             .stellenangebote
             .iter()
             .map(|job| {
-                let location = format!(
-                    "{}{}",
+                let arbeitsort = serde_json::to_value(&job.arbeitsort).unwrap_or_default();
+                let country = country_in(&arbeitsort);
+                let location = format_listing(
                     job.arbeitsort.ort.as_deref().unwrap_or(""),
-                    job.arbeitsort
-                        .plz
-                        .as_ref()
-                        .map(|plz| format!(" ({})", plz))
-                        .unwrap_or_default()
+                    job.arbeitsort.plz.as_deref(),
+                    country.as_deref(),
                 );
 
+                let (published_date, published_date_raw) =
+                    normalize_date(job.aktuelle_veroeffentlichungsdatum.as_deref());
+                let published_days_ago = published_date
+                    .as_deref()
+                    .and_then(|date| days_since(date, today()));
+
                 JobSummary {
                     reference_number: job.refnr.clone(),
                     title: job.titel.clone().unwrap_or_else(|| job.beruf.clone()),
                     employer: job.arbeitgeber.clone(),
                     location,
-                    published_date: job.aktuelle_veroeffentlichungsdatum.clone(),
+                    distance_km: job
+                        .arbeitsort
+                        .entfernung
+                        .as_deref()
+                        .and_then(Self::parse_distance_km),
+                    coordinates: coordinates_in(&arbeitsort),
+                    country,
+                    published_date,
+                    published_date_raw,
+                    published_days_ago,
+                    published_ago: published_days_ago.map(relative_age),
                     external_url: job.externe_url.clone(),
+                    web_url: web_url(&job.refnr),
+                    duplicates: Vec::new(),
                 }
             })
-            .collect();
+            .collect()
+    }
 
-        let duration = start.elapsed();
-        info!(
-            "Search completed: {} jobs found in {:?}",
-            jobs.len(),
-            duration
-        );
+    /// Parse the API's distance from the search location ("12", "12.5 km")
+    fn parse_distance_km(entfernung: &str) -> Option<f64> {
+        let number = entfernung.trim().trim_end_matches("km").trim();
+        number
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok()
+            .filter(|km| km.is_finite() && *km >= 0.0)
+    }
 
-        Ok(SearchJobsResult {
-            total_results: response.max_ergebnisse,
-            current_page: response.page,
-            page_size: response.size,
-            jobs_count: jobs.len(),
-            jobs,
-            search_duration_ms: duration.as_millis() as u64,
-        })
+    /// Remove already seen jobs and refill the page from the following API pages
+    ///
+    /// `last_page_len` is the number of listings on the first page, `filter`
+    /// applies the post-filters to each further page. Returns the number of
+    /// seen jobs that were skipped.
+    async fn backfill_unseen(
+        &self,
+        params: &SearchJobsParams,
+        page_size: u64,
+        mut last_page_len: usize,
+        jobs: &mut Vec<JobSummary>,
+        mut filter: impl FnMut(&mut Vec<JobSummary>),
+    ) -> anyhow::Result<usize> {
+        let seen_refnrs = self.storage.keys(SEEN_JOBS);
+        let cancellation = RequestContext::current().cancellation;
+        let limit = page_size as usize;
+
+        let mut hidden = seen::retain_unseen(jobs, &seen_refnrs);
+        let mut page = params.page.unwrap_or(1);
+
+        for _ in 0..seen::MAX_BACKFILL_PAGES {
+            // A short page means there are no more results
+            if jobs.len() >= limit || last_page_len < limit {
+                break;
+            }
+
+            page += 1;
+            let options = self.search_options(params, page_size, Some(page));
+            let Some(response) = run_cancellable(&cancellation, self.search_api(options)).await
+            else {
+                break;
+            };
+
+            let mut more = Self::summarize_jobs(&response?);
+            last_page_len = more.len();
+            filter(&mut more);
+            hidden += seen::retain_unseen(&mut more, &seen_refnrs);
+            append_unique(jobs, more, limit);
+        }
+
+        if hidden > 0 {
+            info!("Hid {} already seen jobs", hidden);
+        }
+        Ok(hidden)
     }
 
-    /// Get detailed information about a specific job posting
+    /// Fetch job details from the API and convert them
     ///
-    /// Retrieves comprehensive information about a job including the full description,
-    /// requirements, application instructions, and contact details.
-    ///
-    /// # Examples
-    /// - Get job details: `{"reference_number": "10001-1234567890-S"}`
-    #[instrument(skip(self))]
-    pub async fn get_job_details(
+    /// Does not attach notes or archive the result; see get_job_details.
+    async fn fetch_job_details(
         &self,
-        params: GetJobDetailsParams,
+        reference_number: &str,
     ) -> anyhow::Result<GetJobDetailsResult> {
-        info!("Getting job details for: {}", params.reference_number);
-
-        let details: JobDetails = self.client.job_details(&params.reference_number).await?;
+        let details: JobDetails = self.job_details_api(reference_number).await?;
 
         // Serialize to JSON for raw_data field
         let raw_data = serde_json::to_value(&details)?;
@@ -586,265 +1845,4266 @@ impl JobsucheMcpServer {
                 })
         });
 
-        // Format date ranges as strings
-        let entry_period = details.eintrittszeitraum.as_ref().map(|dr| {
-            match (&dr.von, &dr.bis) {
-                (Some(von), Some(bis)) => format!("{} - {}", von, bis),
-                (Some(von), None) => format!("ab {}", von),
-                (None, Some(bis)) => format!("bis {}", bis),
-                (None, None) => String::new(),
-            }
-        });
+        // Normalize dates to ISO 8601, keeping unparseable values as raw
+        let (first_published, first_published_raw) =
+            normalize_date(details.erste_veroeffentlichungsdatum.as_deref());
+        let (entry_period, entry_period_raw) = details
+            .eintrittszeitraum
+            .as_ref()
+            .map_or((None, None), |dr| {
+                normalize_period(dr.von.as_deref(), dr.bis.as_deref())
+            });
+        let (publication_period, publication_period_raw) = details
+            .veroeffentlichungszeitraum
+            .as_ref()
+            .map_or((None, None), |dr| {
+                normalize_period(dr.von.as_deref(), dr.bis.as_deref())
+            });
+        let published_days_ago = publication_period
+            .as_deref()
+            .and_then(period_start)
+            .or(first_published.as_deref())
+            .and_then(|date| days_since(date, today()));
+
+        let salary_parsed = details.verguetung.as_deref().and_then(parse_salary);
+        let external_url = self.known_external_url(reference_number);
+
+        // Skills and language are detected on plain text so markup never
+        // splits a term
+        let plain_description = details
+            .stellenbeschreibung
+            .as_deref()
+            .map(|text| clean_description(text, DescriptionFormat::Text))
+            .unwrap_or_default();
+        let plain_text = format!(
+            "{}\n{}",
+            details.titel.as_deref().unwrap_or_default(),
+            plain_description
+        );
+
+        let result = GetJobDetailsResult {
+            request_id: String::new(),
+            reference_number: reference_number.to_string(),
+            title: details.titel,
+            description: details
+                .stellenbeschreibung
+                .map(|text| clean_description(&text, self.config.description_format)),
+            description_language: detect_language(&plain_description).map(str::to_string),
+            employer: details.arbeitgeber,
+            location: location_str,
+            employment_type: details
+                .arbeitszeit_vollzeit
+                .map(|vz| if vz { "Vollzeit" } else { "Teilzeit" }.to_string()),
+            contract_type: None, // Not available in API v0.3.0
+            start_date: entry_period
+                .as_deref()
+                .and_then(period_start)
+                .map(str::to_string),
+            application_deadline: extract_deadline(&plain_description, today()),
+            contact_info: extract_contact_info(&plain_description),
+            application_domain: external_url.as_deref().and_then(url_domain),
+            external_url,
+            resolved_external_url: None,
+            identifier_type: None,
+            web_url: web_url(reference_number),
+            employer_profile_url: None, // Not available in API v0.3.0
+            partner_url: details.allianzpartner_url,
+            salary: details.verguetung,
+            salary_parsed,
+            salary_estimate: None,
+            extracted_skills: extract_skills(&plain_text),
+            language_requirements: extract_language_requirements(&plain_text),
+            mentions_visa_sponsorship: mentions_visa_sponsorship(&plain_description),
+            mentions_relocation: mentions_relocation(&plain_description),
+            contract_duration: details.vertragsdauer,
+            takeover_opportunity: None, // Not available in API v0.3.0
+            job_type: details.stellenangebots_art,
+            open_positions: None,       // Not available in API v0.3.0
+            company_size: None,         // Not available in API v0.3.0
+            employer_description: None, // Not available in API v0.3.0
+            branch: None,               // Not available in API v0.3.0
+            published_date: None,       // Not available in API v0.3.0
+            first_published,
+            first_published_raw,
+            published_days_ago,
+            published_ago: published_days_ago.map(relative_age),
+            only_for_disabled: details.nur_fuer_schwerbehinderte,
+            fulltime: details.arbeitszeit_vollzeit,
+            entry_period,
+            entry_period_raw,
+            publication_period,
+            publication_period_raw,
+            is_minor_employment: details.ist_geringfuegige_beschaeftigung,
+            is_temp_agency: details.ist_arbeitnehmer_ueberlassung,
+            is_private_agency: details.ist_private_arbeitsvermittlung,
+            career_changer_suitable: details.quereinstieg_geeignet,
+            cipher_number: details.chiffrenummer,
+            raw_data,
+            notes: Vec::new(),
+            profile_match: None,
+        };
+
+        Ok(result)
+    }
+
+    /// The stored user profile
+    fn user_profile(&self) -> anyhow::Result<UserProfile> {
+        self.storage
+            .get(USER_PROFILE, PROFILE_KEY)?
+            .context("No user profile stored; create one with set_user_profile")
+    }
+
+    /// Notes attached to a job, empty if there are none or they cannot be read
+    fn load_job_notes(&self, reference_number: &str) -> Vec<JobNote> {
+        match self.storage.get(JOB_NOTES, reference_number) {
+            Ok(notes) => notes.unwrap_or_default(),
+            Err(e) => {
+                warn!("Failed to load notes for {}: {:#}", reference_number, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Store a snapshot of fetched job details in the archive
+    ///
+    /// The snapshot leaves out the raw API data, which roughly doubles its
+    /// size. Written on the blocking thread pool, since stores write to disk.
+    async fn archive_job(&self, details: &GetJobDetailsResult) {
+        let archived = ArchivedJob {
+            archived_at: now_rfc3339(),
+            details: GetJobDetailsResult {
+                request_id: String::new(),
+                notes: Vec::new(),
+                raw_data: serde_json::Value::Null,
+                ..details.clone()
+            },
+        };
+
+        // Archiving is best effort and must not fail the request
+        let storage = self.storage.clone();
+        let reference_number = details.reference_number.clone();
+        let stored = tokio::task::spawn_blocking(move || {
+            storage.put(JOB_ARCHIVE, &reference_number, &archived)
+        })
+        .await;
+        match stored {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("Failed to archive {}: {:#}", details.reference_number, e),
+            Err(e) => warn!("Failed to archive {}: {}", details.reference_number, e),
+        }
+    }
+
+    /// Add fetched job details to the full-text index
+    async fn index_job(&self, details: &GetJobDetailsResult) {
+        // Indexing is best effort and must not fail the request
+        let job_index = self.job_index.clone();
+        let details = details.clone();
+        let indexed = tokio::task::spawn_blocking(move || {
+            job_index
+                .index(&details, &now_rfc3339())
+                .with_context(|| format!("Failed to index {}", details.reference_number))
+        })
+        .await;
+        match indexed {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("{:#}", e),
+            Err(e) => warn!("Failed to index job details: {}", e),
+        }
+    }
+
+    /// Regional median pay for an occupation from the Entgeltatlas
+    ///
+    /// Uses the federal state of the first work location. Estimates are
+    /// optional extras, so failures are logged and leave the estimate empty.
+    async fn estimate_salary(
+        &self,
+        details: &GetJobDetailsResult,
+        occupation_code: &str,
+    ) -> Option<SalaryEstimate> {
+        let federal_state = details
+            .raw_data
+            .pointer("/arbeitsorte/0/adresse/region")
+            .and_then(|region| region.as_str());
+
+        let estimate = self.entgeltatlas.estimate(occupation_code, federal_state);
+        match estimate.await {
+            Ok(estimate) => estimate,
+            Err(e) => {
+                warn!(
+                    "Failed to estimate salary for {}: {:#}",
+                    details.reference_number, e
+                );
+                None
+            }
+        }
+    }
+
+    /// Coordinates of a place given as an address, if geocoding is enabled
+    ///
+    /// Geocoding is best effort: failures are logged and leave the place as
+    /// it is.
+    async fn geocode_place(&self, place: Place, query: Option<PlaceQuery>) -> Place {
+        let (Place::Address(_), Some(query)) = (&place, query) else {
+            return place;
+        };
+        self.geocode(&query).await.map_or(place, Place::Coordinates)
+    }
+
+    /// Coordinates of a place, if geocoding is enabled and finds it
+    ///
+    /// Failures are logged and leave the coordinates empty.
+    async fn geocode(&self, query: &PlaceQuery) -> Option<Coordinates> {
+        let geocoder = self.geocoder.as_ref()?;
+        match geocoder.geocode(&self.storage, query).await {
+            Ok(geocoded) => geocoded.coordinates,
+            Err(e) => {
+                warn!("Failed to geocode {}: {:#}", query.locality(), e);
+                None
+            }
+        }
+    }
+
+    /// Configured home location, geocoded if it is an address
+    async fn home_place(&self) -> Option<Place> {
+        let home = self.config.home_location.as_deref()?;
+        let query = Some(PlaceQuery::parse(home));
+        Some(self.geocode_place(Place::parse(home), query).await)
+    }
+
+    /// First work location of a job, geocoded if only its address is known
+    ///
+    /// Needs the raw data of the job.
+    async fn job_place(&self, details: &GetJobDetailsResult) -> Option<Place> {
+        let place = Place::of_job(&details.raw_data)?;
+        let query = PlaceQuery::of_job(&details.raw_data);
+        Some(self.geocode_place(place, query).await)
+    }
+
+    /// Travel time from the home location to the work location of a job
+    ///
+    /// Needs the raw data of the job. Commute times are optional extras, so
+    /// failures are logged and leave the time empty.
+    async fn commute_minutes(
+        &self,
+        details: &GetJobDetailsResult,
+        mode: CommuteMode,
+    ) -> Option<u32> {
+        let routing = self.routing.as_ref()?;
+        let home = self.home_place().await?;
+        let Some(place) = self.job_place(details).await else {
+            warn!(
+                "Job {} has no work location to route to",
+                details.reference_number
+            );
+            return None;
+        };
+        match routing.commute_minutes(&home, &place, mode).await {
+            Ok(minutes) => Some(minutes),
+            Err(e) => {
+                warn!(
+                    "Failed to compute the commute to {}: {:#}",
+                    details.reference_number, e
+                );
+                None
+            }
+        }
+    }
+
+    /// Send a watchlist alert to the webhook in the background
+    ///
+    /// Delivery (including retries) does not hold up the check; failures are
+    /// only logged, under the span (and request ID) of the check.
+    fn notify_watchlist(&self, alert: WatchlistAlert) {
+        let Some(notifier) = self.notifier.clone() else {
+            return;
+        };
+        tokio::spawn(
+            async move {
+                if let Err(e) = notifier.notify(&alert).await {
+                    warn!("Failed to notify webhook: {:#}", e);
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
+    /// Run all watchlists or saved searches and log the new postings
+    async fn run_scheduled(&self, source: ScheduledSource) {
+        let run_at = now_rfc3339();
+        let results = match source {
+            ScheduledSource::Watchlist => self.scheduled_watchlists(&run_at).await,
+            ScheduledSource::SavedSearch => self.scheduled_saved_searches(&run_at).await,
+        };
+
+        let new_jobs: usize = results.iter().map(|result| result.new_jobs.len()).sum();
+        info!("Scheduled run found {} new jobs", new_jobs);
+
+        let run = ScheduledRun {
+            run_at,
+            source,
+            results,
+        };
+        let record = |log: &mut ScheduledRunLog| log.record(run, MAX_SCHEDULED_RUNS);
+        if let Err(e) = self.storage.update(SCHEDULED_RUNS, RUNS_KEY, record) {
+            warn!("Failed to record scheduled run: {:#}", e);
+        }
+    }
+
+    /// Check every watchlist; postings found here are not reported again by check_watchlists
+    async fn scheduled_watchlists(&self, run_at: &str) -> Vec<ScheduledResult> {
+        let mut results = Vec::new();
+        let watchlists: Vec<Watchlist> = self.storage.list(WATCHLISTS);
+
+        for (idx, mut watchlist) in watchlists.into_iter().enumerate() {
+            // Small delay between searches to avoid rate limiting (except first)
+            if idx > 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            }
+
+            let outcome = match self.background_search(&watchlist.search).await {
+                Ok(jobs) => {
+                    let new_jobs = watchlist.absorb(jobs, run_at);
+                    name_key(&watchlist.name)
+                        .and_then(|key| self.storage.put(WATCHLISTS, &key, &watchlist))
+                        .map(|()| new_jobs)
+                }
+                Err(e) => Err(e),
+            };
+
+            if let Ok(ref new_jobs) = outcome {
+                if !new_jobs.is_empty() {
+                    self.notify_watchlist(WatchlistAlert {
+                        watchlist: watchlist.name.clone(),
+                        checked_at: run_at.to_string(),
+                        new_jobs: new_jobs.clone(),
+                    });
+                }
+            }
+            results.extend(ScheduledResult::from_outcome(watchlist.name, outcome));
+        }
+        results
+    }
+
+    /// Run every saved search and report postings missing from its last run
+    async fn scheduled_saved_searches(&self, run_at: &str) -> Vec<ScheduledResult> {
+        let mut results = Vec::new();
+        let saved_searches: Vec<SavedSearch> = self.storage.list(SAVED_SEARCHES);
+
+        // Forget the baselines of deleted saved searches
+        let names = self.storage.keys(SAVED_SEARCHES);
+        if let Err(e) = self
+            .storage
+            .retain(SCHEDULE_BASELINES, |key, _: &Watchlist| names.contains(key))
+        {
+            warn!("Failed to prune schedule baselines: {:#}", e);
+        }
+
+        for (idx, saved_search) in saved_searches.into_iter().enumerate() {
+            // Small delay between searches to avoid rate limiting (except first)
+            if idx > 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            }
+
+            let outcome = match self.background_search(&saved_search.search).await {
+                Ok(jobs) => self.absorb_baseline(&saved_search, jobs, run_at),
+                Err(e) => Err(e),
+            };
+            results.extend(ScheduledResult::from_outcome(saved_search.name, outcome));
+        }
+        results
+    }
+
+    /// Record the jobs of a scheduled saved search and return the ones not known before
+    ///
+    /// The first run, and the first run after the search has been changed, only
+    /// record a baseline.
+    fn absorb_baseline(
+        &self,
+        saved_search: &SavedSearch,
+        jobs: Vec<JobSummary>,
+        now: &str,
+    ) -> anyhow::Result<Vec<WatchlistJob>> {
+        let key = name_key(&saved_search.name)?;
+        let search = serde_json::to_value(&saved_search.search)?;
+
+        let (baseline, new_jobs) = match self.storage.get::<Watchlist>(SCHEDULE_BASELINES, &key)? {
+            Some(mut baseline) if serde_json::to_value(&baseline.search)? == search => {
+                let new_jobs = baseline.absorb(jobs, now);
+                (baseline, new_jobs)
+            }
+            _ => {
+                let baseline =
+                    Watchlist::new(&saved_search.name, saved_search.search.clone(), &jobs, now);
+                (baseline, Vec::new())
+            }
+        };
+        self.storage.put(SCHEDULE_BASELINES, &key, &baseline)?;
+        Ok(new_jobs)
+    }
+
+    /// Run a search without recording it in the history or as seen
+    ///
+    /// Used for background runs, whose results are not shown to anyone yet.
+    async fn background_search(
+        &self,
+        params: &SearchJobsParams,
+    ) -> anyhow::Result<Vec<JobSummary>> {
+        let page_size = params
+            .page_size
+            .unwrap_or(self.config.default_page_size)
+            .min(self.config.max_page_size);
+
+        let options = self.search_options(params, page_size, params.page);
+        let response: JobSearchResponse = self.search_api(options).await?;
+        Ok(Self::summarize_jobs(&response))
+    }
+
+    /// Insert or refresh the favorite for fetched job details
+    fn store_favorite(
+        &self,
+        details: &GetJobDetailsResult,
+        labels: Vec<String>,
+    ) -> anyhow::Result<SaveFavoriteResult> {
+        let now = now_rfc3339();
+        let existing: Option<Favorite> = self.storage.get(FAVORITES, &details.reference_number)?;
+        let updated = existing.is_some();
+
+        let favorite = match existing {
+            Some(favorite) => favorite.refresh(details, labels, &now),
+            None => Favorite::from_details(details, labels, &now),
+        };
+        self.storage
+            .put(FAVORITES, &details.reference_number, &favorite)?;
+
+        Ok(SaveFavoriteResult {
+            request_id: String::new(),
+            favorite,
+            updated,
+        })
+    }
+
+    /// Append an executed search to the history log
+    fn record_history(&self, params: SearchJobsParams, result: &SearchJobsResult) {
+        let now = now_rfc3339();
+        let recorded = self.storage.update(
+            SEARCH_HISTORY,
+            HISTORY_KEY,
+            |history: &mut SearchHistory| history.record(params, result, &now, MAX_HISTORY_ENTRIES),
+        );
+
+        // The history is best effort and must not fail the search
+        if let Err(e) = recorded {
+            warn!("Failed to record search history: {:#}", e);
+        }
+    }
+
+    /// External URL of a job from the search result it appeared in
+    ///
+    /// Job details do not carry the URL, so it is taken from the seen-jobs
+    /// record written when the job was first returned by a search.
+    fn known_external_url(&self, reference_number: &str) -> Option<String> {
+        match self.storage.get::<SeenJob>(SEEN_JOBS, reference_number) {
+            Ok(seen) => seen.and_then(|seen| seen.external_url),
+            Err(e) => {
+                warn!("Failed to look up seen job {}: {:#}", reference_number, e);
+                None
+            }
+        }
+    }
+
+    /// Follow the redirects of a job's external URL (best effort)
+    ///
+    /// Fails if the URL is unknown because no search has returned the job
+    /// (see [`Self::known_external_url`]).
+    async fn resolve_external_url(&self, details: &mut GetJobDetailsResult) -> anyhow::Result<()> {
+        let Some(ref url) = details.external_url else {
+            let seen: Option<SeenJob> = self.storage.get(SEEN_JOBS, &details.reference_number)?;
+            anyhow::ensure!(
+                seen.is_some(),
+                "The external URL of {} is unknown: job details do not include it, so it is \
+                 taken from search results. Run a search that returns the job first.",
+                details.reference_number
+            );
+            // The job has no external URL
+            return Ok(());
+        };
+        match self.url_resolver.resolve(url).await {
+            Ok(resolved) => {
+                details.application_domain = url_domain(&resolved);
+                details.resolved_external_url = Some(resolved);
+            }
+            Err(e) => warn!("Failed to resolve external URL {}: {:#}", url, e),
+        }
+        Ok(())
+    }
+
+    /// Current postings of an employer, up to `max_jobs`, with the total count
+    ///
+    /// The API matches employers loosely, so postings of other employers are
+    /// dropped.
+    async fn employer_postings(
+        &self,
+        employer: &str,
+        location: Option<&str>,
+        max_jobs: usize,
+    ) -> anyhow::Result<(Vec<JobSummary>, Option<u64>)> {
+        let page_size = self.config.max_page_size;
+        let mut jobs = Vec::new();
+        let mut total_jobs = None;
+        let mut page = 1;
+        while jobs.len() < max_jobs {
+            let options = employer_search_options(employer, location, page_size, page, None, None);
+            let response: JobSearchResponse = self.search_api(options).await?;
+            total_jobs = total_jobs.or(response.max_ergebnisse);
+
+            let listings = response.stellenangebote.len() as u64;
+            let matching = Self::summarize_jobs(&response)
+                .into_iter()
+                .filter(|job| matches_employer(job, employer))
+                .collect();
+            append_unique(&mut jobs, matching, max_jobs);
+            if listings < page_size {
+                break;
+            }
+            page += 1;
+        }
+        self.record_seen(&jobs);
+        if let Some(total_jobs) = total_jobs {
+            self.record_employer_count(employer, location, total_jobs);
+        }
+        Ok((jobs, total_jobs))
+    }
+
+    /// A chunk of the description of a job
+    async fn description_chunk(
+        &self,
+        reference_number: &str,
+        range: ChunkRange,
+    ) -> anyhow::Result<DescriptionChunk> {
+        let details = self
+            .job_details(GetJobDetailsParams {
+                reference_number: reference_number.to_string(),
+                occupation_code: None,
+                lang: None,
+                resolve_external_url: None,
+                profile_match: None,
+                detail_level: Some(DetailLevel::Full),
+                include_raw: Some(false),
+                fields: None,
+            })
+            .await?;
+        let description = details.description.as_deref().unwrap_or_default();
+        Ok(DescriptionChunk::new(
+            &details.reference_number,
+            details.title.clone(),
+            details.employer.clone(),
+            description,
+            range,
+        ))
+    }
+
+    /// Read a job resource (`jobsuche://jobs/{reference_number}`, optionally
+    /// with `?offset=..&length=..` or `?page=..`)
+    ///
+    /// Returns the requested chunk of the description; `next_uri` names the
+    /// following chunk.
+    pub async fn read_job_resource(&self, uri: &str) -> anyhow::Result<DescriptionChunk> {
+        let resource = parse_job_uri(uri)?;
+        self.description_chunk(&resource.reference_number, resource.range)
+            .await
+    }
+
+    /// Read a fixed resource (`jobsuche://about`)
+    ///
+    /// Returns the resource as JSON; job resources are read with
+    /// [`Self::read_job_resource`].
+    pub fn read_resource(&self, uri: &str) -> anyhow::Result<serde_json::Value> {
+        match uri.trim() {
+            ABOUT_URI => Ok(serde_json::to_value(BuildInfo::current())?),
+            _ => anyhow::bail!(
+                "Unknown resource URI '{}' (available: {})",
+                uri,
+                RESOURCES.join(", ")
+            ),
+        }
+    }
+
+    /// Employer patterns stored by update_employer_filter
+    fn stored_employer_filter(&self) -> anyhow::Result<EmployerFilter> {
+        Ok(self
+            .storage
+            .get(EMPLOYER_FILTER, EMPLOYER_FILTER_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Employer patterns from the configuration
+    fn configured_employer_filter(&self) -> EmployerFilter {
+        EmployerFilter::from_config(
+            &self.config.employer_blocklist,
+            &self.config.employer_allowlist,
+        )
+    }
+
+    /// Employer filter applied to search results: configured and stored patterns
+    fn employer_filter(&self) -> EmployerFilter {
+        let configured = self.configured_employer_filter();
+        match self.stored_employer_filter() {
+            Ok(stored) => configured.merged(&stored),
+            Err(e) => {
+                // Searching without the stored patterns beats failing the search
+                warn!("Failed to load the employer filter: {:#}", e);
+                configured
+            }
+        }
+    }
+
+    /// Add today's posting count of an employer to its hiring history
+    fn record_employer_count(&self, employer: &str, location: Option<&str>, total_jobs: u64) {
+        let recorded = self.storage.update(
+            EMPLOYER_HISTORY,
+            &monitor_key(employer, location),
+            |history: &mut EmployerHistory| history.record(employer, location, today(), total_jobs),
+        );
+
+        // The history is best effort and must not fail the lookup
+        if let Err(e) = recorded {
+            warn!("Failed to record hiring history of {}: {:#}", employer, e);
+        }
+    }
+
+    /// Total number of postings matching the search options
+    async fn count_jobs(&self, options: SearchOptions) -> anyhow::Result<u64> {
+        let response: JobSearchResponse = self.search_api(options).await?;
+        Ok(response.max_ergebnisse.unwrap_or(0))
+    }
+
+    /// Remember returned jobs so later searches can hide them
+    fn record_seen(&self, jobs: &[JobSummary]) {
+        let now = now_rfc3339();
+        let documents = jobs.iter().map(|job| {
+            (
+                job.reference_number.clone(),
+                SeenJob {
+                    first_seen: now.clone(),
+                    external_url: job.external_url.clone(),
+                },
+            )
+        });
+
+        // Tracking is best effort and must not fail the search
+        if let Err(e) = self.storage.insert_missing(SEEN_JOBS, documents) {
+            warn!("Failed to record seen jobs: {:#}", e);
+        }
+    }
+
+    /// Ask the user for a job title or location if the search has no constraints
+    ///
+    /// Only runs when enabled in the configuration and the client supports
+    /// elicitation. Declining keeps the search unchanged; cancelling aborts it.
+    async fn elicit_search_constraints(
+        &self,
+        mut params: SearchJobsParams,
+    ) -> anyhow::Result<SearchJobsParams> {
+        let underspecified = params.job_title.is_none()
+            && params.location.is_none()
+            && self.config.default_location.is_none()
+            && params.nationwide.is_none()
+            && params.employer.is_none()
+            && params.branch.is_none();
+
+        if !underspecified || !self.config.elicit_underspecified_searches {
+            return Ok(params);
+        }
+
+        let Some(elicitor) = self.elicitor.as_ref().filter(|e| e.is_available()) else {
+            return Ok(params);
+        };
+
+        info!("Search has no job title or location, asking the user for constraints");
+        let response = elicitor.elicit(search_constraints_request()).await?;
+
+        match response.action {
+            ElicitationAction::Accept => {
+                params.job_title = response.string_field("job_title");
+                params.location = response.string_field("location");
+            }
+            ElicitationAction::Decline => {
+                info!("User declined to narrow the search, searching nationwide");
+            }
+            ElicitationAction::Cancel => {
+                anyhow::bail!("Search cancelled by the user");
+            }
+        }
+
+        Ok(params)
+    }
+
+    /// Job details as returned by get_job_details, before field filtering
+    async fn job_details(
+        &self,
+        params: GetJobDetailsParams,
+    ) -> anyhow::Result<GetJobDetailsResult> {
+        info!("Getting job details for: {}", params.reference_number);
+        if let Some(ref occupation_code) = params.occupation_code {
+            validate_occupation_code(occupation_code)?;
+        }
+
+        let (reference_number, identifier_type) = resolve_job_id(&params.reference_number);
+        if identifier_type == IdentifierType::HashId {
+            info!("Resolved hashId to reference number {}", reference_number);
+        }
+        let mut result = self.fetch_job_details(&reference_number).await?;
+        result.notes = self.load_job_notes(&reference_number);
+        result.identifier_type = Some(identifier_type);
+
+        if params.resolve_external_url.unwrap_or(false) {
+            self.resolve_external_url(&mut result).await?;
+        }
+
+        if let Some(ref occupation_code) = params.occupation_code {
+            let states_amount = result
+                .salary_parsed
+                .as_ref()
+                .is_some_and(|salary| salary.min.is_some() || salary.max.is_some());
+            if !states_amount {
+                result.salary_estimate = self.estimate_salary(&result, occupation_code).await;
+            }
+        }
+
+        if self.config.archives_job_details() {
+            self.archive_job(&result).await;
+        }
+        self.index_job(&result).await;
+
+        if params.profile_match.unwrap_or(false) {
+            result.profile_match = Some(self.user_profile()?.match_job(&result));
+        }
+
+        localize_details(&mut result, params.lang.unwrap_or(self.config.locale));
+        let include_raw = params.include_raw.unwrap_or(self.config.include_raw_data);
+        apply_detail_level(
+            &mut result,
+            params.detail_level.unwrap_or_default(),
+            include_raw,
+        );
+        info!("Job details retrieved successfully");
+        Ok(result)
+    }
+}
+
+/// MCP tools implementation
+#[mcp_tools]
+impl JobsucheMcpServer {
+    /// Search for jobs in Germany using the Federal Employment Agency database
+    ///
+    /// This tool allows searching for jobs with various filters including location,
+    /// job title, employment type, and more. Results include job summaries with
+    /// reference numbers that can be used to get detailed information.
+    ///
+    /// Without job title, employer, branch and location the user is asked for a
+    /// constraint first (if the client supports elicitation).
+    ///
+    /// # Examples
+    /// - Search for software jobs in Berlin: `{"job_title": "Software Engineer", "location": "Berlin"}`
+    /// - Recent jobs in München: `{"location": "München", "published_since_days": 7}`
+    /// - Full-time jobs nationwide: `{"employment_type": ["fulltime"]}`
+    /// - Titles and employers only: `{"job_title": "Koch", "location": "Köln", "fields": {"include_fields": ["title", "employer"]}}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn search_jobs(&self, params: SearchJobsParams) -> anyhow::Result<serde_json::Value> {
+        let request_id = record_request_id();
+        validate_search_jobs(&params)?;
+        if let Some(ref fields) = params.fields {
+            fields.validate(&field_names::<JobSummary>())?;
+        }
+        let params = self.elicit_search_constraints(params).await?;
+        let mut result = self.execute_user_search(params.clone()).await?;
+        result.relaxations = self.relax_empty_search(&params, &result).await;
+        result.request_id = request_id;
+        project_jobs(&result, params.fields.as_ref())
+    }
+
+    /// Get detailed information about a specific job posting
+    ///
+    /// Retrieves comprehensive information about a job including the full description,
+    /// requirements, application instructions, and contact details. With an
+    /// occupation code, postings without a salary get a regional median pay
+    /// estimate from the Entgeltatlas.
+    ///
+    /// # Examples
+    /// - Get job details: `{"reference_number": "10001-1234567890-S"}`
+    /// - With salary estimate: `{"reference_number": "10001-1234567890-S", "occupation_code": "43104"}`
+    /// - English field values: `{"reference_number": "10001-1234567890-S", "lang": "en"}`
+    /// - Resolve application portal: `{"reference_number": "10001-1234567890-S", "resolve_external_url": true}`
+    /// - hashId from a web link: `{"reference_number": "MTAwMDEtMTIzNDU2Nzg5MC1T"}`
+    /// - Selected fields only: `{"reference_number": "10001-1234567890-S", "fields": {"include_fields": ["title", "salary", "description"]}}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_job_details(
+        &self,
+        params: GetJobDetailsParams,
+    ) -> anyhow::Result<serde_json::Value> {
+        let request_id = record_request_id();
+        validate_job_details(&params)?;
+        if let Some(ref fields) = params.fields {
+            fields.validate(&field_names::<GetJobDetailsResult>())?;
+        }
+        let fields = params.fields.clone();
+        let details = GetJobDetailsResult {
+            request_id,
+            ..self.job_details(params).await?
+        };
+        project(&details, fields.as_ref())
+    }
+
+    /// Search for jobs and automatically fetch details for top results
+    ///
+    /// This tool combines search_jobs and get_job_details into a single operation,
+    /// making it more efficient for AI workflows. It searches for jobs and automatically
+    /// fetches full details for the top results.
+    ///
+    /// # Examples
+    /// - Search with auto-details: `{"location": "Wuppertal", "employment_type": ["parttime"], "max_details": 5}`
+    /// - With field filtering: `{"employer": "BARMER", "location": "Wuppertal", "max_details": 3, "fields": {"include_fields": ["title", "salary", "description"]}}`
+    /// - Within 45 minutes by public transport: `{"job_title": "Pflegefachkraft", "location": "Wuppertal", "max_commute_minutes": 45, "commute_mode": "transit"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn search_jobs_with_details(
+        &self,
+        params: SearchJobsWithDetailsParams,
+    ) -> anyhow::Result<SearchJobsWithDetailsResult> {
+        let request_id = record_request_id();
+        info!("Searching jobs with automatic detail fetching");
+        validate_search_with_details(&params)?;
+        if let Some(ref fields) = params.fields {
+            fields.validate(&field_names::<GetJobDetailsResult>())?;
+        }
+        if params.profile_match.unwrap_or(false) {
+            // Fail before searching rather than on every job
+            self.user_profile()?;
+        }
+        let commute_mode = params
+            .commute_mode
+            .or(params.max_commute_minutes.map(|_| CommuteMode::default()));
+        if commute_mode.is_some() && self.routing.is_none() {
+            anyhow::bail!(
+                "Commute times need a routing service; set JOBSUCHE_ROUTING_PROVIDER and \
+                 JOBSUCHE_HOME_LOCATION"
+            );
+        }
+        let home = if params.sort_by_distance.unwrap_or(false) {
+            match self.home_place().await {
+                Some(Place::Coordinates(home)) => Some(home),
+                _ => anyhow::bail!(
+                    "Sorting by distance needs JOBSUCHE_HOME_LOCATION as \"lat,lon\", or as an \
+                     address with JOBSUCHE_GEOCODING enabled"
+                ),
+            }
+        } else {
+            None
+        };
+        let needs_location = commute_mode.is_some() || home.is_some();
+        let search_start = Instant::now();
+        let mut progress = ProgressReporter::new(self.progress_sink.clone());
+        let cancellation = RequestContext::current().cancellation;
+
+        // Convert to SearchJobsParams
+        let search_params = SearchJobsParams {
+            job_title: params.job_title,
+            location: params.location,
+            radius_km: params.radius_km,
+            nationwide: params.nationwide,
+            employment_type: params.employment_type,
+            contract_type: params.contract_type,
+            published_since_days: params.published_since_days,
+            freshness: params.freshness,
+            page_size: params.page_size,
+            page: params.page,
+            employer: params.employer,
+            branch: params.branch,
+            hide_seen: params.hide_seen,
+            english_postings_only: params.english_postings_only,
+            // Duplicates are collapsed on the details, comparing descriptions
+            collapse_duplicates: None,
+            exclude_locations: params.exclude_locations,
+            country: params.country,
+            include_abroad: params.include_abroad,
+            sort_by: None,
+            fields: None,
+        };
+
+        // Perform search
+        let Some(search_outcome) = run_cancellable(
+            &cancellation,
+            self.execute_user_search(search_params.clone()),
+        )
+        .await
+        else {
+            info!("Search cancelled before completion");
+            return Ok(SearchJobsWithDetailsResult {
+                request_id,
+                total_results: None,
+                current_page: None,
+                page_size: None,
+                jobs_count: 0,
+                jobs: Vec::new(),
+                search_duration_ms: search_start.elapsed().as_millis() as u64,
+                details_duration_ms: 0,
+                cancelled: true,
+                duplicates_collapsed: 0,
+                effective_query: effective_query(&search_params, &self.config),
+                relaxations: Vec::new(),
+            });
+        };
+        let search_result = search_outcome?;
+        let search_duration = search_start.elapsed();
+        let relaxations = self
+            .relax_empty_search(&search_params, &search_result)
+            .await;
+
+        // Determine how many details to fetch (conservative defaults to respect rate limits)
+        let max_details = params.max_details.unwrap_or(3).min(MAX_DETAILS);
+        let english_only = params.english_only.unwrap_or(false);
+        let english_postings_only = params.english_postings_only.unwrap_or(false);
+        let visa_sponsorship_only = params.visa_sponsorship_only.unwrap_or(false);
+        let relocation_only = params.relocation_only.unwrap_or(false);
+        let include_raw = params.include_raw.unwrap_or(self.config.include_raw_data);
+        let jobs_to_fetch = search_result
+            .jobs
+            .iter()
+            .take(max_details as usize)
+            .collect::<Vec<_>>();
+
+        info!("Fetching details for {} jobs", jobs_to_fetch.len());
+        progress.set_total(1 + jobs_to_fetch.len() as u64);
+        progress.advance("Search completed");
+        let details_start = Instant::now();
+
+        // Fetch details for each job with delay to respect rate limits
+        let mut jobs_with_details = Vec::new();
+        let mut job_locations = Vec::new();
+        let mut cancelled = false;
+        for (idx, job) in jobs_to_fetch.iter().enumerate() {
+            // Small delay between requests to avoid rate limiting (except first)
+            if idx > 0 {
+                let delay = tokio::time::sleep(std::time::Duration::from_millis(100));
+                if run_cancellable(&cancellation, delay).await.is_none() {
+                    cancelled = true;
+                    break;
+                }
+            }
+
+            // Commutes and distances use the work location in the raw data, so
+            // the details are trimmed to the requested level afterwards
+            let fetch = self.job_details(GetJobDetailsParams {
+                reference_number: job.reference_number.clone(),
+                occupation_code: None,
+                lang: params.lang,
+                resolve_external_url: None,
+                profile_match: params.profile_match,
+                detail_level: if needs_location {
+                    Some(DetailLevel::Full)
+                } else {
+                    params.detail_level
+                },
+                include_raw: if needs_location {
+                    Some(true)
+                } else {
+                    params.include_raw
+                },
+                fields: None,
+            });
+            let Some(fetch_result) = run_cancellable(&cancellation, fetch).await else {
+                cancelled = true;
+                break;
+            };
+
+            match fetch_result {
+                Ok(mut details) => {
+                    let language = details.description_language.as_deref();
+                    if (english_only && language != Some("en"))
+                        || (english_postings_only && !is_english_posting(&job.title, language))
+                    {
+                        info!("Skipping non-English job {}", job.reference_number);
+                    } else if (visa_sponsorship_only && !details.mentions_visa_sponsorship)
+                        || (relocation_only && !details.mentions_relocation)
+                    {
+                        info!(
+                            "Skipping job {} without visa or relocation support",
+                            job.reference_number
+                        );
+                    } else {
+                        let commute_minutes = match commute_mode {
+                            Some(mode) => self.commute_minutes(&details, mode).await,
+                            None => None,
+                        };
+                        let home_distance_km = match home {
+                            Some(home) => self.job_place(&details).await.and_then(|place| {
+                                let distance = home.distance_km(&place.coordinates().ok()?);
+                                Some((distance * 10.0).round() / 10.0)
+                            }),
+                            None => None,
+                        };
+                        if needs_location {
+                            apply_detail_level(
+                                &mut details,
+                                params.detail_level.unwrap_or_default(),
+                                include_raw,
+                            );
+                        }
+                        if params
+                            .max_commute_minutes
+                            .is_some_and(|max| commute_minutes.is_some_and(|minutes| minutes > max))
+                        {
+                            info!(
+                                "Skipping job {} with a longer commute",
+                                job.reference_number
+                            );
+                        } else {
+                            jobs_with_details.push(details);
+                            job_locations.push((commute_minutes, home_distance_km));
+                        }
+                    }
+                }
+                Err(e) => {
+                    info!(
+                        "Failed to fetch details for {}: {}",
+                        job.reference_number, e
+                    );
+                    // Continue with other jobs even if one fails
+                }
+            }
+
+            progress.advance(format!("Fetched details for {}", job.reference_number));
+        }
+
+        let details_duration = details_start.elapsed();
+
+        info!(
+            "Search completed: {} jobs found, {} details fetched{}",
+            search_result.total_results.unwrap_or(0),
+            jobs_with_details.len(),
+            if cancelled { " (cancelled)" } else { "" }
+        );
+
+        if home.is_some() {
+            // Nearest first; jobs without a known location last
+            let mut jobs: Vec<_> = jobs_with_details.into_iter().zip(job_locations).collect();
+            jobs.sort_by(|(_, (_, a)), (_, (_, b))| {
+                a.unwrap_or(f64::INFINITY)
+                    .total_cmp(&b.unwrap_or(f64::INFINITY))
+            });
+            (jobs_with_details, job_locations) = jobs.into_iter().unzip();
+        }
+
+        let clusters = if params.collapse_duplicates.unwrap_or(false) {
+            let postings: Vec<Posting> = jobs_with_details
+                .iter()
+                .map(|details| Posting {
+                    title: details.title.as_deref().unwrap_or_default(),
+                    location: details.location.as_deref().unwrap_or_default(),
+                    description: details.description.as_deref(),
+                })
+                .collect();
+            duplicate_clusters(&postings)
+        } else {
+            (0..jobs_with_details.len())
+                .map(|index| vec![index])
+                .collect()
+        };
+        let duplicates_collapsed = jobs_with_details.len() - clusters.len();
+
+        let mut jobs = Vec::with_capacity(clusters.len());
+        for cluster in clusters {
+            let mut job = project(&jobs_with_details[cluster[0]], params.fields.as_ref())?;
+            let (commute_minutes, home_distance_km) = job_locations[cluster[0]];
+            if let Some(object) = job.as_object_mut() {
+                if commute_mode.is_some() {
+                    object.insert(
+                        "commute_minutes".to_string(),
+                        serde_json::to_value(commute_minutes)?,
+                    );
+                }
+                if home.is_some() {
+                    object.insert(
+                        "home_distance_km".to_string(),
+                        serde_json::to_value(home_distance_km)?,
+                    );
+                }
+            }
+            if cluster.len() > 1 {
+                let duplicates: Vec<DuplicatePosting> = cluster[1..]
+                    .iter()
+                    .map(|&index| {
+                        let details = &jobs_with_details[index];
+                        DuplicatePosting {
+                            reference_number: details.reference_number.clone(),
+                            employer: details.employer.clone(),
+                            title: details.title.clone(),
+                        }
+                    })
+                    .collect();
+                if let Some(object) = job.as_object_mut() {
+                    object.insert("duplicates".to_string(), serde_json::to_value(duplicates)?);
+                }
+            }
+            jobs.push(job);
+        }
+        Ok(SearchJobsWithDetailsResult {
+            request_id,
+            total_results: search_result.total_results,
+            current_page: search_result.current_page,
+            page_size: search_result.page_size,
+            jobs_count: jobs.len(),
+            jobs,
+            search_duration_ms: search_duration.as_millis() as u64,
+            details_duration_ms: details_duration.as_millis() as u64,
+            cancelled,
+            duplicates_collapsed,
+            effective_query: search_result.effective_query.clone(),
+            relaxations,
+        })
+    }
+
+    /// Perform multiple job searches in a single operation
+    ///
+    /// This tool allows you to search for different types of jobs simultaneously,
+    /// making it perfect for comparing opportunities across employers, locations,
+    /// or job types. Each search can have different parameters and will return
+    /// results independently.
+    ///
+    /// # Examples
+    /// - Compare employers: `{"searches": [{"name": "BARMER", "employer": "BARMER", "location": "Wuppertal"}, {"name": "Siemens", "employer": "Siemens", "location": "Wuppertal"}], "max_details_per_search": 3}`
+    /// - Different job types: `{"searches": [{"name": "Sekretariat", "job_title": "Sekretärin"}, {"name": "Sport", "job_title": "Schwimm"}]}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn batch_search_jobs(
+        &self,
+        params: BatchSearchJobsParams,
+    ) -> anyhow::Result<BatchSearchJobsResult> {
+        let request_id = record_request_id();
+        let start = Instant::now();
+        validate_batch_search(&params)?;
+        self.quota.check_budget("batch_search_jobs")?;
+        let searches_count = params.searches.len().min(5); // Limit to 5 searches to respect rate limits
+        if let Some(ref fields) = params.fields {
+            fields.validate(&field_names::<GetJobDetailsResult>())?;
+        }
+
+        info!("Performing batch search with {} searches", searches_count);
+
+        let max_details = params.max_details_per_search.unwrap_or(2).min(5);
+        let mut results = Vec::new();
+
+        // Each search counts as one step plus one step per detail fetch
+        let steps_per_search = 1 + max_details;
+        let mut progress = ProgressReporter::new(self.progress_sink.clone());
+        progress.set_total(searches_count as u64 * steps_per_search);
+
+        let cancellation = RequestContext::current().cancellation;
+        let mut cancelled = false;
+
+        // Process each search
+        for (search_idx, search_item) in params.searches.iter().take(searches_count).enumerate() {
+            // Small delay between searches to avoid rate limiting (except first)
+            if search_idx > 0 {
+                let delay = tokio::time::sleep(std::time::Duration::from_millis(200));
+                if run_cancellable(&cancellation, delay).await.is_none() {
+                    cancelled = true;
+                    break;
+                }
+            }
+            info!("Processing search: {}", search_item.name);
+
+            // Convert to SearchJobsParams
+            let search_params = SearchJobsParams {
+                job_title: search_item.job_title.clone(),
+                location: search_item.location.clone(),
+                radius_km: search_item.radius_km,
+                nationwide: search_item.nationwide,
+                employment_type: search_item.employment_type.clone(),
+                contract_type: search_item.contract_type.clone(),
+                published_since_days: search_item.published_since_days,
+                freshness: search_item.freshness,
+                page_size: Some(max_details),
+                page: None,
+                employer: search_item.employer.clone(),
+                branch: search_item.branch.clone(),
+                hide_seen: None,
+                english_postings_only: None,
+                collapse_duplicates: None,
+                exclude_locations: None,
+                country: None,
+                include_abroad: None,
+                sort_by: None,
+                fields: None,
+            };
+
+            // Perform search
+            let Some(search_outcome) =
+                run_cancellable(&cancellation, self.execute_user_search(search_params)).await
+            else {
+                cancelled = true;
+                break;
+            };
+
+            let search_result = match search_outcome {
+                Ok(result) => result,
+                Err(e) => {
+                    // If search fails, add error result and continue
+                    results.push(BatchSearchItemResult {
+                        search_name: search_item.name.clone(),
+                        total_results: None,
+                        jobs_count: 0,
+                        jobs: Vec::new(),
+                        error: Some(format!("Search failed: {}", e)),
+                    });
+                    progress.report(
+                        (search_idx as u64 + 1) * steps_per_search,
+                        format!("Search '{}' failed", search_item.name),
+                    );
+                    continue;
+                }
+            };
+            progress.advance(format!("Search '{}' completed", search_item.name));
+
+            // Fetch details if requested (with delay to respect rate limits)
+            let mut jobs_with_details = Vec::new();
+            if max_details > 0 {
+                for (detail_idx, job) in search_result
+                    .jobs
+                    .iter()
+                    .take(max_details as usize)
+                    .enumerate()
+                {
+                    // Small delay between detail fetches (except first in this search)
+                    if detail_idx > 0 {
+                        let delay = tokio::time::sleep(std::time::Duration::from_millis(100));
+                        if run_cancellable(&cancellation, delay).await.is_none() {
+                            cancelled = true;
+                            break;
+                        }
+                    }
+
+                    let fetch = self.job_details(GetJobDetailsParams {
+                        reference_number: job.reference_number.clone(),
+                        occupation_code: None,
+                        lang: None,
+                        resolve_external_url: None,
+                        profile_match: None,
+                        detail_level: params.detail_level,
+                        include_raw: params.include_raw,
+                        fields: None,
+                    });
+                    let Some(fetch_result) = run_cancellable(&cancellation, fetch).await else {
+                        cancelled = true;
+                        break;
+                    };
+
+                    match fetch_result {
+                        Ok(details) => jobs_with_details.push(details),
+                        Err(e) => {
+                            info!(
+                                "Failed to fetch details for {} in search '{}': {}",
+                                job.reference_number, search_item.name, e
+                            );
+                            // Continue with other jobs even if one fails
+                        }
+                    }
+
+                    progress.advance(format!(
+                        "Fetched details for {} ('{}')",
+                        job.reference_number, search_item.name
+                    ));
+                }
+            }
+
+            // Searches with fewer results than max_details finish early
+            if !cancelled {
+                progress.report(
+                    (search_idx as u64 + 1) * steps_per_search,
+                    format!("Search '{}' finished", search_item.name),
+                );
+            }
+
+            // Keep the details fetched so far for the search that was interrupted
+            let jobs = jobs_with_details
+                .iter()
+                .map(|details| project(details, params.fields.as_ref()))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            results.push(BatchSearchItemResult {
+                search_name: search_item.name.clone(),
+                total_results: search_result.total_results,
+                jobs_count: jobs.len(),
+                jobs,
+                error: None,
+            });
+
+            if cancelled {
+                break;
+            }
+        }
+
+        let duration = start.elapsed();
+        info!(
+            "Batch search completed: {} searches in {:?}{}",
+            results.len(),
+            duration,
+            if cancelled { " (cancelled)" } else { "" }
+        );
+
+        Ok(BatchSearchJobsResult {
+            request_id,
+            searches_count: results.len(),
+            results,
+            total_duration_ms: duration.as_millis() as u64,
+            cancelled,
+        })
+    }
+
+    /// Get all current postings of one employer
+    ///
+    /// Returns the employer's postings together with the number of postings per
+    /// location and job type and the share of fixed-term contracts, e.g. to
+    /// research an employer before applying.
+    ///
+    /// # Examples
+    /// - All postings: `{"employer": "BARMER"}`
+    /// - In one region: `{"employer": "Deutsche Bahn", "location": "Berlin", "max_jobs": 50}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_employer_jobs(
+        &self,
+        params: GetEmployerJobsParams,
+    ) -> anyhow::Result<GetEmployerJobsResult> {
+        let request_id = record_request_id();
+        let employer = params.employer.trim();
+        anyhow::ensure!(!employer.is_empty(), "Employer must not be empty");
+        let location = params.location.as_deref();
+        let max_jobs = params
+            .max_jobs
+            .unwrap_or(DEFAULT_EMPLOYER_JOBS)
+            .clamp(1, MAX_EMPLOYER_JOBS) as usize;
+        info!("Getting postings of employer {}", employer);
+        let (jobs, total_jobs) = self.employer_postings(employer, location, max_jobs).await?;
+
+        // The API counts all current postings, not just the returned ones
+        let mut jobs_by_type = BTreeMap::new();
+        for (job_type, name) in JOB_TYPES {
+            let options = employer_search_options(employer, location, 1, 1, Some(*job_type), None);
+            jobs_by_type.insert(name.to_string(), self.count_jobs(options).await?);
+        }
+        let mut contract_counts = Vec::new();
+        for contract_type in CONTRACT_TYPES {
+            let options =
+                employer_search_options(employer, location, 1, 1, None, Some(*contract_type));
+            contract_counts.push(self.count_jobs(options).await?);
+        }
+        let (fixed_term_jobs, permanent_jobs) = (contract_counts[0], contract_counts[1]);
+
+        Ok(GetEmployerJobsResult {
+            request_id,
+            employer: employer.to_string(),
+            total_jobs,
+            jobs_count: jobs.len(),
+            jobs_by_location: count_by_location(&jobs),
+            jobs,
+            jobs_by_type,
+            fixed_term_jobs,
+            permanent_jobs,
+            fixed_term_share: fixed_term_share(fixed_term_jobs, permanent_jobs),
+        })
+    }
+
+    /// Monitor the postings of several employers
+    ///
+    /// For each employer, returns the number of current postings, the postings
+    /// that appeared since the previous call and notable changes such as a
+    /// jump or drop in the posting count. The state is kept per employer and
+    /// location; the first call records the baseline and reports nothing as
+    /// new. Competitor hiring intelligence for recruiters in one call.
+    ///
+    /// # Examples
+    /// - `{"employers": ["BARMER", "Techniker Krankenkasse", "AOK"]}`
+    /// - `{"employers": ["Siemens", "Bosch"], "location": "München", "max_jobs_per_employer": 200}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn monitor_employers(
+        &self,
+        params: MonitorEmployersParams,
+    ) -> anyhow::Result<MonitorEmployersResult> {
+        let request_id = record_request_id();
+        anyhow::ensure!(
+            (1..=MAX_MONITORED_EMPLOYERS).contains(&params.employers.len()),
+            "employers must hold between 1 and {} names",
+            MAX_MONITORED_EMPLOYERS
+        );
+        anyhow::ensure!(
+            params
+                .employers
+                .iter()
+                .all(|employer| !employer.trim().is_empty()),
+            "Employer names must not be empty"
+        );
+        let location = params
+            .location
+            .as_deref()
+            .map(str::trim)
+            .filter(|location| !location.is_empty());
+        let max_jobs = params
+            .max_jobs_per_employer
+            .unwrap_or(DEFAULT_MONITORED_JOBS)
+            .clamp(1, MAX_MONITORED_JOBS) as usize;
+        self.quota.check_budget("monitor_employers")?;
+        info!("Monitoring {} employers", params.employers.len());
+
+        let mut progress = ProgressReporter::new(self.progress_sink.clone());
+        progress.set_total(params.employers.len() as u64);
+        let cancellation = RequestContext::current().cancellation;
+        let checked_at = now_rfc3339();
+        let mut employers = Vec::new();
+        let mut cancelled = false;
+
+        for (idx, employer) in params.employers.iter().enumerate() {
+            let employer = employer.trim();
+            // Small delay between employers to avoid rate limiting (except first)
+            if idx > 0 {
+                let delay = tokio::time::sleep(std::time::Duration::from_millis(200));
+                if run_cancellable(&cancellation, delay).await.is_none() {
+                    cancelled = true;
+                    break;
+                }
+            }
+
+            let Some(postings) = run_cancellable(
+                &cancellation,
+                self.employer_postings(employer, location, max_jobs),
+            )
+            .await
+            else {
+                cancelled = true;
+                break;
+            };
+
+            let entry = match postings {
+                Ok((jobs, total_jobs)) => {
+                    let key = monitor_key(employer, location);
+                    let previous: Option<EmployerMonitor> =
+                        self.storage.get(EMPLOYER_MONITORS, &key)?;
+                    let (monitor, entry) = match previous {
+                        Some(mut monitor) => {
+                            let previous_total_jobs = monitor.total_jobs;
+                            let previous_checked_at = monitor.last_checked_at.clone();
+                            let new_jobs = monitor.absorb(jobs, total_jobs, &checked_at);
+                            let entry = EmployerMonitorEntry {
+                                employer: employer.to_string(),
+                                total_jobs,
+                                previous_total_jobs,
+                                previous_checked_at: Some(previous_checked_at),
+                                first_check: false,
+                                new_jobs_count: new_jobs.len(),
+                                changes: notable_changes(
+                                    previous_total_jobs,
+                                    total_jobs,
+                                    new_jobs.len(),
+                                ),
+                                new_jobs,
+                                error: None,
+                            };
+                            (monitor, entry)
+                        }
+                        None => {
+                            let monitor = EmployerMonitor::new(
+                                employer,
+                                location,
+                                &jobs,
+                                total_jobs,
+                                &checked_at,
+                            );
+                            let entry = EmployerMonitorEntry {
+                                employer: employer.to_string(),
+                                total_jobs,
+                                previous_total_jobs: None,
+                                previous_checked_at: None,
+                                first_check: true,
+                                new_jobs_count: 0,
+                                new_jobs: Vec::new(),
+                                changes: vec![format!(
+                                    "baseline of {} postings recorded",
+                                    monitor.known_jobs.len()
+                                )],
+                                error: None,
+                            };
+                            (monitor, entry)
+                        }
+                    };
+                    self.storage.put(EMPLOYER_MONITORS, &key, &monitor)?;
+                    entry
+                }
+                Err(e) => EmployerMonitorEntry {
+                    employer: employer.to_string(),
+                    total_jobs: None,
+                    previous_total_jobs: None,
+                    previous_checked_at: None,
+                    first_check: false,
+                    new_jobs_count: 0,
+                    new_jobs: Vec::new(),
+                    changes: Vec::new(),
+                    error: Some(format!("Search failed: {}", e)),
+                },
+            };
+
+            progress.advance(format!("Employer '{}' checked", employer));
+            employers.push(entry);
+        }
+
+        let total_new_jobs = employers.iter().map(|entry| entry.new_jobs_count).sum();
+        info!("Employer monitoring found {} new jobs", total_new_jobs);
+
+        Ok(MonitorEmployersResult {
+            request_id,
+            checked_at,
+            employers_checked: employers.len(),
+            total_new_jobs,
+            employers,
+            cancelled,
+        })
+    }
+
+    /// Posting count history of an employer
+    ///
+    /// Returns the posting counts recorded by earlier get_employer_jobs and
+    /// monitor_employers calls as a daily or weekly time series, with the
+    /// change between points and the overall trend. Use it to spot hiring
+    /// freezes or surges; the history only covers days the employer was
+    /// looked up.
+    ///
+    /// # Examples
+    /// - `{"employer": "BARMER"}`
+    /// - `{"employer": "Siemens", "location": "München", "interval": "weekly", "days": 365}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn employer_hiring_history(
+        &self,
+        params: EmployerHiringHistoryParams,
+    ) -> anyhow::Result<EmployerHiringHistoryResult> {
+        let request_id = record_request_id();
+        anyhow::ensure!(
+            !params.employer.trim().is_empty(),
+            "Employer must not be empty"
+        );
+        let location = params
+            .location
+            .as_deref()
+            .map(str::trim)
+            .filter(|location| !location.is_empty());
+        let interval = params.interval.unwrap_or_default();
+        let days = params
+            .days
+            .unwrap_or(DEFAULT_HISTORY_DAYS)
+            .clamp(1, MAX_HISTORY_DAYS);
+        let key = monitor_key(&params.employer, location);
+        let history: EmployerHistory = self
+            .storage
+            .get(EMPLOYER_HISTORY, &key)?
+            .unwrap_or_default();
+
+        let since = today() - time::Duration::days(days as i64);
+        let observations = history
+            .counts
+            .keys()
+            .filter_map(|day| parse_date(day))
+            .filter(|day| *day >= since)
+            .count();
+        let series = hiring_series(&history.counts, interval, since);
+        info!(
+            "Hiring history of {}: {} observations in {} days",
+            params.employer.trim(),
+            observations,
+            days
+        );
+
+        Ok(EmployerHiringHistoryResult {
+            request_id,
+            employer: params.employer.trim().to_string(),
+            location: location.map(str::to_string),
+            interval,
+            days,
+            observations,
+            trend: hiring_trend(&series),
+            series,
+        })
+    }
+
+    /// Block or allow employers in search results
+    ///
+    /// Blocked employers (e.g. staffing firms) are dropped from the results of
+    /// all searches. Once the allowlist holds a pattern, only matching
+    /// employers are returned. Patterns match the employer name
+    /// case-insensitively anywhere in the name; `*` stands for any text in
+    /// between. The patterns are stored and apply together with those from
+    /// JOBSUCHE_EMPLOYER_BLOCKLIST and JOBSUCHE_EMPLOYER_ALLOWLIST.
+    ///
+    /// # Examples
+    /// - Block staffing firms: `{"block": ["Randstad", "Adecco", "zeitarbeit", "personal*service"]}`
+    /// - Only named companies: `{"allow": ["BARMER", "Techniker Krankenkasse"]}`
+    /// - Undo: `{"unblock": ["Adecco"]}` or `{"clear": true}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn update_employer_filter(
+        &self,
+        params: UpdateEmployerFilterParams,
+    ) -> anyhow::Result<UpdateEmployerFilterResult> {
+        let request_id = record_request_id();
+        params.validate()?;
+        let mut stored = self.stored_employer_filter()?;
+        let (added, removed) = stored.apply(&params);
+        stored.check_size()?;
+        self.storage
+            .put(EMPLOYER_FILTER, EMPLOYER_FILTER_KEY, &stored)?;
+        info!(
+            "Employer filter updated: {} patterns added, {} removed",
+            added, removed
+        );
+
+        Ok(UpdateEmployerFilterResult {
+            request_id,
+            added,
+            removed,
+            effective: self.configured_employer_filter().merged(&stored),
+            stored,
+        })
+    }
+
+    /// Show the employer blocklist and allowlist
+    ///
+    /// Lists the configured patterns, the ones stored by
+    /// update_employer_filter and the combination applied to search results.
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_employer_filter(
+        &self,
+        _params: GetEmployerFilterParams,
+    ) -> anyhow::Result<GetEmployerFilterResult> {
+        let request_id = record_request_id();
+        let configured = self.configured_employer_filter();
+        let stored = self.stored_employer_filter()?;
+        Ok(GetEmployerFilterResult {
+            request_id,
+            effective: configured.merged(&stored),
+            configured,
+            stored,
+        })
+    }
+
+    /// Read a long job description in chunks
+    ///
+    /// Returns `length` characters of the description starting at `offset`
+    /// (or the given page), with the total length and the offset of the next
+    /// chunk. Use it when a description is too long for one response; the
+    /// same chunks are available as `jobsuche://jobs/{reference_number}`
+    /// resources.
+    ///
+    /// # Examples
+    /// - `{"reference_number": "10001-1234567890-S"}`
+    /// - `{"reference_number": "10001-1234567890-S", "offset": 8000}`
+    /// - `{"reference_number": "10001-1234567890-S", "page": 2, "length": 4000}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn read_job_description(
+        &self,
+        params: ReadJobDescriptionParams,
+    ) -> anyhow::Result<DescriptionChunk> {
+        let request_id = record_request_id();
+        let range = ChunkRange::new(params.offset, params.length, params.page)?;
+        let chunk = self
+            .description_chunk(params.reference_number.trim(), range)
+            .await?;
+        info!(
+            "Read {} of {} description characters of {}",
+            chunk.length, chunk.total_chars, chunk.reference_number
+        );
+        Ok(DescriptionChunk {
+            request_id,
+            ..chunk
+        })
+    }
+
+    /// Show how many Jobsuche API calls the server made
+    ///
+    /// Counts calls per hour and day (UTC) since the server started, split by
+    /// searches and detail fetches. With JOBSUCHE_DAILY_API_BUDGET set, it
+    /// also tells how many calls are left today before batch tools
+    /// (batch_search_jobs, bulk_export_jobs, import_refnrs, check_watchlists,
+    /// monitor_employers, regional_overview) refuse to start.
+    ///
+    /// # Examples
+    /// - `{}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_quota_usage(
+        &self,
+        _params: GetQuotaUsageParams,
+    ) -> anyhow::Result<QuotaUsage> {
+        let request_id = record_request_id();
+        Ok(QuotaUsage {
+            request_id,
+            ..self.quota.usage()
+        })
+    }
+
+    /// Change the log level of the running server (admin tool)
+    ///
+    /// Replaces the tracing filter without a restart, e.g. to capture debug
+    /// logs of a failing Jobsuche API call. Takes a level or a filter in
+    /// RUST_LOG syntax and returns the previous one to restore afterwards.
+    /// Only available with `JOBSUCHE_ADMIN_TOOLS=true`.
+    ///
+    /// # Examples
+    /// - `{"level": "debug"}`
+    /// - Upstream calls only: `{"level": "info,jobsuche::upstream=debug"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn set_log_level(
+        &self,
+        params: SetLogLevelParams,
+    ) -> anyhow::Result<SetLogLevelResult> {
+        let request_id = record_request_id();
+        anyhow::ensure!(self.config.admin_tools, ADMIN_TOOLS_DISABLED);
+        let result = logging::set_filter(&params.level)?;
+        warn!(
+            "Log filter changed from '{}' to '{}'",
+            result.previous, result.current
+        );
+        Ok(SetLogLevelResult {
+            request_id,
+            ..result
+        })
+    }
+
+    /// Review the audit log of tool calls
+    ///
+    /// Lists the recorded tool calls, newest first, with time, tool,
+    /// parameters (personal data such as CV text, notes and skills redacted),
+    /// duration and outcome, to check what an agent did on the user's behalf.
+    /// Calls are only recorded with `JOBSUCHE_AUDIT_LOG=true`; the newest
+    /// 1000 are kept.
+    ///
+    /// # Examples
+    /// - `{}`
+    /// - Failed calls: `{"outcome": "error"}`
+    /// - One tool since a date: `{"tool": "bulk_export_jobs", "since": "2025-11-01", "limit": 10}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_audit_log(
+        &self,
+        params: GetAuditLogParams,
+    ) -> anyhow::Result<GetAuditLogResult> {
+        let request_id = record_request_id();
+        let (matching_entries, entries) = params.select(&audit::entries(&self.storage));
+        Ok(GetAuditLogResult {
+            request_id,
+            enabled: self.config.audit_log,
+            matching_entries,
+            entries,
+        })
+    }
+
+    /// Resolve a place to coordinates
+    ///
+    /// Looks up a street address, postal code or town with the configured
+    /// Nominatim-compatible service, or answers from the local cache where
+    /// every place ends up after its first lookup. The coordinates can be used
+    /// as JOBSUCHE_HOME_LOCATION. Needs `JOBSUCHE_GEOCODING=true`.
+    ///
+    /// # Examples
+    /// - `{"query": "42103 Wuppertal"}`
+    /// - `{"query": "Friedrich-Engels-Allee 1, 42103 Wuppertal"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn geocode_location(
+        &self,
+        params: GeocodeLocationParams,
+    ) -> anyhow::Result<GeocodeLocationResult> {
+        let request_id = record_request_id();
+        let Some(ref geocoder) = self.geocoder else {
+            anyhow::bail!("Geocoding is disabled; set JOBSUCHE_GEOCODING=true to enable it");
+        };
+        let place = geocoder
+            .geocode(&self.storage, &PlaceQuery::parse(&params.query))
+            .await?;
+        Ok(GeocodeLocationResult {
+            request_id,
+            query: params.query.trim().to_string(),
+            found: place.coordinates.is_some(),
+            place,
+        })
+    }
+
+    /// Search for jobs and return them as a GeoJSON FeatureCollection
+    ///
+    /// One point feature per job with title, employer, location and links as
+    /// properties, ready to be drawn on a map. Listings without coordinates
+    /// are geocoded from their postal code and town if
+    /// `JOBSUCHE_GEOCODING=true`; the rest keep a `null` geometry.
+    ///
+    /// # Examples
+    /// - `{"search": {"job_title": "Pflegefachkraft", "location": "Leipzig", "radius_km": 50}}`
+    /// - `{"search": {"job_title": "Rust Entwickler", "page_size": 100}}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn search_jobs_geojson(
+        &self,
+        params: SearchJobsGeoJsonParams,
+    ) -> anyhow::Result<SearchJobsGeoJsonResult> {
+        let request_id = record_request_id();
+        Validator::new()
+            .nested_search("search", &params.search)
+            .finish()?;
+        let mut result = self.execute_search(params.search).await?;
+
+        for job in &mut result.jobs {
+            if job.coordinates.is_none() {
+                if let Some(query) = PlaceQuery::of_listing(&job.location) {
+                    job.coordinates = self.geocode(&query).await;
+                }
+            }
+        }
+
+        let collection = SearchJobsGeoJsonResult::new(&result.jobs, result.total_results);
+        info!(
+            "Exported {} jobs as GeoJSON, {} without location",
+            collection.features.len(),
+            collection.unlocated_jobs
+        );
+        Ok(SearchJobsGeoJsonResult {
+            request_id,
+            ..collection
+        })
+    }
+
+    /// Search for jobs around several places at once
+    ///
+    /// Runs the search once per area, each a postal code or town with its own
+    /// radius, and combines the results: `union` lists jobs in any area,
+    /// `intersection` only jobs within reach of all of them, e.g. of both
+    /// partners' workplaces. Every job names the areas it was found in with
+    /// the distance from each.
+    ///
+    /// # Examples
+    /// - `{"search": {"job_title": "Buchhalter"}, "areas": [{"location": "42103", "radius_km": 20}, {"location": "40210", "radius_km": 30}], "combine": "intersection"}`
+    /// - `{"search": {"job_title": "Pflegefachkraft"}, "areas": [{"location": "Leipzig", "radius_km": 25}, {"location": "Halle", "radius_km": 10}]}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn search_jobs_in_areas(
+        &self,
+        params: SearchJobsInAreasParams,
+    ) -> anyhow::Result<SearchJobsInAreasResult> {
+        let request_id = record_request_id();
+        let mut validator = Validator::new();
+        validator
+            .nested_search("search", &params.search)
+            .count("areas", params.areas.len(), MAX_SEARCH_AREAS)
+            .range(
+                "max_jobs_per_area",
+                params.max_jobs_per_area,
+                1,
+                MAX_AREA_JOBS,
+            );
+        for (idx, area) in params.areas.iter().enumerate() {
+            validator.range(&format!("areas[{}].radius_km", idx), area.radius_km, 0, 200);
+        }
+        validator.finish()?;
+        let max_jobs = params.max_jobs_per_area.unwrap_or(DEFAULT_AREA_JOBS) as usize;
+        let page_size = self.config.max_page_size;
+
+        let mut summaries = Vec::new();
+        let mut found = Vec::new();
+        for area in params.areas {
+            let search = SearchJobsParams {
+                location: Some(area.location.clone()),
+                radius_km: area.radius_km,
+                nationwide: None,
+                page_size: None,
+                page: None,
+                ..params.search.clone()
+            };
+
+            let mut jobs = Vec::new();
+            let mut total_results = None;
+            let mut page = 1;
+            while jobs.len() < max_jobs {
+                let options = self.search_options(&search, page_size, Some(page));
+                let response: JobSearchResponse = self.search_api(options).await?;
+                total_results = total_results.or(response.max_ergebnisse);
+
+                let listings = response.stellenangebote.len() as u64;
+                append_unique(&mut jobs, Self::summarize_jobs(&response), max_jobs);
+                if listings < page_size {
+                    break;
+                }
+                page += 1;
+            }
+
+            summaries.push(AreaSummary {
+                location: area.location.clone(),
+                radius_km: area.radius_km,
+                total_results,
+                collected_jobs: jobs.len(),
+            });
+            found.push((area.location, jobs));
+        }
+
+        let jobs = combine_areas(found, params.combine);
+        info!(
+            "Combined {} areas into {} jobs ({:?})",
+            summaries.len(),
+            jobs.len(),
+            params.combine
+        );
+        Ok(SearchJobsInAreasResult {
+            request_id,
+            combine: params.combine,
+            areas: summaries,
+            jobs_count: jobs.len(),
+            jobs,
+        })
+    }
+
+    /// Generate a digest of new postings of all saved searches and watchlists
+    ///
+    /// Runs every saved search and watchlist and returns, per search, the
+    /// number of postings that are new since the previous digest and the
+    /// newest of them with their key facts, e.g. for a weekly update to a
+    /// client. Searches without an earlier digest count the postings of the
+    /// last 7 days as new. With `preview`, the postings are not marked as
+    /// reported and show up in the next digest again.
+    ///
+    /// # Examples
+    /// - `{}`
+    /// - `{"top_jobs": 10, "preview": true}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn generate_digest(
+        &self,
+        params: GenerateDigestParams,
+    ) -> anyhow::Result<GenerateDigestResult> {
+        let request_id = record_request_id();
+        Validator::new()
+            .range("top_jobs", params.top_jobs, 0, MAX_DIGEST_TOP_JOBS)
+            .finish()?;
+        let top_limit = params.top_jobs.unwrap_or(DEFAULT_DIGEST_TOP_JOBS) as usize;
+        let preview = params.preview.unwrap_or(false);
+
+        let saved_searches = self
+            .storage
+            .list::<SavedSearch>(SAVED_SEARCHES)
+            .into_iter()
+            .map(|saved| (DigestSource::SavedSearch, saved.name, saved.search));
+        let watchlists = self
+            .storage
+            .list::<Watchlist>(WATCHLISTS)
+            .into_iter()
+            .map(|watchlist| (DigestSource::Watchlist, watchlist.name, watchlist.search));
+        let searches: Vec<_> = saved_searches.chain(watchlists).collect();
+        self.quota.check_budget("generate_digest")?;
+        let state: DigestState = self
+            .storage
+            .get(DIGESTS, DIGEST_STATE_KEY)?
+            .unwrap_or_default();
+        info!("Generating digest of {} searches", searches.len());
+
+        if !preview {
+            // Forget the baselines of deleted searches
+            let keys: Vec<String> = searches
+                .iter()
+                .filter_map(|(source, name, _)| {
+                    name_key(name).ok().map(|key| source.baseline_key(&key))
+                })
+                .collect();
+            if let Err(e) = self.storage.retain(DIGEST_BASELINES, |key, _: &Watchlist| {
+                keys.iter().any(|known| known == key)
+            }) {
+                warn!("Failed to prune digest baselines: {:#}", e);
+            }
+        }
+
+        let mut progress = ProgressReporter::new(self.progress_sink.clone());
+        progress.set_total(searches.len() as u64);
+        let cancellation = RequestContext::current().cancellation;
+        let generated_at = now_rfc3339();
+        let mut sections = Vec::new();
+        let mut cancelled = false;
+
+        for (idx, (source, name, search)) in searches.into_iter().enumerate() {
+            // Small delay between searches to avoid rate limiting (except first)
+            if idx > 0 {
+                let delay = tokio::time::sleep(std::time::Duration::from_millis(200));
+                if run_cancellable(&cancellation, delay).await.is_none() {
+                    cancelled = true;
+                    break;
+                }
+            }
+
+            let Some(search_outcome) =
+                run_cancellable(&cancellation, self.execute_search(search.clone())).await
+            else {
+                cancelled = true;
+                break;
+            };
+
+            let section = match search_outcome {
+                Ok(search_result) => {
+                    let key = source.baseline_key(&name_key(&name)?);
+                    // A changed search starts over like a new one
+                    let search_value = serde_json::to_value(&search)?;
+                    let baseline = self
+                        .storage
+                        .get::<Watchlist>(DIGEST_BASELINES, &key)?
+                        .filter(|baseline| {
+                            serde_json::to_value(&baseline.search).ok().as_ref()
+                                == Some(&search_value)
+                        });
+                    let (baseline, new_jobs) = new_since_baseline(
+                        baseline,
+                        &name,
+                        &search,
+                        search_result.jobs,
+                        &generated_at,
+                    );
+                    if !preview {
+                        self.storage.put(DIGEST_BASELINES, &key, &baseline)?;
+                    }
+                    DigestSection {
+                        name: name.clone(),
+                        source,
+                        total_results: search_result.total_results,
+                        new_jobs_count: new_jobs.len(),
+                        top_jobs: top_jobs(&new_jobs, top_limit),
+                        error: None,
+                    }
+                }
+                Err(e) => DigestSection {
+                    name: name.clone(),
+                    source,
+                    total_results: None,
+                    new_jobs_count: 0,
+                    top_jobs: Vec::new(),
+                    error: Some(format!("Search failed: {}", e)),
+                },
+            };
+
+            progress.advance(format!("Digest of '{}' done", name));
+            sections.push(section);
+        }
+
+        if !preview && !cancelled {
+            let state = DigestState {
+                generated_at: Some(generated_at.clone()),
+            };
+            self.storage.put(DIGESTS, DIGEST_STATE_KEY, &state)?;
+        }
+
+        let total_new_jobs = sections.iter().map(|section| section.new_jobs_count).sum();
+        info!("Digest found {} new jobs", total_new_jobs);
+        Ok(GenerateDigestResult {
+            request_id,
+            generated_at,
+            previous_digest_at: state.generated_at,
+            searches_count: sections.len(),
+            total_new_jobs,
+            sections,
+            cancelled,
+        })
+    }
+
+    /// Build an application checklist for a job
+    ///
+    /// Derives concrete to-dos from the job details: how to send the
+    /// application (portal, e-mail, or by post for cipher postings), the
+    /// documents the description asks for, what to state in the application,
+    /// and the languages, certificates and driver's licenses to prove. Item
+    /// IDs stay the same for the same posting, so hosts can track progress.
+    ///
+    /// # Examples
+    /// - `{"reference_number": "10001-1234567890-S"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn build_application_checklist(
+        &self,
+        params: BuildApplicationChecklistParams,
+    ) -> anyhow::Result<BuildApplicationChecklistResult> {
+        let request_id = record_request_id();
+        Validator::new()
+            .job_id("reference_number", &params.reference_number)
+            .finish()?;
+        let (reference_number, _) = resolve_job_id(&params.reference_number);
+        let details = self.fetch_job_details(&reference_number).await?;
+        let items = build_checklist(&details);
+        info!(
+            "Built checklist with {} items for {}",
+            items.len(),
+            reference_number
+        );
+
+        Ok(BuildApplicationChecklistResult {
+            request_id,
+            reference_number: details.reference_number,
+            title: details.title,
+            employer: details.employer,
+            web_url: details.web_url,
+            items_count: items.len(),
+            items,
+        })
+    }
+
+    /// List upcoming application deadlines and start dates of favorites
+    ///
+    /// Favorites keep the start date of the posting and any application
+    /// deadline named in its description. Returns those due within the next
+    /// days, most urgent first, so no application window is missed. Works on
+    /// stored favorites only; save a favorite again to refresh its dates.
+    ///
+    /// # Examples
+    /// - `{}`
+    /// - Next two weeks, shortlist only: `{"within_days": 14, "label": "apply"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn list_upcoming_deadlines(
+        &self,
+        params: ListUpcomingDeadlinesParams,
+    ) -> anyhow::Result<ListUpcomingDeadlinesResult> {
+        let request_id = record_request_id();
+        Validator::new()
+            .range("within_days", params.within_days, 0, MAX_DEADLINE_DAYS)
+            .finish()?;
+        let within_days = params.within_days.unwrap_or(DEFAULT_DEADLINE_DAYS) as i64;
+
+        let favorites: Vec<Favorite> = self
+            .storage
+            .list::<Favorite>(FAVORITES)
+            .into_iter()
+            .filter(|favorite| match params.label {
+                Some(ref label) => favorite.has_label(label),
+                None => true,
+            })
+            .collect();
+        let favorites_without_dates = favorites
+            .iter()
+            .filter(|favorite| {
+                favorite.start_date.is_none() && favorite.application_deadline.is_none()
+            })
+            .count();
+        let today = today();
+        let deadlines = upcoming_deadlines(
+            &favorites,
+            today,
+            within_days,
+            params.include_passed.unwrap_or(false),
+        );
+
+        info!(
+            "Listing {} deadlines of {} favorites",
+            deadlines.len(),
+            favorites.len()
+        );
+        Ok(ListUpcomingDeadlinesResult {
+            request_id,
+            today: today.to_string(),
+            deadlines_count: deadlines.len(),
+            deadlines,
+            favorites_without_dates,
+        })
+    }
+
+    /// Find jobs similar to a given posting
+    ///
+    /// Runs derived searches (same title nearby, same employer, same title
+    /// anywhere) and returns the candidates scored by title similarity,
+    /// proximity and employer, each with the reasons for its score. Useful to
+    /// find alternatives when a posting is a good fit but already taken.
+    ///
+    /// # Examples
+    /// - `{"reference_number": "10001-1234567890-S"}`
+    /// - Wider area: `{"reference_number": "10001-1234567890-S", "radius_km": 50, "max_results": 20}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn find_similar_jobs(
+        &self,
+        params: FindSimilarJobsParams,
+    ) -> anyhow::Result<FindSimilarJobsResult> {
+        let request_id = record_request_id();
+        let limit = params
+            .max_results
+            .unwrap_or(DEFAULT_SIMILAR_JOBS)
+            .clamp(1, MAX_SIMILAR_JOBS) as usize;
+        let radius_km = params.radius_km.unwrap_or(DEFAULT_SIMILAR_RADIUS_KM);
+        let page_size = self.config.max_page_size.min(25);
+        let details = self.fetch_job_details(&params.reference_number).await?;
+
+        let base = SimilarityBase {
+            reference_number: details.reference_number.clone(),
+            keywords: details
+                .title
+                .as_deref()
+                .map(title_keywords)
+                .unwrap_or_default(),
+            city: details
+                .location
+                .as_deref()
+                .map(|location| city_of(location).to_string()),
+            employer: details.employer.clone(),
+        };
+        info!(
+            "Finding jobs similar to {} ({:?})",
+            base.reference_number, base.keywords
+        );
+
+        let query = base.keywords.join(" ");
+        let mut searches = Vec::new();
+        if !query.is_empty() {
+            if let Some(ref city) = base.city {
+                let mut options = SearchOptions::builder();
+                options
+                    .was(&query)
+                    .wo(city)
+                    .umkreis(radius_km)
+                    .size(page_size);
+                searches.push(("same title nearby", options.build()));
+            }
+        }
+        if let Some(ref employer) = base.employer {
+            let options = employer_search_options(employer, None, page_size, 1, None, None);
+            searches.push(("same employer", options));
+        }
+        if !query.is_empty() {
+            let mut options = SearchOptions::builder();
+            options.was(&query).size(page_size);
+            searches.push(("same title anywhere", options.build()));
+        }
+
+        // A failed search only narrows the candidates
+        let mut candidates = Vec::new();
+        for (name, options) in searches {
+            match self.search_api(options).await {
+                Ok(response) => candidates.extend(Self::summarize_jobs(&response)),
+                Err(e) => warn!("Similar jobs search '{}' failed: {:#}", name, e),
+            }
+        }
+        let jobs = base.rank(candidates, limit);
+
+        Ok(FindSimilarJobsResult {
+            request_id,
+            reference_number: base.reference_number,
+            title: details.title,
+            employer: details.employer,
+            location: base.city,
+            keywords: base.keywords,
+            jobs_count: jobs.len(),
+            jobs,
+        })
+    }
+
+    /// Compare 2 to 5 jobs side by side
+    ///
+    /// Returns the normalized values of each job (salary per year, contract,
+    /// location, remote work, skills, languages) and a table with one row per
+    /// criterion, marking the rows in which the jobs differ. Hosts can render
+    /// the table directly.
+    ///
+    /// # Examples
+    /// - `{"reference_numbers": ["10001-1234567890-S", "10001-0987654321-S"]}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn compare_jobs(
+        &self,
+        params: CompareJobsParams,
+    ) -> anyhow::Result<CompareJobsResult> {
+        let request_id = record_request_id();
+        let reference_numbers = compare_reference_numbers(&params.reference_numbers)?;
+        info!("Comparing {} jobs", reference_numbers.len());
+
+        let mut jobs = Vec::new();
+        for reference_number in &reference_numbers {
+            let details = self
+                .fetch_job_details(reference_number)
+                .await
+                .with_context(|| format!("Failed to fetch job {}", reference_number))?;
+            jobs.push(ComparedJob::from_details(&details));
+        }
+        let common_skills = compare_skills(&mut jobs);
+
+        Ok(CompareJobsResult {
+            request_id,
+            rows: comparison_rows(&jobs),
+            jobs,
+            common_skills,
+        })
+    }
+
+    /// Rank jobs by weighted preferences
+    ///
+    /// Scores each job from 0.0 to 1.0 on salary (relative to the other jobs),
+    /// distance to the search location, contract stability and remote work, and
+    /// sorts by the weighted mean. Every job carries its per-criterion values,
+    /// scores and contributions, so the order can be explained.
+    ///
+    /// # Examples
+    /// - `{"reference_numbers": ["10001-1234567890-S", "10001-0987654321-S"]}`
+    /// - Salary matters most: `{"search": {"job_title": "Pflegefachkraft", "location": "Köln", "radius_km": 30}, "weights": {"salary": 3, "remote": 0}}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn rank_jobs(&self, params: RankJobsParams) -> anyhow::Result<RankJobsResult> {
+        let request_id = record_request_id();
+        if let Some(ref search) = params.search {
+            Validator::new().nested_search("search", search).finish()?;
+        }
+        let weights = params.weights.unwrap_or_default();
+        weights.validate()?;
+        let max_jobs = params
+            .max_jobs
+            .unwrap_or(DEFAULT_RANK_JOBS)
+            .clamp(1, MAX_RANK_JOBS) as usize;
+
+        // Reference number and distance of each job to rank
+        let jobs: Vec<(String, Option<f64>)> = match (params.search, params.reference_numbers) {
+            (Some(search), None) => {
+                let result = self.execute_search(search).await?;
+                result
+                    .jobs
+                    .into_iter()
+                    .take(max_jobs)
+                    .map(|job| (job.reference_number, job.distance_km))
+                    .collect()
+            }
+            (None, Some(reference_numbers)) => {
+                let unique = unique_reference_numbers(&reference_numbers);
+                anyhow::ensure!(!unique.is_empty(), "No reference numbers given");
+                anyhow::ensure!(
+                    unique.len() <= max_jobs,
+                    "Cannot rank more than {} jobs at once, got {}",
+                    max_jobs,
+                    unique.len()
+                );
+                unique.into_iter().map(|refnr| (refnr, None)).collect()
+            }
+            _ => anyhow::bail!("Provide either search or reference_numbers"),
+        };
+        info!("Ranking {} jobs with weights {:?}", jobs.len(), weights);
+
+        let mut progress = ProgressReporter::new(self.progress_sink.clone());
+        progress.set_total(jobs.len() as u64);
+        let cancellation = RequestContext::current().cancellation;
+
+        let mut candidates = Vec::new();
+        let mut missing_details = Vec::new();
+        for (idx, (reference_number, distance_km)) in jobs.into_iter().enumerate() {
+            // Small delay between detail fetches (except first)
+            if idx > 0 {
+                let delay = tokio::time::sleep(std::time::Duration::from_millis(100));
+                if run_cancellable(&cancellation, delay).await.is_none() {
+                    anyhow::bail!("Ranking cancelled");
+                }
+            }
+
+            let Some(fetched) =
+                run_cancellable(&cancellation, self.fetch_job_details(&reference_number)).await
+            else {
+                anyhow::bail!("Ranking cancelled");
+            };
+            match fetched {
+                Ok(details) => candidates.push(RankCandidate {
+                    job: ComparedJob::from_details(&details),
+                    distance_km,
+                }),
+                Err(e) => {
+                    warn!("Ranking without {}: {:#}", reference_number, e);
+                    missing_details.push(reference_number.clone());
+                }
+            }
+            progress.advance(format!("Fetched details for {}", reference_number));
+        }
+
+        let jobs = rank_jobs(&candidates, &weights);
+        Ok(RankJobsResult {
+            request_id,
+            weights,
+            jobs_count: jobs.len(),
+            jobs,
+            missing_details,
+        })
+    }
+
+    /// Create or update the user profile used for matching
+    ///
+    /// The profile holds skills, desired locations, the expected yearly salary,
+    /// language levels and constraints (permanent only, no temporary agencies,
+    /// remote only, full-/part-time, driver's license). Omitted fields keep
+    /// their stored value. get_job_details and search_jobs_with_details compare
+    /// jobs against it when called with `profile_match: true`.
+    ///
+    /// # Examples
+    /// - `{"skills": ["Rust", "Docker"], "desired_locations": ["Berlin", "remote"], "min_annual_salary": 60000}`
+    /// - `{"languages": [{"language": "German", "level": "C1"}], "permanent_only": true}`
+    #[instrument(skip(self, params), fields(request_id), err)]
+    pub async fn set_user_profile(
+        &self,
+        params: SetUserProfileParams,
+    ) -> anyhow::Result<SetUserProfileResult> {
+        let request_id = record_request_id();
+        params.validate()?;
+        let now = now_rfc3339();
+        let (profile, updated) = self.storage.update(
+            USER_PROFILE,
+            PROFILE_KEY,
+            |current: &mut Option<UserProfile>| {
+                let updated = current.is_some();
+                let profile = UserProfile::apply(current.take(), params, &now);
+                *current = Some(profile.clone());
+                (profile, updated)
+            },
+        )?;
+        info!(
+            "User profile {}",
+            if updated { "updated" } else { "created" }
+        );
+
+        Ok(SetUserProfileResult {
+            request_id,
+            profile,
+            updated,
+        })
+    }
+
+    /// Get the stored user profile
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_user_profile(
+        &self,
+        _params: GetUserProfileParams,
+    ) -> anyhow::Result<GetUserProfileResult> {
+        let request_id = record_request_id();
+        Ok(GetUserProfileResult {
+            request_id,
+            profile: self.storage.get(USER_PROFILE, PROFILE_KEY)?,
+        })
+    }
+
+    /// Delete the stored user profile
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn delete_user_profile(
+        &self,
+        _params: DeleteUserProfileParams,
+    ) -> anyhow::Result<DeleteUserProfileResult> {
+        let request_id = record_request_id();
+        let deleted = self.storage.remove(USER_PROFILE, PROFILE_KEY)?;
+        info!(
+            "Delete user profile: {}",
+            if deleted { "deleted" } else { "not found" }
+        );
+
+        Ok(DeleteUserProfileResult {
+            request_id,
+            deleted,
+        })
+    }
+
+    /// Match a CV against job postings
+    ///
+    /// Extracts skills, languages and keywords from the CV and from each job
+    /// and returns the overlap, the missing requirements and a score from 0 to
+    /// 100, best match first. A cheap deterministic pre-filter before reading
+    /// full descriptions.
+    ///
+    /// # Examples
+    /// - `{"cv_text": "Softwareentwickler mit 5 Jahren Erfahrung in Rust ...", "reference_numbers": ["10001-1234567890-S"]}`
+    #[instrument(
+        skip(self, params),
+        fields(jobs = params.reference_numbers.len(), request_id),
+        err
+    )]
+    pub async fn match_cv(&self, params: MatchCvParams) -> anyhow::Result<MatchCvResult> {
+        let request_id = record_request_id();
+        anyhow::ensure!(
+            !params.cv_text.trim().is_empty(),
+            "CV text must not be empty"
+        );
+        let reference_numbers = unique_reference_numbers(&params.reference_numbers);
+        anyhow::ensure!(!reference_numbers.is_empty(), "No reference numbers given");
+        anyhow::ensure!(
+            reference_numbers.len() <= MAX_CV_JOBS,
+            "Cannot match more than {} jobs at once, got {}",
+            MAX_CV_JOBS,
+            reference_numbers.len()
+        );
+        info!("Matching CV against {} jobs", reference_numbers.len());
+
+        let cv = Cv::new(&params.cv_text);
+        let mut progress = ProgressReporter::new(self.progress_sink.clone());
+        progress.set_total(reference_numbers.len() as u64);
+        let cancellation = RequestContext::current().cancellation;
+
+        let mut jobs = Vec::new();
+        let mut missing_details = Vec::new();
+        for (idx, reference_number) in reference_numbers.into_iter().enumerate() {
+            // Small delay between detail fetches (except first)
+            if idx > 0 {
+                let delay = tokio::time::sleep(std::time::Duration::from_millis(100));
+                if run_cancellable(&cancellation, delay).await.is_none() {
+                    anyhow::bail!("CV matching cancelled");
+                }
+            }
+
+            let Some(fetched) =
+                run_cancellable(&cancellation, self.fetch_job_details(&reference_number)).await
+            else {
+                anyhow::bail!("CV matching cancelled");
+            };
+            match fetched {
+                Ok(details) => {
+                    let description = details
+                        .description
+                        .as_deref()
+                        .map(|text| clean_description(text, DescriptionFormat::Text))
+                        .unwrap_or_default();
+                    jobs.push(cv.match_job(&details, &description));
+                }
+                Err(e) => {
+                    warn!("Matching CV without {}: {:#}", reference_number, e);
+                    missing_details.push(reference_number.clone());
+                }
+            }
+            progress.advance(format!("Fetched details for {}", reference_number));
+        }
+        jobs.sort_by_key(|job| std::cmp::Reverse(job.score));
+
+        Ok(MatchCvResult {
+            request_id,
+            cv_skills: cv.skills().to_vec(),
+            cv_languages: cv.languages().to_vec(),
+            jobs_count: jobs.len(),
+            jobs,
+            missing_details,
+        })
+    }
+
+    /// Summarize the job market for a search
+    ///
+    /// Runs the search and returns aggregate figures instead of postings:
+    /// postings per city, employer and employment type, the share of postings
+    /// stating a salary and the median age of the postings. A one-call overview
+    /// of the market for a query.
+    ///
+    /// # Examples
+    /// - `{"search": {"job_title": "Pflegefachkraft", "location": "Hamburg", "radius_km": 30}}`
+    /// - `{"search": {"job_title": "Data Scientist"}, "max_jobs": 300, "salary_sample": 20}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn summarize_market(
+        &self,
+        params: SummarizeMarketParams,
+    ) -> anyhow::Result<SummarizeMarketResult> {
+        let request_id = record_request_id();
+        Validator::new()
+            .nested_search("search", &params.search)
+            .finish()?;
+        let search = params.search;
+        let max_jobs = params
+            .max_jobs
+            .unwrap_or(DEFAULT_MARKET_JOBS)
+            .clamp(1, MAX_MARKET_JOBS) as usize;
+        let salary_sample = params
+            .salary_sample
+            .unwrap_or(DEFAULT_SALARY_SAMPLE)
+            .min(MAX_SALARY_SAMPLE) as usize;
+        let page_size = self.config.max_page_size;
+        info!("Summarizing market for {:?}", search);
+
+        let mut jobs = Vec::new();
+        let mut total_results = None;
+        let mut page = 1;
+        while jobs.len() < max_jobs {
+            let options = self.search_options(&search, page_size, Some(page));
+            let response: JobSearchResponse = self.search_api(options).await?;
+            total_results = total_results.or(response.max_ergebnisse);
+
+            let listings = response.stellenangebote.len() as u64;
+            append_unique(&mut jobs, Self::summarize_jobs(&response), max_jobs);
+            if listings < page_size {
+                break;
+            }
+            page += 1;
+        }
+
+        // Counted over all results; overrides an employment_type filter of the search
+        let mut jobs_by_employment_type = BTreeMap::new();
+        for employment_type in EMPLOYMENT_TYPES {
+            let counted = SearchJobsParams {
+                employment_type: Some(vec![employment_type.to_string()]),
+                ..search.clone()
+            };
+            let options = self.search_options(&counted, 1, None);
+            jobs_by_employment_type
+                .insert(employment_type.to_string(), self.count_jobs(options).await?);
+        }
+
+        let cancellation = RequestContext::current().cancellation;
+        let mut salary_sample_size = 0;
+        let mut salary_stated = 0;
+        for (idx, job) in jobs.iter().take(salary_sample).enumerate() {
+            // Small delay between detail fetches (except first)
+            if idx > 0 {
+                let delay = tokio::time::sleep(std::time::Duration::from_millis(100));
+                if run_cancellable(&cancellation, delay).await.is_none() {
+                    anyhow::bail!("Market summary cancelled");
+                }
+            }
+
+            let Some(fetched) =
+                run_cancellable(&cancellation, self.fetch_job_details(&job.reference_number)).await
+            else {
+                anyhow::bail!("Market summary cancelled");
+            };
+            match fetched {
+                Ok(details) => {
+                    salary_sample_size += 1;
+                    if details
+                        .salary
+                        .is_some_and(|salary| !salary.trim().is_empty())
+                    {
+                        salary_stated += 1;
+                    }
+                }
+                Err(e) => warn!("Salary sample without {}: {:#}", job.reference_number, e),
+            }
+        }
+
+        let mut jobs_by_city = count_by_location(&jobs);
+        jobs_by_city.truncate(TOP_ENTRIES);
+        let mut jobs_by_employer = count_by_employer(&jobs);
+        jobs_by_employer.truncate(TOP_ENTRIES);
+
+        Ok(SummarizeMarketResult {
+            request_id,
+            total_results,
+            analyzed_jobs: jobs.len(),
+            jobs_by_city,
+            jobs_by_employer,
+            jobs_by_employment_type,
+            salary_sample_size,
+            salary_stated_share: share(salary_stated, salary_sample_size),
+            median_published_days_ago: median(
+                jobs.iter()
+                    .filter_map(|job| job.published_days_ago)
+                    .collect(),
+            ),
+        })
+    }
+
+    /// Show how the posting volume of a search developed
+    ///
+    /// Counts the postings published within the last 1, 7, 30 and 90 days,
+    /// splits them into buckets and compares the daily volume of the last week
+    /// with the weeks before. Earlier runs of the same search from the search
+    /// history are added as snapshots of the total over time.
+    ///
+    /// # Examples
+    /// - `{"search": {"job_title": "Rust Entwickler"}}`
+    /// - `{"search": {"job_title": "Pflegefachkraft", "location": "Leipzig", "radius_km": 50}}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn job_trends(&self, params: JobTrendsParams) -> anyhow::Result<JobTrendsResult> {
+        let request_id = record_request_id();
+        let search = SearchJobsParams {
+            published_since_days: None,
+            page: None,
+            ..params.search
+        };
+
+        let total_results = self
+            .count_jobs(self.search_options(&search, 1, None))
+            .await?;
+        let mut windows = Vec::new();
+        for &days in TREND_WINDOWS {
+            let window = SearchJobsParams {
+                published_since_days: Some(days),
+                ..search.clone()
+            };
+            let options = self.search_options(&window, 1, None);
+            windows.push(TrendWindow {
+                days,
+                total_results: self.count_jobs(options).await?,
+            });
+        }
+
+        let history: SearchHistory = self
+            .storage
+            .get(SEARCH_HISTORY, HISTORY_KEY)?
+            .unwrap_or_default();
+
+        info!(
+            "Counted {} postings in {} windows",
+            total_results,
+            windows.len()
+        );
+        Ok(JobTrendsResult {
+            request_id,
+            total_results,
+            buckets: buckets(&windows),
+            trend: trend(&windows),
+            windows,
+            snapshots: snapshots(&history.entries, &search),
+        })
+    }
+
+    /// Search apprenticeships (Ausbildungsplätze)
+    ///
+    /// Searches apprenticeship postings only and adds what school leavers look
+    /// for: the start date and year of each apprenticeship, and for the top
+    /// results the school-leaving certificates asked for and the duration.
+    ///
+    /// # Examples
+    /// - `{"job_title": "Mechatroniker", "location": "Stuttgart", "radius_km": 30}`
+    /// - `{"job_title": "Kaufmann Büromanagement", "location": "Köln", "start_year": 2026, "max_details": 5}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn search_apprenticeships(
+        &self,
+        params: SearchApprenticeshipsParams,
+    ) -> anyhow::Result<SearchApprenticeshipsResult> {
+        let request_id = record_request_id();
+        let page_size = params
+            .page_size
+            .unwrap_or(self.config.default_page_size)
+            .min(self.config.max_page_size);
+        let max_details = params
+            .max_details
+            .unwrap_or(DEFAULT_APPRENTICESHIP_DETAILS)
+            .min(MAX_APPRENTICESHIP_DETAILS) as usize;
+
+        let options = apprenticeship_search_options(&params, page_size);
+        let response: JobSearchResponse = self.search_api(options).await?;
+
+        let mut jobs: Vec<Apprenticeship> = response
+            .stellenangebote
+            .iter()
+            .zip(Self::summarize_jobs(&response))
+            .map(|(listing, job)| {
+                let (start_date, _) = normalize_date(listing.eintrittsdatum.as_deref());
+                Apprenticeship {
+                    job,
+                    start_year: start_date.as_deref().and_then(year_of),
+                    start_date,
+                    school_requirements: None,
+                    duration_years: None,
+                }
+            })
+            .filter(|apprenticeship| {
+                params.start_year.is_none()
+                    || apprenticeship.start_year.is_none()
+                    || apprenticeship.start_year == params.start_year
+            })
+            .collect();
+
+        let mut progress = ProgressReporter::new(self.progress_sink.clone());
+        let cancellation = RequestContext::current().cancellation;
+        let mut missing_details = Vec::new();
+        progress.set_total(jobs.len().min(max_details) as u64);
+        for (idx, apprenticeship) in jobs.iter_mut().take(max_details).enumerate() {
+            // Small delay between detail fetches (except first)
+            if idx > 0 {
+                let delay = tokio::time::sleep(std::time::Duration::from_millis(100));
+                if run_cancellable(&cancellation, delay).await.is_none() {
+                    anyhow::bail!("Apprenticeship search cancelled");
+                }
+            }
+
+            let refnr = apprenticeship.job.reference_number.clone();
+            let Some(fetched) =
+                run_cancellable(&cancellation, self.fetch_job_details(&refnr)).await
+            else {
+                anyhow::bail!("Apprenticeship search cancelled");
+            };
+            match fetched {
+                Ok(details) => {
+                    let description = details.description.unwrap_or_default();
+                    apprenticeship.school_requirements = Some(school_requirements(&description));
+                    apprenticeship.duration_years = duration_years(&description);
+                }
+                Err(e) => {
+                    warn!("Failed to fetch details for {}: {:#}", refnr, e);
+                    missing_details.push(refnr.clone());
+                }
+            }
+            progress.advance(format!("Fetched details for {}", refnr));
+        }
+
+        info!("Found {} apprenticeships", jobs.len());
+        Ok(SearchApprenticeshipsResult {
+            request_id,
+            total_results: response.max_ergebnisse,
+            current_page: response.page,
+            page_size: response.size,
+            jobs_count: jobs.len(),
+            jobs,
+            missing_details,
+        })
+    }
+
+    /// Get salary statistics for an occupation
+    ///
+    /// Returns the median monthly gross pay with quartiles of full-time
+    /// employees in an occupation and region from the Entgeltatlas of the
+    /// Bundesagentur für Arbeit, overall and by gender and age group. Useful
+    /// for negotiation preparation next to a job search.
+    ///
+    /// # Examples
+    /// - `{"occupation_code": "43104"}`
+    /// - `{"occupation_code": "81302", "region": "Bayern"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_salary_statistics(
+        &self,
+        params: GetSalaryStatisticsParams,
+    ) -> anyhow::Result<GetSalaryStatisticsResult> {
+        let request_id = record_request_id();
+        let statistics = self
+            .entgeltatlas
+            .statistics(&params.occupation_code, params.region.as_deref())
+            .await?
+            .with_context(|| {
+                format!(
+                    "No salary statistics published for occupation {}",
+                    params.occupation_code.trim()
+                )
+            })?;
+
+        info!(
+            "Salary statistics for {} in {}",
+            statistics.occupation_code, statistics.region
+        );
+        Ok(GetSalaryStatisticsResult {
+            request_id,
+            ..statistics
+        })
+    }
+
+    /// Search continuing education courses
+    ///
+    /// Searches the Weiterbildungssuche of the Bundesagentur für Arbeit for
+    /// courses on a skill or topic, with provider, place, dates, format and
+    /// whether the course can be funded with a Bildungsgutschein. Follows up a
+    /// missing skill from a job with courses near the user.
+    ///
+    /// # Examples
+    /// - `{"keyword": "SAP FI", "location": "Leipzig"}`
+    /// - `{"keyword": "Staplerschein", "location": "Dortmund", "radius_km": 50, "fundable_only": true}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn search_trainings(
+        &self,
+        params: SearchTrainingsParams,
+    ) -> anyhow::Result<SearchTrainingsResult> {
+        let request_id = record_request_id();
+        let result = self.weiterbildung.search(&params).await?;
+        info!(
+            "Found {} trainings for '{}'",
+            result.trainings_count, params.keyword
+        );
+        Ok(SearchTrainingsResult {
+            request_id,
+            ..result
+        })
+    }
+
+    /// Search AVGS coaching offers
+    ///
+    /// Searches the Coachingangebote of the Bundesagentur für Arbeit for
+    /// coaching that can be paid with an Aktivierungs- und Vermittlungsgutschein
+    /// (AVGS), such as application coaching, career orientation or start-up
+    /// coaching, with provider, place, format and duration. Without a location,
+    /// offers from all of Germany (including online coaching) are found.
+    ///
+    /// # Examples
+    /// - `{"topic": "Bewerbungscoaching", "location": "Kiel"}`
+    /// - `{"topic": "Existenzgründung", "location": "80331", "radius_km": 10}`
+    /// - `{"location": "Hamburg", "page": 2}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn search_coaching_offers(
+        &self,
+        params: SearchCoachingOffersParams,
+    ) -> anyhow::Result<SearchCoachingOffersResult> {
+        let request_id = record_request_id();
+        let result = self.coaching.search(&params).await?;
+        info!("Found {} coaching offers", result.offers_count);
+        Ok(SearchCoachingOffersResult {
+            request_id,
+            ..result
+        })
+    }
+
+    /// Search degree programs
+    ///
+    /// Searches the Studiensuche of the Bundesagentur für Arbeit for study
+    /// programs in a subject, with university, place, degree, study form and
+    /// standard period of study. Complements search_apprenticeships for school
+    /// leavers deciding between a degree and an apprenticeship; `dual_only`
+    /// finds programs that combine both.
+    ///
+    /// # Examples
+    /// - `{"subject": "Informatik", "location": "Aachen"}`
+    /// - `{"subject": "Maschinenbau", "degree_type": "bachelor", "dual_only": true}`
+    /// - `{"subject": "Soziale Arbeit", "location": "Leipzig", "radius_km": 100}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn search_degree_programs(
+        &self,
+        params: SearchDegreeProgramsParams,
+    ) -> anyhow::Result<SearchDegreeProgramsResult> {
+        let request_id = record_request_id();
+        let result = self.studiensuche.search(&params).await?;
+        info!(
+            "Found {} degree programs for '{}'",
+            result.programs_count, params.subject
+        );
+        Ok(SearchDegreeProgramsResult {
+            request_id,
+            ..result
+        })
+    }
+
+    /// Search candidate profiles (recruiter tool)
+    ///
+    /// Searches the Bewerberbörse of the Bundesagentur für Arbeit for
+    /// anonymized profiles of job seekers by occupation, location and
+    /// availability. Only available when the server runs with
+    /// `JOBSUCHE_RECRUITER_TOOLS=true`. Contact details are not part of the
+    /// public API; each candidate links to the profile page on
+    /// arbeitsagentur.de.
+    ///
+    /// # Examples
+    /// - `{"occupation": "Elektroniker", "location": "Bremen", "radius_km": 30}`
+    /// - `{"occupation": "Buchhalter", "location": "München", "available_by": "2026-06-01"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn search_candidates(
+        &self,
+        params: SearchCandidatesParams,
+    ) -> anyhow::Result<SearchCandidatesResult> {
+        let request_id = record_request_id();
+        let client = self
+            .bewerberboerse
+            .as_ref()
+            .context(RECRUITER_TOOLS_DISABLED)?;
+        let result = client.search(&params).await?;
+        info!(
+            "Found {} candidates for '{}'",
+            result.candidates_count, params.occupation
+        );
+        Ok(SearchCandidatesResult {
+            request_id,
+            ..result
+        })
+    }
+
+    /// Get a candidate profile (recruiter tool)
+    ///
+    /// Returns the full anonymized profile of a candidate from
+    /// search_candidates: occupations, working times, experience, education,
+    /// skills and languages. Only available when the server runs with
+    /// `JOBSUCHE_RECRUITER_TOOLS=true`.
+    ///
+    /// # Examples
+    /// - `{"reference_number": "10000-1234567-B"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_candidate_profile(
+        &self,
+        params: GetCandidateProfileParams,
+    ) -> anyhow::Result<GetCandidateProfileResult> {
+        let request_id = record_request_id();
+        let client = self
+            .bewerberboerse
+            .as_ref()
+            .context(RECRUITER_TOOLS_DISABLED)?;
+        let profile = client.profile(&params.reference_number).await?;
+        Ok(GetCandidateProfileResult {
+            request_id,
+            ..profile
+        })
+    }
+
+    /// Suggest canonical job titles
+    ///
+    /// Returns the occupation names of the Bundesagentur für Arbeit that match
+    /// a partial or colloquial job title, from the autocomplete of the
+    /// Jobbörse. Searching with a suggested title matches postings more
+    /// precisely than a free-form one.
+    ///
+    /// # Examples
+    /// - `{"query": "Kassier"}`
+    /// - `{"query": "Softwareentw", "limit": 5}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn suggest_job_titles(
+        &self,
+        params: SuggestJobTitlesParams,
+    ) -> anyhow::Result<SuggestJobTitlesResult> {
+        let request_id = record_request_id();
+        let result = self.suggestions.job_titles(&params).await?;
+        info!(
+            "{} job title suggestions for '{}'",
+            result.suggestions.len(),
+            result.query
+        );
+        Ok(SuggestJobTitlesResult {
+            request_id,
+            ..result
+        })
+    }
+
+    /// Suggest locations
+    ///
+    /// Resolves a partial or misspelled place name or a postal code to places
+    /// with postal code and federal state, from the place autocomplete of the
+    /// Jobbörse. A search for a place the API does not know returns no jobs
+    /// without an error, so check unusual spellings here first. Falls back to a
+    /// built-in list of larger cities (`source: "local"`) if the autocomplete
+    /// is unreachable.
+    ///
+    /// # Examples
+    /// - `{"query": "Frankf"}`
+    /// - `{"query": "803", "limit": 5}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn suggest_locations(
+        &self,
+        params: SuggestLocationsParams,
+    ) -> anyhow::Result<SuggestLocationsResult> {
+        let request_id = record_request_id();
+        let result = self.suggestions.locations(&params).await?;
+        info!(
+            "{} location suggestions for '{}'",
+            result.suggestions.len(),
+            result.query
+        );
+        Ok(SuggestLocationsResult {
+            request_id,
+            ..result
+        })
+    }
+
+    /// List occupational fields and their occupations
+    ///
+    /// Returns the ten occupational fields (Berufsbereiche) of the
+    /// Klassifikation der Berufe with common occupations of each, their KldB
+    /// code and a job title to search for. A query narrows the tree to the
+    /// fields matching an interest or to single occupations, so a vague wish
+    /// like "something with healthcare" leads to concrete searches.
+    ///
+    /// # Examples
+    /// - `{}`
+    /// - `{"query": "healthcare"}`
+    /// - `{"query": "Logistik"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn list_occupation_fields(
+        &self,
+        params: ListOccupationFieldsParams,
+    ) -> anyhow::Result<ListOccupationFieldsResult> {
+        let request_id = record_request_id();
+        let fields = list_occupation_fields(params.query.as_deref());
+        let occupations_count = fields.iter().map(|field| field.occupations.len()).sum();
+        info!(
+            "{} occupation fields with {} occupations",
+            fields.len(),
+            occupations_count
+        );
+        Ok(ListOccupationFieldsResult {
+            request_id,
+            fields_count: fields.len(),
+            occupations_count,
+            fields,
+        })
+    }
+
+    /// Overview of the labor market of a region
+    ///
+    /// For a federal state or a city, returns the employers and occupations
+    /// with the most postings and the number of postings per employment type.
+    /// Runs a fixed set of queries, so it answers "who is hiring there?" in
+    /// one call, e.g. when deciding where to move.
+    ///
+    /// # Examples
+    /// - `{"region": "Sachsen"}`
+    /// - `{"region": "Leipzig", "radius_km": 30}`
+    /// - `{"region": "Hamburg", "job_title": "Pflegefachkraft"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn regional_overview(
+        &self,
+        params: RegionalOverviewParams,
+    ) -> anyhow::Result<RegionalOverviewResult> {
+        let request_id = record_request_id();
+        if params.region.trim().is_empty() {
+            anyhow::bail!("region must not be empty");
+        }
+        Validator::new()
+            .range("radius_km", params.radius_km, 0, MAX_RADIUS_KM)
+            .finish()?;
+        let max_jobs = params
+            .max_jobs
+            .unwrap_or(DEFAULT_REGION_JOBS)
+            .clamp(1, MAX_REGION_JOBS) as usize;
+        self.quota.check_budget("regional_overview")?;
+        let (search, region, region_type) = region_search(&params);
+        let page_size = self.config.max_page_size;
+        info!("Regional overview for {} ({:?})", region, region_type);
+
+        let mut jobs = Vec::new();
+        let mut occupations = BTreeMap::new();
+        let mut total_results = None;
+        let mut page = 1;
+        while jobs.len() < max_jobs {
+            let options = self.search_options(&search, page_size, Some(page));
+            let response: JobSearchResponse = self.search_api(options).await?;
+            total_results = total_results.or(response.max_ergebnisse);
+
+            let listings = response.stellenangebote.len() as u64;
+            occupations.extend(
+                response
+                    .stellenangebote
+                    .iter()
+                    .map(|listing| (listing.refnr.clone(), listing.beruf.clone())),
+            );
+            append_unique(&mut jobs, Self::summarize_jobs(&response), max_jobs);
+            if listings < page_size {
+                break;
+            }
+            page += 1;
+        }
+
+        let mut jobs_by_employment_type = BTreeMap::new();
+        for employment_type in EMPLOYMENT_TYPES {
+            let counted = SearchJobsParams {
+                employment_type: Some(vec![employment_type.to_string()]),
+                ..search.clone()
+            };
+            let options = self.search_options(&counted, 1, None);
+            jobs_by_employment_type
+                .insert(employment_type.to_string(), self.count_jobs(options).await?);
+        }
+
+        let mut top_employers = count_by_employer(&jobs);
+        top_employers.truncate(TOP_REGION_ENTRIES);
+        let mut top_occupations = count_by_occupation(
+            jobs.iter()
+                .filter_map(|job| occupations.get(&job.reference_number))
+                .map(String::as_str),
+        );
+        top_occupations.truncate(TOP_REGION_ENTRIES);
+
+        Ok(RegionalOverviewResult {
+            request_id,
+            region,
+            region_type,
+            radius_km: search.radius_km,
+            total_results,
+            analyzed_jobs: jobs.len(),
+            top_employers,
+            top_occupations,
+            jobs_by_employment_type,
+        })
+    }
+
+    /// Get job details, falling back to the archived snapshot
+    ///
+    /// Tries the live API first. If the posting is no longer available (e.g. the
+    /// position has been filled), the last snapshot taken by get_job_details is
+    /// returned instead, together with the time it was archived.
+    ///
+    /// # Examples
+    /// - `{"reference_number": "10001-1234567890-S"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_archived_job(
+        &self,
+        params: GetArchivedJobParams,
+    ) -> anyhow::Result<GetArchivedJobResult> {
+        let request_id = record_request_id();
+        let live = self
+            .job_details(GetJobDetailsParams {
+                reference_number: params.reference_number.clone(),
+                occupation_code: None,
+                lang: None,
+                resolve_external_url: None,
+                profile_match: None,
+                detail_level: params.detail_level,
+                include_raw: params.include_raw,
+                fields: None,
+            })
+            .await;
+
+        let live_error = match live {
+            Ok(details) => {
+                return Ok(GetArchivedJobResult {
+                    request_id,
+                    source: JobSource::Live,
+                    archived_at: None,
+                    live_error: None,
+                    details,
+                })
+            }
+            Err(e) => e,
+        };
+
+        let Some(archived) = self
+            .storage
+            .get::<ArchivedJob>(JOB_ARCHIVE, &params.reference_number)?
+        else {
+            return Err(live_error.context(format!(
+                "Job {} is not available and has not been archived",
+                params.reference_number
+            )));
+        };
+
+        info!(
+            "Serving archived snapshot of {} from {}",
+            params.reference_number, archived.archived_at
+        );
+        let mut details = GetJobDetailsResult {
+            request_id: String::new(),
+            // Snapshots taken before descriptions were cleaned up may contain HTML
+            description: archived
+                .details
+                .description
+                .as_deref()
+                .map(|text| clean_description(text, self.config.description_format)),
+            notes: self.load_job_notes(&params.reference_number),
+            ..archived.details
+        };
+        let include_raw = params.include_raw.unwrap_or(self.config.include_raw_data);
+        apply_detail_level(
+            &mut details,
+            params.detail_level.unwrap_or_default(),
+            include_raw,
+        );
+        Ok(GetArchivedJobResult {
+            request_id,
+            source: JobSource::Archive,
+            archived_at: Some(archived.archived_at),
+            live_error: Some(format!("{:#}", live_error)),
+            details,
+        })
+    }
+
+    /// Export jobs to a CSV file or Markdown report
+    ///
+    /// Runs a search or takes a list of reference numbers and writes the selected
+    /// columns to a file in the export directory. Returns the file path, and for
+    /// Markdown also the report itself. Salary and employment type are only
+    /// available from the job details, which are fetched for up to 50 jobs.
+    ///
+    /// # Examples
+    /// - Export a search: `{"search": {"job_title": "Data Scientist", "location": "Hamburg"}}`
+    /// - Export jobs: `{"reference_numbers": ["10001-1234567890-S"], "columns": ["title", "employer", "salary", "url"], "file_name": "shortlist"}`
+    /// - Weekly report: `{"search": {"job_title": "Rust", "published_since_days": 7}, "format": "markdown", "group_by": "employer"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn export_results(
+        &self,
+        params: ExportResultsParams,
+    ) -> anyhow::Result<ExportResultsResult> {
+        let request_id = record_request_id();
+        if let Some(ref search) = params.search {
+            Validator::new().nested_search("search", search).finish()?;
+        }
+        let columns: Vec<ExportColumn> = match params.columns {
+            Some(columns) if !columns.is_empty() => columns,
+            _ => DEFAULT_COLUMNS.to_vec(),
+        };
+        let format = params.format.unwrap_or_default();
+        let now = now_rfc3339();
+        let file_name = export_file_name(params.file_name.as_deref(), &now, format.extension())?;
+        let title = params
+            .title
+            .clone()
+            .unwrap_or_else(|| report_title(params.search.as_ref()));
+
+        let (mut rows, fetch_details) = match (params.search, params.reference_numbers) {
+            (Some(search), None) => {
+                let result = self.execute_search(search).await?;
+                let rows: Vec<ExportRow> =
+                    result.jobs.iter().map(ExportRow::from_summary).collect();
+                (rows, columns.iter().any(|column| column.needs_details()))
+            }
+            (None, Some(reference_numbers)) => {
+                let rows = reference_numbers
+                    .iter()
+                    .map(|refnr| ExportRow::from_reference(refnr))
+                    .collect();
+                (rows, true)
+            }
+            _ => anyhow::bail!("Provide either search or reference_numbers"),
+        };
+
+        let mut missing_details = Vec::new();
+        if fetch_details {
+            let mut progress = ProgressReporter::new(self.progress_sink.clone());
+            progress.set_total(rows.len().min(MAX_EXPORT_DETAILS) as u64);
+            let cancellation = RequestContext::current().cancellation;
+
+            for (idx, row) in rows.iter_mut().enumerate() {
+                if idx >= MAX_EXPORT_DETAILS {
+                    missing_details.push(row.reference_number().to_string());
+                    continue;
+                }
+
+                // Small delay between detail fetches (except first)
+                if idx > 0 {
+                    let delay = tokio::time::sleep(std::time::Duration::from_millis(100));
+                    if run_cancellable(&cancellation, delay).await.is_none() {
+                        anyhow::bail!("Export cancelled");
+                    }
+                }
+
+                let details_params = GetJobDetailsParams {
+                    reference_number: row.reference_number().to_string(),
+                    occupation_code: None,
+                    lang: None,
+                    resolve_external_url: None,
+                    profile_match: None,
+                    detail_level: None,
+                    include_raw: None,
+                    fields: None,
+                };
+                let Some(fetched) =
+                    run_cancellable(&cancellation, self.job_details(details_params)).await
+                else {
+                    anyhow::bail!("Export cancelled");
+                };
+
+                match fetched {
+                    Ok(details) => *row = std::mem::take(row).with_details(&details),
+                    Err(e) => {
+                        warn!(
+                            "Exporting {} without details: {}",
+                            row.reference_number(),
+                            e
+                        );
+                        missing_details.push(row.reference_number().to_string());
+                    }
+                }
+                progress.advance(format!("Fetched details for {}", row.reference_number()));
+            }
+        }
+
+        std::fs::create_dir_all(&self.config.export_dir).with_context(|| {
+            format!(
+                "Failed to create export directory {}",
+                self.config.export_dir.display()
+            )
+        })?;
+        let path = self.config.export_dir.join(file_name);
+        let report = match format {
+            ExportFormat::Csv => {
+                write_csv(&path, &columns, &rows)?;
+                None
+            }
+            ExportFormat::Markdown => {
+                let generated_at = now.get(..10).unwrap_or(&now);
+                let report =
+                    render_markdown(&title, generated_at, &columns, &rows, params.group_by);
+                std::fs::write(&path, &report)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                Some(report)
+            }
+        };
+
+        info!("Exported {} jobs to {}", rows.len(), path.display());
+        Ok(ExportResultsResult {
+            request_id,
+            path: path.display().to_string(),
+            format,
+            rows: rows.len(),
+            columns,
+            missing_details,
+            report,
+        })
+    }
+
+    /// Export large numbers of jobs for data analysis
+    ///
+    /// Pages through the results of a search and streams every job (with full
+    /// details by default) to an NDJSON file in the export directory, or to
+    /// Parquet if the server was built with the `parquet` feature. Intended for
+    /// collecting thousands of postings; with details about 10 jobs per second
+    /// are exported. Exported jobs are not added to the seen jobs, history or
+    /// archive.
+    ///
+    /// # Examples
+    /// - `{"search": {"job_title": "Pflegefachkraft", "location": "Deutschland"}, "max_jobs": 2000}`
+    /// - Listings only: `{"search": {"location": "Bayern"}, "max_jobs": 10000, "include_details": false}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn bulk_export_jobs(
+        &self,
+        params: BulkExportParams,
+    ) -> anyhow::Result<BulkExportResult> {
+        let request_id = record_request_id();
+        Validator::new()
+            .nested_search("search", &params.search)
+            .finish()?;
+        self.quota.check_budget("bulk_export_jobs")?;
+        let start = Instant::now();
+        let format = params.format.unwrap_or_default();
+        let max_jobs = params
+            .max_jobs
+            .unwrap_or(DEFAULT_BULK_JOBS)
+            .clamp(1, MAX_BULK_JOBS);
+        let include_details = params.include_details.unwrap_or(true);
+        let page_size = self.config.max_page_size;
+
+        let file_name = export_file_name(
+            params.file_name.as_deref(),
+            &now_rfc3339(),
+            format.extension(),
+        )?;
+        std::fs::create_dir_all(&self.config.export_dir).with_context(|| {
+            format!(
+                "Failed to create export directory {}",
+                self.config.export_dir.display()
+            )
+        })?;
+        let path = self.config.export_dir.join(file_name);
+        let mut writer = BulkWriter::create(&path, format)?;
+        info!(
+            "Bulk export of up to {} jobs to {}",
+            max_jobs,
+            path.display()
+        );
+
+        let mut progress = ProgressReporter::new(self.progress_sink.clone());
+        progress.set_total(max_jobs as u64);
+        let cancellation = RequestContext::current().cancellation;
+
+        let mut page = params.search.page.unwrap_or(1);
+        let mut pages_fetched = 0;
+        let mut total_results = None;
+        let mut jobs_written = 0;
+        let mut failed_details = Vec::new();
+        let mut cancelled = false;
+
+        'pages: while jobs_written < max_jobs {
+            // Small delay between requests to avoid rate limiting (except first)
+            if pages_fetched > 0 {
+                let delay = tokio::time::sleep(std::time::Duration::from_millis(200));
+                if run_cancellable(&cancellation, delay).await.is_none() {
+                    cancelled = true;
+                    break;
+                }
+            }
+
+            let options = self.search_options(&params.search, page_size, Some(page));
+            let Some(response) = run_cancellable(&cancellation, self.search_api(options)).await
+            else {
+                cancelled = true;
+                break;
+            };
+            let response = response?;
+            pages_fetched += 1;
+
+            if total_results.is_none() {
+                total_results = response.max_ergebnisse;
+                if let Some(total) = total_results {
+                    progress.set_total(max_jobs.min(total as usize) as u64);
+                }
+            }
+
+            let jobs = Self::summarize_jobs(&response);
+            let page_len = jobs.len();
+
+            for job in jobs.into_iter().take(max_jobs - jobs_written) {
+                let record = if include_details {
+                    let delay = tokio::time::sleep(std::time::Duration::from_millis(100));
+                    let fetch = async {
+                        delay.await;
+                        self.fetch_job_details(&job.reference_number).await
+                    };
+                    match run_cancellable(&cancellation, fetch).await {
+                        None => {
+                            cancelled = true;
+                            break 'pages;
+                        }
+                        Some(Ok(details)) => serde_json::to_value(details)?,
+                        Some(Err(e)) => {
+                            warn!("Skipping {}: {}", job.reference_number, e);
+                            failed_details.push(job.reference_number);
+                            continue;
+                        }
+                    }
+                } else {
+                    serde_json::to_value(&job)?
+                };
+
+                writer.write(&record)?;
+                jobs_written += 1;
+                progress.advance(format!("Exported {}", job.reference_number));
+            }
+            writer.flush()?;
+
+            // A short page means there are no more results
+            if page_len < page_size as usize {
+                break;
+            }
+            page += 1;
+        }
+
+        writer.finish()?;
+
+        let duration = start.elapsed();
+        info!(
+            "Bulk export wrote {} jobs from {} pages in {:?}",
+            jobs_written, pages_fetched, duration
+        );
+
+        Ok(BulkExportResult {
+            request_id,
+            path: path.display().to_string(),
+            format,
+            jobs_written,
+            pages_fetched,
+            total_results,
+            failed_details,
+            cancelled,
+            duration_ms: duration.as_millis() as u64,
+        })
+    }
+
+    /// Import jobs from a list of reference numbers
+    ///
+    /// Accepts reference numbers as free text, e.g. copied from the
+    /// arbeitsagentur.de website: one per line, comma or semicolon separated,
+    /// or job page URLs. The details of every job are fetched and stored in the
+    /// job archive, and optionally as favorites, so they can be compared later
+    /// even if a posting disappears. At most 100 jobs are imported per call.
+    ///
+    /// # Examples
+    /// - `{"text": "10001-1234567890-S\n10001-1234567891-S"}`
+    /// - As favorites: `{"text": "10001-1234567890-S, 10001-1234567891-S", "target": "favorites", "labels": ["shortlist"]}`
+    #[instrument(skip(self, params), fields(target = ?params.target, request_id), err)]
+    pub async fn import_refnrs(
+        &self,
+        params: ImportRefnrsParams,
+    ) -> anyhow::Result<ImportRefnrsResult> {
+        let request_id = record_request_id();
+        let target = params.target.unwrap_or_default();
+        let labels = params.labels.unwrap_or_default();
+        if target == ImportTarget::Archive && !labels.is_empty() {
+            anyhow::bail!("labels are only supported with target \"favorites\"");
+        }
+
+        let mut parsed = parse_reference_numbers(&params.text);
+        if parsed.reference_numbers.is_empty() {
+            anyhow::bail!("No reference numbers found in text");
+        }
+        let skipped = parsed
+            .reference_numbers
+            .split_off(parsed.reference_numbers.len().min(MAX_IMPORT));
+        self.quota.check_budget("import_refnrs")?;
+        info!(
+            "Importing {} jobs into {:?}",
+            parsed.reference_numbers.len(),
+            target
+        );
+
+        let mut progress = ProgressReporter::new(self.progress_sink.clone());
+        progress.set_total(parsed.reference_numbers.len() as u64);
+        let cancellation = RequestContext::current().cancellation;
+
+        let mut imported = Vec::new();
+        let mut failed = Vec::new();
+        let mut cancelled = false;
+
+        for (idx, reference_number) in parsed.reference_numbers.into_iter().enumerate() {
+            // Small delay between requests to avoid rate limiting (except first)
+            let delay_ms = if idx > 0 { 100 } else { 0 };
+            let fetch = async {
+                tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+                self.fetch_job_details(&reference_number).await
+            };
+            let details = match run_cancellable(&cancellation, fetch).await {
+                None => {
+                    cancelled = true;
+                    break;
+                }
+                Some(Ok(details)) => details,
+                Some(Err(e)) => {
+                    warn!("Failed to import {}: {:#}", reference_number, e);
+                    failed.push(FailedImport {
+                        reference_number,
+                        error: format!("{:#}", e),
+                    });
+                    progress.advance("Import failed");
+                    continue;
+                }
+            };
+
+            self.archive_job(&details).await;
+            self.index_job(&details).await;
+            if target == ImportTarget::Favorites {
+                if let Err(e) = self.store_favorite(&details, labels.clone()) {
+                    failed.push(FailedImport {
+                        reference_number,
+                        error: format!("{:#}", e),
+                    });
+                    progress.advance("Import failed");
+                    continue;
+                }
+            }
+
+            progress.advance(format!("Imported {}", reference_number));
+            imported.push(ImportedJob {
+                reference_number: details.reference_number,
+                title: details.title,
+                employer: details.employer,
+            });
+        }
+
+        Ok(ImportRefnrsResult {
+            request_id,
+            target,
+            imported_count: imported.len(),
+            imported,
+            failed,
+            invalid: parsed.invalid,
+            skipped,
+            cancelled,
+        })
+    }
+
+    /// Full-text search over all jobs fetched so far
+    ///
+    /// Every job whose details were fetched (get_job_details, search_jobs_with_details,
+    /// import_refnrs, ...) is kept in a local index covering title, employer, location
+    /// and the full description. Unlike the upstream search, this finds terms anywhere
+    /// in the description and supports phrases, prefixes and boolean operators.
+    ///
+    /// # Examples
+    /// - Phrase: `{"query": "\"betriebliche Altersvorsorge\""}`
+    /// - Boolean: `{"query": "(rust OR golang) AND kubernetes NOT praktikum"}`
+    /// - Title only: `{"query": "title:entwickler*", "limit": 50}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn search_local_jobs(
+        &self,
+        params: SearchLocalJobsParams,
+    ) -> anyhow::Result<SearchLocalJobsResult> {
+        let request_id = record_request_id();
+        let query = params.query.trim();
+        if query.is_empty() {
+            anyhow::bail!("Query must not be empty");
+        }
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_LOCAL_RESULTS)
+            .clamp(1, MAX_LOCAL_RESULTS);
+
+        let results = self.job_index.search(query, limit)?;
+        info!("Local search '{}' found {} jobs", query, results.len());
+
+        Ok(SearchLocalJobsResult {
+            request_id,
+            query: query.to_string(),
+            indexed_jobs: self.job_index.len()?,
+            results_count: results.len(),
+            results,
+        })
+    }
+
+    /// Prune expired local data and compact the store
+    ///
+    /// Removes job snapshots, seen-job records, search history entries and indexed
+    /// jobs older than the retention period (JOBSUCHE_RETENTION_DAYS, default 180
+    /// days), then compacts the store and the full-text index. Favorites, notes,
+    /// saved searches and watchlists are never removed. The same maintenance runs
+    /// in the background once a day.
+    ///
+    /// # Examples
+    /// - Configured retention: `{}`
+    /// - Keep only the last 30 days: `{"retention_days": 30}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn compact_storage(
+        &self,
+        params: CompactStorageParams,
+    ) -> anyhow::Result<CompactStorageResult> {
+        let request_id = record_request_id();
+        let retention_days = params.retention_days.unwrap_or(self.config.retention_days);
+        let storage = self.storage.clone();
+        let job_index = self.job_index.clone();
+
+        // Keep the log lines of the blocking task in the tool's span
+        let span = tracing::Span::current();
+        let result = tokio::task::spawn_blocking(move || {
+            span.in_scope(|| run_maintenance(&storage, &job_index, retention_days))
+        })
+        .await??;
+        Ok(CompactStorageResult {
+            request_id,
+            ..result
+        })
+    }
+
+    /// Show what the server keeps in local storage
+    ///
+    /// Reports the storage backend and its size on disk, the number and size of
+    /// stored favorites, saved searches, seen jobs, notes, job snapshots,
+    /// watchlists and history entries, and the size of the full-text index.
+    ///
+    /// # Examples
+    /// - `{}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_storage_stats(
+        &self,
+        _params: GetStorageStatsParams,
+    ) -> anyhow::Result<GetStorageStatsResult> {
+        let request_id = record_request_id();
+        let stats = storage_stats(&self.storage, &self.job_index, self.config.retention_days)?;
+        Ok(GetStorageStatsResult {
+            request_id,
+            ..stats
+        })
+    }
+
+    /// Save a job to the persistent favorites list
+    ///
+    /// Stores the reference number together with a snapshot of title, employer,
+    /// location, salary, start date and application deadline, plus optional
+    /// labels. Saving a job that is already a
+    /// favorite refreshes the snapshot and adds the new labels.
+    ///
+    /// # Examples
+    /// - Save a job: `{"reference_number": "10001-1234567890-S"}`
+    /// - Save with labels: `{"reference_number": "10001-1234567890-S", "labels": ["apply", "remote"]}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn save_favorite(
+        &self,
+        params: SaveFavoriteParams,
+    ) -> anyhow::Result<SaveFavoriteResult> {
+        let request_id = record_request_id();
+        info!("Saving favorite: {}", params.reference_number);
+
+        let details = self
+            .job_details(GetJobDetailsParams {
+                reference_number: params.reference_number.clone(),
+                occupation_code: None,
+                lang: None,
+                resolve_external_url: None,
+                profile_match: None,
+                detail_level: None,
+                include_raw: None,
+                fields: None,
+            })
+            .await?;
+
+        let result = self.store_favorite(&details, params.labels.unwrap_or_default())?;
+        Ok(SaveFavoriteResult {
+            request_id,
+            ..result
+        })
+    }
+
+    /// List saved favorite jobs
+    ///
+    /// Returns all favorites (most recently saved first), optionally only those
+    /// carrying a given label.
+    ///
+    /// # Examples
+    /// - All favorites: `{}`
+    /// - By label: `{"label": "apply"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn list_favorites(
+        &self,
+        params: ListFavoritesParams,
+    ) -> anyhow::Result<ListFavoritesResult> {
+        let request_id = record_request_id();
+        let mut favorites: Vec<Favorite> = self
+            .storage
+            .list::<Favorite>(FAVORITES)
+            .into_iter()
+            .filter(|favorite| match params.label {
+                Some(ref label) => favorite.has_label(label),
+                None => true,
+            })
+            .collect();
+        favorites.sort_by(|a, b| b.saved_at.cmp(&a.saved_at));
+
+        info!("Listing {} favorites", favorites.len());
+        Ok(ListFavoritesResult {
+            request_id,
+            favorites_count: favorites.len(),
+            favorites,
+        })
+    }
+
+    /// Remove a job from the favorites list
+    ///
+    /// # Examples
+    /// - Remove a favorite: `{"reference_number": "10001-1234567890-S"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn remove_favorite(
+        &self,
+        params: RemoveFavoriteParams,
+    ) -> anyhow::Result<RemoveFavoriteResult> {
+        let request_id = record_request_id();
+        let removed = self.storage.remove(FAVORITES, &params.reference_number)?;
+        info!(
+            "Remove favorite {}: {}",
+            params.reference_number,
+            if removed { "removed" } else { "not found" }
+        );
+
+        Ok(RemoveFavoriteResult {
+            request_id,
+            reference_number: params.reference_number,
+            removed,
+        })
+    }
+
+    /// Save a named search for later use
+    ///
+    /// Stores a set of search_jobs parameters under a name, so the search can be
+    /// repeated with run_saved_search. An existing search with the same name
+    /// (case-insensitive) is replaced.
+    ///
+    /// # Examples
+    /// - `{"name": "Berlin backend", "search": {"job_title": "Backend Developer", "location": "Berlin", "employment_type": ["fulltime"]}}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn create_saved_search(
+        &self,
+        params: CreateSavedSearchParams,
+    ) -> anyhow::Result<CreateSavedSearchResult> {
+        let request_id = record_request_id();
+        let key = name_key(&params.name)?;
+        let now = now_rfc3339();
+        let existing: Option<SavedSearch> = self.storage.get(SAVED_SEARCHES, &key)?;
+        let replaced = existing.is_some();
+
+        let saved_search = SavedSearch {
+            name: params.name.trim().to_string(),
+            search: params.search,
+            created_at: existing
+                .map(|search| search.created_at)
+                .unwrap_or_else(|| now.clone()),
+            updated_at: now,
+        };
+        self.storage.put(SAVED_SEARCHES, &key, &saved_search)?;
+
+        info!(
+            "Saved search '{}' ({})",
+            saved_search.name,
+            if replaced { "replaced" } else { "created" }
+        );
+        Ok(CreateSavedSearchResult {
+            request_id,
+            saved_search,
+            replaced,
+        })
+    }
 
-        let publication_period = details.veroeffentlichungszeitraum.as_ref().map(|dr| {
-            match (&dr.von, &dr.bis) {
-                (Some(von), Some(bis)) => format!("{} - {}", von, bis),
-                (Some(von), None) => format!("ab {}", von),
-                (None, Some(bis)) => format!("bis {}", bis),
-                (None, None) => String::new(),
-            }
-        });
+    /// List all saved searches
+    ///
+    /// Returns the stored parameters of every saved search, ordered by name.
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn list_saved_searches(
+        &self,
+        _params: ListSavedSearchesParams,
+    ) -> anyhow::Result<ListSavedSearchesResult> {
+        let request_id = record_request_id();
+        let saved_searches: Vec<SavedSearch> = self.storage.list(SAVED_SEARCHES);
+
+        info!("Listing {} saved searches", saved_searches.len());
+        Ok(ListSavedSearchesResult {
+            request_id,
+            saved_searches_count: saved_searches.len(),
+            saved_searches,
+        })
+    }
 
-        let result = GetJobDetailsResult {
-            reference_number: params.reference_number.clone(),
-            title: details.titel,
-            description: details.stellenbeschreibung,
-            employer: details.arbeitgeber,
-            location: location_str,
-            employment_type: details
-                .arbeitszeit_vollzeit
-                .map(|vz| if vz { "Vollzeit" } else { "Teilzeit" }.to_string()),
-            contract_type: None, // Not available in API v0.3.0
-            start_date: entry_period.clone(),
-            application_deadline: None, // Not available in API
-            contact_info: None,         // Not available in API
-            external_url: None,         // Note: May be available in search results, not in details
-            employer_profile_url: None, // Not available in API v0.3.0
-            partner_url: details.allianzpartner_url,
-            salary: details.verguetung,
-            contract_duration: details.vertragsdauer,
-            takeover_opportunity: None, // Not available in API v0.3.0
-            job_type: details.stellenangebots_art,
-            open_positions: None,        // Not available in API v0.3.0
-            company_size: None,          // Not available in API v0.3.0
-            employer_description: None,  // Not available in API v0.3.0
-            branch: None,                // Not available in API v0.3.0
-            published_date: None,        // Not available in API v0.3.0
-            first_published: details.erste_veroeffentlichungsdatum,
-            only_for_disabled: details.nur_fuer_schwerbehinderte,
-            fulltime: details.arbeitszeit_vollzeit,
-            entry_period,
-            publication_period,
-            is_minor_employment: details.ist_geringfuegige_beschaeftigung,
-            is_temp_agency: details.ist_arbeitnehmer_ueberlassung,
-            is_private_agency: details.ist_private_arbeitsvermittlung,
-            career_changer_suitable: details.quereinstieg_geeignet,
-            cipher_number: details.chiffrenummer,
-            raw_data,
-        };
+    /// Delete a saved search
+    ///
+    /// Deleting a name that does not exist is not an error; `deleted` is then false.
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn delete_saved_search(
+        &self,
+        params: DeleteSavedSearchParams,
+    ) -> anyhow::Result<DeleteSavedSearchResult> {
+        let request_id = record_request_id();
+        let key = name_key(&params.name)?;
+        let deleted = self.storage.remove(SAVED_SEARCHES, &key)?;
+        info!(
+            "Delete saved search '{}': {}",
+            params.name,
+            if deleted { "deleted" } else { "not found" }
+        );
 
-        info!("Job details retrieved successfully");
-        Ok(result)
+        Ok(DeleteSavedSearchResult {
+            request_id,
+            name: params.name,
+            deleted,
+        })
     }
 
-    /// Search for jobs and automatically fetch details for top results
+    /// Run a saved search
     ///
-    /// This tool combines search_jobs and get_job_details into a single operation,
-    /// making it more efficient for AI workflows. It searches for jobs and automatically
-    /// fetches full details for the top results.
+    /// Executes the stored parameters like search_jobs. `page` and `hide_seen`
+    /// override the stored values, e.g. to page through results or to only see
+    /// new jobs.
     ///
     /// # Examples
-    /// - Search with auto-details: `{"location": "Wuppertal", "employment_type": ["parttime"], "max_details": 5}`
-    /// - With field filtering: `{"employer": "BARMER", "location": "Wuppertal", "max_details": 3, "fields": {"include_fields": ["title", "salary", "description"]}}`
-    #[instrument(skip(self))]
-    pub async fn search_jobs_with_details(
+    /// - `{"name": "Berlin backend"}`
+    /// - Only new jobs: `{"name": "Berlin backend", "hide_seen": true}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn run_saved_search(
         &self,
-        params: SearchJobsWithDetailsParams,
-    ) -> anyhow::Result<SearchJobsWithDetailsResult> {
-        info!("Searching jobs with automatic detail fetching");
-        let search_start = Instant::now();
+        params: RunSavedSearchParams,
+    ) -> anyhow::Result<RunSavedSearchResult> {
+        let request_id = record_request_id();
+        let key = name_key(&params.name)?;
+        let saved_search: SavedSearch = self
+            .storage
+            .get(SAVED_SEARCHES, &key)?
+            .ok_or_else(|| anyhow::anyhow!("No saved search named '{}'", params.name))?;
+
+        info!("Running saved search '{}'", saved_search.name);
+        let search = params.apply_to(saved_search.search);
+        let result = self.execute_user_search(search.clone()).await?;
+
+        Ok(RunSavedSearchResult {
+            request_id,
+            name: saved_search.name,
+            search,
+            result,
+        })
+    }
 
-        // Convert to SearchJobsParams
-        let search_params = SearchJobsParams {
-            job_title: params.job_title,
-            location: params.location,
-            radius_km: params.radius_km,
-            employment_type: params.employment_type,
-            contract_type: params.contract_type,
-            published_since_days: params.published_since_days,
-            page_size: params.page_size,
-            page: params.page,
-            employer: params.employer,
-            branch: params.branch,
+    /// Add a note to a job
+    ///
+    /// Attaches a free-text note to a reference number, e.g. the outcome of a
+    /// call with the recruiter. Notes are stored locally and included in
+    /// get_job_details responses.
+    ///
+    /// # Examples
+    /// - `{"reference_number": "10001-1234567890-S", "text": "Called recruiter, asked about salary"}`
+    #[instrument(
+        skip(self, params),
+        fields(reference_number = %params.reference_number, request_id),
+        err
+    )]
+    pub async fn add_job_note(&self, params: AddJobNoteParams) -> anyhow::Result<AddJobNoteResult> {
+        let request_id = record_request_id();
+        let note = JobNote {
+            text: note_text(&params.text)?,
+            created_at: now_rfc3339(),
         };
 
-        // Perform search
-        let search_result = self.search_jobs(search_params).await?;
-        let search_duration = search_start.elapsed();
+        let notes_count = self.storage.update(
+            JOB_NOTES,
+            &params.reference_number,
+            |notes: &mut Vec<JobNote>| {
+                notes.push(note.clone());
+                notes.len()
+            },
+        )?;
+
+        info!("Added note to {}", params.reference_number);
+        Ok(AddJobNoteResult {
+            request_id,
+            reference_number: params.reference_number,
+            note,
+            notes_count,
+        })
+    }
 
-        // Determine how many details to fetch (conservative defaults to respect rate limits)
-        let max_details = params.max_details.unwrap_or(3).min(10);
-        let jobs_to_fetch = search_result
-            .jobs
-            .iter()
-            .take(max_details as usize)
-            .collect::<Vec<_>>();
+    /// Get the notes attached to a job
+    ///
+    /// Returns all notes for the reference number, oldest first.
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_job_notes(
+        &self,
+        params: GetJobNotesParams,
+    ) -> anyhow::Result<GetJobNotesResult> {
+        let request_id = record_request_id();
+        let notes: Vec<JobNote> = self
+            .storage
+            .get(JOB_NOTES, &params.reference_number)?
+            .unwrap_or_default();
+
+        Ok(GetJobNotesResult {
+            request_id,
+            reference_number: params.reference_number,
+            notes,
+        })
+    }
 
-        info!("Fetching details for {} jobs", jobs_to_fetch.len());
-        let details_start = Instant::now();
+    /// Create a watchlist from a search definition
+    ///
+    /// Runs the search once to record the current postings as baseline, so
+    /// check_watchlists only reports postings that appear afterwards. An
+    /// existing watchlist with the same name (case-insensitive) is replaced.
+    ///
+    /// # Examples
+    /// - `{"name": "Rust Berlin", "search": {"job_title": "Rust", "location": "Berlin", "published_since_days": 7, "page_size": 100}}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn create_watchlist(
+        &self,
+        params: CreateWatchlistParams,
+    ) -> anyhow::Result<CreateWatchlistResult> {
+        let request_id = record_request_id();
+        let key = name_key(&params.name)?;
 
-        // Fetch details for each job with delay to respect rate limits
-        let mut jobs_with_details = Vec::new();
-        for (idx, job) in jobs_to_fetch.iter().enumerate() {
-            // Small delay between requests to avoid rate limiting (except first)
-            if idx > 0 {
-                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-            }
+        // Watchlists track new postings themselves, hiding globally seen jobs would skip them
+        let mut search = params.search;
+        search.hide_seen = None;
 
-            match self
-                .get_job_details(GetJobDetailsParams {
-                    reference_number: job.reference_number.clone(),
-                })
-                .await
-            {
-                Ok(details) => jobs_with_details.push(details),
-                Err(e) => {
-                    info!(
-                        "Failed to fetch details for {}: {}",
-                        job.reference_number, e
-                    );
-                    // Continue with other jobs even if one fails
-                }
-            }
-        }
+        let baseline = self.execute_search(search.clone()).await?;
+        let watchlist = Watchlist::new(&params.name, search, &baseline.jobs, &now_rfc3339());
+        let replaced = self.storage.get::<Watchlist>(WATCHLISTS, &key)?.is_some();
+        self.storage.put(WATCHLISTS, &key, &watchlist)?;
 
-        let details_duration = details_start.elapsed();
+        info!(
+            "Created watchlist '{}' with {} known jobs",
+            watchlist.name,
+            watchlist.known_jobs.len()
+        );
+        Ok(CreateWatchlistResult {
+            request_id,
+            watchlist: watchlist.into(),
+            replaced,
+        })
+    }
+
+    /// List all watchlists
+    ///
+    /// Returns name, search parameters, last check time and number of known
+    /// postings of every watchlist, ordered by name.
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn list_watchlists(
+        &self,
+        _params: ListWatchlistsParams,
+    ) -> anyhow::Result<ListWatchlistsResult> {
+        let request_id = record_request_id();
+        let watchlists: Vec<WatchlistInfo> = self
+            .storage
+            .list::<Watchlist>(WATCHLISTS)
+            .into_iter()
+            .map(WatchlistInfo::from)
+            .collect();
+
+        Ok(ListWatchlistsResult {
+            request_id,
+            watchlists_count: watchlists.len(),
+            watchlists,
+        })
+    }
 
+    /// Delete a watchlist
+    ///
+    /// Deleting a name that does not exist is not an error; `deleted` is then false.
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn delete_watchlist(
+        &self,
+        params: DeleteWatchlistParams,
+    ) -> anyhow::Result<DeleteWatchlistResult> {
+        let request_id = record_request_id();
+        let key = name_key(&params.name)?;
+        let deleted = self.storage.remove(WATCHLISTS, &key)?;
         info!(
-            "Search completed: {} jobs found, {} details fetched",
-            search_result.total_results.unwrap_or(0),
-            jobs_with_details.len()
+            "Delete watchlist '{}': {}",
+            params.name,
+            if deleted { "deleted" } else { "not found" }
         );
 
-        Ok(SearchJobsWithDetailsResult {
-            total_results: search_result.total_results,
-            current_page: search_result.current_page,
-            page_size: search_result.page_size,
-            jobs_count: jobs_with_details.len(),
-            jobs: jobs_with_details,
-            search_duration_ms: search_duration.as_millis() as u64,
-            details_duration_ms: details_duration.as_millis() as u64,
+        Ok(DeleteWatchlistResult {
+            request_id,
+            name: params.name,
+            deleted,
         })
     }
 
-    /// Perform multiple job searches in a single operation
+    /// Check watchlists for new postings
     ///
-    /// This tool allows you to search for different types of jobs simultaneously,
-    /// making it perfect for comparing opportunities across employers, locations,
-    /// or job types. Each search can have different parameters and will return
-    /// results independently.
+    /// Re-runs the search of each watchlist and returns only postings that did
+    /// not appear in earlier checks, with the time they were first seen. If a
+    /// webhook is configured, it is notified about each watchlist with new jobs.
     ///
     /// # Examples
-    /// - Compare employers: `{"searches": [{"name": "BARMER", "employer": "BARMER", "location": "Wuppertal"}, {"name": "Siemens", "employer": "Siemens", "location": "Wuppertal"}], "max_details_per_search": 3}`
-    /// - Different job types: `{"searches": [{"name": "Sekretariat", "job_title": "Sekretärin"}, {"name": "Sport", "job_title": "Schwimm"}]}`
-    #[instrument(skip(self))]
-    pub async fn batch_search_jobs(
+    /// - Check all watchlists: `{}`
+    /// - Check one watchlist: `{"name": "Rust Berlin"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn check_watchlists(
         &self,
-        params: BatchSearchJobsParams,
-    ) -> anyhow::Result<BatchSearchJobsResult> {
-        let start = Instant::now();
-        let searches_count = params.searches.len().min(5); // Limit to 5 searches to respect rate limits
-
-        info!("Performing batch search with {} searches", searches_count);
+        params: CheckWatchlistsParams,
+    ) -> anyhow::Result<CheckWatchlistsResult> {
+        let request_id = record_request_id();
+        let watchlists: Vec<Watchlist> = match params.name {
+            Some(ref name) => {
+                let watchlist = self
+                    .storage
+                    .get(WATCHLISTS, &name_key(name)?)?
+                    .ok_or_else(|| anyhow::anyhow!("No watchlist named '{}'", name))?;
+                vec![watchlist]
+            }
+            None => self.storage.list(WATCHLISTS),
+        };
+        self.quota.check_budget("check_watchlists")?;
+        info!("Checking {} watchlists", watchlists.len());
 
-        let max_details = params.max_details_per_search.unwrap_or(2).min(5);
+        let mut progress = ProgressReporter::new(self.progress_sink.clone());
+        progress.set_total(watchlists.len() as u64);
+        let cancellation = RequestContext::current().cancellation;
+        let checked_at = now_rfc3339();
         let mut results = Vec::new();
+        let mut cancelled = false;
 
-        // Process each search
-        for (search_idx, search_item) in params.searches.iter().take(searches_count).enumerate() {
+        for (idx, mut watchlist) in watchlists.into_iter().enumerate() {
             // Small delay between searches to avoid rate limiting (except first)
-            if search_idx > 0 {
-                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            if idx > 0 {
+                let delay = tokio::time::sleep(std::time::Duration::from_millis(200));
+                if run_cancellable(&cancellation, delay).await.is_none() {
+                    cancelled = true;
+                    break;
+                }
             }
-            info!("Processing search: {}", search_item.name);
-
-            // Convert to SearchJobsParams
-            let search_params = SearchJobsParams {
-                job_title: search_item.job_title.clone(),
-                location: search_item.location.clone(),
-                radius_km: search_item.radius_km,
-                employment_type: search_item.employment_type.clone(),
-                contract_type: search_item.contract_type.clone(),
-                published_since_days: search_item.published_since_days,
-                page_size: Some(max_details),
-                page: None,
-                employer: search_item.employer.clone(),
-                branch: search_item.branch.clone(),
-            };
 
-            // Perform search
-            let search_result = match self.search_jobs(search_params).await {
-                Ok(result) => result,
-                Err(e) => {
-                    // If search fails, add error result and continue
-                    results.push(BatchSearchItemResult {
-                        search_name: search_item.name.clone(),
-                        total_results: None,
-                        jobs_count: 0,
-                        jobs: Vec::new(),
-                        error: Some(format!("Search failed: {}", e)),
-                    });
-                    continue;
-                }
+            let Some(search_outcome) =
+                run_cancellable(&cancellation, self.execute_search(watchlist.search.clone())).await
+            else {
+                cancelled = true;
+                break;
             };
 
-            // Fetch details if requested (with delay to respect rate limits)
-            let mut jobs_with_details = Vec::new();
-            if max_details > 0 {
-                for (detail_idx, job) in search_result.jobs.iter().take(max_details as usize).enumerate() {
-                    // Small delay between detail fetches (except first in this search)
-                    if detail_idx > 0 {
-                        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            let result = match search_outcome {
+                Ok(search_result) => {
+                    let new_jobs = watchlist.absorb(search_result.jobs, &checked_at);
+                    self.storage
+                        .put(WATCHLISTS, &name_key(&watchlist.name)?, &watchlist)?;
+                    if !new_jobs.is_empty() {
+                        self.notify_watchlist(WatchlistAlert {
+                            watchlist: watchlist.name.clone(),
+                            checked_at: checked_at.clone(),
+                            new_jobs: new_jobs.clone(),
+                        });
                     }
-
-                    match self
-                        .get_job_details(GetJobDetailsParams {
-                            reference_number: job.reference_number.clone(),
-                        })
-                        .await
-                    {
-                        Ok(details) => jobs_with_details.push(details),
-                        Err(e) => {
-                            info!(
-                                "Failed to fetch details for {} in search '{}': {}",
-                                job.reference_number, search_item.name, e
-                            );
-                            // Continue with other jobs even if one fails
-                        }
+                    WatchlistCheckResult {
+                        name: watchlist.name.clone(),
+                        total_results: search_result.total_results,
+                        new_jobs_count: new_jobs.len(),
+                        new_jobs,
+                        error: None,
                     }
                 }
-            }
+                Err(e) => WatchlistCheckResult {
+                    name: watchlist.name.clone(),
+                    total_results: None,
+                    new_jobs_count: 0,
+                    new_jobs: Vec::new(),
+                    error: Some(format!("Search failed: {}", e)),
+                },
+            };
 
-            results.push(BatchSearchItemResult {
-                search_name: search_item.name.clone(),
-                total_results: search_result.total_results,
-                jobs_count: jobs_with_details.len(),
-                jobs: jobs_with_details,
-                error: None,
-            });
+            progress.advance(format!("Watchlist '{}' checked", watchlist.name));
+            results.push(result);
         }
 
-        let duration = start.elapsed();
-        info!(
-            "Batch search completed: {} searches in {:?}",
-            results.len(),
-            duration
-        );
+        let total_new_jobs = results.iter().map(|result| result.new_jobs_count).sum();
+        info!("Watchlist check found {} new jobs", total_new_jobs);
 
-        Ok(BatchSearchJobsResult {
-            searches_count: results.len(),
+        Ok(CheckWatchlistsResult {
+            request_id,
+            checked_at,
+            watchlists_checked: results.len(),
+            total_new_jobs,
             results,
-            total_duration_ms: duration.as_millis() as u64,
+            cancelled,
+        })
+    }
+
+    /// Get new postings found by scheduled runs
+    ///
+    /// With JOBSUCHE_WATCHLIST_SCHEDULE or JOBSUCHE_SAVED_SEARCH_SCHEDULE set,
+    /// watchlists and saved searches are run in the background, also while no
+    /// client is connected. Returns the postings these runs found for the first
+    /// time since the given time, merged per watchlist and saved search.
+    ///
+    /// # Examples
+    /// - New since yesterday: `{}`
+    /// - New since a date: `{"since": "2025-10-20"}`
+    /// - One watchlist: `{"name": "Rust Berlin", "since": "2025-10-20T08:00:00Z"}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_scheduled_updates(
+        &self,
+        params: GetScheduledUpdatesParams,
+    ) -> anyhow::Result<GetScheduledUpdatesResult> {
+        let request_id = record_request_id();
+        let since = parse_since(params.since.as_deref())?;
+        let log: ScheduledRunLog = self
+            .storage
+            .get(SCHEDULED_RUNS, RUNS_KEY)?
+            .unwrap_or_default();
+
+        let config = &self.config;
+        let schedules = [
+            (ScheduledSource::Watchlist, &config.watchlist_schedule),
+            (ScheduledSource::SavedSearch, &config.saved_search_schedule),
+        ]
+        .into_iter()
+        .filter_map(|(source, expression)| {
+            let schedule: Schedule = expression.as_deref()?.parse().ok()?;
+            Some(ScheduleInfo::new(source, &schedule, &log))
+        })
+        .collect();
+
+        let updates = log.updates_since(since, params.name.as_deref());
+        let total_new_jobs = updates.iter().map(|update| update.new_jobs_count).sum();
+
+        Ok(GetScheduledUpdatesResult {
+            request_id,
+            since: since.format(&Rfc3339)?,
+            schedules,
+            total_new_jobs,
+            updates,
+        })
+    }
+
+    /// Get the history of executed searches
+    ///
+    /// Every search (including bulk and saved searches) is recorded with its
+    /// parameters, time and number of results. Use the `index` of an entry with
+    /// rerun_search_history to repeat it.
+    ///
+    /// # Examples
+    /// - Last 20 searches: `{}`
+    /// - Last 5 searches: `{"limit": 5}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn get_search_history(
+        &self,
+        params: GetSearchHistoryParams,
+    ) -> anyhow::Result<GetSearchHistoryResult> {
+        let request_id = record_request_id();
+        let history: SearchHistory = self
+            .storage
+            .get(SEARCH_HISTORY, HISTORY_KEY)?
+            .unwrap_or_default();
+        let entries = history.latest(params.limit.unwrap_or(20));
+
+        info!("Returning {} history entries", entries.len());
+        Ok(GetSearchHistoryResult {
+            request_id,
+            entries_count: entries.len(),
+            total_entries: history.entries.len(),
+            entries,
+        })
+    }
+
+    /// Re-run a search from the history
+    ///
+    /// Executes the recorded parameters of the entry with the given index again.
+    ///
+    /// # Examples
+    /// - `{"index": 42}`
+    /// - Next page of an earlier search: `{"index": 42, "page": 2}`
+    #[instrument(skip(self), fields(request_id), err)]
+    pub async fn rerun_search_history(
+        &self,
+        params: RerunSearchHistoryParams,
+    ) -> anyhow::Result<RerunSearchHistoryResult> {
+        let request_id = record_request_id();
+        let history: SearchHistory = self
+            .storage
+            .get(SEARCH_HISTORY, HISTORY_KEY)?
+            .unwrap_or_default();
+        let entry = history.find(params.index).ok_or_else(|| {
+            anyhow::anyhow!(
+                "No search history entry with index {} (it may have been dropped)",
+                params.index
+            )
+        })?;
+
+        let mut search = entry.search.clone();
+        if params.page.is_some() {
+            search.page = params.page;
+        }
+
+        info!("Re-running search history entry {}", params.index);
+        let result = self.execute_user_search(search.clone()).await?;
+
+        Ok(RerunSearchHistoryResult {
+            request_id,
+            index: params.index,
+            search,
+            result,
         })
     }
 
@@ -852,28 +6112,36 @@ impl JobsucheMcpServer {
     ///
     /// Returns information about the server status, uptime, API configuration,
     /// and available tools.
-    #[instrument(skip(self))]
+    #[instrument(skip(self), fields(request_id), err)]
     pub async fn get_server_status(&self) -> anyhow::Result<JobsucheServerStatus> {
+        let request_id = record_request_id();
         info!("Getting server status");
 
         // Test API connectivity by making a minimal search
         let connection_status = match self
-            .client
-            .search()
-            .list(SearchOptions::builder().size(1).build())
+            .search_api(SearchOptions::builder().size(1).build())
             .await
         {
             Ok(_) => "Connected".to_string(),
             Err(e) => format!("Connection Error: {}", e),
         };
 
+        let build = BuildInfo::current();
         Ok(JobsucheServerStatus {
+            request_id,
             server_name: "Jobsuche MCP Server".to_string(),
-            version: "0.3.0".to_string(),
+            version: build.version.clone(),
             uptime_seconds: self.get_uptime_seconds(),
             api_url: self.config.api_url.clone(),
             api_connection_status: connection_status,
-            tools_count: 5, // search_jobs, get_job_details, search_jobs_with_details, batch_search_jobs, get_server_status
+            tools_count: TOOLS.len(),
+            enabled_tools: enabled_tools(&self.config),
+            features: feature_status(&self.config),
+            resources_count: RESOURCES.len(),
+            resource_templates_count: RESOURCE_TEMPLATES.len(),
+            prompts_count: 0, // The server offers no prompts
+            api_usage: self.quota.usage(),
+            build,
         })
     }
 }
@@ -958,13 +6226,23 @@ mod tests {
             job_title: Some("Software Engineer".to_string()),
             location: Some("Berlin".to_string()),
             radius_km: Some(50),
+            nationwide: None,
             employment_type: Some(vec!["fulltime".to_string()]),
             contract_type: None,
             published_since_days: Some(7),
+            freshness: None,
             page_size: Some(25),
             page: Some(1),
             employer: None,
             branch: None,
+            hide_seen: None,
+            english_postings_only: None,
+            collapse_duplicates: None,
+            exclude_locations: None,
+            country: None,
+            include_abroad: None,
+            sort_by: None,
+            fields: None,
         };
 
         let json = serde_json::to_string(&params).unwrap();
@@ -972,19 +6250,37 @@ mod tests {
         assert!(json.contains("Berlin"));
     }
 
+    #[test]
+    fn test_parse_distance_km() {
+        assert_eq!(JobsucheMcpServer::parse_distance_km("12"), Some(12.0));
+        assert_eq!(JobsucheMcpServer::parse_distance_km("3,5 km"), Some(3.5));
+        assert_eq!(JobsucheMcpServer::parse_distance_km(""), None);
+        assert_eq!(JobsucheMcpServer::parse_distance_km("-1"), None);
+    }
+
     #[test]
     fn test_search_params_with_employer() {
         let params = SearchJobsParams {
             job_title: Some("Kundenberaterin".to_string()),
             location: Some("Wuppertal".to_string()),
             radius_km: None,
+            nationwide: None,
             employment_type: Some(vec!["parttime".to_string()]),
             contract_type: None,
             published_since_days: None,
+            freshness: None,
             page_size: None,
             page: None,
             employer: Some("BARMER".to_string()),
             branch: None,
+            hide_seen: None,
+            english_postings_only: None,
+            collapse_duplicates: None,
+            exclude_locations: None,
+            country: None,
+            include_abroad: None,
+            sort_by: None,
+            fields: None,
         };
 
         let json = serde_json::to_string(&params).unwrap();
@@ -998,13 +6294,23 @@ mod tests {
             job_title: None,
             location: Some("München".to_string()),
             radius_km: Some(25),
+            nationwide: None,
             employment_type: None,
             contract_type: None,
             published_since_days: Some(14),
+            freshness: None,
             page_size: None,
             page: None,
             employer: None,
             branch: Some("IT".to_string()),
+            hide_seen: None,
+            english_postings_only: None,
+            collapse_duplicates: None,
+            exclude_locations: None,
+            country: None,
+            include_abroad: None,
+            sort_by: None,
+            fields: None,
         };
 
         let json = serde_json::to_string(&params).unwrap();
@@ -1015,12 +6321,20 @@ mod tests {
     #[test]
     fn test_server_status_serialization() {
         let status = JobsucheServerStatus {
+            request_id: String::new(),
             server_name: "Test Server".to_string(),
             version: "0.3.0".to_string(),
             uptime_seconds: 3600,
             api_url: "https://test.api".to_string(),
             api_connection_status: "Connected".to_string(),
             tools_count: 5,
+            enabled_tools: vec!["search_jobs".to_string()],
+            features: Vec::new(),
+            resources_count: 1,
+            resource_templates_count: 1,
+            prompts_count: 0,
+            api_usage: QuotaTracker::new(None).usage(),
+            build: BuildInfo::current(),
         };
 
         let json = serde_json::to_string(&status).unwrap();
@@ -1036,8 +6350,16 @@ mod tests {
             title: "Test Job".to_string(),
             employer: "Test Company".to_string(),
             location: "Test City".to_string(),
+            distance_km: None,
+            coordinates: None,
+            country: None,
             published_date: Some("2025-01-01".to_string()),
+            published_date_raw: None,
+            published_days_ago: None,
+            published_ago: None,
             external_url: None,
+            web_url: String::new(),
+            duplicates: Vec::new(),
         };
 
         let json = serde_json::to_string(&summary).unwrap();
@@ -1050,12 +6372,32 @@ mod tests {
     fn test_default_panics() {
         let _ = JobsucheMcpServer::default();
     }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_new_with_store_uses_the_store() {
+        let store: Arc<dyn JobStore> = Arc::new(MemoryStore::new());
+        let server = JobsucheMcpServer::new_with_store(store.clone())
+            .await
+            .unwrap();
+
+        server.storage.put(JOB_NOTES, "TEST-123", &"note").unwrap();
+        let stored: Option<String> = Storage::new(store).get(JOB_NOTES, "TEST-123").unwrap();
+        assert_eq!(stored.as_deref(), Some("note"));
+    }
 }
 
 #[test]
 fn test_get_job_details_params_serialization() {
     let params = GetJobDetailsParams {
         reference_number: "TEST-REF-123".to_string(),
+        occupation_code: None,
+        lang: None,
+        resolve_external_url: None,
+        profile_match: None,
+        detail_level: None,
+        include_raw: None,
+        fields: None,
     };
 
     let json = serde_json::to_string(&params).unwrap();
@@ -1065,9 +6407,11 @@ fn test_get_job_details_params_serialization() {
 #[test]
 fn test_job_details_result_with_location() {
     let result = GetJobDetailsResult {
+        request_id: String::new(),
         reference_number: "TEST-123".to_string(),
         title: Some("Test Title".to_string()),
         description: Some("Test Description".to_string()),
+        description_language: None,
         employer: Some("Test Employer".to_string()),
         location: Some("Test Location".to_string()),
         employment_type: Some("Vollzeit".to_string()),
@@ -1076,9 +6420,19 @@ fn test_job_details_result_with_location() {
         application_deadline: None,
         contact_info: None,
         external_url: None,
+        resolved_external_url: None,
+        identifier_type: None,
+        application_domain: None,
+        web_url: String::new(),
         employer_profile_url: None,
         partner_url: None,
         salary: Some("45.000 - 55.000 EUR".to_string()),
+        salary_parsed: parse_salary("45.000 - 55.000 EUR"),
+        salary_estimate: None,
+        extracted_skills: Vec::new(),
+        language_requirements: Vec::new(),
+        mentions_visa_sponsorship: false,
+        mentions_relocation: false,
         contract_duration: None,
         takeover_opportunity: None,
         job_type: Some("arbeitsstelle".to_string()),
@@ -1088,16 +6442,23 @@ fn test_job_details_result_with_location() {
         branch: None,
         published_date: None,
         first_published: Some("2025-10-15".to_string()),
+        first_published_raw: None,
+        published_days_ago: None,
+        published_ago: None,
         only_for_disabled: Some(false),
         fulltime: Some(true),
-        entry_period: Some("ab 2025-01-01".to_string()),
+        entry_period: Some("2025-01-01/..".to_string()),
+        entry_period_raw: None,
         publication_period: None,
+        publication_period_raw: None,
         is_minor_employment: Some(false),
         is_temp_agency: Some(false),
         is_private_agency: Some(false),
         career_changer_suitable: Some(true),
         cipher_number: None,
         raw_data: serde_json::json!({}),
+        notes: vec![],
+        profile_match: None,
     };
 
     assert_eq!(result.reference_number, "TEST-123");
@@ -1110,12 +6471,19 @@ fn test_job_details_result_with_location() {
 #[test]
 fn test_search_results_empty() {
     let result = SearchJobsResult {
+        request_id: String::new(),
         total_results: Some(0),
         current_page: Some(1),
         page_size: Some(25),
         jobs_count: 0,
         jobs: vec![],
+        hidden_seen_count: 0,
+        duplicates_collapsed: 0,
+        employer_filtered_count: 0,
+        location_excluded_count: 0,
         search_duration_ms: 100,
+        effective_query: EffectiveQuery::default(),
+        relaxations: Vec::new(),
     };
 
     assert_eq!(result.jobs_count, 0);
@@ -1130,26 +6498,49 @@ fn test_search_results_with_jobs() {
             title: "Job 1".to_string(),
             employer: "Company 1".to_string(),
             location: "Berlin".to_string(),
+            distance_km: None,
+            coordinates: None,
+            country: None,
             published_date: Some("2025-01-01".to_string()),
+            published_date_raw: None,
+            published_days_ago: None,
+            published_ago: None,
             external_url: None,
+            web_url: String::new(),
+            duplicates: Vec::new(),
         },
         JobSummary {
             reference_number: "JOB-2".to_string(),
             title: "Job 2".to_string(),
             employer: "Company 2".to_string(),
             location: "München".to_string(),
+            distance_km: None,
+            coordinates: None,
+            country: None,
             published_date: Some("2025-01-02".to_string()),
+            published_date_raw: None,
+            published_days_ago: None,
+            published_ago: None,
             external_url: Some("https://example.com".to_string()),
+            web_url: String::new(),
+            duplicates: Vec::new(),
         },
     ];
 
     let result = SearchJobsResult {
+        request_id: String::new(),
         total_results: Some(2),
         current_page: Some(1),
         page_size: Some(25),
         jobs_count: 2,
         jobs: jobs.clone(),
+        hidden_seen_count: 0,
+        duplicates_collapsed: 0,
+        employer_filtered_count: 0,
+        location_excluded_count: 0,
         search_duration_ms: 150,
+        effective_query: EffectiveQuery::default(),
+        relaxations: Vec::new(),
     };
 
     assert_eq!(result.jobs_count, 2);
@@ -1162,19 +6553,63 @@ fn test_search_results_with_jobs() {
     );
 }
 
+#[test]
+fn test_post_filter_accumulates_counts() {
+    let job = |reference_number: &str, employer: &str, location: &str| JobSummary {
+        reference_number: reference_number.to_string(),
+        title: "Job".to_string(),
+        employer: employer.to_string(),
+        location: location.to_string(),
+        distance_km: None,
+        coordinates: None,
+        country: None,
+        published_date: None,
+        published_date_raw: None,
+        published_days_ago: None,
+        published_ago: None,
+        external_url: None,
+        web_url: String::new(),
+        duplicates: Vec::new(),
+    };
+    let params: SearchJobsParams =
+        serde_json::from_value(serde_json::json!({ "exclude_locations": ["Potsdam"] })).unwrap();
+    let employer_filter = EmployerFilter::from_config(&["Spam".to_string()], &[]);
+    let mut filtered = FilteredCounts::default();
+
+    let mut first_page = vec![job("1", "Acme", "Berlin"), job("2", "Spam GmbH", "Berlin")];
+    JobsucheMcpServer::post_filter(&params, &employer_filter, &mut first_page, &mut filtered);
+    let mut next_page = vec![job("3", "Acme", "Potsdam"), job("4", "Acme", "Köln")];
+    JobsucheMcpServer::post_filter(&params, &employer_filter, &mut next_page, &mut filtered);
+
+    assert_eq!(first_page.len(), 1);
+    assert_eq!(next_page[0].reference_number, "4");
+    assert_eq!(filtered.employer, 1);
+    assert_eq!(filtered.location, 1);
+}
+
 #[test]
 fn test_search_jobs_params_defaults() {
     let params = SearchJobsParams {
         job_title: None,
         location: None,
         radius_km: None,
+        nationwide: None,
         employment_type: None,
         contract_type: None,
         published_since_days: None,
+        freshness: None,
         page_size: None,
         page: None,
         employer: None,
         branch: None,
+        hide_seen: None,
+        english_postings_only: None,
+        collapse_duplicates: None,
+        exclude_locations: None,
+        country: None,
+        include_abroad: None,
+        sort_by: None,
+        fields: None,
     };
 
     // Test all fields are None
@@ -1188,9 +6623,11 @@ fn test_search_jobs_params_defaults() {
 #[test]
 fn test_get_job_details_result_minimal() {
     let result = GetJobDetailsResult {
+        request_id: String::new(),
         reference_number: "MIN-123".to_string(),
         title: None,
         description: None,
+        description_language: None,
         employer: None,
         location: None,
         employment_type: None,
@@ -1199,9 +6636,19 @@ fn test_get_job_details_result_minimal() {
         application_deadline: None,
         contact_info: None,
         external_url: None,
+        resolved_external_url: None,
+        identifier_type: None,
+        application_domain: None,
+        web_url: String::new(),
         employer_profile_url: None,
         partner_url: None,
         salary: None,
+        salary_parsed: None,
+        salary_estimate: None,
+        extracted_skills: Vec::new(),
+        language_requirements: Vec::new(),
+        mentions_visa_sponsorship: false,
+        mentions_relocation: false,
         contract_duration: None,
         takeover_opportunity: None,
         job_type: None,
@@ -1211,16 +6658,23 @@ fn test_get_job_details_result_minimal() {
         branch: None,
         published_date: None,
         first_published: None,
+        first_published_raw: None,
+        published_days_ago: None,
+        published_ago: None,
         only_for_disabled: None,
         fulltime: None,
         entry_period: None,
+        entry_period_raw: None,
         publication_period: None,
+        publication_period_raw: None,
         is_minor_employment: None,
         is_temp_agency: None,
         is_private_agency: None,
         career_changer_suitable: None,
         cipher_number: None,
         raw_data: serde_json::json!({"test": "data"}),
+        notes: vec![],
+        profile_match: None,
     };
 
     assert_eq!(result.reference_number, "MIN-123");
@@ -1231,12 +6685,20 @@ fn test_get_job_details_result_minimal() {
 #[test]
 fn test_server_status_all_fields() {
     let status = JobsucheServerStatus {
+        request_id: String::new(),
         server_name: "Jobsuche MCP Server".to_string(),
         version: "0.3.0".to_string(),
         uptime_seconds: 12345,
         api_url: "https://rest.arbeitsagentur.de/jobboerse/jobsuche-service".to_string(),
         api_connection_status: "Connected".to_string(),
         tools_count: 5,
+        enabled_tools: Vec::new(),
+        features: Vec::new(),
+        resources_count: 1,
+        resource_templates_count: 1,
+        prompts_count: 0,
+        api_usage: QuotaTracker::new(None).usage(),
+        build: BuildInfo::current(),
     };
 
     assert_eq!(status.server_name, "Jobsuche MCP Server");
@@ -0,0 +1,351 @@
+//! Local full-text search over fetched jobs
+//!
+//! The upstream API matches search terms against titles and keywords only.
+//! Every job fetched via get_job_details is therefore added to a local SQLite
+//! FTS5 index, which supports phrase, prefix and boolean queries over the full
+//! descriptions.
+
+use crate::storage::database_size;
+use crate::GetJobDetailsResult;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::info;
+
+/// File name of the index inside the data directory
+pub const INDEX_FILE_NAME: &str = "search_index.db";
+
+/// Default number of results returned by search_local_jobs
+pub const DEFAULT_LOCAL_RESULTS: usize = 20;
+
+/// Maximum number of results returned by search_local_jobs
+pub const MAX_LOCAL_RESULTS: usize = 100;
+
+/// Column layout of the index; reference number and timestamp are not searchable
+const CREATE_INDEX: &str = "CREATE VIRTUAL TABLE IF NOT EXISTS job_text USING fts5(
+    reference_number UNINDEXED,
+    indexed_at UNINDEXED,
+    title,
+    employer,
+    location,
+    description,
+    tokenize = 'unicode61 remove_diacritics 2'
+);";
+
+/// Full-text index of job details
+#[derive(Debug)]
+pub struct JobIndex {
+    /// Index database, `None` for an in-memory index
+    path: Option<PathBuf>,
+
+    conn: Mutex<Connection>,
+}
+
+impl JobIndex {
+    /// Open (or create) the index database at `path`
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open search index {}", path.display()))?;
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+
+        info!("Opened search index at {}", path.display());
+        Self::init(Some(path.to_path_buf()), conn)
+    }
+
+    /// Create an index that is not persisted
+    pub fn in_memory() -> Result<Self> {
+        Self::init(None, Connection::open_in_memory()?)
+    }
+
+    fn init(path: Option<PathBuf>, conn: Connection) -> Result<Self> {
+        conn.execute_batch(CREATE_INDEX)
+            .context("Failed to create search index")?;
+        Ok(Self {
+            path,
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Add a job to the index, replacing an earlier version
+    pub fn index(&self, details: &GetJobDetailsResult, indexed_at: &str) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM job_text WHERE reference_number = ?1",
+            params![details.reference_number],
+        )?;
+        tx.execute(
+            "INSERT INTO job_text
+                (reference_number, indexed_at, title, employer, location, description)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                details.reference_number,
+                indexed_at,
+                details.title,
+                details.employer,
+                details.location,
+                details.description
+            ],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Number of indexed jobs
+    pub fn len(&self) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        let count: i64 = conn.query_row("SELECT count(*) FROM job_text", [], |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
+    /// Remove jobs indexed before `cutoff` (RFC 3339); returns the number removed
+    pub fn prune(&self, cutoff: &str) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        let removed = conn.execute(
+            "DELETE FROM job_text WHERE indexed_at < ?1",
+            params![cutoff],
+        )?;
+        Ok(removed)
+    }
+
+    /// Merge the index segments and release unused space
+    pub fn compact(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute_batch("INSERT INTO job_text (job_text) VALUES ('optimize'); VACUUM;")?;
+        if self.path.is_some() {
+            conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        }
+        Ok(())
+    }
+
+    /// Bytes used on disk, `None` for an in-memory index
+    pub fn disk_usage(&self) -> Option<u64> {
+        self.path.as_deref().map(database_size)
+    }
+
+    /// Whether no job has been indexed yet
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Run an FTS5 query, best matches first
+    ///
+    /// Title matches weigh more than employer, location and description matches.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<LocalJobMatch>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT reference_number, title, employer, location, indexed_at,
+                    snippet(job_text, -1, '**', '**', '…', 16)
+             FROM job_text
+             WHERE job_text MATCH ?1
+             ORDER BY bm25(job_text, 0.0, 0.0, 5.0, 2.0, 1.0, 1.0)
+             LIMIT ?2",
+        )?;
+
+        let rows = stmt.query_map(params![query, limit as i64], |row| {
+            Ok(LocalJobMatch {
+                reference_number: row.get(0)?,
+                title: row.get(1)?,
+                employer: row.get(2)?,
+                location: row.get(3)?,
+                indexed_at: row.get(4)?,
+                snippet: row.get(5)?,
+            })
+        })?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid search query '{}': {} (put terms containing punctuation such as \
+                 \"full-stack\" in double quotes)",
+                query,
+                e
+            )
+        })
+    }
+}
+
+/// Parameters for search_local_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SearchLocalJobsParams {
+    /// Full-text query: words (all must match), "exact phrases", prefix* terms,
+    /// AND / OR / NOT, parentheses, and column filters such as title:rust
+    pub query: String,
+
+    /// Maximum number of results (default: 20, max: 100)
+    pub limit: Option<usize>,
+}
+
+/// A job matching a local full-text query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LocalJobMatch {
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Job title
+    pub title: Option<String>,
+
+    /// Employer name
+    pub employer: Option<String>,
+
+    /// Location
+    pub location: Option<String>,
+
+    /// When the job was indexed (RFC 3339)
+    pub indexed_at: String,
+
+    /// Excerpt around the match, matched terms in **bold**
+    pub snippet: String,
+}
+
+/// Result from search_local_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchLocalJobsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// The query that was run
+    pub query: String,
+
+    /// Number of jobs in the local index
+    pub indexed_jobs: usize,
+
+    /// Number of matches returned
+    pub results_count: usize,
+
+    /// Matches, best first
+    pub results: Vec<LocalJobMatch>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(reference_number: &str, title: &str, description: &str) -> GetJobDetailsResult {
+        serde_json::from_value(serde_json::json!({
+            "reference_number": reference_number,
+            "title": title,
+            "employer": "Example GmbH",
+            "location": "München",
+            "description": description,
+            "raw_data": {}
+        }))
+        .unwrap()
+    }
+
+    /// Sorted reference numbers of the matches
+    fn refs(matches: &[LocalJobMatch]) -> Vec<&str> {
+        let mut refs: Vec<_> = matches
+            .iter()
+            .map(|m| m.reference_number.as_str())
+            .collect();
+        refs.sort();
+        refs
+    }
+
+    fn index() -> JobIndex {
+        let index = JobIndex::in_memory().unwrap();
+        let now = "2025-10-20T10:00:00Z";
+        index
+            .index(
+                &details("A", "Backend Developer", "Wir nutzen Rust und PostgreSQL."),
+                now,
+            )
+            .unwrap();
+        index
+            .index(
+                &details(
+                    "B",
+                    "Frontend Developer",
+                    "TypeScript, React und etwas Rust.",
+                ),
+                now,
+            )
+            .unwrap();
+        index
+            .index(
+                &details("C", "Pflegefachkraft", "Betreuung im Schichtdienst."),
+                now,
+            )
+            .unwrap();
+        index
+    }
+
+    #[test]
+    fn test_phrase_and_boolean_queries() {
+        let index = index();
+
+        assert_eq!(refs(&index.search("rust", 10).unwrap()), vec!["A", "B"]);
+        assert_eq!(
+            refs(&index.search("\"rust und postgresql\"", 10).unwrap()),
+            vec!["A"]
+        );
+        assert_eq!(
+            refs(&index.search("rust NOT react", 10).unwrap()),
+            vec!["A"]
+        );
+        assert_eq!(
+            refs(&index.search("schicht* OR typescript", 10).unwrap()),
+            vec!["B", "C"]
+        );
+    }
+
+    #[test]
+    fn test_title_matches_rank_first() {
+        let index = index();
+        let matches = index.search("frontend OR rust", 10).unwrap();
+        assert_eq!(matches[0].reference_number, "B");
+        assert!(matches[0].snippet.contains("**"));
+    }
+
+    #[test]
+    fn test_diacritics_are_ignored() {
+        let index = index();
+        assert_eq!(refs(&index.search("munchen", 10).unwrap()).len(), 3);
+    }
+
+    #[test]
+    fn test_reindex_replaces_job() {
+        let index = index();
+        index
+            .index(
+                &details("A", "Backend Developer", "Go und Kubernetes."),
+                "2025-10-21T10:00:00Z",
+            )
+            .unwrap();
+
+        assert_eq!(index.len().unwrap(), 3);
+        assert_eq!(refs(&index.search("rust", 10).unwrap()), vec!["B"]);
+        assert_eq!(
+            index.search("kubernetes", 10).unwrap()[0].indexed_at,
+            "2025-10-21T10:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_prune_and_compact() {
+        let index = index();
+        index
+            .index(
+                &details("D", "Data Engineer", "Spark."),
+                "2025-12-01T08:00:00Z",
+            )
+            .unwrap();
+
+        assert_eq!(index.prune("2025-11-01T00:00:00Z").unwrap(), 3);
+        index.compact().unwrap();
+        assert_eq!(index.len().unwrap(), 1);
+        assert_eq!(index.disk_usage(), None);
+    }
+
+    #[test]
+    fn test_invalid_query() {
+        let index = index();
+        let err = index.search("full-stack", 10).unwrap_err();
+        assert!(err.to_string().contains("double quotes"));
+    }
+}
@@ -0,0 +1,178 @@
+//! Output locale for enumerated field values
+//!
+//! The API returns enumerated values in German ("Vollzeit", "befristet",
+//! "AUSBILDUNG"). With the English locale these values are translated in
+//! detail responses so non-German-speaking users and models get consistent
+//! output. Free text (titles, descriptions, employer names) and `raw_data` are
+//! never translated.
+
+use crate::GetJobDetailsResult;
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Language of enumerated field values in responses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// Values as delivered by the API
+    #[default]
+    De,
+    /// Values translated to English
+    En,
+}
+
+impl FromStr for Locale {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().to_lowercase();
+        // Accept region variants like "en-US" or "de_DE"
+        match s.split(['-', '_']).next().unwrap_or_default() {
+            "de" => Ok(Self::De),
+            "en" => Ok(Self::En),
+            _ => anyhow::bail!("Unknown locale '{}' (expected de or en)", s),
+        }
+    }
+}
+
+/// German values (lowercase, underscores as spaces) and their English translation
+const VALUES: &[(&str, &str)] = &[
+    ("vollzeit", "full-time"),
+    ("teilzeit", "part-time"),
+    ("minijob", "mini-job"),
+    (
+        "schicht nachtarbeit wochenende",
+        "shift, night or weekend work",
+    ),
+    ("heim telearbeit", "remote work"),
+    ("befristet", "fixed-term"),
+    ("unbefristet", "permanent"),
+    ("nicht befristet", "permanent"),
+    ("arbeit", "job"),
+    ("arbeitsstelle", "job"),
+    ("ausbildung", "apprenticeship"),
+    ("ausbildung duales studium", "apprenticeship or dual study"),
+    ("duales studium", "dual study"),
+    ("selbstaendigkeit", "self-employment"),
+    ("selbständigkeit", "self-employment"),
+    ("praktikum trainee", "internship or trainee"),
+    ("praktikum", "internship"),
+    ("trainee", "trainee"),
+    ("ja", "yes"),
+    ("nein", "no"),
+    ("keine angabe", "not specified"),
+];
+
+/// German duration units and their English singular and plural
+const UNITS: &[(&str, &str, &str)] = &[
+    ("tag", "day", "days"),
+    ("tage", "day", "days"),
+    ("woche", "week", "weeks"),
+    ("wochen", "week", "weeks"),
+    ("monat", "month", "months"),
+    ("monate", "month", "months"),
+    ("jahr", "year", "years"),
+    ("jahre", "year", "years"),
+];
+
+/// Translate an enumerated value; unknown values are returned unchanged
+pub fn translate_value(value: &str, locale: Locale) -> String {
+    if locale == Locale::De {
+        return value.to_string();
+    }
+
+    let key = value.trim().to_lowercase().replace('_', " ");
+    if let Some((_, english)) = VALUES.iter().find(|(german, _)| *german == key) {
+        return english.to_string();
+    }
+
+    // Durations like "12 Monate"
+    if let Some((amount, unit)) = key.split_once(' ') {
+        let unit = UNITS.iter().find(|(german, _, _)| *german == unit);
+        if let (Ok(amount), Some((_, singular, plural))) = (amount.parse::<u32>(), unit) {
+            let unit = if amount == 1 { singular } else { plural };
+            return format!("{} {}", amount, unit);
+        }
+    }
+    value.to_string()
+}
+
+/// Translate the enumerated values of job details
+pub fn localize_details(details: &mut GetJobDetailsResult, locale: Locale) {
+    if locale == Locale::De {
+        return;
+    }
+
+    for value in [
+        &mut details.employment_type,
+        &mut details.contract_type,
+        &mut details.contract_duration,
+        &mut details.job_type,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        *value = translate_value(value, locale);
+    }
+
+    for skill in &mut details.extracted_skills {
+        if let Some(class) = skill.name.strip_prefix("Führerschein") {
+            skill.name = format!("Driver's license{}", class);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!("en".parse::<Locale>().unwrap(), Locale::En);
+        assert_eq!("en-US".parse::<Locale>().unwrap(), Locale::En);
+        assert_eq!(" DE_de ".parse::<Locale>().unwrap(), Locale::De);
+        assert!("fr".parse::<Locale>().is_err());
+    }
+
+    #[test]
+    fn test_translate_value() {
+        assert_eq!(translate_value("Vollzeit", Locale::En), "full-time");
+        assert_eq!(translate_value("UNBEFRISTET", Locale::En), "permanent");
+        assert_eq!(
+            translate_value("PRAKTIKUM_TRAINEE", Locale::En),
+            "internship or trainee"
+        );
+        assert_eq!(translate_value("12 Monate", Locale::En), "12 months");
+        assert_eq!(translate_value("1 Jahr", Locale::En), "1 year");
+        assert_eq!(
+            translate_value("nach Vereinbarung", Locale::En),
+            "nach Vereinbarung"
+        );
+        assert_eq!(translate_value("Vollzeit", Locale::De), "Vollzeit");
+    }
+
+    #[test]
+    fn test_localize_details() {
+        let mut details: GetJobDetailsResult = serde_json::from_value(serde_json::json!({
+            "reference_number": "10001-1234567890-S",
+            "title": "Fahrer (m/w/d)",
+            "employment_type": "Teilzeit",
+            "job_type": "AUSBILDUNG",
+            "contract_duration": "befristet",
+            "start_date": "2025-11-01",
+            "extracted_skills": [{"name": "Führerschein CE", "category": "drivers_license"}],
+            "raw_data": {}
+        }))
+        .unwrap();
+
+        localize_details(&mut details, Locale::En);
+        assert_eq!(details.title.as_deref(), Some("Fahrer (m/w/d)"));
+        assert_eq!(details.employment_type.as_deref(), Some("part-time"));
+        assert_eq!(details.job_type.as_deref(), Some("apprenticeship"));
+        assert_eq!(details.contract_duration.as_deref(), Some("fixed-term"));
+        assert_eq!(details.start_date.as_deref(), Some("2025-11-01"));
+        assert_eq!(details.extracted_skills[0].name, "Driver's license CE");
+    }
+}
@@ -0,0 +1,78 @@
+//! Excluded work locations
+//!
+//! A radius search around a place includes towns a user will not commute to,
+//! e.g. across a river without a bridge nearby. `exclude_locations` drops
+//! jobs in such places after the search. Entries of digits match the postal
+//! code of the work location by prefix ("42" excludes 42xxx); other entries
+//! match the town case-insensitively, including its districts and suffixes
+//! ("Köln" excludes "Köln-Ehrenfeld" and "Frankfurt" both Frankfurts).
+
+use crate::country::Listing;
+
+/// Maximum number of excluded locations per search
+pub const MAX_EXCLUDED_LOCATIONS: usize = 50;
+
+/// Whether a job location as listed ("Wuppertal (42103)") matches any of the
+/// excluded locations
+pub fn is_excluded_location(location: &str, excluded: &[String]) -> bool {
+    let listing = Listing::parse(location);
+    let town = listing.town.to_lowercase();
+    excluded.iter().any(|entry| {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            false
+        } else if entry.chars().all(|c| c.is_ascii_digit()) {
+            listing
+                .postal_code
+                .is_some_and(|code| code.starts_with(entry))
+        } else {
+            town_matches(&town, &entry.to_lowercase())
+        }
+    })
+}
+
+/// Whether the town is the excluded one or one of its districts
+fn town_matches(town: &str, excluded: &str) -> bool {
+    match town.strip_prefix(excluded) {
+        Some(rest) => rest.is_empty() || rest.starts_with([' ', '-', '/', ',']),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn excluded(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|entry| entry.to_string()).collect()
+    }
+
+    #[test]
+    fn test_postal_code() {
+        let entries = excluded(&["42103", "40"]);
+        assert!(is_excluded_location("Wuppertal (42103)", &entries));
+        assert!(is_excluded_location("Düsseldorf (40210)", &entries));
+        assert!(!is_excluded_location("Wuppertal (42105)", &entries));
+        assert!(!is_excluded_location("Wuppertal", &entries));
+    }
+
+    #[test]
+    fn test_town() {
+        let entries = excluded(&["köln", "Frankfurt"]);
+        assert!(is_excluded_location("Köln (50667)", &entries));
+        assert!(is_excluded_location("Köln-Ehrenfeld (50823)", &entries));
+        assert!(is_excluded_location("Frankfurt am Main (60311)", &entries));
+        assert!(is_excluded_location("Frankfurt (Oder) (15230)", &entries));
+        assert!(!is_excluded_location("Kölnische Str. (34117)", &entries));
+        assert!(!is_excluded_location("Bonn (53111)", &entries));
+        assert!(is_excluded_location("Köln (50667), Deutschland", &entries));
+    }
+
+    #[test]
+    fn test_blank_entries_match_nothing() {
+        assert!(!is_excluded_location(
+            "Köln (50667)",
+            &excluded(&["", "  "])
+        ));
+    }
+}
@@ -0,0 +1,432 @@
+//! Logging setup
+//!
+//! Logs go to stderr, since stdout carries the MCP messages of the STDIO
+//! transport. `JOBSUCHE_LOG_FORMAT=json` switches to one JSON object per
+//! line for log pipelines: every event carries the span of the tool it
+//! belongs to (tool name and parameters), each tool call ends with a
+//! `close` event holding its duration (`time.busy`), and calls to the
+//! Jobsuche API are logged under the [`UPSTREAM_TARGET`] target with
+//! endpoint, status, duration and the reference numbers they touched. The
+//! level comes from `RUST_LOG` (default: info).
+//!
+//! Hosts of the STDIO transport often drop stderr, so `JOBSUCHE_LOG_FILE`
+//! additionally writes the logs to a file that is rotated daily (or hourly,
+//! `JOBSUCHE_LOG_ROTATION`), keeping the newest `JOBSUCHE_LOG_MAX_FILES`.
+//!
+//! The filter can be replaced while the server runs (set_log_level, an admin
+//! tool enabled by `JOBSUCHE_ADMIN_TOOLS`), e.g. to turn on debug logging for
+//! a failing upstream call without a restart.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing::Subscriber;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
+
+/// Log target of the Jobsuche API calls
+pub const UPSTREAM_TARGET: &str = "jobsuche::upstream";
+
+/// Log filter used when `RUST_LOG` is not set
+const DEFAULT_FILTER: &str = "info";
+
+/// Error returned by the admin tools while they are disabled
+pub const ADMIN_TOOLS_DISABLED: &str =
+    "Admin tools are disabled; set JOBSUCHE_ADMIN_TOOLS=true to enable them";
+
+/// Reloadable filter of the installed subscriber
+struct FilterState {
+    handle: reload::Handle<EnvFilter, Registry>,
+    startup: String,
+}
+
+/// Set by [`init`]; stays empty if the server logs through another subscriber
+static FILTER: OnceLock<FilterState> = OnceLock::new();
+
+/// Format of the log lines
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+impl LogFormat {
+    /// Parse "text" or "json" (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "text" | "pretty" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Default number of rotated log files to keep
+pub const DEFAULT_MAX_LOG_FILES: usize = 7;
+
+/// When the log file starts over
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogRotation {
+    /// New file every hour (`jobsuche.log.2025-11-03-09`)
+    Hourly,
+    /// New file every day (`jobsuche.log.2025-11-03`)
+    #[default]
+    Daily,
+    /// A single file that is never rotated
+    Never,
+}
+
+impl LogRotation {
+    /// Parse "hourly", "daily" or "never" (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "hourly" => Some(Self::Hourly),
+            "daily" => Some(Self::Daily),
+            "never" | "none" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    fn rotation(self) -> Rotation {
+        match self {
+            Self::Hourly => Rotation::HOURLY,
+            Self::Daily => Rotation::DAILY,
+            Self::Never => Rotation::NEVER,
+        }
+    }
+}
+
+/// Log file settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFileConfig {
+    /// Path of the log file; rotated files get the date appended
+    pub path: PathBuf,
+
+    /// When to start a new file
+    pub rotation: LogRotation,
+
+    /// Number of rotated files to keep (`None` keeps all)
+    pub max_files: Option<usize>,
+}
+
+impl LogFileConfig {
+    /// Appender writing to the configured file
+    fn appender(&self) -> anyhow::Result<RollingFileAppender> {
+        let file_name = self
+            .path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Log file path '{}' has no file name", self.path.display())
+            })?;
+        let directory = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        std::fs::create_dir_all(directory)?;
+
+        let mut builder = RollingFileAppender::builder()
+            .rotation(self.rotation.rotation())
+            .filename_prefix(file_name);
+        if let Some(max_files) = self.max_files {
+            builder = builder.max_log_files(max_files);
+        }
+        Ok(builder.build(directory)?)
+    }
+}
+
+/// Logging settings, read from the environment before the server starts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoggingConfig {
+    /// Format of the log lines
+    pub format: LogFormat,
+
+    /// Log file in addition to stderr, if configured
+    pub file: Option<LogFileConfig>,
+
+    /// Problems with the settings, reported once logging runs
+    pub warnings: Vec<String>,
+}
+
+impl LoggingConfig {
+    /// Read the settings from the environment
+    ///
+    /// # Environment Variables
+    /// - `JOBSUCHE_LOG_FORMAT`: `text` (default) or `json`
+    /// - `JOBSUCHE_LOG_FILE`: Path of a log file written in addition to stderr (optional)
+    /// - `JOBSUCHE_LOG_ROTATION`: `daily` (default), `hourly` or `never`
+    /// - `JOBSUCHE_LOG_MAX_FILES`: Rotated log files to keep (default: 7, 0 keeps all)
+    /// - `RUST_LOG`: Log filter, e.g. `debug` or `jobsuche::upstream=debug` (default: info)
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        if let Some(value) = var("JOBSUCHE_LOG_FORMAT") {
+            match LogFormat::parse(&value) {
+                Some(format) => config.format = format,
+                None => config.warnings.push(format!(
+                    "Unknown JOBSUCHE_LOG_FORMAT '{}', using text (use text or json)",
+                    value
+                )),
+            }
+        }
+
+        if let Some(path) = var("JOBSUCHE_LOG_FILE") {
+            let rotation = match var("JOBSUCHE_LOG_ROTATION") {
+                Some(value) => LogRotation::parse(&value).unwrap_or_else(|| {
+                    config.warnings.push(format!(
+                        "Unknown JOBSUCHE_LOG_ROTATION '{}', rotating daily",
+                        value
+                    ));
+                    LogRotation::Daily
+                }),
+                None => LogRotation::Daily,
+            };
+            let max_files = match var("JOBSUCHE_LOG_MAX_FILES") {
+                Some(value) => match value.parse::<usize>() {
+                    Ok(0) => None,
+                    Ok(max_files) => Some(max_files),
+                    Err(_) => {
+                        config.warnings.push(format!(
+                            "Invalid JOBSUCHE_LOG_MAX_FILES '{}', keeping {} files",
+                            value, DEFAULT_MAX_LOG_FILES
+                        ));
+                        Some(DEFAULT_MAX_LOG_FILES)
+                    }
+                },
+                None => Some(DEFAULT_MAX_LOG_FILES),
+            };
+            config.file = Some(LogFileConfig {
+                path: PathBuf::from(path),
+                rotation,
+                max_files,
+            });
+        }
+        config
+    }
+}
+
+/// Keeps the log file writer running; drop it only when the server exits
+///
+/// Dropping it flushes the buffered lines of the log file.
+#[must_use = "log lines are lost once the guard is dropped"]
+pub struct LoggingGuard {
+    _file: Option<WorkerGuard>,
+}
+
+/// Log layer writing in the given format
+fn format_layer<S, W>(format: LogFormat, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_ansi(false);
+    match format {
+        LogFormat::Text => layer.boxed(),
+        LogFormat::Json => layer
+            .json()
+            .with_current_span(true)
+            .with_span_list(false)
+            .with_span_events(FmtSpan::CLOSE)
+            .boxed(),
+    }
+}
+
+/// Install the global log subscriber
+///
+/// Call once at startup, before anything logs, and keep the returned guard
+/// until the server exits. If the log file cannot be opened, logs go to
+/// stderr only.
+pub fn init(config: &LoggingConfig) -> LoggingGuard {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let startup = filter.to_string();
+    let (filter, handle) = reload::Layer::new(filter);
+    let mut warnings = config.warnings.clone();
+
+    let (file_layer, file_guard) = match config.file.as_ref().map(|file| (file, file.appender())) {
+        Some((_, Ok(appender))) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (Some(format_layer(config.format, writer)), Some(guard))
+        }
+        Some((file, Err(e))) => {
+            warnings.push(format!(
+                "Cannot write log file {}: {:#}",
+                file.path.display(),
+                e
+            ));
+            (None, None)
+        }
+        None => (None, None),
+    };
+
+    let output = format_layer(config.format, std::io::stderr).and_then(file_layer);
+    tracing_subscriber::registry()
+        .with(output.with_filter(filter))
+        .init();
+    let _ = FILTER.set(FilterState { handle, startup });
+
+    for warning in warnings {
+        tracing::warn!("{}", warning);
+    }
+    if let Some(ref file) = config.file {
+        if file_guard.is_some() {
+            tracing::info!(
+                "Writing logs to {} (rotation: {:?})",
+                file.path.display(),
+                file.rotation
+            );
+        }
+    }
+    LoggingGuard { _file: file_guard }
+}
+
+/// Log filter in effect, `None` if [`init`] did not install the subscriber
+pub fn current_filter() -> Option<String> {
+    FILTER
+        .get()
+        .and_then(|state| state.handle.with_current(|filter| filter.to_string()).ok())
+}
+
+/// Replace the log filter of the running server
+///
+/// Takes `RUST_LOG` syntax ("debug", "info,jobsuche::upstream=debug").
+/// Returns the previous and the new filter.
+pub fn set_filter(directives: &str) -> anyhow::Result<SetLogLevelResult> {
+    let directives = directives.trim();
+    anyhow::ensure!(!directives.is_empty(), "level must not be empty");
+    let filter = EnvFilter::try_new(directives)
+        .map_err(|e| anyhow::anyhow!("Invalid log filter '{}': {}", directives, e))?;
+    let state = FILTER
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("Logging was not set up by the server"))?;
+
+    let previous = state.handle.with_current(|filter| filter.to_string())?;
+    let current = filter.to_string();
+    state.handle.reload(filter)?;
+    Ok(SetLogLevelResult {
+        request_id: String::new(),
+        previous,
+        current,
+        startup: state.startup.clone(),
+    })
+}
+
+/// Parameters for set_log_level
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SetLogLevelParams {
+    /// Level ("error", "warn", "info", "debug", "trace") or a filter in
+    /// RUST_LOG syntax ("info,jobsuche::upstream=debug")
+    #[schemars(length(min = 1), example = &"info,jobsuche::upstream=debug")]
+    pub level: String,
+}
+
+/// Result from set_log_level
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SetLogLevelResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Filter before the change
+    pub previous: String,
+
+    /// Filter now in effect
+    pub current: String,
+
+    /// Filter the server started with, to restore it later
+    pub startup: String,
+}
+
+/// Reference numbers for a log field, comma-separated
+pub fn refnr_list<'a>(refnrs: impl IntoIterator<Item = &'a str>) -> String {
+    refnrs.into_iter().collect::<Vec<_>>().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!(LogFormat::parse("JSON"), Some(LogFormat::Json));
+        assert_eq!(LogFormat::parse(" text "), Some(LogFormat::Text));
+        assert_eq!(LogFormat::parse("xml"), None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_from_env() {
+        std::env::remove_var("JOBSUCHE_LOG_FORMAT");
+        assert_eq!(LoggingConfig::from_env(), LoggingConfig::default());
+
+        std::env::set_var("JOBSUCHE_LOG_FORMAT", "json");
+        assert_eq!(LoggingConfig::from_env().format, LogFormat::Json);
+
+        std::env::set_var("JOBSUCHE_LOG_FORMAT", "logfmt");
+        let config = LoggingConfig::from_env();
+        assert_eq!(config.format, LogFormat::Text);
+        assert_eq!(config.warnings.len(), 1);
+        std::env::remove_var("JOBSUCHE_LOG_FORMAT");
+    }
+
+    #[test]
+    #[serial]
+    fn test_log_file_from_env() {
+        std::env::set_var("JOBSUCHE_LOG_FILE", "/var/log/jobsuche/server.log");
+        let file = LoggingConfig::from_env().file.unwrap();
+        assert_eq!(file.path, PathBuf::from("/var/log/jobsuche/server.log"));
+        assert_eq!(file.rotation, LogRotation::Daily);
+        assert_eq!(file.max_files, Some(DEFAULT_MAX_LOG_FILES));
+
+        std::env::set_var("JOBSUCHE_LOG_ROTATION", "Hourly");
+        std::env::set_var("JOBSUCHE_LOG_MAX_FILES", "0");
+        let file = LoggingConfig::from_env().file.unwrap();
+        assert_eq!(file.rotation, LogRotation::Hourly);
+        assert_eq!(file.max_files, None);
+
+        std::env::remove_var("JOBSUCHE_LOG_FILE");
+        assert_eq!(LoggingConfig::from_env().file, None);
+        std::env::remove_var("JOBSUCHE_LOG_ROTATION");
+        std::env::remove_var("JOBSUCHE_LOG_MAX_FILES");
+    }
+
+    #[test]
+    fn test_set_filter_validates() {
+        assert!(set_filter(" ").is_err());
+        assert!(set_filter("jobsuche=verbose").is_err());
+    }
+
+    #[test]
+    fn test_appender_creates_directory() {
+        let dir = std::env::temp_dir().join(format!("jobsuche-logs-{}", std::process::id()));
+        let file = LogFileConfig {
+            path: dir.join("nested").join("server.log"),
+            rotation: LogRotation::Never,
+            max_files: None,
+        };
+        assert!(file.appender().is_ok());
+        assert!(dir.join("nested").is_dir());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -2,15 +2,21 @@
 //!
 //! This server provides tools for searching German job listings without
 //! requiring knowledge of the Bundesagentur für Arbeit API internals.
+//!
+//! With `--daemon`, the server does not read STDIO and only runs the
+//! scheduled watchlist checks and saved searches until it is stopped, so
+//! they also run while no MCP client is connected.
 
+use jobsuche_mcp_server::logging::{self, LoggingConfig};
+use jobsuche_mcp_server::stdio;
 use jobsuche_mcp_server::JobsucheMcpServer;
-use pulseengine_mcp_server::McpServerBuilder;
 use tracing::{error, info};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Configure logging for STDIO transport
-    JobsucheMcpServer::configure_stdio_logging();
+    // Log to stderr and optionally a rotated file (JOBSUCHE_LOG_FILE); the
+    // guard flushes the file when main returns
+    let _logging = logging::init(&LoggingConfig::from_env());
 
     info!("Starting Jobsuche MCP Server...");
 
@@ -35,14 +41,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
-    info!("Starting MCP server with STDIO transport...");
-
-    // Start the server using the macro-generated infrastructure
-    let mut server = jobsuche_server.serve_stdio().await?;
+    if std::env::args().skip(1).any(|arg| arg == "--daemon") {
+        if !jobsuche_server.has_schedules() {
+            eprintln!(
+                "--daemon needs JOBSUCHE_WATCHLIST_SCHEDULE or JOBSUCHE_SAVED_SEARCH_SCHEDULE"
+            );
+            std::process::exit(1);
+        }
+        info!("Jobsuche MCP Server is running scheduled updates only (daemon mode)");
+        tokio::signal::ctrl_c().await?;
+        info!("Stopping Jobsuche MCP Server");
+        return Ok(());
+    }
 
-    info!("Jobsuche MCP Server is running and ready to serve requests");
+    info!("Jobsuche MCP Server is running and ready to serve requests on STDIO");
 
-    server.run().await?;
+    // Our own STDIO transport, which handles requests concurrently and sends
+    // progress notifications
+    stdio::serve(jobsuche_server).await?;
 
     Ok(())
 }
@@ -0,0 +1,297 @@
+//! Retention and compaction of local data
+//!
+//! Job snapshots, seen jobs, the search history and the full-text index grow
+//! with every request. Entries older than the retention period are pruned by a
+//! background task and on demand via compact_storage, which also compacts the
+//! store and index files. Favorites, notes, saved searches and watchlists are
+//! user data and never expire; archived snapshots of favorites are kept too.
+
+use crate::archive::ArchivedJob;
+use crate::history::{SearchHistory, HISTORY_KEY};
+use crate::local_search::JobIndex;
+use crate::seen::SeenJob;
+use crate::storage::{Storage, FAVORITES, JOB_ARCHIVE, SEARCH_HISTORY, SEEN_JOBS};
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tracing::{info, warn};
+
+/// Whether an RFC 3339 timestamp lies before `cutoff`; unparsable timestamps never expire
+fn is_expired(timestamp: &str, cutoff: OffsetDateTime) -> bool {
+    OffsetDateTime::parse(timestamp, &Rfc3339).is_ok_and(|time| time < cutoff)
+}
+
+/// Prune entries older than `retention_days` (0 keeps everything), then compact
+pub fn run_maintenance(
+    storage: &Storage,
+    index: &JobIndex,
+    retention_days: u64,
+) -> Result<CompactStorageResult> {
+    let bytes_before = disk_usage(storage, index);
+    let mut result = CompactStorageResult {
+        request_id: String::new(),
+        retention_days,
+        cutoff: None,
+        archived_jobs_removed: 0,
+        seen_jobs_removed: 0,
+        history_entries_removed: 0,
+        indexed_jobs_removed: 0,
+        bytes_before,
+        bytes_after: bytes_before,
+        bytes_reclaimed: 0,
+    };
+
+    if retention_days > 0 {
+        let cutoff = OffsetDateTime::now_utc() - time::Duration::days(retention_days as i64);
+        let cutoff_text = cutoff.format(&Rfc3339)?;
+
+        let favorites = storage.keys(FAVORITES);
+        result.archived_jobs_removed =
+            storage.retain(JOB_ARCHIVE, |key, archived: &ArchivedJob| {
+                favorites.contains(key) || !is_expired(&archived.archived_at, cutoff)
+            })?;
+        result.seen_jobs_removed = storage.retain(SEEN_JOBS, |_, seen: &SeenJob| {
+            !is_expired(&seen.first_seen, cutoff)
+        })?;
+        if storage
+            .get::<SearchHistory>(SEARCH_HISTORY, HISTORY_KEY)?
+            .is_some()
+        {
+            result.history_entries_removed = storage.update(
+                SEARCH_HISTORY,
+                HISTORY_KEY,
+                |history: &mut SearchHistory| {
+                    let before = history.entries.len();
+                    history
+                        .entries
+                        .retain(|entry| !is_expired(&entry.executed_at, cutoff));
+                    before - history.entries.len()
+                },
+            )?;
+        }
+        result.indexed_jobs_removed = index.prune(&cutoff_text)?;
+        result.cutoff = Some(cutoff_text);
+    }
+
+    storage.compact()?;
+    index.compact()?;
+
+    result.bytes_after = disk_usage(storage, index);
+    result.bytes_reclaimed = result.bytes_before.saturating_sub(result.bytes_after);
+    info!(
+        "Storage maintenance removed {} snapshots, {} seen jobs, {} history entries and {} indexed jobs, reclaimed {} bytes",
+        result.archived_jobs_removed,
+        result.seen_jobs_removed,
+        result.history_entries_removed,
+        result.indexed_jobs_removed,
+        result.bytes_reclaimed
+    );
+    Ok(result)
+}
+
+/// Bytes used on disk by the store and the index
+fn disk_usage(storage: &Storage, index: &JobIndex) -> u64 {
+    storage.disk_usage().unwrap_or(0) + index.disk_usage().unwrap_or(0)
+}
+
+/// Run maintenance at startup and then every `interval` in the background
+pub fn spawn_maintenance(
+    storage: Storage,
+    index: Arc<JobIndex>,
+    retention_days: u64,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    info!(
+        "Scheduling storage maintenance every {:?} (retention: {} days)",
+        interval, retention_days
+    );
+
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(interval);
+        ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+
+            let storage = storage.clone();
+            let index = index.clone();
+            let run = tokio::task::spawn_blocking(move || {
+                run_maintenance(&storage, &index, retention_days)
+            });
+            match run.await {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => warn!("Storage maintenance failed: {:#}", e),
+                Err(e) => warn!("Storage maintenance task failed: {}", e),
+            }
+        }
+    })
+}
+
+/// Parameters for compact_storage
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CompactStorageParams {
+    /// Retention period in days for this run (default from config; 0 only compacts)
+    pub retention_days: Option<u64>,
+}
+
+/// Result from compact_storage
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompactStorageResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Retention period that was applied (0: nothing pruned)
+    pub retention_days: u64,
+
+    /// Entries older than this were removed (RFC 3339)
+    pub cutoff: Option<String>,
+
+    /// Removed job snapshots
+    pub archived_jobs_removed: usize,
+
+    /// Removed seen-job records
+    pub seen_jobs_removed: usize,
+
+    /// Removed search history entries
+    pub history_entries_removed: usize,
+
+    /// Jobs removed from the full-text index
+    pub indexed_jobs_removed: usize,
+
+    /// Bytes on disk before maintenance (store and index)
+    pub bytes_before: u64,
+
+    /// Bytes on disk after maintenance
+    pub bytes_after: u64,
+
+    /// Bytes reclaimed
+    pub bytes_reclaimed: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::now_rfc3339;
+    use crate::{GetJobDetailsResult, SearchJobsParams, SearchJobsResult};
+
+    fn details(reference_number: &str) -> GetJobDetailsResult {
+        serde_json::from_value(serde_json::json!({
+            "reference_number": reference_number,
+            "raw_data": {}
+        }))
+        .unwrap()
+    }
+
+    fn archived(reference_number: &str, archived_at: &str) -> ArchivedJob {
+        ArchivedJob {
+            archived_at: archived_at.to_string(),
+            details: details(reference_number),
+        }
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let cutoff = OffsetDateTime::parse("2025-06-01T00:00:00Z", &Rfc3339).unwrap();
+        assert!(is_expired("2025-05-31T23:59:59.5Z", cutoff));
+        assert!(!is_expired("2025-06-01T00:00:00Z", cutoff));
+        assert!(!is_expired("yesterday", cutoff));
+    }
+
+    #[test]
+    fn test_run_maintenance_prunes_expired_entries() {
+        let storage = Storage::in_memory();
+        let index = JobIndex::in_memory().unwrap();
+        let now = now_rfc3339();
+        let old = "2020-01-01T00:00:00Z";
+
+        storage
+            .put(JOB_ARCHIVE, "OLD", &archived("OLD", old))
+            .unwrap();
+        storage
+            .put(JOB_ARCHIVE, "FAV", &archived("FAV", old))
+            .unwrap();
+        storage
+            .put(JOB_ARCHIVE, "NEW", &archived("NEW", &now))
+            .unwrap();
+        storage
+            .put(FAVORITES, "FAV", &serde_json::json!({}))
+            .unwrap();
+        storage
+            .insert_missing(
+                SEEN_JOBS,
+                vec![
+                    (
+                        "OLD".to_string(),
+                        SeenJob {
+                            first_seen: old.to_string(),
+                            external_url: None,
+                        },
+                    ),
+                    (
+                        "NEW".to_string(),
+                        SeenJob {
+                            first_seen: now.clone(),
+                            external_url: None,
+                        },
+                    ),
+                ],
+            )
+            .unwrap();
+
+        let result = SearchJobsResult {
+            request_id: String::new(),
+            total_results: Some(1),
+            current_page: Some(1),
+            page_size: Some(25),
+            jobs_count: 1,
+            jobs: vec![],
+            hidden_seen_count: 0,
+            duplicates_collapsed: 0,
+            employer_filtered_count: 0,
+            location_excluded_count: 0,
+            search_duration_ms: 1,
+            effective_query: Default::default(),
+            relaxations: Vec::new(),
+        };
+        let search: SearchJobsParams = serde_json::from_value(serde_json::json!({})).unwrap();
+        let mut history = SearchHistory::default();
+        history.record(search.clone(), &result, old, 10);
+        history.record(search, &result, &now, 10);
+        storage.put(SEARCH_HISTORY, HISTORY_KEY, &history).unwrap();
+
+        index.index(&details("OLD"), old).unwrap();
+        index.index(&details("NEW"), &now).unwrap();
+
+        let report = run_maintenance(&storage, &index, 30).unwrap();
+        assert_eq!(report.archived_jobs_removed, 1);
+        assert_eq!(report.seen_jobs_removed, 1);
+        assert_eq!(report.history_entries_removed, 1);
+        assert_eq!(report.indexed_jobs_removed, 1);
+        assert!(report.cutoff.is_some());
+        assert_eq!(report.bytes_reclaimed, 0);
+
+        let mut archive_keys: Vec<_> = storage.keys(JOB_ARCHIVE).into_iter().collect();
+        archive_keys.sort();
+        assert_eq!(archive_keys, vec!["FAV", "NEW"]);
+        assert_eq!(index.len().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_zero_retention_only_compacts() {
+        let storage = Storage::in_memory();
+        let index = JobIndex::in_memory().unwrap();
+        storage
+            .put(JOB_ARCHIVE, "OLD", &archived("OLD", "2020-01-01T00:00:00Z"))
+            .unwrap();
+
+        let report = run_maintenance(&storage, &index, 0).unwrap();
+        assert_eq!(report.cutoff, None);
+        assert_eq!(report.archived_jobs_removed, 0);
+        assert_eq!(storage.keys(SEARCH_HISTORY).len(), 0);
+    }
+}
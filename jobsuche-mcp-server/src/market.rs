@@ -0,0 +1,183 @@
+//! Labor market summaries
+//!
+//! Condenses the results of a search into aggregate figures: postings per
+//! city, employer and employment type, how often a salary is stated and how
+//! old the postings are. Employment types are counted by the API over all
+//! results; the other figures come from the analyzed postings.
+
+use crate::employer::LocationCount;
+use crate::{JobSummary, SearchJobsParams};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Default number of postings analyzed
+pub const DEFAULT_MARKET_JOBS: u64 = 100;
+
+/// Maximum number of postings analyzed
+pub const MAX_MARKET_JOBS: u64 = 500;
+
+/// Default number of postings whose details are fetched for the salary share
+pub const DEFAULT_SALARY_SAMPLE: u64 = 10;
+
+/// Maximum number of postings whose details are fetched for the salary share
+pub const MAX_SALARY_SAMPLE: u64 = 25;
+
+/// Number of cities and employers listed
+pub const TOP_ENTRIES: usize = 20;
+
+/// Employment types counted with one query each (names as in search_jobs)
+pub const EMPLOYMENT_TYPES: &[&str] = &["fulltime", "parttime", "mini_job", "home_office", "shift"];
+
+/// Parameters for summarize_market
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SummarizeMarketParams {
+    /// Search to summarize (same parameters as search_jobs)
+    pub search: SearchJobsParams,
+
+    /// Maximum number of postings analyzed (default: 100, max: 500)
+    pub max_jobs: Option<u64>,
+
+    /// Number of postings whose details are fetched to tell how often a salary
+    /// is stated (default: 10, max: 25; 0 skips it)
+    pub salary_sample: Option<u64>,
+}
+
+/// Number of postings of one employer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EmployerCount {
+    /// Employer name
+    pub employer: String,
+
+    /// Number of postings
+    pub count: usize,
+}
+
+/// Result from summarize_market
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SummarizeMarketResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Total number of postings found by the search
+    pub total_results: Option<u64>,
+
+    /// Number of postings analyzed for the city, employer and age figures
+    pub analyzed_jobs: usize,
+
+    /// Analyzed postings per city, top 20
+    pub jobs_by_city: Vec<LocationCount>,
+
+    /// Analyzed postings per employer, top 20
+    pub jobs_by_employer: Vec<EmployerCount>,
+
+    /// All postings per employment type ("fulltime", "parttime", "mini_job",
+    /// "home_office", "shift"), counted by the API; a posting can have several
+    pub jobs_by_employment_type: BTreeMap<String, u64>,
+
+    /// Number of postings whose details were checked for a salary
+    pub salary_sample_size: usize,
+
+    /// Share of the checked postings that state a salary (0.0 to 1.0)
+    pub salary_stated_share: Option<f64>,
+
+    /// Median days since publication of the analyzed postings
+    pub median_published_days_ago: Option<f64>,
+}
+
+/// Count postings per employer, most postings first (ties alphabetically)
+pub fn count_by_employer(jobs: &[JobSummary]) -> Vec<EmployerCount> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for job in jobs {
+        let employer = job.employer.trim();
+        let employer = if employer.is_empty() {
+            "unknown"
+        } else {
+            employer
+        };
+        *counts.entry(employer).or_default() += 1;
+    }
+
+    let mut counts: Vec<EmployerCount> = counts
+        .into_iter()
+        .map(|(employer, count)| EmployerCount {
+            employer: employer.to_string(),
+            count,
+        })
+        .collect();
+    counts.sort_by_key(|count| std::cmp::Reverse(count.count));
+    counts
+}
+
+/// Median of a list of values
+pub fn median(mut values: Vec<i64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let middle = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[middle - 1] + values[middle]) as f64 / 2.0
+    } else {
+        values[middle] as f64
+    })
+}
+
+/// Share of `count` in `total`, `None` for an empty total
+pub fn share(count: usize, total: usize) -> Option<f64> {
+    (total > 0).then(|| count as f64 / total as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(employer: &str) -> JobSummary {
+        serde_json::from_value(serde_json::json!({
+            "reference_number": "REF",
+            "title": "Job",
+            "employer": employer,
+            "location": "Berlin",
+            "published_date": null,
+            "external_url": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_count_by_employer() {
+        let jobs = vec![job("B GmbH"), job("A AG"), job("B GmbH"), job(" ")];
+        assert_eq!(
+            count_by_employer(&jobs),
+            vec![
+                EmployerCount {
+                    employer: "B GmbH".to_string(),
+                    count: 2
+                },
+                EmployerCount {
+                    employer: "A AG".to_string(),
+                    count: 1
+                },
+                EmployerCount {
+                    employer: "unknown".to_string(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(vec![]), None);
+        assert_eq!(median(vec![7, 1, 3]), Some(3.0));
+        assert_eq!(median(vec![4, 1, 3, 10]), Some(3.5));
+    }
+
+    #[test]
+    fn test_share() {
+        assert_eq!(share(1, 4), Some(0.25));
+        assert_eq!(share(0, 0), None);
+    }
+}
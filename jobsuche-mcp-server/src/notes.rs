@@ -0,0 +1,103 @@
+//! Per-job notes
+//!
+//! Users attach free-text notes to a reference number (e.g. "called recruiter,
+//! asked about salary"). Notes are kept in the local store and included in
+//! get_job_details responses.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Maximum length of a single note in characters
+pub const MAX_NOTE_LENGTH: usize = 2000;
+
+/// A user note on a job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct JobNote {
+    /// Note text
+    pub text: String,
+
+    /// When the note was added (RFC 3339)
+    pub created_at: String,
+}
+
+/// Validate and normalize the text of a new note
+pub fn note_text(text: &str) -> anyhow::Result<String> {
+    let text = text.trim();
+    if text.is_empty() {
+        anyhow::bail!("Note text must not be empty");
+    }
+    if text.chars().count() > MAX_NOTE_LENGTH {
+        anyhow::bail!("Note text exceeds {} characters", MAX_NOTE_LENGTH);
+    }
+    Ok(text.to_string())
+}
+
+/// Parameters for add_job_note
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AddJobNoteParams {
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Note text (e.g. "Called recruiter, asked about salary")
+    pub text: String,
+}
+
+/// Result from add_job_note
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AddJobNoteResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Job reference number
+    pub reference_number: String,
+
+    /// The added note
+    pub note: JobNote,
+
+    /// Number of notes on this job
+    pub notes_count: usize,
+}
+
+/// Parameters for get_job_notes
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetJobNotesParams {
+    /// Job reference number
+    pub reference_number: String,
+}
+
+/// Result from get_job_notes
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetJobNotesResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Notes, oldest first
+    pub notes: Vec<JobNote>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_text_is_trimmed() {
+        assert_eq!(
+            note_text("  Called recruiter \n").unwrap(),
+            "Called recruiter"
+        );
+    }
+
+    #[test]
+    fn test_note_text_rejects_empty_and_long_text() {
+        assert!(note_text("   ").is_err());
+        assert!(note_text(&"x".repeat(MAX_NOTE_LENGTH)).is_ok());
+        assert!(note_text(&"x".repeat(MAX_NOTE_LENGTH + 1)).is_err());
+    }
+}
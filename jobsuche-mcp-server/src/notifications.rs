@@ -0,0 +1,337 @@
+//! Webhook notifications
+//!
+//! When a watchlist check finds new postings, a JSON payload is POSTed to the
+//! configured webhook, so the server can act as a standalone job alert. Slack
+//! and Discord incoming webhooks get a message in their own format; other
+//! endpoints get a generic event. Requests are retried with exponential backoff
+//! and, if a secret is configured, signed with HMAC-SHA256.
+
+use crate::export::job_url;
+use crate::watchlists::WatchlistJob;
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Maximum number of jobs listed in Slack and Discord messages
+const MAX_LISTED_JOBS: usize = 10;
+
+/// Attempts per notification
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubled for every further retry
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Header carrying the HMAC-SHA256 signature (`sha256=<hex>`)
+pub const SIGNATURE_HEADER: &str = "X-Jobsuche-Signature";
+
+/// Header carrying the Unix timestamp included in the signature
+pub const TIMESTAMP_HEADER: &str = "X-Jobsuche-Timestamp";
+
+/// Payload format of the webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// Generic JSON event with all job fields
+    Generic,
+    /// Slack incoming webhook (`text`)
+    Slack,
+    /// Discord webhook (`content`)
+    Discord,
+}
+
+impl WebhookFormat {
+    /// Guess the format from the webhook URL
+    pub fn detect(url: &str) -> Self {
+        if url.contains("hooks.slack.com") {
+            Self::Slack
+        } else if url.contains("discord.com/api/webhooks")
+            || url.contains("discordapp.com/api/webhooks")
+        {
+            Self::Discord
+        } else {
+            Self::Generic
+        }
+    }
+}
+
+impl FromStr for WebhookFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "generic" => Ok(Self::Generic),
+            "slack" => Ok(Self::Slack),
+            "discord" => Ok(Self::Discord),
+            other => anyhow::bail!(
+                "Unknown webhook format '{}' (expected generic, slack or discord)",
+                other
+            ),
+        }
+    }
+}
+
+/// New postings found for a watchlist
+#[derive(Debug, Clone)]
+pub struct WatchlistAlert {
+    /// Watchlist name
+    pub watchlist: String,
+
+    /// When the check ran (RFC 3339)
+    pub checked_at: String,
+
+    /// The new postings
+    pub new_jobs: Vec<WatchlistJob>,
+}
+
+impl WatchlistAlert {
+    /// Webhook payload in the given format
+    pub fn payload(&self, format: WebhookFormat) -> Value {
+        match format {
+            WebhookFormat::Generic => json!({
+                "event": "watchlist.new_jobs",
+                "watchlist": self.watchlist,
+                "checked_at": self.checked_at,
+                "new_jobs_count": self.new_jobs.len(),
+                "jobs": self.new_jobs.iter().map(|new_job| {
+                    let job = &new_job.job;
+                    json!({
+                        "reference_number": job.reference_number,
+                        "title": job.title,
+                        "employer": job.employer,
+                        "location": job.location,
+                        "published_date": job.published_date,
+                        "url": job_url(&job.reference_number, job.external_url.as_deref()),
+                        "first_seen_at": new_job.first_seen_at,
+                    })
+                }).collect::<Vec<_>>(),
+            }),
+            WebhookFormat::Slack => json!({
+                "text": self.message(|title, url| format!("<{}|{}>", url, title), "*", "•"),
+            }),
+            WebhookFormat::Discord => json!({
+                "content": self.message(|title, url| format!("[{}](<{}>)", title, url), "**", "-"),
+            }),
+        }
+    }
+
+    /// Chat message listing the first jobs
+    fn message(&self, link: impl Fn(&str, &str) -> String, bold: &str, bullet: &str) -> String {
+        let count = self.new_jobs.len();
+        let mut message = format!(
+            "{}{} new job{} for watchlist \"{}\"{}",
+            bold,
+            count,
+            if count == 1 { "" } else { "s" },
+            self.watchlist,
+            bold
+        );
+
+        for new_job in self.new_jobs.iter().take(MAX_LISTED_JOBS) {
+            let job = &new_job.job;
+            let url = job_url(&job.reference_number, job.external_url.as_deref());
+            message.push_str(&format!(
+                "\n{} {} – {}, {}",
+                bullet,
+                link(&job.title, &url),
+                job.employer,
+                job.location
+            ));
+        }
+        if count > MAX_LISTED_JOBS {
+            message.push_str(&format!("\n…and {} more", count - MAX_LISTED_JOBS));
+        }
+        message
+    }
+}
+
+/// HMAC-SHA256 signature of `timestamp.body` as `sha256=<hex>`
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Sends watchlist alerts to a webhook
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+    format: WebhookFormat,
+    secret: Option<String>,
+}
+
+impl WebhookNotifier {
+    /// Create a notifier; the format is detected from the URL if not given
+    pub fn new(url: &str, format: Option<WebhookFormat>, secret: Option<String>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .user_agent(concat!("jobsuche-mcp-server/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Failed to create webhook client")?;
+
+        Ok(Self {
+            client,
+            url: url.to_string(),
+            format: format.unwrap_or_else(|| WebhookFormat::detect(url)),
+            secret,
+        })
+    }
+
+    /// Payload format used for this webhook
+    pub fn format(&self) -> WebhookFormat {
+        self.format
+    }
+
+    /// POST an alert, retrying failed deliveries
+    pub async fn notify(&self, alert: &WatchlistAlert) -> Result<()> {
+        let body = alert.payload(self.format).to_string();
+
+        let mut delay = RETRY_DELAY;
+        for attempt in 1..=MAX_ATTEMPTS {
+            match self.send(&body).await {
+                Ok(()) => {
+                    info!(
+                        "Sent webhook for watchlist '{}' ({} new jobs)",
+                        alert.watchlist,
+                        alert.new_jobs.len()
+                    );
+                    return Ok(());
+                }
+                Err(e) if attempt < MAX_ATTEMPTS => {
+                    warn!(
+                        "Webhook attempt {} for '{}' failed: {:#}",
+                        attempt, alert.watchlist, e
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+                Err(e) => {
+                    return Err(e.context(format!(
+                        "Webhook for '{}' failed after {} attempts",
+                        alert.watchlist, MAX_ATTEMPTS
+                    )))
+                }
+            }
+        }
+        unreachable!("the last attempt always returns")
+    }
+
+    async fn send(&self, body: &str) -> Result<()> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string());
+
+        if let Some(ref secret) = self.secret {
+            let timestamp = time::OffsetDateTime::now_utc().unix_timestamp();
+            request = request
+                .header(TIMESTAMP_HEADER, timestamp.to_string())
+                .header(SIGNATURE_HEADER, sign(secret, timestamp, body));
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("Webhook responded with HTTP {}", status);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::JobSummary;
+
+    fn alert(jobs: usize) -> WatchlistAlert {
+        WatchlistAlert {
+            watchlist: "Berlin Rust".to_string(),
+            checked_at: "2025-10-20T10:00:00Z".to_string(),
+            new_jobs: (0..jobs)
+                .map(|i| WatchlistJob {
+                    first_seen_at: "2025-10-20T10:00:00Z".to_string(),
+                    job: JobSummary {
+                        reference_number: format!("10001-{}-S", i),
+                        title: format!("Developer {}", i),
+                        employer: "Example GmbH".to_string(),
+                        location: "Berlin".to_string(),
+                        distance_km: None,
+                        coordinates: None,
+                        country: None,
+                        published_date: None,
+                        published_date_raw: None,
+                        published_days_ago: None,
+                        published_ago: None,
+                        external_url: None,
+                        web_url: String::new(),
+                        duplicates: Vec::new(),
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            WebhookFormat::detect("https://hooks.slack.com/services/T0/B0/X"),
+            WebhookFormat::Slack
+        );
+        assert_eq!(
+            WebhookFormat::detect("https://discord.com/api/webhooks/1/abc"),
+            WebhookFormat::Discord
+        );
+        assert_eq!(
+            WebhookFormat::detect("https://example.com/hook"),
+            WebhookFormat::Generic
+        );
+        assert_eq!(
+            "Slack".parse::<WebhookFormat>().unwrap(),
+            WebhookFormat::Slack
+        );
+        assert!("teams".parse::<WebhookFormat>().is_err());
+    }
+
+    #[test]
+    fn test_generic_payload() {
+        let payload = alert(2).payload(WebhookFormat::Generic);
+        assert_eq!(payload["event"], "watchlist.new_jobs");
+        assert_eq!(payload["new_jobs_count"], 2);
+        assert_eq!(
+            payload["jobs"][1]["url"],
+            "https://www.arbeitsagentur.de/jobsuche/jobdetail/10001-1-S"
+        );
+    }
+
+    #[test]
+    fn test_chat_payloads() {
+        let slack = alert(1).payload(WebhookFormat::Slack);
+        let text = slack["text"].as_str().unwrap();
+        assert!(text.starts_with("*1 new job for watchlist \"Berlin Rust\"*"));
+        assert!(text.contains(
+            "• <https://www.arbeitsagentur.de/jobsuche/jobdetail/10001-0-S|Developer 0>"
+        ));
+
+        let discord = alert(12).payload(WebhookFormat::Discord);
+        let content = discord["content"].as_str().unwrap();
+        assert!(content.starts_with("**12 new jobs"));
+        assert_eq!(content.lines().count(), 1 + MAX_LISTED_JOBS + 1);
+        assert!(content.ends_with("…and 2 more"));
+    }
+
+    #[test]
+    fn test_sign() {
+        // echo -n '1700000000.{"a":1}' | openssl dgst -sha256 -hmac secret
+        assert_eq!(
+            sign("secret", 1_700_000_000, r#"{"a":1}"#),
+            "sha256=49f24e537407743fa4a0242bb63b94b9a47ee99cbbe071ccd8a22550ae411686"
+        );
+    }
+}
@@ -0,0 +1,405 @@
+//! Occupation field taxonomy
+//!
+//! Users often start from a vague interest ("something with healthcare")
+//! rather than a job title. The Klassifikation der Berufe (KldB 2010) of the
+//! Bundesagentur für Arbeit groups occupations into ten occupational fields
+//! (Berufsbereiche). This module holds those fields with common occupations of
+//! each, so an agent can walk from a field to concrete occupations and search
+//! for them. The list is not exhaustive; suggest_job_titles finds the exact
+//! names of further occupations.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Parameters for list_occupation_fields
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListOccupationFieldsParams {
+    /// Interest, field or occupation to narrow the tree to (e.g. "Gesundheit",
+    /// "healthcare", "Logistik", "8"); without it all fields are listed
+    pub query: Option<String>,
+}
+
+/// A common occupation of a field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OccupationEntry {
+    /// Occupation group of the KldB (e.g. "Gesundheits- und Krankenpflege")
+    pub name: String,
+
+    /// KldB 2010 occupation code (five digits), usable with
+    /// get_salary_statistics
+    pub occupation_code: String,
+
+    /// Job title to pass to search_jobs as `job_title`
+    pub search_term: String,
+}
+
+/// An occupational field (Berufsbereich) with its occupations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OccupationField {
+    /// One-digit code of the field in the KldB 2010
+    pub code: String,
+
+    /// Name of the field
+    pub name: String,
+
+    /// Common occupations of the field
+    pub occupations: Vec<OccupationEntry>,
+}
+
+/// Result from list_occupation_fields
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListOccupationFieldsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Number of fields returned
+    pub fields_count: usize,
+
+    /// Number of occupations returned across all fields
+    pub occupations_count: usize,
+
+    /// Fields with their occupations
+    pub fields: Vec<OccupationField>,
+}
+
+/// Code, name, lowercase keywords (German and English) and occupations
+/// (occupation code, name, search term) of each field
+type FieldEntry = (
+    &'static str,
+    &'static str,
+    &'static [&'static str],
+    &'static [(&'static str, &'static str, &'static str)],
+);
+
+const FIELDS: &[FieldEntry] = &[
+    (
+        "0",
+        "Militär",
+        &["militär", "bundeswehr", "soldat", "military", "army"],
+        &[("01104", "Offiziere", "Offizier")],
+    ),
+    (
+        "1",
+        "Land-, Forst- und Tierwirtschaft und Gartenbau",
+        &[
+            "landwirtschaft",
+            "garten",
+            "tiere",
+            "natur",
+            "agriculture",
+            "farming",
+            "gardening",
+            "animals",
+            "nature",
+        ],
+        &[
+            ("11102", "Landwirtschaft", "Landwirt"),
+            ("12102", "Gartenbau", "Gärtner"),
+            ("12202", "Floristik", "Florist"),
+        ],
+    ),
+    (
+        "2",
+        "Rohstoffgewinnung, Produktion und Fertigung",
+        &[
+            "produktion",
+            "fertigung",
+            "handwerk",
+            "technik",
+            "metall",
+            "elektro",
+            "kfz",
+            "küche",
+            "manufacturing",
+            "production",
+            "craft",
+            "mechanic",
+            "cooking",
+        ],
+        &[
+            ("24412", "Metallbau", "Metallbauer"),
+            ("25102", "Maschinenbau- und Betriebstechnik", "Industriemechaniker"),
+            ("25212", "Kraftfahrzeugtechnik", "Kfz-Mechatroniker"),
+            ("26212", "Bauelektrik", "Elektroniker"),
+            ("29302", "Speisenzubereitung", "Koch"),
+        ],
+    ),
+    (
+        "3",
+        "Bau, Architektur, Vermessung und Gebäudetechnik",
+        &[
+            "bau",
+            "architektur",
+            "gebäude",
+            "handwerk",
+            "construction",
+            "architecture",
+            "building",
+            "craft",
+        ],
+        &[
+            ("31104", "Bauplanung und -überwachung", "Bauingenieur"),
+            ("32122", "Maurerhandwerk", "Maurer"),
+            (
+                "34212",
+                "Sanitär-, Heizungs- und Klimatechnik",
+                "Anlagenmechaniker SHK",
+            ),
+        ],
+    ),
+    (
+        "4",
+        "Naturwissenschaft, Geografie und Informatik",
+        &[
+            "informatik",
+            "it",
+            "software",
+            "computer",
+            "naturwissenschaft",
+            "programmieren",
+            "science",
+            "programming",
+            "tech",
+        ],
+        &[
+            ("43104", "Informatik", "Informatiker"),
+            ("43414", "Softwareentwicklung", "Softwareentwickler"),
+        ],
+    ),
+    (
+        "5",
+        "Verkehr, Logistik, Schutz und Sicherheit",
+        &[
+            "verkehr",
+            "logistik",
+            "lager",
+            "fahrer",
+            "sicherheit",
+            "transport",
+            "logistics",
+            "warehouse",
+            "driving",
+            "security",
+        ],
+        &[
+            ("51312", "Lagerwirtschaft", "Fachkraft für Lagerlogistik"),
+            ("52122", "Berufskraftfahrer (Güterverkehr/LKW)", "Berufskraftfahrer"),
+            ("53112", "Objekt- und Personenschutz", "Sicherheitsmitarbeiter"),
+        ],
+    ),
+    (
+        "6",
+        "Kaufmännische Dienstleistungen, Warenhandel, Vertrieb, Hotel und Tourismus",
+        &[
+            "handel",
+            "verkauf",
+            "vertrieb",
+            "einzelhandel",
+            "gastronomie",
+            "hotel",
+            "tourismus",
+            "retail",
+            "sales",
+            "hospitality",
+            "tourism",
+        ],
+        &[
+            ("61122", "Vertrieb", "Vertriebsmitarbeiter"),
+            ("62102", "Verkauf", "Verkäufer"),
+            ("63302", "Gastronomie", "Restaurantfachmann"),
+        ],
+    ),
+    (
+        "7",
+        "Unternehmensorganisation, Buchhaltung, Recht und Verwaltung",
+        &[
+            "büro",
+            "verwaltung",
+            "buchhaltung",
+            "finanzen",
+            "bank",
+            "recht",
+            "office",
+            "administration",
+            "accounting",
+            "finance",
+            "banking",
+            "law",
+        ],
+        &[
+            ("71402", "Büro und Sekretariat", "Kaufmann für Büromanagement"),
+            ("72112", "Bankkaufleute", "Bankkaufmann"),
+            ("72213", "Buchhaltung", "Buchhalter"),
+            ("73202", "Öffentliche Verwaltung", "Verwaltungsfachangestellter"),
+        ],
+    ),
+    (
+        "8",
+        "Gesundheit, Soziales, Lehre und Erziehung",
+        &[
+            "gesundheit",
+            "pflege",
+            "medizin",
+            "soziales",
+            "erziehung",
+            "schule",
+            "kinder",
+            "health",
+            "healthcare",
+            "nursing",
+            "medicine",
+            "social",
+            "education",
+            "teaching",
+            "children",
+        ],
+        &[
+            ("81102", "Arzt- und Praxishilfe", "Medizinischer Fachangestellter"),
+            ("81302", "Gesundheits- und Krankenpflege", "Pflegefachkraft"),
+            ("81404", "Humanmedizin", "Arzt"),
+            ("82102", "Altenpflege", "Altenpfleger"),
+            ("83112", "Kinderbetreuung und -erziehung", "Erzieher"),
+            ("83124", "Sozialarbeit und Sozialpädagogik", "Sozialarbeiter"),
+            ("84114", "Lehrkräfte in der Primarstufe", "Grundschullehrer"),
+        ],
+    ),
+    (
+        "9",
+        "Sprach-, Literatur-, Geistes-, Gesellschafts- und Wirtschaftswissenschaften, Medien, Kunst, Kultur und Gestaltung",
+        &[
+            "medien",
+            "marketing",
+            "kunst",
+            "kultur",
+            "gestaltung",
+            "design",
+            "sprache",
+            "media",
+            "art",
+            "culture",
+            "languages",
+        ],
+        &[("92113", "Werbung und Marketing", "Marketing Manager")],
+    ),
+];
+
+/// Convert a static field with the given occupations
+fn field(entry: &FieldEntry, occupations: Vec<OccupationEntry>) -> OccupationField {
+    OccupationField {
+        code: entry.0.to_string(),
+        name: entry.1.to_string(),
+        occupations,
+    }
+}
+
+/// Occupations of a static field
+fn occupations(entry: &FieldEntry) -> Vec<OccupationEntry> {
+    entry
+        .3
+        .iter()
+        .map(|(code, name, search_term)| OccupationEntry {
+            name: name.to_string(),
+            occupation_code: code.to_string(),
+            search_term: search_term.to_string(),
+        })
+        .collect()
+}
+
+/// Whether a field matches the query by code, name or keyword
+fn field_matches(entry: &FieldEntry, query: &str) -> bool {
+    entry.0 == query
+        || entry.1.to_lowercase().contains(query)
+        || entry.2.iter().any(|keyword| {
+            query
+                .split(|c: char| !c.is_alphanumeric())
+                .any(|word| word == *keyword)
+        })
+}
+
+/// Fields matching the query with their occupations
+///
+/// A field that matches by code, name or keyword is returned with all its
+/// occupations. Otherwise fields are returned with just the occupations whose
+/// name, search term or code matches.
+pub fn list_occupation_fields(query: Option<&str>) -> Vec<OccupationField> {
+    let query = query.map(|q| q.trim().to_lowercase()).unwrap_or_default();
+    if query.is_empty() {
+        return FIELDS
+            .iter()
+            .map(|entry| field(entry, occupations(entry)))
+            .collect();
+    }
+
+    let matched: Vec<OccupationField> = FIELDS
+        .iter()
+        .filter(|entry| field_matches(entry, &query))
+        .map(|entry| field(entry, occupations(entry)))
+        .collect();
+    if !matched.is_empty() {
+        return matched;
+    }
+
+    FIELDS
+        .iter()
+        .filter_map(|entry| {
+            let occupations: Vec<OccupationEntry> = occupations(entry)
+                .into_iter()
+                .filter(|occupation| {
+                    occupation.name.to_lowercase().contains(&query)
+                        || occupation.search_term.to_lowercase().contains(&query)
+                        || occupation.occupation_code.starts_with(&query)
+                })
+                .collect();
+            (!occupations.is_empty()).then(|| field(entry, occupations))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_all_fields() {
+        let fields = list_occupation_fields(None);
+        assert_eq!(fields.len(), 10);
+        for field in &fields {
+            assert!(!field.occupations.is_empty(), "{}", field.name);
+            for occupation in &field.occupations {
+                assert_eq!(occupation.occupation_code.len(), 5);
+                assert!(occupation.occupation_code.starts_with(&field.code));
+            }
+        }
+    }
+
+    #[test]
+    fn test_list_fields_by_interest() {
+        let fields = list_occupation_fields(Some("something with healthcare"));
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].code, "8");
+        assert!(fields[0]
+            .occupations
+            .iter()
+            .any(|occupation| occupation.search_term == "Pflegefachkraft"));
+
+        assert_eq!(list_occupation_fields(Some("Logistik"))[0].code, "5");
+        assert_eq!(list_occupation_fields(Some(" 4 "))[0].code, "4");
+    }
+
+    #[test]
+    fn test_list_fields_by_occupation() {
+        let fields = list_occupation_fields(Some("Altenpfleg"));
+        assert_eq!(fields.len(), 1);
+        assert_eq!(
+            fields[0].occupations,
+            vec![OccupationEntry {
+                name: "Altenpflege".to_string(),
+                occupation_code: "82102".to_string(),
+                search_term: "Altenpfleger".to_string(),
+            }]
+        );
+        assert!(list_occupation_fields(Some("Astronaut")).is_empty());
+    }
+}
@@ -0,0 +1,534 @@
+//! User profile for matching
+//!
+//! The profile holds what the user brings and wants: skills, desired
+//! locations, salary expectation, language levels and hard constraints. It is
+//! stored once per server; search and detail tools can compare jobs against it
+//! and report a `profile_match` with the score and the reasons for mismatches.
+
+use crate::comparison::ComparedJob;
+use crate::skills::SkillCategory;
+use crate::GetJobDetailsResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Storage key of the profile in the [`USER_PROFILE`](crate::storage::USER_PROFILE) collection
+pub const PROFILE_KEY: &str = "default";
+
+/// CEFR levels in ascending order
+const CEFR_LEVELS: &[&str] = &["A1", "A2", "B1", "B2", "C1", "C2"];
+
+/// Proficiency words (see [`crate::skills`]) and the CEFR level they stand for
+const PROFICIENCY_LEVELS: &[(&str, &str)] = &[
+    ("native", "C2"),
+    ("business fluent", "C1"),
+    ("fluent", "C1"),
+    ("very good", "B2"),
+    ("good", "B1"),
+    ("basic", "A2"),
+];
+
+/// A spoken language of the user
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileLanguage {
+    /// Language in English (e.g. "German")
+    pub language: String,
+
+    /// CEFR level ("A1" to "C2") or "native"
+    pub level: String,
+}
+
+/// The stored user profile
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct UserProfile {
+    /// Skills, tools and certifications (e.g. "Rust", "Docker")
+    pub skills: Vec<String>,
+
+    /// Cities or regions the user wants to work in; "remote" accepts remote jobs
+    pub desired_locations: Vec<String>,
+
+    /// Expected minimum salary per year
+    pub min_annual_salary: Option<f64>,
+
+    /// Spoken languages with level
+    pub languages: Vec<ProfileLanguage>,
+
+    /// Only permanent contracts
+    pub permanent_only: bool,
+
+    /// No temporary employment agencies (Zeitarbeit)
+    pub exclude_temp_agency: bool,
+
+    /// Only jobs with home office or remote work
+    pub remote_only: bool,
+
+    /// Full-time (true) or part-time (false) wanted
+    pub fulltime: Option<bool>,
+
+    /// Whether the user has a driver's license
+    pub has_drivers_license: Option<bool>,
+
+    /// When the profile was last changed (RFC 3339)
+    pub updated_at: String,
+}
+
+/// Parameters for set_user_profile; omitted fields keep their stored value
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SetUserProfileParams {
+    /// Skills, tools and certifications (e.g. ["Rust", "Docker", "Kubernetes"])
+    pub skills: Option<Vec<String>>,
+
+    /// Cities or regions to work in (e.g. ["Berlin", "Potsdam", "remote"])
+    pub desired_locations: Option<Vec<String>>,
+
+    /// Expected minimum salary per year
+    pub min_annual_salary: Option<f64>,
+
+    /// Spoken languages (e.g. [{"language": "German", "level": "C1"}])
+    pub languages: Option<Vec<ProfileLanguage>>,
+
+    /// Only permanent contracts
+    pub permanent_only: Option<bool>,
+
+    /// No temporary employment agencies
+    pub exclude_temp_agency: Option<bool>,
+
+    /// Only jobs with home office or remote work
+    pub remote_only: Option<bool>,
+
+    /// Full-time (true) or part-time (false) wanted
+    pub fulltime: Option<bool>,
+
+    /// Whether the user has a driver's license
+    pub has_drivers_license: Option<bool>,
+}
+
+/// Result from set_user_profile
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SetUserProfileResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// The stored profile
+    pub profile: UserProfile,
+
+    /// False if the profile has been created
+    pub updated: bool,
+}
+
+/// Parameters for get_user_profile
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetUserProfileParams {}
+
+/// Result from get_user_profile
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetUserProfileResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// The stored profile, null if none has been set
+    pub profile: Option<UserProfile>,
+}
+
+/// Parameters for delete_user_profile
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteUserProfileParams {}
+
+/// Result from delete_user_profile
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeleteUserProfileResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// False if no profile was stored
+    pub deleted: bool,
+}
+
+/// How well a job fits the user profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ProfileMatch {
+    /// Share of the checked criteria the job fulfils, from 0.0 to 1.0 (skills
+    /// count with the share of matching skills); 1.0 if nothing could be checked
+    pub score: f64,
+
+    /// Criteria that could be checked ("skills", "location", "salary",
+    /// "languages", "contract", "temp_agency", "remote", "working_time",
+    /// "drivers_license")
+    pub checked: Vec<String>,
+
+    /// Skills the job asks for that the profile has
+    pub matching_skills: Vec<String>,
+
+    /// Skills the job asks for that the profile lacks
+    pub missing_skills: Vec<String>,
+
+    /// Why the job does not fit (e.g. "fixed-term contract")
+    pub mismatches: Vec<String>,
+}
+
+fn trimmed(values: Vec<String>) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::new();
+    for value in values {
+        let value = value.trim();
+        if !value.is_empty() && !unique.iter().any(|v| v.eq_ignore_ascii_case(value)) {
+            unique.push(value.to_string());
+        }
+    }
+    unique
+}
+
+/// Position of a CEFR level or proficiency word, `None` if not recognised
+fn level_rank(level: &str) -> Option<usize> {
+    let level = level.trim();
+    let cefr = PROFICIENCY_LEVELS
+        .iter()
+        .find(|(word, _)| word.eq_ignore_ascii_case(level))
+        .map_or(level, |(_, cefr)| cefr);
+    CEFR_LEVELS
+        .iter()
+        .position(|known| known.eq_ignore_ascii_case(cefr))
+}
+
+impl SetUserProfileParams {
+    /// Reject invalid salaries and unknown language levels
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(salary) = self.min_annual_salary {
+            anyhow::ensure!(
+                salary.is_finite() && salary >= 0.0,
+                "min_annual_salary must be a positive number"
+            );
+        }
+        for language in self.languages.iter().flatten() {
+            anyhow::ensure!(
+                level_rank(&language.level).is_some(),
+                "Unknown level '{}' for {}: use A1 to C2 or native",
+                language.level,
+                language.language
+            );
+        }
+        Ok(())
+    }
+}
+
+impl UserProfile {
+    /// Apply the given fields to a stored profile (or a new one); the
+    /// parameters must have been validated
+    pub fn apply(current: Option<Self>, params: SetUserProfileParams, now: &str) -> Self {
+        let mut profile = current.unwrap_or_default();
+        if let Some(skills) = params.skills {
+            profile.skills = trimmed(skills);
+        }
+        if let Some(locations) = params.desired_locations {
+            profile.desired_locations = trimmed(locations);
+        }
+        if params.min_annual_salary.is_some() {
+            profile.min_annual_salary = params.min_annual_salary;
+        }
+        if let Some(languages) = params.languages {
+            profile.languages = languages
+                .into_iter()
+                .map(|language| ProfileLanguage {
+                    language: language.language.trim().to_string(),
+                    level: language.level.trim().to_string(),
+                })
+                .collect();
+        }
+        profile.permanent_only = params.permanent_only.unwrap_or(profile.permanent_only);
+        profile.exclude_temp_agency = params
+            .exclude_temp_agency
+            .unwrap_or(profile.exclude_temp_agency);
+        profile.remote_only = params.remote_only.unwrap_or(profile.remote_only);
+        profile.fulltime = params.fulltime.or(profile.fulltime);
+        profile.has_drivers_license = params.has_drivers_license.or(profile.has_drivers_license);
+        profile.updated_at = now.to_string();
+        profile
+    }
+
+    /// Level of a language in the profile
+    fn language_rank(&self, language: &str) -> Option<usize> {
+        self.languages
+            .iter()
+            .find(|known| known.language.eq_ignore_ascii_case(language))
+            .and_then(|known| level_rank(&known.level))
+    }
+
+    /// Compare a job against the profile
+    pub fn match_job(&self, details: &GetJobDetailsResult) -> ProfileMatch {
+        let job = ComparedJob::from_details(details);
+        let mut checked = Vec::new();
+        let mut mismatches = Vec::new();
+        let mut points = 0.0;
+
+        // Skills
+        let mut matching_skills = Vec::new();
+        let mut missing_skills = Vec::new();
+        let required_skills = details
+            .extracted_skills
+            .iter()
+            .filter(|skill| skill.category != SkillCategory::DriversLicense);
+        for skill in required_skills {
+            let known = self
+                .skills
+                .iter()
+                .any(|own| own.eq_ignore_ascii_case(&skill.name));
+            if known {
+                matching_skills.push(skill.name.clone());
+            } else {
+                missing_skills.push(skill.name.clone());
+            }
+        }
+        let asked = matching_skills.len() + missing_skills.len();
+        if !self.skills.is_empty() && asked > 0 {
+            checked.push("skills");
+            points += matching_skills.len() as f64 / asked as f64;
+            if !missing_skills.is_empty() {
+                mismatches.push(format!("missing skills: {}", missing_skills.join(", ")));
+            }
+        }
+
+        // Location
+        if let (false, Some(location)) = (self.desired_locations.is_empty(), &job.location) {
+            checked.push("location");
+            let location_lower = location.to_lowercase();
+            let fits = self.desired_locations.iter().any(|desired| {
+                if desired.eq_ignore_ascii_case("remote") {
+                    job.remote.fully_remote
+                } else {
+                    location_lower.contains(&desired.to_lowercase())
+                }
+            });
+            if fits || job.remote.fully_remote {
+                points += 1.0;
+            } else {
+                mismatches.push(format!("{} is not a desired location", location));
+            }
+        }
+
+        // Salary
+        let offered = job.annual_salary_max.or(job.annual_salary_min);
+        if let (Some(expected), Some(offered)) = (self.min_annual_salary, offered) {
+            checked.push("salary");
+            if offered >= expected {
+                points += 1.0;
+            } else {
+                mismatches.push(format!(
+                    "salary up to {:.0} per year is below the expected {:.0}",
+                    offered, expected
+                ));
+            }
+        }
+
+        // Languages
+        let required_languages: Vec<_> = details
+            .language_requirements
+            .iter()
+            .filter(|requirement| requirement.required)
+            .collect();
+        if !self.languages.is_empty() && !required_languages.is_empty() {
+            checked.push("languages");
+            let mut fits = true;
+            for requirement in required_languages {
+                let needed = requirement
+                    .level
+                    .as_deref()
+                    .or(requirement.proficiency.as_deref());
+                let needed_rank = needed.and_then(level_rank).unwrap_or(0);
+                match self.language_rank(&requirement.language) {
+                    Some(rank) if rank >= needed_rank => {}
+                    Some(_) => {
+                        fits = false;
+                        mismatches.push(format!(
+                            "{} {} required",
+                            requirement.language,
+                            needed.unwrap_or_default()
+                        ));
+                    }
+                    None => {
+                        fits = false;
+                        mismatches.push(format!("{} required", requirement.language));
+                    }
+                }
+            }
+            if fits {
+                points += 1.0;
+            }
+        }
+
+        // Constraints
+        let mut constraint = |name, applies: bool, fits: bool, reason: &str| {
+            if applies {
+                checked.push(name);
+                if fits {
+                    points += 1.0;
+                } else {
+                    mismatches.push(reason.to_string());
+                }
+            }
+        };
+        let permanent = job.is_permanent();
+        constraint(
+            "contract",
+            self.permanent_only && permanent.is_some(),
+            permanent == Some(true),
+            "fixed-term contract",
+        );
+        constraint(
+            "temp_agency",
+            self.exclude_temp_agency && job.is_temp_agency.is_some(),
+            job.is_temp_agency != Some(true),
+            "temporary employment agency",
+        );
+        constraint(
+            "remote",
+            self.remote_only,
+            job.remote.remote_possible,
+            "no home office or remote work mentioned",
+        );
+        constraint(
+            "working_time",
+            self.fulltime.is_some() && job.fulltime.is_some(),
+            self.fulltime == job.fulltime,
+            if self.fulltime == Some(true) {
+                "part-time position"
+            } else {
+                "full-time position"
+            },
+        );
+        let license_required = details
+            .extracted_skills
+            .iter()
+            .any(|skill| skill.category == SkillCategory::DriversLicense);
+        constraint(
+            "drivers_license",
+            license_required && self.has_drivers_license == Some(false),
+            false,
+            "driver's license required",
+        );
+
+        let score = if checked.is_empty() {
+            1.0
+        } else {
+            points / checked.len() as f64
+        };
+        ProfileMatch {
+            score: (score * 1000.0).round() / 1000.0,
+            checked: checked.into_iter().map(str::to_string).collect(),
+            matching_skills,
+            missing_skills,
+            mismatches,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(extra: serde_json::Value) -> GetJobDetailsResult {
+        let mut value = serde_json::json!({
+            "reference_number": "REF-1",
+            "raw_data": {}
+        });
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn profile() -> UserProfile {
+        let params: SetUserProfileParams = serde_json::from_value(serde_json::json!({
+            "skills": ["rust", " Docker ", "Rust"],
+            "desired_locations": ["Berlin"],
+            "min_annual_salary": 50000,
+            "languages": [{"language": "German", "level": "B2"}],
+            "permanent_only": true
+        }))
+        .unwrap();
+        UserProfile::apply(None, params, "2025-10-20T10:00:00Z")
+    }
+
+    #[test]
+    fn test_apply_keeps_omitted_fields() {
+        let profile = profile();
+        assert_eq!(profile.skills, vec!["rust", "Docker"]);
+
+        let params = SetUserProfileParams {
+            remote_only: Some(true),
+            ..SetUserProfileParams::default()
+        };
+        let updated = UserProfile::apply(Some(profile.clone()), params, "2025-10-21T10:00:00Z");
+        assert!(updated.remote_only);
+        assert_eq!(updated.skills, profile.skills);
+        assert_eq!(updated.min_annual_salary, Some(50000.0));
+        assert_eq!(updated.updated_at, "2025-10-21T10:00:00Z");
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_level() {
+        let params = SetUserProfileParams {
+            languages: Some(vec![ProfileLanguage {
+                language: "German".to_string(),
+                level: "excellent".to_string(),
+            }]),
+            ..SetUserProfileParams::default()
+        };
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn test_level_rank() {
+        assert_eq!(level_rank("b2"), Some(3));
+        assert_eq!(level_rank("native"), Some(5));
+        assert_eq!(level_rank("business fluent"), level_rank("C1"));
+        assert_eq!(level_rank("excellent"), None);
+    }
+
+    #[test]
+    fn test_match_job() {
+        let job = details(serde_json::json!({
+            "location": "Berlin (10115)",
+            "contract_duration": "befristet",
+            "salary_parsed": {"min": 4500.0, "max": 5000.0, "currency": "EUR", "period": "month"},
+            "extracted_skills": [
+                {"name": "Rust", "category": "programming_language"},
+                {"name": "Kubernetes", "category": "tool"}
+            ],
+            "language_requirements": [
+                {"language": "German", "level": "C1", "required": true},
+                {"language": "French", "required": false}
+            ]
+        }));
+
+        let matched = profile().match_job(&job);
+        assert_eq!(
+            matched.checked,
+            vec!["skills", "location", "salary", "languages", "contract"]
+        );
+        assert_eq!(matched.matching_skills, vec!["Rust"]);
+        assert_eq!(matched.missing_skills, vec!["Kubernetes"]);
+        assert_eq!(
+            matched.mismatches,
+            vec![
+                "missing skills: Kubernetes",
+                "German C1 required",
+                "fixed-term contract"
+            ]
+        );
+        // 0.5 (skills) + 1 (location) + 1 (salary) out of 5
+        assert_eq!(matched.score, 0.5);
+    }
+
+    #[test]
+    fn test_match_job_without_checks() {
+        let matched = UserProfile::default().match_job(&details(serde_json::json!({})));
+        assert!(matched.checked.is_empty());
+        assert_eq!(matched.score, 1.0);
+    }
+}
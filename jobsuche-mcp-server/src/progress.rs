@@ -0,0 +1,166 @@
+//! Progress reporting for long-running tools
+//!
+//! Batch searches and detail fetching can take several seconds. When the client
+//! sends a progress token with its request, each completed step is reported as
+//! an MCP `notifications/progress` message so hosts can show real progress.
+
+use crate::context::RequestContext;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::debug;
+
+/// Progress token as defined by MCP (string or integer)
+pub type ProgressToken = serde_json::Value;
+
+/// Parameters of an MCP `notifications/progress` message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressNotification {
+    /// Token from the originating request
+    pub progress_token: ProgressToken,
+
+    /// Number of completed steps
+    pub progress: u64,
+
+    /// Total number of steps, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+
+    /// Human-readable description of the last completed step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Destination for progress notifications
+///
+/// The transport integration implements this to forward notifications to the
+/// client. The default [`TracingProgressSink`] only logs them.
+pub trait ProgressSink: Send + Sync {
+    /// Deliver a single progress notification
+    fn send(&self, notification: ProgressNotification);
+}
+
+/// Progress sink that writes notifications to the debug log
+#[derive(Debug, Default)]
+pub struct TracingProgressSink;
+
+impl ProgressSink for TracingProgressSink {
+    fn send(&self, notification: ProgressNotification) {
+        debug!(
+            "notifications/progress: {}",
+            serde_json::to_string(&notification).unwrap_or_default()
+        );
+    }
+}
+
+/// Reports progress of a single tool call
+///
+/// Reporting is a no-op when the request carried no progress token.
+pub struct ProgressReporter {
+    token: Option<ProgressToken>,
+    sink: Arc<dyn ProgressSink>,
+    progress: u64,
+    total: Option<u64>,
+}
+
+impl ProgressReporter {
+    /// Create a reporter for the current request context
+    pub fn new(sink: Arc<dyn ProgressSink>) -> Self {
+        Self {
+            token: RequestContext::current().progress_token,
+            sink,
+            progress: 0,
+            total: None,
+        }
+    }
+
+    /// Set or update the expected total number of steps
+    pub fn set_total(&mut self, total: u64) {
+        self.total = Some(total);
+    }
+
+    /// Mark one more step as completed
+    pub fn advance(&mut self, message: impl Into<String>) {
+        self.report(self.progress + 1, message);
+    }
+
+    /// Report an absolute progress value
+    ///
+    /// Values that do not move progress forward are ignored.
+    pub fn report(&mut self, progress: u64, message: impl Into<String>) {
+        if progress <= self.progress {
+            return;
+        }
+        self.progress = progress;
+
+        if let Some(ref token) = self.token {
+            self.sink.send(ProgressNotification {
+                progress_token: token.clone(),
+                progress: self.progress,
+                total: self.total,
+                message: Some(message.into()),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingSink(Mutex<Vec<ProgressNotification>>);
+
+    impl ProgressSink for RecordingSink {
+        fn send(&self, notification: ProgressNotification) {
+            self.0.lock().unwrap().push(notification);
+        }
+    }
+
+    #[test]
+    fn test_notification_serialization() {
+        let notification = ProgressNotification {
+            progress_token: serde_json::json!("abc"),
+            progress: 2,
+            total: Some(5),
+            message: None,
+        };
+
+        let json = serde_json::to_value(&notification).unwrap();
+        assert_eq!(json["progressToken"], "abc");
+        assert_eq!(json["progress"], 2);
+        assert_eq!(json["total"], 5);
+        assert!(json.get("message").is_none());
+    }
+
+    #[test]
+    fn test_reporter_without_token_sends_nothing() {
+        let sink = Arc::new(RecordingSink::default());
+        let mut reporter = ProgressReporter::new(sink.clone());
+        reporter.advance("step");
+        assert!(sink.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reporter_with_token() {
+        let sink = Arc::new(RecordingSink::default());
+        let ctx = RequestContext::with_progress_token(serde_json::json!(7));
+
+        ctx.scope(async {
+            let mut reporter = ProgressReporter::new(sink.clone());
+            reporter.set_total(4);
+            reporter.advance("search done");
+            reporter.report(3, "skipped ahead");
+            reporter.report(1, "stale");
+        })
+        .await;
+
+        let sent = sink.0.lock().unwrap();
+        assert_eq!(sent.len(), 2); // stale report is dropped
+        assert_eq!(sent[0].progress, 1);
+        assert_eq!(sent[0].total, Some(4));
+        assert_eq!(sent[1].progress, 3);
+        assert_eq!(sent[0].progress_token, serde_json::json!(7));
+    }
+}
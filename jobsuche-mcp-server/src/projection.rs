@@ -0,0 +1,241 @@
+//! Field projection of tool results
+//!
+//! A `FieldFilter` reduces each job of a result to the fields a client asked
+//! for. The job is serialized first and the filter applied to the JSON, so
+//! fields that are left out are not sent at all. Paths use dots to reach into
+//! nested objects such as `raw_data.arbeitgeberAdresse.ort`; on arrays they
+//! apply to every element (`extracted_skills.name`). The reference number is
+//! always kept so projected jobs stay identifiable, and so is the request ID
+//! of a projected result.
+
+use crate::FieldFilter;
+use anyhow::Result;
+use schemars::{schema_for, JsonSchema};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Fields kept even if they are not included
+const KEPT_FIELDS: &[&str] = &["reference_number", "request_id"];
+
+/// Top-level field names of a result type
+pub fn field_names<T: JsonSchema>() -> Vec<String> {
+    schema_for!(T)
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Copy the value at `path` of `source` into `target`
+fn copy_path(source: &Value, target: &mut Value, path: &[&str]) {
+    let Some((first, rest)) = path.split_first() else {
+        *target = source.clone();
+        return;
+    };
+    match source {
+        Value::Object(fields) => {
+            let Some(child) = fields.get(*first) else {
+                return;
+            };
+            if !target.is_object() {
+                *target = Value::Object(Map::new());
+            }
+            if let Value::Object(target_fields) = target {
+                let slot = target_fields
+                    .entry(first.to_string())
+                    .or_insert(Value::Null);
+                copy_path(child, slot, rest);
+            }
+        }
+        Value::Array(items) => {
+            if !target.is_array() {
+                *target = Value::Array(vec![Value::Null; items.len()]);
+            }
+            if let Value::Array(slots) = target {
+                for (item, slot) in items.iter().zip(slots.iter_mut()) {
+                    copy_path(item, slot, path);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Remove the value at `path` from `value`
+fn remove_path(value: &mut Value, path: &[&str]) {
+    let Some((first, rest)) = path.split_first() else {
+        return;
+    };
+    match value {
+        Value::Object(fields) if rest.is_empty() => {
+            fields.remove(*first);
+        }
+        Value::Object(fields) => {
+            if let Some(child) = fields.get_mut(*first) {
+                remove_path(child, rest);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                remove_path(item, path);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Split a dot-path into its segments
+fn segments(path: &str) -> Vec<&str> {
+    path.split('.')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+impl FieldFilter {
+    /// Check that every path starts with one of the `known` top-level fields
+    pub fn validate(&self, known: &[String]) -> Result<()> {
+        let paths = self
+            .include_fields
+            .iter()
+            .chain(self.exclude_fields.iter())
+            .flatten();
+        for path in paths {
+            let field = segments(path).first().copied().unwrap_or_default();
+            if !known.iter().any(|name| name == field) {
+                anyhow::bail!(
+                    "Unknown field '{}' in fields filter; known fields: {}",
+                    path,
+                    known.join(", ")
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Reduce a serialized result to the included paths and remove the
+    /// excluded ones
+    pub fn apply(&self, value: Value) -> Value {
+        let mut projected = match self.include_fields.as_deref() {
+            Some(include) if !include.is_empty() => {
+                let mut projected = Value::Object(Map::new());
+                let kept = KEPT_FIELDS.iter().map(|field| field.to_string());
+                for path in include.iter().cloned().chain(kept) {
+                    copy_path(&value, &mut projected, &segments(&path));
+                }
+                projected
+            }
+            _ => value,
+        };
+        for path in self.exclude_fields.iter().flatten() {
+            remove_path(&mut projected, &segments(path));
+        }
+        projected
+    }
+}
+
+/// Serialize a result and apply the field filter, if any
+pub fn project<T: Serialize>(result: &T, filter: Option<&FieldFilter>) -> Result<Value> {
+    let value = serde_json::to_value(result)?;
+    Ok(match filter {
+        Some(filter) => filter.apply(value),
+        None => value,
+    })
+}
+
+/// Serialize a result and apply the field filter to each of its `jobs`
+pub fn project_jobs<T: Serialize>(result: &T, filter: Option<&FieldFilter>) -> Result<Value> {
+    let mut value = serde_json::to_value(result)?;
+    if let (Some(filter), Some(Value::Array(jobs))) = (filter, value.get_mut("jobs")) {
+        for job in jobs.iter_mut() {
+            *job = filter.apply(job.take());
+        }
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn job() -> Value {
+        json!({
+            "reference_number": "REF-1",
+            "title": "Pflegefachkraft",
+            "salary": "3.800 €",
+            "extracted_skills": [
+                {"name": "SAP", "category": "tool"},
+                {"name": "Führerschein B", "category": "drivers_license"}
+            ],
+            "raw_data": {
+                "arbeitgeberAdresse": {"ort": "Leipzig", "plz": "04109"},
+                "stellenbeschreibung": "Lange Beschreibung"
+            }
+        })
+    }
+
+    fn filter(include: &[&str], exclude: &[&str]) -> FieldFilter {
+        let list = |paths: &[&str]| {
+            (!paths.is_empty()).then(|| paths.iter().map(|path| path.to_string()).collect())
+        };
+        FieldFilter {
+            include_fields: list(include),
+            exclude_fields: list(exclude),
+        }
+    }
+
+    #[test]
+    fn test_include_fields() {
+        assert_eq!(
+            filter(&["title", "raw_data.arbeitgeberAdresse.ort"], &[]).apply(job()),
+            json!({
+                "reference_number": "REF-1",
+                "title": "Pflegefachkraft",
+                "raw_data": {"arbeitgeberAdresse": {"ort": "Leipzig"}}
+            })
+        );
+        assert_eq!(
+            filter(&["extracted_skills.name"], &[]).apply(job())["extracted_skills"],
+            json!([{"name": "SAP"}, {"name": "Führerschein B"}])
+        );
+    }
+
+    #[test]
+    fn test_exclude_fields() {
+        let projected = filter(&[], &["raw_data.stellenbeschreibung", "salary"]).apply(job());
+        assert!(projected.get("salary").is_none());
+        assert_eq!(
+            projected["raw_data"],
+            json!({"arbeitgeberAdresse": {"ort": "Leipzig", "plz": "04109"}})
+        );
+
+        let projected =
+            filter(&["title", "raw_data"], &["raw_data.arbeitgeberAdresse"]).apply(job());
+        assert_eq!(
+            projected["raw_data"],
+            json!({"stellenbeschreibung": "Lange Beschreibung"})
+        );
+    }
+
+    #[test]
+    fn test_project_jobs() {
+        let result = json!({"total_results": 1, "jobs": [job()]});
+        let projected = project_jobs(&result, Some(&filter(&["title"], &[]))).unwrap();
+        assert_eq!(projected["total_results"], 1);
+        assert_eq!(
+            projected["jobs"],
+            json!([{"reference_number": "REF-1", "title": "Pflegefachkraft"}])
+        );
+        assert_eq!(project_jobs(&result, None).unwrap(), result);
+    }
+
+    #[test]
+    fn test_validate() {
+        let known = vec!["title".to_string(), "raw_data".to_string()];
+        assert!(filter(&["title", "raw_data.anything"], &[])
+            .validate(&known)
+            .is_ok());
+        assert!(filter(&[], &["titel"]).validate(&known).is_err());
+    }
+}
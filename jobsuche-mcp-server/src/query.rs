@@ -0,0 +1,240 @@
+//! Search query normalization
+//!
+//! Arguments written by a model or pasted by a user carry stray whitespace,
+//! line breaks and now and then control characters, which the API takes
+//! literally. Every search term is trimmed, whitespace runs are collapsed
+//! and control characters dropped before the query is sent. Locations spelled
+//! without umlauts ("Muenchen", "Koeln") are replaced by the city name when
+//! they match a known German city; free keywords are left alone, since "ue"
+//! is no umlaut in "Quelle" or "Feuerwehr". Searches without a location use
+//! the country they target, else the configured default location and radius,
+//! unless they ask for a nationwide search. The query as sent is returned with
+//! the result so users see what was actually searched, including whether it
+//! covered all of Germany.
+
+use crate::completion::{fold_umlauts, GERMAN_CITIES};
+use crate::config::JobsucheConfig;
+use crate::country::country_name;
+use crate::SearchJobsParams;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Search terms as sent to the API
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EffectiveQuery {
+    /// Keywords searched for (job title, employer and branch combined)
+    pub keywords: Option<String>,
+
+    /// Location searched in
+    pub location: Option<String>,
+
+    /// Radius around the location in kilometers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub radius_km: Option<u64>,
+
+    /// The search covers all of Germany (no location was sent)
+    #[serde(default)]
+    pub nationwide: bool,
+}
+
+/// Trim, collapse whitespace and drop control characters
+pub fn sanitize(text: &str) -> String {
+    text.split(|c: char| c.is_whitespace() || c.is_control())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Known city with the umlauts of a transliterated name ("Muenchen" → "München")
+pub fn restore_umlauts(location: &str) -> Option<&'static str> {
+    let location = location.to_lowercase();
+    GERMAN_CITIES
+        .iter()
+        .find(|city| fold_umlauts(&city.to_lowercase(), true) == location)
+        .copied()
+}
+
+/// Sanitized term, if anything is left of it
+fn term(text: Option<&str>) -> Option<String> {
+    text.map(sanitize).filter(|text| !text.is_empty())
+}
+
+/// Query sent to the API for a search
+pub fn effective_query(params: &SearchJobsParams, config: &JobsucheConfig) -> EffectiveQuery {
+    let keywords: Vec<String> = [&params.job_title, &params.employer, &params.branch]
+        .into_iter()
+        .filter_map(|text| term(text.as_deref()))
+        .collect();
+    let keywords = (!keywords.is_empty()).then(|| keywords.join(" "));
+
+    if params.nationwide.unwrap_or(false) {
+        return EffectiveQuery {
+            keywords,
+            location: None,
+            radius_km: None,
+            nationwide: true,
+        };
+    }
+
+    // A targeted country is searched as a whole, without the default radius
+    if let (None, Some(country)) = (
+        term(params.location.as_deref()),
+        term(params.country.as_deref()),
+    ) {
+        return EffectiveQuery {
+            keywords,
+            location: Some(country_name(&country)),
+            radius_km: params.radius_km,
+            nationwide: false,
+        };
+    }
+
+    let location = term(params.location.as_deref())
+        .or_else(|| term(config.default_location.as_deref()))
+        .map(|location| {
+            match restore_umlauts(&location).filter(|_| config.transliterate_umlauts) {
+                Some(city) => city.to_string(),
+                None => location,
+            }
+        });
+    let radius_km = params
+        .radius_km
+        .or(config.default_radius_km.filter(|_| location.is_some()));
+
+    EffectiveQuery {
+        keywords,
+        nationwide: location.is_none(),
+        location,
+        radius_km,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sanitize() {
+        assert_eq!(sanitize("  Software\n\tEngineer  "), "Software Engineer");
+        assert_eq!(sanitize("Koch\u{0}\u{7}in"), "Koch in");
+        assert_eq!(sanitize(" \n "), "");
+    }
+
+    #[test]
+    fn test_restore_umlauts() {
+        assert_eq!(restore_umlauts("Muenchen"), Some("München"));
+        assert_eq!(restore_umlauts("koeln"), Some("Köln"));
+        assert_eq!(restore_umlauts("Berlin"), Some("Berlin"));
+        assert_eq!(restore_umlauts("Quelle"), None);
+    }
+
+    #[test]
+    fn test_effective_query() {
+        let params: SearchJobsParams = serde_json::from_value(json!({
+            "job_title": " Pflege\r\nfachkraft ",
+            "employer": "  ",
+            "branch": "Gesundheit",
+            "location": " Muenchen "
+        }))
+        .unwrap();
+        let mut config = JobsucheConfig::default();
+        assert_eq!(
+            effective_query(&params, &config),
+            EffectiveQuery {
+                keywords: Some("Pflege fachkraft Gesundheit".to_string()),
+                location: Some("München".to_string()),
+                radius_km: None,
+                nationwide: false,
+            }
+        );
+        config.transliterate_umlauts = false;
+        assert_eq!(
+            effective_query(&params, &config).location.as_deref(),
+            Some("Muenchen")
+        );
+
+        let params: SearchJobsParams = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(
+            effective_query(&params, &config),
+            EffectiveQuery {
+                nationwide: true,
+                ..EffectiveQuery::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_effective_query_default_location() {
+        let config = JobsucheConfig {
+            default_location: Some("Wuppertal".to_string()),
+            default_radius_km: Some(25),
+            ..JobsucheConfig::default()
+        };
+
+        let params: SearchJobsParams =
+            serde_json::from_value(json!({"job_title": "Pflegefachkraft"})).unwrap();
+        let query = effective_query(&params, &config);
+        assert_eq!(query.location.as_deref(), Some("Wuppertal"));
+        assert_eq!(query.radius_km, Some(25));
+
+        let params: SearchJobsParams =
+            serde_json::from_value(json!({"location": "Deutschland", "radius_km": 0})).unwrap();
+        let query = effective_query(&params, &config);
+        assert_eq!(query.location.as_deref(), Some("Deutschland"));
+        assert_eq!(query.radius_km, Some(0));
+    }
+
+    #[test]
+    fn test_effective_query_nationwide() {
+        let config = JobsucheConfig {
+            default_location: Some("Wuppertal".to_string()),
+            default_radius_km: Some(25),
+            ..JobsucheConfig::default()
+        };
+        let params: SearchJobsParams = serde_json::from_value(json!({
+            "job_title": "Pflegefachkraft",
+            "location": "Köln",
+            "radius_km": 50,
+            "nationwide": true
+        }))
+        .unwrap();
+
+        assert_eq!(
+            effective_query(&params, &config),
+            EffectiveQuery {
+                keywords: Some("Pflegefachkraft".to_string()),
+                location: None,
+                radius_km: None,
+                nationwide: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_effective_query_country() {
+        let config = JobsucheConfig {
+            default_location: Some("Wuppertal".to_string()),
+            default_radius_km: Some(25),
+            ..JobsucheConfig::default()
+        };
+        let params: SearchJobsParams =
+            serde_json::from_value(json!({"job_title": "Pflegefachkraft", "country": "CH"}))
+                .unwrap();
+        let query = effective_query(&params, &config);
+        assert_eq!(query.location.as_deref(), Some("Schweiz"));
+        assert_eq!(query.radius_km, None);
+        assert!(!query.nationwide);
+
+        let params: SearchJobsParams = serde_json::from_value(json!({
+            "location": "Lörrach",
+            "radius_km": 20,
+            "country": "CH"
+        }))
+        .unwrap();
+        assert_eq!(
+            effective_query(&params, &config).location.as_deref(),
+            Some("Lörrach")
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! Upstream quota usage
+//!
+//! The Jobsuche API does not publish its limits, and a single batch tool can
+//! issue hundreds of calls. [`QuotaTracker`] counts the server's calls to the
+//! Jobsuche API per hour (UTC) for the last seven days, so get_quota_usage
+//! and get_server_status can tell how much was used. With
+//! `JOBSUCHE_DAILY_API_BUDGET` set, batch tools refuse to start once the
+//! calls of the current day reach the budget; single searches and detail
+//! fetches keep working. Counts are kept in memory and start over when the
+//! server restarts.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// Number of hours for which counts are kept
+const RETAINED_HOURS: i64 = 7 * 24;
+
+/// Seconds per hour
+const HOUR_SECONDS: i64 = 3600;
+
+/// Kind of Jobsuche API call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpstreamCall {
+    /// Job search (one page of results)
+    Search,
+    /// Details of one job
+    JobDetails,
+}
+
+/// Number of calls by kind
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CallCounts {
+    /// Search calls (one per page of results)
+    pub search: u64,
+
+    /// Job detail calls
+    pub job_details: u64,
+}
+
+impl CallCounts {
+    /// All calls
+    pub fn total(&self) -> u64 {
+        self.search + self.job_details
+    }
+
+    fn add(&mut self, other: &Self) {
+        self.search += other.search;
+        self.job_details += other.job_details;
+    }
+}
+
+/// Calls within one hour
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HourlyUsage {
+    /// Start of the hour (RFC 3339, UTC)
+    pub hour: String,
+
+    /// Number of calls
+    pub calls: u64,
+}
+
+/// Snapshot of the Jobsuche API usage
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct QuotaUsage {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// When counting started (server start, RFC 3339)
+    pub counting_since: String,
+
+    /// Calls in the current hour
+    pub calls_this_hour: u64,
+
+    /// Calls since midnight UTC
+    pub calls_today: u64,
+
+    /// Calls in the last 24 hours
+    pub calls_last_24_hours: u64,
+
+    /// Calls in the last 7 days
+    pub calls_last_7_days: u64,
+
+    /// Calls since midnight UTC by kind
+    pub today_by_kind: CallCounts,
+
+    /// Hours of the last 24 with calls, oldest first
+    pub hourly: Vec<HourlyUsage>,
+
+    /// Soft daily budget from JOBSUCHE_DAILY_API_BUDGET, if set
+    pub daily_budget: Option<u64>,
+
+    /// Calls left today before batch tools refuse (`None` without a budget)
+    pub remaining_today: Option<u64>,
+
+    /// True once the calls of today reached the budget
+    pub budget_exhausted: bool,
+}
+
+/// Parameters for get_quota_usage
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetQuotaUsageParams {}
+
+/// Hour since the epoch of a point in time
+fn hour_of(time: OffsetDateTime) -> i64 {
+    time.unix_timestamp().div_euclid(HOUR_SECONDS)
+}
+
+/// RFC 3339 start of an hour since the epoch
+fn hour_start(hour: i64) -> String {
+    OffsetDateTime::from_unix_timestamp(hour * HOUR_SECONDS)
+        .ok()
+        .and_then(|time| time.format(&Rfc3339).ok())
+        .unwrap_or_default()
+}
+
+/// Hourly counts of the Jobsuche API calls
+#[derive(Debug)]
+pub struct QuotaTracker {
+    daily_budget: Option<u64>,
+    started: OffsetDateTime,
+    hours: Mutex<BTreeMap<i64, CallCounts>>,
+}
+
+impl QuotaTracker {
+    /// Create a tracker with an optional soft daily budget
+    pub fn new(daily_budget: Option<u64>) -> Self {
+        Self {
+            daily_budget,
+            started: OffsetDateTime::now_utc(),
+            hours: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Count a call
+    pub fn record(&self, call: UpstreamCall) {
+        self.record_at(call, OffsetDateTime::now_utc());
+    }
+
+    fn record_at(&self, call: UpstreamCall, now: OffsetDateTime) {
+        let hour = hour_of(now);
+        let mut hours = self.hours.lock().unwrap();
+        let counts = hours.entry(hour).or_default();
+        match call {
+            UpstreamCall::Search => counts.search += 1,
+            UpstreamCall::JobDetails => counts.job_details += 1,
+        }
+        hours.retain(|&counted, _| counted > hour - RETAINED_HOURS);
+    }
+
+    /// Current usage
+    pub fn usage(&self) -> QuotaUsage {
+        self.usage_at(OffsetDateTime::now_utc())
+    }
+
+    fn usage_at(&self, now: OffsetDateTime) -> QuotaUsage {
+        let hour = hour_of(now);
+        let midnight = hour - i64::from(now.hour());
+        let hours = self.hours.lock().unwrap();
+        let calls_since = |first: i64| -> u64 {
+            hours
+                .range(first..=hour)
+                .map(|(_, counts)| counts.total())
+                .sum()
+        };
+
+        let mut today_by_kind = CallCounts::default();
+        for counts in hours.range(midnight..=hour).map(|(_, counts)| counts) {
+            today_by_kind.add(counts);
+        }
+        let calls_today = today_by_kind.total();
+        let remaining_today = self
+            .daily_budget
+            .map(|budget| budget.saturating_sub(calls_today));
+
+        QuotaUsage {
+            request_id: String::new(),
+            counting_since: self.started.format(&Rfc3339).unwrap_or_default(),
+            calls_this_hour: calls_since(hour),
+            calls_today,
+            calls_last_24_hours: calls_since(hour - 23),
+            calls_last_7_days: calls_since(hour - RETAINED_HOURS + 1),
+            today_by_kind,
+            hourly: hours
+                .range(hour - 23..=hour)
+                .filter(|(_, counts)| counts.total() > 0)
+                .map(|(&hour, counts)| HourlyUsage {
+                    hour: hour_start(hour),
+                    calls: counts.total(),
+                })
+                .collect(),
+            daily_budget: self.daily_budget,
+            remaining_today,
+            budget_exhausted: remaining_today == Some(0),
+        }
+    }
+
+    /// Refuse a batch tool once the daily budget is used up
+    pub fn check_budget(&self, tool: &str) -> anyhow::Result<()> {
+        self.check_budget_at(tool, OffsetDateTime::now_utc())
+    }
+
+    fn check_budget_at(&self, tool: &str, now: OffsetDateTime) -> anyhow::Result<()> {
+        let usage = self.usage_at(now);
+        match usage.daily_budget {
+            Some(budget) if usage.budget_exhausted => anyhow::bail!(
+                "Daily Jobsuche API budget exhausted: {} of {} calls used today (UTC). \
+                 {} is paused until midnight UTC; search_jobs and get_job_details still \
+                 work. Raise JOBSUCHE_DAILY_API_BUDGET to allow more calls.",
+                usage.calls_today,
+                budget,
+                tool
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_usage_windows() {
+        let tracker = QuotaTracker::new(None);
+        tracker.record_at(UpstreamCall::Search, datetime!(2026-03-01 22:30 UTC));
+        tracker.record_at(UpstreamCall::Search, datetime!(2026-03-02 09:05 UTC));
+        tracker.record_at(UpstreamCall::JobDetails, datetime!(2026-03-02 09:40 UTC));
+        tracker.record_at(UpstreamCall::Search, datetime!(2026-02-20 12:00 UTC));
+
+        let usage = tracker.usage_at(datetime!(2026-03-02 09:50 UTC));
+        assert_eq!(usage.calls_this_hour, 2);
+        assert_eq!(usage.calls_today, 2);
+        assert_eq!(
+            usage.today_by_kind,
+            CallCounts {
+                search: 1,
+                job_details: 1
+            }
+        );
+        assert_eq!(usage.calls_last_24_hours, 3);
+        assert_eq!(usage.calls_last_7_days, 3);
+        assert_eq!(usage.hourly.len(), 2);
+        assert_eq!(usage.hourly[0].hour, "2026-03-01T22:00:00Z");
+        assert_eq!(usage.hourly[1].calls, 2);
+        assert_eq!(usage.remaining_today, None);
+        assert!(!usage.budget_exhausted);
+    }
+
+    #[test]
+    fn test_old_hours_are_dropped() {
+        let tracker = QuotaTracker::new(None);
+        tracker.record_at(UpstreamCall::Search, datetime!(2026-03-01 08:00 UTC));
+        tracker.record_at(UpstreamCall::Search, datetime!(2026-03-09 08:00 UTC));
+        assert_eq!(tracker.hours.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_budget() {
+        let tracker = QuotaTracker::new(Some(2));
+        let now = datetime!(2026-03-02 10:00 UTC);
+        tracker.record_at(UpstreamCall::Search, datetime!(2026-03-01 23:59 UTC));
+        tracker.record_at(UpstreamCall::Search, now);
+        assert!(tracker.check_budget_at("bulk_export_jobs", now).is_ok());
+        assert_eq!(tracker.usage_at(now).remaining_today, Some(1));
+
+        tracker.record_at(UpstreamCall::JobDetails, now);
+        let error = tracker
+            .check_budget_at("bulk_export_jobs", now)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("2 of 2 calls"));
+        assert!(error.contains("bulk_export_jobs"));
+
+        // A new day starts with a fresh budget
+        assert!(tracker
+            .check_budget_at("bulk_export_jobs", datetime!(2026-03-03 00:01 UTC))
+            .is_ok());
+    }
+}
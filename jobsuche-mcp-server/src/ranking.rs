@@ -0,0 +1,441 @@
+//! Preference-weighted ranking of jobs
+//!
+//! Every job gets a score from 0.0 to 1.0 per criterion (salary, distance,
+//! contract stability, remote work); the total is the weighted mean of these.
+//! Each criterion is reported with its value, score and contribution so the
+//! order can be explained. A criterion that cannot be determined for a job
+//! scores a neutral 0.5 and is marked as unknown.
+
+use crate::comparison::ComparedJob;
+use crate::SearchJobsParams;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Default number of jobs ranked from a search
+pub const DEFAULT_RANK_JOBS: u64 = 20;
+
+/// Maximum number of jobs ranked at once
+pub const MAX_RANK_JOBS: u64 = 50;
+
+/// Score of a criterion whose value is unknown
+const NEUTRAL_SCORE: f64 = 0.5;
+
+/// Distance in km at which the distance score reaches zero
+const MAX_DISTANCE_KM: f64 = 100.0;
+
+/// Contract stability score of a fixed-term contract
+const FIXED_TERM_SCORE: f64 = 0.4;
+
+/// Factor on the contract stability of temporary agency jobs
+const TEMP_AGENCY_FACTOR: f64 = 0.5;
+
+/// Remote score if working from home is possible but not fully remote
+const REMOTE_POSSIBLE_SCORE: f64 = 0.7;
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+/// Relative importance of the ranking criteria (0 ignores a criterion)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RankingWeights {
+    /// Weight of the yearly salary (default: 1)
+    #[serde(default = "default_weight")]
+    pub salary: f64,
+
+    /// Weight of a short distance to the search location (default: 1)
+    #[serde(default = "default_weight")]
+    pub distance: f64,
+
+    /// Weight of a permanent contract without temporary agency (default: 1)
+    #[serde(default = "default_weight")]
+    pub contract_stability: f64,
+
+    /// Weight of remote work (default: 1)
+    #[serde(default = "default_weight")]
+    pub remote: f64,
+}
+
+impl Default for RankingWeights {
+    fn default() -> Self {
+        Self {
+            salary: default_weight(),
+            distance: default_weight(),
+            contract_stability: default_weight(),
+            remote: default_weight(),
+        }
+    }
+}
+
+impl RankingWeights {
+    /// Reject negative or non-finite weights and all-zero weights
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let weights = [
+            self.salary,
+            self.distance,
+            self.contract_stability,
+            self.remote,
+        ];
+        anyhow::ensure!(
+            weights
+                .iter()
+                .all(|weight| weight.is_finite() && *weight >= 0.0),
+            "Weights must be zero or positive numbers"
+        );
+        anyhow::ensure!(
+            weights.iter().sum::<f64>() > 0.0,
+            "At least one weight must be positive"
+        );
+        Ok(())
+    }
+}
+
+/// Parameters for rank_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RankJobsParams {
+    /// Reference numbers of the jobs to rank
+    pub reference_numbers: Option<Vec<String>>,
+
+    /// Search whose results are ranked instead (same parameters as search_jobs;
+    /// set location for distance scores)
+    pub search: Option<SearchJobsParams>,
+
+    /// Weights of the criteria (default: 1 each)
+    pub weights: Option<RankingWeights>,
+
+    /// Maximum number of jobs to rank (default: 20, max: 50)
+    pub max_jobs: Option<u64>,
+}
+
+/// Score of one criterion for one job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CriterionScore {
+    /// Criterion ("salary", "distance", "contract_stability", "remote")
+    pub criterion: String,
+
+    /// The job's value (e.g. "42000-48000 EUR per year", "12 km", "permanent")
+    pub value: Option<String>,
+
+    /// Score from 0.0 (worst) to 1.0 (best); 0.5 if unknown
+    pub score: f64,
+
+    /// False if the value is unknown and the neutral score was used
+    pub known: bool,
+
+    /// Weight of the criterion
+    pub weight: f64,
+
+    /// Share of the total score: weight × score / sum of weights
+    pub contribution: f64,
+}
+
+/// A ranked job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RankedJob {
+    /// Position, starting at 1
+    pub rank: usize,
+
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Job title
+    pub title: Option<String>,
+
+    /// Employer name
+    pub employer: Option<String>,
+
+    /// Location
+    pub location: Option<String>,
+
+    /// Total score from 0.0 to 1.0, the sum of the contributions
+    pub score: f64,
+
+    /// Score per criterion
+    pub criteria: Vec<CriterionScore>,
+}
+
+/// Result from rank_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RankJobsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Weights used
+    pub weights: RankingWeights,
+
+    /// Number of ranked jobs
+    pub jobs_count: usize,
+
+    /// Jobs, best first
+    pub jobs: Vec<RankedJob>,
+
+    /// Jobs left out because their details could not be fetched
+    pub missing_details: Vec<String>,
+}
+
+/// A job to be ranked
+#[derive(Debug, Clone)]
+pub struct RankCandidate {
+    /// Normalized job details
+    pub job: ComparedJob,
+
+    /// Distance to the search location, if known
+    pub distance_km: Option<f64>,
+}
+
+fn round(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+/// Middle of the yearly salary range
+fn annual_salary(job: &ComparedJob) -> Option<f64> {
+    match (job.annual_salary_min, job.annual_salary_max) {
+        (Some(min), Some(max)) => Some((min + max) / 2.0),
+        (amount, None) | (None, amount) => amount,
+    }
+}
+
+/// Criterion values and scores of one job; the salary score is relative to
+/// the lowest and highest salary among the ranked jobs
+fn criterion_scores(
+    candidate: &RankCandidate,
+    salary_range: Option<(f64, f64)>,
+) -> Vec<(&'static str, Option<String>, Option<f64>)> {
+    let job = &candidate.job;
+
+    let salary = annual_salary(job)
+        .zip(salary_range)
+        .map(|(salary, (lo, hi))| {
+            let score = if hi > lo {
+                (salary - lo) / (hi - lo)
+            } else {
+                1.0
+            };
+            let value = match job.salary_currency {
+                Some(ref currency) => format!("{:.0} {} per year", salary, currency),
+                None => format!("{:.0} per year", salary),
+            };
+            (value, score)
+        });
+
+    let distance = candidate.distance_km.map(|distance| {
+        let score = (1.0 - distance / MAX_DISTANCE_KM).clamp(0.0, 1.0);
+        (format!("{:.0} km", distance), score)
+    });
+
+    let contract = job.is_permanent().map(|permanent| {
+        let (mut value, mut score) = if permanent {
+            ("permanent".to_string(), 1.0)
+        } else {
+            ("fixed-term".to_string(), FIXED_TERM_SCORE)
+        };
+        if job.is_temp_agency == Some(true) {
+            value.push_str(", temporary agency");
+            score *= TEMP_AGENCY_FACTOR;
+        }
+        (value, score)
+    });
+
+    let remote_score = if job.remote.fully_remote {
+        1.0
+    } else if job.remote.remote_possible {
+        REMOTE_POSSIBLE_SCORE
+    } else {
+        0.0
+    };
+    let remote = Some((job.remote.label().to_string(), remote_score));
+
+    [
+        ("salary", salary),
+        ("distance", distance),
+        ("contract_stability", contract),
+        ("remote", remote),
+    ]
+    .into_iter()
+    .map(|(criterion, scored)| match scored {
+        Some((value, score)) => (criterion, Some(value), Some(score)),
+        None => (criterion, None, None),
+    })
+    .collect()
+}
+
+/// Score and sort jobs, best first; ties keep the input order
+pub fn rank_jobs(candidates: &[RankCandidate], weights: &RankingWeights) -> Vec<RankedJob> {
+    let salaries: Vec<f64> = candidates
+        .iter()
+        .filter_map(|candidate| annual_salary(&candidate.job))
+        .collect();
+    let salary_range = salaries
+        .iter()
+        .copied()
+        .reduce(f64::min)
+        .zip(salaries.iter().copied().reduce(f64::max));
+    let total_weight =
+        weights.salary + weights.distance + weights.contract_stability + weights.remote;
+
+    let mut ranked: Vec<RankedJob> = candidates
+        .iter()
+        .map(|candidate| {
+            let criteria: Vec<CriterionScore> = criterion_scores(candidate, salary_range)
+                .into_iter()
+                .map(|(criterion, value, score)| {
+                    let weight = match criterion {
+                        "salary" => weights.salary,
+                        "distance" => weights.distance,
+                        "contract_stability" => weights.contract_stability,
+                        _ => weights.remote,
+                    };
+                    let known = score.is_some();
+                    let score = score.unwrap_or(NEUTRAL_SCORE);
+                    CriterionScore {
+                        criterion: criterion.to_string(),
+                        value,
+                        score: round(score),
+                        known,
+                        weight,
+                        contribution: round(weight * score / total_weight),
+                    }
+                })
+                .collect();
+            let job = &candidate.job;
+            RankedJob {
+                rank: 0,
+                reference_number: job.reference_number.clone(),
+                title: job.title.clone(),
+                employer: job.employer.clone(),
+                location: job.location.clone(),
+                score: round(
+                    criteria.iter().map(|c| c.weight * c.score).sum::<f64>() / total_weight,
+                ),
+                criteria,
+            }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    for (index, job) in ranked.iter_mut().enumerate() {
+        job.rank = index + 1;
+    }
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GetJobDetailsResult;
+
+    fn candidate(
+        reference_number: &str,
+        extra: serde_json::Value,
+        distance_km: Option<f64>,
+    ) -> RankCandidate {
+        let mut value = serde_json::json!({
+            "reference_number": reference_number,
+            "raw_data": {}
+        });
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        let details: GetJobDetailsResult = serde_json::from_value(value).unwrap();
+        RankCandidate {
+            job: ComparedJob::from_details(&details),
+            distance_km,
+        }
+    }
+
+    fn criterion<'a>(job: &'a RankedJob, name: &str) -> &'a CriterionScore {
+        job.criteria.iter().find(|c| c.criterion == name).unwrap()
+    }
+
+    #[test]
+    fn test_weights_default_and_validate() {
+        let weights: RankingWeights =
+            serde_json::from_value(serde_json::json!({"salary": 3})).unwrap();
+        assert_eq!(weights.salary, 3.0);
+        assert_eq!(weights.remote, 1.0);
+        assert!(weights.validate().is_ok());
+
+        let negative = RankingWeights {
+            distance: -1.0,
+            ..RankingWeights::default()
+        };
+        assert!(negative.validate().is_err());
+        let zero = RankingWeights {
+            salary: 0.0,
+            distance: 0.0,
+            contract_stability: 0.0,
+            remote: 0.0,
+        };
+        assert!(zero.validate().is_err());
+    }
+
+    #[test]
+    fn test_rank_jobs() {
+        let candidates = vec![
+            candidate(
+                "LOW",
+                serde_json::json!({
+                    "salary_parsed": {"min": 40000.0, "max": 40000.0, "currency": "EUR", "period": "year"},
+                    "contract_duration": "befristet",
+                    "is_temp_agency": true
+                }),
+                Some(80.0),
+            ),
+            candidate(
+                "HIGH",
+                serde_json::json!({
+                    "salary_parsed": {"min": 5000.0, "max": 5000.0, "currency": "EUR", "period": "month"},
+                    "contract_duration": "unbefristet",
+                    "description": "Homeoffice möglich"
+                }),
+                Some(10.0),
+            ),
+        ];
+
+        let ranked = rank_jobs(&candidates, &RankingWeights::default());
+        assert_eq!(ranked[0].reference_number, "HIGH");
+        assert_eq!(ranked[0].rank, 1);
+        assert_eq!(ranked[1].rank, 2);
+
+        let best = &ranked[0];
+        assert_eq!(criterion(best, "salary").score, 1.0);
+        assert_eq!(
+            criterion(best, "salary").value.as_deref(),
+            Some("60000 EUR per year")
+        );
+        assert_eq!(criterion(best, "distance").score, 0.9);
+        assert_eq!(criterion(best, "contract_stability").score, 1.0);
+        assert_eq!(criterion(best, "remote").score, 0.7);
+        assert_eq!(best.score, 0.9);
+
+        let worst = &ranked[1];
+        assert_eq!(criterion(worst, "salary").score, 0.0);
+        assert_eq!(criterion(worst, "contract_stability").score, 0.2);
+        assert_eq!(
+            criterion(worst, "contract_stability").value.as_deref(),
+            Some("fixed-term, temporary agency")
+        );
+    }
+
+    #[test]
+    fn test_unknown_criteria_score_neutral() {
+        let ranked = rank_jobs(
+            &[candidate("A", serde_json::json!({}), None)],
+            &RankingWeights {
+                remote: 0.0,
+                ..RankingWeights::default()
+            },
+        );
+        let job = &ranked[0];
+        let distance = criterion(job, "distance");
+        assert!(!distance.known);
+        assert_eq!(distance.score, 0.5);
+        assert_eq!(distance.contribution, 0.167);
+        assert_eq!(criterion(job, "remote").contribution, 0.0);
+        assert_eq!(job.score, 0.5);
+    }
+}
@@ -0,0 +1,226 @@
+//! External application URL resolution
+//!
+//! External links in search results often point at tracking or job-board
+//! redirects. Following the redirect chain reveals the application portal
+//! the candidate actually ends up on (e.g. the employer's applicant tracking
+//! system).
+//!
+//! The URLs come from employers, so every hop is checked: only http(s) URLs
+//! whose host is, or resolves to, a public address are requested. Loopback,
+//! private, link-local and other local targets are rejected, so a posting
+//! cannot make the server probe the user's network.
+
+use anyhow::{Context, Result};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::{redirect::Policy, StatusCode, Url};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Maximum number of redirects followed
+const MAX_REDIRECTS: usize = 10;
+
+/// Whether an address belongs to the public internet
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // Shared address space (carrier-grade NAT), 100.64.0.0/10
+                || (a == 100 && (b & 0xc0) == 64)
+                || a == 0)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_public(IpAddr::V4(ip));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // Unique local fc00::/7 and link-local fe80::/10
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+/// Check a URL before it is requested
+///
+/// Hosts given as addresses are checked here; names are checked when they are
+/// resolved ([`PublicResolver`]).
+fn check_target(url: &Url) -> Result<()> {
+    anyhow::ensure!(
+        matches!(url.scheme(), "http" | "https"),
+        "Only http(s) URLs can be resolved"
+    );
+    // IPv6 hosts are written in brackets
+    let host = url
+        .host_str()
+        .unwrap_or_default()
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let public = match host.parse::<IpAddr>() {
+        Ok(ip) => is_public(ip),
+        Err(_) => {
+            let domain = host.trim_end_matches('.').to_lowercase();
+            !domain.is_empty() && domain != "localhost" && !domain.ends_with(".localhost")
+        }
+    };
+    anyhow::ensure!(public, "{} is not a public address", url);
+    Ok(())
+}
+
+/// DNS resolver that only returns public addresses
+#[derive(Debug)]
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} does not resolve to a public address", host).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Follows redirect chains of external URLs
+#[derive(Debug, Clone)]
+pub struct UrlResolver {
+    client: reqwest::Client,
+}
+
+impl UrlResolver {
+    /// Create a resolver
+    pub fn new() -> Result<Self> {
+        let policy = Policy::custom(|attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                return attempt.error("too many redirects");
+            }
+            match check_target(attempt.url()) {
+                Ok(()) => attempt.follow(),
+                Err(e) => attempt.error(e.to_string()),
+            }
+        });
+        // Without a proxy, so that every connection goes through the checks
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .redirect(policy)
+            .dns_resolver(Arc::new(PublicResolver))
+            .no_proxy()
+            .build()
+            .context("Failed to create URL resolver")?;
+        Ok(Self { client })
+    }
+
+    /// Final URL after following all redirects
+    ///
+    /// Uses a HEAD request and falls back to GET for servers that reject HEAD.
+    pub async fn resolve(&self, url: &str) -> Result<String> {
+        let url = Url::parse(url).with_context(|| format!("Invalid URL '{}'", url))?;
+        check_target(&url)?;
+
+        let response = self
+            .client
+            .head(url.clone())
+            .send()
+            .await
+            .with_context(|| format!("Failed to resolve {}", url))?;
+        if !matches!(
+            response.status(),
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+        ) {
+            return Ok(response.url().to_string());
+        }
+
+        let response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .with_context(|| format!("Failed to resolve {}", url))?;
+        Ok(response.url().to_string())
+    }
+}
+
+/// Host of a URL without a leading "www.", e.g. "jobs.example.com"
+pub fn url_domain(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    Some(host.strip_prefix("www.").unwrap_or(host).to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_domain() {
+        assert_eq!(
+            url_domain("https://www.Example-Jobs.de/apply?id=1"),
+            Some("example-jobs.de".to_string())
+        );
+        assert_eq!(
+            url_domain("https://career.example.com:8443/"),
+            Some("career.example.com".to_string())
+        );
+        assert_eq!(url_domain("not a url"), None);
+    }
+
+    #[test]
+    fn test_is_public() {
+        for ip in ["93.184.216.34", "2a00:1450:4001:80b::200e"] {
+            assert!(is_public(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.178.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_rejects_local_targets() {
+        let resolver = UrlResolver::new().unwrap();
+        for url in [
+            "http://127.0.0.1:9/",
+            "http://[::1]/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://localhost:8080/admin",
+            "http://192.168.0.1/",
+        ] {
+            let error = resolver.resolve(url).await.unwrap_err();
+            assert!(format!("{:#}", error).contains("public address"), "{}", url);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_rejects_other_schemes() {
+        let resolver = UrlResolver::new().unwrap();
+        assert!(resolver.resolve("ftp://example.com/job").await.is_err());
+        assert!(resolver.resolve("no url").await.is_err());
+    }
+}
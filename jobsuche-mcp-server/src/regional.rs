@@ -0,0 +1,205 @@
+//! Regional labor market overviews
+//!
+//! Someone weighing a move wants to know who hires in a region and in which
+//! occupations, not a list of postings. The overview runs a fixed set of
+//! queries for a federal state or a city: the postings of the region are
+//! sampled for the top employers and occupations, and the API counts the
+//! postings per employment type. Federal states are searched as a whole,
+//! cities with a radius.
+
+use crate::entgeltatlas::federal_state;
+use crate::market::EmployerCount;
+use crate::SearchJobsParams;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Default search radius in km around a city
+pub const DEFAULT_REGION_RADIUS_KM: u64 = 25;
+
+/// Default number of postings sampled for employers and occupations
+pub const DEFAULT_REGION_JOBS: u64 = 200;
+
+/// Maximum number of postings sampled for employers and occupations
+pub const MAX_REGION_JOBS: u64 = 500;
+
+/// Number of employers and occupations listed
+pub const TOP_REGION_ENTRIES: usize = 10;
+
+/// Parameters for regional_overview
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RegionalOverviewParams {
+    /// Federal state (e.g. "Sachsen") or city (e.g. "Leipzig")
+    pub region: String,
+
+    /// Only count postings matching this job title or keywords
+    pub job_title: Option<String>,
+
+    /// Search radius in km around a city (default: 25; ignored for federal
+    /// states)
+    pub radius_km: Option<u64>,
+
+    /// Number of postings sampled for the top employers and occupations
+    /// (default: 200, max: 500)
+    pub max_jobs: Option<u64>,
+}
+
+/// Kind of region
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RegionType {
+    /// One of the 16 federal states, searched as a whole
+    FederalState,
+    /// A city or place, searched with a radius
+    City,
+}
+
+/// Number of postings for one occupation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OccupationCount {
+    /// Occupation as classified by the Bundesagentur für Arbeit
+    pub occupation: String,
+
+    /// Number of sampled postings
+    pub count: usize,
+}
+
+/// Result from regional_overview
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RegionalOverviewResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Region as searched
+    pub region: String,
+
+    /// Whether the region was searched as a federal state or a city
+    pub region_type: RegionType,
+
+    /// Search radius in km (cities only)
+    pub radius_km: Option<u64>,
+
+    /// Total number of postings in the region
+    pub total_results: Option<u64>,
+
+    /// Number of postings sampled for the top employers and occupations
+    pub analyzed_jobs: usize,
+
+    /// Employers with the most sampled postings, top 10
+    pub top_employers: Vec<EmployerCount>,
+
+    /// Occupations with the most sampled postings, top 10
+    pub top_occupations: Vec<OccupationCount>,
+
+    /// All postings of the region per employment type ("fulltime",
+    /// "parttime", "mini_job", "home_office", "shift"), counted by the API
+    pub jobs_by_employment_type: BTreeMap<String, u64>,
+}
+
+/// Search for the postings of a region, with the region as searched and its
+/// kind
+pub fn region_search(params: &RegionalOverviewParams) -> (SearchJobsParams, String, RegionType) {
+    let region = params.region.trim();
+    let (location, region_type, radius_km) = match federal_state(region) {
+        Some((_, state)) => (state.to_string(), RegionType::FederalState, None),
+        None => (
+            region.to_string(),
+            RegionType::City,
+            Some(params.radius_km.unwrap_or(DEFAULT_REGION_RADIUS_KM)),
+        ),
+    };
+    let search = SearchJobsParams {
+        job_title: params.job_title.clone(),
+        location: Some(location.clone()),
+        radius_km,
+        nationwide: None,
+        employment_type: None,
+        contract_type: None,
+        published_since_days: None,
+        freshness: None,
+        page_size: None,
+        page: None,
+        employer: None,
+        branch: None,
+        hide_seen: None,
+        english_postings_only: None,
+        collapse_duplicates: None,
+        exclude_locations: None,
+        country: None,
+        include_abroad: None,
+        sort_by: None,
+        fields: None,
+    };
+    (search, location, region_type)
+}
+
+/// Count postings per occupation, most postings first (ties alphabetically)
+pub fn count_by_occupation<'a>(
+    occupations: impl IntoIterator<Item = &'a str>,
+) -> Vec<OccupationCount> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for occupation in occupations {
+        let occupation = occupation.trim();
+        if !occupation.is_empty() {
+            *counts.entry(occupation).or_default() += 1;
+        }
+    }
+
+    let mut counts: Vec<OccupationCount> = counts
+        .into_iter()
+        .map(|(occupation, count)| OccupationCount {
+            occupation: occupation.to_string(),
+            count,
+        })
+        .collect();
+    counts.sort_by_key(|count| std::cmp::Reverse(count.count));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(region: &str) -> RegionalOverviewParams {
+        RegionalOverviewParams {
+            region: region.to_string(),
+            job_title: None,
+            radius_km: None,
+            max_jobs: None,
+        }
+    }
+
+    #[test]
+    fn test_region_search() {
+        let (search, region, region_type) = region_search(&params(" sachsen "));
+        assert_eq!(region, "Sachsen");
+        assert_eq!(region_type, RegionType::FederalState);
+        assert_eq!(search.location.as_deref(), Some("Sachsen"));
+        assert_eq!(search.radius_km, None);
+
+        let (search, region, region_type) = region_search(&params("Leipzig"));
+        assert_eq!(region, "Leipzig");
+        assert_eq!(region_type, RegionType::City);
+        assert_eq!(search.radius_km, Some(DEFAULT_REGION_RADIUS_KM));
+    }
+
+    #[test]
+    fn test_count_by_occupation() {
+        let counts = count_by_occupation(["Koch/Köchin", "Altenpfleger/in", "Koch/Köchin", " "]);
+        assert_eq!(
+            counts,
+            vec![
+                OccupationCount {
+                    occupation: "Koch/Köchin".to_string(),
+                    count: 2
+                },
+                OccupationCount {
+                    occupation: "Altenpfleger/in".to_string(),
+                    count: 1
+                },
+            ]
+        );
+    }
+}
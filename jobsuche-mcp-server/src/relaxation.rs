@@ -0,0 +1,186 @@
+//! Relaxed variants of searches without results
+//!
+//! When a search finds nothing, the server counts the results of relaxed
+//! variants (one filter dropped, a wider radius, all filters dropped) and
+//! reports them together with the relaxed search, so the client can see which
+//! change would yield results instead of guessing.
+
+use crate::SearchJobsParams;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Radius the API uses if none is given
+pub const DEFAULT_RADIUS_KM: u64 = 25;
+
+/// Largest radius suggested
+pub const MAX_RADIUS_KM: u64 = 200;
+
+/// Smallest widened radius
+const MIN_WIDENED_RADIUS_KM: u64 = 50;
+
+/// A relaxed variant of a search without results
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchRelaxation {
+    /// What was relaxed (e.g. "drop employment_type", "radius_km 50")
+    pub relaxation: String,
+
+    /// Number of jobs the relaxed search finds (0 if it does not help either)
+    pub total_results: u64,
+
+    /// The relaxed search, ready to run with search_jobs
+    pub search: SearchJobsParams,
+}
+
+/// Radius to try instead of the given one, `None` if it cannot be widened
+pub fn widened_radius(radius_km: Option<u64>) -> Option<u64> {
+    let current = radius_km.unwrap_or(DEFAULT_RADIUS_KM);
+    (current < MAX_RADIUS_KM).then(|| {
+        current
+            .saturating_mul(2)
+            .clamp(MIN_WIDENED_RADIUS_KM, MAX_RADIUS_KM)
+    })
+}
+
+/// Relaxed variants of a search, least invasive first
+///
+/// Each variant drops one filter (or widens the radius); if more than one
+/// applies, a last variant relaxes all of them. Variants start at the first
+/// page.
+pub fn relaxed_searches(params: &SearchJobsParams) -> Vec<(String, SearchJobsParams)> {
+    let base = SearchJobsParams {
+        page: None,
+        ..params.clone()
+    };
+    let mut all = base.clone();
+    let mut variants = Vec::new();
+
+    if params.employment_type.is_some() {
+        all.employment_type = None;
+        variants.push((
+            "drop employment_type".to_string(),
+            SearchJobsParams {
+                employment_type: None,
+                ..base.clone()
+            },
+        ));
+    }
+    if params.published_since_days.is_some() {
+        all.published_since_days = None;
+        variants.push((
+            "drop published_since_days".to_string(),
+            SearchJobsParams {
+                published_since_days: None,
+                ..base.clone()
+            },
+        ));
+    }
+    if params.freshness.is_some() {
+        all.freshness = None;
+        variants.push((
+            "drop freshness".to_string(),
+            SearchJobsParams {
+                freshness: None,
+                ..base.clone()
+            },
+        ));
+    }
+    if params.location.is_some() && !params.nationwide.unwrap_or(false) {
+        if let Some(radius_km) = widened_radius(params.radius_km) {
+            all.radius_km = Some(radius_km);
+            variants.push((
+                format!("radius_km {}", radius_km),
+                SearchJobsParams {
+                    radius_km: Some(radius_km),
+                    ..base.clone()
+                },
+            ));
+        }
+    }
+    if params.employer.is_some() {
+        all.employer = None;
+        variants.push((
+            "drop employer".to_string(),
+            SearchJobsParams {
+                employer: None,
+                ..base.clone()
+            },
+        ));
+    }
+    if params.branch.is_some() {
+        all.branch = None;
+        variants.push((
+            "drop branch".to_string(),
+            SearchJobsParams {
+                branch: None,
+                ..base.clone()
+            },
+        ));
+    }
+
+    if variants.len() > 1 {
+        let relaxation = variants
+            .iter()
+            .map(|(relaxation, _)| relaxation.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        variants.push((relaxation, all));
+    }
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(value: serde_json::Value) -> SearchJobsParams {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_widened_radius() {
+        assert_eq!(widened_radius(None), Some(50));
+        assert_eq!(widened_radius(Some(10)), Some(50));
+        assert_eq!(widened_radius(Some(80)), Some(160));
+        assert_eq!(widened_radius(Some(150)), Some(200));
+        assert_eq!(widened_radius(Some(200)), None);
+    }
+
+    #[test]
+    fn test_relaxed_searches() {
+        let search = params(serde_json::json!({
+            "job_title": "Koch",
+            "location": "Husum",
+            "employment_type": ["parttime"],
+            "employer": "Strandhotel",
+            "page": 3
+        }));
+
+        let variants = relaxed_searches(&search);
+        let names: Vec<&str> = variants.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "drop employment_type",
+                "radius_km 50",
+                "drop employer",
+                "drop employment_type, radius_km 50, drop employer"
+            ]
+        );
+
+        let (_, drop_type) = &variants[0];
+        assert!(drop_type.employment_type.is_none());
+        assert_eq!(drop_type.employer.as_deref(), Some("Strandhotel"));
+        assert_eq!(drop_type.page, None);
+
+        let (_, all) = &variants[3];
+        assert!(all.employment_type.is_none() && all.employer.is_none());
+        assert_eq!(all.radius_km, Some(50));
+        assert_eq!(all.job_title.as_deref(), Some("Koch"));
+    }
+
+    #[test]
+    fn test_no_relaxation_without_filters() {
+        let search = params(serde_json::json!({"job_title": "Koch"}));
+        assert!(relaxed_searches(&search).is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+//! Visa sponsorship and relocation signals
+//!
+//! Whether an employer sponsors a visa or helps with the move is among the
+//! first questions of applicants from outside the EU, and the API has no field
+//! for either. Descriptions that offer such help usually say so in a sentence
+//! mentioning a visa, the Blue Card or relocation support. Sentences that deny
+//! it ("no visa sponsorship") or ask the applicant for it ("gültiges Visum
+//! erforderlich") do not count.
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Visa, residence permit for skilled workers or Blue Card
+fn visa_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(?:visas?|visum|visums\w*|visa-\w+|blauen? karte|blue card|work permit sponsorship|sponsor(?:s|ing)? (?:your |a |the )?(?:visa|work permit))\b",
+        )
+        .expect("valid visa pattern")
+    })
+}
+
+/// Relocation support or moving costs
+fn relocation_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(?:relocation|relocate|relocating|umzugs(?:kosten|hilfe|unterstützung|pauschale|paket|service)\w*|umzug|unterstützung bei der wohnungssuche|hilfe bei der wohnungssuche)\b",
+        )
+        .expect("valid relocation pattern")
+    })
+}
+
+/// Words that turn a mention into a denial or a requirement
+fn negation_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(?:no|not|cannot|can't|unable|without|required|valid|kein|keine|keinen|nicht|ohne|erforderlich|gültige[sn]?|voraussetzung)\b",
+        )
+        .expect("valid negation pattern")
+    })
+}
+
+/// Whether a sentence of the text matches `pattern` without a negation
+fn mentions(text: &str, pattern: &Regex) -> bool {
+    text.split(['.', '!', '?', '\n', ';'])
+        .any(|sentence| pattern.is_match(sentence) && !negation_pattern().is_match(sentence))
+}
+
+/// Whether a description offers visa sponsorship or help with the Blue Card
+pub fn mentions_visa_sponsorship(text: &str) -> bool {
+    mentions(text, visa_pattern())
+}
+
+/// Whether a description offers relocation support
+pub fn mentions_relocation(text: &str) -> bool {
+    mentions(text, relocation_pattern())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentions_visa_sponsorship() {
+        assert!(mentions_visa_sponsorship(
+            "We offer visa sponsorship and support with your Blue Card application."
+        ));
+        assert!(mentions_visa_sponsorship(
+            "Wir unterstützen Sie beim Visumsverfahren."
+        ));
+        assert!(mentions_visa_sponsorship(
+            "Unterstützung bei der Beantragung der Blauen Karte EU"
+        ));
+        assert!(!mentions_visa_sponsorship(
+            "Unfortunately we cannot offer visa sponsorship."
+        ));
+        assert!(!mentions_visa_sponsorship(
+            "Voraussetzung ist ein gültiges Visum für Deutschland."
+        ));
+        assert!(!mentions_visa_sponsorship("Wir bieten 30 Tage Urlaub."));
+    }
+
+    #[test]
+    fn test_mentions_relocation() {
+        assert!(mentions_relocation(
+            "Relocation package and help finding an apartment"
+        ));
+        assert!(mentions_relocation(
+            "Wir übernehmen Ihre Umzugskosten nach Hamburg."
+        ));
+        assert!(!mentions_relocation("No relocation support is provided."));
+        assert!(!mentions_relocation("Ein Umzug ist nicht erforderlich."));
+        assert!(!mentions_relocation("Flexible Arbeitszeiten"));
+    }
+}
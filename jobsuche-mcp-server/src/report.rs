@@ -0,0 +1,234 @@
+//! Markdown reports
+//!
+//! Renders job lists as a Markdown document with a linked heading and the key
+//! facts per job, optionally grouped by employer or location. The report can
+//! be handed to the user as is, e.g. as a weekly job-hunt summary.
+
+use crate::export::{ExportColumn, ExportRow};
+use crate::SearchJobsParams;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// How jobs are grouped in a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportGrouping {
+    Employer,
+    Location,
+}
+
+impl ReportGrouping {
+    fn column(self) -> ExportColumn {
+        match self {
+            Self::Employer => ExportColumn::Employer,
+            Self::Location => ExportColumn::Location,
+        }
+    }
+
+    fn unknown(self) -> &'static str {
+        match self {
+            Self::Employer => "Unknown employer",
+            Self::Location => "Unknown location",
+        }
+    }
+}
+
+/// Report heading derived from the search parameters
+pub fn report_title(search: Option<&SearchJobsParams>) -> String {
+    let Some(search) = search else {
+        return "Selected jobs".to_string();
+    };
+
+    let what = [&search.job_title, &search.employer, &search.branch]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    match (what.is_empty(), &search.location) {
+        (false, Some(location)) => format!("Jobs: {} in {}", what, location),
+        (false, None) => format!("Jobs: {}", what),
+        (true, Some(location)) => format!("Jobs in {}", location),
+        (true, None) => "Jobs".to_string(),
+    }
+}
+
+/// Render jobs as a Markdown report
+///
+/// Every job gets a heading linking to the posting and a list with the
+/// selected columns; title and link are always shown.
+pub fn render_markdown(
+    title: &str,
+    generated_at: &str,
+    columns: &[ExportColumn],
+    rows: &[ExportRow],
+    group_by: Option<ReportGrouping>,
+) -> String {
+    let mut report = String::new();
+    let _ = writeln!(report, "# {}\n", escape_markdown(title));
+    let _ = writeln!(
+        report,
+        "_Generated {} · {} jobs_\n",
+        generated_at,
+        rows.len()
+    );
+
+    if rows.is_empty() {
+        report.push_str("No jobs found.\n");
+        return report;
+    }
+
+    let Some(grouping) = group_by else {
+        for row in rows {
+            write_job(&mut report, row, columns, None);
+        }
+        return report;
+    };
+
+    let mut groups: BTreeMap<&str, Vec<&ExportRow>> = BTreeMap::new();
+    for row in rows {
+        let key = match row.value(grouping.column()) {
+            "" => grouping.unknown(),
+            value => value,
+        };
+        groups.entry(key).or_default().push(row);
+    }
+
+    for (group, group_rows) in groups {
+        let _ = writeln!(
+            report,
+            "## {} ({})\n",
+            escape_markdown(group),
+            group_rows.len()
+        );
+        for row in group_rows {
+            write_job(&mut report, row, columns, Some(grouping.column()));
+        }
+    }
+    report
+}
+
+fn write_job(
+    report: &mut String,
+    row: &ExportRow,
+    columns: &[ExportColumn],
+    group_column: Option<ExportColumn>,
+) {
+    let title = match row.value(ExportColumn::Title) {
+        "" => row.reference_number(),
+        title => title,
+    };
+    let _ = writeln!(
+        report,
+        "### [{}]({})\n",
+        escape_markdown(title),
+        row.value(ExportColumn::Url)
+    );
+
+    for column in columns {
+        let shown_elsewhere = matches!(column, ExportColumn::Title | ExportColumn::Url)
+            || Some(*column) == group_column;
+        let value = row.value(*column);
+        if shown_elsewhere || value.is_empty() {
+            continue;
+        }
+        let _ = writeln!(
+            report,
+            "- **{}:** {}",
+            column.label(),
+            escape_markdown(value)
+        );
+    }
+    report.push('\n');
+}
+
+/// Escape characters with a meaning in Markdown
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::DEFAULT_COLUMNS;
+    use crate::JobSummary;
+
+    fn row(reference_number: &str, title: &str, employer: &str) -> ExportRow {
+        ExportRow::from_summary(&JobSummary {
+            reference_number: reference_number.to_string(),
+            title: title.to_string(),
+            employer: employer.to_string(),
+            location: "Berlin".to_string(),
+            distance_km: None,
+            coordinates: None,
+            country: None,
+            published_date: None,
+            published_date_raw: None,
+            published_days_ago: None,
+            published_ago: None,
+            external_url: None,
+            web_url: String::new(),
+            duplicates: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_report_title() {
+        let search: SearchJobsParams = serde_json::from_value(serde_json::json!({
+            "job_title": "Data Scientist",
+            "location": "Hamburg"
+        }))
+        .unwrap();
+        assert_eq!(
+            report_title(Some(&search)),
+            "Jobs: Data Scientist in Hamburg"
+        );
+        assert_eq!(report_title(None), "Selected jobs");
+    }
+
+    #[test]
+    fn test_render_grouped_by_employer() {
+        let rows = vec![
+            row("B-1", "Backend Dev", "Zeta AG"),
+            row("A-1", "Frontend Dev", "Alpha GmbH"),
+            row("A-2", "Ops [m/w/d]", "Alpha GmbH"),
+        ];
+        let report = render_markdown(
+            "Weekly",
+            "2025-10-20",
+            DEFAULT_COLUMNS,
+            &rows,
+            Some(ReportGrouping::Employer),
+        );
+
+        assert!(report.starts_with("# Weekly\n\n_Generated 2025-10-20 · 3 jobs_\n"));
+        let alpha = report.find("## Alpha GmbH (2)").unwrap();
+        let zeta = report.find("## Zeta AG (1)").unwrap();
+        assert!(alpha < zeta);
+        assert!(report.contains(
+            "### [Ops \\[m/w/d\\]](https://www.arbeitsagentur.de/jobsuche/jobdetail/A-2)"
+        ));
+        assert!(report.contains("- **Location:** Berlin"));
+        // The group value is not repeated per job
+        assert!(!report.contains("- **Employer:**"));
+    }
+
+    #[test]
+    fn test_render_empty() {
+        let report = render_markdown("Empty", "2025-10-20", DEFAULT_COLUMNS, &[], None);
+        assert!(report.contains("No jobs found."));
+    }
+}
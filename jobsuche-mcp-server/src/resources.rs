@@ -0,0 +1,332 @@
+//! Job resources and chunked description reads
+//!
+//! A job is addressable as `jobsuche://jobs/{reference_number}`. Some
+//! descriptions run to tens of thousands of characters, more than a host
+//! wants in one message, so the URI takes a character range
+//! (`?offset=8000&length=8000`) or a page (`?page=2`, pages of `length`
+//! characters). Every chunk names the URI of the next one, so a long posting
+//! can be consumed incrementally. read_job_description offers the same for
+//! hosts without resource support.
+//!
+//! `jobsuche://about` returns the build metadata ([`crate::build_info`]).
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// URI prefix of job resources
+pub const JOB_URI_PREFIX: &str = "jobsuche://jobs/";
+
+/// URI template of job resources
+pub const JOB_URI_TEMPLATE: &str = "jobsuche://jobs/{reference_number}";
+
+/// URI templates of all resources the server offers
+pub const RESOURCE_TEMPLATES: &[&str] = &[JOB_URI_TEMPLATE];
+
+/// URI of the build metadata resource
+pub const ABOUT_URI: &str = "jobsuche://about";
+
+/// Fixed resources the server offers
+pub const RESOURCES: &[&str] = &[ABOUT_URI];
+
+/// Default number of characters per chunk
+pub const DEFAULT_CHUNK_CHARS: usize = 8000;
+
+/// Maximum number of characters per chunk
+pub const MAX_CHUNK_CHARS: usize = 32000;
+
+/// Character range of a chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkRange {
+    /// First character (0-based)
+    pub offset: usize,
+
+    /// Number of characters
+    pub length: usize,
+}
+
+impl ChunkRange {
+    /// Range from an offset or a 1-based page, with the default length if none is given
+    pub fn new(
+        offset: Option<usize>,
+        length: Option<usize>,
+        page: Option<usize>,
+    ) -> anyhow::Result<Self> {
+        let length = length.unwrap_or(DEFAULT_CHUNK_CHARS);
+        anyhow::ensure!(
+            (1..=MAX_CHUNK_CHARS).contains(&length),
+            "length must be between 1 and {}",
+            MAX_CHUNK_CHARS
+        );
+        let offset = match (offset, page) {
+            (Some(_), Some(_)) => anyhow::bail!("Use either offset or page, not both"),
+            (_, Some(0)) => anyhow::bail!("page starts at 1"),
+            (_, Some(page)) => (page - 1) * length,
+            (offset, None) => offset.unwrap_or(0),
+        };
+        Ok(Self { offset, length })
+    }
+}
+
+/// A parsed job resource URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobResourceUri {
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Requested part of the description
+    pub range: ChunkRange,
+}
+
+/// URI of a chunk of a job
+pub fn job_uri(reference_number: &str, range: ChunkRange) -> String {
+    let mut uri = format!("{}{}", JOB_URI_PREFIX, reference_number);
+    if range.offset > 0 {
+        uri.push_str(&format!("?offset={}", range.offset));
+    }
+    if range.length != DEFAULT_CHUNK_CHARS {
+        let separator = if range.offset > 0 { '&' } else { '?' };
+        uri.push_str(&format!("{}length={}", separator, range.length));
+    }
+    uri
+}
+
+/// Parse `jobsuche://jobs/{reference_number}[?offset=..&length=..|?page=..]`
+pub fn parse_job_uri(uri: &str) -> anyhow::Result<JobResourceUri> {
+    let Some(rest) = uri.trim().strip_prefix(JOB_URI_PREFIX) else {
+        anyhow::bail!(
+            "Unknown resource URI '{}', expected {}{{reference_number}}",
+            uri,
+            JOB_URI_PREFIX
+        );
+    };
+    let (reference_number, query) = rest.split_once('?').unwrap_or((rest, ""));
+    anyhow::ensure!(
+        !reference_number.is_empty() && !reference_number.contains('/'),
+        "Resource URI '{}' does not name a job",
+        uri
+    );
+
+    let (mut offset, mut length, mut page) = (None, None, None);
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let number: usize = value
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid value '{}' for {} in '{}'", value, name, uri))?;
+        match name {
+            "offset" => offset = Some(number),
+            "length" => length = Some(number),
+            "page" => page = Some(number),
+            _ => anyhow::bail!(
+                "Unknown parameter '{}' in '{}' (use offset, length or page)",
+                name,
+                uri
+            ),
+        }
+    }
+
+    Ok(JobResourceUri {
+        reference_number: reference_number.to_string(),
+        range: ChunkRange::new(offset, length, page)?,
+    })
+}
+
+/// Characters of `text` in `range`, cut at character (not byte) boundaries
+pub fn chunk_text(text: &str, range: ChunkRange) -> &str {
+    let mut boundaries = text
+        .char_indices()
+        .map(|(index, _)| index)
+        .chain(std::iter::once(text.len()));
+    let Some(start) = boundaries.nth(range.offset) else {
+        return "";
+    };
+    let end = boundaries.nth(range.length - 1).unwrap_or(text.len());
+    &text[start..end]
+}
+
+/// Parameters for read_job_description
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReadJobDescriptionParams {
+    /// Job reference number
+    #[schemars(length(min = 1))]
+    pub reference_number: String,
+
+    /// First character to return (0-based, default: 0)
+    pub offset: Option<usize>,
+
+    /// Number of characters to return (1-32000, default: 8000)
+    #[schemars(range(min = 1, max = 32000))]
+    pub length: Option<usize>,
+
+    /// Page of `length` characters (starting from 1) instead of offset
+    #[schemars(range(min = 1))]
+    pub page: Option<usize>,
+}
+
+/// A chunk of a job description
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DescriptionChunk {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Job reference number
+    pub reference_number: String,
+
+    /// Job title
+    pub title: Option<String>,
+
+    /// Employer name
+    pub employer: Option<String>,
+
+    /// URI of this chunk
+    pub uri: String,
+
+    /// First character of the chunk (0-based)
+    pub offset: usize,
+
+    /// Number of characters in the chunk
+    pub length: usize,
+
+    /// Number of characters of the whole description
+    pub total_chars: usize,
+
+    /// Number of pages of the requested length
+    pub total_pages: usize,
+
+    /// The characters of the description in this chunk
+    pub text: String,
+
+    /// URI of the next chunk (`None` once the description is complete)
+    pub next_uri: Option<String>,
+
+    /// Offset of the next chunk for read_job_description (`None` once the
+    /// description is complete)
+    pub next_offset: Option<usize>,
+}
+
+impl DescriptionChunk {
+    /// Chunk of a description
+    pub fn new(
+        reference_number: &str,
+        title: Option<String>,
+        employer: Option<String>,
+        description: &str,
+        range: ChunkRange,
+    ) -> Self {
+        let total_chars = description.chars().count();
+        let text = chunk_text(description, range).to_string();
+        let length = text.chars().count();
+        let end = range.offset + length;
+        let next_offset = (end < total_chars).then_some(end);
+        Self {
+            request_id: String::new(),
+            reference_number: reference_number.to_string(),
+            title,
+            employer,
+            uri: job_uri(reference_number, range),
+            offset: range.offset,
+            length,
+            total_chars,
+            total_pages: total_chars.div_ceil(range.length),
+            text,
+            next_uri: next_offset.map(|offset| {
+                job_uri(
+                    reference_number,
+                    ChunkRange {
+                        offset,
+                        length: range.length,
+                    },
+                )
+            }),
+            next_offset,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_job_uri() {
+        let uri = parse_job_uri("jobsuche://jobs/10000-1234567890-S").unwrap();
+        assert_eq!(uri.reference_number, "10000-1234567890-S");
+        assert_eq!(
+            uri.range,
+            ChunkRange {
+                offset: 0,
+                length: DEFAULT_CHUNK_CHARS
+            }
+        );
+
+        let uri = parse_job_uri("jobsuche://jobs/10000-1234567890-S?page=3&length=1000").unwrap();
+        assert_eq!(
+            uri.range,
+            ChunkRange {
+                offset: 2000,
+                length: 1000
+            }
+        );
+
+        assert!(parse_job_uri("jobsuche://jobs/").is_err());
+        assert!(parse_job_uri("https://example.org/jobs/1").is_err());
+        assert!(parse_job_uri("jobsuche://jobs/1?offset=5&page=2").is_err());
+        assert!(parse_job_uri("jobsuche://jobs/1?length=0").is_err());
+        assert!(parse_job_uri("jobsuche://jobs/1?size=10").is_err());
+    }
+
+    #[test]
+    fn test_job_uri_round_trip() {
+        let range = ChunkRange {
+            offset: 16000,
+            length: 4000,
+        };
+        let uri = job_uri("10000-1234567890-S", range);
+        assert_eq!(
+            uri,
+            "jobsuche://jobs/10000-1234567890-S?offset=16000&length=4000"
+        );
+        assert_eq!(parse_job_uri(&uri).unwrap().range, range);
+    }
+
+    #[test]
+    fn test_chunk_text_respects_characters() {
+        let text = "Größe für Führung";
+        let range = |offset, length| ChunkRange { offset, length };
+        assert_eq!(chunk_text(text, range(0, 5)), "Größe");
+        assert_eq!(chunk_text(text, range(6, 3)), "für");
+        assert_eq!(chunk_text(text, range(10, 100)), "Führung");
+        assert_eq!(chunk_text(text, range(100, 5)), "");
+    }
+
+    #[test]
+    fn test_description_chunks_cover_text() {
+        let description = "a".repeat(25);
+        let first = DescriptionChunk::new(
+            "1",
+            None,
+            None,
+            &description,
+            ChunkRange::new(None, Some(10), None).unwrap(),
+        );
+        assert_eq!(first.length, 10);
+        assert_eq!(first.total_pages, 3);
+        assert_eq!(first.next_offset, Some(10));
+        assert_eq!(
+            first.next_uri.as_deref(),
+            Some("jobsuche://jobs/1?offset=10&length=10")
+        );
+
+        let last = DescriptionChunk::new(
+            "1",
+            None,
+            None,
+            &description,
+            ChunkRange::new(None, Some(10), Some(3)).unwrap(),
+        );
+        assert_eq!(last.offset, 20);
+        assert_eq!(last.length, 5);
+        assert_eq!(last.next_uri, None);
+    }
+}
@@ -0,0 +1,550 @@
+//! Salary parsing
+//!
+//! `verguetung` is free text such as "45.000 - 55.000 EUR p.a.", "ab 17 €/Std."
+//! or "Vergütung nach TVöD E 13". The parser extracts a range, currency,
+//! period and tariff reference where the text allows it, so salaries can be
+//! compared and filtered without re-reading the string. Anything it cannot
+//! recognise is left out; the raw text is always returned alongside.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Period a salary refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SalaryPeriod {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// Structured form of a salary text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ParsedSalary {
+    /// Lower bound (or the single amount)
+    pub min: Option<f64>,
+
+    /// Upper bound (or the single amount)
+    pub max: Option<f64>,
+
+    /// ISO currency code (e.g. "EUR")
+    pub currency: Option<String>,
+
+    /// Period the amounts refer to
+    pub period: Option<SalaryPeriod>,
+
+    /// Collective agreement (e.g. "TVöD"; "Tarifvertrag" if not named)
+    pub tariff: Option<String>,
+
+    /// Pay grade within the agreement (e.g. "E13")
+    pub pay_grade: Option<String>,
+}
+
+/// Named collective agreements, lowercase spelling and display name
+const TARIFFS: &[(&str, &str)] = &[
+    ("tvöd", "TVöD"),
+    ("tv-l", "TV-L"),
+    ("tv-h", "TV-H"),
+    ("tv-v", "TV-V"),
+    ("tv-n", "TV-N"),
+    ("tv-ärzte", "TV-Ärzte"),
+    ("tv-bund", "TV-Bund"),
+    ("avr", "AVR"),
+    ("bat", "BAT"),
+    ("era", "ERA"),
+    ("ig metall", "IG Metall"),
+    ("besoldung", "Besoldung"),
+];
+
+/// Words introducing a pay grade number ("E 13", "Entgeltgruppe 9", "A13")
+const GRADE_PREFIXES: &[&str] = &[
+    "e",
+    "eg",
+    "entgeltgruppe",
+    "a",
+    "besoldungsgruppe",
+    "s",
+    "p",
+    "kr",
+];
+
+/// Units after a number that make it a quantity rather than an amount
+const QUANTITY_UNITS: &[&str] = &[
+    "std",
+    "stunde",
+    "stunden",
+    "wochenstunden",
+    "h",
+    "tag",
+    "tage",
+    "urlaubstage",
+    "woche",
+    "wochen",
+    "monat",
+    "monate",
+    "jahr",
+    "jahre",
+    "gehalt",
+    "gehälter",
+    "monatsgehalt",
+    "monatsgehälter",
+    "%",
+    "prozent",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(String),
+    Word(String),
+    Symbol(char),
+}
+
+/// Token with whether it directly follows the previous one (no whitespace)
+#[derive(Debug, Clone)]
+struct Item {
+    token: Token,
+    adjacent: bool,
+    used: bool,
+}
+
+fn tokenize(text: &str) -> Vec<Item> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut items = Vec::new();
+    let mut adjacent = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            adjacent = false;
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let token = if c.is_ascii_digit() {
+            // Digits with inner separators, e.g. "45.000,50"
+            while i < chars.len()
+                && (chars[i].is_ascii_digit()
+                    || (matches!(chars[i], '.' | ',')
+                        && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit())))
+            {
+                i += 1;
+            }
+            Token::Number(chars[start..i].iter().collect())
+        } else if c.is_alphabetic() {
+            while i < chars.len() && chars[i].is_alphabetic() {
+                i += 1;
+            }
+            Token::Word(chars[start..i].iter().collect::<String>().to_lowercase())
+        } else {
+            i += 1;
+            Token::Symbol(c)
+        };
+        items.push(Item {
+            token,
+            adjacent,
+            used: false,
+        });
+        adjacent = true;
+    }
+    items
+}
+
+/// Parse a number written with German or English separators
+fn parse_number(number: &str) -> Option<f64> {
+    let dots = number.matches('.').count();
+    let commas = number.matches(',').count();
+    let groups_of_three = |separator: char| {
+        number
+            .split(separator)
+            .skip(1)
+            .all(|group| group.len() == 3)
+    };
+
+    let normalized = match (dots, commas) {
+        (0, 0) => number.to_string(),
+        // "45.000,50" or "45,000.50": the last separator is the decimal point
+        (_, _) if dots > 0 && commas > 0 => {
+            if number.rfind(',') > number.rfind('.') {
+                number.replace('.', "").replace(',', ".")
+            } else {
+                number.replace(',', "")
+            }
+        }
+        (_, 0) if groups_of_three('.') => number.replace('.', ""),
+        (1, 0) => number.to_string(),
+        (0, _) if commas > 1 && groups_of_three(',') => number.replace(',', ""),
+        (0, 1) if groups_of_three(',') && number.len() > 5 => number.replace(',', ""),
+        (0, 1) => number.replace(',', "."),
+        // Dates such as "01.01.2025"
+        _ => return None,
+    };
+    normalized.parse().ok()
+}
+
+fn word(item: Option<&Item>) -> Option<&str> {
+    match item.map(|item| &item.token) {
+        Some(Token::Word(word)) => Some(word),
+        _ => None,
+    }
+}
+
+fn symbol(item: Option<&Item>) -> Option<char> {
+    match item.map(|item| &item.token) {
+        Some(Token::Symbol(c)) => Some(*c),
+        _ => None,
+    }
+}
+
+/// Find a named collective agreement as a whole word
+fn find_tariff(lower: &str) -> Option<&'static str> {
+    TARIFFS.iter().find_map(|(pattern, name)| {
+        lower.match_indices(pattern).find_map(|(idx, _)| {
+            let before = lower[..idx].chars().next_back();
+            let after = lower[idx + pattern.len()..].chars().next();
+            let boundary = |c: Option<char>| !c.is_some_and(|c| c.is_alphanumeric());
+            (boundary(before) && boundary(after)).then_some(*name)
+        })
+    })
+}
+
+/// Mark a pay grade such as "E 13" or "EG9a" as used and return it
+fn take_pay_grade(items: &mut [Item]) -> Option<String> {
+    for idx in 0..items.len() {
+        let Some(prefix) = word(items.get(idx)).filter(|word| GRADE_PREFIXES.contains(word)) else {
+            continue;
+        };
+        let Some(Token::Number(number)) = items.get(idx + 1).map(|item| &item.token) else {
+            continue;
+        };
+        if number.contains(['.', ',']) || number.len() > 2 {
+            continue;
+        }
+
+        let prefix = match prefix {
+            "entgeltgruppe" | "eg" => "E".to_string(),
+            "besoldungsgruppe" => "A".to_string(),
+            other => other.to_uppercase(),
+        };
+        let mut grade = format!("{}{}", prefix, number);
+        items[idx].used = true;
+        items[idx + 1].used = true;
+
+        // Suffix as in "S 8a"
+        if let Some(suffix) = word(items.get(idx + 2)).filter(|word| word.len() == 1) {
+            if items[idx + 2].adjacent {
+                grade.push_str(suffix);
+                items[idx + 2].used = true;
+            }
+        }
+        return Some(grade);
+    }
+    None
+}
+
+/// Mark numbers followed by a unit such as "40 Std./Woche" as used
+fn skip_quantities(items: &mut [Item]) {
+    for idx in 0..items.len() {
+        if !matches!(items[idx].token, Token::Number(_)) || items[idx].used {
+            continue;
+        }
+        let mut next = idx + 1;
+        // "13. Monatsgehalt"
+        if symbol(items.get(next)) == Some('.') {
+            next += 1;
+        }
+        let Some(unit) = word(items.get(next))
+            .or_else(|| symbol(items.get(next)).filter(|c| *c == '%').map(|_| "%"))
+        else {
+            continue;
+        };
+        if !QUANTITY_UNITS.contains(&unit) {
+            continue;
+        }
+
+        for item in &mut items[idx..=next] {
+            item.used = true;
+        }
+        // "Std./Woche"
+        let mut rest = next + 1;
+        while matches!(symbol(items.get(rest)), Some('.' | '/')) {
+            rest += 1;
+        }
+        if rest > next + 1 && word(items.get(rest)).is_some() {
+            for item in &mut items[next + 1..=rest] {
+                item.used = true;
+            }
+        }
+    }
+}
+
+fn currency_of(items: &[Item]) -> Option<String> {
+    items.iter().filter(|item| !item.used).find_map(|item| {
+        let code = match &item.token {
+            Token::Symbol('€') => "EUR",
+            Token::Symbol('$') => "USD",
+            Token::Symbol('£') => "GBP",
+            Token::Word(word) if word == "eur" || word.starts_with("euro") => "EUR",
+            Token::Word(word) if word == "chf" || word == "franken" => "CHF",
+            Token::Word(word) if word == "usd" || word == "dollar" => "USD",
+            Token::Word(word) if word == "gbp" => "GBP",
+            _ => return None,
+        };
+        Some(code.to_string())
+    })
+}
+
+fn period_of(lower: &str, items: &[Item]) -> Option<SalaryPeriod> {
+    if lower.contains("p.a.") || lower.contains("p. a.") {
+        return Some(SalaryPeriod::Year);
+    }
+
+    for (idx, item) in items.iter().enumerate() {
+        let Token::Word(ref word) = item.token else {
+            continue;
+        };
+        if item.used {
+            continue;
+        }
+        let after_slash = symbol(idx.checked_sub(1).and_then(|prev| items.get(prev))) == Some('/');
+
+        let period = if word.starts_with("std")
+            || word.starts_with("stunde")
+            || word.starts_with("stündlich")
+            || word == "hour"
+            || word == "hourly"
+            || (word == "h" && after_slash)
+        {
+            SalaryPeriod::Hour
+        } else if word == "tag" || word.starts_with("tages") || word == "täglich" || word == "day"
+        {
+            SalaryPeriod::Day
+        } else if word.starts_with("woche") || word.starts_with("wöchentlich") || word == "week" {
+            SalaryPeriod::Week
+        } else if word.starts_with("monat")
+            || word == "mtl"
+            || word == "mon"
+            || word == "month"
+            || word == "monthly"
+        {
+            SalaryPeriod::Month
+        } else if word.starts_with("jahr")
+            || word.starts_with("jährlich")
+            || word == "pa"
+            || word == "year"
+            || word == "annual"
+            || word == "yearly"
+        {
+            SalaryPeriod::Year
+        } else {
+            continue;
+        };
+        return Some(period);
+    }
+    None
+}
+
+/// An amount found in the text
+struct Amount {
+    /// Position of the number token
+    idx: usize,
+    value: f64,
+    /// Followed by "k" or "Tsd."
+    thousands: bool,
+}
+
+/// Amounts in order of appearance, with "k"/"Tsd." expanded
+fn amounts_of(items: &[Item]) -> Vec<Amount> {
+    let mut amounts = Vec::new();
+    for (idx, item) in items.iter().enumerate() {
+        let Token::Number(ref number) = item.token else {
+            continue;
+        };
+        // Part of a word such as "E13" or "A4"
+        if item.used
+            || (item.adjacent
+                && word(idx.checked_sub(1).and_then(|prev| items.get(prev))).is_some())
+        {
+            continue;
+        }
+        let Some(mut value) = parse_number(number) else {
+            continue;
+        };
+        let thousands = matches!(
+            word(items.get(idx + 1)),
+            Some("k" | "t" | "tsd" | "tausend")
+        );
+        if thousands {
+            value *= 1000.0;
+        }
+        if value >= 1.0 {
+            amounts.push(Amount {
+                idx,
+                value,
+                thousands,
+            });
+        }
+    }
+    amounts
+}
+
+/// Parse a salary text; `None` if it contains neither an amount nor a tariff reference
+pub fn parse_salary(text: &str) -> Option<ParsedSalary> {
+    let lower = text.to_lowercase();
+    let mut items = tokenize(text);
+
+    let named_tariff = find_tariff(&lower);
+    let mentions_tariff = named_tariff.is_some()
+        || lower.contains("tarif")
+        || lower.contains("entgeltgruppe")
+        || lower.contains("besoldungsgruppe");
+    let pay_grade = if mentions_tariff {
+        take_pay_grade(&mut items)
+    } else {
+        None
+    };
+    let tariff = match named_tariff {
+        Some(name) => Some(name.to_string()),
+        None if mentions_tariff => Some("Tarifvertrag".to_string()),
+        None => None,
+    };
+
+    skip_quantities(&mut items);
+    let amounts = amounts_of(&items);
+
+    let (min, max) = match amounts.as_slice() {
+        [] => (None, None),
+        [amount] => {
+            let qualifier = items[amount.idx.saturating_sub(3)..amount.idx]
+                .iter()
+                .rev()
+                .find_map(|item| match &item.token {
+                    Token::Word(word) => Some(word.as_str()),
+                    _ => None,
+                });
+            match qualifier {
+                Some("ab" | "mindestens" | "min" | "from" | "über" | "ueber") => {
+                    (Some(amount.value), None)
+                }
+                Some("bis" | "max" | "maximal" | "höchstens" | "to") => (None, Some(amount.value)),
+                _ => (Some(amount.value), Some(amount.value)),
+            }
+        }
+        [first, second, ..] => {
+            // "50 bis 65 Tsd." applies the suffix to both bounds
+            let mut low = first.value;
+            if second.thousands && !first.thousands && low < 1000.0 {
+                low *= 1000.0;
+            }
+            (Some(low.min(second.value)), Some(low.max(second.value)))
+        }
+    };
+
+    if min.is_none() && max.is_none() && tariff.is_none() {
+        return None;
+    }
+
+    Some(ParsedSalary {
+        min,
+        max,
+        currency: currency_of(&items),
+        period: period_of(&lower, &items),
+        tariff,
+        pay_grade,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> ParsedSalary {
+        parse_salary(text).unwrap()
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("45.000"), Some(45000.0));
+        assert_eq!(parse_number("45.000,50"), Some(45000.5));
+        assert_eq!(parse_number("45,000.50"), Some(45000.5));
+        assert_eq!(parse_number("1.250.000"), Some(1250000.0));
+        assert_eq!(parse_number("17,50"), Some(17.5));
+        assert_eq!(parse_number("17.5"), Some(17.5));
+        assert_eq!(parse_number("45,000"), Some(45000.0));
+        assert_eq!(parse_number("01.01.2025"), None);
+    }
+
+    #[test]
+    fn test_annual_range() {
+        let salary = parse("45.000 - 55.000 EUR p.a.");
+        assert_eq!(salary.min, Some(45000.0));
+        assert_eq!(salary.max, Some(55000.0));
+        assert_eq!(salary.currency.as_deref(), Some("EUR"));
+        assert_eq!(salary.period, Some(SalaryPeriod::Year));
+        assert_eq!(salary.tariff, None);
+    }
+
+    #[test]
+    fn test_hourly_minimum() {
+        let salary = parse("ab 17 €/Std.");
+        assert_eq!(salary.min, Some(17.0));
+        assert_eq!(salary.max, None);
+        assert_eq!(salary.currency.as_deref(), Some("EUR"));
+        assert_eq!(salary.period, Some(SalaryPeriod::Hour));
+
+        let salary = parse("14,50 Euro pro Stunde");
+        assert_eq!(salary.min, Some(14.5));
+        assert_eq!(salary.max, Some(14.5));
+        assert_eq!(salary.period, Some(SalaryPeriod::Hour));
+    }
+
+    #[test]
+    fn test_monthly_with_working_hours() {
+        let salary = parse("3.200 € brutto monatlich bei 40 Std./Woche, 13. Monatsgehalt");
+        assert_eq!(salary.min, Some(3200.0));
+        assert_eq!(salary.max, Some(3200.0));
+        assert_eq!(salary.period, Some(SalaryPeriod::Month));
+    }
+
+    #[test]
+    fn test_upper_bound_and_thousands_suffix() {
+        let salary = parse("bis 60k EUR jährlich");
+        assert_eq!(salary.min, None);
+        assert_eq!(salary.max, Some(60000.0));
+        assert_eq!(salary.period, Some(SalaryPeriod::Year));
+
+        let salary = parse("zwischen 50 und 65 Tsd. € im Jahr");
+        assert_eq!(salary.min, Some(50000.0));
+        assert_eq!(salary.max, Some(65000.0));
+    }
+
+    #[test]
+    fn test_tariff_references() {
+        let salary = parse("Vergütung nach TVöD E 13");
+        assert_eq!(salary.tariff.as_deref(), Some("TVöD"));
+        assert_eq!(salary.pay_grade.as_deref(), Some("E13"));
+        assert_eq!(salary.min, None);
+
+        let salary = parse("Entgeltgruppe 9a TV-L");
+        assert_eq!(salary.tariff.as_deref(), Some("TV-L"));
+        assert_eq!(salary.pay_grade.as_deref(), Some("E9a"));
+
+        let salary = parse("Bezahlung nach Tarifvertrag, ca. 3.100 EUR brutto/Monat");
+        assert_eq!(salary.tariff.as_deref(), Some("Tarifvertrag"));
+        assert_eq!(salary.min, Some(3100.0));
+        assert_eq!(salary.period, Some(SalaryPeriod::Month));
+
+        // "era" and "bat" only count as whole words
+        assert_eq!(parse_salary("Generation Batterie"), None);
+    }
+
+    #[test]
+    fn test_unparsable() {
+        assert_eq!(parse_salary("nach Vereinbarung"), None);
+        assert_eq!(parse_salary("attraktive Vergütung"), None);
+        assert_eq!(parse_salary(""), None);
+    }
+}
@@ -0,0 +1,185 @@
+//! Saved searches
+//!
+//! A saved search stores a named set of [`SearchJobsParams`], so a user can run
+//! "my Berlin backend search" again without restating every parameter. Names
+//! are matched case-insensitively.
+
+use crate::{SearchJobsParams, SearchJobsResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A named search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SavedSearch {
+    /// Name as given by the user
+    pub name: String,
+
+    /// Stored search parameters
+    pub search: SearchJobsParams,
+
+    /// When the search was first saved (RFC 3339)
+    pub created_at: String,
+
+    /// When the search was last changed (RFC 3339)
+    pub updated_at: String,
+}
+
+/// Parameters for create_saved_search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateSavedSearchParams {
+    /// Name of the search (e.g. "Berlin backend"); an existing search with the
+    /// same name is replaced
+    pub name: String,
+
+    /// Search parameters (same as search_jobs)
+    pub search: SearchJobsParams,
+}
+
+/// Result from create_saved_search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateSavedSearchResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// The stored search
+    pub saved_search: SavedSearch,
+
+    /// True if a search with the same name has been replaced
+    pub replaced: bool,
+}
+
+/// Parameters for list_saved_searches
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListSavedSearchesParams {}
+
+/// Result from list_saved_searches
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListSavedSearchesResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Number of saved searches
+    pub saved_searches_count: usize,
+
+    /// Saved searches, ordered by name
+    pub saved_searches: Vec<SavedSearch>,
+}
+
+/// Parameters for delete_saved_search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteSavedSearchParams {
+    /// Name of the search to delete
+    pub name: String,
+}
+
+/// Result from delete_saved_search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeleteSavedSearchResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Name that was requested
+    pub name: String,
+
+    /// False if no search with this name existed
+    pub deleted: bool,
+}
+
+/// Parameters for run_saved_search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RunSavedSearchParams {
+    /// Name of the search to run
+    pub name: String,
+
+    /// Page number, overrides the stored value
+    pub page: Option<u64>,
+
+    /// Skip already seen jobs, overrides the stored value
+    pub hide_seen: Option<bool>,
+}
+
+impl RunSavedSearchParams {
+    /// Apply the overrides to the stored search parameters
+    pub fn apply_to(&self, mut search: SearchJobsParams) -> SearchJobsParams {
+        if self.page.is_some() {
+            search.page = self.page;
+        }
+        if self.hide_seen.is_some() {
+            search.hide_seen = self.hide_seen;
+        }
+        search
+    }
+}
+
+/// Result from run_saved_search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RunSavedSearchResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Name of the search that was run
+    pub name: String,
+
+    /// Search parameters that were used
+    pub search: SearchJobsParams,
+
+    /// Search result
+    pub result: SearchJobsResult,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search_params() -> SearchJobsParams {
+        serde_json::from_value(serde_json::json!({
+            "job_title": "Backend Developer",
+            "location": "Berlin",
+            "page": 2
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_run_params_override_stored_values() {
+        let run = RunSavedSearchParams {
+            name: "berlin".to_string(),
+            page: Some(3),
+            hide_seen: Some(true),
+        };
+        let search = run.apply_to(search_params());
+        assert_eq!(search.page, Some(3));
+        assert_eq!(search.hide_seen, Some(true));
+        assert_eq!(search.location.as_deref(), Some("Berlin"));
+    }
+
+    #[test]
+    fn test_run_params_keep_stored_values() {
+        let run = RunSavedSearchParams {
+            name: "berlin".to_string(),
+            page: None,
+            hide_seen: None,
+        };
+        let search = run.apply_to(search_params());
+        assert_eq!(search.page, Some(2));
+        assert_eq!(search.hide_seen, None);
+    }
+
+    #[test]
+    fn test_create_params_reject_unknown_search_fields() {
+        let result: Result<CreateSavedSearchParams, _> =
+            serde_json::from_value(serde_json::json!({
+                "name": "berlin",
+                "search": {"city": "Berlin"}
+            }));
+        assert!(result.is_err());
+    }
+}
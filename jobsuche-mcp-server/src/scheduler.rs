@@ -0,0 +1,563 @@
+//! Background scheduler
+//!
+//! Watchlists and saved searches can be run on cron-like schedules, also while
+//! no MCP client is connected. Postings that a scheduled run finds for the
+//! first time are recorded in a bounded log, so the next session can ask what
+//! is new since yesterday via get_scheduled_updates.
+//!
+//! Schedules use the five fields of cron (minute, hour, day of month, month,
+//! day of week) with `*`, lists, ranges and steps, or one of `@hourly`,
+//! `@daily`, `@weekly` and `@monthly`. They are evaluated in UTC.
+
+use crate::watchlists::WatchlistJob;
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::str::FromStr;
+use time::format_description::well_known::Rfc3339;
+use time::{Date, Month, OffsetDateTime};
+use tracing::{info, warn};
+
+/// Maximum number of runs with results kept in the log
+pub const MAX_SCHEDULED_RUNS: usize = 500;
+
+/// Storage key of the log document in the [`SCHEDULED_RUNS`](crate::storage::SCHEDULED_RUNS) collection
+pub const RUNS_KEY: &str = "log";
+
+/// How far ahead the next run of a schedule is searched
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+/// A parsed cron expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Schedule {
+    /// The expression as configured
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// First matching minute strictly after `after` (UTC)
+    pub fn next_after(&self, after: OffsetDateTime) -> Option<OffsetDateTime> {
+        let after = after.to_offset(time::UtcOffset::UTC);
+        let limit = after + time::Duration::days(MAX_LOOKAHEAD_DAYS);
+        let mut next =
+            after.replace_second(0).ok()?.replace_nanosecond(0).ok()? + time::Duration::minutes(1);
+
+        while next <= limit {
+            if !has(self.months, u8::from(next.month())) {
+                let (year, month) = match next.month() {
+                    Month::December => (next.year() + 1, Month::January),
+                    month => (next.year(), month.next()),
+                };
+                next = Date::from_calendar_date(year, month, 1)
+                    .ok()?
+                    .midnight()
+                    .assume_utc();
+            } else if !self.day_matches(next.date()) {
+                next = next.date().next_day()?.midnight().assume_utc();
+            } else if !has(self.hours, next.hour()) {
+                next = next.replace_minute(0).ok()? + time::Duration::hours(1);
+            } else if !has(self.minutes, next.minute()) {
+                next += time::Duration::minutes(1);
+            } else {
+                return Some(next);
+            }
+        }
+        None
+    }
+
+    /// Day of month and day of week; like cron, either matches if both are restricted
+    fn day_matches(&self, date: Date) -> bool {
+        let day = has(self.days, date.day());
+        let weekday = has(self.weekdays, date.weekday().number_days_from_sunday());
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let expression = s.trim();
+        let fields: Vec<&str> = match expression {
+            "@hourly" => vec!["0", "*", "*", "*", "*"],
+            "@daily" => vec!["0", "0", "*", "*", "*"],
+            "@weekly" => vec!["0", "0", "*", "*", "0"],
+            "@monthly" => vec!["0", "0", "1", "*", "*"],
+            _ => expression.split_whitespace().collect(),
+        };
+        if fields.len() != 5 {
+            anyhow::bail!(
+                "Invalid schedule '{}': expected 5 fields (minute hour day month weekday)",
+                expression
+            );
+        }
+
+        let field = |idx: usize, name: &str, min: u8, max: u8| {
+            parse_field(fields[idx], min, max)
+                .with_context(|| format!("Invalid {} field in schedule '{}'", name, expression))
+        };
+        let weekdays = field(4, "weekday", 0, 7)?;
+
+        Ok(Self {
+            expression: expression.to_string(),
+            minutes: field(0, "minute", 0, 59)?,
+            hours: field(1, "hour", 0, 23)?,
+            days: field(2, "day", 1, 31)?,
+            months: field(3, "month", 1, 12)?,
+            // 7 is an alias for Sunday
+            weekdays: (weekdays | weekdays >> 7) & 0x7f,
+            any_day: fields[2] == "*",
+            any_weekday: fields[4] == "*",
+        })
+    }
+}
+
+fn has(set: u64, value: u8) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parse a cron field (`*`, `5`, `1-5`, `*/15`, `0-30/10`, `1,15`) into a bit set
+fn parse_field(field: &str, min: u8, max: u8) -> Result<u64> {
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u8>().context("Invalid step")?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            anyhow::bail!("Step must be greater than 0");
+        }
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (start.parse()?, end.parse()?),
+                None => {
+                    let value = range.parse()?;
+                    // "5/10" means every 10 starting at 5
+                    (value, if part.contains('/') { max } else { value })
+                }
+            },
+        };
+        if start < min || end > max || start > end {
+            anyhow::bail!("'{}' is outside {}-{}", part, min, max);
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+/// Run `job` whenever `schedule` is due, until the process exits
+pub fn spawn_schedule<F, Fut>(schedule: Schedule, label: &'static str, job: F)
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    info!("Scheduling {} at '{}' (UTC)", label, schedule.expression());
+
+    tokio::spawn(async move {
+        loop {
+            let now = OffsetDateTime::now_utc();
+            let Some(next) = schedule.next_after(now) else {
+                warn!(
+                    "Schedule '{}' never matches, {} are not run",
+                    schedule.expression(),
+                    label
+                );
+                return;
+            };
+            let wait = (next - now).try_into().unwrap_or_default();
+            tokio::time::sleep(wait).await;
+
+            info!("Running scheduled {}", label);
+            job().await;
+        }
+    });
+}
+
+/// What a scheduled run checked
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledSource {
+    /// Watchlists
+    Watchlist,
+    /// Saved searches
+    SavedSearch,
+}
+
+/// Outcome of one watchlist or saved search in a scheduled run
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScheduledResult {
+    /// Name of the watchlist or saved search
+    pub name: String,
+
+    /// Postings found for the first time
+    pub new_jobs: Vec<WatchlistJob>,
+
+    /// Error message if the search failed
+    pub error: Option<String>,
+}
+
+impl ScheduledResult {
+    /// Result of a search, or `None` if it succeeded without new postings
+    pub fn from_outcome(name: String, outcome: Result<Vec<WatchlistJob>>) -> Option<Self> {
+        match outcome {
+            Ok(new_jobs) if new_jobs.is_empty() => None,
+            Ok(new_jobs) => Some(Self {
+                name,
+                new_jobs,
+                error: None,
+            }),
+            Err(e) => Some(Self {
+                name,
+                new_jobs: Vec::new(),
+                error: Some(format!("Search failed: {:#}", e)),
+            }),
+        }
+    }
+}
+
+/// A scheduled run that found new postings or failed
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScheduledRun {
+    /// When the run started (RFC 3339)
+    pub run_at: String,
+
+    /// What was checked
+    pub source: ScheduledSource,
+
+    /// Watchlists or saved searches with new postings or errors
+    pub results: Vec<ScheduledResult>,
+}
+
+/// Persisted log of scheduled runs, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduledRunLog {
+    /// When each source was last run (RFC 3339)
+    pub last_run_at: BTreeMap<String, String>,
+
+    /// Runs with results
+    pub runs: Vec<ScheduledRun>,
+}
+
+impl ScheduledRunLog {
+    /// Record a run; runs without results only update the last run time
+    pub fn record(&mut self, run: ScheduledRun, max_runs: usize) {
+        self.last_run_at
+            .insert(source_name(run.source), run.run_at.clone());
+        if run.results.is_empty() {
+            return;
+        }
+
+        self.runs.push(run);
+        if self.runs.len() > max_runs {
+            let excess = self.runs.len() - max_runs;
+            self.runs.drain(..excess);
+        }
+    }
+
+    /// Merge the results of runs at or after `since`, optionally for one name
+    pub fn updates_since(&self, since: OffsetDateTime, name: Option<&str>) -> Vec<ScheduledUpdate> {
+        let mut updates: BTreeMap<(ScheduledSource, String), ScheduledUpdate> = BTreeMap::new();
+
+        let runs = self.runs.iter().filter(|run| {
+            OffsetDateTime::parse(&run.run_at, &Rfc3339).is_ok_and(|run_at| run_at >= since)
+        });
+        for run in runs {
+            for result in &run.results {
+                if name.is_some_and(|name| !result.name.eq_ignore_ascii_case(name.trim())) {
+                    continue;
+                }
+
+                let update = updates
+                    .entry((run.source, result.name.to_lowercase()))
+                    .or_insert_with(|| ScheduledUpdate {
+                        source: run.source,
+                        name: result.name.clone(),
+                        new_jobs_count: 0,
+                        new_jobs: Vec::new(),
+                        last_error: None,
+                    });
+                for job in &result.new_jobs {
+                    let known = update
+                        .new_jobs
+                        .iter()
+                        .any(|known| known.job.reference_number == job.job.reference_number);
+                    if !known {
+                        update.new_jobs.push(job.clone());
+                    }
+                }
+                update.new_jobs_count = update.new_jobs.len();
+                update.last_error = result.error.clone();
+            }
+        }
+        updates.into_values().collect()
+    }
+}
+
+/// Name of a source as used in [`ScheduledRunLog::last_run_at`]
+fn source_name(source: ScheduledSource) -> String {
+    match source {
+        ScheduledSource::Watchlist => "watchlist",
+        ScheduledSource::SavedSearch => "saved_search",
+    }
+    .to_string()
+}
+
+/// Parse `since` (RFC 3339 or `YYYY-MM-DD`), defaulting to 24 hours ago
+pub fn parse_since(since: Option<&str>) -> Result<OffsetDateTime> {
+    let Some(since) = since.map(str::trim).filter(|since| !since.is_empty()) else {
+        return Ok(OffsetDateTime::now_utc() - time::Duration::days(1));
+    };
+    if let Ok(time) = OffsetDateTime::parse(since, &Rfc3339) {
+        return Ok(time);
+    }
+    let date = Date::parse(
+        since,
+        time::macros::format_description!("[year]-[month]-[day]"),
+    )
+    .with_context(|| {
+        format!(
+            "Invalid 'since' value '{}' (expected RFC 3339 or YYYY-MM-DD)",
+            since
+        )
+    })?;
+    Ok(date.midnight().assume_utc())
+}
+
+/// A configured schedule
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScheduleInfo {
+    /// What the schedule runs
+    pub source: ScheduledSource,
+
+    /// Cron expression (UTC)
+    pub expression: String,
+
+    /// When the schedule last ran (RFC 3339)
+    pub last_run_at: Option<String>,
+
+    /// When the schedule runs next (RFC 3339)
+    pub next_run_at: Option<String>,
+}
+
+impl ScheduleInfo {
+    /// Describe a schedule using the run log
+    pub fn new(source: ScheduledSource, schedule: &Schedule, log: &ScheduledRunLog) -> Self {
+        Self {
+            source,
+            expression: schedule.expression().to_string(),
+            last_run_at: log.last_run_at.get(&source_name(source)).cloned(),
+            next_run_at: schedule
+                .next_after(OffsetDateTime::now_utc())
+                .and_then(|next| next.format(&Rfc3339).ok()),
+        }
+    }
+}
+
+/// New postings of one watchlist or saved search, merged across runs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScheduledUpdate {
+    /// Whether this is a watchlist or a saved search
+    pub source: ScheduledSource,
+
+    /// Name of the watchlist or saved search
+    pub name: String,
+
+    /// Number of new postings
+    pub new_jobs_count: usize,
+
+    /// New postings, in the order they were found
+    pub new_jobs: Vec<WatchlistJob>,
+
+    /// Error of the most recent run that reported on this search, if it failed
+    pub last_error: Option<String>,
+}
+
+/// Parameters for get_scheduled_updates
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetScheduledUpdatesParams {
+    /// Only report runs since this time, RFC 3339 or YYYY-MM-DD (default: 24 hours ago)
+    pub since: Option<String>,
+
+    /// Only report this watchlist or saved search
+    pub name: Option<String>,
+}
+
+/// Result from get_scheduled_updates
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetScheduledUpdatesResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Start of the reported period (RFC 3339)
+    pub since: String,
+
+    /// Configured schedules (empty if the scheduler is disabled)
+    pub schedules: Vec<ScheduleInfo>,
+
+    /// Total number of new postings
+    pub total_new_jobs: usize,
+
+    /// New postings per watchlist and saved search
+    pub updates: Vec<ScheduledUpdate>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::JobSummary;
+    use time::macros::datetime;
+
+    fn schedule(expression: &str) -> Schedule {
+        expression.parse().unwrap()
+    }
+
+    fn next(expression: &str, after: OffsetDateTime) -> OffsetDateTime {
+        schedule(expression).next_after(after).unwrap()
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        assert!("0 7 * * 1-5".parse::<Schedule>().is_ok());
+        assert!("*/15 * * * *".parse::<Schedule>().is_ok());
+        assert!("@daily".parse::<Schedule>().is_ok());
+        assert!("0 7 * *".parse::<Schedule>().is_err());
+        assert!("60 7 * * *".parse::<Schedule>().is_err());
+        assert!("0 7 * * mon".parse::<Schedule>().is_err());
+        assert!("*/0 * * * *".parse::<Schedule>().is_err());
+        assert!("0 5-3 * * *".parse::<Schedule>().is_err());
+    }
+
+    #[test]
+    fn test_next_after() {
+        // Monday
+        let now = datetime!(2025-10-20 10:17:30 UTC);
+        assert_eq!(next("*/15 * * * *", now), datetime!(2025-10-20 10:30 UTC));
+        assert_eq!(next("@hourly", now), datetime!(2025-10-20 11:00 UTC));
+        assert_eq!(next("0 7 * * *", now), datetime!(2025-10-21 07:00 UTC));
+        assert_eq!(next("0 7 * * 0", now), datetime!(2025-10-26 07:00 UTC));
+        assert_eq!(next("0 7 * * 7", now), datetime!(2025-10-26 07:00 UTC));
+        assert_eq!(next("30 8 1 * *", now), datetime!(2025-11-01 08:30 UTC));
+        assert_eq!(next("0 0 1 1 *", now), datetime!(2026-01-01 00:00 UTC));
+        assert_eq!(next("0 6,18 * * 1-5", now), datetime!(2025-10-20 18:00 UTC));
+        // Day of month or day of week when both are restricted
+        assert_eq!(next("0 0 31 * 3", now), datetime!(2025-10-22 00:00 UTC));
+        assert!(schedule("0 0 31 2 *").next_after(now).is_none());
+    }
+
+    fn run(run_at: &str, name: &str, refnrs: &[&str]) -> ScheduledRun {
+        ScheduledRun {
+            run_at: run_at.to_string(),
+            source: ScheduledSource::Watchlist,
+            results: vec![ScheduledResult {
+                name: name.to_string(),
+                new_jobs: refnrs
+                    .iter()
+                    .map(|refnr| WatchlistJob {
+                        first_seen_at: run_at.to_string(),
+                        job: JobSummary {
+                            reference_number: refnr.to_string(),
+                            title: "Developer".to_string(),
+                            employer: "Example GmbH".to_string(),
+                            location: "Berlin".to_string(),
+                            distance_km: None,
+                            coordinates: None,
+                            country: None,
+                            published_date: None,
+                            published_date_raw: None,
+                            published_days_ago: None,
+                            published_ago: None,
+                            external_url: None,
+                            web_url: String::new(),
+                            duplicates: Vec::new(),
+                        },
+                    })
+                    .collect(),
+                error: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_record_keeps_runs_with_results() {
+        let mut log = ScheduledRunLog::default();
+        log.record(run("2025-10-20T07:00:00Z", "Rust", &["A"]), 2);
+        log.record(
+            ScheduledRun {
+                run_at: "2025-10-21T07:00:00Z".to_string(),
+                source: ScheduledSource::Watchlist,
+                results: Vec::new(),
+            },
+            2,
+        );
+        assert_eq!(log.runs.len(), 1);
+        assert_eq!(log.last_run_at["watchlist"], "2025-10-21T07:00:00Z");
+
+        log.record(run("2025-10-22T07:00:00Z", "Rust", &["B"]), 2);
+        log.record(run("2025-10-23T07:00:00Z", "Rust", &["C"]), 2);
+        assert_eq!(log.runs.len(), 2);
+        assert_eq!(log.runs[0].run_at, "2025-10-22T07:00:00Z");
+    }
+
+    #[test]
+    fn test_updates_since() {
+        let mut log = ScheduledRunLog::default();
+        log.record(run("2025-10-20T07:00:00Z", "Rust", &["A"]), 10);
+        log.record(run("2025-10-21T07:00:00Z", "Rust", &["B", "C"]), 10);
+        log.record(run("2025-10-22T07:00:00Z", "Rust", &["C", "D"]), 10);
+        log.record(run("2025-10-22T07:00:00Z", "Go", &["E"]), 10);
+
+        let updates = log.updates_since(datetime!(2025-10-21 00:00 UTC), None);
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].name, "Go");
+        let refnrs: Vec<&str> = updates[1]
+            .new_jobs
+            .iter()
+            .map(|job| job.job.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["B", "C", "D"]);
+        assert_eq!(updates[1].new_jobs_count, 3);
+
+        let updates = log.updates_since(datetime!(2025-10-01 00:00 UTC), Some("rust"));
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].new_jobs_count, 4);
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(
+            parse_since(Some("2025-10-20")).unwrap(),
+            datetime!(2025-10-20 00:00 UTC)
+        );
+        assert_eq!(
+            parse_since(Some("2025-10-20T08:00:00+02:00")).unwrap(),
+            datetime!(2025-10-20 06:00 UTC)
+        );
+        assert!(parse_since(Some("yesterday")).is_err());
+        assert!(parse_since(None).unwrap() < OffsetDateTime::now_utc());
+    }
+}
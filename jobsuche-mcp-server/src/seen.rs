@@ -0,0 +1,91 @@
+//! Seen-jobs tracking
+//!
+//! Every reference number returned to the client is remembered in the
+//! [`SEEN_JOBS`](crate::storage::SEEN_JOBS) collection. Searches with
+//! `hide_seen` skip these jobs, so repeated daily searches only show what is new.
+
+use crate::JobSummary;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Maximum number of additional API pages fetched to refill a page after hiding seen jobs
+pub const MAX_BACKFILL_PAGES: u64 = 5;
+
+/// Record of a job that has been returned to the client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeenJob {
+    /// When the job was first returned (RFC 3339)
+    pub first_seen: String,
+
+    /// External application URL from the search result; job details lack it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_url: Option<String>,
+}
+
+/// Remove seen jobs from `jobs`, returning how many were removed
+pub fn retain_unseen(jobs: &mut Vec<JobSummary>, seen: &HashSet<String>) -> usize {
+    let before = jobs.len();
+    jobs.retain(|job| !seen.contains(&job.reference_number));
+    before - jobs.len()
+}
+
+/// Append jobs that are not in `jobs` yet, up to `limit` entries in total
+pub fn append_unique(jobs: &mut Vec<JobSummary>, more: Vec<JobSummary>, limit: usize) {
+    for job in more {
+        if jobs.len() >= limit {
+            break;
+        }
+        if !jobs
+            .iter()
+            .any(|existing| existing.reference_number == job.reference_number)
+        {
+            jobs.push(job);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(reference_number: &str) -> JobSummary {
+        JobSummary {
+            reference_number: reference_number.to_string(),
+            title: "Job".to_string(),
+            employer: "Employer".to_string(),
+            location: "Berlin".to_string(),
+            distance_km: None,
+            coordinates: None,
+            country: None,
+            published_date: None,
+            published_date_raw: None,
+            published_days_ago: None,
+            published_ago: None,
+            external_url: None,
+            web_url: String::new(),
+            duplicates: Vec::new(),
+        }
+    }
+
+    fn refnrs(jobs: &[JobSummary]) -> Vec<&str> {
+        jobs.iter()
+            .map(|job| job.reference_number.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_retain_unseen() {
+        let mut jobs = vec![job("A"), job("B"), job("C")];
+        let seen = HashSet::from(["B".to_string(), "X".to_string()]);
+
+        assert_eq!(retain_unseen(&mut jobs, &seen), 1);
+        assert_eq!(refnrs(&jobs), vec!["A", "C"]);
+    }
+
+    #[test]
+    fn test_append_unique_respects_limit_and_duplicates() {
+        let mut jobs = vec![job("A")];
+        append_unique(&mut jobs, vec![job("A"), job("B"), job("C"), job("D")], 3);
+        assert_eq!(refnrs(&jobs), vec!["A", "B", "C"]);
+    }
+}
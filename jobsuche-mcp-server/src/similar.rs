@@ -0,0 +1,290 @@
+//! Similar job recommendations
+//!
+//! Finds alternatives to a posting by running derived searches (same title
+//! nearby, same employer, same title anywhere) and scoring the candidates.
+//! The score is a transparent sum of title similarity, proximity and employer
+//! match, with the reasons listed per job.
+
+use crate::employer::matches_employer;
+use crate::JobSummary;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Default number of similar jobs returned
+pub const DEFAULT_SIMILAR_JOBS: u64 = 10;
+
+/// Maximum number of similar jobs returned
+pub const MAX_SIMILAR_JOBS: u64 = 50;
+
+/// Default radius in km for the nearby search
+pub const DEFAULT_SIMILAR_RADIUS_KM: u64 = 25;
+
+/// Weight of the title similarity in the score
+const TITLE_WEIGHT: f64 = 0.6;
+
+/// Weight of being in the same place
+const LOCATION_WEIGHT: f64 = 0.25;
+
+/// Weight of the same employer
+const EMPLOYER_WEIGHT: f64 = 0.15;
+
+/// Distance in km at which the location part of the score reaches zero
+const MAX_DISTANCE_KM: f64 = 100.0;
+
+/// Words in job titles that say nothing about the job
+const STOPWORDS: &[&str] = &[
+    "m", "w", "d", "f", "x", "i", "mwd", "div", "divers", "in", "innen", "und", "oder", "für",
+    "fuer", "mit", "der", "die", "das", "im", "am", "bei", "zum", "zur", "als", "von", "and", "or",
+    "for", "with", "the", "of", "senior", "junior", "vollzeit", "teilzeit", "ab", "sofort",
+];
+
+/// Parameters for find_similar_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FindSimilarJobsParams {
+    /// Job reference number to find alternatives for
+    pub reference_number: String,
+
+    /// Radius in km for the nearby search (default: 25)
+    pub radius_km: Option<u64>,
+
+    /// Maximum number of similar jobs (default: 10, max: 50)
+    pub max_results: Option<u64>,
+}
+
+/// A recommended job with its score
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SimilarJob {
+    /// Score from 0.0 to 1.0: title similarity (up to 0.6), same place or
+    /// proximity (up to 0.25) and same employer (0.15)
+    pub score: f64,
+
+    /// Why the job is similar (e.g. "same employer", "shared title words: rust, developer")
+    pub reasons: Vec<String>,
+
+    /// The job
+    pub job: JobSummary,
+}
+
+/// Result from find_similar_jobs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FindSimilarJobsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Job the recommendations are based on
+    pub reference_number: String,
+
+    /// Title of that job
+    pub title: Option<String>,
+
+    /// Employer of that job
+    pub employer: Option<String>,
+
+    /// City of that job
+    pub location: Option<String>,
+
+    /// Title words used for the searches and the similarity
+    pub keywords: Vec<String>,
+
+    /// Number of recommendations
+    pub jobs_count: usize,
+
+    /// Recommendations, best first
+    pub jobs: Vec<SimilarJob>,
+}
+
+/// The job alternatives are sought for
+#[derive(Debug, Clone)]
+pub struct SimilarityBase {
+    /// Reference number, excluded from the results
+    pub reference_number: String,
+
+    /// Significant title words
+    pub keywords: Vec<String>,
+
+    /// City
+    pub city: Option<String>,
+
+    /// Employer
+    pub employer: Option<String>,
+}
+
+/// Significant lowercase words of a job title, in order and without duplicates
+pub fn title_keywords(title: &str) -> Vec<String> {
+    let mut keywords: Vec<String> = Vec::new();
+    let words = title
+        .to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || c == '+' || c == '#'))
+        .filter(|word| word.chars().count() > 1 || *word == "c" || *word == "r")
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    for word in words {
+        if !STOPWORDS.contains(&word.as_str()) && !keywords.contains(&word) {
+            keywords.push(word);
+        }
+    }
+    keywords
+}
+
+/// City of a location like "Berlin (10115)"
+pub fn city_of(location: &str) -> &str {
+    location
+        .split_once(" (")
+        .map_or(location, |(city, _)| city)
+        .trim()
+}
+
+impl SimilarityBase {
+    /// Score a candidate, or `None` for the base job itself
+    pub fn score(&self, job: JobSummary) -> Option<SimilarJob> {
+        if job.reference_number == self.reference_number {
+            return None;
+        }
+        let mut score = 0.0;
+        let mut reasons = Vec::new();
+
+        let candidate_keywords = title_keywords(&job.title);
+        let shared: Vec<&str> = self
+            .keywords
+            .iter()
+            .filter(|keyword| candidate_keywords.contains(keyword))
+            .map(String::as_str)
+            .collect();
+        let union = self.keywords.len() + candidate_keywords.len() - shared.len();
+        if !shared.is_empty() && union > 0 {
+            score += TITLE_WEIGHT * shared.len() as f64 / union as f64;
+            reasons.push(format!("shared title words: {}", shared.join(", ")));
+        }
+
+        let same_city = self.city.as_deref().is_some_and(|city| {
+            !city.is_empty() && city_of(&job.location).eq_ignore_ascii_case(city)
+        });
+        if same_city {
+            score += LOCATION_WEIGHT;
+            reasons.push("same location".to_string());
+        } else if let Some(distance) = job.distance_km {
+            let proximity = (1.0 - distance / MAX_DISTANCE_KM).max(0.0);
+            if proximity > 0.0 {
+                score += LOCATION_WEIGHT * proximity;
+                reasons.push(format!("{:.0} km away", distance));
+            }
+        }
+
+        if let Some(ref employer) = self.employer {
+            if !employer.trim().is_empty() && matches_employer(&job, employer) {
+                score += EMPLOYER_WEIGHT;
+                reasons.push("same employer".to_string());
+            }
+        }
+
+        Some(SimilarJob {
+            score: (score * 1000.0).round() / 1000.0,
+            reasons,
+            job,
+        })
+    }
+
+    /// Score candidates from several searches: duplicates and the base job are
+    /// dropped, the best `limit` are returned, best first
+    pub fn rank(&self, candidates: Vec<JobSummary>, limit: usize) -> Vec<SimilarJob> {
+        let mut ranked: Vec<SimilarJob> = Vec::new();
+        for candidate in candidates {
+            let duplicate = ranked
+                .iter()
+                .any(|similar| similar.job.reference_number == candidate.reference_number);
+            if duplicate {
+                continue;
+            }
+            ranked.extend(self.score(candidate));
+        }
+        ranked.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.job.reference_number.cmp(&b.job.reference_number))
+        });
+        ranked.truncate(limit);
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(reference_number: &str, title: &str, employer: &str, location: &str) -> JobSummary {
+        serde_json::from_value(serde_json::json!({
+            "reference_number": reference_number,
+            "title": title,
+            "employer": employer,
+            "location": location,
+            "published_date": null,
+            "external_url": null
+        }))
+        .unwrap()
+    }
+
+    fn base() -> SimilarityBase {
+        SimilarityBase {
+            reference_number: "BASE".to_string(),
+            keywords: title_keywords("Senior Rust Developer (m/w/d)"),
+            city: Some("Berlin".to_string()),
+            employer: Some("Example GmbH".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_title_keywords() {
+        assert_eq!(
+            title_keywords("Senior Rust Developer (m/w/d) für Backend und C++"),
+            vec!["rust", "developer", "backend", "c++"]
+        );
+        assert_eq!(
+            title_keywords("Kaufmann/-frau (w/m/d)"),
+            vec!["kaufmann", "frau"]
+        );
+    }
+
+    #[test]
+    fn test_score() {
+        let similar = base()
+            .score(job("A", "Rust Developer", "Example GmbH", "Berlin (10115)"))
+            .unwrap();
+        assert_eq!(similar.score, 1.0);
+        assert_eq!(
+            similar.reasons,
+            vec![
+                "shared title words: rust, developer",
+                "same location",
+                "same employer"
+            ]
+        );
+
+        let similar = base()
+            .score(job("B", "Python Developer", "Other AG", "Hamburg (20095)"))
+            .unwrap();
+        assert_eq!(similar.score, 0.2);
+
+        assert!(base()
+            .score(job("BASE", "Rust Developer", "Example GmbH", "Berlin"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_rank_deduplicates_and_sorts() {
+        let candidates = vec![
+            job("B", "Python Developer", "Other AG", "Hamburg"),
+            job("A", "Rust Developer", "Other AG", "Berlin"),
+            job("B", "Python Developer", "Other AG", "Hamburg"),
+            job("BASE", "Rust Developer", "Example GmbH", "Berlin"),
+            job("C", "Koch", "Other AG", "München"),
+        ];
+        let ranked = base().rank(candidates, 2);
+        let refnrs: Vec<&str> = ranked
+            .iter()
+            .map(|similar| similar.job.reference_number.as_str())
+            .collect();
+        assert_eq!(refnrs, vec!["A", "B"]);
+    }
+}
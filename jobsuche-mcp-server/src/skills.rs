@@ -0,0 +1,606 @@
+//! Skill and language requirement extraction
+//!
+//! A lightweight dictionary pass over the title and description that lists
+//! programming languages, frameworks, tools, certifications and driver's
+//! licenses mentioned in a posting, plus the spoken languages it asks for with
+//! their level. The lists make postings comparable and filterable without
+//! reading every description; they only contain what is literally mentioned.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Kind of an extracted skill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillCategory {
+    ProgrammingLanguage,
+    Framework,
+    Tool,
+    Certification,
+    DriversLicense,
+}
+
+/// A skill mentioned in a posting
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ExtractedSkill {
+    /// Canonical name (e.g. "TypeScript", "Führerschein CE")
+    pub name: String,
+
+    /// Kind of skill
+    pub category: SkillCategory,
+}
+
+/// A spoken language asked for in a posting
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct LanguageRequirement {
+    /// Language in English (e.g. "German")
+    pub language: String,
+
+    /// CEFR level if stated (e.g. "B2")
+    pub level: Option<String>,
+
+    /// Proficiency if described in words ("native", "business fluent", "fluent",
+    /// "very good", "good", "basic")
+    pub proficiency: Option<String>,
+
+    /// False if the language is only a plus ("von Vorteil", "nice to have")
+    pub required: bool,
+}
+
+/// Canonical name, category and lowercase spellings
+const SKILLS: &[(&str, SkillCategory, &[&str])] = {
+    use SkillCategory::*;
+    &[
+        ("Rust", ProgrammingLanguage, &["rust"]),
+        ("Python", ProgrammingLanguage, &["python"]),
+        ("Java", ProgrammingLanguage, &["java"]),
+        (
+            "JavaScript",
+            ProgrammingLanguage,
+            &["javascript", "ecmascript"],
+        ),
+        ("TypeScript", ProgrammingLanguage, &["typescript"]),
+        ("C#", ProgrammingLanguage, &["c#", "csharp"]),
+        ("C++", ProgrammingLanguage, &["c++", "cpp"]),
+        ("Go", ProgrammingLanguage, &["golang"]),
+        ("Kotlin", ProgrammingLanguage, &["kotlin"]),
+        ("Swift", ProgrammingLanguage, &["swift"]),
+        ("PHP", ProgrammingLanguage, &["php"]),
+        ("Ruby", ProgrammingLanguage, &["ruby"]),
+        ("Scala", ProgrammingLanguage, &["scala"]),
+        ("SQL", ProgrammingLanguage, &["sql", "t-sql", "pl/sql"]),
+        ("ABAP", ProgrammingLanguage, &["abap"]),
+        ("COBOL", ProgrammingLanguage, &["cobol"]),
+        ("MATLAB", ProgrammingLanguage, &["matlab"]),
+        ("Bash", ProgrammingLanguage, &["bash", "shell-scripting"]),
+        ("PowerShell", ProgrammingLanguage, &["powershell"]),
+        ("Perl", ProgrammingLanguage, &["perl"]),
+        ("Dart", ProgrammingLanguage, &["dart"]),
+        ("Haskell", ProgrammingLanguage, &["haskell"]),
+        ("Elixir", ProgrammingLanguage, &["elixir"]),
+        ("VBA", ProgrammingLanguage, &["vba"]),
+        ("Delphi", ProgrammingLanguage, &["delphi"]),
+        ("HTML", ProgrammingLanguage, &["html", "html5"]),
+        ("CSS", ProgrammingLanguage, &["css", "css3"]),
+        ("React", Framework, &["react", "react.js", "reactjs"]),
+        ("React Native", Framework, &["react native"]),
+        ("Angular", Framework, &["angular", "angularjs"]),
+        ("Vue.js", Framework, &["vue", "vue.js", "vuejs"]),
+        ("Svelte", Framework, &["svelte"]),
+        ("Next.js", Framework, &["next.js", "nextjs"]),
+        ("Node.js", Framework, &["node.js", "nodejs"]),
+        ("Spring", Framework, &["spring", "spring boot"]),
+        ("Django", Framework, &["django"]),
+        ("Flask", Framework, &["flask"]),
+        ("FastAPI", Framework, &["fastapi"]),
+        ("Ruby on Rails", Framework, &["ruby on rails", "rails"]),
+        ("Laravel", Framework, &["laravel"]),
+        ("Symfony", Framework, &["symfony"]),
+        (
+            ".NET",
+            Framework,
+            &[".net", "asp.net", "dotnet", ".net core"],
+        ),
+        ("Flutter", Framework, &["flutter"]),
+        ("Qt", Framework, &["qt"]),
+        ("TensorFlow", Framework, &["tensorflow"]),
+        ("PyTorch", Framework, &["pytorch"]),
+        ("pandas", Framework, &["pandas"]),
+        ("Hibernate", Framework, &["hibernate"]),
+        ("jQuery", Framework, &["jquery"]),
+        ("Docker", Tool, &["docker"]),
+        ("Kubernetes", Tool, &["kubernetes", "k8s"]),
+        ("Terraform", Tool, &["terraform"]),
+        ("Ansible", Tool, &["ansible"]),
+        ("AWS", Tool, &["aws", "amazon web services"]),
+        ("Azure", Tool, &["azure"]),
+        ("Google Cloud", Tool, &["gcp", "google cloud"]),
+        ("Git", Tool, &["git"]),
+        ("GitLab", Tool, &["gitlab"]),
+        ("GitHub", Tool, &["github"]),
+        ("Jenkins", Tool, &["jenkins"]),
+        ("Linux", Tool, &["linux"]),
+        ("PostgreSQL", Tool, &["postgresql", "postgres"]),
+        ("MySQL", Tool, &["mysql", "mariadb"]),
+        ("Oracle", Tool, &["oracle"]),
+        ("MongoDB", Tool, &["mongodb"]),
+        ("Redis", Tool, &["redis"]),
+        ("Kafka", Tool, &["kafka"]),
+        ("Elasticsearch", Tool, &["elasticsearch"]),
+        ("SAP", Tool, &["sap", "sap s/4hana", "s/4hana"]),
+        ("Jira", Tool, &["jira"]),
+        ("Microsoft Excel", Tool, &["excel", "ms excel"]),
+        (
+            "Microsoft Office",
+            Tool,
+            &["ms office", "ms-office", "microsoft office", "office 365"],
+        ),
+        ("DATEV", Tool, &["datev"]),
+        ("AutoCAD", Tool, &["autocad"]),
+        ("SolidWorks", Tool, &["solidworks"]),
+        ("CATIA", Tool, &["catia"]),
+        ("Salesforce", Tool, &["salesforce"]),
+        ("Power BI", Tool, &["power bi", "powerbi"]),
+        ("Tableau", Tool, &["tableau"]),
+        ("SPS", Tool, &["sps", "simatic", "tia portal"]),
+        ("AWS Certification", Certification, &["aws certified"]),
+        ("CCNA", Certification, &["ccna"]),
+        ("CCNP", Certification, &["ccnp"]),
+        ("PMP", Certification, &["pmp"]),
+        ("PRINCE2", Certification, &["prince2"]),
+        ("ITIL", Certification, &["itil"]),
+        (
+            "Scrum Master",
+            Certification,
+            &["scrum master", "psm", "csm"],
+        ),
+        ("CISSP", Certification, &["cissp"]),
+        ("CISM", Certification, &["cism"]),
+        ("ISTQB", Certification, &["istqb"]),
+        ("TOGAF", Certification, &["togaf"]),
+        ("Six Sigma", Certification, &["six sigma"]),
+        ("IPMA", Certification, &["ipma"]),
+        (
+            "AdA-Schein",
+            Certification,
+            &["ada-schein", "ausbildereignung", "aevo"],
+        ),
+        (
+            "Staplerschein",
+            Certification,
+            &[
+                "staplerschein",
+                "gabelstaplerschein",
+                "flurförderzeugschein",
+            ],
+        ),
+        (
+            "Schweißschein",
+            Certification,
+            &["schweißschein", "schweisszertifikat", "schweißzertifikat"],
+        ),
+        ("SCC", Certification, &["scc", "scc-schein"]),
+        (
+            "Sachkunde §34a",
+            Certification,
+            &["34a", "sachkundeprüfung"],
+        ),
+        (
+            "Ersthelfer",
+            Certification,
+            &["ersthelfer", "ersthelferausbildung"],
+        ),
+    ]
+};
+
+/// Driver's license classes, longest first so "CE" wins over "C"
+const LICENSE_CLASSES: &[&str] = &[
+    "C1E", "D1E", "AM", "A1", "A2", "BE", "C1", "CE", "D1", "DE", "A", "B", "C", "D", "L", "T",
+];
+
+/// Words introducing a driver's license
+const LICENSE_WORDS: &[&str] = &[
+    "führerschein",
+    "fuhrerschein",
+    "fahrerlaubnis",
+    "führerscheinklasse",
+    "führerscheinklassen",
+    "driving licence",
+    "driving license",
+    "driver's license",
+    "drivers license",
+];
+
+/// English name and lowercase German/English spellings of spoken languages
+const LANGUAGES: &[(&str, &[&str])] = &[
+    ("German", &["deutsch", "german"]),
+    ("English", &["englisch", "english"]),
+    ("French", &["französisch", "franzoesisch", "french"]),
+    ("Spanish", &["spanisch", "spanish"]),
+    ("Italian", &["italienisch", "italian"]),
+    ("Portuguese", &["portugiesisch", "portuguese"]),
+    ("Dutch", &["niederländisch", "niederlaendisch", "dutch"]),
+    ("Polish", &["polnisch", "polish"]),
+    ("Czech", &["tschechisch", "czech"]),
+    ("Russian", &["russisch", "russian"]),
+    ("Ukrainian", &["ukrainisch", "ukrainian"]),
+    ("Turkish", &["türkisch", "tuerkisch", "turkish"]),
+    ("Arabic", &["arabisch", "arabic"]),
+    ("Chinese", &["chinesisch", "mandarin", "chinese"]),
+    ("Japanese", &["japanisch", "japanese"]),
+];
+
+/// Descriptions of proficiency, lowercase, most specific first
+const PROFICIENCIES: &[(&str, &str)] = &[
+    ("muttersprach", "native"),
+    ("native", "native"),
+    ("verhandlungssicher", "business fluent"),
+    ("business fluent", "business fluent"),
+    ("fließend", "fluent"),
+    ("fliessend", "fluent"),
+    ("fluent", "fluent"),
+    ("sehr gut", "very good"),
+    ("very good", "very good"),
+    ("excellent", "very good"),
+    ("grundkenntnisse", "basic"),
+    ("basic", "basic"),
+    ("gute", "good"),
+    ("gut", "good"),
+    ("good", "good"),
+];
+
+/// Phrases marking a requirement as optional
+const OPTIONAL_MARKERS: &[&str] = &[
+    "von vorteil",
+    "wünschenswert",
+    "wuenschenswert",
+    "wünschens",
+    "ein plus",
+    "nice to have",
+    "is a plus",
+    "a plus",
+    "optional",
+    "idealerweise",
+    "ideally",
+];
+
+/// Whether `text[start..end]` is a whole word (or phrase)
+fn is_word_at(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    let is_word_char = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    // "Node.js" should not match "js" and "C++" should not match "c"
+    !is_word_char(before)
+        && !is_word_char(after)
+        && !matches!(after, Some('+' | '#'))
+        && before != Some('.')
+}
+
+/// Byte offsets of whole-word occurrences of `term` in `text`
+fn find_word<'a>(text: &'a str, term: &'a str) -> impl Iterator<Item = usize> + 'a {
+    text.match_indices(term)
+        .map(|(idx, _)| idx)
+        .filter(move |idx| is_word_at(text, *idx, idx + term.len()))
+}
+
+/// Skills mentioned in a text, in dictionary order
+pub fn extract_skills(text: &str) -> Vec<ExtractedSkill> {
+    let lower = text.to_lowercase();
+    let mut skills: Vec<ExtractedSkill> = SKILLS
+        .iter()
+        .filter(|(_, _, terms)| {
+            terms
+                .iter()
+                .any(|term| find_word(&lower, term).next().is_some())
+        })
+        .map(|(name, category, _)| ExtractedSkill {
+            name: name.to_string(),
+            category: *category,
+        })
+        .collect();
+
+    for class in license_classes(&lower) {
+        let name = match class.as_str() {
+            "" => "Führerschein".to_string(),
+            class => format!("Führerschein {}", class),
+        };
+        skills.push(ExtractedSkill {
+            name,
+            category: SkillCategory::DriversLicense,
+        });
+    }
+    skills
+}
+
+/// Driver's license classes listed after a license word, or "" for a license
+/// without class
+fn license_classes(lower: &str) -> Vec<String> {
+    const FILLERS: &[&str] = &[
+        "klasse",
+        "klassen",
+        "kl",
+        "der",
+        "die",
+        "mind",
+        "mindestens",
+        "class",
+        "classes",
+        "category",
+        "und",
+        "oder",
+        "and",
+        "or",
+    ];
+
+    let mut classes: Vec<String> = Vec::new();
+    let mut mentioned = false;
+
+    for word in LICENSE_WORDS {
+        for (idx, _) in lower.match_indices(word) {
+            mentioned = true;
+            // "Führerschein der Klasse B/BE", "Fahrerlaubnis Kl. CE und C1E"
+            let rest = &lower[idx + word.len()..];
+            let tokens = rest
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|token| !token.is_empty())
+                .filter(|token| !FILLERS.contains(token));
+            for token in tokens {
+                let class = token.to_uppercase();
+                if !LICENSE_CLASSES.contains(&class.as_str()) {
+                    break;
+                }
+                if !classes.contains(&class) {
+                    classes.push(class);
+                }
+            }
+        }
+    }
+    if mentioned && classes.is_empty() {
+        return vec![String::new()];
+    }
+    classes
+}
+
+/// Spoken languages asked for in a text
+pub fn extract_language_requirements(text: &str) -> Vec<LanguageRequirement> {
+    let mut requirements: Vec<LanguageRequirement> = Vec::new();
+
+    for sentence in sentences(text) {
+        let sentence = sentence.to_lowercase();
+        let optional = OPTIONAL_MARKERS
+            .iter()
+            .any(|marker| sentence.contains(marker));
+
+        // Levels belong to the languages of their clause: in "sehr gutes Deutsch
+        // und Englisch (B2)" German is "very good" and English is "B2"
+        for clause in clauses(&sentence) {
+            let languages = LANGUAGES.iter().filter(|(_, terms)| {
+                terms
+                    .iter()
+                    .any(|term| find_language(clause, term).is_some())
+            });
+            for (language, _) in languages {
+                let requirement = LanguageRequirement {
+                    language: language.to_string(),
+                    level: cefr_level(clause),
+                    proficiency: PROFICIENCIES
+                        .iter()
+                        .find(|(term, _)| find_prefix(clause, term))
+                        .map(|(_, proficiency)| proficiency.to_string()),
+                    required: !optional,
+                };
+
+                match requirements
+                    .iter_mut()
+                    .find(|existing| existing.language == requirement.language)
+                {
+                    Some(existing) => {
+                        existing.level = existing.level.take().or(requirement.level);
+                        existing.proficiency =
+                            existing.proficiency.take().or(requirement.proficiency);
+                        existing.required |= requirement.required;
+                    }
+                    None => requirements.push(requirement),
+                }
+            }
+        }
+    }
+    requirements
+}
+
+/// Split a text into sentences without breaking at abbreviations such as
+/// "mind. C1" or "z. B."
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    for (idx, c) in text.char_indices() {
+        let ends = match c {
+            '!' | '?' | ';' | '\n' => true,
+            '.' => {
+                let previous = text[start..idx]
+                    .rsplit(|c: char| !c.is_alphanumeric())
+                    .next()
+                    .unwrap_or_default();
+                let next = text[idx + 1..]
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default();
+                text[idx + 1..].starts_with(char::is_whitespace)
+                    && previous.chars().count() > 4
+                    && next.chars().count() > 2
+                    && next.chars().all(char::is_alphabetic)
+            }
+            _ => false,
+        };
+        if ends {
+            sentences.push(&text[start..idx]);
+            start = idx + c.len_utf8();
+        }
+    }
+    sentences.push(&text[start..]);
+    sentences
+}
+
+/// Split a lowercase sentence into clauses at commas and conjunctions
+fn clauses(sentence: &str) -> Vec<&str> {
+    const CONJUNCTIONS: &[&str] = &[" und ", " sowie ", " oder ", " and ", " or "];
+
+    let mut clauses = Vec::new();
+    for part in sentence.split(',') {
+        let mut rest = part;
+        while let Some((idx, len)) = CONJUNCTIONS
+            .iter()
+            .filter_map(|conjunction| rest.find(conjunction).map(|idx| (idx, conjunction.len())))
+            .min()
+        {
+            clauses.push(&rest[..idx]);
+            rest = &rest[idx + len..];
+        }
+        clauses.push(rest);
+    }
+    clauses
+}
+
+/// Offset of a language mention: "Deutsch", "Deutschkenntnisse", "deutschsprachig"
+/// or "deutsche Sprache", but not "Deutschland"
+fn find_language(lower: &str, term: &str) -> Option<usize> {
+    lower.match_indices(term).map(|(idx, _)| idx).find(|idx| {
+        let before = lower[..*idx].chars().next_back();
+        if before.is_some_and(char::is_alphanumeric) {
+            return false;
+        }
+        let rest = &lower[idx + term.len()..];
+        let next = rest.chars().next();
+        !next.is_some_and(char::is_alphanumeric)
+            || rest.starts_with("kenntnis")
+            || rest.starts_with("sprachkenntnis")
+            || rest.starts_with("sprachig")
+            || rest.starts_with("e sprache")
+            || rest.starts_with("en sprache")
+            || rest.starts_with("-")
+    })
+}
+
+/// Whether a word starts with `term` somewhere in `text`
+fn find_prefix(text: &str, term: &str) -> bool {
+    text.match_indices(term).any(|(idx, _)| {
+        !text[..idx]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric)
+    })
+}
+
+/// First CEFR level (A1 to C2) in a lowercase text
+fn cefr_level(text: &str) -> Option<String> {
+    const LEVELS: &[&str] = &["a1", "a2", "b1", "b2", "c1", "c2"];
+    text.split(|c: char| !c.is_alphanumeric())
+        .find(|token| LEVELS.contains(token))
+        .map(str::to_uppercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(text: &str) -> Vec<String> {
+        extract_skills(text)
+            .into_iter()
+            .map(|skill| skill.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_skills() {
+        let text = "Backend-Entwicklung mit Rust und TypeScript (Node.js), Deployment mit \
+                    Docker auf AWS. Erfahrung mit C++ und C# sowie PostgreSQL.";
+        assert_eq!(
+            names(text),
+            vec![
+                "Rust",
+                "TypeScript",
+                "C#",
+                "C++",
+                "Node.js",
+                "Docker",
+                "AWS",
+                "PostgreSQL"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_skills_respects_word_boundaries() {
+        // "Java" in "JavaScript", "SAP" in "Sapporo", "Excel" in "excellent"
+        assert_eq!(
+            names("JavaScript in Sapporo, excellent!"),
+            vec!["JavaScript"]
+        );
+        assert_eq!(names("Gute Kenntnisse in ASP.NET"), vec![".NET"]);
+        assert!(names("Wir bieten ein gutes Betriebsklima").is_empty());
+    }
+
+    #[test]
+    fn test_certifications_and_licenses() {
+        let skills =
+            extract_skills("ITIL-Zertifizierung und Führerschein Klasse B/BE, Staplerschein");
+        assert!(skills.contains(&ExtractedSkill {
+            name: "ITIL".to_string(),
+            category: SkillCategory::Certification,
+        }));
+        assert!(skills.contains(&ExtractedSkill {
+            name: "Staplerschein".to_string(),
+            category: SkillCategory::Certification,
+        }));
+        let licenses: Vec<&str> = skills
+            .iter()
+            .filter(|skill| skill.category == SkillCategory::DriversLicense)
+            .map(|skill| skill.name.as_str())
+            .collect();
+        assert_eq!(licenses, vec!["Führerschein B", "Führerschein BE"]);
+
+        assert_eq!(
+            names("Fahrerlaubnis der Klasse CE"),
+            vec!["Führerschein CE"]
+        );
+        assert_eq!(names("Führerschein erforderlich"), vec!["Führerschein"]);
+    }
+
+    #[test]
+    fn test_extract_language_requirements() {
+        let text = "Sehr gute Deutschkenntnisse (mind. C1) und verhandlungssicheres Englisch.\n\
+                    Französisch ist von Vorteil. Standort: Deutschland";
+        let requirements = extract_language_requirements(text);
+        assert_eq!(requirements.len(), 3);
+
+        assert_eq!(requirements[0].language, "German");
+        assert_eq!(requirements[0].level.as_deref(), Some("C1"));
+        assert_eq!(requirements[0].proficiency.as_deref(), Some("very good"));
+        assert!(requirements[0].required);
+
+        assert_eq!(requirements[1].language, "English");
+        assert_eq!(requirements[1].level, None);
+        assert_eq!(
+            requirements[1].proficiency.as_deref(),
+            Some("business fluent")
+        );
+
+        assert_eq!(requirements[2].language, "French");
+        assert!(!requirements[2].required);
+    }
+
+    #[test]
+    fn test_language_levels_in_one_sentence() {
+        let requirements =
+            extract_language_requirements("Deutsch auf B2-Niveau und Englisch C1 erforderlich");
+        assert_eq!(requirements[0].level.as_deref(), Some("B2"));
+        assert_eq!(requirements[1].level.as_deref(), Some("C1"));
+    }
+
+    #[test]
+    fn test_no_language_in_country_name() {
+        assert!(
+            extract_language_requirements("Arbeitsort: Deutschland, Englischer Garten").is_empty()
+        );
+    }
+}
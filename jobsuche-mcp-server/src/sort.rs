@@ -0,0 +1,92 @@
+//! Order of search results
+//!
+//! The Jobsuche API returns jobs in its own relevance order and cannot sort.
+//! `sort_by` reorders the jobs of the returned page; it does not change which
+//! jobs are on the page.
+
+use crate::JobSummary;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Accepted values for `sort_by`
+pub const SORT_BY_VALUES: &[&str] = &["relevance", "newest", "distance"];
+
+/// Order of the jobs of a result page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    /// The API's order
+    #[default]
+    Relevance,
+    /// Most recently published first
+    Newest,
+    /// Closest to the search location first
+    Distance,
+}
+
+impl SortBy {
+    /// Sort a page of jobs; jobs without a date or distance go last
+    pub fn sort(self, jobs: &mut [JobSummary]) {
+        match self {
+            Self::Relevance => {}
+            Self::Newest => jobs.sort_by_key(|job| job.published_days_ago.unwrap_or(i64::MAX)),
+            Self::Distance => jobs.sort_by(|a, b| match (a.distance_km, b.distance_km) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(reference_number: &str, days_ago: Option<i64>, km: Option<f64>) -> JobSummary {
+        serde_json::from_value(serde_json::json!({
+            "reference_number": reference_number,
+            "title": "Job",
+            "employer": "Acme",
+            "location": "Berlin",
+            "distance_km": km,
+            "published_days_ago": days_ago,
+            "web_url": "",
+        }))
+        .unwrap()
+    }
+
+    fn order(jobs: &[JobSummary]) -> Vec<&str> {
+        jobs.iter()
+            .map(|job| job.reference_number.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let sort_by: Vec<SortBy> =
+            serde_json::from_value(serde_json::json!(SORT_BY_VALUES)).unwrap();
+        assert_eq!(
+            sort_by,
+            vec![SortBy::Relevance, SortBy::Newest, SortBy::Distance]
+        );
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut jobs = vec![
+            job("a", Some(5), None),
+            job("b", None, Some(3.0)),
+            job("c", Some(1), Some(12.5)),
+        ];
+
+        SortBy::Relevance.sort(&mut jobs);
+        assert_eq!(order(&jobs), vec!["a", "b", "c"]);
+        SortBy::Newest.sort(&mut jobs);
+        assert_eq!(order(&jobs), vec!["c", "a", "b"]);
+        SortBy::Distance.sort(&mut jobs);
+        assert_eq!(order(&jobs), vec!["b", "c", "a"]);
+    }
+}
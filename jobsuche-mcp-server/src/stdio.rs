@@ -0,0 +1,745 @@
+//! STDIO transport
+//!
+//! The framework's STDIO server answers one message at a time and cannot send
+//! messages of its own to the client. This transport reads JSON-RPC messages
+//! from stdin and handles every request in a task of its own, so a tool can
+//! report progress while it runs, ask the user via `elicitation/create` and
+//! wait for the answer, and a `notifications/cancelled` from the client
+//! reaches it before it has finished. All outgoing messages go through one queue
+//! and are written to stdout as one line each.
+
+use crate::completion::{complete_argument, CompleteRequest};
+use crate::context::{RequestContext, RequestId};
+use crate::elicitation::{ElicitationRequest, ElicitationResponse, Elicitor};
+use crate::progress::{ProgressNotification, ProgressSink};
+use crate::tools;
+use crate::JobsucheMcpServer;
+use anyhow::Context;
+use async_trait::async_trait;
+use pulseengine_mcp_protocol::{
+    CallToolRequestParam, Error, GetPromptRequestParam, PaginatedRequestParam,
+    ReadResourceRequestParam, MCP_VERSION,
+};
+use pulseengine_mcp_server::McpBackend;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::{mpsc, oneshot};
+use tracing::debug;
+
+/// Queue of JSON-RPC messages to the client
+pub type Outgoing = mpsc::UnboundedSender<Value>;
+
+/// Progress sink that forwards notifications to the client
+#[derive(Debug, Clone)]
+struct StdioProgressSink {
+    outgoing: Outgoing,
+}
+
+impl ProgressSink for StdioProgressSink {
+    fn send(&self, notification: ProgressNotification) {
+        // The client is gone if the queue is closed; the tool finishes anyway
+        let _ = self.outgoing.send(json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/progress",
+            "params": notification,
+        }));
+    }
+}
+
+/// Answer of the client to a request of the server: its result or error
+type ClientResponse = Result<Value, Value>;
+
+/// How long a tool waits for the user to answer an elicitation request
+const ELICITATION_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Requests of the server waiting for the client's answer, by JSON-encoded ID
+type PendingRequests = Mutex<HashMap<String, oneshot::Sender<ClientResponse>>>;
+
+/// Removes a request from the pending requests when its wait ends, however
+/// it ends
+struct PendingRequest<'a> {
+    pending: &'a PendingRequests,
+    id: String,
+}
+
+impl Drop for PendingRequest<'_> {
+    fn drop(&mut self) {
+        self.pending.lock().unwrap().remove(&self.id);
+    }
+}
+
+/// Sends `elicitation/create` requests to the client
+///
+/// Available once the client announced the `elicitation` capability in
+/// `initialize`.
+#[derive(Debug)]
+struct StdioElicitor {
+    outgoing: Outgoing,
+    supported: AtomicBool,
+    next_id: AtomicU64,
+    pending: PendingRequests,
+    timeout: Duration,
+}
+
+impl StdioElicitor {
+    fn new(outgoing: Outgoing, timeout: Duration) -> Self {
+        Self {
+            outgoing,
+            supported: AtomicBool::new(false),
+            next_id: AtomicU64::new(1),
+            pending: Mutex::new(HashMap::new()),
+            timeout,
+        }
+    }
+
+    /// Tell the client that the server no longer waits for an answer
+    fn cancel(&self, id: &Value, reason: &str) {
+        let _ = self.outgoing.send(json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/cancelled",
+            "params": {"requestId": id, "reason": reason},
+        }));
+    }
+
+    /// Pass the client's answer to the waiting request; `false` if none waits
+    fn resolve(&self, id: &Value, response: ClientResponse) -> bool {
+        match self.pending.lock().unwrap().remove(&id.to_string()) {
+            Some(waiting) => waiting.send(response).is_ok(),
+            None => false,
+        }
+    }
+}
+
+#[async_trait]
+impl Elicitor for StdioElicitor {
+    fn is_available(&self) -> bool {
+        self.supported.load(Ordering::Relaxed)
+    }
+
+    async fn elicit(&self, request: ElicitationRequest) -> anyhow::Result<ElicitationResponse> {
+        let id = json!(format!(
+            "elicitation-{}",
+            self.next_id.fetch_add(1, Ordering::Relaxed)
+        ));
+        let (answer, answered) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id.to_string(), answer);
+        let _pending = PendingRequest {
+            pending: &self.pending,
+            id: id.to_string(),
+        };
+        self.outgoing
+            .send(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "elicitation/create",
+                "params": request,
+            }))
+            .ok()
+            .context("The client is disconnected")?;
+
+        let cancellation = RequestContext::current().cancellation;
+        let answer = tokio::select! {
+            answer = tokio::time::timeout(self.timeout, answered) => answer,
+            _ = cancellation.cancelled() => {
+                self.cancel(&id, "The tool call was cancelled");
+                anyhow::bail!("The tool call was cancelled while waiting for the user");
+            }
+        };
+        let Ok(answer) = answer else {
+            self.cancel(&id, "No answer in time");
+            anyhow::bail!(
+                "The user did not answer within {} seconds",
+                self.timeout.as_secs()
+            );
+        };
+        match answer.context("The client is disconnected")? {
+            Ok(result) => Ok(serde_json::from_value(result)?),
+            Err(error) => anyhow::bail!("The client rejected the elicitation request: {}", error),
+        }
+    }
+}
+
+/// Connection to one client
+#[derive(Clone)]
+pub struct Session {
+    server: JobsucheMcpServer,
+    outgoing: Outgoing,
+    elicitor: Arc<StdioElicitor>,
+}
+
+impl Session {
+    /// Create a session that queues its messages to the client in `outgoing`
+    pub fn new(server: JobsucheMcpServer, outgoing: Outgoing) -> Self {
+        let sink = Arc::new(StdioProgressSink {
+            outgoing: outgoing.clone(),
+        });
+        let elicitor = Arc::new(StdioElicitor::new(outgoing.clone(), ELICITATION_TIMEOUT));
+        Self {
+            server: server
+                .with_progress_sink(sink)
+                .with_elicitor(elicitor.clone()),
+            outgoing,
+            elicitor,
+        }
+    }
+
+    /// Handle one line read from the client
+    pub fn handle_line(&self, line: &str) {
+        match serde_json::from_str(line) {
+            Ok(message) => self.handle_message(message),
+            Err(e) => self.send(json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": Error::parse_error(e.to_string()),
+            })),
+        }
+    }
+
+    /// Handle one message from the client
+    ///
+    /// Requests are answered from a task of their own; notifications are
+    /// handled before the next message is read.
+    pub fn handle_message(&self, message: Value) {
+        let method = message.get("method").and_then(Value::as_str);
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        match (method, message.get("id")) {
+            (Some(method), Some(id)) => {
+                let session = self.clone();
+                let method = method.to_string();
+                let id = id.clone();
+                tokio::spawn(async move {
+                    let response = match session.handle_request(&method, id.clone(), params).await {
+                        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+                        Err(error) => json!({"jsonrpc": "2.0", "id": id, "error": error}),
+                    };
+                    session.send(response);
+                });
+            }
+            (Some(method), None) => self.handle_notification(method, &params),
+            (None, Some(id)) => {
+                let response = match message.get("error") {
+                    Some(error) => Err(error.clone()),
+                    None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+                };
+                if !self.elicitor.resolve(id, response) {
+                    debug!("Ignoring response to unknown request {}", id);
+                }
+            }
+            (None, None) => debug!("Ignoring message without a method: {}", message),
+        }
+    }
+
+    fn handle_notification(&self, method: &str, params: &Value) {
+        match method {
+            "notifications/cancelled" => match params.get("requestId") {
+                Some(request_id) => {
+                    if !self.server.cancel_request(request_id) {
+                        debug!("Request {} to cancel is not running", request_id);
+                    }
+                }
+                None => debug!("Ignoring cancellation without a request ID"),
+            },
+            _ => debug!("Ignoring notification {}", method),
+        }
+    }
+
+    /// Queue a message to the client
+    fn send(&self, message: Value) {
+        let _ = self.outgoing.send(message);
+    }
+
+    async fn handle_request(
+        &self,
+        method: &str,
+        id: RequestId,
+        params: Value,
+    ) -> Result<Value, Error> {
+        let server = &self.server;
+        match method {
+            "initialize" => {
+                let elicitation = params.pointer("/capabilities/elicitation").is_some();
+                self.elicitor
+                    .supported
+                    .store(elicitation, Ordering::Relaxed);
+                Ok(self.initialize())
+            }
+            "ping" => Ok(json!({})),
+            "tools/list" => {
+                let mut list = to_result(McpBackend::list_tools(server, paginated(params)?).await)?;
+                if let Some(tools) = list.get_mut("tools").and_then(Value::as_array_mut) {
+                    tools.iter_mut().for_each(tools::describe);
+                }
+                Ok(list)
+            }
+            "tools/call" => {
+                let progress_token = params.pointer("/_meta/progressToken").cloned();
+                let request: CallToolRequestParam = parse(params)?;
+                to_result(server.call_tool_request(id, progress_token, request).await)
+            }
+            "resources/list" => {
+                to_result(McpBackend::list_resources(server, paginated(params)?).await)
+            }
+            "resources/templates/list" => {
+                to_result(McpBackend::list_resource_templates(server, paginated(params)?).await)
+            }
+            "resources/read" => {
+                let request: ReadResourceRequestParam = parse(params)?;
+                to_result(McpBackend::read_resource(server, request).await)
+            }
+            "prompts/list" => to_result(McpBackend::list_prompts(server, paginated(params)?).await),
+            "prompts/get" => {
+                let request: GetPromptRequestParam = parse(params)?;
+                to_result(McpBackend::get_prompt(server, request).await)
+            }
+            "completion/complete" => {
+                let request: CompleteRequest = parse(params)?;
+                let argument = request.argument;
+                Ok(json!({"completion": complete_argument(&argument.name, &argument.value)}))
+            }
+            _ => Err(Error::method_not_found(method)),
+        }
+    }
+
+    /// Result of `initialize`
+    ///
+    /// Capabilities are written by hand: the protocol crate serializes their
+    /// fields in snake case, and logging/setLevel is not supported.
+    fn initialize(&self) -> Value {
+        let info = McpBackend::get_server_info(&self.server);
+        json!({
+            "protocolVersion": MCP_VERSION,
+            "capabilities": {
+                "tools": {"listChanged": false},
+                "resources": {"subscribe": false, "listChanged": false},
+                "prompts": {"listChanged": false},
+                "completions": {},
+            },
+            "serverInfo": info.server_info,
+            "instructions": info.instructions,
+        })
+    }
+}
+
+/// Parse request parameters
+fn parse<T: DeserializeOwned>(params: Value) -> Result<T, Error> {
+    serde_json::from_value(params).map_err(|e| Error::invalid_params(e.to_string()))
+}
+
+/// Parse the parameters of a list request, which may be omitted
+fn paginated(params: Value) -> Result<PaginatedRequestParam, Error> {
+    if params.is_null() {
+        Ok(PaginatedRequestParam { cursor: None })
+    } else {
+        parse(params)
+    }
+}
+
+/// Convert the result of a backend method into a JSON-RPC result
+fn to_result<T: Serialize, E: Into<Error>>(result: Result<T, E>) -> Result<Value, Error> {
+    let result = result.map_err(Into::into)?;
+    serde_json::to_value(result).map_err(|e| Error::internal_error(e.to_string()))
+}
+
+/// Serve MCP over stdin and stdout until stdin is closed
+///
+/// Requests still running when stdin closes are answered before returning.
+pub async fn serve(server: JobsucheMcpServer) -> anyhow::Result<()> {
+    let (outgoing, mut queue) = mpsc::unbounded_channel::<Value>();
+    let writer = tokio::spawn(async move {
+        let mut stdout = tokio::io::stdout();
+        while let Some(message) = queue.recv().await {
+            let mut line = message.to_string();
+            line.push('\n');
+            stdout.write_all(line.as_bytes()).await?;
+            stdout.flush().await?;
+        }
+        anyhow::Ok(())
+    });
+
+    let session = Session::new(server, outgoing);
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(line) = lines.next_line().await? {
+        if !line.trim().is_empty() {
+            session.handle_line(&line);
+        }
+    }
+
+    // The writer stops once the session and all request tasks are gone
+    drop(session);
+    writer.await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::JobsucheConfig;
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    /// Jobsuche API stand-in answering every request with `body`, or never
+    pub(crate) async fn stub_api(body: Option<&'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let Some(body) = body else {
+                        return std::future::pending().await;
+                    };
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                         content-length: {}\r\nconnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        url
+    }
+
+    /// Session against a stub API, with the queue of its outgoing messages
+    pub(crate) async fn session(
+        body: Option<&'static str>,
+    ) -> (Session, mpsc::UnboundedReceiver<Value>) {
+        let config = JobsucheConfig {
+            api_url: stub_api(body).await,
+            ..Default::default()
+        };
+        let server = JobsucheMcpServer::with_config(config).await.unwrap();
+        let (outgoing, queue) = mpsc::unbounded_channel();
+        (Session::new(server, outgoing), queue)
+    }
+
+    pub(crate) async fn next(queue: &mut mpsc::UnboundedReceiver<Value>) -> Value {
+        tokio::time::timeout(Duration::from_secs(10), queue.recv())
+            .await
+            .expect("no message within 10 seconds")
+            .expect("queue closed")
+    }
+
+    const EMPTY_SEARCH: &str = r#"{"stellenangebote": [], "maxErgebnisse": 0}"#;
+
+    #[tokio::test]
+    async fn test_initialize_and_unknown_method() {
+        let (session, mut queue) = session(Some(EMPTY_SEARCH)).await;
+
+        session.handle_line(r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}"#);
+        let response = next(&mut queue).await;
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["protocolVersion"], MCP_VERSION);
+        assert_eq!(
+            response["result"]["serverInfo"]["name"],
+            "Jobsuche MCP Server"
+        );
+
+        session.handle_line(r#"{"jsonrpc": "2.0", "id": "x", "method": "nope"}"#);
+        let response = next(&mut queue).await;
+        assert_eq!(response["id"], "x");
+        assert_eq!(response["error"]["code"], -32601);
+
+        session.handle_line("not json");
+        assert_eq!(next(&mut queue).await["error"]["code"], -32700);
+    }
+
+    #[tokio::test]
+    async fn test_tools_list_has_annotations_and_output_schemas() {
+        let (session, mut queue) = session(Some(EMPTY_SEARCH)).await;
+
+        session.handle_line(r#"{"jsonrpc": "2.0", "id": 1, "method": "tools/list"}"#);
+        let response = next(&mut queue).await;
+        let listed = response["result"]["tools"].as_array().unwrap();
+        assert_eq!(listed.len(), tools::TOOLS.len());
+
+        let tool = |name: &str| {
+            listed
+                .iter()
+                .find(|tool| tool["name"] == name)
+                .unwrap_or_else(|| panic!("{} is not listed", name))
+        };
+        for tool in listed {
+            assert!(tool["annotations"].is_object(), "{}", tool["name"]);
+        }
+        assert_eq!(tool("search_jobs")["annotations"]["readOnlyHint"], false);
+        assert_eq!(tool("search_jobs")["annotations"]["idempotentHint"], false);
+        assert_eq!(
+            tool("suggest_job_titles")["annotations"]["readOnlyHint"],
+            true
+        );
+        assert_eq!(tool("search_jobs")["annotations"]["openWorldHint"], true);
+        assert_eq!(
+            tool("remove_favorite")["annotations"]["readOnlyHint"],
+            false
+        );
+        assert_eq!(
+            tool("remove_favorite")["annotations"]["destructiveHint"],
+            true
+        );
+
+        for tool in listed {
+            assert_eq!(tool["outputSchema"]["type"], "object", "{}", tool["name"]);
+        }
+        assert!(tool("search_jobs")["outputSchema"]["properties"]["jobs"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_completion() {
+        let (session, mut queue) = session(Some(EMPTY_SEARCH)).await;
+
+        session.handle_message(json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "completion/complete",
+            "params": {
+                "ref": {"type": "ref/prompt", "name": "search"},
+                "argument": {"name": "sort_by", "value": "n"}
+            }
+        }));
+        let completion = &next(&mut queue).await["result"]["completion"];
+        assert_eq!(completion["values"], json!(["newest"]));
+        assert_eq!(completion["hasMore"], false);
+
+        session.handle_message(json!({
+            "jsonrpc": "2.0",
+            "id": 4,
+            "method": "completion/complete",
+            "params": {
+                "ref": {"type": "ref/prompt", "name": "search"},
+                "argument": {"name": "location", "value": "koln"}
+            }
+        }));
+        let completion = &next(&mut queue).await["result"]["completion"];
+        assert_eq!(completion["values"], json!(["Köln"]));
+    }
+
+    #[tokio::test]
+    async fn test_underspecified_search_elicits_constraints() {
+        let (session, mut queue) = session(Some(EMPTY_SEARCH)).await;
+        session.handle_message(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {"capabilities": {"elicitation": {}}}
+        }));
+        next(&mut queue).await;
+
+        session.handle_message(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {"name": "search_jobs", "arguments": {}}
+        }));
+        let request = next(&mut queue).await;
+        assert_eq!(request["method"], "elicitation/create");
+        assert!(request["params"]["requestedSchema"]["properties"]["location"].is_object());
+
+        session.handle_message(json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": {"action": "accept", "content": {"location": "Köln"}}
+        }));
+        let response = next(&mut queue).await;
+        assert_eq!(response["id"], 2);
+        assert_eq!(
+            response["result"]["structuredContent"]["effective_query"]["location"],
+            "Köln"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_elicitation_without_client_support() {
+        let (session, mut queue) = session(Some(EMPTY_SEARCH)).await;
+        session.handle_message(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {"capabilities": {}}
+        }));
+        next(&mut queue).await;
+
+        session.handle_message(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {"name": "search_jobs", "arguments": {}}
+        }));
+        let response = next(&mut queue).await;
+        assert_eq!(response["id"], 2);
+        assert!(response["result"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_sends_progress() {
+        let (session, mut queue) = session(Some(EMPTY_SEARCH)).await;
+
+        session.handle_message(json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/call",
+            "params": {
+                "name": "batch_search_jobs",
+                "arguments": {"searches": [
+                    {"name": "rust", "job_title": "Rust"},
+                    {"name": "go", "job_title": "Go"}
+                ]},
+                "_meta": {"progressToken": "batch-1"}
+            }
+        }));
+
+        let mut progress = Vec::new();
+        let response = loop {
+            let message = next(&mut queue).await;
+            if message["method"] == "notifications/progress" {
+                progress.push(message["params"].clone());
+            } else {
+                break message;
+            }
+        };
+
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["result"]["isError"], false);
+        assert!(!progress.is_empty());
+        assert!(progress.iter().all(|p| p["progressToken"] == "batch-1"));
+        assert_eq!(progress.last().unwrap()["progress"], 6);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_notification_stops_tool_call() {
+        // The API never answers, so the call only ends by cancellation
+        let (session, mut queue) = session(None).await;
+
+        session.handle_message(json!({
+            "jsonrpc": "2.0",
+            "id": "call-1",
+            "method": "tools/call",
+            "params": {
+                "name": "batch_search_jobs",
+                "arguments": {"searches": [{"name": "rust", "job_title": "Rust"}]}
+            }
+        }));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        session.handle_message(json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/cancelled",
+            "params": {"requestId": "call-1", "reason": "user aborted"}
+        }));
+
+        let response = next(&mut queue).await;
+        assert_eq!(response["id"], "call-1");
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        let result: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(result["cancelled"], true);
+        assert_eq!(session.server.cancellations.active_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_tool_calls_are_audited() {
+        let config = JobsucheConfig {
+            api_url: stub_api(Some(EMPTY_SEARCH)).await,
+            audit_log: true,
+            ..Default::default()
+        };
+        let server = JobsucheMcpServer::with_config(config).await.unwrap();
+        let (outgoing, mut queue) = mpsc::unbounded_channel();
+        let session = Session::new(server, outgoing);
+
+        let call = |id: u64, name: &str, arguments: Value| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": "tools/call",
+                "params": {"name": name, "arguments": arguments}
+            })
+        };
+        session.handle_message(call(1, "search_jobs", json!({"job_title": "Koch"})));
+        let search = next(&mut queue).await;
+        session.handle_message(call(2, "no_such_tool", json!({})));
+        assert!(next(&mut queue).await["error"].is_object());
+
+        // Entries are written in the background
+        let mut entries = Value::Null;
+        for id in 3..50 {
+            session.handle_message(call(id, "get_audit_log", json!({})));
+            let response = next(&mut queue).await;
+            entries = response["result"]["structuredContent"]["entries"].clone();
+            if entries.as_array().unwrap().len() >= 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let entries = entries.as_array().unwrap();
+        let search_entry = entries
+            .iter()
+            .find(|entry| entry["tool"] == "search_jobs")
+            .unwrap();
+        assert_eq!(search_entry["outcome"], "ok");
+        assert_eq!(
+            search_entry["request_id"],
+            search["result"]["structuredContent"]["request_id"]
+        );
+        assert_eq!(search_entry["params"], r#"{"job_title":"Koch"}"#);
+        let failed = entries
+            .iter()
+            .find(|entry| entry["tool"] == "no_such_tool")
+            .unwrap();
+        assert_eq!(failed["outcome"], "error");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_external_url_is_an_error() {
+        let details = r#"{"referenznummer": "10001-1234567890-S", "stellenangebotsTitel": "Koch"}"#;
+        let (session, mut queue) = session(Some(details)).await;
+
+        session.handle_message(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "get_job_details",
+                "arguments": {"reference_number": "10001-1234567890-S", "resolve_external_url": true}
+            }
+        }));
+        let response = next(&mut queue).await;
+        let message = response["error"]["message"].as_str().unwrap();
+        assert!(message.contains("external URL"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_elicitation_ends_on_timeout_and_cancellation() {
+        let (outgoing, mut queue) = mpsc::unbounded_channel();
+        let elicitor = StdioElicitor::new(outgoing, Duration::from_millis(50));
+        let request = crate::elicitation::search_constraints_request();
+
+        let error = elicitor.elicit(request.clone()).await.unwrap_err();
+        assert!(error.to_string().contains("did not answer"), "{}", error);
+        assert_eq!(next(&mut queue).await["method"], "elicitation/create");
+        assert_eq!(next(&mut queue).await["method"], "notifications/cancelled");
+        assert!(elicitor.pending.lock().unwrap().is_empty());
+
+        let ctx = RequestContext::default();
+        let cancellation = ctx.cancellation.clone();
+        let cancel = async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            cancellation.cancel();
+        };
+        let (result, ()) = tokio::join!(ctx.scope(elicitor.elicit(request)), cancel);
+        assert!(result.unwrap_err().to_string().contains("cancelled"));
+        assert!(elicitor.pending.lock().unwrap().is_empty());
+    }
+}
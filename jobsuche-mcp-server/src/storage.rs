@@ -0,0 +1,580 @@
+//! Persistent local storage
+//!
+//! State that has to survive restarts (favorites and similar user data) is kept
+//! as named collections of JSON documents. Where the documents live is decided
+//! by a [`JobStore`] backend:
+//!
+//! - [`MemoryStore`]: in memory only, lost when the server exits
+//! - [`JsonFileStore`]: a single JSON file in the data directory, rewritten on
+//!   every change using a temporary file and rename so that a crash never
+//!   leaves a truncated store behind
+//! - [`SqliteStore`]: an SQLite database in WAL mode; every change is committed
+//!   immediately and the schema is versioned and upgraded on open
+//!
+//! Any backend can be wrapped in an [`EncryptedStore`] to encrypt the documents
+//! at rest.
+//!
+//! Embedders of the library crate can supply their own backend via
+//! [`JobsucheMcpServer::new_with_store`](crate::JobsucheMcpServer::new_with_store).
+//! [`Storage`] provides typed access on top of the backend.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tracing::warn;
+
+mod encrypted;
+mod json;
+mod memory;
+mod sqlite;
+
+#[cfg(feature = "keychain")]
+pub use encrypted::keychain_key;
+pub use encrypted::{ensure_unencrypted, EncryptedStore, StoreKey};
+pub use json::{JsonFileStore, STORE_FILE_NAME};
+pub use memory::MemoryStore;
+pub(crate) use sqlite::database_size;
+pub use sqlite::SqliteStore;
+
+/// Collection holding favorite jobs, keyed by reference number
+pub const FAVORITES: &str = "favorites";
+
+/// Collection holding saved searches, keyed by lowercase name
+pub const SAVED_SEARCHES: &str = "saved_searches";
+
+/// Collection of reference numbers already returned to the client
+pub const SEEN_JOBS: &str = "seen_jobs";
+
+/// Collection holding user notes, keyed by reference number
+pub const JOB_NOTES: &str = "job_notes";
+
+/// Collection holding job detail snapshots, keyed by reference number
+pub const JOB_ARCHIVE: &str = "job_archive";
+
+/// Collection holding watchlists, keyed by lowercase name
+pub const WATCHLISTS: &str = "watchlists";
+
+/// Collection holding the search history log
+pub const SEARCH_HISTORY: &str = "search_history";
+
+/// Collection holding the log of scheduled runs
+pub const SCHEDULED_RUNS: &str = "scheduled_runs";
+
+/// Collection holding the known jobs of scheduled saved searches, keyed by lowercase name
+pub const SCHEDULE_BASELINES: &str = "schedule_baselines";
+
+/// Collection holding the user profile (a single document)
+pub const USER_PROFILE: &str = "user_profile";
+
+/// Collection holding monitored employers, keyed by lowercase name and location
+pub const EMPLOYER_MONITORS: &str = "employer_monitors";
+
+/// Collection holding daily posting counts of employers, keyed like [`EMPLOYER_MONITORS`]
+pub const EMPLOYER_HISTORY: &str = "employer_history";
+
+/// Collection holding the stored employer blocklist and allowlist (a single document)
+pub const EMPLOYER_FILTER: &str = "employer_filter";
+
+/// Collection holding the audit log of tool calls
+pub const AUDIT_LOG: &str = "audit_log";
+
+/// Collection holding geocoded places, keyed by their normalized address
+pub const GEOCODE_CACHE: &str = "geocode_cache";
+
+/// Collection holding the time of the last digest (a single document)
+pub const DIGESTS: &str = "digests";
+
+/// Collection holding the jobs already reported by digests, keyed by source and lowercase name
+pub const DIGEST_BASELINES: &str = "digest_baselines";
+
+/// All collections used by the server
+pub const ALL_COLLECTIONS: &[&str] = &[
+    FAVORITES,
+    SAVED_SEARCHES,
+    SEEN_JOBS,
+    JOB_NOTES,
+    JOB_ARCHIVE,
+    WATCHLISTS,
+    SEARCH_HISTORY,
+    SCHEDULED_RUNS,
+    SCHEDULE_BASELINES,
+    USER_PROFILE,
+    EMPLOYER_MONITORS,
+    EMPLOYER_HISTORY,
+    EMPLOYER_FILTER,
+    AUDIT_LOG,
+    GEOCODE_CACHE,
+    DIGESTS,
+    DIGEST_BASELINES,
+];
+
+/// Number and total size of the documents in a collection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollectionSize {
+    /// Number of documents
+    pub documents: usize,
+
+    /// Size of the documents as compact JSON
+    pub bytes: u64,
+}
+
+/// Computes the new version of a document from the current one (`None` if missing)
+pub type UpdateFn<'a> = Box<dyn FnOnce(Option<Value>) -> Result<Value> + 'a>;
+
+/// Backend holding the document collections
+///
+/// Documents are JSON values addressed by collection name and key. A store is
+/// shared by all concurrent requests, so `update` must read and write the
+/// document atomically.
+pub trait JobStore: Send + Sync + fmt::Debug {
+    /// Read a document
+    fn get(&self, collection: &str, key: &str) -> Result<Option<Value>>;
+
+    /// Insert or replace a document
+    fn put(&self, collection: &str, key: &str, document: Value) -> Result<()>;
+
+    /// Replace a document with the result of `f`; nothing is written if `f` fails
+    fn update(&self, collection: &str, key: &str, f: UpdateFn<'_>) -> Result<()>;
+
+    /// Insert documents whose keys are not present yet; returns the number inserted
+    fn insert_missing(&self, collection: &str, documents: Vec<(String, Value)>) -> Result<usize>;
+
+    /// Delete a document; returns `false` if it did not exist
+    fn remove(&self, collection: &str, key: &str) -> Result<bool>;
+
+    /// Delete several documents; returns the number of deleted documents
+    fn remove_many(&self, collection: &str, keys: &[String]) -> Result<usize> {
+        let mut removed = 0;
+        for key in keys {
+            if self.remove(collection, key)? {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// All documents of a collection, ordered by key
+    fn list(&self, collection: &str) -> Result<Vec<(String, Value)>>;
+
+    /// Keys of all documents in a collection
+    fn keys(&self, collection: &str) -> Result<HashSet<String>>;
+
+    /// Number and size of the documents in a collection
+    fn collection_size(&self, collection: &str) -> Result<CollectionSize> {
+        let documents = self.list(collection)?;
+        Ok(CollectionSize {
+            documents: documents.len(),
+            bytes: documents
+                .iter()
+                .map(|(_, document)| document.to_string().len() as u64)
+                .sum(),
+        })
+    }
+
+    /// Short name of the backend (e.g. "sqlite")
+    fn backend_name(&self) -> &'static str {
+        "custom"
+    }
+
+    /// File or database the documents are persisted in, `None` if not persisted
+    fn location(&self) -> Option<&Path> {
+        None
+    }
+
+    /// Bytes used on disk, `None` if not persisted
+    fn disk_usage(&self) -> Option<u64> {
+        None
+    }
+
+    /// Release space left behind by deleted documents
+    fn compact(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Typed access to a [`JobStore`]
+#[derive(Debug, Clone)]
+pub struct Storage {
+    store: Arc<dyn JobStore>,
+}
+
+impl Storage {
+    /// Wrap a store backend
+    pub fn new(store: Arc<dyn JobStore>) -> Self {
+        Self { store }
+    }
+
+    /// Open the JSON store in `data_dir`, creating the directory if needed
+    pub fn open(data_dir: &Path) -> Result<Self> {
+        Ok(Self::new(Arc::new(JsonFileStore::open(data_dir)?)))
+    }
+
+    /// Open (or create) the SQLite database at `path` and upgrade its schema
+    pub fn open_sqlite(path: &Path) -> Result<Self> {
+        Ok(Self::new(Arc::new(SqliteStore::open(path)?)))
+    }
+
+    /// Create a store that is not persisted
+    pub fn in_memory() -> Self {
+        Self::new(Arc::new(MemoryStore::new()))
+    }
+
+    /// Path of the store file, if persisted
+    pub fn path(&self) -> Option<&Path> {
+        self.store.location()
+    }
+
+    /// Read a document
+    pub fn get<T: DeserializeOwned>(&self, collection: &str, key: &str) -> Result<Option<T>> {
+        self.store
+            .get(collection, key)?
+            .map(serde_json::from_value)
+            .transpose()
+            .with_context(|| format!("Invalid document '{}' in '{}'", key, collection))
+    }
+
+    /// Insert or replace a document
+    pub fn put<T: Serialize>(&self, collection: &str, key: &str, document: &T) -> Result<()> {
+        self.store
+            .put(collection, key, serde_json::to_value(document)?)
+    }
+
+    /// Modify a document in place, starting from `T::default()` if it is missing
+    ///
+    /// The document is locked during `f`, so concurrent updates are not lost.
+    pub fn update<T, R>(
+        &self,
+        collection: &str,
+        key: &str,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> Result<R>
+    where
+        T: Serialize + DeserializeOwned + Default,
+    {
+        let mut output = None;
+        self.store.update(
+            collection,
+            key,
+            Box::new(|value| {
+                let mut document: T = match value {
+                    Some(value) => serde_json::from_value(value).with_context(|| {
+                        format!("Invalid document '{}' in '{}'", key, collection)
+                    })?,
+                    None => T::default(),
+                };
+                output = Some(f(&mut document));
+                Ok(serde_json::to_value(document)?)
+            }),
+        )?;
+        output.context("Store did not apply the update")
+    }
+
+    /// Insert documents whose keys are not present yet, in a single write
+    ///
+    /// Returns the number of inserted documents.
+    pub fn insert_missing<T, I>(&self, collection: &str, documents: I) -> Result<usize>
+    where
+        T: Serialize,
+        I: IntoIterator<Item = (String, T)>,
+    {
+        let documents = documents
+            .into_iter()
+            .map(|(key, document)| Ok((key, serde_json::to_value(document)?)))
+            .collect::<Result<Vec<_>>>()?;
+        self.store.insert_missing(collection, documents)
+    }
+
+    /// Delete a document; returns `false` if it did not exist
+    pub fn remove(&self, collection: &str, key: &str) -> Result<bool> {
+        self.store.remove(collection, key)
+    }
+
+    /// Delete all documents of a collection for which `keep` returns false
+    ///
+    /// Documents that no longer match `T` are kept. Returns the number of
+    /// deleted documents.
+    pub fn retain<T: DeserializeOwned>(
+        &self,
+        collection: &str,
+        keep: impl Fn(&str, &T) -> bool,
+    ) -> Result<usize> {
+        let expired: Vec<String> = self
+            .entries(collection)
+            .into_iter()
+            .filter(|(key, document)| !keep(key, document))
+            .map(|(key, _)| key)
+            .collect();
+
+        if expired.is_empty() {
+            return Ok(0);
+        }
+        self.store.remove_many(collection, &expired)
+    }
+
+    /// Bytes used on disk, `None` if not persisted
+    pub fn disk_usage(&self) -> Option<u64> {
+        self.store.disk_usage()
+    }
+
+    /// Number and size of the documents in a collection
+    pub fn collection_size(&self, collection: &str) -> Result<CollectionSize> {
+        self.store.collection_size(collection)
+    }
+
+    /// Short name of the backend (e.g. "sqlite")
+    pub fn backend_name(&self) -> &'static str {
+        self.store.backend_name()
+    }
+
+    /// Release space left behind by deleted documents
+    pub fn compact(&self) -> Result<()> {
+        self.store.compact()
+    }
+
+    /// All documents of a collection, ordered by key
+    ///
+    /// Documents that no longer match `T` are skipped with a warning.
+    pub fn list<T: DeserializeOwned>(&self, collection: &str) -> Vec<T> {
+        self.entries(collection)
+            .into_iter()
+            .map(|(_, document)| document)
+            .collect()
+    }
+
+    /// All documents of a collection with their keys, ordered by key
+    ///
+    /// Documents that no longer match `T` are skipped with a warning.
+    pub fn entries<T: DeserializeOwned>(&self, collection: &str) -> Vec<(String, T)> {
+        let documents = match self.store.list(collection) {
+            Ok(documents) => documents,
+            Err(e) => {
+                warn!("Failed to list '{}': {:#}", collection, e);
+                return Vec::new();
+            }
+        };
+
+        documents
+            .into_iter()
+            .filter_map(|(key, value)| match serde_json::from_value(value) {
+                Ok(document) => Some((key, document)),
+                Err(e) => {
+                    warn!(
+                        "Skipping invalid document '{}' in '{}': {}",
+                        key, collection, e
+                    );
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Keys of all documents in a collection
+    pub fn keys(&self, collection: &str) -> HashSet<String> {
+        self.store.keys(collection).unwrap_or_else(|e| {
+            warn!("Failed to read keys of '{}': {:#}", collection, e);
+            HashSet::new()
+        })
+    }
+}
+
+/// Storage key for a user-chosen name (case-insensitive, trimmed)
+///
+/// Fails for blank names.
+pub fn name_key(name: &str) -> Result<String> {
+    let key = name.trim().to_lowercase();
+    if key.is_empty() {
+        anyhow::bail!("Name must not be empty");
+    }
+    Ok(key)
+}
+
+/// Current UTC time as RFC 3339 string
+pub fn now_rfc3339() -> String {
+    OffsetDateTime::now_utc()
+        .format(&Rfc3339)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    pub(super) struct Doc {
+        name: String,
+    }
+
+    pub(super) fn doc(name: &str) -> Doc {
+        Doc {
+            name: name.to_string(),
+        }
+    }
+
+    pub(super) fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("jobsuche-storage-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Behavior every backend has to provide
+    pub(super) fn check_backend(storage: &Storage) {
+        storage.put("docs", "b", &doc("second")).unwrap();
+        storage.put("docs", "a", &doc("first")).unwrap();
+        storage.put("docs", "a", &doc("replaced")).unwrap();
+        assert_eq!(
+            storage.get::<Doc>("docs", "a").unwrap(),
+            Some(doc("replaced"))
+        );
+        assert_eq!(storage.get::<Doc>("docs", "missing").unwrap(), None);
+        assert_eq!(
+            storage.list::<Doc>("docs"),
+            vec![doc("replaced"), doc("second")]
+        );
+
+        let inserted = storage
+            .insert_missing(
+                "docs",
+                vec![
+                    ("a".to_string(), doc("ignored")),
+                    ("c".to_string(), doc("third")),
+                ],
+            )
+            .unwrap();
+        assert_eq!(inserted, 1);
+        assert_eq!(
+            storage.keys("docs"),
+            HashSet::from(["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+
+        for _ in 0..3 {
+            storage
+                .update("counters", "calls", |count: &mut u32| *count += 1)
+                .unwrap();
+        }
+        assert_eq!(storage.get::<u32>("counters", "calls").unwrap(), Some(3));
+
+        assert!(storage.remove("docs", "c").unwrap());
+        assert!(!storage.remove("docs", "c").unwrap());
+        assert!(storage.list::<Doc>("other").is_empty());
+
+        storage.put("docs", "d", &doc("expired")).unwrap();
+        storage.put("docs", "e", &doc("expired")).unwrap();
+        let removed = storage
+            .retain("docs", |_, document: &Doc| document.name != "expired")
+            .unwrap();
+        assert_eq!(removed, 2);
+        storage.compact().unwrap();
+
+        assert_eq!(
+            storage.collection_size("docs").unwrap(),
+            CollectionSize {
+                documents: 2,
+                bytes: (r#"{"name":"replaced"}"#.len() + r#"{"name":"second"}"#.len()) as u64
+            }
+        );
+        assert_eq!(
+            storage.collection_size("other").unwrap(),
+            CollectionSize::default()
+        );
+        assert!(storage.keys("other").is_empty());
+    }
+
+    #[test]
+    fn test_memory_backend() {
+        let storage = Storage::in_memory();
+        assert!(storage.path().is_none());
+        check_backend(&storage);
+    }
+
+    #[test]
+    fn test_update_failure_keeps_document() {
+        let storage = Storage::in_memory();
+        storage.put("docs", "a", &42).unwrap();
+
+        let result = storage.update("docs", "a", |document: &mut Doc| {
+            document.name.push('!');
+        });
+        assert!(result.is_err());
+        assert_eq!(storage.get::<u32>("docs", "a").unwrap(), Some(42));
+    }
+
+    #[test]
+    fn test_list_skips_invalid_documents() {
+        let storage = Storage::in_memory();
+        storage.put("docs", "good", &doc("ok")).unwrap();
+        storage.put("docs", "bad", &42).unwrap();
+        assert_eq!(storage.list::<Doc>("docs"), vec![doc("ok")]);
+    }
+
+    /// A backend supplied by an embedder
+    #[derive(Debug, Default)]
+    struct ReadOnlyStore;
+
+    impl JobStore for ReadOnlyStore {
+        fn get(&self, _collection: &str, key: &str) -> Result<Option<Value>> {
+            Ok(Some(serde_json::json!({ "name": key })))
+        }
+
+        fn put(&self, _collection: &str, _key: &str, _document: Value) -> Result<()> {
+            anyhow::bail!("read-only")
+        }
+
+        fn update(&self, _collection: &str, _key: &str, _f: UpdateFn<'_>) -> Result<()> {
+            Ok(())
+        }
+
+        fn insert_missing(
+            &self,
+            _collection: &str,
+            _documents: Vec<(String, Value)>,
+        ) -> Result<usize> {
+            Ok(0)
+        }
+
+        fn remove(&self, _collection: &str, _key: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        fn list(&self, _collection: &str) -> Result<Vec<(String, Value)>> {
+            anyhow::bail!("unavailable")
+        }
+
+        fn keys(&self, _collection: &str) -> Result<HashSet<String>> {
+            Ok(HashSet::new())
+        }
+    }
+
+    #[test]
+    fn test_custom_backend() {
+        let storage = Storage::new(Arc::new(ReadOnlyStore));
+        assert_eq!(storage.get::<Doc>("docs", "x").unwrap(), Some(doc("x")));
+        assert!(storage.put("docs", "x", &doc("y")).is_err());
+        assert!(storage.list::<Doc>("docs").is_empty());
+
+        // The update callback was never run
+        let result = storage.update("docs", "x", |_: &mut u32| 1);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_name_key() {
+        assert_eq!(name_key("  Berlin Backend ").unwrap(), "berlin backend");
+        assert!(name_key("   ").is_err());
+    }
+
+    #[test]
+    fn test_now_rfc3339() {
+        let now = now_rfc3339();
+        assert!(OffsetDateTime::parse(&now, &Rfc3339).is_ok());
+    }
+}
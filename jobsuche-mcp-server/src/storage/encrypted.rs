@@ -0,0 +1,412 @@
+//! Encryption at rest
+//!
+//! [`EncryptedStore`] wraps another backend and encrypts every document with
+//! AES-256-GCM before it is written. Documents are bound to their collection
+//! and key, so encrypted values cannot be swapped between entries unnoticed.
+//! Collection names and document keys (reference numbers, lowercase names of
+//! saved searches and watchlists) are stored in plain text; keys that would
+//! reveal personal data are hashed with a secret derived from the store key
+//! (see [`StoreKey::derive`]).
+//!
+//! A marker document records that a store is encrypted and lets a wrong key be
+//! detected at startup instead of on the first read. Documents written before
+//! encryption was enabled are encrypted once when the store is first opened
+//! with a key; after that, plain documents are rejected as tampered.
+
+use super::{JobStore, UpdateFn, ALL_COLLECTIONS};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::info;
+
+/// Prefix of encrypted documents
+const ENVELOPE_PREFIX: &str = "enc:v1:";
+
+/// Collection holding the key check marker
+const ENCRYPTION_COLLECTION: &str = "_encryption";
+
+/// Key of the key check marker
+const KEY_CHECK: &str = "key_check";
+
+/// Plain text of the key check marker
+const KEY_CHECK_VALUE: &str = "jobsuche-mcp-server";
+
+/// Key of the marker recording that plain documents have been encrypted
+const MIGRATED: &str = "migrated";
+
+/// Nonce length of AES-GCM
+const NONCE_LEN: usize = 12;
+
+/// 256-bit key for [`EncryptedStore`]
+#[derive(Clone)]
+pub struct StoreKey([u8; 32]);
+
+impl StoreKey {
+    /// Parse a key given as 64 hex digits or base64 (e.g. from `openssl rand -hex 32`)
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = text.trim();
+        let bytes = if text.len() == 64 && text.chars().all(|c| c.is_ascii_hexdigit()) {
+            hex::decode(text)?
+        } else {
+            BASE64
+                .decode(text)
+                .context("Store key must be 32 bytes as hex or base64")?
+        };
+
+        let key: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Store key must be exactly 32 bytes long"))?;
+        Ok(Self(key))
+    }
+
+    /// Create a random key
+    pub fn generate() -> Self {
+        Self(Aes256Gcm::generate_key(&mut OsRng).into())
+    }
+
+    /// Key as 64 hex digits
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    /// Secret for one `purpose`, such as hashing document keys
+    pub fn derive(&self, purpose: &str) -> [u8; 32] {
+        let mut mac =
+            <Hmac<Sha256> as Mac>::new_from_slice(&self.0).expect("HMAC accepts 32-byte keys");
+        mac.update(purpose.as_bytes());
+        mac.finalize().into_bytes().into()
+    }
+}
+
+impl fmt::Debug for StoreKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StoreKey(..)")
+    }
+}
+
+/// Store that encrypts all documents of an inner store
+pub struct EncryptedStore {
+    inner: Arc<dyn JobStore>,
+    cipher: Aes256Gcm,
+}
+
+impl fmt::Debug for EncryptedStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedStore")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EncryptedStore {
+    /// Wrap `inner`, checking the key against a store encrypted earlier
+    pub fn open(inner: Arc<dyn JobStore>, key: &StoreKey) -> Result<Self> {
+        let store = Self {
+            inner,
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key.0)),
+        };
+
+        match store.inner.get(ENCRYPTION_COLLECTION, KEY_CHECK)? {
+            Some(marker) => {
+                let check = store
+                    .decrypt(ENCRYPTION_COLLECTION, KEY_CHECK, marker)
+                    .context("The store key does not match the encrypted store")?;
+                if check.as_str() != Some(KEY_CHECK_VALUE) {
+                    anyhow::bail!("The store key does not match the encrypted store");
+                }
+            }
+            None => {
+                let marker = store.encrypt(
+                    ENCRYPTION_COLLECTION,
+                    KEY_CHECK,
+                    &Value::from(KEY_CHECK_VALUE),
+                )?;
+                store.inner.put(ENCRYPTION_COLLECTION, KEY_CHECK, marker)?;
+            }
+        }
+
+        if store.inner.get(ENCRYPTION_COLLECTION, MIGRATED)?.is_none() {
+            store.encrypt_plain_documents()?;
+        }
+        Ok(store)
+    }
+
+    /// Encrypt the documents written before encryption was enabled
+    fn encrypt_plain_documents(&self) -> Result<()> {
+        let mut encrypted = 0;
+        for collection in ALL_COLLECTIONS {
+            for (key, document) in self.inner.list(collection)? {
+                if !is_sealed(&document) {
+                    let sealed = self.encrypt(collection, &key, &document)?;
+                    self.inner.put(collection, &key, sealed)?;
+                    encrypted += 1;
+                }
+            }
+        }
+        if encrypted > 0 {
+            info!(
+                "Encrypted {} documents written without a store key",
+                encrypted
+            );
+        }
+
+        let marker = self.encrypt(ENCRYPTION_COLLECTION, MIGRATED, &Value::Bool(true))?;
+        self.inner.put(ENCRYPTION_COLLECTION, MIGRATED, marker)
+    }
+
+    /// Associated data binding a document to its location
+    fn aad(collection: &str, key: &str) -> Vec<u8> {
+        [collection.as_bytes(), b"\0", key.as_bytes()].concat()
+    }
+
+    fn encrypt(&self, collection: &str, key: &str, document: &Value) -> Result<Value> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let plaintext = document.to_string();
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: &Self::aad(collection, key),
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt '{}' in '{}'", key, collection))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(Value::String(format!(
+            "{}{}",
+            ENVELOPE_PREFIX,
+            BASE64.encode(sealed)
+        )))
+    }
+
+    /// Decrypt a document, rejecting documents that are not encrypted
+    fn decrypt(&self, collection: &str, key: &str, document: Value) -> Result<Value> {
+        let sealed = match document {
+            Value::String(ref text) if text.starts_with(ENVELOPE_PREFIX) => {
+                BASE64.decode(&text[ENVELOPE_PREFIX.len()..])?
+            }
+            _ => anyhow::bail!("Document '{}' in '{}' is not encrypted", key, collection),
+        };
+        if sealed.len() < NONCE_LEN {
+            anyhow::bail!(
+                "Encrypted document '{}' in '{}' is truncated",
+                key,
+                collection
+            );
+        }
+
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &Self::aad(collection, key),
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Failed to decrypt '{}' in '{}'", key, collection))?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+}
+
+/// Whether a stored document is an encrypted envelope
+fn is_sealed(document: &Value) -> bool {
+    document
+        .as_str()
+        .is_some_and(|text| text.starts_with(ENVELOPE_PREFIX))
+}
+
+/// Fail if `store` was encrypted but is opened without a key
+pub fn ensure_unencrypted(store: &dyn JobStore) -> Result<()> {
+    if store.get(ENCRYPTION_COLLECTION, KEY_CHECK)?.is_some() {
+        anyhow::bail!("The store is encrypted; set JOBSUCHE_STORE_KEY to open it");
+    }
+    Ok(())
+}
+
+impl JobStore for EncryptedStore {
+    fn get(&self, collection: &str, key: &str) -> Result<Option<Value>> {
+        self.inner
+            .get(collection, key)?
+            .map(|document| self.decrypt(collection, key, document))
+            .transpose()
+    }
+
+    fn put(&self, collection: &str, key: &str, document: Value) -> Result<()> {
+        let sealed = self.encrypt(collection, key, &document)?;
+        self.inner.put(collection, key, sealed)
+    }
+
+    fn update(&self, collection: &str, key: &str, f: UpdateFn<'_>) -> Result<()> {
+        self.inner.update(
+            collection,
+            key,
+            Box::new(|document| {
+                let document = document
+                    .map(|document| self.decrypt(collection, key, document))
+                    .transpose()?;
+                self.encrypt(collection, key, &f(document)?)
+            }),
+        )
+    }
+
+    fn insert_missing(&self, collection: &str, documents: Vec<(String, Value)>) -> Result<usize> {
+        let documents = documents
+            .into_iter()
+            .map(|(key, document)| {
+                let sealed = self.encrypt(collection, &key, &document)?;
+                Ok((key, sealed))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.inner.insert_missing(collection, documents)
+    }
+
+    fn remove(&self, collection: &str, key: &str) -> Result<bool> {
+        self.inner.remove(collection, key)
+    }
+
+    fn remove_many(&self, collection: &str, keys: &[String]) -> Result<usize> {
+        self.inner.remove_many(collection, keys)
+    }
+
+    fn list(&self, collection: &str) -> Result<Vec<(String, Value)>> {
+        self.inner
+            .list(collection)?
+            .into_iter()
+            .map(|(key, document)| {
+                let document = self.decrypt(collection, &key, document)?;
+                Ok((key, document))
+            })
+            .collect()
+    }
+
+    fn keys(&self, collection: &str) -> Result<HashSet<String>> {
+        self.inner.keys(collection)
+    }
+
+    fn backend_name(&self) -> &'static str {
+        self.inner.backend_name()
+    }
+
+    fn location(&self) -> Option<&Path> {
+        self.inner.location()
+    }
+
+    fn disk_usage(&self) -> Option<u64> {
+        self.inner.disk_usage()
+    }
+
+    fn compact(&self) -> Result<()> {
+        self.inner.compact()
+    }
+}
+
+/// Read the store key from the OS keychain, creating a random key on first use
+#[cfg(feature = "keychain")]
+pub fn keychain_key() -> Result<StoreKey> {
+    let entry = keyring::Entry::new("jobsuche-mcp-server", "store-key")?;
+    match entry.get_password() {
+        Ok(key) => StoreKey::parse(&key).context("Invalid store key in the OS keychain"),
+        Err(keyring::Error::NoEntry) => {
+            let key = StoreKey::generate();
+            entry.set_password(&key.to_hex())?;
+            tracing::info!("Created a new store key in the OS keychain");
+            Ok(key)
+        }
+        Err(e) => Err(e).context("Failed to read the store key from the OS keychain"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{check_backend, doc, Doc};
+    use super::super::{MemoryStore, Storage, JOB_NOTES};
+    use super::*;
+
+    fn key() -> StoreKey {
+        StoreKey::parse(&"ab".repeat(32)).unwrap()
+    }
+
+    #[test]
+    fn test_parse_store_key() {
+        assert_eq!(key().to_hex(), "ab".repeat(32));
+        let base64 = BASE64.encode([7u8; 32]);
+        assert_eq!(StoreKey::parse(&base64).unwrap().0, [7u8; 32]);
+        assert!(StoreKey::parse("too short").is_err());
+        assert!(StoreKey::parse(&BASE64.encode([7u8; 16])).is_err());
+        assert_eq!(format!("{:?}", key()), "StoreKey(..)");
+    }
+
+    #[test]
+    fn test_encrypted_backend() {
+        let inner: Arc<dyn JobStore> = Arc::new(MemoryStore::new());
+        let store = EncryptedStore::open(inner.clone(), &key()).unwrap();
+        check_backend(&Storage::new(Arc::new(store)));
+
+        // Nothing readable is left in the inner store
+        let (_, sealed) = &inner.list("docs").unwrap()[0];
+        assert!(sealed.as_str().unwrap().starts_with(ENVELOPE_PREFIX));
+        assert!(!sealed.to_string().contains("replaced"));
+    }
+
+    #[test]
+    fn test_wrong_or_missing_key_is_rejected() {
+        let inner: Arc<dyn JobStore> = Arc::new(MemoryStore::new());
+        assert!(ensure_unencrypted(inner.as_ref()).is_ok());
+        EncryptedStore::open(inner.clone(), &key()).unwrap();
+
+        assert!(EncryptedStore::open(inner.clone(), &StoreKey::generate()).is_err());
+        assert!(ensure_unencrypted(inner.as_ref()).is_err());
+        assert!(EncryptedStore::open(inner, &key()).is_ok());
+    }
+
+    #[test]
+    fn test_documents_are_bound_to_their_key() {
+        let inner: Arc<dyn JobStore> = Arc::new(MemoryStore::new());
+        let storage = Storage::new(Arc::new(
+            EncryptedStore::open(inner.clone(), &key()).unwrap(),
+        ));
+        storage.put("docs", "a", &doc("secret")).unwrap();
+
+        let sealed = inner.get("docs", "a").unwrap().unwrap();
+        inner.put("docs", "b", sealed).unwrap();
+        assert!(storage.get::<Doc>("docs", "b").is_err());
+    }
+
+    #[test]
+    fn test_plain_documents_are_encrypted_on_open() {
+        let inner: Arc<dyn JobStore> = Arc::new(MemoryStore::new());
+        inner
+            .put(JOB_NOTES, "a", serde_json::json!({"name": "plain"}))
+            .unwrap();
+
+        let storage = Storage::new(Arc::new(
+            EncryptedStore::open(inner.clone(), &key()).unwrap(),
+        ));
+        assert!(is_sealed(&inner.get(JOB_NOTES, "a").unwrap().unwrap()));
+        assert_eq!(
+            storage.get::<Doc>(JOB_NOTES, "a").unwrap(),
+            Some(doc("plain"))
+        );
+
+        // Plain documents appearing later were not written by this store
+        inner
+            .put(JOB_NOTES, "b", serde_json::json!({"name": "injected"}))
+            .unwrap();
+        let storage = Storage::new(Arc::new(EncryptedStore::open(inner, &key()).unwrap()));
+        assert!(storage.get::<Doc>(JOB_NOTES, "b").is_err());
+    }
+}
@@ -0,0 +1,170 @@
+//! JSON file store backend
+
+use super::memory::StoreData;
+use super::{JobStore, UpdateFn};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::info;
+
+/// File name of the store inside the data directory
+pub const STORE_FILE_NAME: &str = "store.json";
+
+/// Store that keeps all documents in memory and writes them to a JSON file on
+/// every change
+#[derive(Debug)]
+pub struct JsonFileStore {
+    path: PathBuf,
+    data: Mutex<StoreData>,
+}
+
+impl JsonFileStore {
+    /// Open the store in `data_dir`, creating the directory if needed
+    pub fn open(data_dir: &Path) -> Result<Self> {
+        fs::create_dir_all(data_dir)
+            .with_context(|| format!("Failed to create data directory {}", data_dir.display()))?;
+
+        let path = data_dir.join(STORE_FILE_NAME);
+        let data = if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read store {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Store {} is corrupted", path.display()))?
+        } else {
+            StoreData::default()
+        };
+
+        info!("Opened local store at {}", path.display());
+        Ok(Self {
+            path,
+            data: Mutex::new(data),
+        })
+    }
+
+    /// Write the store to disk
+    fn persist(&self, data: &StoreData) -> Result<()> {
+        let tmp_path = self.path.with_extension("json.tmp");
+        let content = serde_json::to_string_pretty(data)?;
+        fs::write(&tmp_path, content)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("Failed to replace {}", self.path.display()))?;
+        Ok(())
+    }
+}
+
+impl JobStore for JsonFileStore {
+    fn get(&self, collection: &str, key: &str) -> Result<Option<Value>> {
+        Ok(self.data.lock().unwrap().get(collection, key))
+    }
+
+    fn put(&self, collection: &str, key: &str, document: Value) -> Result<()> {
+        let mut data = self.data.lock().unwrap();
+        data.put(collection, key, document);
+        self.persist(&data)
+    }
+
+    fn update(&self, collection: &str, key: &str, f: UpdateFn<'_>) -> Result<()> {
+        let mut data = self.data.lock().unwrap();
+        data.update(collection, key, f)?;
+        self.persist(&data)
+    }
+
+    fn insert_missing(&self, collection: &str, documents: Vec<(String, Value)>) -> Result<usize> {
+        let mut data = self.data.lock().unwrap();
+        let inserted = data.insert_missing(collection, documents);
+        if inserted > 0 {
+            self.persist(&data)?;
+        }
+        Ok(inserted)
+    }
+
+    fn remove(&self, collection: &str, key: &str) -> Result<bool> {
+        let mut data = self.data.lock().unwrap();
+        let removed = data.remove(collection, key);
+        if removed {
+            self.persist(&data)?;
+        }
+        Ok(removed)
+    }
+
+    fn remove_many(&self, collection: &str, keys: &[String]) -> Result<usize> {
+        let mut data = self.data.lock().unwrap();
+        let removed = keys
+            .iter()
+            .filter(|key| data.remove(collection, key))
+            .count();
+        if removed > 0 {
+            self.persist(&data)?;
+        }
+        Ok(removed)
+    }
+
+    fn list(&self, collection: &str) -> Result<Vec<(String, Value)>> {
+        Ok(self.data.lock().unwrap().list(collection))
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "json"
+    }
+
+    fn keys(&self, collection: &str) -> Result<HashSet<String>> {
+        Ok(self.data.lock().unwrap().keys(collection))
+    }
+
+    fn location(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+
+    fn disk_usage(&self) -> Option<u64> {
+        fs::metadata(&self.path).ok().map(|metadata| metadata.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{check_backend, doc, temp_dir, Doc};
+    use super::super::{Storage, FAVORITES};
+    use super::*;
+
+    #[test]
+    fn test_json_backend() {
+        let dir = temp_dir("json");
+        check_backend(&Storage::open(&dir).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_persists_across_reopen() {
+        let dir = temp_dir("reopen");
+
+        let storage = Storage::open(&dir).unwrap();
+        assert_eq!(storage.path(), Some(dir.join(STORE_FILE_NAME).as_path()));
+        storage.put(FAVORITES, "REF-1", &doc("kept")).unwrap();
+        drop(storage);
+
+        let reopened = Storage::open(&dir).unwrap();
+        assert_eq!(
+            reopened.get::<Doc>(FAVORITES, "REF-1").unwrap(),
+            Some(doc("kept"))
+        );
+        assert!(!dir.join("store.json.tmp").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_open_corrupted_store_fails() {
+        let dir = temp_dir("corrupted");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(STORE_FILE_NAME), "{not json").unwrap();
+
+        let result = Storage::open(&dir);
+        assert!(result.unwrap_err().to_string().contains("corrupted"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+//! In-memory store backend
+
+use super::{JobStore, UpdateFn};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+
+/// All collections of a store; also the on-disk layout of [`JsonFileStore`](super::JsonFileStore)
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct StoreData {
+    /// Collection name -> document key -> document
+    #[serde(default)]
+    collections: BTreeMap<String, BTreeMap<String, Value>>,
+}
+
+impl StoreData {
+    pub(super) fn get(&self, collection: &str, key: &str) -> Option<Value> {
+        self.collections
+            .get(collection)
+            .and_then(|documents| documents.get(key))
+            .cloned()
+    }
+
+    pub(super) fn put(&mut self, collection: &str, key: &str, document: Value) {
+        self.collections
+            .entry(collection.to_string())
+            .or_default()
+            .insert(key.to_string(), document);
+    }
+
+    pub(super) fn update(&mut self, collection: &str, key: &str, f: UpdateFn<'_>) -> Result<()> {
+        let document = f(self.get(collection, key))?;
+        self.put(collection, key, document);
+        Ok(())
+    }
+
+    pub(super) fn insert_missing(
+        &mut self,
+        collection: &str,
+        documents: Vec<(String, Value)>,
+    ) -> usize {
+        let target = self.collections.entry(collection.to_string()).or_default();
+
+        let mut inserted = 0;
+        for (key, document) in documents {
+            if let Entry::Vacant(entry) = target.entry(key) {
+                entry.insert(document);
+                inserted += 1;
+            }
+        }
+        inserted
+    }
+
+    pub(super) fn remove(&mut self, collection: &str, key: &str) -> bool {
+        self.collections
+            .get_mut(collection)
+            .and_then(|documents| documents.remove(key))
+            .is_some()
+    }
+
+    pub(super) fn list(&self, collection: &str) -> Vec<(String, Value)> {
+        self.collections
+            .get(collection)
+            .map(|documents| {
+                documents
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub(super) fn keys(&self, collection: &str) -> HashSet<String> {
+        self.collections
+            .get(collection)
+            .map(|documents| documents.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Store that keeps all documents in memory
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    data: Mutex<StoreData>,
+}
+
+impl MemoryStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl JobStore for MemoryStore {
+    fn get(&self, collection: &str, key: &str) -> Result<Option<Value>> {
+        Ok(self.data.lock().unwrap().get(collection, key))
+    }
+
+    fn put(&self, collection: &str, key: &str, document: Value) -> Result<()> {
+        self.data.lock().unwrap().put(collection, key, document);
+        Ok(())
+    }
+
+    fn update(&self, collection: &str, key: &str, f: UpdateFn<'_>) -> Result<()> {
+        self.data.lock().unwrap().update(collection, key, f)
+    }
+
+    fn insert_missing(&self, collection: &str, documents: Vec<(String, Value)>) -> Result<usize> {
+        Ok(self
+            .data
+            .lock()
+            .unwrap()
+            .insert_missing(collection, documents))
+    }
+
+    fn remove(&self, collection: &str, key: &str) -> Result<bool> {
+        Ok(self.data.lock().unwrap().remove(collection, key))
+    }
+
+    fn list(&self, collection: &str) -> Result<Vec<(String, Value)>> {
+        Ok(self.data.lock().unwrap().list(collection))
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn keys(&self, collection: &str) -> Result<HashSet<String>> {
+        Ok(self.data.lock().unwrap().keys(collection))
+    }
+}
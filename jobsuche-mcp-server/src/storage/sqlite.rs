@@ -0,0 +1,290 @@
+//! SQLite store backend
+
+use super::{CollectionSize, JobStore, UpdateFn};
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// Schema migrations, applied in order
+///
+/// The number of applied migrations is kept in `PRAGMA user_version`.
+const MIGRATIONS: &[&str] = &["CREATE TABLE documents (
+        collection TEXT NOT NULL,
+        key TEXT NOT NULL,
+        value TEXT NOT NULL,
+        PRIMARY KEY (collection, key)
+    ) WITHOUT ROWID;"];
+
+/// Store backed by an SQLite database
+#[derive(Debug)]
+pub struct SqliteStore {
+    path: PathBuf,
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    /// Open (or create) the database at `path` and upgrade its schema
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+
+        let mut conn = Connection::open(path)
+            .with_context(|| format!("Failed to open database {}", path.display()))?;
+        let journal_mode: String =
+            conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))?;
+        if !journal_mode.eq_ignore_ascii_case("wal") {
+            warn!("Database {} does not support WAL mode", path.display());
+        }
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        migrate(&mut conn)
+            .with_context(|| format!("Failed to migrate database {}", path.display()))?;
+
+        info!("Opened SQLite store at {}", path.display());
+        Ok(Self {
+            path: path.to_path_buf(),
+            conn: Mutex::new(conn),
+        })
+    }
+}
+
+impl JobStore for SqliteStore {
+    fn get(&self, collection: &str, key: &str) -> Result<Option<Value>> {
+        select_document(&self.conn.lock().unwrap(), collection, key)
+    }
+
+    fn put(&self, collection: &str, key: &str, document: Value) -> Result<()> {
+        upsert_document(&self.conn.lock().unwrap(), collection, key, &document)
+    }
+
+    fn update(&self, collection: &str, key: &str, f: UpdateFn<'_>) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        let document = f(select_document(&tx, collection, key)?)?;
+        upsert_document(&tx, collection, key, &document)?;
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn insert_missing(&self, collection: &str, documents: Vec<(String, Value)>) -> Result<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        let mut inserted = 0;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR IGNORE INTO documents (collection, key, value) VALUES (?1, ?2, ?3)",
+            )?;
+            for (key, document) in documents {
+                inserted += insert.execute(params![collection, key, document.to_string()])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    fn remove(&self, collection: &str, key: &str) -> Result<bool> {
+        let deleted = self.conn.lock().unwrap().execute(
+            "DELETE FROM documents WHERE collection = ?1 AND key = ?2",
+            params![collection, key],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    fn remove_many(&self, collection: &str, keys: &[String]) -> Result<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        let mut removed = 0;
+        {
+            let mut delete =
+                tx.prepare("DELETE FROM documents WHERE collection = ?1 AND key = ?2")?;
+            for key in keys {
+                removed += delete.execute(params![collection, key])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    fn list(&self, collection: &str) -> Result<Vec<(String, Value)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt =
+            conn.prepare("SELECT key, value FROM documents WHERE collection = ?1 ORDER BY key")?;
+        let rows = stmt.query_map(params![collection], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut documents = Vec::new();
+        for row in rows {
+            let (key, value) = row?;
+            let value = serde_json::from_str(&value)
+                .with_context(|| format!("Invalid document '{}' in '{}'", key, collection))?;
+            documents.push((key, value));
+        }
+        Ok(documents)
+    }
+
+    fn collection_size(&self, collection: &str) -> Result<CollectionSize> {
+        let conn = self.conn.lock().unwrap();
+        let (documents, bytes): (i64, i64) = conn.query_row(
+            "SELECT count(*), coalesce(sum(length(value)), 0) FROM documents WHERE collection = ?1",
+            params![collection],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(CollectionSize {
+            documents: documents as usize,
+            bytes: bytes as u64,
+        })
+    }
+
+    fn backend_name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn keys(&self, collection: &str) -> Result<HashSet<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT key FROM documents WHERE collection = ?1")?;
+        let keys = stmt
+            .query_map(params![collection], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(keys)
+    }
+
+    fn location(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+
+    fn disk_usage(&self) -> Option<u64> {
+        Some(database_size(&self.path))
+    }
+
+    fn compact(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute_batch("VACUUM;")?;
+        // Truncate the write-ahead log as well
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        Ok(())
+    }
+}
+
+/// Size of an SQLite database including its write-ahead log
+pub(crate) fn database_size(path: &Path) -> u64 {
+    ["", "-wal", "-shm"]
+        .iter()
+        .filter_map(|suffix| {
+            let mut file = path.as_os_str().to_owned();
+            file.push(suffix);
+            fs::metadata(file).ok()
+        })
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Apply pending schema migrations
+fn migrate(conn: &mut Connection) -> Result<()> {
+    let version: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version > MIGRATIONS.len() {
+        anyhow::bail!(
+            "Database schema version {} is newer than supported ({})",
+            version,
+            MIGRATIONS.len()
+        );
+    }
+
+    let tx = conn.transaction()?;
+    for (idx, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        info!("Applying database migration {}", idx + 1);
+        tx.execute_batch(migration)?;
+    }
+    tx.pragma_update(None, "user_version", MIGRATIONS.len())?;
+    tx.commit()?;
+    Ok(())
+}
+
+fn select_document(conn: &Connection, collection: &str, key: &str) -> Result<Option<Value>> {
+    let value: Option<String> = conn
+        .query_row(
+            "SELECT value FROM documents WHERE collection = ?1 AND key = ?2",
+            params![collection, key],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    value
+        .map(|value| serde_json::from_str(&value))
+        .transpose()
+        .with_context(|| format!("Invalid document '{}' in '{}'", key, collection))
+}
+
+fn upsert_document(conn: &Connection, collection: &str, key: &str, value: &Value) -> Result<()> {
+    conn.execute(
+        "INSERT INTO documents (collection, key, value) VALUES (?1, ?2, ?3)
+         ON CONFLICT (collection, key) DO UPDATE SET value = excluded.value",
+        params![collection, key, value.to_string()],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{check_backend, doc, temp_dir, Doc};
+    use super::super::Storage;
+    use super::*;
+
+    #[test]
+    fn test_sqlite_backend_and_reopen() {
+        let dir = temp_dir("sqlite");
+        let db_path = dir.join("nested").join("jobsuche.db");
+
+        let storage = Storage::open_sqlite(&db_path).unwrap();
+        assert_eq!(storage.path(), Some(db_path.as_path()));
+        check_backend(&storage);
+        drop(storage);
+
+        let reopened = Storage::open_sqlite(&db_path).unwrap();
+        assert_eq!(
+            reopened.list::<Doc>("docs"),
+            vec![doc("replaced"), doc("second")]
+        );
+        assert_eq!(reopened.get::<u32>("counters", "calls").unwrap(), Some(3));
+        assert!(reopened.disk_usage().unwrap() > 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_sqlite_uses_wal_and_records_schema_version() {
+        let dir = temp_dir("sqlite-wal");
+        let db_path = dir.join("jobsuche.db");
+        drop(SqliteStore::open(&db_path).unwrap());
+
+        let conn = Connection::open(&db_path).unwrap();
+        let journal_mode: String = conn
+            .pragma_query_value(None, "journal_mode", |row| row.get(0))
+            .unwrap();
+        let version: usize = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(journal_mode, "wal");
+        assert_eq!(version, MIGRATIONS.len());
+
+        // A database from a newer server version is rejected
+        conn.pragma_update(None, "user_version", 99).unwrap();
+        drop(conn);
+        assert!(SqliteStore::open(&db_path).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
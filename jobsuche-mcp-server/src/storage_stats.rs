@@ -0,0 +1,119 @@
+//! Storage statistics
+//!
+//! Reports what the server keeps locally, per collection and for the full-text
+//! index, so users can see how much data has accumulated and whether a
+//! compact_storage run is worthwhile.
+
+use crate::local_search::JobIndex;
+use crate::storage::{Storage, ALL_COLLECTIONS};
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Parameters for get_storage_stats
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetStorageStatsParams {}
+
+/// Size of one collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CollectionStats {
+    /// Collection name (e.g. "favorites", "job_archive")
+    pub name: String,
+
+    /// Number of documents
+    pub documents: usize,
+
+    /// Size of the documents as compact JSON
+    pub bytes: u64,
+}
+
+/// Result from get_storage_stats
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GetStorageStatsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Storage backend ("memory", "json", "sqlite" or "custom")
+    pub backend: String,
+
+    /// Store file or database, if persisted
+    pub location: Option<String>,
+
+    /// Bytes used on disk by the store, if persisted
+    pub disk_bytes: Option<u64>,
+
+    /// Documents per collection
+    pub collections: Vec<CollectionStats>,
+
+    /// Jobs in the full-text index
+    pub indexed_jobs: usize,
+
+    /// Bytes used on disk by the full-text index, if persisted
+    pub index_disk_bytes: Option<u64>,
+
+    /// Retention period in days (0: data is kept forever)
+    pub retention_days: u64,
+}
+
+/// Collect statistics for the store and the full-text index
+pub fn storage_stats(
+    storage: &Storage,
+    index: &JobIndex,
+    retention_days: u64,
+) -> Result<GetStorageStatsResult> {
+    let collections = ALL_COLLECTIONS
+        .iter()
+        .map(|name| {
+            let size = storage.collection_size(name)?;
+            Ok(CollectionStats {
+                name: name.to_string(),
+                documents: size.documents,
+                bytes: size.bytes,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(GetStorageStatsResult {
+        request_id: String::new(),
+        backend: storage.backend_name().to_string(),
+        location: storage.path().map(|path| path.display().to_string()),
+        disk_bytes: storage.disk_usage(),
+        collections,
+        indexed_jobs: index.len()?,
+        index_disk_bytes: index.disk_usage(),
+        retention_days,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::FAVORITES;
+
+    #[test]
+    fn test_storage_stats() {
+        let storage = Storage::in_memory();
+        let index = JobIndex::in_memory().unwrap();
+        storage.put(FAVORITES, "A", &serde_json::json!({})).unwrap();
+        storage
+            .put(FAVORITES, "B", &serde_json::json!([1]))
+            .unwrap();
+
+        let stats = storage_stats(&storage, &index, 180).unwrap();
+        assert_eq!(stats.backend, "memory");
+        assert_eq!(stats.location, None);
+        assert_eq!(stats.disk_bytes, None);
+        assert_eq!(stats.collections.len(), ALL_COLLECTIONS.len());
+        assert_eq!(
+            stats.collections[0],
+            CollectionStats {
+                name: "favorites".to_string(),
+                documents: 2,
+                bytes: 5,
+            }
+        );
+        assert_eq!(stats.indexed_jobs, 0);
+    }
+}
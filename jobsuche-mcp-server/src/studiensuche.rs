@@ -0,0 +1,375 @@
+//! Degree programs from the Studiensuche
+//!
+//! School leavers often weigh a degree against an apprenticeship. The
+//! Studiensuche of the Bundesagentur für Arbeit lists the study programs of
+//! German universities with degree, place and study form, including dual
+//! programs that combine a degree with training in a company. Degree type and
+//! dual study are filtered on the returned page, like the filters of the
+//! Weiterbildungssuche.
+
+use crate::weiterbildung::{first_text, place};
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Default base URL of the Studiensuche API
+pub const DEFAULT_STUDIENSUCHE_URL: &str =
+    "https://rest.arbeitsagentur.de/infosysbub/studisu/pc/v1";
+
+/// Public API key of the Studiensuche web application
+const API_KEY: &str = "infosysbub-studisu";
+
+/// Default search radius in km around the location
+pub const DEFAULT_STUDY_RADIUS_KM: u64 = 50;
+
+/// Default number of programs per page
+pub const DEFAULT_DEGREE_PROGRAMS: u64 = 20;
+
+/// Maximum number of programs per page
+pub const MAX_DEGREE_PROGRAMS: u64 = 100;
+
+/// Type of degree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DegreeType {
+    /// Bachelor (B.A., B.Sc., B.Eng., LL.B., …)
+    Bachelor,
+    /// Master (M.A., M.Sc., M.Eng., …)
+    Master,
+    /// Staatsexamen (medicine, law, teaching, pharmacy)
+    Staatsexamen,
+    /// Any other degree, e.g. Diplom, Magister or a church exam
+    Other,
+}
+
+impl DegreeType {
+    /// Classify the degree name of a program
+    pub fn of(degree: &str) -> Self {
+        let degree = degree.to_lowercase();
+        if degree.contains("bachelor") || degree.starts_with("b.") {
+            DegreeType::Bachelor
+        } else if degree.contains("master") || degree.starts_with("m.") {
+            DegreeType::Master
+        } else if degree.contains("staatsexamen") || degree.contains("staatsprüfung") {
+            DegreeType::Staatsexamen
+        } else {
+            DegreeType::Other
+        }
+    }
+}
+
+/// Parameters for search_degree_programs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SearchDegreeProgramsParams {
+    /// Subject (e.g. "Informatik", "Soziale Arbeit", "Maschinenbau")
+    pub subject: String,
+
+    /// City or postal code; without it programs from all of Germany are found
+    pub location: Option<String>,
+
+    /// Search radius in km around the location (default: 50)
+    pub radius_km: Option<u64>,
+
+    /// Only programs leading to this degree
+    pub degree_type: Option<DegreeType>,
+
+    /// Only dual study programs that combine the degree with training in a
+    /// company (default: false)
+    pub dual_only: Option<bool>,
+
+    /// Programs per page (default: 20, max: 100)
+    pub page_size: Option<u64>,
+
+    /// Page number, starting at 1
+    pub page: Option<u64>,
+}
+
+/// A study program
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DegreeProgram {
+    /// Program ID of the Studiensuche
+    pub program_id: Option<String>,
+
+    /// Name of the program
+    pub title: Option<String>,
+
+    /// University or college
+    pub university: Option<String>,
+
+    /// Place of study ("Aachen (52062)")
+    pub location: Option<String>,
+
+    /// Degree as named by the university (e.g. "Bachelor of Science")
+    pub degree: Option<String>,
+
+    /// Classified degree
+    pub degree_type: Option<DegreeType>,
+
+    /// Study form such as "Vollzeitstudium", "Teilzeitstudium" or "Duales
+    /// Studium"
+    pub study_form: Option<String>,
+
+    /// Whether the program is a dual study program
+    pub dual: bool,
+
+    /// Standard period of study (e.g. "6 Semester")
+    pub standard_period: Option<String>,
+
+    /// Admission restriction (e.g. "örtlich zulassungsbeschränkt")
+    pub admission: Option<String>,
+}
+
+/// Result from search_degree_programs
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchDegreeProgramsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Total number of programs found (before the degree and dual filters)
+    pub total_results: Option<u64>,
+
+    /// Current page number
+    pub current_page: u64,
+
+    /// Number of programs returned
+    pub programs_count: usize,
+
+    /// Programs of this page (after the degree and dual filters)
+    pub programs: Vec<DegreeProgram>,
+}
+
+/// Convert one program of a Studiensuche response
+fn parse_program(entry: &Value) -> DegreeProgram {
+    // Entries either wrap the program in `studienangebot` or are the program
+    let program = entry.get("studienangebot").unwrap_or(entry);
+    let degree = first_text(
+        program,
+        &["/abschlussgrad/bezeichnung", "/abschlussgrad", "/abschluss"],
+    );
+    let study_form = first_text(program, &["/studienform/bezeichnung", "/studienform"]);
+    let dual = study_form
+        .iter()
+        .chain(first_text(program, &["/studiBezeichnung", "/titel"]).iter())
+        .any(|text| text.to_lowercase().contains("dual"));
+
+    DegreeProgram {
+        program_id: program
+            .get("id")
+            .filter(|id| !id.is_null())
+            .map(|id| id.to_string().trim_matches('"').to_string()),
+        title: first_text(program, &["/studiBezeichnung", "/titel"]),
+        university: first_text(program, &["/hochschule/name", "/hochschule"]),
+        location: place(program, "/studienort").or_else(|| place(program, "/adresse")),
+        degree_type: degree.as_deref().map(DegreeType::of),
+        degree,
+        study_form,
+        dual,
+        standard_period: first_text(
+            program,
+            &["/regelstudienzeit/bezeichnung", "/regelstudienzeit"],
+        ),
+        admission: first_text(
+            program,
+            &["/zulassungsmodus/bezeichnung", "/zulassungsmodus"],
+        ),
+    }
+}
+
+/// Extract the programs and the total count from a response
+fn parse_programs(response: &Value) -> (Vec<DegreeProgram>, Option<u64>) {
+    let programs = response
+        .get("items")
+        .or_else(|| response.pointer("/_embedded/studienangebote"))
+        .and_then(Value::as_array)
+        .map(|entries| entries.iter().map(parse_program).collect())
+        .unwrap_or_default();
+    let total = response
+        .get("maxErgebnisse")
+        .or_else(|| response.pointer("/page/totalElements"))
+        .and_then(Value::as_u64);
+    (programs, total)
+}
+
+/// Whether a program passes the degree and dual filters
+pub fn matches_filters(
+    program: &DegreeProgram,
+    degree_type: Option<DegreeType>,
+    dual_only: bool,
+) -> bool {
+    degree_type.is_none_or(|wanted| program.degree_type == Some(wanted))
+        && (!dual_only || program.dual)
+}
+
+/// Client for the Studiensuche API
+#[derive(Debug, Clone)]
+pub struct StudiensucheClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl StudiensucheClient {
+    /// Create a client for the given base URL
+    pub fn new(base_url: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .context("Failed to create Studiensuche client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Search study programs
+    pub async fn search(
+        &self,
+        params: &SearchDegreeProgramsParams,
+    ) -> Result<SearchDegreeProgramsResult> {
+        if params.subject.trim().is_empty() {
+            anyhow::bail!("subject must not be empty");
+        }
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params
+            .page_size
+            .unwrap_or(DEFAULT_DEGREE_PROGRAMS)
+            .clamp(1, MAX_DEGREE_PROGRAMS);
+
+        let mut query = vec![
+            ("sw", params.subject.trim().to_string()),
+            // Unlike the Weiterbildungssuche, the Studiensuche counts pages from 1
+            ("pg", page.to_string()),
+            ("size", page_size.to_string()),
+        ];
+        if let Some(ref location) = params.location {
+            query.push(("orte", location.trim().to_string()));
+            let radius_km = params.radius_km.unwrap_or(DEFAULT_STUDY_RADIUS_KM);
+            query.push(("uk", radius_km.to_string()));
+        } else {
+            query.push(("uk", "Bundesweit".to_string()));
+        }
+
+        let url = format!("{}/studienangebote", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .header("X-API-Key", API_KEY)
+            .query(&query)
+            .send()
+            .await
+            .context("Studiensuche request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("Studiensuche responded with HTTP {}", status);
+        }
+        let body: Value = response
+            .json()
+            .await
+            .context("Invalid Studiensuche response")?;
+
+        let dual_only = params.dual_only.unwrap_or(false);
+        let (programs, total_results) = parse_programs(&body);
+        let programs: Vec<DegreeProgram> = programs
+            .into_iter()
+            .filter(|program| matches_filters(program, params.degree_type, dual_only))
+            .collect();
+        Ok(SearchDegreeProgramsResult {
+            request_id: String::new(),
+            total_results,
+            current_page: page,
+            programs_count: programs.len(),
+            programs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn response() -> Value {
+        json!({
+            "maxErgebnisse": 214,
+            "items": [
+                {"studienangebot": {
+                    "id": 8723,
+                    "studiBezeichnung": "Informatik (B.Sc.)",
+                    "hochschule": {"name": "RWTH Aachen"},
+                    "studienort": {"ort": "Aachen", "plz": "52062"},
+                    "abschlussgrad": {"bezeichnung": "Bachelor of Science"},
+                    "studienform": {"bezeichnung": "Vollzeitstudium"},
+                    "regelstudienzeit": {"bezeichnung": "6 Semester"},
+                    "zulassungsmodus": {"bezeichnung": "zulassungsfrei"}
+                }},
+                {"studienangebot": {
+                    "id": "X-17",
+                    "studiBezeichnung": "Informatik - dual",
+                    "hochschule": {"name": "DHBW Stuttgart"},
+                    "abschlussgrad": "Bachelor of Science",
+                    "studienform": {"bezeichnung": "Duales Studium"}
+                }},
+                {"studienangebot": {
+                    "studiBezeichnung": "Informatik",
+                    "abschlussgrad": {"bezeichnung": "Master of Science"}
+                }}
+            ]
+        })
+    }
+
+    #[test]
+    fn test_degree_type() {
+        assert_eq!(DegreeType::of("Bachelor of Arts"), DegreeType::Bachelor);
+        assert_eq!(DegreeType::of("M.Eng."), DegreeType::Master);
+        assert_eq!(
+            DegreeType::of("Staatsexamen (Lehramt)"),
+            DegreeType::Staatsexamen
+        );
+        assert_eq!(DegreeType::of("Diplom"), DegreeType::Other);
+    }
+
+    #[test]
+    fn test_parse_programs() {
+        let (programs, total) = parse_programs(&response());
+        assert_eq!(total, Some(214));
+        assert_eq!(
+            programs[0],
+            DegreeProgram {
+                program_id: Some("8723".to_string()),
+                title: Some("Informatik (B.Sc.)".to_string()),
+                university: Some("RWTH Aachen".to_string()),
+                location: Some("Aachen (52062)".to_string()),
+                degree: Some("Bachelor of Science".to_string()),
+                degree_type: Some(DegreeType::Bachelor),
+                study_form: Some("Vollzeitstudium".to_string()),
+                dual: false,
+                standard_period: Some("6 Semester".to_string()),
+                admission: Some("zulassungsfrei".to_string()),
+            }
+        );
+        assert_eq!(programs[1].program_id.as_deref(), Some("X-17"));
+        assert!(programs[1].dual);
+        assert_eq!(parse_programs(&json!({})), (Vec::new(), None));
+    }
+
+    #[test]
+    fn test_matches_filters() {
+        let (programs, _) = parse_programs(&response());
+        assert!(matches_filters(&programs[0], None, false));
+        assert!(!matches_filters(&programs[0], None, true));
+        assert!(matches_filters(
+            &programs[1],
+            Some(DegreeType::Bachelor),
+            true
+        ));
+        assert!(!matches_filters(
+            &programs[2],
+            Some(DegreeType::Bachelor),
+            false
+        ));
+    }
+}
@@ -0,0 +1,399 @@
+//! Suggestions from the Jobbörse autocomplete
+//!
+//! Users often type colloquial or misspelled job titles ("Kassierer",
+//! "Softwareentwickler m/w/d"), which the search matches less precisely than
+//! the occupation names of the Bundesagentur für Arbeit. The autocomplete of
+//! the Jobbörse suggests canonical occupation names for a partial string. It is
+//! served by the same service as the job search and uses the same API key.
+//!
+//! The same goes for places: a misspelled town makes a search return nothing
+//! without saying why. The place autocomplete resolves partial names and postal
+//! codes to places with postal code and state. Should it be unreachable, the
+//! cities offered for argument completion are matched instead.
+
+use crate::completion::complete_argument;
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+use tracing::warn;
+
+/// Public API key of the Jobbörse, used unless `JOBSUCHE_API_KEY` is set
+const DEFAULT_API_KEY: &str = "jobboerse-jobsuche";
+
+/// Minimum length of a query
+const MIN_QUERY_LENGTH: usize = 2;
+
+/// Default number of suggestions
+pub const DEFAULT_SUGGESTIONS: u64 = 10;
+
+/// Maximum number of suggestions
+pub const MAX_SUGGESTIONS: u64 = 50;
+
+/// Parameters for suggest_job_titles
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SuggestJobTitlesParams {
+    /// Partial or colloquial job title (at least 2 characters, e.g. "Kassier",
+    /// "Softwareentw")
+    pub query: String,
+
+    /// Maximum number of suggestions (default: 10, max: 50)
+    pub limit: Option<u64>,
+}
+
+/// A canonical occupation name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct JobTitleSuggestion {
+    /// Occupation name, usable as `job_title` in search_jobs
+    pub title: String,
+
+    /// Occupation code (DKZ), usable with get_salary_statistics
+    pub occupation_code: Option<String>,
+}
+
+/// Result from suggest_job_titles
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SuggestJobTitlesResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Query as searched
+    pub query: String,
+
+    /// Suggestions, best match first
+    pub suggestions: Vec<JobTitleSuggestion>,
+}
+
+/// Parameters for suggest_locations
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SuggestLocationsParams {
+    /// Partial place name or postal code (at least 2 characters, e.g. "Frankf",
+    /// "803")
+    pub query: String,
+
+    /// Maximum number of suggestions (default: 10, max: 50)
+    pub limit: Option<u64>,
+}
+
+/// A place
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LocationSuggestion {
+    /// Place name, usable as `location` in search_jobs
+    pub name: String,
+
+    /// Postal code (PLZ)
+    pub postal_code: Option<String>,
+
+    /// Federal state (Bundesland)
+    pub state: Option<String>,
+}
+
+/// Where location suggestions come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LocationSource {
+    /// Place autocomplete of the Jobbörse
+    Api,
+    /// Built-in list of larger cities (names only), used when the API fails
+    Local,
+}
+
+/// Result from suggest_locations
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SuggestLocationsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Query as searched
+    pub query: String,
+
+    /// Where the suggestions come from
+    pub source: LocationSource,
+
+    /// Suggestions, best match first
+    pub suggestions: Vec<LocationSuggestion>,
+}
+
+/// Validate a query and the number of suggestions
+fn query_and_limit(query: &str, limit: Option<u64>) -> Result<(&str, usize)> {
+    let query = query.trim();
+    if query.chars().count() < MIN_QUERY_LENGTH {
+        anyhow::bail!("query must have at least {} characters", MIN_QUERY_LENGTH);
+    }
+    let limit = limit
+        .unwrap_or(DEFAULT_SUGGESTIONS)
+        .clamp(1, MAX_SUGGESTIONS);
+    Ok((query, limit as usize))
+}
+
+/// String field of an entry, trying several keys
+fn field(entry: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| match entry.get(key)? {
+            Value::String(text) => Some(text.trim().to_string()),
+            Value::Number(number) => Some(number.to_string()),
+            _ => None,
+        })
+        .filter(|text| !text.is_empty())
+}
+
+/// Entries of a suggestion response: a list, or the first list in an object
+fn entries(response: &Value) -> &[Value] {
+    match response {
+        Value::Array(entries) => entries,
+        Value::Object(object) => object
+            .values()
+            .find_map(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default(),
+        _ => &[],
+    }
+}
+
+/// Convert one entry of a job title suggestion response
+fn parse_job_title(entry: &Value) -> Option<JobTitleSuggestion> {
+    let title = match entry {
+        Value::String(title) => Some(title.as_str()),
+        _ => ["bezeichnung", "name", "begriff", "vorschlag"]
+            .iter()
+            .find_map(|key| entry.get(key).and_then(Value::as_str)),
+    }?
+    .trim();
+    if title.is_empty() {
+        return None;
+    }
+    let occupation_code = ["dkz", "id", "code"]
+        .iter()
+        .find_map(|key| entry.get(key))
+        .filter(|code| !code.is_null())
+        .map(|code| code.to_string().trim_matches('"').to_string());
+
+    Some(JobTitleSuggestion {
+        title: title.to_string(),
+        occupation_code,
+    })
+}
+
+/// Extract unique job title suggestions from a response
+fn parse_job_titles(response: &Value, limit: usize) -> Vec<JobTitleSuggestion> {
+    let mut suggestions: Vec<JobTitleSuggestion> = Vec::new();
+    for suggestion in entries(response).iter().filter_map(parse_job_title) {
+        let title = suggestion.title.to_lowercase();
+        let duplicate = suggestions
+            .iter()
+            .any(|seen| seen.title.to_lowercase() == title);
+        if !duplicate {
+            suggestions.push(suggestion);
+        }
+        if suggestions.len() == limit {
+            break;
+        }
+    }
+    suggestions
+}
+
+/// Convert one entry of a place suggestion response
+fn parse_location(entry: &Value) -> Option<LocationSuggestion> {
+    let name = match entry {
+        Value::String(name) => Some(name.trim().to_string()).filter(|name| !name.is_empty()),
+        _ => field(entry, &["ort", "name", "bezeichnung"]),
+    }?;
+    Some(LocationSuggestion {
+        name,
+        postal_code: field(entry, &["plz", "postleitzahl"]),
+        state: field(entry, &["bundesland", "region"]),
+    })
+}
+
+/// Extract unique place suggestions from a response
+fn parse_locations(response: &Value, limit: usize) -> Vec<LocationSuggestion> {
+    let mut suggestions: Vec<LocationSuggestion> = Vec::new();
+    for suggestion in entries(response).iter().filter_map(parse_location) {
+        if !suggestions.contains(&suggestion) {
+            suggestions.push(suggestion);
+        }
+        if suggestions.len() == limit {
+            break;
+        }
+    }
+    suggestions
+}
+
+/// Place suggestions from the built-in city list
+pub fn local_locations(query: &str, limit: usize) -> Vec<LocationSuggestion> {
+    complete_argument("location", query)
+        .values
+        .into_iter()
+        .take(limit)
+        .map(|name| LocationSuggestion {
+            name,
+            postal_code: None,
+            state: None,
+        })
+        .collect()
+}
+
+/// Client for the Jobbörse autocomplete
+#[derive(Debug, Clone)]
+pub struct SuggestionClient {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+}
+
+impl SuggestionClient {
+    /// Create a client for the Jobsuche API base URL and optional custom key
+    pub fn new(base_url: &str, api_key: Option<&str>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .context("Failed to create suggestion client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.unwrap_or(DEFAULT_API_KEY).to_string(),
+        })
+    }
+
+    /// GET a path of the API as JSON
+    async fn get(&self, path: &str, query: &[(&str, &str)]) -> Result<Value> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self
+            .client
+            .get(&url)
+            .header("X-API-Key", &self.api_key)
+            .query(query)
+            .send()
+            .await
+            .context("Suggestion request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("Suggestion service responded with HTTP {}", status);
+        }
+        response.json().await.context("Invalid suggestion response")
+    }
+
+    /// Suggest canonical occupation names for a partial job title
+    pub async fn job_titles(
+        &self,
+        params: &SuggestJobTitlesParams,
+    ) -> Result<SuggestJobTitlesResult> {
+        let (query, limit) = query_and_limit(&params.query, params.limit)?;
+        let body = self
+            .get("/pc/v1/berufsvorschlag", &[("suchbegriff", query)])
+            .await?;
+        Ok(SuggestJobTitlesResult {
+            request_id: String::new(),
+            query: query.to_string(),
+            suggestions: parse_job_titles(&body, limit),
+        })
+    }
+
+    /// Resolve a partial place name or postal code to places
+    ///
+    /// Falls back to the built-in city list if the autocomplete fails.
+    pub async fn locations(
+        &self,
+        params: &SuggestLocationsParams,
+    ) -> Result<SuggestLocationsResult> {
+        let (query, limit) = query_and_limit(&params.query, params.limit)?;
+        let (source, suggestions) = match self
+            .get("/pc/v1/ortsvorschlag", &[("suchbegriff", query)])
+            .await
+        {
+            Ok(body) => (LocationSource::Api, parse_locations(&body, limit)),
+            Err(e) => {
+                warn!("Place autocomplete failed, using built-in cities: {:#}", e);
+                (LocationSource::Local, local_locations(query, limit))
+            }
+        };
+        Ok(SuggestLocationsResult {
+            request_id: String::new(),
+            query: query.to_string(),
+            source,
+            suggestions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_job_titles() {
+        let response = json!({"berufe": [
+            {"bezeichnung": "Kassierer/in", "dkz": 6224},
+            {"bezeichnung": "Kassierer/in - Handel"},
+            {"bezeichnung": "kassierer/in"},
+            {"bezeichnung": " "}
+        ]});
+        assert_eq!(
+            parse_job_titles(&response, 10),
+            vec![
+                JobTitleSuggestion {
+                    title: "Kassierer/in".to_string(),
+                    occupation_code: Some("6224".to_string()),
+                },
+                JobTitleSuggestion {
+                    title: "Kassierer/in - Handel".to_string(),
+                    occupation_code: None,
+                },
+            ]
+        );
+        assert_eq!(parse_job_titles(&response, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_parse_job_titles_from_strings() {
+        let response = json!(["Softwareentwickler/in", "Softwaretester/in"]);
+        let titles: Vec<String> = parse_job_titles(&response, 10)
+            .into_iter()
+            .map(|suggestion| suggestion.title)
+            .collect();
+        assert_eq!(titles, vec!["Softwareentwickler/in", "Softwaretester/in"]);
+        assert!(parse_job_titles(&json!(null), 10).is_empty());
+    }
+
+    #[test]
+    fn test_parse_locations() {
+        let response = json!([
+            {"ort": "Frankfurt am Main", "plz": "60311", "bundesland": "Hessen"},
+            {"ort": "Frankfurt (Oder)", "plz": 15230, "region": "Brandenburg"},
+            {"ort": "Frankfurt am Main", "plz": "60311", "bundesland": "Hessen"},
+            {"plz": "60312"}
+        ]);
+        assert_eq!(
+            parse_locations(&response, 10),
+            vec![
+                LocationSuggestion {
+                    name: "Frankfurt am Main".to_string(),
+                    postal_code: Some("60311".to_string()),
+                    state: Some("Hessen".to_string()),
+                },
+                LocationSuggestion {
+                    name: "Frankfurt (Oder)".to_string(),
+                    postal_code: Some("15230".to_string()),
+                    state: Some("Brandenburg".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_local_locations() {
+        let names: Vec<String> = local_locations("muench", 10)
+            .into_iter()
+            .map(|suggestion| suggestion.name)
+            .collect();
+        assert_eq!(names, vec!["München"]);
+        assert!(local_locations("80331", 10).is_empty());
+    }
+}
@@ -0,0 +1,953 @@
+//! Tool metadata
+//!
+//! Behavioral hints and output schemas for every tool exposed by the server.
+//! Hosts use the MCP `ToolAnnotations` to decide which tools may run without
+//! asking the user (e.g. auto-approving read-only tools), and the `outputSchema`
+//! to parse the `structuredContent` of tool results.
+
+use crate::apprenticeship::{SearchApprenticeshipsParams, SearchApprenticeshipsResult};
+use crate::archive::{GetArchivedJobParams, GetArchivedJobResult};
+use crate::areas::{SearchJobsInAreasParams, SearchJobsInAreasResult};
+use crate::audit::{GetAuditLogParams, GetAuditLogResult};
+use crate::bewerberboerse::{
+    GetCandidateProfileParams, GetCandidateProfileResult, SearchCandidatesParams,
+    SearchCandidatesResult,
+};
+use crate::bulk_export::{BulkExportParams, BulkExportResult};
+use crate::checklist::{BuildApplicationChecklistParams, BuildApplicationChecklistResult};
+use crate::coaching::{SearchCoachingOffersParams, SearchCoachingOffersResult};
+use crate::comparison::{CompareJobsParams, CompareJobsResult};
+use crate::config::JobsucheConfig;
+use crate::cv_match::{MatchCvParams, MatchCvResult};
+use crate::deadlines::{ListUpcomingDeadlinesParams, ListUpcomingDeadlinesResult};
+use crate::digest::{GenerateDigestParams, GenerateDigestResult};
+use crate::employer::{GetEmployerJobsParams, GetEmployerJobsResult};
+use crate::employer_filter::{
+    GetEmployerFilterParams, GetEmployerFilterResult, UpdateEmployerFilterParams,
+    UpdateEmployerFilterResult,
+};
+use crate::employer_monitor::{MonitorEmployersParams, MonitorEmployersResult};
+use crate::entgeltatlas::{GetSalaryStatisticsParams, GetSalaryStatisticsResult};
+use crate::export::{ExportResultsParams, ExportResultsResult};
+use crate::favorites::{
+    ListFavoritesParams, ListFavoritesResult, RemoveFavoriteParams, RemoveFavoriteResult,
+    SaveFavoriteParams, SaveFavoriteResult,
+};
+use crate::geocoding::{GeocodeLocationParams, GeocodeLocationResult};
+use crate::geojson::{SearchJobsGeoJsonParams, SearchJobsGeoJsonResult};
+use crate::hiring_history::{EmployerHiringHistoryParams, EmployerHiringHistoryResult};
+use crate::history::{
+    GetSearchHistoryParams, GetSearchHistoryResult, RerunSearchHistoryParams,
+    RerunSearchHistoryResult,
+};
+use crate::import::{ImportRefnrsParams, ImportRefnrsResult};
+use crate::local_search::{SearchLocalJobsParams, SearchLocalJobsResult};
+use crate::logging::{SetLogLevelParams, SetLogLevelResult};
+use crate::maintenance::{CompactStorageParams, CompactStorageResult};
+use crate::market::{SummarizeMarketParams, SummarizeMarketResult};
+use crate::notes::{AddJobNoteParams, AddJobNoteResult, GetJobNotesParams, GetJobNotesResult};
+use crate::occupation_fields::{ListOccupationFieldsParams, ListOccupationFieldsResult};
+use crate::profile::{
+    DeleteUserProfileParams, DeleteUserProfileResult, GetUserProfileParams, GetUserProfileResult,
+    SetUserProfileParams, SetUserProfileResult,
+};
+use crate::quota::{GetQuotaUsageParams, QuotaUsage};
+use crate::ranking::{RankJobsParams, RankJobsResult};
+use crate::regional::{RegionalOverviewParams, RegionalOverviewResult};
+use crate::resources::{DescriptionChunk, ReadJobDescriptionParams};
+use crate::saved_searches::{
+    CreateSavedSearchParams, CreateSavedSearchResult, DeleteSavedSearchParams,
+    DeleteSavedSearchResult, ListSavedSearchesParams, ListSavedSearchesResult,
+    RunSavedSearchParams, RunSavedSearchResult,
+};
+use crate::scheduler::{GetScheduledUpdatesParams, GetScheduledUpdatesResult};
+use crate::similar::{FindSimilarJobsParams, FindSimilarJobsResult};
+use crate::storage_stats::{GetStorageStatsParams, GetStorageStatsResult};
+use crate::studiensuche::{SearchDegreeProgramsParams, SearchDegreeProgramsResult};
+use crate::suggestions::{
+    SuggestJobTitlesParams, SuggestJobTitlesResult, SuggestLocationsParams, SuggestLocationsResult,
+};
+use crate::trends::{JobTrendsParams, JobTrendsResult};
+use crate::watchlists::{
+    CheckWatchlistsParams, CheckWatchlistsResult, CreateWatchlistParams, CreateWatchlistResult,
+    DeleteWatchlistParams, DeleteWatchlistResult, ListWatchlistsParams, ListWatchlistsResult,
+};
+use crate::weiterbildung::{SearchTrainingsParams, SearchTrainingsResult};
+use crate::{
+    BatchSearchJobsParams, BatchSearchJobsResult, GetJobDetailsParams, GetJobDetailsResult,
+    JobsucheServerStatus, SearchJobsParams, SearchJobsResult, SearchJobsWithDetailsParams,
+    SearchJobsWithDetailsResult,
+};
+use schemars::{json_schema, schema_for, JsonSchema, Schema};
+use serde::{Deserialize, Serialize};
+
+/// MCP tool annotations (`readOnlyHint`, `destructiveHint`, ...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    /// The tool does not modify any state
+    pub read_only_hint: bool,
+
+    /// The tool may delete or overwrite existing data (only meaningful if not read-only)
+    pub destructive_hint: bool,
+
+    /// Repeating the call with the same arguments has no additional effect
+    pub idempotent_hint: bool,
+
+    /// The tool talks to external systems (the Bundesagentur für Arbeit API)
+    pub open_world_hint: bool,
+}
+
+impl ToolAnnotations {
+    /// Read-only query against the job search API
+    pub const READ_ONLY: Self = Self {
+        read_only_hint: true,
+        destructive_hint: false,
+        idempotent_hint: true,
+        open_world_hint: true,
+    };
+
+    /// Query against the job search API that records its results locally
+    ///
+    /// Marks jobs as seen, adds to the search history, archives or indexes
+    /// details; with `hide_seen`, a repeated call returns other jobs.
+    pub const RECORDING_QUERY: Self = Self {
+        read_only_hint: false,
+        destructive_hint: false,
+        idempotent_hint: false,
+        open_world_hint: true,
+    };
+
+    /// Read-only query against local data
+    pub const LOCAL_READ_ONLY: Self = Self {
+        read_only_hint: true,
+        destructive_hint: false,
+        idempotent_hint: true,
+        open_world_hint: false,
+    };
+}
+
+/// Static description of a tool
+#[derive(Debug, Clone, Copy)]
+pub struct ToolInfo {
+    /// Tool name as exposed via `tools/list`
+    pub name: &'static str,
+
+    /// Behavioral hints for the host
+    pub annotations: ToolAnnotations,
+
+    /// JSON Schema of the tool result (`outputSchema`)
+    pub output_schema: fn() -> Schema,
+
+    /// JSON Schema of the tool parameters (`inputSchema`)
+    pub input_schema: fn() -> Schema,
+}
+
+fn output_schema_of<T: JsonSchema>() -> Schema {
+    schema_for!(T)
+}
+
+fn input_schema_of<T: JsonSchema>() -> Schema {
+    schema_for!(T)
+}
+
+/// Parameters of tools that take no arguments
+fn no_arguments() -> Schema {
+    json_schema!({
+        "type": "object",
+        "properties": {},
+        "additionalProperties": false
+    })
+}
+
+/// All tools provided by the server
+pub const TOOLS: &[ToolInfo] = &[
+    ToolInfo {
+        name: "search_jobs",
+        annotations: ToolAnnotations::RECORDING_QUERY,
+        output_schema: output_schema_of::<SearchJobsResult>,
+        input_schema: input_schema_of::<SearchJobsParams>,
+    },
+    ToolInfo {
+        name: "get_job_details",
+        annotations: ToolAnnotations::RECORDING_QUERY,
+        output_schema: output_schema_of::<GetJobDetailsResult>,
+        input_schema: input_schema_of::<GetJobDetailsParams>,
+    },
+    ToolInfo {
+        name: "search_jobs_with_details",
+        annotations: ToolAnnotations::RECORDING_QUERY,
+        output_schema: output_schema_of::<SearchJobsWithDetailsResult>,
+        input_schema: input_schema_of::<SearchJobsWithDetailsParams>,
+    },
+    ToolInfo {
+        name: "batch_search_jobs",
+        annotations: ToolAnnotations::RECORDING_QUERY,
+        output_schema: output_schema_of::<BatchSearchJobsResult>,
+        input_schema: input_schema_of::<BatchSearchJobsParams>,
+    },
+    ToolInfo {
+        name: "get_server_status",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<JobsucheServerStatus>,
+        input_schema: no_arguments,
+    },
+    ToolInfo {
+        name: "save_favorite",
+        // Fetches the job details, then inserts or refreshes the favorite
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: false,
+            idempotent_hint: true,
+            open_world_hint: true,
+        },
+        output_schema: output_schema_of::<SaveFavoriteResult>,
+        input_schema: input_schema_of::<SaveFavoriteParams>,
+    },
+    ToolInfo {
+        name: "list_favorites",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<ListFavoritesResult>,
+        input_schema: input_schema_of::<ListFavoritesParams>,
+    },
+    ToolInfo {
+        name: "remove_favorite",
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: true,
+            open_world_hint: false,
+        },
+        output_schema: output_schema_of::<RemoveFavoriteResult>,
+        input_schema: input_schema_of::<RemoveFavoriteParams>,
+    },
+    ToolInfo {
+        name: "create_saved_search",
+        // Replaces a saved search with the same name
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: true,
+            open_world_hint: false,
+        },
+        output_schema: output_schema_of::<CreateSavedSearchResult>,
+        input_schema: input_schema_of::<CreateSavedSearchParams>,
+    },
+    ToolInfo {
+        name: "list_saved_searches",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<ListSavedSearchesResult>,
+        input_schema: input_schema_of::<ListSavedSearchesParams>,
+    },
+    ToolInfo {
+        name: "delete_saved_search",
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: true,
+            open_world_hint: false,
+        },
+        output_schema: output_schema_of::<DeleteSavedSearchResult>,
+        input_schema: input_schema_of::<DeleteSavedSearchParams>,
+    },
+    ToolInfo {
+        name: "run_saved_search",
+        annotations: ToolAnnotations::RECORDING_QUERY,
+        output_schema: output_schema_of::<RunSavedSearchResult>,
+        input_schema: input_schema_of::<RunSavedSearchParams>,
+    },
+    ToolInfo {
+        name: "get_search_history",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<GetSearchHistoryResult>,
+        input_schema: input_schema_of::<GetSearchHistoryParams>,
+    },
+    ToolInfo {
+        name: "rerun_search_history",
+        annotations: ToolAnnotations::RECORDING_QUERY,
+        output_schema: output_schema_of::<RerunSearchHistoryResult>,
+        input_schema: input_schema_of::<RerunSearchHistoryParams>,
+    },
+    ToolInfo {
+        name: "add_job_note",
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: false,
+            idempotent_hint: false,
+            open_world_hint: false,
+        },
+        output_schema: output_schema_of::<AddJobNoteResult>,
+        input_schema: input_schema_of::<AddJobNoteParams>,
+    },
+    ToolInfo {
+        name: "get_job_notes",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<GetJobNotesResult>,
+        input_schema: input_schema_of::<GetJobNotesParams>,
+    },
+    ToolInfo {
+        name: "create_watchlist",
+        // Runs the search for the baseline and replaces a watchlist with the same name
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: false,
+            open_world_hint: true,
+        },
+        output_schema: output_schema_of::<CreateWatchlistResult>,
+        input_schema: input_schema_of::<CreateWatchlistParams>,
+    },
+    ToolInfo {
+        name: "list_watchlists",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<ListWatchlistsResult>,
+        input_schema: input_schema_of::<ListWatchlistsParams>,
+    },
+    ToolInfo {
+        name: "delete_watchlist",
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: true,
+            open_world_hint: false,
+        },
+        output_schema: output_schema_of::<DeleteWatchlistResult>,
+        input_schema: input_schema_of::<DeleteWatchlistParams>,
+    },
+    ToolInfo {
+        name: "check_watchlists",
+        // New postings are reported only once, so repeated checks differ
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: false,
+            idempotent_hint: false,
+            open_world_hint: true,
+        },
+        output_schema: output_schema_of::<CheckWatchlistsResult>,
+        input_schema: input_schema_of::<CheckWatchlistsParams>,
+    },
+    ToolInfo {
+        name: "get_archived_job",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<GetArchivedJobResult>,
+        input_schema: input_schema_of::<GetArchivedJobParams>,
+    },
+    ToolInfo {
+        name: "export_results",
+        // Overwrites an existing export with the same file name
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: true,
+            open_world_hint: true,
+        },
+        output_schema: output_schema_of::<ExportResultsResult>,
+        input_schema: input_schema_of::<ExportResultsParams>,
+    },
+    ToolInfo {
+        name: "bulk_export_jobs",
+        // Overwrites an existing export with the same file name
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: true,
+            open_world_hint: true,
+        },
+        output_schema: output_schema_of::<BulkExportResult>,
+        input_schema: input_schema_of::<BulkExportParams>,
+    },
+    ToolInfo {
+        name: "import_refnrs",
+        // Refreshes archived snapshots and favorites of the imported jobs
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: false,
+            idempotent_hint: true,
+            open_world_hint: true,
+        },
+        output_schema: output_schema_of::<ImportRefnrsResult>,
+        input_schema: input_schema_of::<ImportRefnrsParams>,
+    },
+    ToolInfo {
+        name: "search_local_jobs",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<SearchLocalJobsResult>,
+        input_schema: input_schema_of::<SearchLocalJobsParams>,
+    },
+    ToolInfo {
+        name: "compact_storage",
+        // Deletes expired snapshots, seen jobs, history entries and index entries
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: true,
+            open_world_hint: false,
+        },
+        output_schema: output_schema_of::<CompactStorageResult>,
+        input_schema: input_schema_of::<CompactStorageParams>,
+    },
+    ToolInfo {
+        name: "get_storage_stats",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<GetStorageStatsResult>,
+        input_schema: input_schema_of::<GetStorageStatsParams>,
+    },
+    ToolInfo {
+        name: "get_scheduled_updates",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<GetScheduledUpdatesResult>,
+        input_schema: input_schema_of::<GetScheduledUpdatesParams>,
+    },
+    ToolInfo {
+        name: "get_employer_jobs",
+        annotations: ToolAnnotations::RECORDING_QUERY,
+        output_schema: output_schema_of::<GetEmployerJobsResult>,
+        input_schema: input_schema_of::<GetEmployerJobsParams>,
+    },
+    ToolInfo {
+        name: "find_similar_jobs",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<FindSimilarJobsResult>,
+        input_schema: input_schema_of::<FindSimilarJobsParams>,
+    },
+    ToolInfo {
+        name: "compare_jobs",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<CompareJobsResult>,
+        input_schema: input_schema_of::<CompareJobsParams>,
+    },
+    ToolInfo {
+        name: "rank_jobs",
+        // Searches are recorded in the search history like search_jobs
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<RankJobsResult>,
+        input_schema: input_schema_of::<RankJobsParams>,
+    },
+    ToolInfo {
+        name: "set_user_profile",
+        // Overwrites the given fields of the stored profile
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: true,
+            open_world_hint: false,
+        },
+        output_schema: output_schema_of::<SetUserProfileResult>,
+        input_schema: input_schema_of::<SetUserProfileParams>,
+    },
+    ToolInfo {
+        name: "get_user_profile",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<GetUserProfileResult>,
+        input_schema: input_schema_of::<GetUserProfileParams>,
+    },
+    ToolInfo {
+        name: "delete_user_profile",
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: true,
+            open_world_hint: false,
+        },
+        output_schema: output_schema_of::<DeleteUserProfileResult>,
+        input_schema: input_schema_of::<DeleteUserProfileParams>,
+    },
+    ToolInfo {
+        name: "match_cv",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<MatchCvResult>,
+        input_schema: input_schema_of::<MatchCvParams>,
+    },
+    ToolInfo {
+        name: "summarize_market",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<SummarizeMarketResult>,
+        input_schema: input_schema_of::<SummarizeMarketParams>,
+    },
+    ToolInfo {
+        name: "job_trends",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<JobTrendsResult>,
+        input_schema: input_schema_of::<JobTrendsParams>,
+    },
+    ToolInfo {
+        name: "search_apprenticeships",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<SearchApprenticeshipsResult>,
+        input_schema: input_schema_of::<SearchApprenticeshipsParams>,
+    },
+    ToolInfo {
+        name: "get_salary_statistics",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<GetSalaryStatisticsResult>,
+        input_schema: input_schema_of::<GetSalaryStatisticsParams>,
+    },
+    ToolInfo {
+        name: "search_trainings",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<SearchTrainingsResult>,
+        input_schema: input_schema_of::<SearchTrainingsParams>,
+    },
+    ToolInfo {
+        name: "search_coaching_offers",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<SearchCoachingOffersResult>,
+        input_schema: input_schema_of::<SearchCoachingOffersParams>,
+    },
+    ToolInfo {
+        name: "search_degree_programs",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<SearchDegreeProgramsResult>,
+        input_schema: input_schema_of::<SearchDegreeProgramsParams>,
+    },
+    ToolInfo {
+        name: "search_candidates",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<SearchCandidatesResult>,
+        input_schema: input_schema_of::<SearchCandidatesParams>,
+    },
+    ToolInfo {
+        name: "get_candidate_profile",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<GetCandidateProfileResult>,
+        input_schema: input_schema_of::<GetCandidateProfileParams>,
+    },
+    ToolInfo {
+        name: "suggest_job_titles",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<SuggestJobTitlesResult>,
+        input_schema: input_schema_of::<SuggestJobTitlesParams>,
+    },
+    ToolInfo {
+        name: "suggest_locations",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<SuggestLocationsResult>,
+        input_schema: input_schema_of::<SuggestLocationsParams>,
+    },
+    ToolInfo {
+        name: "list_occupation_fields",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<ListOccupationFieldsResult>,
+        input_schema: input_schema_of::<ListOccupationFieldsParams>,
+    },
+    ToolInfo {
+        name: "regional_overview",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<RegionalOverviewResult>,
+        input_schema: input_schema_of::<RegionalOverviewParams>,
+    },
+    ToolInfo {
+        name: "monitor_employers",
+        // New postings are reported only once, so repeated calls differ
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: false,
+            idempotent_hint: false,
+            open_world_hint: true,
+        },
+        output_schema: output_schema_of::<MonitorEmployersResult>,
+        input_schema: input_schema_of::<MonitorEmployersParams>,
+    },
+    ToolInfo {
+        name: "employer_hiring_history",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<EmployerHiringHistoryResult>,
+        input_schema: input_schema_of::<EmployerHiringHistoryParams>,
+    },
+    ToolInfo {
+        name: "update_employer_filter",
+        // unblock, disallow and clear remove stored patterns
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: true,
+            idempotent_hint: true,
+            open_world_hint: false,
+        },
+        output_schema: output_schema_of::<UpdateEmployerFilterResult>,
+        input_schema: input_schema_of::<UpdateEmployerFilterParams>,
+    },
+    ToolInfo {
+        name: "get_employer_filter",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<GetEmployerFilterResult>,
+        input_schema: input_schema_of::<GetEmployerFilterParams>,
+    },
+    ToolInfo {
+        name: "read_job_description",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<DescriptionChunk>,
+        input_schema: input_schema_of::<ReadJobDescriptionParams>,
+    },
+    ToolInfo {
+        name: "get_quota_usage",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<QuotaUsage>,
+        input_schema: input_schema_of::<GetQuotaUsageParams>,
+    },
+    ToolInfo {
+        name: "set_log_level",
+        // Changes the running server's log filter; no data is touched
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: false,
+            idempotent_hint: true,
+            open_world_hint: false,
+        },
+        output_schema: output_schema_of::<SetLogLevelResult>,
+        input_schema: input_schema_of::<SetLogLevelParams>,
+    },
+    ToolInfo {
+        name: "get_audit_log",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<GetAuditLogResult>,
+        input_schema: input_schema_of::<GetAuditLogParams>,
+    },
+    ToolInfo {
+        name: "geocode_location",
+        // Asks the geocoding service; answers are cached locally
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<GeocodeLocationResult>,
+        input_schema: input_schema_of::<GeocodeLocationParams>,
+    },
+    ToolInfo {
+        name: "search_jobs_geojson",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<SearchJobsGeoJsonResult>,
+        input_schema: input_schema_of::<SearchJobsGeoJsonParams>,
+    },
+    ToolInfo {
+        name: "search_jobs_in_areas",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<SearchJobsInAreasResult>,
+        input_schema: input_schema_of::<SearchJobsInAreasParams>,
+    },
+    ToolInfo {
+        name: "generate_digest",
+        // Postings are reported in one digest only, so repeated digests differ
+        annotations: ToolAnnotations {
+            read_only_hint: false,
+            destructive_hint: false,
+            idempotent_hint: false,
+            open_world_hint: true,
+        },
+        output_schema: output_schema_of::<GenerateDigestResult>,
+        input_schema: input_schema_of::<GenerateDigestParams>,
+    },
+    ToolInfo {
+        name: "build_application_checklist",
+        annotations: ToolAnnotations::READ_ONLY,
+        output_schema: output_schema_of::<BuildApplicationChecklistResult>,
+        input_schema: input_schema_of::<BuildApplicationChecklistParams>,
+    },
+    ToolInfo {
+        name: "list_upcoming_deadlines",
+        annotations: ToolAnnotations::LOCAL_READ_ONLY,
+        output_schema: output_schema_of::<ListUpcomingDeadlinesResult>,
+        input_schema: input_schema_of::<ListUpcomingDeadlinesParams>,
+    },
+];
+
+/// Optional feature that enables tools
+#[derive(Debug, Clone, Copy)]
+pub struct ToolFeature {
+    /// Feature name
+    pub name: &'static str,
+
+    /// Environment variable that enables the feature
+    pub env_var: &'static str,
+
+    /// Tools that only work with the feature enabled
+    pub tools: &'static [&'static str],
+
+    /// Whether a configuration enables the feature
+    pub enabled: fn(&JobsucheConfig) -> bool,
+}
+
+/// Features that tools depend on; tools not listed here are always enabled
+pub const TOOL_FEATURES: &[ToolFeature] = &[
+    ToolFeature {
+        name: "recruiter_tools",
+        env_var: "JOBSUCHE_RECRUITER_TOOLS",
+        tools: &["search_candidates", "get_candidate_profile"],
+        enabled: |config| config.recruiter_tools,
+    },
+    ToolFeature {
+        name: "admin_tools",
+        env_var: "JOBSUCHE_ADMIN_TOOLS",
+        tools: &["set_log_level"],
+        enabled: |config| config.admin_tools,
+    },
+    ToolFeature {
+        name: "geocoding",
+        env_var: "JOBSUCHE_GEOCODING",
+        tools: &["geocode_location"],
+        enabled: |config| config.geocoding,
+    },
+];
+
+/// State of an optional feature, as reported by get_server_status
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FeatureStatus {
+    /// Feature name
+    pub name: String,
+
+    /// Environment variable that enables the feature
+    pub env_var: String,
+
+    /// Whether the feature is enabled
+    pub enabled: bool,
+
+    /// Tools that depend on the feature
+    pub tools: Vec<String>,
+}
+
+/// State of all optional features under a configuration
+pub fn feature_status(config: &JobsucheConfig) -> Vec<FeatureStatus> {
+    TOOL_FEATURES
+        .iter()
+        .map(|feature| FeatureStatus {
+            name: feature.name.to_string(),
+            env_var: feature.env_var.to_string(),
+            enabled: (feature.enabled)(config),
+            tools: feature.tools.iter().map(|tool| tool.to_string()).collect(),
+        })
+        .collect()
+}
+
+/// Names of the tools that work under a configuration
+pub fn enabled_tools(config: &JobsucheConfig) -> Vec<String> {
+    TOOLS
+        .iter()
+        .filter(|tool| {
+            TOOL_FEATURES
+                .iter()
+                .filter(|feature| feature.tools.contains(&tool.name))
+                .all(|feature| (feature.enabled)(config))
+        })
+        .map(|tool| tool.name.to_string())
+        .collect()
+}
+
+/// Add the annotations and output schema of [`TOOLS`] to a tool entry of a
+/// `tools/list` result
+///
+/// The `#[mcp_tools]` macro lists tools without either, and the protocol
+/// crate's own annotation type serializes its fields in snake case.
+pub fn describe(tool: &mut serde_json::Value) {
+    let Some(info) = tool
+        .get("name")
+        .and_then(serde_json::Value::as_str)
+        .and_then(tool_info)
+    else {
+        return;
+    };
+    tool["annotations"] = serde_json::json!(info.annotations);
+    tool["outputSchema"] = served_output_schema(info);
+}
+
+fn tool_info(name: &str) -> Option<&'static ToolInfo> {
+    TOOLS.iter().find(|tool| tool.name == name)
+}
+
+/// Output schema of a tool as served in `tools/list`
+///
+/// Tools taking `fields` return only the selected fields of their result, so
+/// none of its fields is required.
+pub fn served_output_schema(tool: &ToolInfo) -> serde_json::Value {
+    let mut schema = (tool.output_schema)().to_value();
+    if (tool.input_schema)().to_value()["properties"]
+        .get("fields")
+        .is_some()
+    {
+        drop_required(&mut schema);
+    }
+    schema
+}
+
+/// Remove the `required` lists of a schema and all its subschemas
+fn drop_required(schema: &mut serde_json::Value) {
+    match schema {
+        serde_json::Value::Object(keywords) => {
+            // A property named "required" holds a schema, not a list
+            if keywords.get("required").is_some_and(|r| r.is_array()) {
+                keywords.remove("required");
+            }
+            keywords.values_mut().for_each(drop_required);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(drop_required),
+        _ => {}
+    }
+}
+
+/// Look up the annotations of a tool by name
+pub fn annotations_for(name: &str) -> Option<ToolAnnotations> {
+    tool_info(name).map(|tool| tool.annotations)
+}
+
+/// Look up the output schema of a tool by name
+pub fn output_schema_for(name: &str) -> Option<Schema> {
+    tool_info(name).map(|tool| (tool.output_schema)())
+}
+
+/// Look up the parameter schema of a tool by name
+pub fn input_schema_for(name: &str) -> Option<Schema> {
+    tool_info(name).map(|tool| (tool.input_schema)())
+}
+
+/// Convert a tool result into MCP `structuredContent`
+///
+/// The value conforms to the tool's [`output_schema_for`] schema.
+pub fn structured_content<T: Serialize>(result: &T) -> serde_json::Result<serde_json::Value> {
+    serde_json::to_value(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_tool_names_are_unique() {
+        let names: HashSet<_> = TOOLS.iter().map(|tool| tool.name).collect();
+        assert_eq!(names.len(), TOOLS.len());
+    }
+
+    #[test]
+    fn test_feature_tools_exist() {
+        for feature in TOOL_FEATURES {
+            for tool in feature.tools {
+                assert!(annotations_for(tool).is_some(), "{}", tool);
+            }
+        }
+    }
+
+    #[test]
+    fn test_enabled_tools_follow_features() {
+        let mut config = JobsucheConfig::default();
+        let tools = enabled_tools(&config);
+        assert_eq!(tools.len(), TOOLS.len() - 4);
+        assert!(tools.iter().any(|tool| tool == "search_jobs"));
+        assert!(!tools.iter().any(|tool| tool == "search_candidates"));
+        assert!(!feature_status(&config)[0].enabled);
+
+        config.recruiter_tools = true;
+        assert_eq!(enabled_tools(&config).len(), TOOLS.len() - 2);
+        assert!(feature_status(&config)[0].enabled);
+
+        config.admin_tools = true;
+        config.geocoding = true;
+        assert_eq!(enabled_tools(&config).len(), TOOLS.len());
+    }
+
+    #[test]
+    fn test_read_only_tools_are_not_destructive() {
+        for tool in TOOLS {
+            if tool.annotations.read_only_hint {
+                assert!(!tool.annotations.destructive_hint, "{}", tool.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_annotations_for() {
+        assert_eq!(
+            annotations_for("search_jobs"),
+            Some(ToolAnnotations::RECORDING_QUERY)
+        );
+        assert_eq!(
+            annotations_for("get_server_status"),
+            Some(ToolAnnotations::READ_ONLY)
+        );
+        assert_eq!(annotations_for("unknown_tool"), None);
+    }
+
+    #[test]
+    fn test_output_schemas_are_objects() {
+        for tool in TOOLS {
+            let schema = (tool.output_schema)().to_value();
+            assert_eq!(schema["type"], "object", "{}", tool.name);
+            assert!(schema["properties"].is_object(), "{}", tool.name);
+        }
+    }
+
+    #[test]
+    fn test_input_schemas_are_closed_objects() {
+        for tool in TOOLS {
+            let schema = (tool.input_schema)().to_value();
+            assert_eq!(schema["type"], "object", "{}", tool.name);
+            assert_eq!(schema["additionalProperties"], false, "{}", tool.name);
+        }
+    }
+
+    #[test]
+    fn test_input_schema_bounds_and_values() {
+        let schema = input_schema_for("search_jobs").unwrap().to_value();
+        let properties = &schema["properties"];
+        assert_eq!(properties["radius_km"]["maximum"], 200);
+        assert_eq!(properties["page_size"]["minimum"], 1);
+        assert_eq!(properties["page_size"]["maximum"], 100);
+        assert_eq!(properties["published_since_days"]["maximum"], 100);
+        assert_eq!(properties["page"]["minimum"], 1);
+        assert_eq!(
+            properties["employment_type"]["items"]["enum"],
+            serde_json::json!(["fulltime", "parttime", "mini_job", "home_office", "shift"])
+        );
+        assert_eq!(properties["location"]["examples"][0], "Berlin");
+
+        let schema = input_schema_for("batch_search_jobs").unwrap().to_value();
+        assert_eq!(schema["properties"]["searches"]["maxItems"], 5);
+    }
+
+    #[test]
+    fn test_output_schema_for_search_jobs() {
+        let schema = output_schema_for("search_jobs").unwrap().to_value();
+        let properties = &schema["properties"];
+        assert!(properties.get("total_results").is_some());
+        assert!(properties.get("jobs").is_some());
+        assert!(output_schema_for("unknown_tool").is_none());
+    }
+
+    #[test]
+    fn test_structured_content_matches_schema_properties() {
+        let result = SearchJobsResult {
+            request_id: String::new(),
+            total_results: Some(0),
+            current_page: Some(1),
+            page_size: Some(25),
+            jobs_count: 0,
+            jobs: vec![],
+            hidden_seen_count: 0,
+            duplicates_collapsed: 0,
+            employer_filtered_count: 0,
+            location_excluded_count: 0,
+            search_duration_ms: 12,
+            effective_query: Default::default(),
+            relaxations: Vec::new(),
+        };
+
+        let content = structured_content(&result).unwrap();
+        let schema = output_schema_for("search_jobs").unwrap().to_value();
+        for key in content.as_object().unwrap().keys() {
+            assert!(schema["properties"].get(key).is_some(), "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_projected_results_require_no_fields() {
+        let served = served_output_schema(tool_info("search_jobs").unwrap());
+        assert!(served["properties"]["jobs"].is_object());
+        assert!(!served.to_string().contains("\"required\""));
+
+        let served = served_output_schema(tool_info("get_server_status").unwrap());
+        assert!(served["required"].is_array());
+    }
+
+    #[test]
+    fn test_annotations_serialization() {
+        let json = serde_json::to_value(ToolAnnotations::READ_ONLY).unwrap();
+        assert_eq!(json["readOnlyHint"], true);
+        assert_eq!(json["destructiveHint"], false);
+        assert_eq!(json["idempotentHint"], true);
+        assert_eq!(json["openWorldHint"], true);
+    }
+}
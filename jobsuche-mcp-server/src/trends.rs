@@ -0,0 +1,245 @@
+//! Posting volume trends
+//!
+//! The API can only filter by publication date (`veroeffentlichtseit`), so the
+//! volume of a query is counted once per window (1, 7, 30 and 90 days) and the
+//! counts are split into disjoint buckets. Postings that were taken down no
+//! longer count, so older buckets are lower bounds. Earlier runs of the same
+//! search in the search history add the total over time.
+
+use crate::history::HistoryEntry;
+use crate::SearchJobsParams;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Publication windows counted, in days
+pub const TREND_WINDOWS: &[u64] = &[1, 7, 30, 90];
+
+/// Ratio of recent to earlier daily volume above which a trend is rising
+const RISING_RATIO: f64 = 1.2;
+
+/// Ratio of recent to earlier daily volume below which a trend is falling
+const FALLING_RATIO: f64 = 0.8;
+
+/// Parameters for job_trends
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JobTrendsParams {
+    /// Search to analyze (same parameters as search_jobs; published_since_days
+    /// and page are ignored)
+    pub search: SearchJobsParams,
+}
+
+/// Postings published within the last `days` days
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TrendWindow {
+    /// Window length in days
+    pub days: u64,
+
+    /// Postings published within the window
+    pub total_results: u64,
+}
+
+/// Postings published between `from_days_ago` and `to_days_ago`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TrendBucket {
+    /// Start of the bucket in days ago (exclusive, 0 for today)
+    pub from_days_ago: u64,
+
+    /// End of the bucket in days ago (inclusive)
+    pub to_days_ago: u64,
+
+    /// Postings published in the bucket
+    pub postings: u64,
+
+    /// Postings per day in the bucket
+    pub per_day: f64,
+}
+
+/// Total results of an earlier run of the same search
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TrendSnapshot {
+    /// When the search was executed (RFC 3339)
+    pub executed_at: String,
+
+    /// Total number of results at that time
+    pub total_results: Option<u64>,
+}
+
+/// Direction of the posting volume
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TrendDirection {
+    Rising,
+    Stable,
+    Falling,
+}
+
+/// Result from job_trends
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JobTrendsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Postings currently online, regardless of publication date
+    pub total_results: u64,
+
+    /// Postings per publication window (1, 7, 30 and 90 days)
+    pub windows: Vec<TrendWindow>,
+
+    /// Disjoint buckets between the windows, newest first
+    pub buckets: Vec<TrendBucket>,
+
+    /// Daily volume of the last 7 days compared to the 83 days before; `None`
+    /// without postings in the last 90 days
+    pub trend: Option<TrendDirection>,
+
+    /// Earlier runs of the same search from the search history, oldest first
+    pub snapshots: Vec<TrendSnapshot>,
+}
+
+/// Split cumulative window counts into disjoint buckets
+///
+/// `windows` must be sorted by days. Counts that shrink with a longer window
+/// (postings taken down between the queries) are clamped to zero.
+pub fn buckets(windows: &[TrendWindow]) -> Vec<TrendBucket> {
+    let mut previous = (0, 0);
+    windows
+        .iter()
+        .map(|window| {
+            let (from_days_ago, counted) = previous;
+            previous = (window.days, window.total_results);
+            let postings = window.total_results.saturating_sub(counted);
+            TrendBucket {
+                from_days_ago,
+                to_days_ago: window.days,
+                postings,
+                per_day: postings as f64 / (window.days - from_days_ago) as f64,
+            }
+        })
+        .collect()
+}
+
+/// Compare the daily volume of the last 7 days with the rest of the 90 days
+pub fn trend(windows: &[TrendWindow]) -> Option<TrendDirection> {
+    let count = |days: u64| {
+        windows
+            .iter()
+            .find(|window| window.days == days)
+            .map(|window| window.total_results)
+    };
+    let recent = count(7)?;
+    let all = count(90)?;
+    if all == 0 {
+        return None;
+    }
+
+    let recent_per_day = recent as f64 / 7.0;
+    let earlier_per_day = all.saturating_sub(recent) as f64 / 83.0;
+    if earlier_per_day == 0.0 {
+        return Some(TrendDirection::Rising);
+    }
+    Some(direction(recent_per_day / earlier_per_day))
+}
+
+/// Direction for the ratio of a recent to an earlier volume
+pub fn direction(ratio: f64) -> TrendDirection {
+    if ratio > RISING_RATIO {
+        TrendDirection::Rising
+    } else if ratio < FALLING_RATIO {
+        TrendDirection::Falling
+    } else {
+        TrendDirection::Stable
+    }
+}
+
+/// Whether two searches ask for the same jobs (paging aside)
+pub fn same_query(a: &SearchJobsParams, b: &SearchJobsParams) -> bool {
+    let normalized = |search: &SearchJobsParams| {
+        serde_json::to_value(SearchJobsParams {
+            page: None,
+            page_size: None,
+            ..search.clone()
+        })
+        .ok()
+    };
+    normalized(a) == normalized(b)
+}
+
+/// Earlier runs of a search, oldest first
+pub fn snapshots(entries: &[HistoryEntry], search: &SearchJobsParams) -> Vec<TrendSnapshot> {
+    entries
+        .iter()
+        .filter(|entry| same_query(&entry.search, search))
+        .map(|entry| TrendSnapshot {
+            executed_at: entry.executed_at.clone(),
+            total_results: entry.total_results,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn windows(counts: &[u64]) -> Vec<TrendWindow> {
+        TREND_WINDOWS
+            .iter()
+            .zip(counts)
+            .map(|(&days, &total_results)| TrendWindow {
+                days,
+                total_results,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_buckets() {
+        let buckets = buckets(&windows(&[2, 14, 30, 120]));
+        let postings: Vec<u64> = buckets.iter().map(|bucket| bucket.postings).collect();
+        assert_eq!(postings, vec![2, 12, 16, 90]);
+        assert_eq!(buckets[1].from_days_ago, 1);
+        assert_eq!(buckets[1].to_days_ago, 7);
+        assert_eq!(buckets[1].per_day, 2.0);
+        assert_eq!(buckets[3].per_day, 1.5);
+    }
+
+    #[test]
+    fn test_buckets_clamp_shrinking_counts() {
+        assert_eq!(buckets(&windows(&[5, 4, 4, 4]))[1].postings, 0);
+    }
+
+    #[test]
+    fn test_trend() {
+        assert_eq!(trend(&windows(&[0, 0, 0, 0])), None);
+        assert_eq!(
+            trend(&windows(&[10, 70, 150, 153])),
+            Some(TrendDirection::Rising)
+        );
+        assert_eq!(
+            trend(&windows(&[1, 7, 30, 90])),
+            Some(TrendDirection::Stable)
+        );
+        assert_eq!(
+            trend(&windows(&[0, 1, 50, 167])),
+            Some(TrendDirection::Falling)
+        );
+    }
+
+    #[test]
+    fn test_same_query_ignores_paging() {
+        let search = |value: serde_json::Value| -> SearchJobsParams {
+            serde_json::from_value(value).unwrap()
+        };
+        let base = search(serde_json::json!({"job_title": "Koch", "location": "Kiel"}));
+        let paged = search(serde_json::json!({
+            "job_title": "Koch",
+            "location": "Kiel",
+            "page": 2,
+            "page_size": 50
+        }));
+        let other = search(serde_json::json!({"job_title": "Koch"}));
+        assert!(same_query(&base, &paged));
+        assert!(!same_query(&base, &other));
+    }
+}
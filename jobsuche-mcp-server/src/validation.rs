@@ -0,0 +1,428 @@
+//! Parameter validation
+//!
+//! Out-of-range values used to go to the API as they were, which answered
+//! with a bare HTTP 400 or silently ignored them. Tools now check their
+//! parameters first and report every violated constraint at once, so a model
+//! can fix all of them in one retry. The limits match the bounds declared in
+//! the parameter schemas.
+
+use crate::country::is_home_country;
+use crate::freshness::Freshness;
+use crate::location_filter::MAX_EXCLUDED_LOCATIONS;
+use crate::{
+    BatchSearchJobsParams, GetJobDetailsParams, SearchJobsParams, SearchJobsWithDetailsParams,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Maximum search radius in km accepted by the API
+pub const MAX_RADIUS_KM: u64 = 200;
+
+/// Maximum number of days since publication accepted by the API
+pub const MAX_PUBLISHED_SINCE_DAYS: u64 = 100;
+
+/// Maximum page size accepted by the API
+pub const MAX_PAGE_SIZE: u64 = 100;
+
+/// Maximum number of jobs search_jobs_with_details fetches details for
+///
+/// Kept at 10 rather than 20: each detail is a separate API call.
+pub const MAX_DETAILS: u64 = 10;
+
+/// Maximum number of searches of batch_search_jobs
+pub const MAX_BATCH_SEARCHES: usize = 5;
+
+/// Maximum number of jobs per search batch_search_jobs fetches details for
+pub const MAX_DETAILS_PER_SEARCH: u64 = 5;
+
+/// A violated constraint of one parameter
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Violation {
+    /// Path of the parameter (e.g. "radius_km", "searches[1].page")
+    pub field: String,
+
+    /// Constraint the value violates (e.g. "must be at most 200")
+    pub constraint: String,
+
+    /// Value as given
+    pub value: Value,
+}
+
+/// All violated constraints of a tool call
+///
+/// Returned (inside `anyhow::Error`) by the tools; transport integrations can
+/// downcast to it to get the violations as data.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ValidationError {
+    /// Violated constraints in parameter order
+    pub violations: Vec<Violation>,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid parameters:")?;
+        for violation in &self.violations {
+            write!(
+                f,
+                "\n- {}: {} (got {})",
+                violation.field, violation.constraint, violation.value
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Collects the violations of a tool call
+#[derive(Debug, Default)]
+pub struct Validator {
+    prefix: String,
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn path(&self, field: &str) -> String {
+        if self.prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", self.prefix, field)
+        }
+    }
+
+    fn violation(&mut self, field: &str, constraint: String, value: impl Into<Value>) {
+        self.violations.push(Violation {
+            field: self.path(field),
+            constraint,
+            value: value.into(),
+        });
+    }
+
+    /// Check that a number lies within `min..=max`
+    pub fn range(&mut self, field: &str, value: Option<u64>, min: u64, max: u64) -> &mut Self {
+        match value {
+            Some(value) if value < min && max == u64::MAX => {
+                self.violation(field, format!("must be at least {}", min), value)
+            }
+            Some(value) if value < min || value > max => {
+                let constraint = if min == 0 {
+                    format!("must be at most {}", max)
+                } else {
+                    format!("must be between {} and {}", min, max)
+                };
+                self.violation(field, constraint, value)
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// Check that a list holds `1..=max` entries
+    pub fn count(&mut self, field: &str, count: usize, max: usize) -> &mut Self {
+        if count == 0 || count > max {
+            let constraint = format!("must hold between 1 and {} entries", max);
+            self.violation(field, constraint, count);
+        }
+        self
+    }
+
+    /// Check that a job identifier is a non-empty refnr or hashId
+    ///
+    /// Reference numbers ("10001-1234567890-S") and Base64 hashIds only use
+    /// letters, digits and `- _ + / = %`.
+    pub fn job_id(&mut self, field: &str, value: &str) -> &mut Self {
+        let value = value.trim();
+        if value.is_empty() {
+            self.violation(field, "must not be empty".to_string(), value);
+        } else if !value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '+' | '/' | '=' | '%'))
+        {
+            let constraint =
+                "must be a reference number (e.g. \"10001-1234567890-S\") or hashId".to_string();
+            self.violation(field, constraint, value);
+        }
+        self
+    }
+
+    /// Check that `freshness` does not compete with `published_since_days`
+    pub fn freshness(
+        &mut self,
+        freshness: Option<Freshness>,
+        published_since_days: Option<u64>,
+    ) -> &mut Self {
+        if let (Some(freshness), Some(_)) = (freshness, published_since_days) {
+            let value = serde_json::to_value(freshness).unwrap_or_default();
+            let constraint = "cannot be combined with published_since_days".to_string();
+            self.violation("freshness", constraint, value);
+        }
+        self
+    }
+
+    /// Check that `country` does not contradict `nationwide` or
+    /// `include_abroad`
+    pub fn country(
+        &mut self,
+        country: Option<&str>,
+        nationwide: Option<bool>,
+        include_abroad: Option<bool>,
+    ) -> &mut Self {
+        let Some(country) = country else {
+            return self;
+        };
+        if nationwide.unwrap_or(false) {
+            let constraint = "cannot be combined with nationwide".to_string();
+            self.violation("country", constraint, country);
+        } else if !include_abroad.unwrap_or(true) && !is_home_country(country) {
+            let constraint = "cannot be combined with include_abroad: false".to_string();
+            self.violation("country", constraint, country);
+        }
+        self
+    }
+
+    /// Check the search parameters shared by the search tools
+    pub fn search(
+        &mut self,
+        radius_km: Option<u64>,
+        published_since_days: Option<u64>,
+        page_size: Option<u64>,
+        page: Option<u64>,
+    ) -> &mut Self {
+        self.range("radius_km", radius_km, 0, MAX_RADIUS_KM)
+            .range(
+                "published_since_days",
+                published_since_days,
+                0,
+                MAX_PUBLISHED_SINCE_DAYS,
+            )
+            .range("page_size", page_size, 1, MAX_PAGE_SIZE)
+            .range("page", page, 1, u64::MAX)
+    }
+
+    /// Check a search nested under `field` (e.g. the `search` of summarize_market)
+    pub fn nested_search(&mut self, field: &str, search: &SearchJobsParams) -> &mut Self {
+        let nested = self.path(field);
+        let prefix = std::mem::replace(&mut self.prefix, nested);
+        self.search(
+            search.radius_km,
+            search.published_since_days,
+            search.page_size,
+            search.page,
+        )
+        .freshness(search.freshness, search.published_since_days)
+        .country(
+            search.country.as_deref(),
+            search.nationwide,
+            search.include_abroad,
+        )
+        .exclude_locations(search.exclude_locations.as_deref());
+        self.prefix = prefix;
+        self
+    }
+
+    /// Check that at most [`MAX_EXCLUDED_LOCATIONS`] locations are excluded
+    pub fn exclude_locations(&mut self, locations: Option<&[String]>) -> &mut Self {
+        let count = locations.map_or(0, <[String]>::len);
+        if count > MAX_EXCLUDED_LOCATIONS {
+            let constraint = format!("must hold at most {} entries", MAX_EXCLUDED_LOCATIONS);
+            self.violation("exclude_locations", constraint, count);
+        }
+        self
+    }
+
+    /// Fail with all collected violations, if any
+    pub fn finish(&mut self) -> Result<(), ValidationError> {
+        if self.violations.is_empty() {
+            return Ok(());
+        }
+        Err(ValidationError {
+            violations: std::mem::take(&mut self.violations),
+        })
+    }
+}
+
+/// Validate the parameters of search_jobs
+pub fn validate_search_jobs(params: &SearchJobsParams) -> Result<(), ValidationError> {
+    Validator::new()
+        .search(
+            params.radius_km,
+            params.published_since_days,
+            params.page_size,
+            params.page,
+        )
+        .freshness(params.freshness, params.published_since_days)
+        .country(
+            params.country.as_deref(),
+            params.nationwide,
+            params.include_abroad,
+        )
+        .exclude_locations(params.exclude_locations.as_deref())
+        .finish()
+}
+
+/// Validate the parameters of get_job_details
+pub fn validate_job_details(params: &GetJobDetailsParams) -> Result<(), ValidationError> {
+    Validator::new()
+        .job_id("reference_number", &params.reference_number)
+        .finish()
+}
+
+/// Validate the parameters of search_jobs_with_details
+pub fn validate_search_with_details(
+    params: &SearchJobsWithDetailsParams,
+) -> Result<(), ValidationError> {
+    Validator::new()
+        .search(
+            params.radius_km,
+            params.published_since_days,
+            params.page_size,
+            params.page,
+        )
+        .freshness(params.freshness, params.published_since_days)
+        .country(
+            params.country.as_deref(),
+            params.nationwide,
+            params.include_abroad,
+        )
+        .exclude_locations(params.exclude_locations.as_deref())
+        .range("max_details", params.max_details, 0, MAX_DETAILS)
+        .finish()
+}
+
+/// Validate the parameters of batch_search_jobs
+pub fn validate_batch_search(params: &BatchSearchJobsParams) -> Result<(), ValidationError> {
+    let mut validator = Validator::new();
+    validator
+        .count("searches", params.searches.len(), MAX_BATCH_SEARCHES)
+        .range(
+            "max_details_per_search",
+            params.max_details_per_search,
+            0,
+            MAX_DETAILS_PER_SEARCH,
+        );
+    for (idx, search) in params.searches.iter().enumerate() {
+        validator.prefix = format!("searches[{}]", idx);
+        validator
+            .search(search.radius_km, search.published_since_days, None, None)
+            .freshness(search.freshness, search.published_since_days);
+    }
+    validator.prefix.clear();
+    validator.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_search_jobs_reports_every_violation() {
+        let params: SearchJobsParams = serde_json::from_value(json!({
+            "radius_km": 500,
+            "published_since_days": 365,
+            "page_size": 0,
+            "page": 0
+        }))
+        .unwrap();
+        let error = validate_search_jobs(&params).unwrap_err();
+        let fields: Vec<&str> = error
+            .violations
+            .iter()
+            .map(|violation| violation.field.as_str())
+            .collect();
+        assert_eq!(
+            fields,
+            vec!["radius_km", "published_since_days", "page_size", "page"]
+        );
+        assert_eq!(
+            error.to_string().lines().nth(1),
+            Some("- radius_km: must be at most 200 (got 500)")
+        );
+        assert_eq!(error.violations[3].constraint, "must be at least 1");
+
+        let params: SearchJobsParams =
+            serde_json::from_value(json!({"radius_km": 200, "page": 1})).unwrap();
+        assert!(validate_search_jobs(&params).is_ok());
+    }
+
+    #[test]
+    fn test_validate_freshness() {
+        let params: SearchJobsParams =
+            serde_json::from_value(json!({"freshness": "week", "published_since_days": 7}))
+                .unwrap();
+        let error = validate_search_jobs(&params).unwrap_err();
+        assert_eq!(
+            error.to_string().lines().nth(1),
+            Some("- freshness: cannot be combined with published_since_days (got \"week\")")
+        );
+
+        let params: SearchJobsParams =
+            serde_json::from_value(json!({"freshness": "today"})).unwrap();
+        assert!(validate_search_jobs(&params).is_ok());
+    }
+
+    #[test]
+    fn test_validate_country() {
+        let params: SearchJobsParams =
+            serde_json::from_value(json!({"country": "CH", "nationwide": true})).unwrap();
+        let error = validate_search_jobs(&params).unwrap_err();
+        assert_eq!(
+            error.to_string().lines().nth(1),
+            Some("- country: cannot be combined with nationwide (got \"CH\")")
+        );
+
+        let params: SearchJobsParams =
+            serde_json::from_value(json!({"country": "AT", "include_abroad": false})).unwrap();
+        assert!(validate_search_jobs(&params).is_err());
+
+        let params: SearchJobsParams =
+            serde_json::from_value(json!({"country": "DE", "include_abroad": false})).unwrap();
+        assert!(validate_search_jobs(&params).is_ok());
+    }
+
+    #[test]
+    fn test_validate_job_id() {
+        let mut validator = Validator::new();
+        validator
+            .job_id("a", "10001-1234567890-S")
+            .job_id("b", "MTAwMDEtMTIzNDU2Nzg5MC1T%3D")
+            .job_id("c", "  ")
+            .job_id("d", "10001 1234567890");
+        let error = validator.finish().unwrap_err();
+        assert_eq!(error.violations.len(), 2);
+        assert_eq!(error.violations[0].field, "c");
+        assert_eq!(error.violations[0].constraint, "must not be empty");
+        assert_eq!(error.violations[1].field, "d");
+    }
+
+    #[test]
+    fn test_validate_batch_search_paths() {
+        let params: BatchSearchJobsParams = serde_json::from_value(json!({
+            "searches": [{"name": "A"}, {"name": "B", "radius_km": 300}],
+            "max_details_per_search": 9
+        }))
+        .unwrap();
+        let error = validate_batch_search(&params).unwrap_err();
+        let fields: Vec<&str> = error
+            .violations
+            .iter()
+            .map(|violation| violation.field.as_str())
+            .collect();
+        assert_eq!(
+            fields,
+            vec!["max_details_per_search", "searches[1].radius_km"]
+        );
+
+        let mut validator = Validator::new();
+        let search: SearchJobsParams = serde_json::from_value(json!({"page": 0})).unwrap();
+        let error = validator
+            .nested_search("search", &search)
+            .finish()
+            .unwrap_err();
+        assert_eq!(error.violations[0].field, "search.page");
+    }
+}
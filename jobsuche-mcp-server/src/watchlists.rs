@@ -0,0 +1,320 @@
+//! Watchlists
+//!
+//! A watchlist is a search definition plus the reference numbers it has
+//! returned so far. Checking a watchlist re-runs the search and reports only
+//! postings that have not appeared before, together with the time they were
+//! first seen. The baseline is recorded when the watchlist is created, so the
+//! first check does not report every existing posting as new.
+//!
+//! Each watchlist keeps its own set of known jobs, independent of the global
+//! seen-jobs tracking used by `hide_seen`.
+
+use crate::{JobSummary, SearchJobsParams};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Maximum number of known reference numbers kept per watchlist
+pub const MAX_KNOWN_JOBS: usize = 2000;
+
+/// A stored watchlist
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Watchlist {
+    /// Name as given by the user
+    pub name: String,
+
+    /// Search that is re-run on every check
+    pub search: SearchJobsParams,
+
+    /// When the watchlist was created (RFC 3339)
+    pub created_at: String,
+
+    /// When the watchlist was last checked (RFC 3339)
+    pub last_checked_at: Option<String>,
+
+    /// Reference number -> time of first appearance (RFC 3339)
+    pub known_jobs: BTreeMap<String, String>,
+}
+
+impl Watchlist {
+    /// Create a watchlist whose baseline are the given jobs
+    pub fn new(name: &str, search: SearchJobsParams, baseline: &[JobSummary], now: &str) -> Self {
+        let mut watchlist = Self {
+            name: name.trim().to_string(),
+            search,
+            created_at: now.to_string(),
+            last_checked_at: None,
+            known_jobs: BTreeMap::new(),
+        };
+        watchlist.absorb(baseline.to_vec(), now);
+        watchlist
+    }
+
+    /// Record the jobs of a check and return the ones not known before
+    pub fn absorb(&mut self, jobs: Vec<JobSummary>, now: &str) -> Vec<WatchlistJob> {
+        let mut new_jobs = Vec::new();
+        for job in jobs {
+            if self.known_jobs.contains_key(&job.reference_number) {
+                continue;
+            }
+            self.known_jobs
+                .insert(job.reference_number.clone(), now.to_string());
+            new_jobs.push(WatchlistJob {
+                first_seen_at: now.to_string(),
+                job,
+            });
+        }
+        self.last_checked_at = Some(now.to_string());
+        self.prune();
+        new_jobs
+    }
+
+    /// Drop the oldest known jobs beyond [`MAX_KNOWN_JOBS`]
+    fn prune(&mut self) {
+        if self.known_jobs.len() <= MAX_KNOWN_JOBS {
+            return;
+        }
+
+        let mut by_age: Vec<(String, String)> =
+            std::mem::take(&mut self.known_jobs).into_iter().collect();
+        // RFC 3339 timestamps in UTC sort chronologically
+        by_age.sort_by(|a, b| b.1.cmp(&a.1));
+        by_age.truncate(MAX_KNOWN_JOBS);
+        self.known_jobs = by_age.into_iter().collect();
+    }
+}
+
+/// A posting that appeared for the first time
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WatchlistJob {
+    /// When the posting first appeared in this watchlist (RFC 3339)
+    pub first_seen_at: String,
+
+    /// The posting
+    pub job: JobSummary,
+}
+
+/// Summary of a watchlist (without the known jobs)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WatchlistInfo {
+    /// Watchlist name
+    pub name: String,
+
+    /// Search that is re-run on every check
+    pub search: SearchJobsParams,
+
+    /// When the watchlist was created (RFC 3339)
+    pub created_at: String,
+
+    /// When the watchlist was last checked (RFC 3339)
+    pub last_checked_at: Option<String>,
+
+    /// Number of reference numbers known to the watchlist
+    pub known_jobs_count: usize,
+}
+
+impl From<Watchlist> for WatchlistInfo {
+    fn from(watchlist: Watchlist) -> Self {
+        Self {
+            known_jobs_count: watchlist.known_jobs.len(),
+            name: watchlist.name,
+            search: watchlist.search,
+            created_at: watchlist.created_at,
+            last_checked_at: watchlist.last_checked_at,
+        }
+    }
+}
+
+/// Parameters for create_watchlist
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateWatchlistParams {
+    /// Name of the watchlist; an existing watchlist with the same name is replaced
+    pub name: String,
+
+    /// Search to watch (same parameters as search_jobs)
+    /// Tip: use published_since_days and a large page_size so new postings are on the first page
+    pub search: SearchJobsParams,
+}
+
+/// Result from create_watchlist
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreateWatchlistResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// The created watchlist
+    pub watchlist: WatchlistInfo,
+
+    /// True if a watchlist with the same name has been replaced
+    pub replaced: bool,
+}
+
+/// Parameters for list_watchlists
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ListWatchlistsParams {}
+
+/// Result from list_watchlists
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListWatchlistsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Number of watchlists
+    pub watchlists_count: usize,
+
+    /// Watchlists, ordered by name
+    pub watchlists: Vec<WatchlistInfo>,
+}
+
+/// Parameters for delete_watchlist
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteWatchlistParams {
+    /// Name of the watchlist to delete
+    pub name: String,
+}
+
+/// Result from delete_watchlist
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeleteWatchlistResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Name that was requested
+    pub name: String,
+
+    /// False if no watchlist with this name existed
+    pub deleted: bool,
+}
+
+/// Parameters for check_watchlists
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CheckWatchlistsParams {
+    /// Only check the watchlist with this name (default: all)
+    pub name: Option<String>,
+}
+
+/// New postings of one watchlist
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WatchlistCheckResult {
+    /// Watchlist name
+    pub name: String,
+
+    /// Total number of results of the search
+    pub total_results: Option<u64>,
+
+    /// Number of new postings
+    pub new_jobs_count: usize,
+
+    /// Postings that did not appear in earlier checks
+    pub new_jobs: Vec<WatchlistJob>,
+
+    /// Error message if the search failed
+    pub error: Option<String>,
+}
+
+/// Result from check_watchlists
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CheckWatchlistsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// When the check ran (RFC 3339)
+    pub checked_at: String,
+
+    /// Number of watchlists checked
+    pub watchlists_checked: usize,
+
+    /// Total number of new postings across all watchlists
+    pub total_new_jobs: usize,
+
+    /// Results per watchlist
+    pub results: Vec<WatchlistCheckResult>,
+
+    /// True if the client cancelled the request; `results` then holds the
+    /// watchlists checked before cancellation
+    pub cancelled: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(reference_number: &str) -> JobSummary {
+        JobSummary {
+            reference_number: reference_number.to_string(),
+            title: "Job".to_string(),
+            employer: "Employer".to_string(),
+            location: "Berlin".to_string(),
+            distance_km: None,
+            coordinates: None,
+            country: None,
+            published_date: None,
+            published_date_raw: None,
+            published_days_ago: None,
+            published_ago: None,
+            external_url: None,
+            web_url: String::new(),
+            duplicates: Vec::new(),
+        }
+    }
+
+    fn search() -> SearchJobsParams {
+        serde_json::from_value(serde_json::json!({ "job_title": "Rust" })).unwrap()
+    }
+
+    #[test]
+    fn test_baseline_is_not_reported_as_new() {
+        let mut watchlist = Watchlist::new(
+            " Rust Berlin ",
+            search(),
+            &[job("A"), job("B")],
+            "2025-10-20T08:00:00Z",
+        );
+        assert_eq!(watchlist.name, "Rust Berlin");
+        assert!(watchlist.last_checked_at.is_some());
+
+        let new_jobs = watchlist.absorb(vec![job("A"), job("C")], "2025-10-21T08:00:00Z");
+        assert_eq!(new_jobs.len(), 1);
+        assert_eq!(new_jobs[0].job.reference_number, "C");
+        assert_eq!(new_jobs[0].first_seen_at, "2025-10-21T08:00:00Z");
+        assert_eq!(watchlist.known_jobs["A"], "2025-10-20T08:00:00Z");
+
+        // Reported only once
+        assert!(watchlist
+            .absorb(vec![job("C")], "2025-10-22T08:00:00Z")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_prune_keeps_most_recent_jobs() {
+        let mut watchlist = Watchlist::new("w", search(), &[], "2025-01-01T00:00:00Z");
+        for i in 0..MAX_KNOWN_JOBS {
+            watchlist
+                .known_jobs
+                .insert(format!("OLD-{}", i), "2025-01-01T00:00:00Z".to_string());
+        }
+
+        watchlist.absorb(vec![job("NEW")], "2025-02-01T00:00:00Z");
+        assert_eq!(watchlist.known_jobs.len(), MAX_KNOWN_JOBS);
+        assert!(watchlist.known_jobs.contains_key("NEW"));
+    }
+
+    #[test]
+    fn test_info_counts_known_jobs() {
+        let watchlist = Watchlist::new("w", search(), &[job("A")], "2025-01-01T00:00:00Z");
+        let info = WatchlistInfo::from(watchlist);
+        assert_eq!(info.known_jobs_count, 1);
+        assert!(serde_json::to_value(&info)
+            .unwrap()
+            .get("known_jobs")
+            .is_none());
+    }
+}
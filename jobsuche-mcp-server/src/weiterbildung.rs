@@ -0,0 +1,355 @@
+//! Continuing education from the Weiterbildungssuche
+//!
+//! The Weiterbildungssuche of the Bundesagentur für Arbeit lists courses of
+//! training providers with dates, location and format. Courses approved under
+//! AZAV can be funded with a Bildungsgutschein, which is what job seekers
+//! usually ask about after learning which skill they lack. Responses are read
+//! with JSON pointers so that fields the API leaves out do not fail a search.
+
+use crate::dates::{normalize_date, parse_date};
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+use time::Date;
+
+/// Default base URL of the Weiterbildungssuche API
+pub const DEFAULT_WEITERBILDUNG_URL: &str =
+    "https://rest.arbeitsagentur.de/infosysbub/wbsuche/pc/v2";
+
+/// Public API key of the Weiterbildungssuche web application
+const API_KEY: &str = "infosysbub-wbsuche";
+
+/// Course page on arbeitsagentur.de
+const COURSE_URL: &str = "https://web.arbeitsagentur.de/weiterbildungssuche/angebot";
+
+/// Default search radius in km around the location
+pub const DEFAULT_TRAINING_RADIUS_KM: u64 = 25;
+
+/// Default number of courses per page
+pub const DEFAULT_TRAININGS: u64 = 20;
+
+/// Maximum number of courses per page
+pub const MAX_TRAININGS: u64 = 100;
+
+/// Phrases marking a course as fundable with a Bildungsgutschein, lowercase
+const FUNDING_PHRASES: &[&str] = &["azav", "bildungsgutschein"];
+
+/// Parameters for search_trainings
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SearchTrainingsParams {
+    /// Skill or topic (e.g. "SAP", "Staplerschein", "Python")
+    pub keyword: String,
+
+    /// City or postal code; without it courses from all of Germany are found
+    pub location: Option<String>,
+
+    /// Search radius in km around the location (default: 25)
+    pub radius_km: Option<u64>,
+
+    /// Only courses starting on or after this date (YYYY-MM-DD)
+    pub start_from: Option<String>,
+
+    /// Only courses that can be funded with a Bildungsgutschein (AZAV
+    /// approval; default: false)
+    pub fundable_only: Option<bool>,
+
+    /// Courses per page (default: 20, max: 100)
+    pub page_size: Option<u64>,
+
+    /// Page number, starting at 1
+    pub page: Option<u64>,
+}
+
+/// A course date
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Training {
+    /// Course title
+    pub title: Option<String>,
+
+    /// Training provider
+    pub provider: Option<String>,
+
+    /// Place of the course ("Berlin (10115)")
+    pub location: Option<String>,
+
+    /// First day (ISO 8601, YYYY-MM-DD)
+    pub start_date: Option<String>,
+
+    /// Last day (ISO 8601, YYYY-MM-DD)
+    pub end_date: Option<String>,
+
+    /// Format such as "Vollzeit", "Teilzeit" or "Virtuelles Klassenzimmer"
+    pub format: Option<String>,
+
+    /// Qualification awarded at the end
+    pub certificate: Option<String>,
+
+    /// Course fee in EUR
+    pub cost_eur: Option<f64>,
+
+    /// Whether the course is AZAV-approved and can be funded with a
+    /// Bildungsgutschein
+    pub fundable: bool,
+
+    /// Course page on arbeitsagentur.de
+    pub web_url: Option<String>,
+}
+
+/// Result from search_trainings
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchTrainingsResult {
+    /// ID of this tool call, logged with every line the call wrote
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_id: String,
+
+    /// Total number of course dates found
+    pub total_results: Option<u64>,
+
+    /// Current page number
+    pub current_page: u64,
+
+    /// Number of courses returned
+    pub trainings_count: usize,
+
+    /// Courses of this page (after the start date and funding filters)
+    pub trainings: Vec<Training>,
+}
+
+/// String at a JSON pointer, `None` if missing or empty
+pub(crate) fn text(entry: &Value, pointer: &str) -> Option<String> {
+    entry
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+/// First non-empty string among several JSON pointers
+pub(crate) fn first_text(entry: &Value, pointers: &[&str]) -> Option<String> {
+    pointers.iter().find_map(|pointer| text(entry, pointer))
+}
+
+/// Date at a JSON pointer, as YYYY-MM-DD
+pub(crate) fn date(entry: &Value, pointer: &str) -> Option<String> {
+    normalize_date(entry.pointer(pointer).and_then(Value::as_str)).0
+}
+
+/// Place of an address object at a JSON pointer ("Berlin (10115)")
+pub(crate) fn place(entry: &Value, address: &str) -> Option<String> {
+    let city = text(entry, &format!("{}/ort", address));
+    match (city, text(entry, &format!("{}/plz", address))) {
+        (Some(city), Some(plz)) => Some(format!("{} ({})", city, plz)),
+        (city, _) => city,
+    }
+}
+
+/// Convert one course date of a Weiterbildungssuche response
+fn parse_training(entry: &Value) -> Training {
+    let location = place(entry, "/adresse/adresse");
+    let fundable = {
+        let all_text = entry.to_string().to_lowercase();
+        FUNDING_PHRASES
+            .iter()
+            .any(|phrase| all_text.contains(phrase))
+    };
+
+    Training {
+        title: text(entry, "/angebot/titel"),
+        provider: text(entry, "/angebot/bildungsanbieter/name"),
+        location,
+        start_date: date(entry, "/beginn"),
+        end_date: date(entry, "/ende"),
+        format: text(entry, "/unterrichtsform/bezeichnung"),
+        certificate: text(entry, "/angebot/abschlussbezeichnung"),
+        cost_eur: entry
+            .pointer("/kosten")
+            .and_then(Value::as_f64)
+            .filter(|cost| *cost > 0.0),
+        fundable,
+        web_url: entry
+            .pointer("/angebot/id")
+            .filter(|id| !id.is_null())
+            .map(|id| format!("{}/{}", COURSE_URL, id.to_string().trim_matches('"'))),
+    }
+}
+
+/// Extract the course dates and the total count from a response
+fn parse_trainings(response: &Value) -> (Vec<Training>, Option<u64>) {
+    let trainings = response
+        .pointer("/_embedded/termine")
+        .and_then(Value::as_array)
+        .map(|entries| entries.iter().map(parse_training).collect())
+        .unwrap_or_default();
+    let total = response
+        .pointer("/page/totalElements")
+        .and_then(Value::as_u64);
+    (trainings, total)
+}
+
+/// Whether a course passes the start date and funding filters; courses
+/// without a start date are kept
+pub fn matches_filters(training: &Training, start_from: Option<Date>, fundable_only: bool) -> bool {
+    let starts_in_time = match (start_from, training.start_date.as_deref()) {
+        (Some(from), Some(start)) => parse_date(start).is_none_or(|start| start >= from),
+        _ => true,
+    };
+    starts_in_time && (!fundable_only || training.fundable)
+}
+
+/// Client for the Weiterbildungssuche API
+#[derive(Debug, Clone)]
+pub struct WeiterbildungClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl WeiterbildungClient {
+    /// Create a client for the given base URL
+    pub fn new(base_url: &str) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .context("Failed to create Weiterbildungssuche client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Search course dates
+    pub async fn search(&self, params: &SearchTrainingsParams) -> Result<SearchTrainingsResult> {
+        if params.keyword.trim().is_empty() {
+            anyhow::bail!("keyword must not be empty");
+        }
+        let start_from = match params.start_from.as_deref() {
+            Some(from) => Some(parse_date(from).with_context(|| {
+                format!(
+                    "Invalid start_from '{}': expected a date such as 2026-03-01",
+                    from
+                )
+            })?),
+            None => None,
+        };
+        let fundable_only = params.fundable_only.unwrap_or(false);
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params
+            .page_size
+            .unwrap_or(DEFAULT_TRAININGS)
+            .clamp(1, MAX_TRAININGS);
+
+        let mut query = vec![
+            ("sw", params.keyword.trim().to_string()),
+            // The API counts pages from 0
+            ("page", (page - 1).to_string()),
+            ("size", page_size.to_string()),
+        ];
+        if let Some(ref location) = params.location {
+            query.push(("orte", location.trim().to_string()));
+            let radius_km = params.radius_km.unwrap_or(DEFAULT_TRAINING_RADIUS_KM);
+            query.push(("uk", radius_km.to_string()));
+        } else {
+            query.push(("uk", "Bundesweit".to_string()));
+        }
+
+        let url = format!("{}/bildungsangebot", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .header("X-API-Key", API_KEY)
+            .query(&query)
+            .send()
+            .await
+            .context("Weiterbildungssuche request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("Weiterbildungssuche responded with HTTP {}", status);
+        }
+        let body: Value = response
+            .json()
+            .await
+            .context("Invalid Weiterbildungssuche response")?;
+
+        let (trainings, total_results) = parse_trainings(&body);
+        let trainings: Vec<Training> = trainings
+            .into_iter()
+            .filter(|training| matches_filters(training, start_from, fundable_only))
+            .collect();
+        Ok(SearchTrainingsResult {
+            request_id: String::new(),
+            total_results,
+            current_page: page,
+            trainings_count: trainings.len(),
+            trainings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn response() -> Value {
+        json!({
+            "_embedded": {"termine": [
+                {
+                    "beginn": "2026-03-02T00:00:00",
+                    "ende": "2026-06-26",
+                    "kosten": 4800.0,
+                    "unterrichtsform": {"bezeichnung": "Vollzeit"},
+                    "adresse": {"adresse": {"ort": "Leipzig", "plz": "04109"}},
+                    "angebot": {
+                        "id": 123456,
+                        "titel": "SAP S/4HANA Finanzbuchhaltung",
+                        "abschlussbezeichnung": "Trägerinternes Zertifikat",
+                        "bildungsanbieter": {"name": "Akademie Mitte GmbH"},
+                        "foerderung": "Zugelassen nach AZAV"
+                    }
+                },
+                {"beginn": "2025-01-10", "angebot": {"titel": "SAP Grundlagen"}}
+            ]},
+            "page": {"size": 20, "totalElements": 57, "number": 0}
+        })
+    }
+
+    #[test]
+    fn test_parse_trainings() {
+        let (trainings, total) = parse_trainings(&response());
+        assert_eq!(total, Some(57));
+        assert_eq!(trainings.len(), 2);
+
+        let training = &trainings[0];
+        assert_eq!(
+            training.title.as_deref(),
+            Some("SAP S/4HANA Finanzbuchhaltung")
+        );
+        assert_eq!(training.provider.as_deref(), Some("Akademie Mitte GmbH"));
+        assert_eq!(training.location.as_deref(), Some("Leipzig (04109)"));
+        assert_eq!(training.start_date.as_deref(), Some("2026-03-02"));
+        assert_eq!(training.cost_eur, Some(4800.0));
+        assert!(training.fundable);
+        assert_eq!(
+            training.web_url.as_deref(),
+            Some("https://web.arbeitsagentur.de/weiterbildungssuche/angebot/123456")
+        );
+
+        assert!(!trainings[1].fundable);
+        assert_eq!(trainings[1].web_url, None);
+        assert_eq!(parse_trainings(&json!({})), (Vec::new(), None));
+    }
+
+    #[test]
+    fn test_matches_filters() {
+        let (trainings, _) = parse_trainings(&response());
+        let from = parse_date("2026-01-01");
+        assert!(matches_filters(&trainings[0], from, true));
+        assert!(!matches_filters(&trainings[1], from, false));
+        assert!(matches_filters(&trainings[1], None, false));
+        assert!(!matches_filters(&trainings[1], None, true));
+    }
+}